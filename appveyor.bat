@@ -44,17 +44,48 @@ CL
 rustc --version
 cargo --version
 
+REM the interop features need much newer compilers, see the markers in Cargo.toml.
+SET OLD_RUSTC=
+IF [%RUST%]==[1.22.1] SET OLD_RUSTC=1
+IF [%RUST%]==[1.43.1] SET OLD_RUSTC=1
+IF DEFINED OLD_RUSTC (
+    powershell -Command "(Get-Content Cargo.toml -Raw) -replace '(?s)# BEGIN newer compilers.*?# END newer compilers\r?\n', '' | Set-Content Cargo.toml"
+    IF !ERRORLEVEL! NEQ 0 EXIT 1
+    cargo update -p serde --precise 1.0.102
+    IF !ERRORLEVEL! NEQ 0 EXIT 1
+    cargo update -p serde_derive --precise 1.0.98
+    IF !ERRORLEVEL! NEQ 0 EXIT 1
+    cargo update -p serde_test --precise 1.0.102
+    IF !ERRORLEVEL! NEQ 0 EXIT 1
+)
+
 cargo build --all --no-default-features
 IF %ERRORLEVEL% NEQ 0 EXIT 1
 
 cargo test --all --no-default-features
 IF %ERRORLEVEL% NEQ 0 EXIT 1
 
-cargo build --all --all-features
+cargo test --all
+IF %ERRORLEVEL% NEQ 0 EXIT 1
+
+cargo test --all --features builtin-interop
+IF %ERRORLEVEL% NEQ 0 EXIT 1
+
+cargo test --all --features "capi overflow-hooks native-backend"
+IF %ERRORLEVEL% NEQ 0 EXIT 1
+
+IF DEFINED OLD_RUSTC EXIT 0
+
+FOR %%F IN (primitive-types bson borsh rkyv postgres "rusqlite rusqlite/bundled" sqlx serde_json bitvec rayon zeroize subtle num-format const-traits cpu-dispatch) DO (
+    cargo test --all --features %%F
+    IF !ERRORLEVEL! NEQ 0 EXIT 1
+)
+
+cargo build --all --all-features --features rusqlite/bundled
 IF %ERRORLEVEL% NEQ 0 EXIT 1
 
-cargo test --all --all-features
+cargo test --all --all-features --features rusqlite/bundled
 IF %ERRORLEVEL% NEQ 0 EXIT 1
 
-cargo bench --all-features
+cargo bench --all-features --features rusqlite/bundled
 IF %ERRORLEVEL% NEQ 0 EXIT 1
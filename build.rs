@@ -9,9 +9,31 @@ pub fn main() {
         Channel::Dev | Channel::Nightly => "unstable",
         Channel::Beta | Channel::Stable => "stable",
     };
+    println!("cargo:rustc-check-cfg=cfg(extprim_channel, values(\"stable\", \"unstable\"))");
+    println!("cargo:rustc-check-cfg=cfg(extprim_has_stable_i128)");
+    println!("cargo:rustc-check-cfg=cfg(extprim_has_target_feature)");
+    println!("cargo:rustc-check-cfg=cfg(extprim_has_const_fn)");
+    println!("cargo:rustc-check-cfg=cfg(extprim_has_const_generics)");
+    println!("cargo:rustc-check-cfg=cfg(extprim_has_isqrt)");
+
     println!("cargo:rustc-cfg=extprim_channel=\"{}\"", channel);
     if version.semver >= Version::new(1, 26, 0) {
         println!("cargo:rustc-cfg=extprim_has_stable_i128");
     }
+    // `#[target_feature]`, `is_x86_feature_detected!` and the BMI2/ADX intrinsics of `std::arch`.
+    if version.semver >= Version::new(1, 33, 0) {
+        println!("cargo:rustc-cfg=extprim_has_target_feature");
+    }
+    // `const fn` with loops, branches and panics.
+    if version.semver >= Version::new(1, 57, 0) {
+        println!("cargo:rustc-cfg=extprim_has_const_fn");
+        // const generics, with `assert!` usable to validate the parameters.
+        println!("cargo:rustc-cfg=extprim_has_const_generics");
+    }
+    // `isqrt` on the built-in integers, the newest of the methods the `std_compat` tests compare
+    // against.
+    if version.semver >= Version::new(1, 84, 0) {
+        println!("cargo:rustc-cfg=extprim_has_isqrt");
+    }
 }
 
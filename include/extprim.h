@@ -0,0 +1,197 @@
+#ifndef EXTPRIM_H
+#define EXTPRIM_H
+
+/* Generated with cbindgen from the `capi` module of extprim. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// The string was parsed successfully.
+#define EXTPRIM_OK 0
+
+// The string is empty.
+#define EXTPRIM_ERR_EMPTY 1
+
+// The string contains a character which is not a digit, or is not valid UTF-8.
+#define EXTPRIM_ERR_INVALID_DIGIT 2
+
+// The number is too large for the type.
+#define EXTPRIM_ERR_OVERFLOW 3
+
+// The number is too small for the type.
+#define EXTPRIM_ERR_UNDERFLOW 4
+
+// The radix is not between 2 and 36.
+#define EXTPRIM_ERR_INVALID_RADIX 5
+
+// An unsigned 128-bit number.
+typedef struct ExtprimU128 {
+  // The lower 64 bits of the number.
+  uint64_t lo;
+  // The upper 64 bits of the number.
+  uint64_t hi;
+} ExtprimU128;
+
+// A signed 128-bit number in two's complement.
+typedef struct ExtprimI128 {
+  // The lower 64 bits of the number.
+  uint64_t lo;
+  // The upper 64 bits of the number, which carry the sign.
+  int64_t hi;
+} ExtprimI128;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Computes `a + b`, wrapping around at 2<sup>128</sup>.
+ExtprimU128 extprim_u128_add(ExtprimU128 a, ExtprimU128 b);
+
+// Computes `a - b`, wrapping around at 0.
+ExtprimU128 extprim_u128_sub(ExtprimU128 a, ExtprimU128 b);
+
+// Computes `a * b`, wrapping around at 2<sup>128</sup>.
+ExtprimU128 extprim_u128_mul(ExtprimU128 a, ExtprimU128 b);
+
+// Computes `a << (shift % 128)`.
+ExtprimU128 extprim_u128_shl(ExtprimU128 a, uint32_t shift);
+
+// Computes `a >> (shift % 128)`.
+ExtprimU128 extprim_u128_shr(ExtprimU128 a, uint32_t shift);
+
+// Compares `a` and `b`, returning -1, 0 or 1 if `a` is less than, equal to or greater than `b`.
+int extprim_u128_cmp(ExtprimU128 a, ExtprimU128 b);
+
+// Computes `a + b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+bool extprim_u128_checked_add(ExtprimU128 a, ExtprimU128 b, ExtprimU128 *out);
+
+// Computes `a - b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+bool extprim_u128_checked_sub(ExtprimU128 a, ExtprimU128 b, ExtprimU128 *out);
+
+// Computes `a * b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+bool extprim_u128_checked_mul(ExtprimU128 a, ExtprimU128 b, ExtprimU128 *out);
+
+// Computes the quotient `a / b` and remainder `a % b`. Returns `false`, leaving the outputs
+// untouched, if `b` is 0.
+//
+// # Safety
+//
+// `quotient` and `remainder` must each be null or valid for writes.
+bool extprim_u128_divmod(ExtprimU128 a,
+                         ExtprimU128 b,
+                         ExtprimU128 *quotient,
+                         ExtprimU128 *remainder);
+
+// Parses the NUL-terminated string `s` in the given radix into `out`. The digits may be
+// preceded by `+`. Returns `EXTPRIM_OK` on success, and one of the `EXTPRIM_ERR_*` codes
+// otherwise, leaving `out` untouched.
+//
+// # Safety
+//
+// `s` must point to a NUL-terminated string, and `out` must be null or valid for writes.
+int extprim_u128_from_string(const char *s, uint32_t radix, ExtprimU128 *out);
+
+// Formats `a` in the given radix, using lowercase letters for digits above 9.
+//
+// Like `snprintf`, returns the length of the string excluding the terminating NUL, and only
+// writes the NUL-terminated string to `buf` if `len` is greater than that length. A buffer of
+// 129 bytes is always enough. Returns 0 if the radix is not between 2 and 36.
+//
+// # Safety
+//
+// `buf` must be null or valid for writes of `len` bytes.
+size_t extprim_u128_to_string(ExtprimU128 a, uint32_t radix, char *buf, size_t len);
+
+// Computes `a + b`, wrapping around at the boundary of the type.
+ExtprimI128 extprim_i128_add(ExtprimI128 a, ExtprimI128 b);
+
+// Computes `a - b`, wrapping around at the boundary of the type.
+ExtprimI128 extprim_i128_sub(ExtprimI128 a, ExtprimI128 b);
+
+// Computes `a * b`, wrapping around at the boundary of the type.
+ExtprimI128 extprim_i128_mul(ExtprimI128 a, ExtprimI128 b);
+
+// Computes `-a`, wrapping around at the boundary of the type.
+ExtprimI128 extprim_i128_neg(ExtprimI128 a);
+
+// Computes `a << (shift % 128)`.
+ExtprimI128 extprim_i128_shl(ExtprimI128 a, uint32_t shift);
+
+// Computes the arithmetic shift `a >> (shift % 128)`.
+ExtprimI128 extprim_i128_shr(ExtprimI128 a, uint32_t shift);
+
+// Compares `a` and `b`, returning -1, 0 or 1 if `a` is less than, equal to or greater than `b`.
+int extprim_i128_cmp(ExtprimI128 a, ExtprimI128 b);
+
+// Computes `a + b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+bool extprim_i128_checked_add(ExtprimI128 a, ExtprimI128 b, ExtprimI128 *out);
+
+// Computes `a - b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+bool extprim_i128_checked_sub(ExtprimI128 a, ExtprimI128 b, ExtprimI128 *out);
+
+// Computes `a * b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+//
+// # Safety
+//
+// `out` must be null or valid for writes.
+bool extprim_i128_checked_mul(ExtprimI128 a, ExtprimI128 b, ExtprimI128 *out);
+
+// Computes the quotient `a / b`, rounded towards zero, and the remainder `a % b`, which has the
+// sign of `a`. Returns `false`, leaving the outputs untouched, if `b` is 0 or the division
+// overflows (when computing `MIN / -1`).
+//
+// # Safety
+//
+// `quotient` and `remainder` must each be null or valid for writes.
+bool extprim_i128_divmod(ExtprimI128 a,
+                         ExtprimI128 b,
+                         ExtprimI128 *quotient,
+                         ExtprimI128 *remainder);
+
+// Parses the NUL-terminated string `s` in the given radix into `out`. The digits may be
+// preceded by `+` or `-`. Returns `EXTPRIM_OK` on success, and one of the `EXTPRIM_ERR_*` codes
+// otherwise, leaving `out` untouched.
+//
+// # Safety
+//
+// `s` must point to a NUL-terminated string, and `out` must be null or valid for writes.
+int extprim_i128_from_string(const char *s, uint32_t radix, ExtprimI128 *out);
+
+// Formats `a` in the given radix, with a leading `-` if it is negative, using lowercase letters
+// for digits above 9.
+//
+// Like `snprintf`, returns the length of the string excluding the terminating NUL, and only
+// writes the NUL-terminated string to `buf` if `len` is greater than that length. A buffer of
+// 130 bytes is always enough. Returns 0 if the radix is not between 2 and 36.
+//
+// # Safety
+//
+// `buf` must be null or valid for writes of `len` bytes.
+size_t extprim_i128_to_string(ExtprimI128 a, uint32_t radix, char *buf, size_t len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* EXTPRIM_H */
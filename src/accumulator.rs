@@ -0,0 +1,233 @@
+//! Overflow-free summation of many 128-bit numbers.
+//!
+//! Summing `u128` values directly either panics (debug builds) or silently wraps (release builds)
+//! once the total exceeds 2<sup>128</sup>. The [`Accumulator`](struct.Accumulator.html) keeps a
+//! 256-bit running sum instead, which cannot overflow for any realistic number of samples.
+//!
+//! ```rust
+//! use extprim::u128::u128;
+//! use extprim::accumulator::Accumulator;
+//!
+//! let mut acc = Accumulator::new();
+//! acc.add(u128::max_value());
+//! acc.add(u128::new(2));
+//! assert_eq!(acc.checked_total(), None);
+//! assert_eq!(acc.total_parts(), (u128::one(), u128::one()));
+//! ```
+//!
+//! The functions [`sum_checked`](fn.sum_checked.html), [`sum_wrapping`](fn.sum_wrapping.html) and
+//! [`mean`](fn.mean.html) provide the common statistics over a slice directly.
+
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign};
+
+use u128::u128;
+use u256::u256;
+
+/// A running sum of `u128` values, with 256 bits of precision.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Accumulator {
+    sum: u256,
+}
+
+impl Accumulator {
+    /// Creates a new accumulator with a total of 0.
+    pub fn new() -> Accumulator {
+        Accumulator::default()
+    }
+
+    /// Adds a value to the running sum.
+    ///
+    /// The sum would only wrap around after adding 2<sup>128</sup> values.
+    pub fn add(&mut self, value: u128) {
+        self.sum = self.sum.wrapping_add(u256::new(value));
+    }
+
+    /// Returns the total as a `u128`, or `None` if it does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::accumulator::Accumulator;
+    ///
+    /// let acc: Accumulator = (1..101).map(u128::new).collect();
+    /// assert_eq!(acc.checked_total(), Some(u128::new(5050)));
+    /// ```
+    pub fn checked_total(&self) -> Option<u128> {
+        if self.sum.high128() == u128::zero() {
+            Some(self.sum.low128())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the total as the `(high, low)` 128-bit halves of a 256-bit number.
+    pub fn total_parts(&self) -> (u128, u128) {
+        (self.sum.high128(), self.sum.low128())
+    }
+
+    /// Returns the total as a 256-bit number.
+    pub fn total(&self) -> u256 {
+        self.sum
+    }
+}
+
+impl AddAssign<u128> for Accumulator {
+    fn add_assign(&mut self, value: u128) {
+        self.add(value);
+    }
+}
+
+/// Combines two running sums, e.g. of different parts of a slice.
+impl Add for Accumulator {
+    type Output = Accumulator;
+
+    fn add(self, other: Accumulator) -> Accumulator {
+        Accumulator { sum: self.sum.wrapping_add(other.sum) }
+    }
+}
+
+impl Extend<u128> for Accumulator {
+    fn extend<I: IntoIterator<Item=u128>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<'a> Extend<&'a u128> for Accumulator {
+    fn extend<I: IntoIterator<Item=&'a u128>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl FromIterator<u128> for Accumulator {
+    fn from_iter<I: IntoIterator<Item=u128>>(iter: I) -> Accumulator {
+        let mut acc = Accumulator::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+impl<'a> FromIterator<&'a u128> for Accumulator {
+    fn from_iter<I: IntoIterator<Item=&'a u128>>(iter: I) -> Accumulator {
+        iter.into_iter().cloned().collect()
+    }
+}
+
+/// Computes the sum of all values in the slice, or `None` if it does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::accumulator::sum_checked;
+///
+/// assert_eq!(sum_checked(&[u128::new(1), u128::new(2), u128::new(3)]), Some(u128::new(6)));
+/// assert_eq!(sum_checked(&[u128::max_value(), u128::one()]), None);
+/// assert_eq!(sum_checked(&[]), Some(u128::zero()));
+/// ```
+pub fn sum_checked(values: &[u128]) -> Option<u128> {
+    values.iter().collect::<Accumulator>().checked_total()
+}
+
+/// Computes the sum of all values in the slice, wrapping around at the boundary of the type.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::accumulator::sum_wrapping;
+///
+/// assert_eq!(sum_wrapping(&[u128::max_value(), u128::new(5)]), u128::new(4));
+/// ```
+pub fn sum_wrapping(values: &[u128]) -> u128 {
+    values.iter().fold(u128::zero(), |sum, &value| sum.wrapping_add(value))
+}
+
+/// Computes the arithmetic mean of all values in the slice, rounded down. Returns `None` if the
+/// slice is empty.
+///
+/// The mean is exact even when the sum of the values exceeds `u128::max_value()`.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::accumulator::mean;
+///
+/// let max = u128::max_value();
+/// assert_eq!(mean(&[max, max - u128::new(2)]), Some(max - u128::one()));
+/// assert_eq!(mean(&[u128::new(1), u128::new(2)]), Some(u128::new(1)));
+/// assert_eq!(mean(&[]), None);
+/// ```
+pub fn mean(values: &[u128]) -> Option<u128> {
+    if values.is_empty() {
+        return None;
+    }
+    let total = values.iter().collect::<Accumulator>().total();
+    Some((total / u256::new(u128::new(values.len() as u64))).low128())
+}
+
+#[cfg(test)]
+mod accumulator_tests {
+    use u128::u128;
+    use u256::u256;
+    use accumulator::{Accumulator, sum_checked, sum_wrapping, mean};
+
+    #[test]
+    fn test_add() {
+        let mut acc = Accumulator::new();
+        assert_eq!(acc.checked_total(), Some(u128::zero()));
+        acc.add(u128::from_parts(1, 2));
+        acc += u128::new(3);
+        assert_eq!(acc.checked_total(), Some(u128::from_parts(1, 5)));
+        assert_eq!(acc.total_parts(), (u128::zero(), u128::from_parts(1, 5)));
+    }
+
+    #[test]
+    fn test_no_overflow() {
+        let max = u128::max_value();
+        let acc: Accumulator = [max; 1000].iter().collect();
+        assert_eq!(acc.checked_total(), None);
+        assert_eq!(acc.total_parts(), (u128::new(999), u128::from_parts(!0, !0 - 999)));
+        assert_eq!(acc.total(), u256::new(max) * u256::new_u64(1000));
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut acc = Accumulator::new();
+        acc.extend(vec![u128::new(1), u128::new(2)]);
+        acc.extend(&[u128::new(3)]);
+        assert_eq!(acc.checked_total(), Some(u128::new(6)));
+    }
+
+    #[test]
+    fn test_combine() {
+        let max = u128::max_value();
+        let a: Accumulator = [max, max].iter().collect();
+        let b: Accumulator = [max, u128::new(3)].iter().collect();
+        assert_eq!((a + b).total_parts(), (u128::new(3), u128::zero()));
+        assert_eq!((a + Accumulator::new()), a);
+    }
+
+    #[test]
+    fn test_slice_sums() {
+        let max = u128::max_value();
+        let values = [max, max, u128::new(3)];
+        assert_eq!(sum_checked(&values), None);
+        assert_eq!(sum_checked(&values[2..]), Some(u128::new(3)));
+        assert_eq!(sum_checked(&[max, u128::one(), max - u128::one()][1..]), Some(max));
+        assert_eq!(sum_wrapping(&values), u128::new(1));
+        assert_eq!(sum_wrapping(&[]), u128::zero());
+    }
+
+    #[test]
+    fn test_mean() {
+        let max = u128::max_value();
+        assert_eq!(mean(&[max; 7]), Some(max));
+        assert_eq!(mean(&[max, u128::zero()]), Some(max >> 1));
+        assert_eq!(mean(&[u128::new(10), u128::new(20), u128::new(35)]), Some(u128::new(21)));
+    }
+}
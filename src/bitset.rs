@@ -0,0 +1,384 @@
+//! A set of small integers stored in the bits of a `u128`.
+//!
+//! [`BitSet128`](struct.BitSet128.html) holds any subset of `0..128`, where bit `i` of the
+//! underlying number is set when `i` is a member. All operations are a few instructions on the two
+//! 64-bit words, and the set is `Copy`.
+//!
+//! ```rust
+//! use extprim::bitset::BitSet128;
+//!
+//! let mut a = BitSet128::new();
+//! a.insert(3);
+//! a.insert(100);
+//! let b: BitSet128 = [3, 5, 7].iter().cloned().collect();
+//!
+//! assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![3, 5, 7, 100]);
+//! assert_eq!((a & b).len(), 1);
+//! assert!((a - b).contains(100));
+//! ```
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::*;
+
+use u128::{u128, ZERO, ONE, MAX};
+
+/// A set of integers in the range `0..128`, stored as the bits of a `u128`.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet128(u128);
+
+/// Returns the bit representing `index`.
+fn bit(index: u32) -> u128 {
+    assert!(index < 128, "BitSet128 index out of range: {}", index);
+    ONE << index
+}
+
+impl BitSet128 {
+    /// Creates an empty set.
+    pub fn new() -> BitSet128 {
+        BitSet128(ZERO)
+    }
+
+    /// Creates the set of all integers in `0..128`.
+    pub fn full() -> BitSet128 {
+        BitSet128(MAX)
+    }
+
+    /// Creates a set from its bits, where bit `i` is set when `i` is a member.
+    pub fn from_bits(bits: u128) -> BitSet128 {
+        BitSet128(bits)
+    }
+
+    /// Returns the bits of the set, where bit `i` is set when `i` is a member.
+    pub fn bits(self) -> u128 {
+        self.0
+    }
+
+    /// Returns the number of members.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Checks whether the set has no members.
+    pub fn is_empty(self) -> bool {
+        self.0 == ZERO
+    }
+
+    /// Removes all members.
+    pub fn clear(&mut self) {
+        self.0 = ZERO;
+    }
+
+    /// Checks whether `index` is a member. Integers outside `0..128` are never members.
+    pub fn contains(self, index: u32) -> bool {
+        index < 128 && self.0 & bit(index) != ZERO
+    }
+
+    /// Adds `index` to the set. Returns whether it was not already a member.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `index` is not less than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::bitset::BitSet128;
+    ///
+    /// let mut set = BitSet128::new();
+    /// assert!(set.insert(42));
+    /// assert!(!set.insert(42));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn insert(&mut self, index: u32) -> bool {
+        let bit = bit(index);
+        let is_new = self.0 & bit == ZERO;
+        self.0 |= bit;
+        is_new
+    }
+
+    /// Removes `index` from the set. Returns whether it was a member.
+    pub fn remove(&mut self, index: u32) -> bool {
+        if !self.contains(index) {
+            return false;
+        }
+        self.0 &= !bit(index);
+        true
+    }
+
+    /// Returns the set of integers which are members of `self` or `other`.
+    pub fn union(self, other: BitSet128) -> BitSet128 {
+        BitSet128(self.0 | other.0)
+    }
+
+    /// Returns the set of integers which are members of both `self` and `other`.
+    pub fn intersection(self, other: BitSet128) -> BitSet128 {
+        BitSet128(self.0 & other.0)
+    }
+
+    /// Returns the set of integers which are members of `self` but not `other`.
+    pub fn difference(self, other: BitSet128) -> BitSet128 {
+        BitSet128(self.0 & !other.0)
+    }
+
+    /// Returns the set of integers which are members of exactly one of `self` and `other`.
+    pub fn symmetric_difference(self, other: BitSet128) -> BitSet128 {
+        BitSet128(self.0 ^ other.0)
+    }
+
+    /// Returns the set of integers in `0..128` which are not members of `self`.
+    pub fn complement(self) -> BitSet128 {
+        BitSet128(!self.0)
+    }
+
+    /// Checks whether every member of `self` is a member of `other`.
+    pub fn is_subset(self, other: BitSet128) -> bool {
+        self.0 & !other.0 == ZERO
+    }
+
+    /// Checks whether every member of `other` is a member of `self`.
+    pub fn is_superset(self, other: BitSet128) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Checks whether `self` and `other` have no members in common.
+    pub fn is_disjoint(self, other: BitSet128) -> bool {
+        self.0 & other.0 == ZERO
+    }
+
+    /// Returns the smallest member, or `None` if the set is empty.
+    pub fn first(self) -> Option<u32> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.0.trailing_zeros())
+        }
+    }
+
+    /// Returns the largest member, or `None` if the set is empty.
+    pub fn last(self) -> Option<u32> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(127 - self.0.leading_zeros())
+        }
+    }
+
+    /// Returns an iterator over the members in increasing order.
+    pub fn iter(self) -> Iter {
+        Iter { remaining: self.0 }
+    }
+}
+
+/// An iterator over the members of a [`BitSet128`](struct.BitSet128.html) in increasing order.
+#[derive(Clone, Debug)]
+pub struct Iter {
+    remaining: u128,
+}
+
+impl Iterator for Iter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.remaining == ZERO {
+            return None;
+        }
+        let index = self.remaining.trailing_zeros();
+        // Clears the lowest set bit.
+        self.remaining &= self.remaining - ONE;
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.remaining == ZERO {
+            return None;
+        }
+        let index = 127 - self.remaining.leading_zeros();
+        self.remaining &= !(ONE << index);
+        Some(index)
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl IntoIterator for BitSet128 {
+    type Item = u32;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &BitSet128 {
+    type Item = u32;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<u32> for BitSet128 {
+    fn from_iter<I: IntoIterator<Item=u32>>(iter: I) -> BitSet128 {
+        let mut set = BitSet128::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<u32> for BitSet128 {
+    fn extend<I: IntoIterator<Item=u32>>(&mut self, iter: I) {
+        for index in iter {
+            self.insert(index);
+        }
+    }
+}
+
+impl fmt::Debug for BitSet128 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_set().entries(self.iter()).finish()
+    }
+}
+
+macro_rules! impl_set_op {
+    ($tn:ident($name:ident, $method:ident), $atn:ident($aname:ident)) => {
+        impl $tn for BitSet128 {
+            type Output = BitSet128;
+            fn $name(self, other: BitSet128) -> BitSet128 {
+                self.$method(other)
+            }
+        }
+
+        impl $atn for BitSet128 {
+            fn $aname(&mut self, other: BitSet128) {
+                *self = self.$method(other);
+            }
+        }
+    }
+}
+
+impl_set_op!(BitOr(bitor, union), BitOrAssign(bitor_assign));
+impl_set_op!(BitAnd(bitand, intersection), BitAndAssign(bitand_assign));
+impl_set_op!(BitXor(bitxor, symmetric_difference), BitXorAssign(bitxor_assign));
+impl_set_op!(Sub(sub, difference), SubAssign(sub_assign));
+
+impl Not for BitSet128 {
+    type Output = BitSet128;
+    fn not(self) -> BitSet128 {
+        self.complement()
+    }
+}
+
+impl From<u128> for BitSet128 {
+    fn from(bits: u128) -> BitSet128 {
+        BitSet128(bits)
+    }
+}
+
+impl From<BitSet128> for u128 {
+    fn from(set: BitSet128) -> u128 {
+        set.0
+    }
+}
+
+#[cfg(test)]
+mod bitset_tests {
+    use bitset::BitSet128;
+    use u128::{u128, MAX};
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = BitSet128::new();
+        assert!(set.is_empty());
+        assert!(set.insert(0));
+        assert!(set.insert(64));
+        assert!(set.insert(127));
+        assert!(!set.insert(64));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.bits(), u128::from_parts(0x8000_0000_0000_0001, 1));
+        assert!(set.contains(127));
+        assert!(!set.contains(1));
+        assert!(!set.contains(128));
+        assert!(!set.contains(!0));
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert!(!set.remove(1000));
+        assert_eq!(set.len(), 2);
+        set.clear();
+        assert_eq!(set, BitSet128::new());
+    }
+
+    #[test]
+    #[should_panic(expected="BitSet128 index out of range: 128")]
+    fn test_insert_out_of_range() {
+        BitSet128::new().insert(128);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: BitSet128 = vec![1, 2, 3, 70].into_iter().collect();
+        let b: BitSet128 = vec![3, 4, 70, 127].into_iter().collect();
+        assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 70, 127]);
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![3, 70]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), vec![1, 2, 4, 127]);
+        assert_eq!((!a).len(), 124);
+        assert_eq!(a.union(b), a | b);
+        assert_eq!(a.intersection(b), a & b);
+        assert_eq!(a.difference(b), a - b);
+        assert_eq!(a.symmetric_difference(b), a ^ b);
+        assert_eq!(a.complement(), !a);
+
+        let mut c = a;
+        c |= b;
+        c -= a;
+        assert_eq!(c, b - a);
+        c &= a;
+        assert!(c.is_empty());
+        c ^= a;
+        assert_eq!(c, a);
+
+        assert!((a & b).is_subset(a));
+        assert!(a.is_superset(a & b));
+        assert!(!a.is_subset(b));
+        assert!((a - b).is_disjoint(b));
+        assert!(!a.is_disjoint(b));
+        assert!(BitSet128::new().is_subset(a));
+        assert!(BitSet128::full().is_superset(a));
+    }
+
+    #[test]
+    fn test_iter() {
+        let set = BitSet128::from_bits(u128::from_parts(0x8000_0000_0000_0002, 0x11));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 4, 65, 127]);
+        assert_eq!(set.iter().rev().collect::<Vec<_>>(), vec![127, 65, 4, 0]);
+        assert_eq!(set.iter().len(), 4);
+        let mut iter = set.iter();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(127));
+        assert_eq!(iter.len(), 2);
+        assert_eq!((&set).into_iter().sum::<u32>(), 196);
+        assert_eq!(set.first(), Some(0));
+        assert_eq!(set.last(), Some(127));
+        assert_eq!(BitSet128::new().first(), None);
+        assert_eq!(BitSet128::new().last(), None);
+        assert_eq!(BitSet128::full().iter().count(), 128);
+        assert_eq!(BitSet128::full().into_iter().collect::<BitSet128>(), BitSet128::full());
+        assert_eq!(u128::from(BitSet128::from(MAX)), MAX);
+    }
+
+    #[test]
+    fn test_debug() {
+        let set: BitSet128 = vec![5, 1, 100].into_iter().collect();
+        assert_eq!(format!("{:?}", set), "{1, 5, 100}");
+        assert_eq!(format!("{:?}", BitSet128::new()), "{}");
+    }
+}
@@ -0,0 +1,66 @@
+//! Conversions between `u128` and the bit arrays of the `bitvec` crate.
+//!
+//! A `u128` converts to and from a `BitArray<[u64; 2], Lsb0>`, in which bit `i` of the number is
+//! bit `i` of the array. The conversion only moves the two 64-bit words (see
+//! [`u128::to_le_words`](../u128/struct.u128.html#method.to_le_words)), and the array derefs to a
+//! `BitSlice<u64, Lsb0>` for parsing bitfields.
+//!
+//! `u128` cannot be used as the storage of a `BitSlice` itself. The `BitStore` and `BitRegister`
+//! traits of `bitvec` are not sealed, but `BitRegister` requires `funty::Unsigned`, i.e. the whole
+//! API of a primitive integer, including conversions from and to every other primitive type.
+//! `BitStore` further needs an access type implementing `radium::Radium`, which `radium` only
+//! implements for the atomics and the `Cell`s of primitive integers, and which the orphan rule
+//! does not allow this crate to implement for `Cell<u128>`. `bitvec` also documents that the
+//! storage is always one of the unsigned integer primitives, so an implementation outside of it
+//! would not be supported.
+
+use bitvec::array::BitArray;
+use bitvec::order::Lsb0;
+
+use u128::u128;
+
+impl From<u128> for BitArray<[u64; 2], Lsb0> {
+    fn from(value: u128) -> BitArray<[u64; 2], Lsb0> {
+        BitArray::new(value.to_le_words())
+    }
+}
+
+impl From<BitArray<[u64; 2], Lsb0>> for u128 {
+    fn from(bits: BitArray<[u64; 2], Lsb0>) -> u128 {
+        u128::from_le_words(bits.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod bitvec_tests {
+    use bitvec::array::BitArray;
+    use bitvec::field::BitField;
+    use bitvec::order::Lsb0;
+
+    use u128::u128;
+
+    #[test]
+    fn test_bits() {
+        let value = u128::from_parts(0x8000_0000_0000_0001, 0x0000_0000_0000_0006);
+        let bits = BitArray::<[u64; 2], Lsb0>::from(value);
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), vec![1, 2, 64, 127]);
+        for i in 0..128 {
+            assert_eq!(bits[i], (value >> i) & u128::one() == u128::one());
+        }
+        assert_eq!(u128::from(bits), value);
+    }
+
+    #[test]
+    fn test_bitfields() {
+        // a header with a 4-bit version, a 12-bit length and a 64-bit id straddling the two words.
+        let mut bits = BitArray::<[u64; 2], Lsb0>::ZERO;
+        bits[0..4].store::<u8>(0xa);
+        bits[4..16].store::<u16>(0x123);
+        bits[40..104].store::<u64>(0xfedc_ba98_7654_3210);
+        let value = u128::from(bits);
+        assert_eq!(value, u128::from_parts(0xfe_dcba_9876, 0x5432_1000_0000_123a));
+        let bits = BitArray::<[u64; 2], Lsb0>::from(value);
+        assert_eq!(bits[40..104].load::<u64>(), 0xfedc_ba98_7654_3210);
+        assert_eq!(bits[4..16].load::<u16>(), 0x123);
+    }
+}
@@ -0,0 +1,71 @@
+//! Borsh serialization of the 128-bit integers with the `borsh` crate.
+//!
+//! Like the built-in `u128` and `i128`, the integers are encoded as 16 bytes in little endian (two's
+//! complement for `i128`), so a struct can switch between the built-in types and these without
+//! changing its encoding.
+
+use borsh::{BorshSerialize, BorshDeserialize};
+use borsh::io::{Read, Write, Result};
+
+use u128::u128;
+use i128::i128;
+
+macro_rules! impl_borsh {
+    ($($ty:ident)*) => {$(
+        impl BorshSerialize for $ty {
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
+        }
+
+        impl BorshDeserialize for $ty {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<$ty> {
+                let mut bytes = [0; 16];
+                reader.read_exact(&mut bytes)?;
+                Ok($ty::from_le_bytes(bytes))
+            }
+        }
+    )*}
+}
+
+impl_borsh!(u128 i128);
+
+#[cfg(test)]
+mod borsh_tests {
+    use borsh::{to_vec, from_slice};
+
+    use u128::u128;
+    use i128::i128;
+
+    #[test]
+    fn test_u128() {
+        let value = u128::from_parts(0x100f0e0d0c0b0a09, 0x0807060504030201);
+        let bytes = to_vec(&value).unwrap();
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        assert_eq!(from_slice::<u128>(&bytes).unwrap(), value);
+        assert_eq!(to_vec(&u128::max_value()).unwrap(), to_vec(&!0u128).unwrap());
+    }
+
+    #[test]
+    fn test_i128() {
+        let value = i128::new(-2);
+        let bytes = to_vec(&value).unwrap();
+        assert_eq!(bytes, to_vec(&-2i128).unwrap());
+        assert_eq!(from_slice::<i128>(&bytes).unwrap(), value);
+        assert_eq!(from_slice::<i128>(&to_vec(&i128::min_value()).unwrap()).unwrap(), i128::min_value());
+    }
+
+    #[test]
+    fn test_in_struct() {
+        let values = (u128::new(7), vec![i128::new(-1), i128::max_value()]);
+        let bytes = to_vec(&values).unwrap();
+        assert_eq!(bytes.len(), 16 + 4 + 2 * 16);
+        assert_eq!(from_slice::<(u128, Vec<i128>)>(&bytes).unwrap(), values);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(from_slice::<u128>(&[0; 15]).is_err());
+        assert!(from_slice::<i128>(&[0; 17]).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! Conversions between the 128-bit integers of this crate and BSON's `Decimal128`.
+//!
+//! `Decimal128` is the IEEE 754-2008 128-bit decimal floating point type in the binary integer
+//! decimal encoding, as stored by MongoDB. It has a coefficient of up to 34 decimal digits and a
+//! decimal exponent. Converting from a `Decimal128` succeeds if the value is a finite integer which
+//! fits in the target type. Converting to a `Decimal128` succeeds if the integer has at most 34
+//! significant digits, i.e. trailing zeros beyond that are moved to the exponent.
+
+use std::convert::TryFrom;
+
+use bson::Decimal128;
+
+use error::Decimal128Error;
+use u128::{u128, ZERO, div_rem};
+use i128::i128;
+
+/// The largest coefficient, `10^34 - 1`.
+const MAX_COEFFICIENT: u128 = u128 { lo: 0x378d8e63ffffffff, hi: 0x1ed09bead87c0 };
+
+/// The exponent is stored with this bias added.
+const EXPONENT_BIAS: i32 = 6176;
+
+/// Splits a `Decimal128` into its sign, and the absolute value as an integer.
+fn decode(value: Decimal128) -> Result<(bool, u128), Decimal128Error> {
+    let bits = u128::from_le_bytes(value.bytes());
+    let is_negative = bits.hi >> 63 != 0;
+
+    let (mut coefficient, mut exponent) = if (bits.hi >> 61) & 3 == 3 {
+        if (bits.hi >> 59) & 3 == 3 {
+            return Err(Decimal128Error::NotFinite);
+        }
+        // The coefficient has the implicit prefix 0b100 and so exceeds MAX_COEFFICIENT. Such
+        // non-canonical values are zero.
+        (ZERO, 0)
+    } else {
+        let coefficient = u128::from_parts(bits.hi & ((1 << 49) - 1), bits.lo);
+        let exponent = ((bits.hi >> 49) & 0x3fff) as i32 - EXPONENT_BIAS;
+        if coefficient > MAX_COEFFICIENT {
+            (ZERO, 0)
+        } else {
+            (coefficient, exponent)
+        }
+    };
+
+    if coefficient == ZERO {
+        return Ok((is_negative, coefficient));
+    }
+    while exponent < 0 {
+        let (q, r) = div_rem(coefficient, u128::new(10));
+        if r != ZERO {
+            return Err(Decimal128Error::NotIntegral);
+        }
+        coefficient = q;
+        exponent += 1;
+    }
+    while exponent > 0 {
+        coefficient = coefficient.checked_mul_64(10).ok_or(Decimal128Error::Overflow)?;
+        exponent -= 1;
+    }
+    Ok((is_negative, coefficient))
+}
+
+/// Creates a `Decimal128` from its sign and absolute value.
+fn encode(is_negative: bool, mut abs: u128) -> Result<Decimal128, Decimal128Error> {
+    let mut exponent = 0;
+    while abs > MAX_COEFFICIENT {
+        let (q, r) = div_rem(abs, u128::new(10));
+        if r != ZERO {
+            return Err(Decimal128Error::Inexact);
+        }
+        abs = q;
+        exponent += 1;
+    }
+    let mut bits = abs | (u128::new((exponent + EXPONENT_BIAS) as u64) << 113u32);
+    if is_negative {
+        bits.hi |= 1 << 63;
+    }
+    Ok(Decimal128::from_bytes(bits.to_le_bytes()))
+}
+
+impl TryFrom<Decimal128> for u128 {
+    type Error = Decimal128Error;
+
+    fn try_from(value: Decimal128) -> Result<Self, Decimal128Error> {
+        match decode(value)? {
+            (true, abs) if abs != ZERO => Err(Decimal128Error::Overflow),
+            (_, abs) => Ok(abs),
+        }
+    }
+}
+
+impl TryFrom<Decimal128> for i128 {
+    type Error = Decimal128Error;
+
+    fn try_from(value: Decimal128) -> Result<Self, Decimal128Error> {
+        let (is_negative, abs) = decode(value)?;
+        let limit = u128::from_parts(1 << 63, 0);
+        if abs > limit || (abs == limit && !is_negative) {
+            Err(Decimal128Error::Overflow)
+        } else if is_negative {
+            Ok(i128(abs.wrapping_neg()))
+        } else {
+            Ok(i128(abs))
+        }
+    }
+}
+
+impl TryFrom<u128> for Decimal128 {
+    type Error = Decimal128Error;
+
+    fn try_from(value: u128) -> Result<Self, Decimal128Error> {
+        encode(false, value)
+    }
+}
+
+impl TryFrom<i128> for Decimal128 {
+    type Error = Decimal128Error;
+
+    fn try_from(value: i128) -> Result<Self, Decimal128Error> {
+        encode(value.is_negative(), value.unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+mod bson_tests {
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    use bson::{Bson, Decimal128, Document};
+
+    use error::Decimal128Error;
+    use u128::{u128, ZERO, MAX};
+    use i128::{i128, MIN};
+
+    fn dec(s: &str) -> Decimal128 {
+        Decimal128::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_from_decimal128() {
+        assert_eq!(u128::try_from(dec("0")), Ok(ZERO));
+        assert_eq!(u128::try_from(dec("-0")), Ok(ZERO));
+        assert_eq!(u128::try_from(dec("1234567890123456789012345678901234")),
+                   Ok(u128::from_str("1234567890123456789012345678901234").unwrap()));
+        assert_eq!(u128::try_from(dec("1.5E+3")), Ok(u128::new(1500)));
+        assert_eq!(u128::try_from(dec("1000E-3")), Ok(u128::new(1)));
+        assert_eq!(u128::try_from(dec("3.402823669209384634633746074317682E+38")),
+                   Ok(u128::from_str("340282366920938463463374607431768200000").unwrap()));
+        assert_eq!(u128::try_from(dec("3.402823669209384634633746074317683E+38")), Err(Decimal128Error::Overflow));
+        assert_eq!(u128::try_from(dec("1E+6111")), Err(Decimal128Error::Overflow));
+        assert_eq!(u128::try_from(dec("0E+6111")), Ok(ZERO));
+        assert_eq!(u128::try_from(dec("-1")), Err(Decimal128Error::Overflow));
+        assert_eq!(u128::try_from(dec("1.5")), Err(Decimal128Error::NotIntegral));
+        assert_eq!(u128::try_from(dec("1E-6176")), Err(Decimal128Error::NotIntegral));
+        assert_eq!(u128::try_from(dec("Infinity")), Err(Decimal128Error::NotFinite));
+        assert_eq!(u128::try_from(dec("NaN")), Err(Decimal128Error::NotFinite));
+
+        assert_eq!(i128::try_from(dec("-1.5E+3")), Ok(i128::new(-1500)));
+        assert_eq!(i128::try_from(dec("-1.701411834604692317316873037158841E+38")),
+                   Ok(i128::from_str("-170141183460469231731687303715884100000").unwrap()));
+        assert_eq!(i128::try_from(dec("1.701411834604692317316873037158842E+38")), Err(Decimal128Error::Overflow));
+        assert_eq!(i128::try_from(dec("-1.701411834604692317316873037158842E+38")), Err(Decimal128Error::Overflow));
+        assert_eq!(i128::try_from(dec("-Infinity")), Err(Decimal128Error::NotFinite));
+    }
+
+    #[test]
+    fn test_non_canonical_is_zero() {
+        let mut bytes = [0xff; 16];
+        bytes[15] = 0x6f;
+        assert_eq!(u128::try_from(Decimal128::from_bytes(bytes)), Ok(ZERO));
+        let mut bytes = [0xff; 16];
+        bytes[14] = 0x41;
+        bytes[15] = 0x30;
+        assert_eq!(u128::try_from(Decimal128::from_bytes(bytes)), Ok(ZERO));
+    }
+
+    #[test]
+    fn test_to_decimal128() {
+        assert_eq!(Decimal128::try_from(ZERO).unwrap(), dec("0"));
+        assert_eq!(Decimal128::try_from(u128::new(1500)).unwrap(), dec("1500"));
+        assert_eq!(Decimal128::try_from(i128::new(-42)).unwrap(), dec("-42"));
+        assert_eq!(Decimal128::try_from(u128::from_str("1000000000000000000000000000000000000").unwrap()).unwrap(),
+                   dec("1.000000000000000000000000000000000E+36"));
+        assert_eq!(Decimal128::try_from(MAX), Err(Decimal128Error::Inexact));
+        assert_eq!(Decimal128::try_from(MIN), Err(Decimal128Error::Inexact));
+
+        for s in &["0", "1", "-17", "9999999999999999999999999999999999", "1.234E+37", "-5E+20"] {
+            let value = i128::try_from(dec(s)).unwrap();
+            let decimal = Decimal128::try_from(value).unwrap();
+            assert_eq!(i128::try_from(decimal), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_document_round_trip() {
+        let counter = u128::from_str("1234567890123456789012345678901234").unwrap();
+        let mut document = Document::new();
+        document.insert("counter", Decimal128::try_from(counter).unwrap());
+        let mut buffer = Vec::new();
+        document.to_writer(&mut buffer).unwrap();
+
+        let document = Document::from_reader(&buffer[..]).unwrap();
+        match *document.get("counter").unwrap() {
+            Bson::Decimal128(decimal) => assert_eq!(u128::try_from(decimal), Ok(counter)),
+            ref other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,568 @@
+//! C API for the 128-bit integer routines.
+//!
+//! This module is only available with the `capi` feature. It exports `extern "C"` functions named
+//! `extprim_u128_*` and `extprim_i128_*`, which pass the numbers by value as
+//! [`ExtprimU128`](struct.ExtprimU128.html) and [`ExtprimI128`](struct.ExtprimI128.html), two
+//! structures of two 64-bit words with a layout independent of the target. The declarations for C
+//! and C++ are in `include/extprim.h`, which can be regenerated with `cbindgen`.
+//!
+//! To link the functions into a C project, build the crate as a static or dynamic library:
+//!
+//! ```text
+//! cargo rustc --release --features capi --crate-type staticlib
+//! ```
+//!
+//! None of the functions panic. Operations which may fail, such as division or parsing, report
+//! the failure in their return value instead.
+
+use std::cmp::Ordering;
+use std::ffi::CStr;
+use std::num::ParseIntError;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use error;
+use u128::u128;
+use i128::{i128, checked_div_rem};
+
+/// An unsigned 128-bit number.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExtprimU128 {
+    /// The lower 64 bits of the number.
+    pub lo: u64,
+    /// The upper 64 bits of the number.
+    pub hi: u64,
+}
+
+/// A signed 128-bit number in two's complement.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExtprimI128 {
+    /// The lower 64 bits of the number.
+    pub lo: u64,
+    /// The upper 64 bits of the number, which carry the sign.
+    pub hi: i64,
+}
+
+impl From<u128> for ExtprimU128 {
+    fn from(x: u128) -> ExtprimU128 {
+        ExtprimU128 { lo: x.low64(), hi: x.high64() }
+    }
+}
+
+impl From<ExtprimU128> for u128 {
+    fn from(x: ExtprimU128) -> u128 {
+        u128::from_parts(x.hi, x.lo)
+    }
+}
+
+impl From<i128> for ExtprimI128 {
+    fn from(x: i128) -> ExtprimI128 {
+        ExtprimI128 { lo: x.low64(), hi: x.high64() }
+    }
+}
+
+impl From<ExtprimI128> for i128 {
+    fn from(x: ExtprimI128) -> i128 {
+        i128::from_parts(x.hi, x.lo)
+    }
+}
+
+/// The string was parsed successfully.
+pub const EXTPRIM_OK: c_int = 0;
+/// The string is empty.
+pub const EXTPRIM_ERR_EMPTY: c_int = 1;
+/// The string contains a character which is not a digit, or is not valid UTF-8.
+pub const EXTPRIM_ERR_INVALID_DIGIT: c_int = 2;
+/// The number is too large for the type.
+pub const EXTPRIM_ERR_OVERFLOW: c_int = 3;
+/// The number is too small for the type.
+pub const EXTPRIM_ERR_UNDERFLOW: c_int = 4;
+/// The radix is not between 2 and 36.
+pub const EXTPRIM_ERR_INVALID_RADIX: c_int = 5;
+
+fn error_code(e: &ParseIntError) -> c_int {
+    if *e == error::empty() {
+        EXTPRIM_ERR_EMPTY
+    } else if *e == error::overflow() {
+        EXTPRIM_ERR_OVERFLOW
+    } else if *e == error::underflow() {
+        EXTPRIM_ERR_UNDERFLOW
+    } else {
+        EXTPRIM_ERR_INVALID_DIGIT
+    }
+}
+
+fn ordering_to_int(ordering: Ordering) -> c_int {
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// Writes `value` to the pointer if it is not null.
+unsafe fn write_opt<T>(out: *mut T, value: T) {
+    if !out.is_null() {
+        ptr::write(out, value);
+    }
+}
+
+/// Parses a NUL-terminated string with `parse`, writing the result to `out` on success.
+unsafe fn parse_c_str<T, U, F>(s: *const c_char, radix: u32, out: *mut U, parse: F) -> c_int
+where
+    F: FnOnce(&str, u32) -> Result<T, ParseIntError>,
+    U: From<T>,
+{
+    if radix < 2 || radix > 36 {
+        return EXTPRIM_ERR_INVALID_RADIX;
+    }
+    let s = match CStr::from_ptr(s).to_str() {
+        Ok(s) => s,
+        Err(_) => return EXTPRIM_ERR_INVALID_DIGIT,
+    };
+    match parse(s, radix) {
+        Ok(value) => {
+            write_opt(out, U::from(value));
+            EXTPRIM_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Formats the magnitude `value` in the given radix, with an optional minus sign, into `buf` as
+/// a NUL-terminated string. Returns the length of the full string excluding the NUL.
+unsafe fn format_c_str(mut value: u128, negative: bool, radix: u32, buf: *mut c_char, len: usize) -> usize {
+    if radix < 2 || radix > 36 {
+        return 0;
+    }
+    let mut digits = [0u8; 129];
+    let mut start = digits.len();
+    loop {
+        let digit = value.rem_u64(radix as u64) as u32;
+        value /= u128::new(radix as u64);
+        start -= 1;
+        digits[start] = ::std::char::from_digit(digit, radix).unwrap_or('0') as u8;
+        if value == u128::zero() {
+            break;
+        }
+    }
+    if negative {
+        start -= 1;
+        digits[start] = b'-';
+    }
+
+    let string = &digits[start..];
+    if !buf.is_null() && len > string.len() {
+        ptr::copy_nonoverlapping(string.as_ptr() as *const c_char, buf, string.len());
+        ptr::write(buf.add(string.len()), 0);
+    }
+    string.len()
+}
+
+//{{{ u128
+
+/// Computes `a + b`, wrapping around at 2<sup>128</sup>.
+#[no_mangle]
+pub extern "C" fn extprim_u128_add(a: ExtprimU128, b: ExtprimU128) -> ExtprimU128 {
+    u128::from(a).wrapping_add(u128::from(b)).into()
+}
+
+/// Computes `a - b`, wrapping around at 0.
+#[no_mangle]
+pub extern "C" fn extprim_u128_sub(a: ExtprimU128, b: ExtprimU128) -> ExtprimU128 {
+    u128::from(a).wrapping_sub(u128::from(b)).into()
+}
+
+/// Computes `a * b`, wrapping around at 2<sup>128</sup>.
+#[no_mangle]
+pub extern "C" fn extprim_u128_mul(a: ExtprimU128, b: ExtprimU128) -> ExtprimU128 {
+    u128::from(a).wrapping_mul(u128::from(b)).into()
+}
+
+/// Computes `a << (shift % 128)`.
+#[no_mangle]
+pub extern "C" fn extprim_u128_shl(a: ExtprimU128, shift: u32) -> ExtprimU128 {
+    u128::from(a).wrapping_shl(shift).into()
+}
+
+/// Computes `a >> (shift % 128)`.
+#[no_mangle]
+pub extern "C" fn extprim_u128_shr(a: ExtprimU128, shift: u32) -> ExtprimU128 {
+    u128::from(a).wrapping_shr(shift).into()
+}
+
+/// Compares `a` and `b`, returning -1, 0 or 1 if `a` is less than, equal to or greater than `b`.
+#[no_mangle]
+pub extern "C" fn extprim_u128_cmp(a: ExtprimU128, b: ExtprimU128) -> c_int {
+    ordering_to_int(u128::from(a).cmp(&u128::from(b)))
+}
+
+/// Computes `a + b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_u128_checked_add(a: ExtprimU128, b: ExtprimU128, out: *mut ExtprimU128) -> bool {
+    match u128::from(a).checked_add(u128::from(b)) {
+        Some(v) => { write_opt(out, v.into()); true }
+        None => false,
+    }
+}
+
+/// Computes `a - b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_u128_checked_sub(a: ExtprimU128, b: ExtprimU128, out: *mut ExtprimU128) -> bool {
+    match u128::from(a).checked_sub(u128::from(b)) {
+        Some(v) => { write_opt(out, v.into()); true }
+        None => false,
+    }
+}
+
+/// Computes `a * b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_u128_checked_mul(a: ExtprimU128, b: ExtprimU128, out: *mut ExtprimU128) -> bool {
+    match u128::from(a).checked_mul(u128::from(b)) {
+        Some(v) => { write_opt(out, v.into()); true }
+        None => false,
+    }
+}
+
+/// Computes the quotient `a / b` and remainder `a % b`. Returns `false`, leaving the outputs
+/// untouched, if `b` is 0.
+///
+/// # Safety
+///
+/// `quotient` and `remainder` must each be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_u128_divmod(
+    a: ExtprimU128,
+    b: ExtprimU128,
+    quotient: *mut ExtprimU128,
+    remainder: *mut ExtprimU128,
+) -> bool {
+    let b = u128::from(b);
+    if b == u128::zero() {
+        return false;
+    }
+    let (q, r) = ::u128::div_rem(u128::from(a), b);
+    write_opt(quotient, q.into());
+    write_opt(remainder, r.into());
+    true
+}
+
+/// Parses the NUL-terminated string `s` in the given radix into `out`. The digits may be
+/// preceded by `+`. Returns `EXTPRIM_OK` on success, and one of the `EXTPRIM_ERR_*` codes
+/// otherwise, leaving `out` untouched.
+///
+/// # Safety
+///
+/// `s` must point to a NUL-terminated string, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_u128_from_string(s: *const c_char, radix: u32, out: *mut ExtprimU128) -> c_int {
+    parse_c_str(s, radix, out, u128::from_str_radix)
+}
+
+/// Formats `a` in the given radix, using lowercase letters for digits above 9.
+///
+/// Like `snprintf`, returns the length of the string excluding the terminating NUL, and only
+/// writes the NUL-terminated string to `buf` if `len` is greater than that length. A buffer of
+/// 129 bytes is always enough. Returns 0 if the radix is not between 2 and 36.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_u128_to_string(a: ExtprimU128, radix: u32, buf: *mut c_char, len: usize) -> usize {
+    format_c_str(u128::from(a), false, radix, buf, len)
+}
+
+//}}}
+
+//{{{ i128
+
+/// Computes `a + b`, wrapping around at the boundary of the type.
+#[no_mangle]
+pub extern "C" fn extprim_i128_add(a: ExtprimI128, b: ExtprimI128) -> ExtprimI128 {
+    i128::from(a).wrapping_add(i128::from(b)).into()
+}
+
+/// Computes `a - b`, wrapping around at the boundary of the type.
+#[no_mangle]
+pub extern "C" fn extprim_i128_sub(a: ExtprimI128, b: ExtprimI128) -> ExtprimI128 {
+    i128::from(a).wrapping_sub(i128::from(b)).into()
+}
+
+/// Computes `a * b`, wrapping around at the boundary of the type.
+#[no_mangle]
+pub extern "C" fn extprim_i128_mul(a: ExtprimI128, b: ExtprimI128) -> ExtprimI128 {
+    i128::from(a).wrapping_mul(i128::from(b)).into()
+}
+
+/// Computes `-a`, wrapping around at the boundary of the type.
+#[no_mangle]
+pub extern "C" fn extprim_i128_neg(a: ExtprimI128) -> ExtprimI128 {
+    i128::from(a).wrapping_neg().into()
+}
+
+/// Computes `a << (shift % 128)`.
+#[no_mangle]
+pub extern "C" fn extprim_i128_shl(a: ExtprimI128, shift: u32) -> ExtprimI128 {
+    i128::from(a).wrapping_shl(shift).into()
+}
+
+/// Computes the arithmetic shift `a >> (shift % 128)`.
+#[no_mangle]
+pub extern "C" fn extprim_i128_shr(a: ExtprimI128, shift: u32) -> ExtprimI128 {
+    i128::from(a).wrapping_shr(shift).into()
+}
+
+/// Compares `a` and `b`, returning -1, 0 or 1 if `a` is less than, equal to or greater than `b`.
+#[no_mangle]
+pub extern "C" fn extprim_i128_cmp(a: ExtprimI128, b: ExtprimI128) -> c_int {
+    ordering_to_int(i128::from(a).cmp(&i128::from(b)))
+}
+
+/// Computes `a + b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_i128_checked_add(a: ExtprimI128, b: ExtprimI128, out: *mut ExtprimI128) -> bool {
+    match i128::from(a).checked_add(i128::from(b)) {
+        Some(v) => { write_opt(out, v.into()); true }
+        None => false,
+    }
+}
+
+/// Computes `a - b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_i128_checked_sub(a: ExtprimI128, b: ExtprimI128, out: *mut ExtprimI128) -> bool {
+    match i128::from(a).checked_sub(i128::from(b)) {
+        Some(v) => { write_opt(out, v.into()); true }
+        None => false,
+    }
+}
+
+/// Computes `a * b` into `out`. Returns `false`, leaving `out` untouched, on overflow.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_i128_checked_mul(a: ExtprimI128, b: ExtprimI128, out: *mut ExtprimI128) -> bool {
+    match i128::from(a).checked_mul(i128::from(b)) {
+        Some(v) => { write_opt(out, v.into()); true }
+        None => false,
+    }
+}
+
+/// Computes the quotient `a / b`, rounded towards zero, and the remainder `a % b`, which has the
+/// sign of `a`. Returns `false`, leaving the outputs untouched, if `b` is 0 or the division
+/// overflows (when computing `MIN / -1`).
+///
+/// # Safety
+///
+/// `quotient` and `remainder` must each be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_i128_divmod(
+    a: ExtprimI128,
+    b: ExtprimI128,
+    quotient: *mut ExtprimI128,
+    remainder: *mut ExtprimI128,
+) -> bool {
+    match checked_div_rem(i128::from(a), i128::from(b)) {
+        Some((q, r)) => {
+            write_opt(quotient, q.into());
+            write_opt(remainder, r.into());
+            true
+        }
+        None => false,
+    }
+}
+
+/// Parses the NUL-terminated string `s` in the given radix into `out`. The digits may be
+/// preceded by `+` or `-`. Returns `EXTPRIM_OK` on success, and one of the `EXTPRIM_ERR_*` codes
+/// otherwise, leaving `out` untouched.
+///
+/// # Safety
+///
+/// `s` must point to a NUL-terminated string, and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_i128_from_string(s: *const c_char, radix: u32, out: *mut ExtprimI128) -> c_int {
+    parse_c_str(s, radix, out, i128::from_str_radix)
+}
+
+/// Formats `a` in the given radix, with a leading `-` if it is negative, using lowercase letters
+/// for digits above 9.
+///
+/// Like `snprintf`, returns the length of the string excluding the terminating NUL, and only
+/// writes the NUL-terminated string to `buf` if `len` is greater than that length. A buffer of
+/// 130 bytes is always enough. Returns 0 if the radix is not between 2 and 36.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn extprim_i128_to_string(a: ExtprimI128, radix: u32, buf: *mut c_char, len: usize) -> usize {
+    let a = i128::from(a);
+    format_c_str(a.unsigned_abs(), a.is_negative(), radix, buf, len)
+}
+
+//}}}
+
+#[cfg(test)]
+mod capi_tests {
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::ptr;
+
+    use u128::u128;
+    use i128::i128;
+    use capi::*;
+
+    fn u(x: u128) -> ExtprimU128 {
+        x.into()
+    }
+
+    fn i(x: i128) -> ExtprimI128 {
+        x.into()
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(::std::mem::size_of::<ExtprimU128>(), 16);
+        assert_eq!(u(u128::from_parts(1, 2)), ExtprimU128 { lo: 2, hi: 1 });
+        assert_eq!(i(i128::new(-2)), ExtprimI128 { lo: !1, hi: -1 });
+    }
+
+    #[test]
+    fn test_u128_arithmetic() {
+        let max = u128::max_value();
+        assert_eq!(extprim_u128_add(u(max), u(u128::new(2))), u(u128::one()));
+        assert_eq!(extprim_u128_sub(u(u128::zero()), u(u128::one())), u(max));
+        assert_eq!(extprim_u128_mul(u(u128::new(1 << 32)), u(u128::new(1 << 32))), u(u128::from_parts(1, 0)));
+        assert_eq!(extprim_u128_shl(u(u128::one()), 129), u(u128::new(2)));
+        assert_eq!(extprim_u128_shr(u(max), 127), u(u128::one()));
+        assert_eq!(extprim_u128_cmp(u(max), u(u128::one())), 1);
+
+        let mut out = ExtprimU128::default();
+        unsafe {
+            assert!(!extprim_u128_checked_add(u(max), u(u128::one()), &mut out));
+            assert_eq!(out, ExtprimU128::default());
+            assert!(extprim_u128_checked_mul(u(u128::new(6)), u(u128::new(7)), &mut out));
+            assert_eq!(out, u(u128::new(42)));
+            assert!(!extprim_u128_checked_sub(u(u128::zero()), u(u128::one()), ptr::null_mut()));
+
+            let (mut q, mut r) = (ExtprimU128::default(), ExtprimU128::default());
+            assert!(extprim_u128_divmod(u(u128::new(100)), u(u128::new(8)), &mut q, &mut r));
+            assert_eq!((q, r), (u(u128::new(12)), u(u128::new(4))));
+            assert!(!extprim_u128_divmod(u(max), u(u128::zero()), &mut q, &mut r));
+            assert!(extprim_u128_divmod(u(max), u(max), ptr::null_mut(), &mut r));
+            assert_eq!(r, u(u128::zero()));
+        }
+    }
+
+    #[test]
+    fn test_i128_arithmetic() {
+        let min = i128::min_value();
+        assert_eq!(extprim_i128_add(i(i128::max_value()), i(i128::one())), i(min));
+        assert_eq!(extprim_i128_sub(i(i128::new(-3)), i(i128::new(4))), i(i128::new(-7)));
+        assert_eq!(extprim_i128_mul(i(i128::new(-3)), i(i128::new(4))), i(i128::new(-12)));
+        assert_eq!(extprim_i128_neg(i(min)), i(min));
+        assert_eq!(extprim_i128_shr(i(min), 127), i(-i128::one()));
+        assert_eq!(extprim_i128_shl(i(-i128::one()), 1), i(i128::new(-2)));
+        assert_eq!(extprim_i128_cmp(i(min), i(i128::zero())), -1);
+
+        let mut out = ExtprimI128::default();
+        unsafe {
+            assert!(!extprim_i128_checked_sub(i(min), i(i128::one()), &mut out));
+            assert!(extprim_i128_checked_add(i(min), i(i128::one()), &mut out));
+            assert_eq!(i128::from(out), min + i128::one());
+            assert!(!extprim_i128_checked_mul(i(min), i(-i128::one()), &mut out));
+
+            let (mut q, mut r) = (ExtprimI128::default(), ExtprimI128::default());
+            assert!(extprim_i128_divmod(i(i128::new(-100)), i(i128::new(8)), &mut q, &mut r));
+            assert_eq!((q, r), (i(i128::new(-12)), i(i128::new(-4))));
+            assert!(!extprim_i128_divmod(i(min), i(-i128::one()), &mut q, &mut r));
+            assert!(!extprim_i128_divmod(i(min), i(i128::zero()), &mut q, &mut r));
+        }
+    }
+
+    fn to_string_u(a: u128, radix: u32) -> String {
+        let mut buf = [0 as c_char; 129];
+        unsafe {
+            let len = extprim_u128_to_string(u(a), radix, buf.as_mut_ptr(), buf.len());
+            let s = CStr::from_ptr(buf.as_ptr()).to_str().unwrap().to_owned();
+            assert_eq!(len, s.len());
+            s
+        }
+    }
+
+    fn to_string_i(a: i128, radix: u32) -> String {
+        let mut buf = [0 as c_char; 130];
+        unsafe {
+            let len = extprim_i128_to_string(i(a), radix, buf.as_mut_ptr(), buf.len());
+            let s = CStr::from_ptr(buf.as_ptr()).to_str().unwrap().to_owned();
+            assert_eq!(len, s.len());
+            s
+        }
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string_u(u128::max_value(), 10), u128::max_value().to_string());
+        assert_eq!(to_string_u(u128::max_value(), 2), "1".repeat(128));
+        assert_eq!(to_string_u(u128::zero(), 36), "0");
+        assert_eq!(to_string_u(u128::new(0xdead_beef), 16), "deadbeef");
+        assert_eq!(to_string_i(i128::min_value(), 10), i128::min_value().to_string());
+        assert_eq!(to_string_i(i128::min_value(), 2).len(), 129);
+        assert_eq!(to_string_i(i128::new(-35), 36), "-z");
+
+        unsafe {
+            let mut buf = [1 as c_char; 4];
+            assert_eq!(extprim_u128_to_string(u(u128::new(12345)), 10, buf.as_mut_ptr(), buf.len()), 5);
+            assert_eq!(buf, [1; 4]);
+            assert_eq!(extprim_u128_to_string(u(u128::new(12345)), 10, ptr::null_mut(), 0), 5);
+            assert_eq!(extprim_u128_to_string(u(u128::new(12345)), 37, buf.as_mut_ptr(), buf.len()), 0);
+        }
+    }
+
+    #[test]
+    fn test_from_string() {
+        let mut out = ExtprimU128::default();
+        let mut out_i = ExtprimI128::default();
+        unsafe {
+            let s = b"340282366920938463463374607431768211455\0";
+            assert_eq!(extprim_u128_from_string(s.as_ptr() as *const c_char, 10, &mut out), EXTPRIM_OK);
+            assert_eq!(out, u(u128::max_value()));
+            let s = b"340282366920938463463374607431768211456\0";
+            assert_eq!(extprim_u128_from_string(s.as_ptr() as *const c_char, 10, &mut out), EXTPRIM_ERR_OVERFLOW);
+            assert_eq!(extprim_u128_from_string(b"\0".as_ptr() as *const c_char, 10, &mut out), EXTPRIM_ERR_EMPTY);
+            assert_eq!(extprim_u128_from_string(b"12x\0".as_ptr() as *const c_char, 10, &mut out), EXTPRIM_ERR_INVALID_DIGIT);
+            assert_eq!(extprim_u128_from_string(b"\xff\0".as_ptr() as *const c_char, 10, &mut out), EXTPRIM_ERR_INVALID_DIGIT);
+            assert_eq!(extprim_u128_from_string(b"1\0".as_ptr() as *const c_char, 1, &mut out), EXTPRIM_ERR_INVALID_RADIX);
+            assert_eq!(out, u(u128::max_value()));
+
+            assert_eq!(extprim_i128_from_string(b"-zz\0".as_ptr() as *const c_char, 36, &mut out_i), EXTPRIM_OK);
+            assert_eq!(out_i, i(i128::new(-1295)));
+            let s = b"-170141183460469231731687303715884105729\0";
+            assert_eq!(extprim_i128_from_string(s.as_ptr() as *const c_char, 10, &mut out_i), EXTPRIM_ERR_UNDERFLOW);
+        }
+    }
+}
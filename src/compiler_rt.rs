@@ -1,12 +1,34 @@
 pub use self::detail::{udiv128, umod128, udivmod128};
 
+use std::ops::{BitAnd, BitOr, Div, Rem};
+
+use u128::{u128, u64_long_mul};
+
 #[cfg(extprim_has_stable_i128)]
 pub mod builtins {
     pub type I128 = i128;
     pub type U128 = u128;
 }
 
-#[cfg(all(target_pointer_width="64", unix))]
+#[cfg(all(feature="native-backend", extprim_has_stable_i128))]
+mod detail {
+    use u128::u128;
+
+    pub fn udiv128(a: u128, b: u128) -> u128 {
+        u128::from_built_in(a.as_built_in() / b.as_built_in())
+    }
+
+    pub fn umod128(a: u128, b: u128) -> u128 {
+        u128::from_built_in(a.as_built_in() % b.as_built_in())
+    }
+
+    pub fn udivmod128(a: u128, b: u128) -> (u128, u128) {
+        let (a, b) = (a.as_built_in(), b.as_built_in());
+        (u128::from_built_in(a / b), u128::from_built_in(a % b))
+    }
+}
+
+#[cfg(all(target_pointer_width="64", unix, not(all(feature="native-backend", extprim_has_stable_i128))))]
 mod detail {
     use u128::u128;
     use std::mem::uninitialized;
@@ -35,13 +57,13 @@ mod detail {
     }
 }
 
-#[cfg(not(all(target_pointer_width="64", unix)))]
+#[cfg(not(any(all(target_pointer_width="64", unix), all(feature="native-backend", extprim_has_stable_i128))))]
 mod detail {
-    use u128::{u128, ZERO};
-    use std::mem::uninitialized;
+    use u128::u128;
+    use super::udivmod_double;
 
     pub fn udiv128(a: u128, b: u128) -> u128 {
-        udivmodti4(a, b, None)
+        udivmod128(a, b).0
     }
 
     pub fn umod128(a: u128, b: u128) -> u128 {
@@ -49,74 +71,587 @@ mod detail {
     }
 
     pub fn udivmod128(a: u128, b: u128) -> (u128, u128) {
-        let mut rem = unsafe { uninitialized() };
-        let div = udivmodti4(a, b, Some(&mut rem));
-        (div, rem)
-    }
-
-    fn udivmodti4(n: u128, d: u128, rem: Option<&mut u128>) -> u128 {
-        // Source is based on
-        // http://llvm.org/klaus/compiler-rt/blob/master/lib/builtins/udivmodti4.c.
-        // compiler-rt is an LLVM project. It is licensed in MIT and UIOSL.
-
-        if n < d {
-            rem.map(|r| { *r = n; });
-            return ZERO;
-        }
-
-        let sr = match (n.hi, n.lo, d.hi, d.lo) {
-            (0, x, 0, y) => {
-                rem.map(|r| {
-                    r.hi = 0;
-                    r.lo = x % y;
-                });
-                return u128::new(x / y);
-            },
-            (x, 0, y, 0) => {
-                rem.map(|r| {
-                    r.hi = x % y;
-                    r.lo = 0;
-                });
-                return u128::new(x / y);
-            },
-            (_, _, dh, 0) if dh.is_power_of_two() => {
-                rem.map(|r| {
-                    r.lo = n.lo;
-                    r.hi = n.hi & (dh - 1);
-                });
-                return u128::new(n.hi >> dh.trailing_zeros());
-            },
-            (_, _, 0, dl) if dl.is_power_of_two() => {
-                rem.map(|r| {
-                    r.lo = n.lo & (dl - 1);
-                    r.hi = 0;
-                });
-                return n >> dl.trailing_zeros();
+        let ((qh, ql), (rh, rl)) = udivmod_double((a.hi, a.lo), (b.hi, b.lo));
+        (u128::from_parts(qh, ql), u128::from_parts(rh, rl))
+    }
+}
+
+//{{{ Generic limb kernels
+
+/// A machine word, used as the digit type of the generic multiplication and division kernels.
+///
+/// A pair of limbs `(hi, lo)` represents a number of twice the width, e.g. the fallback `u128`
+/// division is the `u64` instantiation. The division is only used by that fallback, the wider types
+/// use `udivmod_limbs` instead.
+#[cfg_attr(any(all(target_pointer_width="64", unix), all(feature="native-backend", extprim_has_stable_i128)), allow(dead_code))]
+pub trait Limb: Copy + Ord
+    + BitAnd<Output=Self> + BitOr<Output=Self> + Div<Output=Self> + Rem<Output=Self>
+{
+    /// Number of bits in the limb.
+    const BITS: u32;
+    /// The limb 0.
+    const ZERO: Self;
+    /// The limb 1.
+    const ONE: Self;
+
+    fn leading_zeros(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+    fn wrapping_add(self, other: Self) -> Self;
+    fn wrapping_sub(self, other: Self) -> Self;
+    fn wrapping_mul(self, other: Self) -> Self;
+    fn overflowing_add(self, other: Self) -> (Self, bool);
+    fn overflowing_sub(self, other: Self) -> (Self, bool);
+    /// Shifts left by `shift` bits, where `shift < BITS`.
+    fn shl(self, shift: u32) -> Self;
+    /// Shifts right by `shift` bits, where `shift < BITS`.
+    fn shr(self, shift: u32) -> Self;
+
+    fn is_power_of_two(self) -> bool {
+        self != Self::ZERO && self & self.wrapping_sub(Self::ONE) == Self::ZERO
+    }
+}
+
+macro_rules! impl_limb {
+    ($($ty:ident)*) => {$(
+        impl Limb for $ty {
+            const BITS: u32 = ::std::mem::size_of::<$ty>() as u32 * 8;
+            const ZERO: $ty = 0;
+            const ONE: $ty = 1;
+
+            fn leading_zeros(self) -> u32 { $ty::leading_zeros(self) }
+            fn trailing_zeros(self) -> u32 { $ty::trailing_zeros(self) }
+            fn wrapping_add(self, other: $ty) -> $ty { $ty::wrapping_add(self, other) }
+            fn wrapping_sub(self, other: $ty) -> $ty { $ty::wrapping_sub(self, other) }
+            fn wrapping_mul(self, other: $ty) -> $ty { $ty::wrapping_mul(self, other) }
+            fn overflowing_add(self, other: $ty) -> ($ty, bool) { $ty::overflowing_add(self, other) }
+            fn overflowing_sub(self, other: $ty) -> ($ty, bool) { $ty::overflowing_sub(self, other) }
+            fn shl(self, shift: u32) -> $ty { self << shift }
+            fn shr(self, shift: u32) -> $ty { self >> shift }
+        }
+    )*}
+}
+
+impl_limb!(u32 u64);
+
+// A miniature limb type, so that the double-limb algorithms can be verified exhaustively.
+#[cfg(test)]
+impl_limb!(u8);
+
+impl Limb for u128 {
+    const BITS: u32 = 128;
+    const ZERO: u128 = ::u128::ZERO;
+    const ONE: u128 = ::u128::ONE;
+
+    fn leading_zeros(self) -> u32 { u128::leading_zeros(self) }
+    fn trailing_zeros(self) -> u32 { u128::trailing_zeros(self) }
+    fn wrapping_add(self, other: u128) -> u128 { u128::wrapping_add(self, other) }
+    fn wrapping_sub(self, other: u128) -> u128 { u128::wrapping_sub(self, other) }
+    fn wrapping_mul(self, other: u128) -> u128 { u128::wrapping_mul(self, other) }
+    fn overflowing_add(self, other: u128) -> (u128, bool) { u128::overflowing_add(self, other) }
+    fn overflowing_sub(self, other: u128) -> (u128, bool) { u128::overflowing_sub(self, other) }
+    fn shl(self, shift: u32) -> u128 { self.wrapping_shl(shift) }
+    fn shr(self, shift: u32) -> u128 { self.wrapping_shr(shift) }
+    fn is_power_of_two(self) -> bool { u128::is_power_of_two(self) }
+}
+
+/// Computes the full product of two limbs, returning `(hi, lo)`.
+///
+/// This is the schoolbook multiplication on half-limbs, for targets without a native
+/// double-width multiply.
+pub fn long_mul<L: Limb>(left: L, right: L) -> (L, L) {
+    let half = L::BITS / 2;
+    let mask = L::ONE.shl(half).wrapping_sub(L::ONE);
+    let a = left.shr(half);
+    let b = left & mask;
+    let c = right.shr(half);
+    let d = right & mask;
+
+    let lo = b.wrapping_mul(d);
+    let (mid, carry) = a.wrapping_mul(d).overflowing_add(b.wrapping_mul(c));
+    let hi = a.wrapping_mul(c).wrapping_add(if carry { L::ONE.shl(half) } else { L::ZERO });
+
+    add_double((hi, lo), (mid.shr(half), mid.shl(half))).0
+}
+
+/// Adds two double limbs, returning the wrapped sum and whether a carry occurred.
+fn add_double<L: Limb>((ah, al): (L, L), (bh, bl): (L, L)) -> ((L, L), bool) {
+    let (lo, carry) = al.overflowing_add(bl);
+    let (hi, carry_hi) = ah.overflowing_add(bh);
+    let (hi, carry_lo) = hi.overflowing_add(if carry { L::ONE } else { L::ZERO });
+    ((hi, lo), carry_hi || carry_lo)
+}
+
+/// Subtracts two double limbs, returning the wrapped difference and whether a borrow occurred.
+#[cfg_attr(any(all(target_pointer_width="64", unix), all(feature="native-backend", extprim_has_stable_i128)), allow(dead_code))]
+fn sub_double<L: Limb>((ah, al): (L, L), (bh, bl): (L, L)) -> ((L, L), bool) {
+    let (lo, borrow) = al.overflowing_sub(bl);
+    let (hi, borrow_hi) = ah.overflowing_sub(bh);
+    let (hi, borrow_lo) = hi.overflowing_sub(if borrow { L::ONE } else { L::ZERO });
+    ((hi, lo), borrow_hi || borrow_lo)
+}
+
+#[cfg_attr(any(all(target_pointer_width="64", unix), all(feature="native-backend", extprim_has_stable_i128)), allow(dead_code))]
+fn leading_zeros_double<L: Limb>((hi, lo): (L, L)) -> u32 {
+    if hi == L::ZERO {
+        L::BITS + lo.leading_zeros()
+    } else {
+        hi.leading_zeros()
+    }
+}
+
+/// Shifts the double limb left by `shift` bits, where `shift < 2 * BITS`.
+#[cfg_attr(any(all(target_pointer_width="64", unix), all(feature="native-backend", extprim_has_stable_i128)), allow(dead_code))]
+fn shl_double<L: Limb>((hi, lo): (L, L), shift: u32) -> (L, L) {
+    if shift == 0 {
+        (hi, lo)
+    } else if shift < L::BITS {
+        (hi.shl(shift) | lo.shr(L::BITS - shift), lo.shl(shift))
+    } else {
+        (lo.shl(shift - L::BITS), L::ZERO)
+    }
+}
+
+/// Shifts the double limb right by `shift` bits, where `shift < 2 * BITS`.
+#[cfg_attr(any(all(target_pointer_width="64", unix), all(feature="native-backend", extprim_has_stable_i128)), allow(dead_code))]
+fn shr_double<L: Limb>((hi, lo): (L, L), shift: u32) -> (L, L) {
+    if shift == 0 {
+        (hi, lo)
+    } else if shift < L::BITS {
+        (hi.shr(shift), lo.shr(shift) | hi.shl(L::BITS - shift))
+    } else {
+        (L::ZERO, hi.shr(shift - L::BITS))
+    }
+}
+
+/// Computes the quotient and remainder of two double limbs `(hi, lo)`.
+///
+/// Source is based on
+/// http://llvm.org/klaus/compiler-rt/blob/master/lib/builtins/udivmodti4.c.
+/// compiler-rt is an LLVM project. It is licensed in MIT and UIOSL.
+#[cfg_attr(any(all(target_pointer_width="64", unix), all(feature="native-backend", extprim_has_stable_i128)), allow(dead_code))]
+pub fn udivmod_double<L: Limb>(n: (L, L), d: (L, L)) -> ((L, L), (L, L)) {
+    let zero = L::ZERO;
+    if n < d {
+        return ((zero, zero), n);
+    }
+
+    let sr = match (n, d) {
+        ((nh, nl), (dh, dl)) if nh == zero && dh == zero => {
+            return ((zero, nl / dl), (zero, nl % dl));
+        },
+        ((nh, nl), (dh, dl)) if nl == zero && dl == zero => {
+            return ((zero, nh / dh), (nh % dh, zero));
+        },
+        ((nh, nl), (dh, dl)) if dl == zero && dh.is_power_of_two() => {
+            return ((zero, nh.shr(dh.trailing_zeros())), (nh & dh.wrapping_sub(L::ONE), nl));
+        },
+        ((_, nl), (dh, dl)) if dh == zero && dl.is_power_of_two() => {
+            return (shr_double(n, dl.trailing_zeros()), (zero, nl & dl.wrapping_sub(L::ONE)));
+        },
+        _ => leading_zeros_double(d) - leading_zeros_double(n) + 1,
+    };
+
+    let mut q = shl_double(n, 2 * L::BITS - sr);
+    let mut r = shr_double(n, sr);
+    let mut carry = L::ZERO;
+    for _ in 0 .. sr {
+        let q_top = q.0.shr(L::BITS - 1);
+        r = shl_double(r, 1);
+        r.1 = r.1 | q_top;
+        q = shl_double(q, 1);
+        q.1 = q.1 | carry;
+        carry = L::ZERO;
+        if r >= d {
+            r = sub_double(r, d).0;
+            carry = L::ONE;
+        }
+    }
+    q = shl_double(q, 1);
+    q.1 = q.1 | carry;
+    (q, r)
+}
+
+/// Shifts the little-endian limbs left by `shift` bits, where `shift < 64`, returning the bits
+/// shifted out.
+fn shl_limbs(limbs: &mut [u64], shift: u32) -> u64 {
+    if shift == 0 {
+        return 0;
+    }
+    let mut carry = 0;
+    for limb in limbs.iter_mut() {
+        let next_carry = *limb >> (64 - shift);
+        *limb = *limb << shift | carry;
+        carry = next_carry;
+    }
+    carry
+}
+
+/// Shifts the little-endian limbs right by `shift` bits, where `shift < 64`.
+fn shr_limbs(limbs: &mut [u64], shift: u32) {
+    if shift == 0 {
+        return;
+    }
+    let mut carry = 0;
+    for limb in limbs.iter_mut().rev() {
+        let next_carry = *limb << (64 - shift);
+        *limb = *limb >> shift | carry;
+        carry = next_carry;
+    }
+}
+
+/// Divides the number in the little-endian 64-bit limbs `u` by the one in `v`, writing the
+/// quotient to `q` and leaving the remainder in the lowest `v.len()` limbs of `u`.
+///
+/// This is Algorithm D of Knuth, The Art of Computer Programming, vol. 2, section 4.3.1, which
+/// finds one 64-bit digit of the quotient per step, instead of one bit as `udivmod_double`. It is
+/// shared by all integer types wider than 128 bits. `v` must have at least 2 limbs, the highest of
+/// which is non-zero, `u` at least as many as `v`, and `q` exactly `u.len() - v.len() + 1`. `v` is
+/// modified during the division, and restored before returning.
+pub fn udivmod_limbs(u: &mut [u64], v: &mut [u64], q: &mut [u64]) {
+    let n = v.len();
+    let m = u.len() - n;
+    debug_assert!(n >= 2 && v[n - 1] != 0 && q.len() == m + 1);
+
+    // Normalize, so that the highest bit of `v` is set and the estimates below are off by at most
+    // 2. The bits shifted out of `u` form an extra, highest limb.
+    let shift = v[n - 1].leading_zeros();
+    shl_limbs(v, shift);
+    let u_top = shl_limbs(u, shift);
+    let (v1, v2) = (v[n - 1], v[n - 2]);
+
+    for j in (0 .. m + 1).rev() {
+        // The remainder so far is in `u[j .. j + n + 1]`, and less than `v` shifted by `j` limbs.
+        let top = if j == m { u_top } else { u[j + n] };
+        let (mut q_hat, mut r_hat, mut r_hat_overflowed) = if top == v1 {
+            let (r_hat, overflowed) = u[j + n - 1].overflowing_add(v1);
+            (!0, r_hat, overflowed)
+        } else {
+            let (q_hat, r_hat) = udivmod128(u128::from_parts(top, u[j + n - 1]), u128::new(v1));
+            (q_hat.lo, r_hat.lo, false)
+        };
+        while !r_hat_overflowed && u64_long_mul(q_hat, v2) > u128::from_parts(r_hat, u[j + n - 2]) {
+            q_hat -= 1;
+            let (sum, overflowed) = r_hat.overflowing_add(v1);
+            r_hat = sum;
+            r_hat_overflowed = overflowed;
+        }
+
+        // Subtract `q_hat * v`. If the result is negative, `q_hat` was still one too large.
+        let mut mul_carry = 0;
+        let mut borrow = false;
+        for i in 0 .. n {
+            let product = u64_long_mul(q_hat, v[i]).wrapping_add(u128::new(mul_carry));
+            mul_carry = product.hi;
+            let (diff, borrow1) = u[j + i].overflowing_sub(product.lo);
+            let (diff, borrow2) = diff.overflowing_sub(borrow as u64);
+            u[j + i] = diff;
+            borrow = borrow1 || borrow2;
+        }
+        let (top, borrow1) = top.overflowing_sub(mul_carry);
+        let (_, borrow2) = top.overflowing_sub(borrow as u64);
+        if borrow1 || borrow2 {
+            q_hat -= 1;
+            let mut carry = false;
+            for i in 0 .. n {
+                let (sum, carry1) = u[j + i].overflowing_add(v[i]);
+                let (sum, carry2) = sum.overflowing_add(carry as u64);
+                u[j + i] = sum;
+                carry = carry1 || carry2;
+            }
+        }
+        // the remainder is now less than `v` shifted by `j` limbs, so its highest limb is 0.
+        if j < m {
+            u[j + n] = 0;
+        }
+        q[j] = q_hat;
+    }
+
+    shr_limbs(&mut u[.. n], shift);
+    shr_limbs(v, shift);
+}
+
+#[cfg(test)]
+mod limb_tests {
+    use u128::u128;
+    use u256::u256;
+    use rng::Mcg128;
+    use compiler_rt::{long_mul, udivmod_double, udivmod128, udivmod_limbs};
+
+    #[test]
+    fn test_long_mul() {
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+        for _ in 0..1000 {
+            let (a, b) = (rng.next_u64(), rng.next_u64());
+            let (hi, lo) = long_mul(a as u32, b as u32);
+            assert_eq!((hi as u64) << 32 | lo as u64, (a as u32 as u64) * (b as u32 as u64));
+            let (hi, lo) = long_mul(a, b);
+            assert_eq!(u128::from_parts(hi, lo), u128::new(a).wrapping_mul_64(b));
+        }
+        assert_eq!(long_mul(!0u64, !0u64), (!0 - 1, 1));
+        let max = u128::max_value();
+        assert_eq!(long_mul(max, max), (max - u128::one(), u128::one()));
+    }
+
+    #[test]
+    fn test_udivmod_double() {
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+        for i in 0..1000u32 {
+            let n = rng.next_u64();
+            let d = rng.next_u64() >> (i % 64);
+            if d == 0 {
+                continue;
             }
-            _ => {
-                d.leading_zeros() - n.leading_zeros() + 1
-            },
+            let ((qh, ql), (rh, rl)) = udivmod_double(((n >> 32) as u32, n as u32), ((d >> 32) as u32, d as u32));
+            assert_eq!((qh as u64) << 32 | ql as u64, n / d);
+            assert_eq!((rh as u64) << 32 | rl as u64, n % d);
+
+            let n = rng.next_u128();
+            let d = rng.next_u128() >> (i % 128) | u128::one();
+            let ((qh, ql), (rh, rl)) = udivmod_double((n.high64(), n.low64()), (d.high64(), d.low64()));
+            assert_eq!((u128::from_parts(qh, ql), u128::from_parts(rh, rl)), udivmod128(n, d));
+
+            let n2 = u256::from_parts(rng.next_u128(), rng.next_u128());
+            let (q, r) = udivmod_double((n2.high128(), n2.low128()), (u128::zero(), d));
+            let q = u256::from_parts(q.0, q.1);
+            let r = u256::from_parts(r.0, r.1);
+            assert_eq!(q * u256::new(d) + r, n2);
+            assert!(r < u256::new(d));
+        }
+    }
+
+    fn to_limbs(x: u256) -> [u64; 4] {
+        [x.low128().low64(), x.low128().high64(), x.high128().low64(), x.high128().high64()]
+    }
+
+    /// Checks `udivmod_limbs` against `udivmod_double` with 128-bit limbs.
+    fn check_udivmod_limbs(n: u256, d: u256) {
+        let mut u = to_limbs(n);
+        let mut v = to_limbs(d);
+        let mut q = [0u64; 4];
+        let v_len = v.iter().rposition(|&limb| limb != 0).unwrap() + 1;
+        udivmod_limbs(&mut u, &mut v[.. v_len], &mut q[.. 5 - v_len]);
+
+        let (eq, er) = udivmod_double((n.high128(), n.low128()), (d.high128(), d.low128()));
+        assert_eq!(q, to_limbs(u256::from_parts(eq.0, eq.1)), "{:x} / {:x}", n, d);
+        assert_eq!(u, to_limbs(u256::from_parts(er.0, er.1)), "{:x} % {:x}", n, d);
+        assert_eq!(v, to_limbs(d));
+    }
+
+    #[test]
+    fn test_udivmod_limbs() {
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+        let special = [0, 1, 2, 0x7fff_ffff_ffff_ffff, 0x8000_0000_0000_0000, !0 - 1, !0];
+        let next_limb = |rng: &mut Mcg128| {
+            let r = rng.next_u64();
+            if r & 1 == 0 { special[(r >> 1) as usize % special.len()] } else { rng.next_u64() }
         };
+        for i in 0..10000u32 {
+            let n = [next_limb(&mut rng), next_limb(&mut rng), next_limb(&mut rng), next_limb(&mut rng)];
+            let mut d = [next_limb(&mut rng), next_limb(&mut rng), next_limb(&mut rng), next_limb(&mut rng)];
+            let d_len = 2 + i as usize % 3;
+            for limb in &mut d[d_len ..] {
+                *limb = 0;
+            }
+            if d[d_len - 1] == 0 {
+                d[d_len - 1] = 1;
+            }
+            let n = u256::from_parts(u128::from_parts(n[3], n[2]), u128::from_parts(n[1], n[0]));
+            let d = u256::from_parts(u128::from_parts(d[3], d[2]), u128::from_parts(d[1], d[0]));
+            check_udivmod_limbs(n, d);
+        }
+
+        // cases where the estimated quotient digit is too large, and the divisor is added back.
+        check_udivmod_limbs(u256::from_parts(u128::from_parts(0x7fff_ffff_ffff_ffff, 0x8000_0000_0000_0000), u128::zero()),
+                            u256::from_parts(u128::new(0x8000_0000_0000_0000), u128::new(1)));
+        check_udivmod_limbs(u256::from_parts(u128::from_parts(0x8000_0000_0000_0000, 0), u128::new(3)),
+                            u256::from_parts(u128::zero(), u128::from_parts(0x2000_0000_0000_0000, 1)));
+        check_udivmod_limbs(u256::max_value(), u256::from_parts(u128::zero(), u128::from_parts(1, 0)));
+        check_udivmod_limbs(u256::max_value(), u256::max_value());
+    }
+}
+
+/// Exhaustive verification of the double-limb algorithms on a 16-bit type made of two 8-bit limbs,
+/// against native 16-bit arithmetic. Unlike random testing of the 128-bit types, this covers every
+/// carry and borrow pattern.
+#[cfg(test)]
+mod small_model_tests {
+    use compiler_rt::{long_mul, udivmod_double, add_double, sub_double, shl_double, shr_double};
+
+    fn split(x: u16) -> (u8, u8) {
+        ((x >> 8) as u8, x as u8)
+    }
+
+    fn join((hi, lo): (u8, u8)) -> u16 {
+        (hi as u16) << 8 | lo as u16
+    }
+
+    /// Divisors covering every small value, every value adjacent to a power of two, and a stride
+    /// through the rest of the range.
+    fn divisors() -> Vec<u16> {
+        let mut divisors: Vec<u16> = (1..32).collect();
+        for shift in 0..16 {
+            let p = 1u16 << shift;
+            divisors.extend_from_slice(&[p.wrapping_sub(1), p, p.wrapping_add(1)]);
+        }
+        let mut d = 32u32;
+        while d < 0xffff {
+            divisors.push(d as u16);
+            d += 4099;
+        }
+        divisors.push(0xffff);
+        divisors.retain(|&d| d != 0);
+        divisors
+    }
+
+    #[test]
+    fn test_long_mul_exhaustive() {
+        for a in 0..0x100u16 {
+            for b in 0..0x100u16 {
+                assert_eq!(join(long_mul(a as u8, b as u8)), a * b, "{} * {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let operands = divisors();
+        for a in 0..0x10000u32 {
+            let a = a as u16;
+            for &b in &operands {
+                let (sum, carry) = add_double(split(a), split(b));
+                assert_eq!((join(sum), carry), a.overflowing_add(b), "{} + {}", a, b);
+                let (diff, borrow) = sub_double(split(a), split(b));
+                assert_eq!((join(diff), borrow), a.overflowing_sub(b), "{} - {}", a, b);
+            }
+        }
+    }
 
-        let mut q = n << (128 - sr);
-        let mut r = n >> sr;
-        let mut carry = 0;
-        for _ in 0 .. sr {
-            r = r << 1;
-            r.lo |= q.hi >> 63;
-            q = q << 1;
-            q.lo |= carry;
-            carry = 0;
-            if r >= d {
-                r = r - d;
-                carry = 1;
+    #[test]
+    fn test_shift_exhaustive() {
+        for a in 0..0x10000u32 {
+            let a = a as u16;
+            for shift in 0..16 {
+                assert_eq!(join(shl_double(split(a), shift)), a << shift, "{} << {}", a, shift);
+                assert_eq!(join(shr_double(split(a), shift)), a >> shift, "{} >> {}", a, shift);
+            }
+        }
+    }
+
+    #[test]
+    fn test_udivmod() {
+        let divisors = divisors();
+        for n in 0..0x10000u32 {
+            let n = n as u16;
+            for &d in &divisors {
+                let (q, r) = udivmod_double(split(n), split(d));
+                assert_eq!((join(q), join(r)), (n / d, n % d), "{} / {}", n, d);
+            }
+        }
+    }
+
+    /// Checks every pair of operands. This takes a few minutes, so run it with `--ignored`, preferably
+    /// with `--release`.
+    #[test]
+    #[ignore]
+    fn test_add_sub_udivmod_exhaustive() {
+        for a in 0..0x10000u32 {
+            let a = a as u16;
+            for b in 0..0x10000u32 {
+                let b = b as u16;
+                let (sum, carry) = add_double(split(a), split(b));
+                assert_eq!((join(sum), carry), a.overflowing_add(b), "{} + {}", a, b);
+                let (diff, borrow) = sub_double(split(a), split(b));
+                assert_eq!((join(diff), borrow), a.overflowing_sub(b), "{} - {}", a, b);
+                if b != 0 {
+                    let (q, r) = udivmod_double(split(a), split(b));
+                    assert_eq!((join(q), join(r)), (a / b, a % b), "{} / {}", a, b);
+                }
             }
         }
-        q = q << 1;
-        q.lo |= carry;
-        rem.map(|rp| { *rp = r; });
-        q
     }
 }
 
+//}}}
 
+
+
+/// Computes `(n / d, n % d)` in a `const` context.
+///
+/// This is a plain shift-and-subtract long division operating on the raw 64-bit halves, since
+/// trait methods (comparison, shifts) cannot be called from `const fn`. It is slower than
+/// `udivmod128`, so it should only be used for values computed at compile time.
+#[cfg(extprim_has_const_fn)]
+pub const fn udivmod128_const(n: u128, d: u128) -> (u128, u128) {
+    if d.hi == 0 && d.lo == 0 {
+        panic!("attempted to divide by zero");
+    }
+    if n.hi == 0 && d.hi == 0 {
+        return (u128 { lo: n.lo / d.lo, hi: 0 }, u128 { lo: n.lo % d.lo, hi: 0 });
+    }
+
+    let n_lz = if n.hi == 0 { 64 + n.lo.leading_zeros() } else { n.hi.leading_zeros() };
+    let d_lz = if d.hi == 0 { 64 + d.lo.leading_zeros() } else { d.hi.leading_zeros() };
+    if n_lz > d_lz {
+        return (u128 { lo: 0, hi: 0 }, n);
+    }
+
+    let mut q = u128 { lo: 0, hi: 0 };
+    let mut r = n;
+    let mut shift = d_lz - n_lz + 1;
+    while shift > 0 {
+        shift -= 1;
+        let (ds_hi, ds_lo) = if shift == 0 {
+            (d.hi, d.lo)
+        } else if shift < 64 {
+            (d.hi << shift | d.lo >> (64 - shift), d.lo << shift)
+        } else {
+            (d.lo << (shift - 64), 0)
+        };
+        if r.hi > ds_hi || r.hi == ds_hi && r.lo >= ds_lo {
+            let (lo, borrow) = r.lo.overflowing_sub(ds_lo);
+            r = u128 { lo: lo, hi: r.hi.wrapping_sub(ds_hi).wrapping_sub(borrow as u64) };
+            if shift < 64 {
+                q.lo |= 1 << shift;
+            } else {
+                q.hi |= 1 << (shift - 64);
+            }
+        }
+    }
+    (q, r)
+}
+
+#[cfg(all(test, extprim_has_const_fn))]
+mod const_div_tests {
+    use u128::u128;
+    use compiler_rt::{udivmod128, udivmod128_const};
+
+    const SAMPLES: &[u128] = &[
+        u128 { lo: 1, hi: 0 },
+        u128 { lo: 3, hi: 0 },
+        u128 { lo: 10, hi: 0 },
+        u128 { lo: !0, hi: 0 },
+        u128 { lo: 0, hi: 1 },
+        u128 { lo: 0x9a4b_1c7e_22f3_0d58, hi: 0x3e8c_5f01_b6a2_7d94 },
+        u128 { lo: 0x0000_0001_0000_0000, hi: 0x0000_0000_0000_0007 },
+        u128 { lo: !0, hi: 0x7fff_ffff_ffff_ffff },
+        u128 { lo: 0, hi: 0x8000_0000_0000_0000 },
+        u128 { lo: 0x1234_5678_9abc_def0, hi: 0xc001_d00d_f00d_beef },
+        u128 { lo: !0, hi: !0 },
+    ];
+
+    #[test]
+    fn test_matches_runtime_division() {
+        for &n in SAMPLES {
+            for &d in SAMPLES {
+                assert_eq!(udivmod128_const(n, d), udivmod128(n, d), "{:?} / {:?}", n, d);
+            }
+            assert_eq!(udivmod128_const(u128::zero(), n), (u128::zero(), u128::zero()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_divide_by_zero() {
+        udivmod128_const(u128::one(), u128::zero());
+    }
+}
@@ -0,0 +1,183 @@
+//! Selection of CPU-specific arithmetic kernels.
+//!
+//! On x86-64, the kernels below have accelerated versions using the BMI2 (`mulx`), ADX (`adcx`)
+//! and PCLMULQDQ instructions (on compilers supporting them, 1.33+). If the crate is compiled with
+//! the corresponding target features, the accelerated versions are called directly. Otherwise the
+//! portable versions are used, unless the `cpu-dispatch` feature is enabled: then the running CPU
+//! is checked with `is_x86_feature_detected!` on the first call and the choice is cached in a
+//! function pointer, so a single binary still gets the fast paths, at the cost of an indirect call
+//! each time. On other architectures the portable versions are always used.
+
+#[cfg(all(target_arch="x86_64", extprim_has_target_feature, feature="cpu-dispatch"))]
+use std::mem::transmute;
+#[cfg(all(target_arch="x86_64", extprim_has_target_feature, feature="cpu-dispatch"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(all(target_arch="x86_64", extprim_has_target_feature))] use std::arch::x86_64::*;
+
+use u128::u128;
+
+/// Defines a kernel which calls `$fast` if the CPU supports all the `$feature`s, and `$portable`
+/// otherwise. `$fast` must be an `unsafe fn` which is only safe to call when the features exist.
+macro_rules! dispatch {
+    (
+        $(#[$attr:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;
+        $($feature:tt),+ => $fast:path,
+        _ => $portable:path
+    ) => {
+        $(#[$attr])*
+        #[cfg(all(target_arch="x86_64", extprim_has_target_feature, feature="cpu-dispatch"))]
+        pub(crate) fn $name($($arg: $ty),*) -> $ret {
+            if cfg!(all($(target_feature=$feature),+)) {
+                return unsafe { $fast($($arg),*) };
+            }
+
+            // 0 means the CPU has not been checked yet. A race only causes the check to be
+            // repeated, and every thread stores the same pointer.
+            static KERNEL: AtomicUsize = AtomicUsize::new(0);
+            let mut kernel = KERNEL.load(Ordering::Relaxed);
+            if kernel == 0 {
+                let selected: unsafe fn($($ty),*) -> $ret = if $(is_x86_feature_detected!($feature))&&+ {
+                    $fast
+                } else {
+                    $portable
+                };
+                kernel = selected as usize;
+                KERNEL.store(kernel, Ordering::Relaxed);
+            }
+            unsafe {
+                let selected = transmute::<usize, unsafe fn($($ty),*) -> $ret>(kernel);
+                selected($($arg),*)
+            }
+        }
+
+        $(#[$attr])*
+        #[cfg(all(target_arch="x86_64", extprim_has_target_feature, not(feature="cpu-dispatch")))]
+        #[inline]
+        pub(crate) fn $name($($arg: $ty),*) -> $ret {
+            if cfg!(all($(target_feature=$feature),+)) {
+                unsafe { $fast($($arg),*) }
+            } else {
+                $portable($($arg),*)
+            }
+        }
+
+        $(#[$attr])*
+        #[cfg(not(all(target_arch="x86_64", extprim_has_target_feature)))]
+        #[inline]
+        pub(crate) fn $name($($arg: $ty),*) -> $ret {
+            $portable($($arg),*)
+        }
+    }
+}
+
+dispatch! {
+    /// Computes the full 128-bit product of two 64-bit numbers.
+    fn mul_64(left: u64, right: u64) -> u128;
+    "bmi2" => mul_64_bmi2,
+    _ => ::u128::u64_long_mul_portable
+}
+
+dispatch! {
+    /// Computes the full 256-bit product of two 128-bit numbers, as little-endian 64-bit limbs.
+    fn mul_128(left: u128, right: u128) -> [u64; 4];
+    "bmi2", "adx" => mul_128_adx,
+    _ => ::u256::mul_128_portable
+}
+
+dispatch! {
+    /// Computes the carry-less product of two 64-bit numbers.
+    fn clmul_64(left: u64, right: u64) -> u128;
+    "pclmulqdq" => clmul_64_pclmul,
+    _ => ::u128::clmul64_portable
+}
+
+#[cfg(all(target_arch="x86_64", extprim_has_target_feature))]
+#[target_feature(enable="bmi2")]
+unsafe fn mul_64_bmi2(left: u64, right: u64) -> u128 {
+    let mut hi = 0;
+    let lo = _mulx_u64(left, right, &mut hi);
+    u128::from_parts(hi, lo)
+}
+
+#[cfg(all(target_arch="x86_64", extprim_has_target_feature))]
+#[target_feature(enable="bmi2,adx")]
+unsafe fn mul_128_adx(left: u128, right: u128) -> [u64; 4] {
+    let (mut h00, mut h01, mut h10, mut h11) = (0, 0, 0, 0);
+    let l00 = _mulx_u64(left.lo, right.lo, &mut h00);
+    let l01 = _mulx_u64(left.lo, right.hi, &mut h01);
+    let l10 = _mulx_u64(left.hi, right.lo, &mut h10);
+    let l11 = _mulx_u64(left.hi, right.hi, &mut h11);
+
+    // Two carry chains add the cross products `l01 + h01 * 2^64` and `l10 + h10 * 2^64` into the
+    // outer products, starting from the second limb.
+    let (mut r1, mut r2, mut r3) = (0, 0, 0);
+    let carry = _addcarryx_u64(0, h00, l01, &mut r1);
+    let carry = _addcarryx_u64(carry, l11, h01, &mut r2);
+    _addcarryx_u64(carry, h11, 0, &mut r3);
+    let carry = _addcarryx_u64(0, r1, l10, &mut r1);
+    let carry = _addcarryx_u64(carry, r2, h10, &mut r2);
+    _addcarryx_u64(carry, r3, 0, &mut r3);
+    [l00, r1, r2, r3]
+}
+
+#[cfg(all(target_arch="x86_64", extprim_has_target_feature))]
+#[target_feature(enable="pclmulqdq")]
+unsafe fn clmul_64_pclmul(left: u64, right: u64) -> u128 {
+    let product = _mm_clmulepi64_si128(_mm_set_epi64x(0, left as i64), _mm_set_epi64x(0, right as i64), 0);
+    u128 {
+        lo: _mm_cvtsi128_si64(product) as u64,
+        hi: _mm_cvtsi128_si64(_mm_unpackhi_epi64(product, product)) as u64,
+    }
+}
+
+#[cfg(all(test, target_arch="x86_64", extprim_has_target_feature, feature="use-std"))]
+mod cpu_tests {
+    use u128::{u128, MAX, u64_long_mul_portable, clmul64_portable};
+    use u256::mul_128_portable;
+    use rng::Mcg128;
+    use super::*;
+
+    fn inputs() -> Vec<u128> {
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+        let mut values = vec![u128::zero(), u128::one(), MAX, u128::from_parts(!0, 0), u128::new(!0)];
+        values.extend((0..50).map(|_| rng.next_u128()));
+        values
+    }
+
+    #[test]
+    fn test_mul_64() {
+        if is_x86_feature_detected!("bmi2") {
+            for &a in &inputs() {
+                for &b in &inputs() {
+                    assert_eq!(unsafe { mul_64_bmi2(a.lo, b.hi) }, u64_long_mul_portable(a.lo, b.hi));
+                }
+            }
+        }
+        assert_eq!(mul_64(!0, !0), u128::from_parts(!0 - 1, 1));
+    }
+
+    #[test]
+    fn test_mul_128() {
+        if is_x86_feature_detected!("bmi2") && is_x86_feature_detected!("adx") {
+            for &a in &inputs() {
+                for &b in &inputs() {
+                    assert_eq!(unsafe { mul_128_adx(a, b) }, mul_128_portable(a, b));
+                }
+            }
+        }
+        assert_eq!(mul_128(MAX, MAX), [1, 0, !0 - 1, !0]);
+    }
+
+    #[test]
+    fn test_clmul_64() {
+        if is_x86_feature_detected!("pclmulqdq") {
+            for &a in &inputs() {
+                for &b in &inputs() {
+                    assert_eq!(unsafe { clmul_64_pclmul(a.hi, b.lo) }, clmul64_portable(a.hi, b.lo));
+                }
+            }
+        }
+        assert_eq!(clmul_64(3, 3), u128::new(5));
+    }
+}
@@ -0,0 +1,151 @@
+//! Integers stored in a fixed byte order.
+//!
+//! [`BigEndian<T>`](struct.BigEndian.html) and [`LittleEndian<T>`](struct.LittleEndian.html) hold
+//! an integer whose in-memory representation is in the given byte order regardless of the target,
+//! so they can be used as the fields of `#[repr(C)]` structs describing on-disk or network
+//! formats. The value is byte-swapped as needed when it is read with `get` or written with `set`.
+//!
+//! Comparison and formatting go through the native value, so e.g. `BigEndian<u128>` is ordered
+//! and printed like a `u128`.
+//!
+//! ```rust
+//! use extprim::endian::{BigEndian, LittleEndian};
+//! use extprim::u128::u128;
+//!
+//! #[repr(C)]
+//! struct Header {
+//!     id: BigEndian<u128>,
+//!     length: LittleEndian<u128>,
+//! }
+//!
+//! let mut header = Header { id: BigEndian::new(u128::new(0x0102)), length: LittleEndian::default() };
+//! header.length.set(u128::new(4096));
+//! assert_eq!(header.id.get(), u128::new(0x0102));
+//! assert_eq!(header.length.to_string(), "4096");
+//! assert!(header.id < BigEndian::new(u128::new(0x0201)));
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use num_traits::PrimInt;
+
+macro_rules! endian_type {
+    ($(#[$attr:meta])* struct $name:ident, $to:ident, $from:ident) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct $name<T>(T);
+
+        impl<T: PrimInt> $name<T> {
+            /// Stores a value, converting it to the byte order of this type.
+            pub fn new(value: T) -> $name<T> {
+                $name(value.$to())
+            }
+
+            /// Returns the stored value, converted to the native byte order.
+            pub fn get(self) -> T {
+                T::$from(self.0)
+            }
+
+            /// Replaces the stored value.
+            pub fn set(&mut self, value: T) {
+                self.0 = value.$to();
+            }
+        }
+
+        impl<T: PrimInt> From<T> for $name<T> {
+            fn from(value: T) -> $name<T> {
+                $name::new(value)
+            }
+        }
+
+        impl<T: PrimInt> PartialOrd for $name<T> {
+            fn partial_cmp(&self, other: &$name<T>) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<T: PrimInt> Ord for $name<T> {
+            fn cmp(&self, other: &$name<T>) -> Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl<T: PrimInt + fmt::Debug> fmt::Debug for $name<T> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.debug_tuple(stringify!($name)).field(&self.get()).finish()
+            }
+        }
+
+        endian_type!(@fmt $name: Display LowerHex UpperHex Octal Binary);
+    };
+    (@fmt $name:ident: $($tr:ident)*) => {$(
+        impl<T: PrimInt + fmt::$tr> fmt::$tr for $name<T> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                fmt::$tr::fmt(&self.get(), formatter)
+            }
+        }
+    )*};
+}
+
+endian_type! {
+    /// An integer stored in big endian (network) byte order.
+    struct BigEndian, to_be, from_be
+}
+
+endian_type! {
+    /// An integer stored in little endian byte order.
+    struct LittleEndian, to_le, from_le
+}
+
+#[cfg(test)]
+mod endian_tests {
+    use std::mem::{size_of, transmute};
+    use endian::{BigEndian, LittleEndian};
+    use u128::u128;
+    use i128::i128;
+
+    #[test]
+    fn test_layout() {
+        let value = u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10);
+        let be: [u8; 16] = unsafe { transmute(BigEndian::new(value)) };
+        let le: [u8; 16] = unsafe { transmute(LittleEndian::new(value)) };
+        assert_eq!(be, value.to_be_bytes());
+        assert_eq!(le, value.to_le_bytes());
+        assert_eq!(size_of::<BigEndian<u128>>(), 16);
+        assert_eq!(size_of::<LittleEndian<u32>>(), 4);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut a = BigEndian::new(i128::new(-5));
+        assert_eq!(a.get(), i128::new(-5));
+        a.set(i128::max_value());
+        assert_eq!(a.get(), i128::max_value());
+        let b: LittleEndian<u128> = u128::new(12345).into();
+        assert_eq!(b.get(), u128::new(12345));
+        assert_eq!(LittleEndian::<u128>::default().get(), u128::zero());
+    }
+
+    #[test]
+    fn test_cmp() {
+        let small = BigEndian::new(u128::from_parts(0, 0xff));
+        let large = BigEndian::new(u128::from_parts(1, 0));
+        assert!(small < large);
+        assert!(LittleEndian::new(i128::new(-1)) < LittleEndian::new(i128::one()));
+        assert_eq!(small, BigEndian::new(u128::new(0xff)));
+    }
+
+    #[test]
+    fn test_fmt() {
+        let a = BigEndian::new(u128::new(0xabc));
+        assert_eq!(format!("{}", a), "2748");
+        assert_eq!(format!("{:x}", a), "abc");
+        assert_eq!(format!("{:#X}", a), "0xABC");
+        assert_eq!(format!("{:o}", a), "5274");
+        assert_eq!(format!("{:b}", LittleEndian::new(u128::new(5))), "101");
+        assert_eq!(format!("{:?}", LittleEndian::new(7u32)), "LittleEndian(7)");
+        assert_eq!(format!("{:>6}", LittleEndian::new(i128::new(-12))), "   -12");
+    }
+}
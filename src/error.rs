@@ -1,5 +1,6 @@
 use core::num::ParseIntError;
 use core::mem::transmute;
+use core::fmt;
 
 pub fn invalid_digit() -> ParseIntError {
     unsafe { transmute(1u8) }
@@ -21,6 +22,104 @@ pub fn is_overflow(e: &ParseIntError) -> bool {
     *e == overflow()
 }
 
+/// An error which can be returned when parsing an integer written in scientific notation, e.g. by
+/// [`u128::from_str_scientific`](../u128/struct.u128.html#method.from_str_scientific).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseScientificError {
+    /// The string is not a valid number, or the number does not fit in the target type.
+    Int(ParseIntError),
+
+    /// The string is a valid number, but its value is not an integer (e.g. `"2.5"` or `"1e-3"`).
+    NotIntegral,
+}
+
+impl From<ParseIntError> for ParseScientificError {
+    fn from(e: ParseIntError) -> Self {
+        ParseScientificError::Int(e)
+    }
+}
+
+impl fmt::Display for ParseScientificError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseScientificError::Int(ref e) => e.fmt(formatter),
+            ParseScientificError::NotIntegral => formatter.write_str("number is not an integer"),
+        }
+    }
+}
+
+#[cfg(feature="use-std")]
+impl ::std::error::Error for ParseScientificError {
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        match *self {
+            ParseScientificError::Int(ref e) => ::std::error::Error::description(e),
+            ParseScientificError::NotIntegral => "number is not an integer",
+        }
+    }
+}
+
+/// An error which can be returned when converting between an integer and BSON's `Decimal128`.
+#[cfg(feature="bson")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Decimal128Error {
+    /// The decimal is infinite or NaN.
+    NotFinite,
+
+    /// The decimal is finite, but its value is not an integer.
+    NotIntegral,
+
+    /// The decimal is an integer which does not fit in the target type.
+    Overflow,
+
+    /// The integer has more than 34 significant digits, and cannot be represented exactly.
+    Inexact,
+}
+
+#[cfg(feature="bson")]
+impl fmt::Display for Decimal128Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match *self {
+            Decimal128Error::NotFinite => "decimal is not finite",
+            Decimal128Error::NotIntegral => "number is not an integer",
+            Decimal128Error::Overflow => "number too large to fit in target type",
+            Decimal128Error::Inexact => "number has too many significant digits for a decimal",
+        })
+    }
+}
+
+#[cfg(feature="bson")]
+impl ::std::error::Error for Decimal128Error {}
+
+/// An error which can be returned when converting between an integer and a Postgres `NUMERIC` or
+/// `INT8`.
+#[cfg(any(feature="postgres", feature="sqlx"))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NumericError {
+    /// The numeric is NaN or infinite.
+    NotFinite,
+
+    /// The numeric is finite, but its value is not an integer.
+    NotIntegral,
+
+    /// The number does not fit in the target type.
+    Overflow,
+}
+
+#[cfg(any(feature="postgres", feature="sqlx"))]
+impl fmt::Display for NumericError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match *self {
+            NumericError::NotFinite => "numeric is not finite",
+            NumericError::NotIntegral => "number is not an integer",
+            NumericError::Overflow => "number too large to fit in target type",
+        })
+    }
+}
+
+#[cfg(any(feature="postgres", feature="sqlx"))]
+impl ::std::error::Error for NumericError {}
+
 #[cfg(test)]
 mod tests {
     use error;
@@ -31,6 +130,17 @@ mod tests {
         assert_fmt_eq!("cannot parse integer from empty string", 38, "{}", error::empty());
         assert_fmt_eq!("number too large to fit in target type", 38, "{}", error::overflow());
         assert_fmt_eq!("number too small to fit in target type", 38, "{}", error::underflow());
+        assert_fmt_eq!("number is not an integer", 24, "{}", error::ParseScientificError::NotIntegral);
+        assert_fmt_eq!("number too large to fit in target type", 38, "{}",
+                        error::ParseScientificError::from(error::overflow()));
+    }
+
+    #[test]
+    #[cfg(feature="use-std")]
+    #[allow(deprecated)]
+    fn test_parse_scientific_error_description() {
+        use std::error::Error;
+        assert_eq!(error::ParseScientificError::NotIntegral.description(), "number is not an integer");
     }
 }
 
@@ -0,0 +1,357 @@
+//! IEEE 754 half-precision floating point number (binary16).
+//!
+//! `f16` is a storage format: it converts to and from `f32` and `f64`, and supports comparison
+//! and formatting, but arithmetic should be done after converting to `f32`. Every `f16` value is
+//! exactly representable as an `f32`, so widening never loses precision, while narrowing rounds to
+//! the nearest `f16`, with ties rounded to even.
+//!
+//! ```rust
+//! use extprim::f16::f16;
+//!
+//! let x = f16::from_f32(1.5);
+//! assert_eq!(x.to_bits(), 0x3e00);
+//! assert_eq!(x.to_f32(), 1.5);
+//! assert_eq!(f16::from_f64(65520.0), f16::INFINITY);
+//! assert_eq!(f16::from_f32(0.1).to_string(), "0.099975586");
+//! assert!(f16::from_f32(-2.0) < f16::ONE);
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Neg;
+
+/// A 16-bit floating point number, with 1 sign bit, 5 exponent bits and 10 fraction bits.
+#[derive(Default, Copy, Clone)]
+#[allow(non_camel_case_types)]
+pub struct f16(u16);
+
+const SIGN_MASK: u16 = 0x8000;
+const EXP_MASK: u16 = 0x7c00;
+const FRAC_MASK: u16 = 0x03ff;
+
+impl f16 {
+    /// Positive zero.
+    pub const ZERO: f16 = f16(0);
+
+    /// The constant 1.
+    pub const ONE: f16 = f16(0x3c00);
+
+    /// The largest finite value, 65504.
+    pub const MAX: f16 = f16(0x7bff);
+
+    /// The smallest finite value, -65504.
+    pub const MIN: f16 = f16(0xfbff);
+
+    /// The smallest positive normal value, 2<sup>-14</sup>.
+    pub const MIN_POSITIVE: f16 = f16(0x0400);
+
+    /// The difference between 1 and the next larger representable number, 2<sup>-10</sup>.
+    pub const EPSILON: f16 = f16(0x1400);
+
+    /// Positive infinity.
+    pub const INFINITY: f16 = f16(0x7c00);
+
+    /// Negative infinity.
+    pub const NEG_INFINITY: f16 = f16(0xfc00);
+
+    /// Not a number.
+    pub const NAN: f16 = f16(0x7e00);
+
+    /// Creates a number from its binary representation.
+    pub fn from_bits(bits: u16) -> f16 {
+        f16(bits)
+    }
+
+    /// Returns the binary representation of the number.
+    pub fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if this value is NaN.
+    pub fn is_nan(self) -> bool {
+        self.0 & EXP_MASK == EXP_MASK && self.0 & FRAC_MASK != 0
+    }
+
+    /// Returns `true` if this value is positive or negative infinity.
+    pub fn is_infinite(self) -> bool {
+        self.0 & !SIGN_MASK == EXP_MASK
+    }
+
+    /// Returns `true` if this value is neither infinite nor NaN.
+    pub fn is_finite(self) -> bool {
+        self.0 & EXP_MASK != EXP_MASK
+    }
+
+    /// Returns `true` if this value is neither zero, infinite, subnormal nor NaN.
+    pub fn is_normal(self) -> bool {
+        let exp = self.0 & EXP_MASK;
+        exp != 0 && exp != EXP_MASK
+    }
+
+    /// Returns `true` if the sign bit is set, including for `-0.0` and NaNs with a negative sign.
+    pub fn is_sign_negative(self) -> bool {
+        self.0 & SIGN_MASK != 0
+    }
+
+    /// Returns `true` if the sign bit is clear, including for `+0.0` and NaNs with a positive
+    /// sign.
+    pub fn is_sign_positive(self) -> bool {
+        !self.is_sign_negative()
+    }
+
+    /// Computes the absolute value of the number.
+    pub fn abs(self) -> f16 {
+        f16(self.0 & !SIGN_MASK)
+    }
+}
+
+//{{{ Conversion
+
+impl f16 {
+    /// Converts an `f64` to the nearest `f16`, rounding ties to even.
+    ///
+    /// Values too large in magnitude become infinity, and values too small become zero, keeping
+    /// the sign. NaN stays NaN.
+    pub fn from_f64(value: f64) -> f16 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 48) as u16) & SIGN_MASK;
+        let exp = ((bits >> 52) & 0x7ff) as i32;
+        let frac = bits & 0xf_ffff_ffff_ffff;
+
+        if exp == 0x7ff {
+            return if frac == 0 {
+                f16(sign | EXP_MASK)
+            } else {
+                f16(sign | EXP_MASK | 0x200 | (frac >> 42) as u16)
+            };
+        }
+
+        let exp = exp - 1023;
+        if exp > 15 {
+            return f16(sign | EXP_MASK);
+        }
+
+        let mantissa = frac | (1 << 52);
+
+        // Drop the mantissa bits which do not fit. A normal result keeps 11 bits, and a subnormal
+        // one keeps fewer, counting in units of 2^-24. The rounding increment may carry into the
+        // exponent, which correctly produces the next binade or infinity. Subnormal `f64` values
+        // are far below the range and become zero.
+        let (kept, shift) = if exp >= -14 {
+            ((((exp + 15) as u64) << 10) | ((mantissa >> 42) & 0x3ff), 42)
+        } else if exp >= -25 {
+            let shift = (28 - exp) as u32;
+            (mantissa >> shift, shift)
+        } else {
+            return f16(sign);
+        };
+        let rest = mantissa & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        let rounded = if rest > half || (rest == half && kept & 1 != 0) {
+            kept + 1
+        } else {
+            kept
+        };
+        f16(sign | rounded as u16)
+    }
+
+    /// Converts an `f32` to the nearest `f16`, rounding ties to even.
+    ///
+    /// Values too large in magnitude become infinity, and values too small become zero, keeping
+    /// the sign. NaN stays NaN.
+    pub fn from_f32(value: f32) -> f16 {
+        f16::from_f64(value as f64)
+    }
+
+    /// Converts the number to an `f64`. This conversion is exact.
+    pub fn to_f64(self) -> f64 {
+        let sign = ((self.0 & SIGN_MASK) as u64) << 48;
+        let exp = (self.0 & EXP_MASK) >> 10;
+        let frac = (self.0 & FRAC_MASK) as u64;
+        match exp {
+            0 => {
+                let magnitude = frac as f64 / 16777216.0;
+                if sign != 0 { -magnitude } else { magnitude }
+            }
+            0x1f => f64::from_bits(sign | 0x7ff0_0000_0000_0000 | frac << 42),
+            _ => f64::from_bits(sign | (exp as u64 + 1023 - 15) << 52 | frac << 42),
+        }
+    }
+
+    /// Converts the number to an `f32`. This conversion is exact.
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+}
+
+impl From<f16> for f32 {
+    fn from(value: f16) -> f32 {
+        value.to_f32()
+    }
+}
+
+impl From<f16> for f64 {
+    fn from(value: f16) -> f64 {
+        value.to_f64()
+    }
+}
+
+//}}}
+
+//{{{ Comparison
+
+impl PartialEq for f16 {
+    fn eq(&self, other: &f16) -> bool {
+        self.to_f32() == other.to_f32()
+    }
+}
+
+impl PartialOrd for f16 {
+    fn partial_cmp(&self, other: &f16) -> Option<Ordering> {
+        self.to_f32().partial_cmp(&other.to_f32())
+    }
+}
+
+impl Neg for f16 {
+    type Output = f16;
+    fn neg(self) -> f16 {
+        f16(self.0 ^ SIGN_MASK)
+    }
+}
+
+//}}}
+
+//{{{ Display
+
+// The number is formatted as the equivalent `f32`, so the shortest representation printed by
+// `Display` is the one which round-trips through `f32`, not necessarily the shortest which
+// round-trips through `f16`.
+
+impl fmt::Display for f16 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f32(), formatter)
+    }
+}
+
+impl fmt::Debug for f16 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f32(), formatter)
+    }
+}
+
+impl fmt::LowerExp for f16 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerExp::fmt(&self.to_f32(), formatter)
+    }
+}
+
+impl fmt::UpperExp for f16 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperExp::fmt(&self.to_f32(), formatter)
+    }
+}
+
+//}}}
+
+#[cfg(test)]
+mod conv_tests {
+    use f16::f16;
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(f16::ONE.to_f64(), 1.0);
+        assert_eq!(f16::MAX.to_f64(), 65504.0);
+        assert_eq!(f16::MIN.to_f64(), -65504.0);
+        assert_eq!(f16::MIN_POSITIVE.to_f64(), 6.103515625e-5);
+        assert_eq!(f16::EPSILON.to_f64(), 0.0009765625);
+        assert_eq!(f16::from_bits(0x0001).to_f64(), 5.960464477539063e-8);
+        assert_eq!(f16::from_bits(0x83ff).to_f64(), -6.097555160522461e-5);
+        assert_eq!(f16::INFINITY.to_f64(), 1.0 / 0.0);
+        assert_eq!(f16::NEG_INFINITY.to_f32(), -1.0 / 0.0);
+        assert!(f16::NAN.to_f64().is_nan());
+        assert!(f16::from_bits(0x8000).to_f64().is_sign_negative());
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(f16::from_f64(1.0).to_bits(), 0x3c00);
+        assert_eq!(f16::from_f64(-2.5).to_bits(), 0xc100);
+        assert_eq!(f16::from_f64(65504.0).to_bits(), 0x7bff);
+        assert_eq!(f16::from_f64(65519.99).to_bits(), 0x7bff);
+        assert_eq!(f16::from_f64(65520.0).to_bits(), 0x7c00);
+        assert_eq!(f16::from_f64(-1e10).to_bits(), 0xfc00);
+        assert_eq!(f16::from_f64(-0.0).to_bits(), 0x8000);
+        assert_eq!(f16::from_f64(1e-300).to_bits(), 0x0000);
+        assert_eq!(f16::from_f64(-5e-324).to_bits(), 0x8000);
+
+        // Ties round to even.
+        assert_eq!(f16::from_f64(1.0 + 1.0 / 2048.0).to_bits(), 0x3c00);
+        assert_eq!(f16::from_f64(1.0 + 3.0 / 2048.0).to_bits(), 0x3c02);
+        assert_eq!(f16::from_f64(1.0 + 1.0 / 2048.0 + 1e-10).to_bits(), 0x3c01);
+
+        // Subnormals, and rounding between subnormals and normals.
+        assert_eq!(f16::from_f64(5.960464477539063e-8).to_bits(), 0x0001);
+        assert_eq!(f16::from_f64(2.9802322387695312e-8).to_bits(), 0x0000);
+        assert_eq!(f16::from_f64(2.9802322387695313e-8 * 1.0000001).to_bits(), 0x0001);
+        assert_eq!(f16::from_f64(8.940696716308594e-8).to_bits(), 0x0002);
+        assert_eq!(f16::from_f64(6.1e-5).to_bits(), 0x03ff);
+        assert_eq!(f16::from_f64(6.1032e-5).to_bits(), 0x0400);
+
+        let nan = f16::from_f64(f64::from_bits(0x7ff8_0000_0000_0000));
+        assert!(nan.is_nan());
+        assert!(f16::from_f32(f32::from_bits(0xffc0_0000)).is_sign_negative());
+        assert!(f16::from_f64(f64::from_bits(0x7ff0_0000_0000_0001)).is_nan());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for bits in 0..0x10000u32 {
+            let x = f16::from_bits(bits as u16);
+            if x.is_nan() {
+                assert!(f16::from_f32(x.to_f32()).is_nan());
+            } else {
+                assert_eq!(f16::from_f64(x.to_f64()).to_bits(), x.to_bits());
+                assert_eq!(f16::from_f32(x.to_f32()).to_bits(), x.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert!(f16::NAN.is_nan() && !f16::NAN.is_finite());
+        assert!(f16::NEG_INFINITY.is_infinite() && !f16::NEG_INFINITY.is_nan());
+        assert!(f16::MAX.is_finite() && f16::MAX.is_normal());
+        assert!(!f16::from_bits(0x0001).is_normal());
+        assert!(!f16::ZERO.is_normal());
+        assert!((-f16::ONE).is_sign_negative());
+        assert_eq!((-f16::ONE).abs().to_bits(), f16::ONE.to_bits());
+        assert_eq!(f32::from(f16::EPSILON), 0.0009765625);
+    }
+}
+
+#[cfg(test)]
+mod cmp_tests {
+    use f16::f16;
+
+    #[test]
+    fn test_cmp() {
+        assert!(f16::MIN < f16::ZERO);
+        assert!(f16::NEG_INFINITY < f16::MIN);
+        assert!(f16::from_bits(0x0001) > f16::ZERO);
+        assert_eq!(f16::ZERO, -f16::ZERO);
+        assert!(f16::NAN != f16::NAN);
+        assert_eq!(f16::NAN.partial_cmp(&f16::ONE), None);
+    }
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(f16::ONE.to_string(), "1");
+        assert_eq!(f16::MAX.to_string(), "65504");
+        assert_eq!(format!("{:?}", f16::from_f32(-0.5)), "-0.5");
+        assert_eq!(format!("{:.3}", f16::from_f32(2.71875)), "2.719");
+        assert_eq!(format!("{:e}", f16::MIN_POSITIVE), "6.1035156e-5");
+        assert_eq!(format!("{:E}", f16::from_f32(1500.0)), "1.5E3");
+        assert_eq!(f16::INFINITY.to_string(), "inf");
+        assert_eq!(f16::NAN.to_string(), "NaN");
+    }
+}
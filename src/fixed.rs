@@ -0,0 +1,597 @@
+//! Fixed-point numbers with 64 integer bits and 64 fractional bits.
+//!
+//! [`U64F64`](struct.U64F64.html) stores a number as a `u128` counting units of 2<sup>-64</sup>,
+//! and [`I64F64`](struct.I64F64.html) does the same with an `i128`. Addition and subtraction are
+//! exact. Multiplication and division compute the full 256-bit intermediate product or dividend,
+//! and then truncate the result towards zero to the nearest representable value.
+//!
+//! The operators panic if the result does not fit (or on division by zero); the `checked_*`
+//! methods return `None` instead.
+//!
+//! ```rust
+//! use extprim::fixed::{U64F64, I64F64};
+//!
+//! let price = U64F64::from_f64(1.25).unwrap();
+//! let quantity = U64F64::from_int(3);
+//! assert_eq!((price * quantity).to_string(), "3.75");
+//! assert_eq!((quantity / U64F64::from_int(8)).to_string(), "0.375");
+//! assert_eq!(format!("{:.3}", U64F64::ONE / quantity), "0.333");
+//!
+//! let delta = I64F64::from_int(-3) / I64F64::from_int(8);
+//! assert_eq!(delta.to_string(), "-0.375");
+//! assert_eq!(delta.to_f64(), -0.375);
+//! ```
+
+use std::fmt;
+use std::ops::*;
+use std::str::from_utf8_unchecked;
+
+use num_traits::{ToPrimitive, FromPrimitive};
+
+use u128::u128;
+use i128::i128;
+use u256::u256;
+
+/// The value of one unit of the integer part, 2<sup>64</sup>, as a float.
+const SCALE: f64 = 18446744073709551616.0;
+
+/// The maximum length of the integer part, a '.' and 64 fractional digits.
+const MAX_STR_LEN: usize = 20 + 1 + 64;
+
+//{{{ Structure
+
+/// An unsigned fixed-point number with 64 integer bits and 64 fractional bits.
+///
+/// The representable values are the multiples of 2<sup>-64</sup> from 0 to
+/// 2<sup>64</sup> - 2<sup>-64</sup>.
+#[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct U64F64 {
+    bits: u128,
+}
+
+/// A signed fixed-point number with 64 integer bits (including the sign) and 64 fractional bits.
+///
+/// The representable values are the multiples of 2<sup>-64</sup> from -2<sup>63</sup> to
+/// 2<sup>63</sup> - 2<sup>-64</sup>.
+#[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct I64F64 {
+    bits: i128,
+}
+
+impl U64F64 {
+    /// The constant 0.
+    pub const ZERO: U64F64 = U64F64 { bits: u128 { lo: 0, hi: 0 } };
+
+    /// The constant 1.
+    pub const ONE: U64F64 = U64F64 { bits: u128 { lo: 0, hi: 1 } };
+
+    /// The smallest positive value, 2<sup>-64</sup>.
+    pub const DELTA: U64F64 = U64F64 { bits: u128 { lo: 1, hi: 0 } };
+
+    /// The largest value, 2<sup>64</sup> - 2<sup>-64</sup>.
+    pub const MAX: U64F64 = U64F64 { bits: u128 { lo: !0, hi: !0 } };
+
+    /// Creates a number from its representation in units of 2<sup>-64</sup>.
+    pub fn from_bits(bits: u128) -> U64F64 {
+        U64F64 { bits: bits }
+    }
+
+    /// Returns the representation of the number in units of 2<sup>-64</sup>.
+    pub fn to_bits(self) -> u128 {
+        self.bits
+    }
+
+    /// Creates a number from an integer.
+    pub fn from_int(value: u64) -> U64F64 {
+        U64F64 { bits: u128::from_parts(value, 0) }
+    }
+
+    /// Returns the integer part of the number, rounding down.
+    pub fn int_part(self) -> u64 {
+        self.bits.high64()
+    }
+
+    /// Returns the fractional part of the number, which lies in `0 .. 1`.
+    pub fn frac_part(self) -> U64F64 {
+        U64F64 { bits: u128::new(self.bits.low64()) }
+    }
+}
+
+impl I64F64 {
+    /// The constant 0.
+    pub const ZERO: I64F64 = I64F64 { bits: i128(u128 { lo: 0, hi: 0 }) };
+
+    /// The constant 1.
+    pub const ONE: I64F64 = I64F64 { bits: i128(u128 { lo: 0, hi: 1 }) };
+
+    /// The smallest positive value, 2<sup>-64</sup>.
+    pub const DELTA: I64F64 = I64F64 { bits: i128(u128 { lo: 1, hi: 0 }) };
+
+    /// The smallest value, -2<sup>63</sup>.
+    pub const MIN: I64F64 = I64F64 { bits: i128(u128 { lo: 0, hi: 0x8000_0000_0000_0000 }) };
+
+    /// The largest value, 2<sup>63</sup> - 2<sup>-64</sup>.
+    pub const MAX: I64F64 = I64F64 { bits: i128(u128 { lo: !0, hi: 0x7fff_ffff_ffff_ffff }) };
+
+    /// Creates a number from its representation in units of 2<sup>-64</sup>.
+    pub fn from_bits(bits: i128) -> I64F64 {
+        I64F64 { bits: bits }
+    }
+
+    /// Returns the representation of the number in units of 2<sup>-64</sup>.
+    pub fn to_bits(self) -> i128 {
+        self.bits
+    }
+
+    /// Creates a number from an integer.
+    pub fn from_int(value: i64) -> I64F64 {
+        I64F64 { bits: i128::from_parts(value, 0) }
+    }
+
+    /// Returns the integer part of the number, rounding down (towards negative infinity).
+    pub fn int_part(self) -> i64 {
+        self.bits.high64()
+    }
+
+    /// Returns the fractional part of the number, which lies in `0 .. 1`. This is the difference
+    /// between the number and its integer part, so it is never negative.
+    pub fn frac_part(self) -> I64F64 {
+        I64F64 { bits: i128::from_parts(0, self.bits.low64()) }
+    }
+
+    /// Returns `true` if the number is negative.
+    pub fn is_negative(self) -> bool {
+        self.bits.is_negative()
+    }
+
+    /// Splits the number into its sign and magnitude in units of 2<sup>-64</sup>.
+    fn sign_abs(self) -> (bool, u128) {
+        let negative = self.is_negative();
+        let magnitude = if negative { self.bits.0.wrapping_neg() } else { self.bits.0 };
+        (negative, magnitude)
+    }
+
+    /// Creates a number from its sign and magnitude, returning `None` if it is out of range.
+    fn from_sign_abs(negative: bool, magnitude: u128) -> Option<I64F64> {
+        let limit = u128::from_parts(0x8000_0000_0000_0000, 0);
+        if negative && magnitude <= limit {
+            Some(I64F64 { bits: i128(magnitude.wrapping_neg()) })
+        } else if !negative && magnitude < limit {
+            Some(I64F64 { bits: i128(magnitude) })
+        } else {
+            None
+        }
+    }
+}
+
+//}}}
+
+//{{{ Arithmetic
+
+/// Computes `(left * right) >> 64`, returning `None` if it does not fit in 128 bits.
+fn mul_magnitude(left: u128, right: u128) -> Option<u128> {
+    let product = u256::mul_128(left, right);
+    let (hi, lo) = (product.high128(), product.low128());
+    if hi.high64() != 0 {
+        None
+    } else {
+        Some(u128::from_parts(hi.low64(), lo.high64()))
+    }
+}
+
+/// Computes `(left << 64) / right`, returning `None` if it does not fit in 128 bits or if `right`
+/// is zero.
+fn div_magnitude(left: u128, right: u128) -> Option<u128> {
+    let dividend = u256::from_parts(u128::new(left.high64()), u128::from_parts(left.low64(), 0));
+    match dividend.checked_div(u256::new(right)) {
+        Some(quotient) if quotient.high128() == u128::zero() => Some(quotient.low128()),
+        _ => None,
+    }
+}
+
+impl U64F64 {
+    /// Checked addition. Computes `self + other`, returning `None` if overflow occurred.
+    pub fn checked_add(self, other: U64F64) -> Option<U64F64> {
+        self.bits.checked_add(other.bits).map(U64F64::from_bits)
+    }
+
+    /// Checked subtraction. Computes `self - other`, returning `None` if overflow occurred.
+    pub fn checked_sub(self, other: U64F64) -> Option<U64F64> {
+        self.bits.checked_sub(other.bits).map(U64F64::from_bits)
+    }
+
+    /// Checked multiplication. Computes `self * other` truncated to a multiple of
+    /// 2<sup>-64</sup>, returning `None` if overflow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::fixed::U64F64;
+    ///
+    /// let half = U64F64::ONE / U64F64::from_int(2);
+    /// assert_eq!(half.checked_mul(U64F64::from_int(7)), Some(U64F64::from_f64(3.5).unwrap()));
+    /// assert_eq!(U64F64::MAX.checked_mul(U64F64::from_int(2)), None);
+    /// ```
+    pub fn checked_mul(self, other: U64F64) -> Option<U64F64> {
+        mul_magnitude(self.bits, other.bits).map(U64F64::from_bits)
+    }
+
+    /// Checked division. Computes `self / other` truncated to a multiple of 2<sup>-64</sup>,
+    /// returning `None` if `other == 0` or overflow occurred.
+    pub fn checked_div(self, other: U64F64) -> Option<U64F64> {
+        div_magnitude(self.bits, other.bits).map(U64F64::from_bits)
+    }
+}
+
+impl I64F64 {
+    /// Checked addition. Computes `self + other`, returning `None` if overflow occurred.
+    pub fn checked_add(self, other: I64F64) -> Option<I64F64> {
+        self.bits.checked_add(other.bits).map(I64F64::from_bits)
+    }
+
+    /// Checked subtraction. Computes `self - other`, returning `None` if overflow occurred.
+    pub fn checked_sub(self, other: I64F64) -> Option<I64F64> {
+        self.bits.checked_sub(other.bits).map(I64F64::from_bits)
+    }
+
+    /// Checked multiplication. Computes `self * other` truncated towards zero to a multiple of
+    /// 2<sup>-64</sup>, returning `None` if overflow occurred.
+    pub fn checked_mul(self, other: I64F64) -> Option<I64F64> {
+        let (ls, lm) = self.sign_abs();
+        let (rs, rm) = other.sign_abs();
+        mul_magnitude(lm, rm).and_then(|m| I64F64::from_sign_abs(ls != rs, m))
+    }
+
+    /// Checked division. Computes `self / other` truncated towards zero to a multiple of
+    /// 2<sup>-64</sup>, returning `None` if `other == 0` or overflow occurred.
+    pub fn checked_div(self, other: I64F64) -> Option<I64F64> {
+        let (ls, lm) = self.sign_abs();
+        let (rs, rm) = other.sign_abs();
+        div_magnitude(lm, rm).and_then(|m| I64F64::from_sign_abs(ls != rs, m))
+    }
+
+    /// Checked negation. Computes `-self`, returning `None` if `self == MIN`.
+    pub fn checked_neg(self) -> Option<I64F64> {
+        self.bits.checked_neg().map(I64F64::from_bits)
+    }
+}
+
+macro_rules! impl_fixed_ops {
+    ($ty:ident) => {
+        impl Add for $ty {
+            type Output = $ty;
+            fn add(self, other: $ty) -> $ty {
+                self.checked_add(other).expect("arithmetic operation overflowed")
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = $ty;
+            fn sub(self, other: $ty) -> $ty {
+                self.checked_sub(other).expect("arithmetic operation overflowed")
+            }
+        }
+
+        impl Mul for $ty {
+            type Output = $ty;
+            fn mul(self, other: $ty) -> $ty {
+                self.checked_mul(other).expect("arithmetic operation overflowed")
+            }
+        }
+
+        impl Div for $ty {
+            type Output = $ty;
+            fn div(self, other: $ty) -> $ty {
+                if other == $ty::ZERO {
+                    panic!("attempted to divide by zero");
+                }
+                self.checked_div(other).expect("arithmetic operation overflowed")
+            }
+        }
+
+        forward_assign!(AddAssign(add_assign, add) for $ty);
+        forward_assign!(SubAssign(sub_assign, sub) for $ty);
+        forward_assign!(MulAssign(mul_assign, mul) for $ty);
+        forward_assign!(DivAssign(div_assign, div) for $ty);
+    }
+}
+
+impl_fixed_ops!(U64F64);
+impl_fixed_ops!(I64F64);
+
+impl Neg for I64F64 {
+    type Output = I64F64;
+    fn neg(self) -> I64F64 {
+        self.checked_neg().expect("arithmetic operation overflowed")
+    }
+}
+
+//}}}
+
+//{{{ Float conversion
+
+impl U64F64 {
+    /// Converts a float to the nearest fixed-point number towards zero. Returns `None` if the
+    /// value is negative, not finite, or too large.
+    pub fn from_f64(value: f64) -> Option<U64F64> {
+        if !(value >= 0.0 && value < SCALE) {
+            return None;
+        }
+        u128::from_f64(value * SCALE).map(U64F64::from_bits)
+    }
+
+    /// Converts a float to the nearest fixed-point number towards zero. Returns `None` if the
+    /// value is negative, not finite, or too large.
+    pub fn from_f32(value: f32) -> Option<U64F64> {
+        U64F64::from_f64(value as f64)
+    }
+
+    /// Converts the number to the nearest float.
+    pub fn to_f64(self) -> f64 {
+        self.bits.to_f64().unwrap() / SCALE
+    }
+
+    /// Converts the number to the nearest float.
+    pub fn to_f32(self) -> f32 {
+        // bits close to 2^128 round up to 2^128, which does not fit in an `f32` although the scaled
+        // value 2^64 does.
+        let bits = self.bits.to_f32().unwrap();
+        if bits.is_infinite() {
+            SCALE as f32
+        } else {
+            bits / SCALE as f32
+        }
+    }
+}
+
+impl I64F64 {
+    /// Converts a float to the nearest fixed-point number towards zero. Returns `None` if the
+    /// value is not finite or out of range.
+    pub fn from_f64(value: f64) -> Option<I64F64> {
+        if !(value >= -SCALE / 2.0 && value < SCALE / 2.0) {
+            return None;
+        }
+        i128::from_f64(value * SCALE).map(I64F64::from_bits)
+    }
+
+    /// Converts a float to the nearest fixed-point number towards zero. Returns `None` if the
+    /// value is not finite or out of range.
+    pub fn from_f32(value: f32) -> Option<I64F64> {
+        I64F64::from_f64(value as f64)
+    }
+
+    /// Converts the number to the nearest float.
+    pub fn to_f64(self) -> f64 {
+        self.bits.to_f64().unwrap() / SCALE
+    }
+
+    /// Converts the number to the nearest float.
+    pub fn to_f32(self) -> f32 {
+        self.bits.to_f32().unwrap() / SCALE as f32
+    }
+}
+
+//}}}
+
+//{{{ Display
+
+/// Writes the decimal expansion of a magnitude in units of 2<sup>-64</sup> to `buffer`.
+///
+/// Every such number has a terminating expansion of at most 64 fractional digits. Without a
+/// precision, all of them are written, except trailing zeros. Otherwise the expansion is rounded
+/// to the requested number of digits, with ties rounded away from zero.
+fn format_fixed(magnitude: u128, precision: Option<usize>, buffer: &mut [u8; MAX_STR_LEN]) -> &str {
+    let mut int_part = u128::new(magnitude.high64());
+    let mut frac = u128::new(magnitude.low64());
+
+    let mut digits = [0u8; 64];
+    let mut len = 0;
+    let limit = precision.map_or(64, |p| if p < 64 { p } else { 64 });
+    while len < limit && (frac != u128::zero() || precision.is_some()) {
+        frac *= u128::new(10);
+        digits[len] = frac.high64() as u8;
+        frac = u128::new(frac.low64());
+        len += 1;
+    }
+
+    if frac.high64() == 0 && frac.low64() >= 0x8000_0000_0000_0000 {
+        let mut pos = len;
+        loop {
+            if pos == 0 {
+                int_part += u128::one();
+                break;
+            }
+            pos -= 1;
+            if digits[pos] == 9 {
+                digits[pos] = 0;
+            } else {
+                digits[pos] += 1;
+                break;
+            }
+        }
+    }
+
+    let mut int_buffer = [0u8; ::u128::MAX_STR_LEN];
+    let int_str = ::format_buffer::format_decimal(int_part, &mut int_buffer).as_bytes();
+    buffer[.. int_str.len()].copy_from_slice(int_str);
+    let mut pos = int_str.len();
+    if len > 0 {
+        buffer[pos] = b'.';
+        pos += 1;
+        for &digit in &digits[.. len] {
+            buffer[pos] = b'0' + digit;
+            pos += 1;
+        }
+    }
+
+    unsafe { from_utf8_unchecked(&buffer[.. pos]) }
+}
+
+/// Formats the number in decimal.
+///
+/// The precision gives the number of fractional digits, up to 64. Without a precision, the exact
+/// value is written.
+impl fmt::Display for U64F64 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut buffer = [0u8; MAX_STR_LEN];
+        formatter.pad_integral(true, "", format_fixed(self.bits, formatter.precision(), &mut buffer))
+    }
+}
+
+/// Formats the number in decimal.
+///
+/// The precision gives the number of fractional digits, up to 64. Without a precision, the exact
+/// value is written.
+impl fmt::Display for I64F64 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let (negative, magnitude) = self.sign_abs();
+        let mut buffer = [0u8; MAX_STR_LEN];
+        formatter.pad_integral(!negative, "", format_fixed(magnitude, formatter.precision(), &mut buffer))
+    }
+}
+
+//}}}
+
+#[cfg(test)]
+mod arith_tests {
+    use fixed::{U64F64, I64F64};
+    use u128::u128;
+
+    #[test]
+    fn test_unsigned() {
+        let a = U64F64::from_int(6);
+        let b = U64F64::from_f64(0.25).unwrap();
+        assert_eq!(a + b, U64F64::from_f64(6.25).unwrap());
+        assert_eq!(a - b, U64F64::from_f64(5.75).unwrap());
+        assert_eq!(a * b, U64F64::from_f64(1.5).unwrap());
+        assert_eq!(a / b, U64F64::from_int(24));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(U64F64::MAX.checked_add(U64F64::DELTA), None);
+        assert_eq!(U64F64::DELTA * U64F64::DELTA, U64F64::ZERO);
+        assert_eq!(U64F64::MAX * U64F64::ONE, U64F64::MAX);
+        assert_eq!(U64F64::ONE / U64F64::from_int(3), U64F64::from_bits(u128::new(0x5555_5555_5555_5555)));
+        assert_eq!(U64F64::from_int(1 << 32).checked_mul(U64F64::from_int(1 << 32)), None);
+        assert_eq!(U64F64::from_int(1 << 32).checked_div(U64F64::from_f64(1.0 / 4294967296.0).unwrap()), None);
+        assert_eq!(a.checked_div(U64F64::ZERO), None);
+
+        let mut c = a;
+        c *= b;
+        c += U64F64::ONE;
+        c /= U64F64::from_int(5);
+        c -= U64F64::from_f64(0.5).unwrap();
+        assert_eq!(c, U64F64::ZERO);
+        assert_eq!(a.int_part(), 6);
+        assert_eq!((a + b).frac_part(), b);
+    }
+
+    #[test]
+    fn test_signed() {
+        let a = I64F64::from_int(-6);
+        let b = I64F64::from_f64(0.25).unwrap();
+        assert_eq!(a + b, I64F64::from_f64(-5.75).unwrap());
+        assert_eq!(b - a, I64F64::from_f64(6.25).unwrap());
+        assert_eq!(a * b, I64F64::from_f64(-1.5).unwrap());
+        assert_eq!(a * -b, I64F64::from_f64(1.5).unwrap());
+        assert_eq!(a / b, I64F64::from_int(-24));
+        assert_eq!(-I64F64::ONE / I64F64::from_int(3), -(I64F64::ONE / I64F64::from_int(3)));
+        assert_eq!(I64F64::MIN * I64F64::ONE, I64F64::MIN);
+        assert_eq!(I64F64::MIN.checked_mul(-I64F64::ONE), None);
+        assert_eq!(I64F64::MIN.checked_div(-I64F64::ONE), None);
+        assert_eq!(I64F64::MIN.checked_neg(), None);
+        assert_eq!(I64F64::MAX.checked_add(I64F64::DELTA), None);
+        assert_eq!(I64F64::from_int(-4).checked_mul(I64F64::from_int(1 << 61)), Some(I64F64::MIN));
+        assert_eq!(b.checked_div(I64F64::ZERO), None);
+
+        let c = I64F64::from_f64(-2.75).unwrap();
+        assert_eq!(c.int_part(), -3);
+        assert_eq!(c.frac_part(), b);
+        assert!(c.is_negative());
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_div_by_zero() {
+        let _ = U64F64::ONE / U64F64::ZERO;
+    }
+
+    #[test]
+    #[should_panic(expected="arithmetic operation overflowed")]
+    fn test_mul_overflow() {
+        let _ = I64F64::MAX * I64F64::from_int(2);
+    }
+}
+
+#[cfg(test)]
+mod conv_tests {
+    use fixed::{U64F64, I64F64};
+    use u128::u128;
+    use i128::i128;
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(U64F64::from_f64(1.5), Some(U64F64::from_bits(u128::from_parts(1, 0x8000_0000_0000_0000))));
+        assert_eq!(U64F64::from_f64(18446744073709549568.0), Some(U64F64::from_int(18446744073709549568)));
+        assert_eq!(U64F64::from_f64(18446744073709551616.0), None);
+        assert_eq!(U64F64::from_f64(-0.5), None);
+        assert_eq!(U64F64::from_f64(f64::from_bits(0x7ff8_0000_0000_0000)), None);
+        assert_eq!(U64F64::from_f32(0.75), U64F64::from_f64(0.75));
+
+        assert_eq!(I64F64::from_f64(-1.5), Some(I64F64::from_bits(i128::from_parts(-2, 0x8000_0000_0000_0000))));
+        assert_eq!(I64F64::from_f64(-9223372036854775808.0), Some(I64F64::MIN));
+        assert_eq!(I64F64::from_f64(9223372036854775808.0), None);
+        assert_eq!(I64F64::from_f64(f64::from_bits(0x7ff0_0000_0000_0000)), None);
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(U64F64::from_int(3).to_f64(), 3.0);
+        assert_eq!(U64F64::DELTA.to_f64(), 5.421010862427522e-20);
+        assert_eq!(U64F64::MAX.to_f64(), 18446744073709551616.0);
+        assert_eq!(U64F64::from_f64(0.1).unwrap().to_f64(), 0.1);
+        assert_eq!(U64F64::from_f64(0.1).unwrap().to_f32(), 0.1f32);
+        assert_eq!(I64F64::MIN.to_f64(), -9223372036854775808.0);
+        assert_eq!(I64F64::from_f64(-0.1).unwrap().to_f64(), -0.1);
+        assert_eq!(I64F64::from_int(-7).to_f32(), -7.0);
+    }
+
+    #[test]
+    fn test_to_f32() {
+        assert_eq!(U64F64::MAX.to_f32(), 18446744073709551616.0);
+        assert_eq!(U64F64::from_bits(u128::max_value() >> 24).to_f32(), 1099511627776.0);
+        assert_eq!(U64F64::DELTA.to_f32(), 5.421011e-20);
+        assert_eq!(I64F64::MAX.to_f32(), 9223372036854775808.0);
+        assert_eq!(I64F64::MIN.to_f32(), -9223372036854775808.0);
+        assert_eq!(I64F64::DELTA.to_f32(), 5.421011e-20);
+    }
+}
+
+#[cfg(test)]
+mod show_tests {
+    use fixed::{U64F64, I64F64};
+
+    #[test]
+    fn test_display() {
+        assert_eq!(U64F64::ZERO.to_string(), "0");
+        assert_eq!(U64F64::from_int(42).to_string(), "42");
+        assert_eq!(U64F64::from_f64(0.5).unwrap().to_string(), "0.5");
+        assert_eq!(U64F64::DELTA.to_string(), "0.0000000000000000000542101086242752217003726400434970855712890625");
+        assert_eq!(U64F64::MAX.to_string(), "18446744073709551615.9999999999999999999457898913757247782996273599565029144287109375");
+        assert_eq!(I64F64::MIN.to_string(), "-9223372036854775808");
+        assert_eq!(I64F64::from_f64(-0.125).unwrap().to_string(), "-0.125");
+    }
+
+    #[test]
+    fn test_display_precision() {
+        let third = U64F64::ONE / U64F64::from_int(3);
+        assert_eq!(format!("{:.0}", third), "0");
+        assert_eq!(format!("{:.4}", third), "0.3333");
+        assert_eq!(format!("{:.2}", U64F64::from_f64(2.0 / 3.0).unwrap()), "0.67");
+        assert_eq!(format!("{:.1}", U64F64::from_f64(9.96).unwrap()), "10.0");
+        assert_eq!(format!("{:.3}", U64F64::from_int(5)), "5.000");
+        assert_eq!(format!("{:.2}", U64F64::MAX), "18446744073709551616.00");
+        assert_eq!(format!("{:.2}", -I64F64::from_f64(0.5).unwrap()), "-0.50");
+        assert_eq!(format!("{:>8.2}", U64F64::from_f64(1.5).unwrap()), "    1.50");
+        assert_eq!(format!("{:+09.3}", I64F64::from_f64(2.25).unwrap()), "+0002.250");
+        assert_eq!(format!("{:.100}", U64F64::DELTA).len(), 66);
+    }
+}
@@ -0,0 +1,280 @@
+//! Flag-reporting arithmetic.
+//!
+//! CPU emulators and virtual machines usually need the carry, overflow, zero and sign flags of an
+//! addition or subtraction together with its result. The `*_full` methods on [`u128`] and
+//! [`i128`] compute all of them in one pass, instead of recomputing them from separate
+//! `overflowing_*` calls.
+//!
+//! The flags only depend on the bit pattern of the operands, so the `u128` and `i128` versions
+//! always produce the same flags for the same bits.
+//!
+//! [`u128`]: ../u128/struct.u128.html
+//! [`i128`]: ../i128/struct.i128.html
+
+use u128::u128;
+use i128::i128;
+
+/// The status flags produced by an arithmetic operation.
+#[derive(Default, Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub struct Flags {
+    /// Unsigned overflow. For addition this is the carry out of the most significant bit; for
+    /// subtraction this is the borrow.
+    pub carry: bool,
+
+    /// Signed (two's complement) overflow.
+    pub overflow: bool,
+
+    /// Whether the result is zero.
+    pub zero: bool,
+
+    /// Whether the most significant bit of the result is set, i.e. the result is negative when
+    /// treated as an `i128`.
+    pub sign: bool,
+}
+
+impl Flags {
+    fn new(result: u128, carry: bool, overflow_bits: u64) -> Flags {
+        Flags {
+            carry: carry,
+            overflow: overflow_bits >> 63 != 0,
+            zero: result.lo | result.hi == 0,
+            sign: result.hi >> 63 != 0,
+        }
+    }
+}
+
+impl u128 {
+    /// Calculates `self + other + carry`, returning the wrapped result together with all status
+    /// flags.
+    ///
+    /// This is the "add with carry" instruction found in most CPUs. Chaining the `carry` flag
+    /// into the next call allows adding numbers wider than 128 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let (res, flags) = u128::max_value().adc_full(u128::zero(), true);
+    /// assert_eq!(res, u128::zero());
+    /// assert!(flags.carry && flags.zero);
+    /// assert!(!flags.overflow && !flags.sign);
+    /// ```
+    pub fn adc_full(self, other: u128, carry: bool) -> (u128, Flags) {
+        let (lo, lo_carry_1) = self.lo.overflowing_add(other.lo);
+        let (lo, lo_carry_2) = lo.overflowing_add(carry as u64);
+        let (hi, hi_carry_1) = self.hi.overflowing_add(other.hi);
+        let (hi, hi_carry_2) = hi.overflowing_add((lo_carry_1 || lo_carry_2) as u64);
+        let overflow_bits = (self.hi ^ hi) & (other.hi ^ hi);
+        let res = u128::from_parts(hi, lo);
+        (res, Flags::new(res, hi_carry_1 || hi_carry_2, overflow_bits))
+    }
+
+    /// Calculates `self + other`, returning the wrapped result together with all status flags.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let (res, flags) = u128::from_parts(0x7fffffff_ffffffff, !0).add_full(u128::one());
+    /// assert_eq!(res, u128::from_parts(0x80000000_00000000, 0));
+    /// assert!(flags.overflow && flags.sign);
+    /// assert!(!flags.carry && !flags.zero);
+    /// ```
+    pub fn add_full(self, other: u128) -> (u128, Flags) {
+        self.adc_full(other, false)
+    }
+
+    /// Calculates `self - other - borrow`, returning the wrapped result together with all status
+    /// flags. The `carry` flag of the result is set when a borrow occurred.
+    ///
+    /// This is the "subtract with borrow" instruction found in most CPUs. Chaining the `carry`
+    /// flag into the next call allows subtracting numbers wider than 128 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let (res, flags) = u128::one().sbb_full(u128::zero(), true);
+    /// assert_eq!(res, u128::zero());
+    /// assert!(flags.zero);
+    /// assert!(!flags.carry && !flags.overflow && !flags.sign);
+    /// ```
+    pub fn sbb_full(self, other: u128, borrow: bool) -> (u128, Flags) {
+        let (lo, lo_borrow_1) = self.lo.overflowing_sub(other.lo);
+        let (lo, lo_borrow_2) = lo.overflowing_sub(borrow as u64);
+        let (hi, hi_borrow_1) = self.hi.overflowing_sub(other.hi);
+        let (hi, hi_borrow_2) = hi.overflowing_sub((lo_borrow_1 || lo_borrow_2) as u64);
+        let overflow_bits = (self.hi ^ other.hi) & (self.hi ^ hi);
+        let res = u128::from_parts(hi, lo);
+        (res, Flags::new(res, hi_borrow_1 || hi_borrow_2, overflow_bits))
+    }
+
+    /// Calculates `self - other`, returning the wrapped result together with all status flags.
+    /// The `carry` flag of the result is set when a borrow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let (res, flags) = u128::zero().sub_full(u128::one());
+    /// assert_eq!(res, u128::max_value());
+    /// assert!(flags.carry && flags.sign);
+    /// assert!(!flags.overflow && !flags.zero);
+    /// ```
+    pub fn sub_full(self, other: u128) -> (u128, Flags) {
+        self.sbb_full(other, false)
+    }
+}
+
+impl i128 {
+    /// Calculates `self + other + carry`, returning the wrapped result together with all status
+    /// flags.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let (res, flags) = i128::max_value().adc_full(i128::zero(), true);
+    /// assert_eq!(res, i128::min_value());
+    /// assert!(flags.overflow && flags.sign);
+    /// assert!(!flags.carry && !flags.zero);
+    /// ```
+    pub fn adc_full(self, other: i128, carry: bool) -> (i128, Flags) {
+        let (res, flags) = self.0.adc_full(other.0, carry);
+        (i128(res), flags)
+    }
+
+    /// Calculates `self + other`, returning the wrapped result together with all status flags.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let (res, flags) = i128::new(-1).add_full(i128::one());
+    /// assert_eq!(res, i128::zero());
+    /// assert!(flags.carry && flags.zero);
+    /// assert!(!flags.overflow && !flags.sign);
+    /// ```
+    pub fn add_full(self, other: i128) -> (i128, Flags) {
+        self.adc_full(other, false)
+    }
+
+    /// Calculates `self - other - borrow`, returning the wrapped result together with all status
+    /// flags. The `carry` flag of the result is set when a borrow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let (res, flags) = i128::min_value().sbb_full(i128::zero(), true);
+    /// assert_eq!(res, i128::max_value());
+    /// assert!(flags.overflow);
+    /// assert!(!flags.carry && !flags.zero && !flags.sign);
+    /// ```
+    pub fn sbb_full(self, other: i128, borrow: bool) -> (i128, Flags) {
+        let (res, flags) = self.0.sbb_full(other.0, borrow);
+        (i128(res), flags)
+    }
+
+    /// Calculates `self - other`, returning the wrapped result together with all status flags.
+    /// The `carry` flag of the result is set when a borrow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let (res, flags) = i128::new(3).sub_full(i128::new(5));
+    /// assert_eq!(res, i128::new(-2));
+    /// assert!(flags.carry && flags.sign);
+    /// assert!(!flags.overflow && !flags.zero);
+    /// ```
+    pub fn sub_full(self, other: i128) -> (i128, Flags) {
+        self.sbb_full(other, false)
+    }
+}
+
+#[cfg(test)]
+mod flags_tests {
+    use u128::u128;
+    use i128::i128;
+    use flags::Flags;
+
+    const SAMPLES: &[u128] = &[
+        u128 { lo: 0, hi: 0 },
+        u128 { lo: 1, hi: 0 },
+        u128 { lo: !0, hi: 0 },
+        u128 { lo: 0, hi: 1 },
+        u128 { lo: 0x9a4b_1c7e_22f3_0d58, hi: 0x3e8c_5f01_b6a2_7d94 },
+        u128 { lo: !0, hi: 0x7fff_ffff_ffff_ffff },
+        u128 { lo: 0, hi: 0x8000_0000_0000_0000 },
+        u128 { lo: 0x1234_5678_9abc_def0, hi: 0xc001_d00d_f00d_beef },
+        u128 { lo: !0, hi: !0 },
+    ];
+
+    fn flags(carry: bool, overflow: bool, zero: bool, sign: bool) -> Flags {
+        Flags { carry: carry, overflow: overflow, zero: zero, sign: sign }
+    }
+
+    #[test]
+    fn test_add_full() {
+        assert_eq!(u128::one().add_full(u128::one()), (u128::new(2), flags(false, false, false, false)));
+        assert_eq!(u128::max_value().add_full(u128::one()), (u128::zero(), flags(true, false, true, false)));
+        assert_eq!(u128::from_parts(0x80000000_00000000, 0).add_full(u128::from_parts(0x80000000_00000000, 0)),
+                    (u128::zero(), flags(true, true, true, false)));
+        assert_eq!(u128::new(!0).add_full(u128::one()), (u128::from_parts(1, 0), flags(false, false, false, false)));
+        assert_eq!(u128::max_value().adc_full(u128::max_value(), true),
+                    (u128::max_value(), flags(true, false, false, true)));
+    }
+
+    #[test]
+    fn test_sub_full() {
+        assert_eq!(u128::one().sub_full(u128::one()), (u128::zero(), flags(false, false, true, false)));
+        assert_eq!(u128::zero().sub_full(u128::max_value()), (u128::one(), flags(true, false, false, false)));
+        assert_eq!(u128::from_parts(1, 0).sub_full(u128::one()), (u128::new(!0), flags(false, false, false, false)));
+        assert_eq!(u128::zero().sbb_full(u128::max_value(), true), (u128::zero(), flags(true, false, true, false)));
+        assert_eq!(i128::max_value().sub_full(-i128::one()), (i128::min_value(), flags(true, true, false, true)));
+    }
+
+    #[test]
+    fn test_consistent_with_overflowing() {
+        for &a in SAMPLES {
+            for &b in SAMPLES {
+                let (sum, add_flags) = a.add_full(b);
+                assert_eq!((sum, add_flags.carry), a.overflowing_add(b));
+                assert_eq!(add_flags.overflow, i128(a).overflowing_add(i128(b)).1);
+                assert_eq!(add_flags.zero, sum == u128::zero());
+                assert_eq!(add_flags.sign, i128(sum).is_negative());
+                assert_eq!(i128(a).add_full(i128(b)), (i128(sum), add_flags));
+
+                let (diff, sub_flags) = a.sub_full(b);
+                assert_eq!((diff, sub_flags.carry), a.overflowing_sub(b));
+                assert_eq!(sub_flags.overflow, i128(a).overflowing_sub(i128(b)).1);
+                assert_eq!(sub_flags.zero, diff == u128::zero());
+                assert_eq!(sub_flags.sign, i128(diff).is_negative());
+                assert_eq!(i128(a).sub_full(i128(b)), (i128(diff), sub_flags));
+            }
+        }
+    }
+
+    #[test]
+    fn test_chained_carry() {
+        // (2^128 - 1) * 2^128 + (2^128 - 1) + 1 == 2^256, computed as two 128-bit limbs.
+        let (lo, lo_flags) = u128::max_value().add_full(u128::one());
+        let (hi, hi_flags) = u128::max_value().adc_full(u128::zero(), lo_flags.carry);
+        assert_eq!((lo, hi), (u128::zero(), u128::zero()));
+        assert!(hi_flags.carry);
+
+        let (lo, lo_flags) = u128::zero().sub_full(u128::one());
+        let (hi, hi_flags) = u128::one().sbb_full(u128::zero(), lo_flags.carry);
+        assert_eq!((lo, hi), (u128::max_value(), u128::zero()));
+        assert!(!hi_flags.carry && hi_flags.zero);
+    }
+}
@@ -0,0 +1,126 @@
+//! The 128-bit FNV-1a hash function.
+//!
+//! FNV-1a is a simple non-cryptographic hash which XORs each byte into the state and then
+//! multiplies by a fixed prime. It is fast for short inputs, and the 128-bit variant gives
+//! digests with a negligible chance of accidental collision, suitable for content-addressed keys.
+//! It is not resistant to deliberately constructed collisions.
+//!
+//! ```rust
+//! use extprim::u128::u128;
+//! use extprim::fnv::{fnv128, Fnv128};
+//!
+//! let mut hasher = Fnv128::new();
+//! hasher.write(b"foo");
+//! hasher.write(b"bar");
+//! assert_eq!(hasher.digest(), fnv128(b"foobar"));
+//! assert_eq!(fnv128(b"foobar"), u128::from_parts(0x343e1662793c64bf, 0x6f0d3597ba446f18));
+//! ```
+
+use std::hash::Hasher;
+
+use u128::u128;
+
+/// The initial state, `144066263297769815596495629667062367629`.
+const OFFSET_BASIS: u128 = u128 { lo: 0x62b821756295c58d, hi: 0x6c62272e07bb0142 };
+
+/// The multiplier, `2**88 + 0x13b`.
+const PRIME: u128 = u128 { lo: 0x13b, hi: 0x1000000 };
+
+/// A streaming FNV-1a hasher with a 128-bit digest.
+///
+/// Feeding the input in several pieces gives the same digest as hashing it all at once with
+/// [`fnv128`](fn.fnv128.html). The type also implements `std::hash::Hasher`, whose `finish` method
+/// returns the lower 64 bits of the digest.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Fnv128 {
+    state: u128,
+}
+
+impl Fnv128 {
+    /// Creates a new hasher which has not seen any input.
+    pub fn new() -> Fnv128 {
+        Fnv128 { state: OFFSET_BASIS }
+    }
+
+    /// Feeds the bytes into the hash.
+    pub fn write(&mut self, bytes: &[u8]) {
+        let mut state = self.state;
+        for &byte in bytes {
+            state.lo ^= byte as u64;
+            state = state.wrapping_mul(PRIME);
+        }
+        self.state = state;
+    }
+
+    /// Returns the digest of all bytes written so far.
+    pub fn digest(&self) -> u128 {
+        self.state
+    }
+}
+
+impl Default for Fnv128 {
+    fn default() -> Fnv128 {
+        Fnv128::new()
+    }
+}
+
+impl Hasher for Fnv128 {
+    fn write(&mut self, bytes: &[u8]) {
+        Fnv128::write(self, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.lo
+    }
+}
+
+/// Computes the 128-bit FNV-1a digest of the bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::fnv::fnv128;
+///
+/// assert_eq!(fnv128(b""), u128::from_parts(0x6c62272e07bb0142, 0x62b821756295c58d));
+/// assert_eq!(fnv128(b"a"), u128::from_parts(0xd228cb696f1a8caf, 0x78912b704e4a8964));
+/// ```
+pub fn fnv128(bytes: &[u8]) -> u128 {
+    let mut hasher = Fnv128::new();
+    hasher.write(bytes);
+    hasher.digest()
+}
+
+#[cfg(test)]
+mod fnv_tests {
+    use std::hash::{Hash, Hasher};
+
+    use u128::u128;
+    use fnv::{fnv128, Fnv128};
+
+    #[test]
+    fn test_vectors() {
+        assert_eq!(fnv128(b""), u128::from_parts(0x6c62272e07bb0142, 0x62b821756295c58d));
+        assert_eq!(fnv128(b"a"), u128::from_parts(0xd228cb696f1a8caf, 0x78912b704e4a8964));
+        assert_eq!(fnv128(b"foobar"), u128::from_parts(0x343e1662793c64bf, 0x6f0d3597ba446f18));
+    }
+
+    #[test]
+    fn test_streaming() {
+        let input = b"The quick brown fox jumps over the lazy dog";
+        for split in 0..input.len() + 1 {
+            let mut hasher = Fnv128::default();
+            hasher.write(&input[..split]);
+            hasher.write(&input[split..]);
+            assert_eq!(hasher.digest(), fnv128(input));
+        }
+    }
+
+    #[test]
+    fn test_hasher() {
+        let mut hasher = Fnv128::new();
+        0x12345678u32.hash(&mut hasher);
+        assert_eq!(hasher.finish(), hasher.digest().low64());
+        assert_eq!(hasher.digest(), fnv128(&0x12345678u32.to_ne_bytes()));
+    }
+}
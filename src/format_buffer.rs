@@ -1,6 +1,8 @@
 use std::fmt;
 use std::str::from_utf8_unchecked;
 
+use u128::{u128, div_rem, MAX_STR_LEN};
+
 /// An internal structure used to format numbers. This is not intended for general use, since
 /// irrelevant error checking is intentionally omitted.
 pub struct FormatBuffer<'a> {
@@ -31,6 +33,45 @@ impl<'a> fmt::Write for FormatBuffer<'a> {
     }
 }
 
+/// Writes the decimal digits of `value` to the end of `buffer`, and returns the written part.
+///
+/// The digits are produced 19 at a time, so a 128-bit number needs at most two divisions. The sign
+/// and padding are left to `Formatter::pad_integral`, so that signed and unsigned numbers share the
+/// same formatting rules.
+pub fn format_decimal(mut value: u128, buffer: &mut [u8; MAX_STR_LEN]) -> &str {
+    const TEN19: u128 = u128 { lo: 10000000000000000000, hi: 0 };
+
+    let mut pos = buffer.len();
+    loop {
+        let (rest, mut chunk) = if value.hi == 0 {
+            (u128::zero(), value.lo)
+        } else {
+            let (q, r) = div_rem(value, TEN19);
+            (q, r.lo)
+        };
+
+        let chunk_end = pos;
+        loop {
+            pos -= 1;
+            buffer[pos] = b'0' + (chunk % 10) as u8;
+            chunk /= 10;
+            if chunk == 0 {
+                break;
+            }
+        }
+        if rest == u128::zero() {
+            break;
+        }
+        while pos > chunk_end - 19 {
+            pos -= 1;
+            buffer[pos] = b'0';
+        }
+        value = rest;
+    }
+
+    unsafe { from_utf8_unchecked(&buffer[pos ..]) }
+}
+
 #[cfg(test)]
 macro_rules! assert_fmt_eq {
     ($expected:expr, $max_len:expr, $($args:expr),*) => {
@@ -51,3 +92,13 @@ fn test_format_buffer() {
     assert_fmt_eq!("5678", 16, "{}{}{}", 5, 6, 78);
 }
 
+#[test]
+fn test_format_decimal() {
+    let mut buffer = [0u8; 39];
+    assert_eq!(format_decimal(u128::zero(), &mut buffer), "0");
+    assert_eq!(format_decimal(u128::new(10000000000000000000), &mut buffer), "10000000000000000000");
+    assert_eq!(format_decimal(u128::from_parts(1, 0), &mut buffer), "18446744073709551616");
+    assert_eq!(format_decimal(u128::from_parts(5421010862427522170, 687399551400673280), &mut buffer),
+               "100000000000000000000000000000000000000");
+    assert_eq!(format_decimal(u128::max_value(), &mut buffer), "340282366920938463463374607431768211455");
+}
@@ -1,3 +1,68 @@
+//! Macros generating the arithmetic operators of the extra primitive types from their `checked_*`,
+//! `wrapping_*` and `overflowing_*` methods.
+//!
+//! These are exported so that crates defining their own wide integer types can provide the same
+//! operator surface as `u128` and `i128`. Import them with `#[macro_use] extern crate extprim;`.
+
+/// Implements a binary arithmetic operator and its checked method, forwarding to the
+/// `overflowing_*` and `wrapping_*` methods of the type.
+///
+/// `impl Add(add, checked_add, wrapping_add, overflowing_add) for T` generates:
+///
+/// * the inherent method `T::checked_add(self, T) -> Option<T>`, carrying the attached doc
+///   comments, built from `T::overflowing_add(self, T) -> (T, bool)`,
+/// * `impl Add<T> for T`, which panics on overflow when debug assertions are enabled, and calls
+///   `T::wrapping_add` otherwise,
+/// * `impl Add<Wrapping<T>> for Wrapping<T>`, which calls `T::wrapping_add`.
+///
+/// Write `impl Add<U>(...) for T` to use `U` as the right hand side instead. The operator trait
+/// and a tuple struct `Wrapping<T>` must be in scope at the call site. Due to the orphan rule, the
+/// `Wrapping` type must be defined in the calling crate; it cannot be
+/// [`extprim::traits::Wrapping`](traits/struct.Wrapping.html).
+///
+/// The supported operators are `Add`, `Sub` and `Mul`. When the `overflow-hooks` feature is
+/// enabled, overflows of the generated operators are reported to
+/// [`extprim::overflow`](overflow/index.html).
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use] extern crate extprim;
+/// use std::ops::{Add, AddAssign};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// pub struct Wrapping<T>(pub T);
+///
+/// /// A 24-bit unsigned integer.
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// pub struct U24(u32);
+///
+/// impl U24 {
+///     pub fn overflowing_add(self, other: U24) -> (U24, bool) {
+///         let sum = self.0 + other.0;
+///         (U24(sum & 0xff_ffff), sum > 0xff_ffff)
+///     }
+///
+///     pub fn wrapping_add(self, other: U24) -> U24 {
+///         self.overflowing_add(other).0
+///     }
+/// }
+///
+/// forward_symmetric! {
+///     /// Checked addition. Computes `self + other`, returning `None` if overflow occurred.
+///     impl Add(add, checked_add, wrapping_add, overflowing_add) for U24
+/// }
+/// forward_assign!(AddAssign(add_assign, add) for U24);
+///
+/// # fn main() {
+/// let mut a = U24(0xff_fffe);
+/// a += U24(1);
+/// assert_eq!(a, U24(0xff_ffff));
+/// assert_eq!(a.checked_add(U24(1)), None);
+/// assert_eq!(Wrapping(a) + Wrapping(U24(2)), Wrapping(U24(1)));
+/// # }
+/// ```
+#[macro_export]
 macro_rules! forward_symmetric {
     (
         $(#[$cattr:meta])*
@@ -23,6 +88,18 @@ macro_rules! forward_symmetric {
     }
 }
 
+/// Implements a shift operator and its checked method, forwarding to the `overflowing_*` and
+/// `wrapping_*` methods of the type.
+///
+/// `impl Shl(shl, checked_shl, wrapping_shl, overflowing_shl) for T` generates the inherent method
+/// `T::checked_shl(self, u32) -> Option<T>`, `impl Shl<S> for T` for every primitive integer type
+/// `S`, and `impl Shl<u32> for Wrapping<T>`. The methods of `T` take the shift amount as `u32`.
+/// Shifting by a negative amount or by at least the bit width panics when debug assertions are
+/// enabled.
+///
+/// The same requirements as [`forward_symmetric!`](macro.forward_symmetric.html) apply. The
+/// supported operators are `Shl` and `Shr`.
+#[macro_export]
 macro_rules! forward_shift {
     (
         $(#[$cattr:meta])*
@@ -32,13 +109,22 @@ macro_rules! forward_shift {
             $(#[$cattr])*
             impl $tn<
                 u32;
-                u8|u16|u32|u64|usize|i8|i16|i32|i64|isize { y => y.to_u32().unwrap_or_else(|| panic!("shift operation overflowed")) };
+                u8|u16|u32|u64|usize|i8|i16|i32|i64|isize {
+                    y => $crate::macros::ToPrimitive::to_u32(&y).unwrap_or_else(|| panic!("shift operation overflowed"))
+                };
                 u32 { x => x }
             > ($name, $cname, $wname, $oname) for $target, "shift operation overflowed"
         }
     }
 }
 
+/// Implements a compound assignment operator by forwarding to the corresponding binary operator.
+///
+/// `forward_assign!(AddAssign(add_assign, add) for T)` implements `AddAssign<T> for T` as
+/// `*self = self.add(other)`. Write `AddAssign<U|V>(...)` to implement it for the right hand sides
+/// `U` and `V` instead. The assignment trait and the binary operator trait must be in scope at the
+/// call site.
+#[macro_export]
 macro_rules! forward_assign {
     ($tn:ident($name:ident, $fwd:ident) for $target:ty) => {
         forward_assign!($tn<$target>($name, $fwd) for $target);
@@ -52,6 +138,8 @@ macro_rules! forward_assign {
     }
 }
 
+#[doc(hidden)]
+#[macro_export]
 macro_rules! forward_impl {
     (
         $(#[$cattr:meta])*
@@ -80,11 +168,15 @@ macro_rules! forward_impl {
                 let other = match other {
                     $t => $uncheck_cast,
                 };
-                self.$cname(other).unwrap_or_else(|| panic!($emsg))
+                self.$cname(other).unwrap_or_else(|| {
+                    forward_record_overflow!($tn, $target);
+                    panic!($emsg)
+                })
             }
             #[cfg(not(debug_assertions))]
+            #[allow(unused_comparisons, overflowing_literals)]
             fn $name(self, other: $targ) -> Self {
-                self.$wname(match other { $t => $uncheck_cast })
+                forward_release_op!(self.$wname, $oname(match other { $t => $uncheck_cast }) as $tn for $target)
             }
         })+
 
@@ -99,3 +191,45 @@ macro_rules! forward_impl {
     }
 }
 
+// The helpers below are selected by the features of extprim itself, since a `#[cfg]` inside an
+// exported macro would be evaluated against the features of the calling crate.
+
+#[cfg(feature="overflow-hooks")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! forward_record_overflow {
+    ($tn:ident, $target:ty) => {
+        $crate::overflow::record($crate::overflow::Operation::$tn, stringify!($target))
+    }
+}
+
+#[cfg(not(feature="overflow-hooks"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! forward_record_overflow {
+    ($tn:ident, $target:ty) => {
+        ()
+    }
+}
+
+#[cfg(feature="overflow-hooks")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! forward_release_op {
+    ($lhs:ident.$wname:ident, $oname:ident($rhs:expr) as $tn:ident for $target:ty) => {{
+        let (result, overflowed) = $lhs.$oname($rhs);
+        if overflowed {
+            forward_record_overflow!($tn, $target);
+        }
+        result
+    }}
+}
+
+#[cfg(not(feature="overflow-hooks"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! forward_release_op {
+    ($lhs:ident.$wname:ident, $oname:ident($rhs:expr) as $tn:ident for $target:ty) => {
+        $lhs.$wname($rhs)
+    }
+}
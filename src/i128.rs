@@ -1,7 +1,7 @@
 //! Signed 128-bit integer.
 
 use std::cmp::Ordering;
-use std::fmt::{self, Write};
+use std::fmt;
 use std::iter::{Product, Sum};
 use std::num::ParseIntError;
 use std::ops::*;
@@ -9,12 +9,16 @@ use std::str::FromStr;
 
 #[cfg(feature="rand")] use rand::Rng;
 #[cfg(feature="rand")] use rand::distributions::{Standard, Distribution};
+#[cfg(feature="rand")] use rand::distributions::uniform::{SampleUniform, UniformSampler, SampleBorrow};
 use num_traits::*;
 
-use error;
-use format_buffer::FormatBuffer;
-use traits::{ToExtraPrimitive, Wrapping};
-use u128::u128;
+use compiler_rt::udivmod128;
+use error::{self, ParseScientificError};
+use format_buffer::format_decimal;
+use traits::{ToExtraPrimitive, FromExtraPrimitive, Wrapping, Round};
+use u128::{u128, parse_digits, parse_decimal_digits, checked_pow10};
+#[cfg(extprim_has_const_fn)] use u128::parse_digits_const;
+#[cfg(feature="rand")] use u128::UniformU128;
 #[cfg(extprim_has_stable_i128)] use compiler_rt::builtins::{U128, I128};
 
 //{{{ Structure
@@ -37,8 +41,17 @@ pub const ZERO: i128 = i128(::u128::ZERO);
 /// The constant 1.
 pub const ONE: i128 = i128(::u128::ONE);
 
+/// The maximum length of a signed 128-bit number formatted in decimal without padding (40, the
+/// sign and the digits of [`MIN`](constant.MIN.html)).
+pub const MAX_STR_LEN: usize = 40;
+
+/// The maximum precision (number of decimal digits) of an Arrow or Parquet `DECIMAL128` value.
+pub const DECIMAL128_MAX_PRECISION: u32 = 38;
+
 /// An signed 128-bit number.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+///
+/// With the `serde` feature, it is serialized as a decimal string in human-readable formats, and
+/// as 16 bytes in little endian (two's complement) in binary formats.
 #[derive(Default, Copy, Clone, Hash, PartialEq, Eq)]
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -49,27 +62,27 @@ pub struct i128(
 
 impl i128 {
     /// Constructs a new 128-bit integer from a 64-bit integer.
-    #[cfg(extprim_channel="stable")]
+    #[cfg(not(any(extprim_channel="unstable", extprim_has_const_fn)))]
     pub fn new(lo: i64) -> i128 {
         i128(u128 { lo: lo as u64, hi: (lo >> 63) as u64 })
     }
 
     /// Constructs a new 128-bit integer from a 64-bit integer.
-    #[cfg(extprim_channel="unstable")]
+    #[cfg(any(extprim_channel="unstable", extprim_has_const_fn))]
     pub const fn new(lo: i64) -> i128 {
         i128(u128 { lo: lo as u64, hi: (lo >> 63) as u64 })
     }
 
     /// Constructs a new 128-bit integer from the built-in 128-bit integer.
     #[cfg(extprim_has_stable_i128)]
-    #[cfg(extprim_channel="stable")]
+    #[cfg(not(any(extprim_channel="unstable", extprim_has_const_fn)))]
     pub fn from_built_in(value: I128) -> i128 {
         i128(u128::from_built_in(value as U128))
     }
 
     /// Constructs a new 128-bit integer from the built-in 128-bit integer.
     #[cfg(extprim_has_stable_i128)]
-    #[cfg(extprim_channel="unstable")]
+    #[cfg(any(extprim_channel="unstable", extprim_has_const_fn))]
     pub const fn from_built_in(value: I128) -> i128 {
         i128(u128::from_built_in(value as U128))
     }
@@ -84,7 +97,7 @@ impl i128 {
     /// assert_eq!(format!("{}", number), "-123456789012345678901234567890");
     /// // Note: -123456789012345678901234567890 = -6692605943 << 64 | 4362896299872285998
     /// ```
-    #[cfg(extprim_channel="stable")]
+    #[cfg(not(any(extprim_channel="unstable", extprim_has_const_fn)))]
     pub fn from_parts(hi: i64, lo: u64) -> i128 {
         i128(u128 { lo: lo, hi: hi as u64 })
     }
@@ -99,7 +112,7 @@ impl i128 {
     /// assert_eq!(format!("{}", number), "-123456789012345678901234567890");
     /// // Note: -123456789012345678901234567890 = -6692605943 << 64 | 4362896299872285998
     /// ```
-    #[cfg(extprim_channel="unstable")]
+    #[cfg(any(extprim_channel="unstable", extprim_has_const_fn))]
     pub const fn from_parts(hi: i64, lo: u64) -> i128 {
         i128(u128 { lo: lo, hi: hi as u64 })
     }
@@ -132,6 +145,48 @@ impl i128 {
         self.0.hi as i64
     }
 
+    /// Exchanges the higher-64-bit and lower-64-bit of the number, treating both as bit patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let number = i128::from_parts(-1, 2);
+    /// assert_eq!(number.swap_halves(), i128::from_parts(2, !0));
+    /// ```
+    pub fn swap_halves(self) -> i128 {
+        i128(self.0.swap_halves())
+    }
+
+    /// Returns the number with the lower-64-bit replaced by `lo`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let number = i128::from_parts(-1, 2);
+    /// assert_eq!(number.with_low64(3), i128::from_parts(-1, 3));
+    /// ```
+    pub fn with_low64(self, lo: u64) -> i128 {
+        i128(self.0.with_low64(lo))
+    }
+
+    /// Returns the number with the higher-64-bit replaced by `hi`, which also determines the sign.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let number = i128::from_parts(-1, 2);
+    /// assert_eq!(number.with_high64(3), i128::from_parts(3, 2));
+    /// ```
+    pub fn with_high64(self, hi: i64) -> i128 {
+        i128(self.0.with_high64(hi as u64))
+    }
+
     /// Convert this number to unsigned with wrapping.
     ///
     /// # Examples
@@ -167,6 +222,16 @@ mod structure_tests {
         assert_eq!(i128::from_parts(-1, !65), i128::new(-66));
         assert_eq!(i128::from_parts(-1, 0x8000000000000000), i128::new(i64::MIN));
     }
+
+    #[test]
+    fn test_halves() {
+        let a = i128::from_parts(i64::MIN, 5);
+        assert_eq!(a.swap_halves(), i128::from_parts(5, 0x8000000000000000));
+        assert_eq!(a.swap_halves().swap_halves(), a);
+        assert_eq!(a.with_low64(!0), i128::from_parts(i64::MIN, !0));
+        assert_eq!(a.with_high64(0), i128::new(5));
+        assert_eq!(i128::new(-1).with_high64(0).with_low64(7), i128::new(7));
+    }
 }
 
 //}}}
@@ -180,6 +245,114 @@ impl Distribution<i128> for Standard {
     }
 }
 
+/// The back-end of `rand::distributions::Uniform<i128>`, sampling uniformly from a range.
+///
+/// The range is mapped into the unsigned space by flipping the sign bit, which preserves the order,
+/// and sampled there. This type should not be used directly, use `Uniform` or `Rng::gen_range`
+/// instead.
+#[cfg(feature="rand")]
+#[derive(Clone, Copy, Debug)]
+pub struct UniformI128(UniformU128);
+
+#[cfg(feature="rand")]
+impl SampleUniform for i128 {
+    type Sampler = UniformI128;
+}
+
+#[cfg(feature="rand")]
+impl UniformSampler for UniformI128 {
+    type X = i128;
+
+    fn new<B1, B2>(low: B1, high: B2) -> UniformI128
+        where B1: SampleBorrow<i128> + Sized,
+              B2: SampleBorrow<i128> + Sized
+    {
+        let low = low.borrow().to_ordered_u128();
+        let high = high.borrow().to_ordered_u128();
+        UniformI128(UniformU128::new(low, high))
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> UniformI128
+        where B1: SampleBorrow<i128> + Sized,
+              B2: SampleBorrow<i128> + Sized
+    {
+        let low = low.borrow().to_ordered_u128();
+        let high = high.borrow().to_ordered_u128();
+        UniformI128(UniformU128::new_inclusive(low, high))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i128 {
+        self.0.sample(rng).to_ordered_i128()
+    }
+}
+
+#[cfg(all(test, feature="rand"))]
+mod rand_tests {
+    use rand::Rng;
+    use rand::distributions::{Distribution, Standard, Uniform};
+    use i128::{i128, MIN, MAX, ZERO, ONE};
+    use u128::u128;
+    use rng::Mcg128;
+
+    #[test]
+    fn test_standard() {
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+        let mut negative = 0;
+        for _ in 0..1000 {
+            let x: i128 = Standard.sample(&mut rng);
+            if x.is_negative() {
+                negative += 1;
+            }
+        }
+        assert!(negative > 400 && negative < 600);
+    }
+
+    #[test]
+    fn test_uniform() {
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+
+        let full = Uniform::new_inclusive(MIN, MAX);
+        let x = full.sample(&mut rng);
+        assert!(x != full.sample(&mut rng));
+
+        for _ in 0..10 {
+            assert_eq!(rng.gen_range(MIN, MIN + ONE), MIN);
+            assert_eq!(rng.gen_range(MAX - ONE, MAX), MAX - ONE);
+            assert_eq!(Uniform::new_inclusive(MAX, MAX).sample(&mut rng), MAX);
+            assert_eq!(Uniform::new_inclusive(MIN, MIN).sample(&mut rng), MIN);
+        }
+
+        let dist = Uniform::new_inclusive(-ONE, ONE);
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let x = dist.sample(&mut rng);
+            assert!(x >= -ONE && x <= ONE);
+            seen[(x + ONE).low64() as usize] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+
+        let negative = Uniform::new(MIN, ZERO);
+        let positive = Uniform::new_inclusive(ZERO, MAX);
+        for _ in 0..100 {
+            assert!(negative.sample(&mut rng).is_negative());
+            assert!(!positive.sample(&mut rng).is_negative());
+        }
+
+        let low = i128::from_parts(-1, 0) - i128::new(2);
+        let dist = Uniform::new(low, low + i128::new(4));
+        for _ in 0..100 {
+            let x = dist.sample(&mut rng);
+            assert!(x >= low && x < low + i128::new(4));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="Uniform::new_inclusive called with `low > high`")]
+    fn test_uniform_reversed() {
+        Uniform::new_inclusive(ONE, -ONE);
+    }
+}
+
 //}}}
 
 //{{{ Add, Sub
@@ -396,7 +569,11 @@ forward_assign!(SubAssign(sub_assign, sub) for i128);
 impl Neg for i128 {
     type Output = Self;
     fn neg(self) -> Self {
-        self.checked_neg().unwrap_or_else(|| panic!("arithmetic operation overflowed"))
+        self.checked_neg().unwrap_or_else(|| {
+            #[cfg(feature="overflow-hooks")]
+            ::overflow::record(::overflow::Operation::Neg, "i128");
+            panic!("arithmetic operation overflowed")
+        })
     }
 }
 
@@ -425,7 +602,19 @@ impl Saturating for i128 {
     }
 
     fn saturating_sub(self, other: Self) -> Self {
-        Self::saturating_add(self, other)
+        Self::saturating_sub(self, other)
+    }
+}
+
+impl SaturatingAdd for i128 {
+    fn saturating_add(&self, other: &Self) -> Self {
+        Self::saturating_add(*self, *other)
+    }
+}
+
+impl SaturatingSub for i128 {
+    fn saturating_sub(&self, other: &Self) -> Self {
+        Self::saturating_sub(*self, *other)
     }
 }
 
@@ -497,6 +686,22 @@ mod add_sub_tests {
         assert_eq!(MIN.saturating_neg(), MAX);
         assert_eq!(MIN.checked_neg(), None);
     }
+
+    #[test]
+    fn test_saturating_traits() {
+        use num_traits::{Saturating, SaturatingAdd, SaturatingSub, SaturatingMul};
+
+        fn saturate<T: SaturatingAdd + SaturatingSub + SaturatingMul>(a: T, b: T) -> (T, T, T) {
+            (a.saturating_add(&b), a.saturating_sub(&b), a.saturating_mul(&b))
+        }
+
+        assert_eq!(saturate(MAX, ONE), (MAX, MAX - ONE, MAX));
+        assert_eq!(saturate(MIN, ONE), (MIN + ONE, MIN, MIN));
+        assert_eq!(saturate(MIN, -ONE), (MIN, MIN + ONE, MAX));
+        assert_eq!(saturate(i128::new(-6), i128::new(4)), (i128::new(-2), i128::new(-10), i128::new(-24)));
+        assert_eq!(Saturating::saturating_sub(MIN, ONE), MIN);
+        assert_eq!(Saturating::saturating_sub(ONE, MIN), MAX);
+    }
 }
 
 //}}}
@@ -515,12 +720,86 @@ impl Ord for i128 {
     }
 }
 
+impl i128 {
+    const_fn! {
+        /// Returns the smaller of `self` and `other`.
+        ///
+        /// Unlike `Ord::min`, this is a `const fn` on compilers that support it.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use extprim::i128::i128;
+        ///
+        /// assert_eq!(i128::new(-3).min(i128::new(2)), i128::new(-3));
+        /// ```
+        pub fn min(self, other: i128) -> i128 {
+            let (a, b) = ((self.0).hi as i64, (other.0).hi as i64);
+            if a < b || a == b && (self.0).lo <= (other.0).lo {
+                self
+            } else {
+                other
+            }
+        }
+    }
+
+    const_fn! {
+        /// Returns the larger of `self` and `other`.
+        ///
+        /// Unlike `Ord::max`, this is a `const fn` on compilers that support it.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use extprim::i128::i128;
+        ///
+        /// assert_eq!(i128::new(-3).max(i128::new(2)), i128::new(2));
+        /// ```
+        pub fn max(self, other: i128) -> i128 {
+            let (a, b) = ((self.0).hi as i64, (other.0).hi as i64);
+            if a > b || a == b && (self.0).lo >= (other.0).lo {
+                self
+            } else {
+                other
+            }
+        }
+    }
+
+    const_fn! {
+        /// Restricts `self` to the range `min ..= max`.
+        ///
+        /// Unlike `Ord::clamp`, this is a `const fn` on compilers that support it.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `min` is greater than `max`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use extprim::i128::i128;
+        ///
+        /// let (lo, hi) = (i128::new(-10), i128::new(10));
+        /// assert_eq!(i128::min_value().clamp(lo, hi), lo);
+        /// assert_eq!(i128::new(7).clamp(lo, hi), i128::new(7));
+        /// assert_eq!(i128::new(70).clamp(lo, hi), hi);
+        /// ```
+        pub fn clamp(self, min: i128, max: i128) -> i128 {
+            let (a, b) = ((min.0).hi as i64, (max.0).hi as i64);
+            if a > b || a == b && (min.0).lo > (max.0).lo {
+                panic!("clamp: min must not be greater than max");
+            }
+            self.max(min).min(max)
+        }
+    }
+}
+
 #[cfg(test)]
 mod cmp_tests {
     use i128::{i128, MIN, MAX};
     use u128::u128;
 
-    const TEST_CASES: &'static [i128; 7] = &[
+    const TEST_CASES: &[i128; 7] = &[
         MIN,
         i128(u128 { lo: 0, hi: !0 }),
         i128(u128 { lo: !0, hi: !0 }),
@@ -538,6 +817,35 @@ mod cmp_tests {
             }
         }
     }
+
+    #[test]
+    fn test_min_max_clamp() {
+        for &a in TEST_CASES.iter() {
+            for &b in TEST_CASES.iter() {
+                assert_eq!(a.min(b), ::std::cmp::min(a, b));
+                assert_eq!(a.max(b), ::std::cmp::max(a, b));
+                if a <= b {
+                    for &x in TEST_CASES.iter() {
+                        assert_eq!(x.clamp(a, b), ::std::cmp::max(a, ::std::cmp::min(x, b)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="clamp: min must not be greater than max")]
+    fn test_clamp_invalid_range() {
+        MIN.clamp(MAX, MIN);
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    fn test_const_min_max() {
+        const CLAMPED: i128 = MIN.clamp(i128(u128 { lo: !0, hi: !0 }), MAX);
+        assert_eq!(CLAMPED, i128::new(-1));
+        assert_eq!(MAX.min(MIN), MIN);
+    }
 }
 
 //}}}
@@ -728,6 +1036,57 @@ impl i128 {
     pub fn overflowing_shr(self, other: u32) -> (i128, bool) {
         (self.wrapping_shr(other), other >= 128)
     }
+
+    /// Saturating shift left. Computes `self * 2**other`, saturating at the numeric bounds instead
+    /// of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(1).saturating_shl(126), i128::from_parts(0x4000000000000000, 0));
+    /// assert_eq!(i128::new(1).saturating_shl(127), i128::max_value());
+    /// assert_eq!(i128::new(-1).saturating_shl(127), i128::min_value());
+    /// assert_eq!(i128::new(-3).saturating_shl(127), i128::min_value());
+    /// ```
+    pub fn saturating_shl(self, other: u32) -> i128 {
+        let redundant_sign_bits = if self.is_negative() {
+            (!self).leading_zeros()
+        } else {
+            self.leading_zeros()
+        };
+        if self == ZERO || other < redundant_sign_bits {
+            self.wrapping_shl(other)
+        } else if self.is_negative() {
+            MIN
+        } else {
+            MAX
+        }
+    }
+
+    /// Saturating shift right. Computes `self / 2**other` rounded towards negative infinity,
+    /// giving 0 or -1 instead of overflowing when the shift is larger than or equal to the number
+    /// of bits (128).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-50).saturating_shr(2), i128::new(-13));
+    /// assert_eq!(i128::new(-50).saturating_shr(200), i128::new(-1));
+    /// assert_eq!(i128::new(50).saturating_shr(200), i128::zero());
+    /// ```
+    pub fn saturating_shr(self, other: u32) -> i128 {
+        if other < 128 {
+            self.wrapping_shr(other)
+        } else if self.is_negative() {
+            -ONE
+        } else {
+            ZERO
+        }
+    }
 }
 
 forward_shift!(
@@ -862,6 +1221,15 @@ fn from_sign_abs(sign: bool, abs: u128) -> i128 {
     i128(if sign { abs.wrapping_neg() } else { abs })
 }
 
+/// Converts a 64-bit signed integer into the sign and the absolute value, like `sign_abs`.
+fn sign_abs_64(x: i64) -> (bool, u64) {
+    if x < 0 {
+        (true, (x as u64).wrapping_neg())
+    } else {
+        (false, x as u64)
+    }
+}
+
 impl i128 {
     /// Calculates the multiplication of `self` and `other`.
     ///
@@ -930,6 +1298,70 @@ impl i128 {
             if self.is_negative() == other.is_negative() { MAX } else { MIN }
         })
     }
+
+    /// Wrapping (modular) multiplication with a 64-bit number. Computes `self * other`, wrapping
+    /// around at the boundary of the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-6).wrapping_mul_i64(11), i128::new(-66));
+    /// assert_eq!(i128::max_value().wrapping_mul_i64(-2), i128::new(2));
+    /// ```
+    pub fn wrapping_mul_i64(self, other: i64) -> i128 {
+        let mut res = self.0.wrapping_mul_64(other as u64);
+        if other < 0 {
+            // `other` is sign-extended, i.e. the product also contains `-self << 64`.
+            res.hi = res.hi.wrapping_sub(self.0.lo);
+        }
+        i128(res)
+    }
+
+    /// Calculates the multiplication of `self` and `other` with a 64-bit number.
+    ///
+    /// Returns a tuple of the multiplication along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-6).overflowing_mul_i64(11), (i128::new(-66), false));
+    /// assert_eq!(i128::max_value().overflowing_mul_i64(-2), (i128::new(2), true));
+    /// ```
+    pub fn overflowing_mul_i64(self, other: i64) -> (i128, bool) {
+        if self == ZERO || other == 0 {
+            return (ZERO, false);
+        }
+
+        let (sa, a) = sign_abs(self);
+        let (sb, b) = sign_abs_64(other);
+        let res_is_neg = sa != sb;
+
+        let (res, res_overflow) = a.overflowing_mul_64(b);
+        let res = from_sign_abs(res_is_neg, res);
+        (res, res_overflow || res.is_negative() != res_is_neg)
+    }
+
+    /// Saturating integer multiplication with a 64-bit number. Computes `self * other`, saturating
+    /// at the numeric bounds instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-6).saturating_mul_i64(11), i128::new(-66));
+    /// assert_eq!(i128::max_value().saturating_mul_i64(-2), i128::min_value());
+    /// ```
+    pub fn saturating_mul_i64(self, other: i64) -> i128 {
+        self.checked_mul_i64(other).unwrap_or_else(|| {
+            if self.is_negative() == (other < 0) { MAX } else { MIN }
+        })
+    }
 }
 
 forward_symmetric! {
@@ -949,7 +1381,38 @@ forward_symmetric! {
     /// ```
     impl Mul(mul, checked_mul, wrapping_mul, overflowing_mul) for i128
 }
+forward_symmetric! {
+    /// Checked integer multiplication with a 64-bit number. Computes `self * other`, returning
+    /// `None` if underflow or overflow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-6).checked_mul_i64(11), Some(i128::new(-66)));
+    /// assert_eq!(i128::max_value().checked_mul_i64(-2), None);
+    /// ```
+    impl Mul<i64>(mul, checked_mul_i64, wrapping_mul_i64, overflowing_mul_i64) for i128
+}
 forward_assign!(MulAssign(mul_assign, mul) for i128);
+forward_assign!(MulAssign<i64>(mul_assign, mul) for i128);
+
+impl Mul<i128> for i64 {
+    type Output = i128;
+
+    fn mul(self, other: i128) -> i128 {
+        other * self
+    }
+}
+
+impl Mul<Wrapping<i128>> for Wrapping<i64> {
+    type Output = Wrapping<i128>;
+
+    fn mul(self, other: Wrapping<i128>) -> Wrapping<i128> {
+        other * self
+    }
+}
 
 impl CheckedMul for i128 {
     fn checked_mul(&self, other: &Self) -> Option<Self> {
@@ -957,9 +1420,15 @@ impl CheckedMul for i128 {
     }
 }
 
+impl SaturatingMul for i128 {
+    fn saturating_mul(&self, other: &Self) -> Self {
+        Self::saturating_mul(*self, *other)
+    }
+}
+
 #[cfg(test)]
 mod mul_tests {
-    use i128::{i128, ONE, MAX, MIN};
+    use i128::{i128, ZERO, ONE, MAX, MIN};
 
     #[test]
     fn test_mul() {
@@ -995,6 +1464,27 @@ mod mul_tests {
         assert_eq!(MAX.checked_mul(i128::new(2)), None);
         assert_eq!(MAX.saturating_mul(i128::new(2)), MAX);
     }
+
+    #[test]
+    fn test_mul_i64() {
+        let values = [ZERO, ONE, -ONE, MIN, MAX, i128::from_parts(1, 1), i128::from_parts(-2, 7),
+                      i128::from_parts(0x3fff_ffff_ffff_ffff, !0), i128::new(0x1234_5678_9abc)];
+        let factors = [0, 1, -1, 2, -2, 0x7fff_ffff_ffff_ffff, -0x8000_0000_0000_0000, -12345];
+        for &a in values.iter() {
+            for &b in factors.iter() {
+                let wide = i128::new(b);
+                assert_eq!(a.wrapping_mul_i64(b), a.wrapping_mul(wide));
+                assert_eq!(a.overflowing_mul_i64(b), a.overflowing_mul(wide));
+                assert_eq!(a.checked_mul_i64(b), a.checked_mul(wide));
+                assert_eq!(a.saturating_mul_i64(b), a.saturating_mul(wide));
+            }
+        }
+
+        let mut x = i128::new(-3);
+        x *= 7i64;
+        assert_eq!(x, i128::new(-21));
+        assert_eq!(-2i64 * x, i128::new(42));
+    }
 }
 
 //}}}
@@ -1142,37 +1632,249 @@ impl i128 {
             Some(self.wrapping_rem(other))
         }
     }
-}
-
-impl Div for i128 {
-    type Output = Self;
-    fn div(self, other: Self) -> Self {
-        self.wrapping_div(other)
-    }
-}
 
-impl Rem for i128 {
-    type Output = Self;
-    fn rem(self, other: Self) -> Self {
-        self.wrapping_rem(other)
+    /// Computes `self / other`, rounding the quotient towards negative infinity.
+    ///
+    /// Unlike the `/` operator which rounds towards zero, the result is one less when the
+    /// operands have different signs and the division is not exact.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0. If debug assertions is enabled, this function
+    /// will also panic on overflow (when computing `MIN.div_floor(-1)`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(7).div_floor(i128::new(2)), i128::new(3));
+    /// assert_eq!(i128::new(-7).div_floor(i128::new(2)), i128::new(-4));
+    /// assert_eq!(i128::new(7).div_floor(i128::new(-2)), i128::new(-4));
+    /// assert_eq!(i128::new(-7).div_floor(i128::new(-2)), i128::new(3));
+    /// assert_eq!(i128::new(-8).div_floor(i128::new(2)), i128::new(-4));
+    /// ```
+    pub fn div_floor(self, other: i128) -> i128 {
+        let (div, rem) = div_rem(self, other);
+        if rem != ZERO && rem.is_negative() != other.is_negative() {
+            div - ONE
+        } else {
+            div
+        }
     }
-}
 
-impl Div for Wrapping<i128> {
-    type Output = Self;
-    fn div(self, other: Self) -> Self {
-        Wrapping(self.0.wrapping_div(other.0))
+    /// Computes `self / other`, rounding the quotient towards positive infinity.
+    ///
+    /// Unlike the `/` operator which rounds towards zero, the result is one more when the
+    /// operands have the same sign and the division is not exact.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0. If debug assertions is enabled, this function
+    /// will also panic on overflow (when computing `MIN.div_ceil(-1)`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(7).div_ceil(i128::new(2)), i128::new(4));
+    /// assert_eq!(i128::new(-7).div_ceil(i128::new(2)), i128::new(-3));
+    /// assert_eq!(i128::new(7).div_ceil(i128::new(-2)), i128::new(-3));
+    /// assert_eq!(i128::new(-7).div_ceil(i128::new(-2)), i128::new(4));
+    /// assert_eq!(i128::new(-8).div_ceil(i128::new(2)), i128::new(-4));
+    /// ```
+    pub fn div_ceil(self, other: i128) -> i128 {
+        let (div, rem) = div_rem(self, other);
+        if rem != ZERO && rem.is_negative() == other.is_negative() {
+            div + ONE
+        } else {
+            div
+        }
     }
-}
 
-impl Rem for Wrapping<i128> {
-    type Output = Self;
+    /// Checked version of [`div_floor`](#method.div_floor). Returns `None` if `other == 0` or the
+    /// division overflows (when computing `MIN / -1`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-7).checked_div_floor(i128::new(2)), Some(i128::new(-4)));
+    /// assert_eq!(i128::min_value().checked_div_floor(i128::new(-1)), None);
+    /// assert_eq!(i128::new(3).checked_div_floor(i128::zero()), None);
+    /// ```
+    pub fn checked_div_floor(self, other: i128) -> Option<i128> {
+        if other == ZERO || self == MIN && other == -ONE {
+            None
+        } else {
+            Some(self.div_floor(other))
+        }
+    }
+
+    /// Checked version of [`div_ceil`](#method.div_ceil). Returns `None` if `other == 0` or the
+    /// division overflows (when computing `MIN / -1`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-7).checked_div_ceil(i128::new(-2)), Some(i128::new(4)));
+    /// assert_eq!(i128::min_value().checked_div_ceil(i128::new(-1)), None);
+    /// assert_eq!(i128::new(3).checked_div_ceil(i128::zero()), None);
+    /// ```
+    pub fn checked_div_ceil(self, other: i128) -> Option<i128> {
+        if other == ZERO || self == MIN && other == -ONE {
+            None
+        } else {
+            Some(self.div_ceil(other))
+        }
+    }
+
+    /// Computes `self / other`, where the divisor is a 64-bit number. Like the `/` operator, the
+    /// quotient is rounded towards zero, and `MIN / -1` wraps around to `MIN`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-100).div_i64(8), i128::new(-12));
+    /// assert_eq!(i128::from_parts(-1, 0) / -2i64, i128::from_parts(0, 1 << 63));
+    /// ```
+    pub fn div_i64(self, other: i64) -> i128 {
+        if other == 0 {
+            panic!("attempted to divide by zero");
+        }
+        let (sa, a) = sign_abs(self);
+        let (sb, b) = sign_abs_64(other);
+        from_sign_abs(sa != sb, udivmod_abs_64(a, b).0)
+    }
+
+    /// Computes `self % other`, where the divisor is a 64-bit number. The remainder has the same
+    /// sign as `self`, and since its magnitude is less than the divisor, it is returned as an
+    /// `i64`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-100).rem_i64(8), -4);
+    /// let r: i64 = i128::min_value() % 1000i64;
+    /// assert_eq!(r, -728);
+    /// ```
+    pub fn rem_i64(self, other: i64) -> i64 {
+        if other == 0 {
+            panic!("attempted remainder with a divisor of zero");
+        }
+        let (sa, a) = sign_abs(self);
+        let (_, b) = sign_abs_64(other);
+        let r = udivmod_abs_64(a, b).1;
+        (if sa { r.wrapping_neg() } else { r }) as i64
+    }
+
+    /// Checked integer division with a 64-bit number. Computes `self / other`, returning `None`
+    /// if `other == 0` or the operation results in underflow or overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-100).checked_div_i64(8), Some(i128::new(-12)));
+    /// assert_eq!(i128::min_value().checked_div_i64(-1), None);
+    /// assert_eq!(i128::new(3).checked_div_i64(0), None);
+    /// ```
+    pub fn checked_div_i64(self, other: i64) -> Option<i128> {
+        if other == 0 || self == MIN && other == -1 {
+            None
+        } else {
+            Some(self.div_i64(other))
+        }
+    }
+
+    /// Checked integer remainder with a 64-bit number. Computes `self % other`, returning `None`
+    /// if `other == 0` or the operation results in underflow or overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-100).checked_rem_i64(8), Some(-4));
+    /// assert_eq!(i128::min_value().checked_rem_i64(-1), None);
+    /// assert_eq!(i128::new(3).checked_rem_i64(0), None);
+    /// ```
+    pub fn checked_rem_i64(self, other: i64) -> Option<i64> {
+        if other == 0 || self == MIN && other == -1 {
+            None
+        } else {
+            Some(self.rem_i64(other))
+        }
+    }
+}
+
+/// Divides an absolute value by a non-zero 64-bit divisor, returning the quotient and remainder.
+fn udivmod_abs_64(a: u128, b: u64) -> (u128, u64) {
+    // Reducing the high word first makes the quotient of the remaining division fit in 64 bits.
+    let (q, r) = udivmod128(u128 { lo: a.lo, hi: a.hi % b }, u128::new(b));
+    (u128 { lo: q.lo, hi: a.hi / b }, r.lo)
+}
+
+impl Div for i128 {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        self.wrapping_div(other)
+    }
+}
+
+impl Rem for i128 {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        self.wrapping_rem(other)
+    }
+}
+
+impl Div for Wrapping<i128> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Wrapping(self.0.wrapping_div(other.0))
+    }
+}
+
+impl Rem for Wrapping<i128> {
+    type Output = Self;
     fn rem(self, other: Self) -> Self {
         Wrapping(self.0.wrapping_rem(other.0))
     }
 }
 
-forward_assign!(DivAssign(div_assign, div) for i128);
+impl Div<i64> for i128 {
+    type Output = Self;
+    fn div(self, other: i64) -> Self {
+        self.div_i64(other)
+    }
+}
+
+impl Rem<i64> for i128 {
+    type Output = i64;
+    fn rem(self, other: i64) -> i64 {
+        self.rem_i64(other)
+    }
+}
+
+forward_assign!(DivAssign<i128|i64>(div_assign, div) for i128);
 forward_assign!(RemAssign(rem_assign, rem) for i128);
 
 impl CheckedDiv for i128 {
@@ -1208,9 +1910,112 @@ pub fn div_rem(numerator: i128, denominator: i128) -> (i128, i128) {
     (from_sign_abs(sn != sd, div), from_sign_abs(sn, rem))
 }
 
+/// Computes the Euclidean divisor and remainder simultaneously.
+///
+/// The quotient is rounded such that the remainder is always non-negative, i.e. `0 <= r < |b|`
+/// and `a == q * b + r`. This is equivalent to `(a.div_euclid(b), a.rem_euclid(b))` on the
+/// primitive types.
+///
+/// # Panics
+///
+/// This function will panic if `denominator` is 0. If debug assertions is enabled, this function
+/// will also panic on overflow (when computing `div_rem_euclid(MIN, -1)`).
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::i128::{div_rem_euclid, i128};
+///
+/// assert_eq!(div_rem_euclid(i128::new(100), i128::new(-8)), (i128::new(-12), i128::new(4)));
+/// assert_eq!(div_rem_euclid(i128::new(-100), i128::new(8)), (i128::new(-13), i128::new(4)));
+/// assert_eq!(div_rem_euclid(i128::new(-100), i128::new(-8)), (i128::new(13), i128::new(4)));
+/// ```
+pub fn div_rem_euclid(numerator: i128, denominator: i128) -> (i128, i128) {
+    let (div, rem) = div_rem(numerator, denominator);
+    if rem.is_negative() {
+        if denominator.is_negative() {
+            (div.wrapping_add(ONE), rem.wrapping_sub(denominator))
+        } else {
+            (div.wrapping_sub(ONE), rem.wrapping_add(denominator))
+        }
+    } else {
+        (div, rem)
+    }
+}
+
+/// Checked version of [`div_rem`](fn.div_rem.html). Returns `None` if `denominator` is 0 or the
+/// division overflows (when computing `MIN / -1`).
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::i128::{checked_div_rem, i128};
+///
+/// assert_eq!(checked_div_rem(i128::new(-100), i128::new(8)), Some((i128::new(-12), i128::new(-4))));
+/// assert_eq!(checked_div_rem(i128::min_value(), i128::new(-1)), None);
+/// assert_eq!(checked_div_rem(i128::new(3), i128::zero()), None);
+/// ```
+pub fn checked_div_rem(numerator: i128, denominator: i128) -> Option<(i128, i128)> {
+    if denominator == ZERO || numerator == MIN && denominator == -ONE {
+        None
+    } else {
+        Some(div_rem(numerator, denominator))
+    }
+}
+
+/// Checked version of [`div_rem_euclid`](fn.div_rem_euclid.html). Returns `None` if `denominator`
+/// is 0 or the division overflows (when computing `MIN / -1`).
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::i128::{checked_div_rem_euclid, i128};
+///
+/// assert_eq!(checked_div_rem_euclid(i128::new(-100), i128::new(8)), Some((i128::new(-13), i128::new(4))));
+/// assert_eq!(checked_div_rem_euclid(i128::min_value(), i128::new(-1)), None);
+/// assert_eq!(checked_div_rem_euclid(i128::new(3), i128::zero()), None);
+/// ```
+pub fn checked_div_rem_euclid(numerator: i128, denominator: i128) -> Option<(i128, i128)> {
+    if denominator == ZERO || numerator == MIN && denominator == -ONE {
+        None
+    } else {
+        Some(div_rem_euclid(numerator, denominator))
+    }
+}
+
 #[cfg(test)]
 mod div_rem_tests {
-    use i128::{i128, ONE, div_rem};
+    use i128::{i128, ONE, ZERO, MIN, MAX, div_rem, div_rem_euclid, checked_div_rem, checked_div_rem_euclid};
+
+    #[test]
+    fn test_div_rem_i64() {
+        let values = [ZERO, ONE, -ONE, MIN, MAX, i128::from_parts(1, 1), i128::from_parts(-2, 7),
+                      i128::from_parts(0x3fff_ffff_ffff_ffff, !0), i128::new(-0x1234_5678_9abc)];
+        let divisors = [1, -1, 2, -3, 1000, 0x7fff_ffff_ffff_ffff, -0x8000_0000_0000_0000];
+        for &a in values.iter() {
+            for &b in divisors.iter() {
+                let (q, r) = checked_div_rem(a, i128::new(b)).unwrap_or((MIN, ZERO));
+                assert_eq!(a.div_i64(b), q);
+                assert_eq!(a / b, q);
+                assert_eq!(i128::new(a.rem_i64(b)), r);
+                assert_eq!(i128::new(a % b), r);
+                assert_eq!(a.checked_div_i64(b), a.checked_div(i128::new(b)));
+                assert_eq!(a.checked_rem_i64(b).map(i128::new), a.checked_rem(i128::new(b)));
+            }
+            assert_eq!(a.checked_div_i64(0), None);
+            assert_eq!(a.checked_rem_i64(0), None);
+        }
+
+        let mut x = i128::new(-100);
+        x /= 8i64;
+        assert_eq!(x, i128::new(-12));
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_div_i64_by_zero() {
+        ONE.div_i64(0);
+    }
 
     #[test]
     fn test_div() {
@@ -1255,6 +2060,75 @@ mod div_rem_tests {
         assert_eq!(div_rem(-nine, five), (-ONE, -four));
         assert_eq!(div_rem(-nine, -five), (ONE, -four));
     }
+
+    #[test]
+    fn test_div_rem_euclid() {
+        let nine = i128::new(9);
+        let five = i128::new(5);
+        let four = i128::new(4);
+        let two = i128::new(2);
+
+        assert_eq!(div_rem_euclid(nine, five), (ONE, four));
+        assert_eq!(div_rem_euclid(nine, -five), (-ONE, four));
+        assert_eq!(div_rem_euclid(-nine, five), (-two, ONE));
+        assert_eq!(div_rem_euclid(-nine, -five), (two, ONE));
+        assert_eq!(div_rem_euclid(-five, five), (-ONE, ZERO));
+        assert_eq!(div_rem_euclid(MIN, MAX), (-two, MAX - ONE));
+        assert_eq!(div_rem_euclid(MIN + ONE, MIN), (ONE, ONE));
+        assert_eq!(div_rem_euclid(MIN, MIN), (ONE, ZERO));
+        assert_eq!(div_rem_euclid(-ONE, MIN), (ONE, MAX));
+    }
+
+    #[test]
+    fn test_checked_div_rem() {
+        assert_eq!(checked_div_rem(MIN, -ONE), None);
+        assert_eq!(checked_div_rem(ONE, ZERO), None);
+        assert_eq!(checked_div_rem(MIN, ONE), Some((MIN, ZERO)));
+        assert_eq!(checked_div_rem_euclid(MIN, -ONE), None);
+        assert_eq!(checked_div_rem_euclid(ONE, ZERO), None);
+        assert_eq!(checked_div_rem_euclid(-ONE, i128::new(7)), Some((-ONE, i128::new(6))));
+    }
+
+    #[test]
+    fn test_div_floor_ceil() {
+        let seven = i128::new(7);
+        let two = i128::new(2);
+        let three = i128::new(3);
+        let four = i128::new(4);
+
+        assert_eq!(seven.div_floor(two), three);
+        assert_eq!(seven.div_floor(-two), -four);
+        assert_eq!((-seven).div_floor(two), -four);
+        assert_eq!((-seven).div_floor(-two), three);
+        assert_eq!(seven.div_ceil(two), four);
+        assert_eq!(seven.div_ceil(-two), -three);
+        assert_eq!((-seven).div_ceil(two), -three);
+        assert_eq!((-seven).div_ceil(-two), four);
+
+        assert_eq!(MIN.div_floor(two), MIN >> 1);
+        assert_eq!(MIN.div_ceil(two), MIN >> 1);
+        assert_eq!(MIN.div_floor(MAX), -two);
+        assert_eq!(MIN.div_ceil(MAX), -ONE);
+        assert_eq!(MAX.div_floor(MIN), -ONE);
+        assert_eq!(MAX.div_ceil(MIN), ZERO);
+        assert_eq!(MIN.div_floor(MIN), ONE);
+        assert_eq!((MIN + ONE).div_ceil(-ONE), MAX);
+        assert_eq!(ONE.div_floor(MIN), -ONE);
+        assert_eq!((-ONE).div_ceil(MIN), ONE);
+
+        assert_eq!(MIN.checked_div_floor(-ONE), None);
+        assert_eq!(MIN.checked_div_ceil(-ONE), None);
+        assert_eq!(ONE.checked_div_floor(ZERO), None);
+        assert_eq!(ONE.checked_div_ceil(ZERO), None);
+        assert_eq!(MAX.checked_div_ceil(two), Some((MAX >> 1) + ONE));
+    }
+
+    #[test]
+    #[should_panic(expected="arithmetic operation overflowed")]
+    #[cfg(debug_assertions)]
+    fn test_div_rem_euclid_overflow() {
+        div_rem_euclid(MIN, -ONE);
+    }
 }
 
 //}}}
@@ -1334,6 +2208,16 @@ impl ToExtraPrimitive for i128 {
     }
 }
 
+impl FromExtraPrimitive for i128 {
+    fn from_u128(n: u128) -> Option<i128> {
+        ToExtraPrimitive::to_i128(&n)
+    }
+
+    fn from_i128(n: i128) -> Option<i128> {
+        Some(n)
+    }
+}
+
 impl From<i8> for i128 {
     fn from(arg: i8) -> Self {
         i128::new(arg as i64)
@@ -1365,6 +2249,164 @@ impl From<I128> for i128 {
     }
 }
 
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl From<i128> for I128 {
+    fn from(arg: i128) -> Self {
+        arg.as_built_in()
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialEq<I128> for i128 {
+    fn eq(&self, other: &I128) -> bool {
+        self.as_built_in() == *other
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialEq<i128> for I128 {
+    fn eq(&self, other: &i128) -> bool {
+        *self == other.as_built_in()
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialOrd<I128> for i128 {
+    fn partial_cmp(&self, other: &I128) -> Option<Ordering> {
+        self.as_built_in().partial_cmp(other)
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialOrd<i128> for I128 {
+    fn partial_cmp(&self, other: &i128) -> Option<Ordering> {
+        self.partial_cmp(&other.as_built_in())
+    }
+}
+
+impl i128 {
+    /// Converts a 64-bit floating point number to a signed 128-bit integer, using the given
+    /// rounding mode.
+    ///
+    /// Returns `None` if the number is NaN, infinite, or if the rounded value is outside of the
+    /// range `-2**127 ..= 2**127 - 1`. `-0.0` is converted to 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    /// use extprim::traits::Round;
+    ///
+    /// assert_eq!(i128::from_f64_with(-2.5, Round::Floor), Some(i128::new(-3)));
+    /// assert_eq!(i128::from_f64_with(-2.5, Round::Ceil), Some(i128::new(-2)));
+    /// assert_eq!(i128::from_f64_with(-2.5, Round::Nearest), Some(i128::new(-3)));
+    /// assert_eq!(i128::from_f64_with(-2.5, Round::Trunc), Some(i128::new(-2)));
+    /// assert_eq!(i128::from_f64_with(-2.0f64.powi(127), Round::Floor), Some(i128::min_value()));
+    /// assert_eq!(i128::from_f64_with(2.0f64.powi(127), Round::Floor), None);
+    /// ```
+    pub fn from_f64_with(value: f64, round: Round) -> Option<i128> {
+        let is_negative = value.is_sign_negative();
+        let (abs, round) = if is_negative {
+            let reversed = match round {
+                Round::Floor => Round::Ceil,
+                Round::Ceil => Round::Floor,
+                r => r,
+            };
+            (-value, reversed)
+        } else {
+            (value, round)
+        };
+
+        let abs = u128::from_f64_with(abs, round)?;
+        let res = from_sign_abs(is_negative, abs);
+        if res != ZERO && res.is_negative() != is_negative {
+            None
+        } else {
+            Some(res)
+        }
+    }
+
+    /// Converts a 64-bit floating point number to a signed 128-bit integer, rounding towards zero
+    /// and saturating at the bounds.
+    ///
+    /// This matches the behavior of an `as` cast between primitive types: NaN is converted to 0,
+    /// and numbers outside of the range (including infinities) are converted to
+    /// `i128::min_value()` or `i128::max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::from_f64_saturating(-2.9), i128::new(-2));
+    /// assert_eq!(i128::from_f64_saturating(1e100), i128::max_value());
+    /// assert_eq!(i128::from_f64_saturating(-1e100), i128::min_value());
+    /// assert_eq!(i128::from_f64_saturating(0.0 / 0.0), i128::zero());
+    /// ```
+    pub fn from_f64_saturating(value: f64) -> i128 {
+        let saturated = if value > 0.0 {
+            MAX
+        } else if value < 0.0 {
+            MIN
+        } else {
+            ZERO
+        };
+        i128::from_f64_with(value, Round::Trunc).unwrap_or(saturated)
+    }
+
+    /// Converts a 32-bit floating point number to a signed 128-bit integer, rounding towards zero
+    /// and saturating at the bounds, like an `as` cast.
+    ///
+    /// See [`from_f64_saturating`](#method.from_f64_saturating) for details.
+    pub fn from_f32_saturating(value: f32) -> i128 {
+        i128::from_f64_saturating(value as f64)
+    }
+
+    /// Decomposes this integer into the 64-bit floating point number nearest to it, as a signed
+    /// mantissa and a binary exponent.
+    ///
+    /// Returns `(mantissa, exponent, exact)`, where `mantissa * 2**exponent` is `self` rounded to
+    /// 53 significant bits (ties to even), `|mantissa| < 2**53` and `exponent >= 0`. `exact` is
+    /// `true` if no bits were lost in the rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-12345).to_f64_parts(), (-12345, 0, true));
+    /// assert_eq!(i128::min_value().to_f64_parts(), (-1 << 52, 75, true));
+    /// assert_eq!(i128::max_value().to_f64_parts(), (1 << 52, 75, false));
+    /// ```
+    pub fn to_f64_parts(self) -> (i64, i32, bool) {
+        let (sign, abs) = sign_abs(self);
+        let (mantissa, exp, exact) = abs.to_f64_parts();
+        let mantissa = if sign { -(mantissa as i64) } else { mantissa as i64 };
+        (mantissa, exp, exact)
+    }
+
+    /// Converts this integer to a 64-bit floating point number, reporting whether any precision
+    /// was lost.
+    ///
+    /// Returns `Ok` if the value is exactly representable as an `f64`, otherwise returns `Err`
+    /// with the nearest `f64` (ties to even).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-1 << 53).to_f64_exact(), Ok(-9007199254740992.0));
+    /// assert_eq!(i128::new(-(1 << 53) - 1).to_f64_exact(), Err(-9007199254740992.0));
+    /// assert_eq!(i128::min_value().to_f64_exact(), Ok(-170141183460469231731687303715884105728.0));
+    /// ```
+    pub fn to_f64_exact(self) -> Result<f64, f64> {
+        let (sign, abs) = sign_abs(self);
+        let negate = |f: f64| if sign { -f } else { f };
+        abs.to_f64_exact().map(&negate).map_err(&negate)
+    }
+}
+
 #[cfg(test)]
 mod conv_tests {
     use i128::{i128, MIN, MAX};
@@ -1381,6 +2423,77 @@ mod conv_tests {
         assert_eq!(MIN.to_f64(), Some(-170141183460469231731687303715884105728.0f64));
     }
 
+    #[test]
+    fn test_to_f64_exact() {
+        assert_eq!(i128::new(0).to_f64_exact(), Ok(0.0));
+        assert_eq!(i128::new(-9007199254740993).to_f64_exact(), Err(-9007199254740992.0));
+        assert_eq!(i128::new(-9007199254740995).to_f64_exact(), Err(-9007199254740996.0));
+        assert_eq!(i128::new(9007199254740995).to_f64_exact(), Err(9007199254740996.0));
+        assert_eq!(i128::new(i64::min_value()).to_f64_exact(), Ok(-9223372036854775808.0));
+        assert_eq!(i128::new(-3).to_f64_parts(), (-3, 0, true));
+        assert_eq!(MAX.to_f64_exact(), Err(170141183460469231731687303715884105728.0));
+        assert_eq!(MIN.to_f64_exact(), Ok(-170141183460469231731687303715884105728.0));
+        assert_eq!((MIN + i128::one()).to_f64_parts(), (-1 << 52, 75, false));
+    }
+
+    #[test]
+    fn test_from_f64_with() {
+        use traits::Round;
+
+        const ALL: [Round; 4] = [Round::Floor, Round::Ceil, Round::Nearest, Round::Trunc];
+
+        for &round in &ALL {
+            assert_eq!(i128::from_f64_with(0.0, round), Some(i128::zero()));
+            assert_eq!(i128::from_f64_with(-0.0, round), Some(i128::zero()));
+            assert_eq!(i128::from_f64_with(-1.0, round), Some(-i128::one()));
+            assert_eq!(i128::from_f64_with(-170141183460469231731687303715884105728.0, round), Some(MIN));
+            assert_eq!(i128::from_f64_with(170141183460469231731687303715884105728.0, round), None);
+            assert_eq!(i128::from_f64_with(-170141183460469269510619166673045815296.0, round), None);
+            assert_eq!(i128::from_f64_with(170141183460469212842221372237303250944.0, round),
+                        Some(i128::from_parts(0x7ffffffffffffc00, 0)));
+            assert_eq!(i128::from_f64_with(-1e-300, round), Some(if round == Round::Floor { -i128::one() } else { i128::zero() }));
+            assert_eq!(i128::from_f64_with(f64::NAN, round), None);
+            assert_eq!(i128::from_f64_with(f64::NEG_INFINITY, round), None);
+        }
+
+        assert_eq!(i128::from_f64_with(-0.5, Round::Nearest), Some(-i128::one()));
+        assert_eq!(i128::from_f64_with(-0.49999999999999994, Round::Nearest), Some(i128::zero()));
+        assert_eq!(i128::from_f64_with(-1.5, Round::Floor), Some(i128::new(-2)));
+        assert_eq!(i128::from_f64_with(-1.5, Round::Ceil), Some(i128::new(-1)));
+        assert_eq!(i128::from_f64_with(-1.5, Round::Trunc), Some(i128::new(-1)));
+        assert_eq!(i128::from_f64_with(1.5, Round::Floor), Some(i128::new(1)));
+        assert_eq!(i128::from_f64_with(1.5, Round::Ceil), Some(i128::new(2)));
+    }
+
+    #[test]
+    fn test_from_float_saturating() {
+        assert_eq!(i128::from_f64_saturating(-0.0), i128::zero());
+        assert_eq!(i128::from_f64_saturating(-0.99), i128::zero());
+        assert_eq!(i128::from_f64_saturating(-170141183460469231731687303715884105728.0), MIN);
+        assert_eq!(i128::from_f64_saturating(-170141183460469269510619166673045815296.0), MIN);
+        assert_eq!(i128::from_f64_saturating(170141183460469231731687303715884105728.0), MAX);
+        assert_eq!(i128::from_f64_saturating(170141183460469212842221372237303250944.0),
+                   i128::from_parts(0x7fff_ffff_ffff_fc00, 0));
+        assert_eq!(i128::from_f64_saturating(f64::INFINITY), MAX);
+        assert_eq!(i128::from_f64_saturating(f64::NEG_INFINITY), MIN);
+        assert_eq!(i128::from_f64_saturating(f64::NAN), i128::zero());
+        assert_eq!(i128::from_f32_saturating(f32::MIN), MIN);
+        assert_eq!(i128::from_f32_saturating(-16777217.0), i128::new(-16777216));
+    }
+
+    #[test]
+    #[cfg(extprim_has_stable_i128)]
+    fn test_from_float_saturating_matches_cast() {
+        use compiler_rt::builtins::I128;
+
+        let values = [0.0, -0.5, 1.0, -1.0, -1e19, 1e38, -1.7e38, 1.8e38, -1.8e38, 1e300, -1e300,
+                      f64::INFINITY, f64::NEG_INFINITY, f64::NAN, -f64::MIN_POSITIVE, -123456789.987];
+        for &v in &values {
+            assert_eq!(i128::from_f64_saturating(v).as_built_in(), v as I128);
+            assert_eq!(i128::from_f32_saturating(v as f32).as_built_in(), v as f32 as I128);
+        }
+    }
+
     #[test]
     #[cfg(extprim_has_stable_i128)]
     fn test_builtin_i128_to_i128() {
@@ -1389,6 +2502,27 @@ mod conv_tests {
         assert_eq!(0x76571c252122c42e_8cdf8e3b4b75c4d0i128, i128::from_parts(0x76571c252122c42e, 0x8cdf8e3b4b75c4d0).as_built_in());
         assert_eq!(-0x76571c252122c42e_8cdf8e3b4b75c4d0i128, i128::from_parts(-0x76571c252122c42f, 0x732071c4b48a3b30).as_built_in());
     }
+
+    #[test]
+    #[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+    fn test_builtin_i128_interop() {
+        let a = i128::from_parts(-0x76571c252122c42f, 0x732071c4b48a3b30);
+        use compiler_rt::builtins::I128;
+
+        let b: I128 = a.into();
+        assert_eq!(b, -0x76571c252122c42e_8cdf8e3b4b75c4d0i128);
+        assert_eq!(i128::from(b), a);
+        assert!(a == b);
+        assert!(b == a);
+        assert!(a != b + 1);
+        assert!(b + 1 != a);
+        assert!(a < b + 1);
+        assert!(b + 1 > a);
+        assert!(a > b - 1);
+        assert!(b - 1 < a);
+        assert!(a <= b);
+        assert!(b >= a);
+    }
 }
 
 //}}}
@@ -1409,6 +2543,12 @@ impl i128 {
 
     /// Returns the constant 1.
     pub fn one() -> i128 { ONE }
+
+    /// The constant 0.
+    pub const ZERO: i128 = ZERO;
+
+    /// The constant 1.
+    pub const ONE: i128 = ONE;
 }
 
 impl Bounded for i128 {
@@ -1425,6 +2565,16 @@ impl One for i128 {
     fn one() -> Self { ONE }
 }
 
+#[cfg(feature="const-traits")]
+impl ConstZero for i128 {
+    const ZERO: Self = ZERO;
+}
+
+#[cfg(feature="const-traits")]
+impl ConstOne for i128 {
+    const ONE: Self = ONE;
+}
+
 //}}}
 
 //{{{ PrimInt
@@ -1489,6 +2639,50 @@ impl i128 {
         self.0.trailing_zeros()
     }
 
+    /// Returns the number of bits needed to represent the absolute value of `self`, excluding the
+    /// sign bit. Zero needs 0 bits.
+    ///
+    /// This is the same as `int.bit_length()` in Python. A signed integer with at least
+    /// `self.bit_length() + 1` bits can hold `self`, except when it is the negative power of two
+    /// `-2**(bit_length - 1)`, which fits in one bit less.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::zero().bit_length(), 0);
+    /// assert_eq!(i128::new(255).bit_length(), 8);
+    /// assert_eq!(i128::new(-255).bit_length(), 8);
+    /// assert_eq!(i128::new(-256).bit_length(), 9);
+    /// assert_eq!(i128::min_value().bit_length(), 128);
+    /// ```
+    pub fn bit_length(self) -> u32 {
+        self.unsigned_abs().bit_length()
+    }
+
+    /// Treats the lowest `bits` bits of `self` as a signed `bits`-bit number in two's complement,
+    /// and sign-extends it to 128 bits. The higher bits of `self` are ignored.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `bits` is 0 or greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(0x80).sign_extend_from(8), i128::new(-128));
+    /// assert_eq!(i128::new(0x17f).sign_extend_from(8), i128::new(127));
+    /// assert_eq!(i128::new(-1).sign_extend_from(1), i128::new(-1));
+    /// ```
+    pub fn sign_extend_from(self, bits: u32) -> i128 {
+        assert!(bits != 0 && bits <= 128, "bit width must lie in the range `[1, 128]` - found {}", bits);
+        let shift = 128 - bits;
+        self.wrapping_shl(shift).wrapping_shr(shift)
+    }
+
     /// Shifts the bits to the left by a specified amount, `shift`, wrapping the truncated bits to
     /// the end of the resulting integer.
     ///
@@ -1572,6 +2766,110 @@ impl i128 {
         Self::from_le(self)
     }
 
+    /// Returns the memory representation of this integer as a byte array in big-endian (network)
+    /// byte order, using two's complement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let bytes = (-i128::one()).to_be_bytes();
+    /// assert_eq!(bytes, [0xff; 16]);
+    /// ```
+    pub fn to_be_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    /// Returns the memory representation of this integer as a byte array in little-endian byte
+    /// order, using two's complement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let bytes = i128::new(-2).to_le_bytes();
+    /// assert_eq!(bytes, [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    ///                    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    /// ```
+    pub fn to_le_bytes(self) -> [u8; 16] {
+        self.0.to_le_bytes()
+    }
+
+    /// Creates an integer from its two's complement representation as a byte array in big-endian
+    /// byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let mut bytes = [0; 16];
+    /// bytes[0] = 0x80;
+    /// assert_eq!(i128::from_be_bytes(bytes), i128::min_value());
+    /// ```
+    pub fn from_be_bytes(bytes: [u8; 16]) -> i128 {
+        i128(u128::from_be_bytes(bytes))
+    }
+
+    /// Creates an integer from its two's complement representation as a byte array in
+    /// little-endian byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// let mut bytes = [0; 16];
+    /// bytes[0] = 0x80;
+    /// assert_eq!(i128::from_le_bytes(bytes), i128::new(0x80));
+    /// ```
+    pub fn from_le_bytes(bytes: [u8; 16]) -> i128 {
+        i128(u128::from_le_bytes(bytes))
+    }
+
+    /// Maps `self` to a `u128` by flipping the sign bit, so that the order of the results is the
+    /// same as the order of the inputs. This allows signed numbers to be radix-sorted or compared
+    /// as unsigned numbers. The inverse is
+    /// [`u128::to_ordered_i128`](../u128/struct.u128.html#method.to_ordered_i128).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::min_value().to_ordered_u128(), u128::zero());
+    /// assert_eq!(i128::zero().to_ordered_u128(), u128::from_parts(1 << 63, 0));
+    /// assert!(i128::new(-1).to_ordered_u128() < i128::new(1).to_ordered_u128());
+    /// ```
+    pub fn to_ordered_u128(self) -> u128 {
+        u128::from_parts(self.0.high64() ^ (1 << 63), self.0.low64())
+    }
+
+    /// Returns a byte string whose lexicographical order is the same as the numerical order of
+    /// `self`, suitable as a key in byte-ordered storage. This is the big-endian representation of
+    /// [`to_ordered_u128`](#method.to_ordered_u128).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert!(i128::new(-2).to_sortable_key() < i128::new(-1).to_sortable_key());
+    /// assert!(i128::new(-1).to_sortable_key() < i128::zero().to_sortable_key());
+    /// assert_eq!(i128::from_sortable_key(i128::new(-2).to_sortable_key()), i128::new(-2));
+    /// ```
+    pub fn to_sortable_key(self) -> [u8; 16] {
+        self.to_ordered_u128().to_be_bytes()
+    }
+
+    /// Recovers a number from a key created by [`to_sortable_key`](#method.to_sortable_key).
+    pub fn from_sortable_key(key: [u8; 16]) -> i128 {
+        u128::from_be_bytes(key).to_ordered_i128()
+    }
+
     /// Raises `self` to the power of `exp`, using exponentiation by squaring.
     ///
     /// # Examples
@@ -1625,6 +2923,47 @@ mod checked_tests {
     use std::i64;
     use i128::{i128, ZERO, ONE, MAX, MIN};
 
+    #[test]
+    fn test_bit_length() {
+        assert_eq!(ZERO.bit_length(), 0);
+        assert_eq!((-ONE).bit_length(), 1);
+        for k in 0..127u32 {
+            let power = ONE << k as usize;
+            assert_eq!(power.bit_length(), k + 1);
+            assert_eq!((-power).bit_length(), k + 1);
+            assert_eq!((power - ONE).bit_length(), k);
+            assert_eq!((ONE - power).bit_length(), k);
+        }
+        assert_eq!(MAX.bit_length(), 127);
+        assert_eq!(MIN.bit_length(), 128);
+        assert_eq!(i128::new(i64::MIN).bit_length(), 64);
+    }
+
+    #[test]
+    fn test_sign_extend_from() {
+        let value = i128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+        assert_eq!(value.sign_extend_from(128), value);
+        assert_eq!(value.sign_extend_from(64), i128::new(0xfedc_ba98_7654_3210u64 as i64));
+        assert_eq!(value.sign_extend_from(68), i128::from_parts(-1, 0xfedc_ba98_7654_3210));
+        assert_eq!(value.sign_extend_from(2), ZERO);
+        assert_eq!(value.sign_extend_from(5), i128::new(-16));
+        assert_eq!(MIN.sign_extend_from(127), ZERO);
+        assert_eq!(MAX.sign_extend_from(127), -ONE);
+        for bits in 1..129 {
+            let min = MIN >> (128 - bits) as usize;
+            assert_eq!(min.sign_extend_from(bits), min);
+            assert_eq!(min.wrapping_sub(ONE).sign_extend_from(bits), !min);
+            assert_eq!(ZERO.sign_extend_from(bits), ZERO);
+            assert_eq!((-ONE).sign_extend_from(bits), -ONE);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="bit width must lie in the range")]
+    fn test_sign_extend_from_zero_bits() {
+        ONE.sign_extend_from(0);
+    }
+
     #[test]
     fn test_checked_add() {
         assert_eq!(Some(ZERO), ONE.checked_add(-ONE));
@@ -1690,6 +3029,32 @@ mod checked_tests {
         assert_eq!(Some(-MAX), MAX.checked_div(-ONE));
         assert_eq!(None, MIN.checked_div(-ONE));
     }
+
+    #[test]
+    fn test_sortable_key() {
+        let values = [MIN, MIN + ONE, i128::from_parts(-1, 0), -ONE, ZERO, ONE,
+                      i128::from_parts(0, u64::MAX), i128::from_parts(1, 0), MAX];
+        for w in values.windows(2) {
+            assert!(w[0].to_ordered_u128() < w[1].to_ordered_u128());
+            assert!(w[0].to_sortable_key() < w[1].to_sortable_key());
+        }
+        for v in &values {
+            assert_eq!(v.to_ordered_u128().to_ordered_i128(), *v);
+            assert_eq!(i128::from_sortable_key(v.to_sortable_key()), *v);
+        }
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a = i128::from_parts(-0x7fdac9c2232ae28, 0x509d78e4a3008bcd);
+        assert_eq!(i128::from_be_bytes(a.to_be_bytes()), a);
+        assert_eq!(i128::from_le_bytes(a.to_le_bytes()), a);
+        assert_eq!(a.to_be_bytes()[0], 0xf8);
+        assert_eq!(a.to_le_bytes()[0], 0xcd);
+        assert_eq!(MIN.to_le_bytes()[15], 0x80);
+        assert_eq!(MAX.to_be_bytes()[0], 0x7f);
+        assert_eq!(i128::from_be_bytes([0; 16]), ZERO);
+    }
 }
 
 //}}}
@@ -1798,53 +3163,277 @@ impl Signed for i128 {
 
 //}}}
 
+//{{{ Gcd, Lcm
+
+impl i128 {
+    fn unsigned_magnitude(self) -> u128 {
+        if self.is_negative() {
+            self.0.wrapping_neg()
+        } else {
+            self.0
+        }
+    }
+
+    /// Computes the greatest common divisor of `self` and `other`, which is always non-negative.
+    ///
+    /// # Overflow behavior
+    ///
+    /// The results of `gcd(MIN, MIN)` and `gcd(MIN, 0)` are 2<sup>127</sup>, which cannot be
+    /// represented as an `i128`. This case will return `MIN`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-48).gcd(i128::new(180)), i128::new(12));
+    /// assert_eq!(i128::new(-48).gcd(i128::zero()), i128::new(48));
+    /// ```
+    pub fn gcd(self, other: i128) -> i128 {
+        i128(self.unsigned_magnitude().gcd(other.unsigned_magnitude()))
+    }
+
+    /// Computes the least common multiple of `self` and `other`, which is always non-negative.
+    /// The result is 0 if either number is 0.
+    ///
+    /// # Overflow behavior
+    ///
+    /// If the result cannot be represented as an `i128`, this function will panic in debug mode
+    /// and wrap around in optimized code, the same as the `*` operator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-48).lcm(i128::new(180)), i128::new(720));
+    /// assert_eq!(i128::new(-48).lcm(i128::new(-180)), i128::new(720));
+    /// ```
+    pub fn lcm(self, other: i128) -> i128 {
+        if self == ZERO || other == ZERO {
+            ZERO
+        } else {
+            (self / self.gcd(other) * other).abs()
+        }
+    }
+}
+
+#[cfg(test)]
+mod gcd_tests {
+    use i128::{i128, ZERO, ONE, MIN, MAX};
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(ZERO.gcd(ZERO), ZERO);
+        assert_eq!(MAX.gcd(MIN), ONE);
+        assert_eq!(MIN.gcd(MIN), MIN);
+        assert_eq!(MIN.gcd(i128::new(-6)), i128::new(2));
+        assert_eq!(i128::new(-1071).gcd(i128::new(-462)), i128::new(21));
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(ZERO.lcm(MIN), ZERO);
+        assert_eq!(MAX.lcm(-ONE), MAX);
+        assert_eq!(i128::new(-1071).lcm(i128::new(462)), i128::new(23562));
+    }
+}
+
+//}}}
+
 //{{{ FromStr, FromStrRadix
 
 impl i128 {
     /// Converts a string slice in a given base to an integer.
     ///
-    /// Leading and trailing whitespace represent an error.
+    /// The digits may be preceded by a `+` sign or a `-` sign. Leading and trailing whitespace represent an
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::from_str_radix("123456abcdef1234567890", 16),
+    ///             Ok(i128::from_parts(0x123456, 0xabcdef1234567890)));
+    /// ```
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<i128, ParseIntError> {
+        assert!(radix >= 2 && radix <= 36,
+                "from_str_radix_int: must lie in the range `[2, 36]` - found {}",
+                radix);
+
+        let mut src_chars = src.chars();
+        let (is_negative, src) = match src_chars.next() {
+            Some('-') => (true, src_chars.as_str()),
+            Some('+') => (false, src_chars.as_str()),
+            Some(_) => (false, src),
+            None => return Err(error::empty()),
+        };
+        if src.is_empty() {
+            return Err(error::invalid_digit());
+        }
+
+        match parse_digits(src, radix) {
+            Ok(res) => {
+                let res = from_sign_abs(is_negative, res);
+                if res != ZERO && res.is_negative() != is_negative {
+                    Err(if is_negative {
+                        error::underflow()
+                    } else {
+                        error::overflow()
+                    })
+                } else {
+                    Ok(res)
+                }
+            },
+            Err(e) => {
+                if is_negative && error::is_overflow(&e) {
+                    Err(error::underflow())
+                } else {
+                    Err(e)
+                }
+            },
+        }
+    }
+
+    /// Parses a hexadecimal string as the raw two's complement bit pattern of an integer.
+    ///
+    /// This is the inverse of the `{:x}` and `{:#x}` formatters, which print the bit pattern of
+    /// negative numbers instead of a minus sign. The string may start with `0x` or `0X`, and must
+    /// not contain a sign. Shorter strings are zero-extended, so `"ff"` is 255 and not -1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::from_hex_twos_complement("ffffffffffffffffffffffffffffffff"),
+    ///            Ok(i128::new(-1)));
+    /// assert_eq!(i128::from_hex_twos_complement("0x80000000000000000000000000000000"),
+    ///            Ok(i128::min_value()));
+    /// assert_eq!(i128::from_hex_twos_complement("ff"), Ok(i128::new(255)));
+    ///
+    /// let x = i128::new(-123456789);
+    /// assert_eq!(i128::from_hex_twos_complement(&format!("{:#x}", x)), Ok(x));
+    /// ```
+    pub fn from_hex_twos_complement(src: &str) -> Result<i128, ParseIntError> {
+        let digits = if src.starts_with("0x") || src.starts_with("0X") {
+            &src[2..]
+        } else {
+            src
+        };
+        parse_digits(digits, 16).map(i128)
+    }
+
+    /// Converts a decimal string slice which may be written in scientific notation to an integer.
+    ///
+    /// See [`u128::from_str_scientific`](../u128/struct.u128.html#method.from_str_scientific)
+    /// for the accepted syntax. A leading `-` sign is also accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    /// use extprim::ParseScientificError;
+    ///
+    /// assert_eq!(i128::from_str_scientific("-2.5e10"), Ok(i128::new(-25_000_000_000)));
+    /// assert_eq!(i128::from_str_scientific("-1e-1"), Err(ParseScientificError::NotIntegral));
+    /// assert!(i128::from_str_scientific("2e38").is_err());
+    /// ```
+    pub fn from_str_scientific(src: &str) -> Result<i128, ParseScientificError> {
+        let mut src_chars = src.chars();
+        let (is_negative, src) = match src_chars.next() {
+            Some('-') => (true, src_chars.as_str()),
+            Some(_) => (false, src),
+            None => return Err(error::empty().into()),
+        };
+
+        match u128::from_str_scientific(src) {
+            Ok(res) => {
+                let res = from_sign_abs(is_negative, res);
+                if res != ZERO && res.is_negative() != is_negative {
+                    Err(if is_negative {
+                        error::underflow()
+                    } else {
+                        error::overflow()
+                    }.into())
+                } else {
+                    Ok(res)
+                }
+            },
+            Err(ParseScientificError::Int(ref e)) if is_negative && error::is_overflow(e) => {
+                Err(error::underflow().into())
+            },
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(extprim_has_const_fn)]
+impl i128 {
+    /// Converts a decimal string slice to an integer in a `const` context.
+    ///
+    /// This accepts the same strings as `from_str`, and is meant for writing large constants
+    /// without the literal macros.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the string does not represent a valid number. In a `const`
+    /// context this is a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// const K: i128 = i128::parse_const("-123456789012345678901234567890");
+    /// assert_eq!(K, -i128::from_parts(6692605942, 14083847773837265618));
+    /// ```
+    pub const fn parse_const(src: &str) -> i128 {
+        i128::parse_const_radix(src, 10)
+    }
+
+    /// Converts a string slice in a given base to an integer in a `const` context.
+    ///
+    /// This accepts the same strings as `from_str_radix`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `radix` is not in the range [2 ... 36], or if the string does
+    /// not represent a valid number. In a `const` context this is a compile error.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use extprim::i128::i128;
+    /// use extprim::i128::{i128, MIN};
     ///
-    /// assert_eq!(i128::from_str_radix("123456abcdef1234567890", 16),
-    ///             Ok(i128::from_parts(0x123456, 0xabcdef1234567890)));
+    /// const LOWEST: i128 = i128::parse_const_radix("-80000000000000000000000000000000", 16);
+    /// assert_eq!(LOWEST, MIN);
     /// ```
-    pub fn from_str_radix(src: &str, radix: u32) -> Result<i128, ParseIntError> {
-        assert!(radix >= 2 && radix <= 36,
-                "from_str_radix_int: must lie in the range `[2, 36]` - found {}",
-                radix);
-
-        let mut src_chars = src.chars();
-        let (is_negative, src) = match src_chars.next() {
-            Some('-') => (true, src_chars.as_str()),
-            Some(_) => (false, src),
-            None => return Err(error::empty()),
+    pub const fn parse_const_radix(src: &str, radix: u32) -> i128 {
+        assert!(radix >= 2 && radix <= 36, "from_str_radix_int: must lie in the range `[2, 36]`");
+
+        let bytes = src.as_bytes();
+        let is_negative = !bytes.is_empty() && bytes[0] == b'-';
+        let start = if !bytes.is_empty() && (bytes[0] == b'-' || bytes[0] == b'+') { 1 } else { 0 };
+        let abs = match parse_digits_const(bytes, start, radix) {
+            Some(abs) => abs,
+            None if is_negative => panic!("number too small to fit in target type"),
+            None => panic!("number too large to fit in target type"),
         };
 
-        match u128::from_str_radix(src, radix) {
-            Ok(res) => {
-                let res = from_sign_abs(is_negative, res);
-                if res != ZERO && res.is_negative() != is_negative {
-                    Err(if is_negative {
-                        error::underflow()
-                    } else {
-                        error::overflow()
-                    })
-                } else {
-                    Ok(res)
-                }
-            },
-            Err(e) => {
-                if is_negative && error::is_overflow(&e) {
-                    Err(error::underflow())
-                } else {
-                    Err(e)
-                }
-            },
+        if !is_negative {
+            if abs.hi >> 63 != 0 {
+                panic!("number too large to fit in target type");
+            }
+            i128(abs)
+        } else if abs.hi >> 63 != 0 && (abs.hi << 1 != 0 || abs.lo != 0) {
+            panic!("number too small to fit in target type");
+        } else {
+            // Two's complement negation, as `wrapping_neg` is not a const fn.
+            let lo = (!abs.lo).wrapping_add(1);
+            let hi = if lo == 0 { (!abs.hi).wrapping_add(1) } else { !abs.hi };
+            i128(u128::from_parts(hi, lo))
         }
     }
 }
@@ -1870,9 +3459,30 @@ mod from_str_tests {
     use i128::{i128, ZERO, ONE, MIN, MAX};
     use error;
 
+    #[test]
+    fn test_from_hex_twos_complement() {
+        assert_eq!(i128::from_hex_twos_complement("ffffffffffffffffffffffffffffffff"), Ok(-ONE));
+        assert_eq!(i128::from_hex_twos_complement("0X7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"), Ok(MAX));
+        assert_eq!(i128::from_hex_twos_complement("0x8000000000000000ffffffffffffffff"),
+                   Ok(i128::from_parts(-0x8000000000000000, !0)));
+        assert_eq!(i128::from_hex_twos_complement("0"), Ok(ZERO));
+        assert_eq!(i128::from_hex_twos_complement("0000000000000000000000000000000001"), Ok(ONE));
+        for &x in &[ZERO, ONE, -ONE, MIN, MAX, i128::new(-0x1234)] {
+            assert_eq!(i128::from_hex_twos_complement(&format!("{:x}", x)), Ok(x));
+            assert_eq!(i128::from_hex_twos_complement(&format!("{:#X}", x)), Ok(x));
+        }
+
+        assert_eq!(i128::from_hex_twos_complement(""), Err(error::empty()));
+        assert_eq!(i128::from_hex_twos_complement("0x"), Err(error::empty()));
+        assert_eq!(i128::from_hex_twos_complement("-1"), Err(error::invalid_digit()));
+        assert_eq!(i128::from_hex_twos_complement("0xg"), Err(error::invalid_digit()));
+        assert_eq!(i128::from_hex_twos_complement("1ffffffffffffffffffffffffffffffff"),
+                   Err(error::overflow()));
+    }
+
     #[test]
     fn test_from_str_radix() {
-        const NEG_TEST_RESULTS: &'static [&'static str] = &[
+        const NEG_TEST_RESULTS: &[&str] = &[
             "-1101001110000001100001110100110110011101000101000000010101010011111111110000011111001111010001011110010010111111100000110111000",
             "-22120002200011001100011122212011101112220120010100201111102212122012112012001022",
             "-1221300030032212303220220002222133332003321322023302113330012320",
@@ -1913,7 +3523,7 @@ mod from_str_tests {
         let neg = i128::from_parts(-7620305690708017834, 34929685051752008);
         for (base2, res) in NEG_TEST_RESULTS.iter().enumerate() {
             let base = (base2 + 2) as u32;
-            assert_eq!(Ok(neg), i128::from_str_radix(*res, base));
+            assert_eq!(Ok(neg), i128::from_str_radix(res, base));
             assert_eq!(Ok(-neg), i128::from_str_radix(&res[1..], base));
         }
 
@@ -1929,6 +3539,53 @@ mod from_str_tests {
         assert_eq!(Err(error::overflow()), i128::from_str_radix("7ksyyizzkutudzbv8aqztecjk", 36));
         assert_eq!(Err(error::underflow()), i128::from_str_radix("-7ksyyizzkutudzbv8aqztecjl", 36));
     }
+
+    #[test]
+    fn test_from_str_scientific() {
+        use error::ParseScientificError;
+
+        assert_eq!(Ok(ZERO), i128::from_str_scientific("-0e5"));
+        assert_eq!(Ok(-ONE), i128::from_str_scientific("-1"));
+        assert_eq!(Ok(i128::new(-1200)), i128::from_str_scientific("-1.2e3"));
+        assert_eq!(Ok(MAX), i128::from_str_scientific("1.70141183460469231731687303715884105727e38"));
+        assert_eq!(Ok(MIN), i128::from_str_scientific("-1.70141183460469231731687303715884105728e38"));
+        assert_eq!(Err(ParseScientificError::Int(error::overflow())),
+                    i128::from_str_scientific("1.70141183460469231731687303715884105728e38"));
+        assert_eq!(Err(ParseScientificError::Int(error::underflow())),
+                    i128::from_str_scientific("-1.70141183460469231731687303715884105729e38"));
+        assert_eq!(Err(ParseScientificError::Int(error::underflow())), i128::from_str_scientific("-1e50"));
+        assert_eq!(Err(ParseScientificError::NotIntegral), i128::from_str_scientific("-0.5"));
+        assert_eq!(Err(ParseScientificError::Int(error::empty())), i128::from_str_scientific(""));
+        assert_eq!(Err(ParseScientificError::Int(error::empty())), i128::from_str_scientific("-"));
+        assert_eq!(Err(ParseScientificError::Int(error::invalid_digit())), i128::from_str_scientific("--1"));
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    fn test_parse_const() {
+        const K: i128 = i128::parse_const("-123456789012345678901234567890");
+        const LARGEST: i128 = i128::parse_const("+170141183460469231731687303715884105727");
+        const SMALLEST: i128 = i128::parse_const("-170141183460469231731687303715884105728");
+        assert_eq!(Some(K), i128::from_str_radix("-123456789012345678901234567890", 10).ok());
+        assert_eq!(LARGEST, MAX);
+        assert_eq!(SMALLEST, MIN);
+        assert_eq!(i128::parse_const("-0"), ZERO);
+        assert_eq!(i128::parse_const_radix("-1", 2), -ONE);
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    #[should_panic(expected = "number too large to fit in target type")]
+    fn test_parse_const_overflow() {
+        i128::parse_const("170141183460469231731687303715884105728");
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    #[should_panic(expected = "number too small to fit in target type")]
+    fn test_parse_const_underflow() {
+        i128::parse_const("-170141183460469231731687303715884105729");
+    }
 }
 
 //}}}
@@ -1964,16 +3621,26 @@ impl fmt::Octal for i128 {
 
 impl fmt::Display for i128 {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        if !self.is_negative() {
-            self.0.fmt(formatter)
-        } else if *self == MIN {
-            formatter.pad_integral(false, "", "170141183460469231731687303715884105728")
-        } else {
-            let mut buffer = [0u8; 39];
-            let mut buf = FormatBuffer::new(&mut buffer);
-            write!(&mut buf, "{}", self.0.wrapping_neg())?;
-            formatter.pad_integral(false, "", unsafe { buf.into_str() })
-        }
+        let mut buffer = [0u8; ::u128::MAX_STR_LEN];
+        let digits = format_decimal(self.unsigned_magnitude(), &mut buffer);
+        formatter.pad_integral(!self.is_negative(), "", digits)
+    }
+}
+
+impl i128 {
+    /// Returns the number of decimal digits of the absolute value of the number, not counting the
+    /// sign. Zero has 1 digit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-999).num_digits(), 3);
+    /// assert_eq!(i128::min_value().num_digits(), 39);
+    /// ```
+    pub fn num_digits(self) -> u32 {
+        self.unsigned_magnitude().num_digits()
     }
 }
 
@@ -1985,7 +3652,7 @@ impl fmt::Debug for i128 {
 
 #[cfg(test)]
 mod show_tests {
-    use i128::{i128, ZERO, ONE, MIN, MAX};
+    use i128::{i128, ZERO, ONE, MIN, MAX, MAX_STR_LEN};
 
     #[test]
     fn test_show() {
@@ -1999,6 +3666,39 @@ mod show_tests {
                        "{}", i128::from_parts(-2222696624240918362, 11097545986877534604));
         assert_fmt_eq!("+00170141183460469231731687303715884105727", 42, "{:+042}", MAX);
         assert_fmt_eq!("-00170141183460469231731687303715884105728", 42, "{:+042}", MIN);
+    }
+
+    #[test]
+    fn test_num_digits() {
+        assert_eq!(ZERO.num_digits(), 1);
+        assert_eq!((-ONE).num_digits(), 1);
+        assert_eq!(i128::new(-10).num_digits(), 2);
+        assert_eq!(MAX.num_digits(), 39);
+        assert_eq!(MIN.to_string().len(), MAX_STR_LEN);
+        assert_eq!(MIN.num_digits() as usize + 1, MAX_STR_LEN);
+    }
+
+    #[test]
+    fn test_show_padding() {
+        assert_fmt_eq!("-0000001", 8, "{:08}", -ONE);
+        assert_fmt_eq!("00000001", 8, "{:08}", ONE);
+        assert_fmt_eq!("+1", 2, "{:+}", ONE);
+        assert_fmt_eq!("+0", 2, "{:+}", ZERO);
+        assert_fmt_eq!("-1", 2, "{:+}", -ONE);
+        assert_fmt_eq!("     -12", 8, "{:>8}", i128::new(-12));
+        assert_fmt_eq!("-12     ", 8, "{:<8}", i128::new(-12));
+        assert_fmt_eq!("**-12***", 8, "{:*^8}", i128::new(-12));
+        assert_fmt_eq!("   +12", 6, "{:>+6}", i128::new(12));
+        assert_fmt_eq!("-0000000000000000000012", 23, "{:023}", i128::new(-12));
+        assert_fmt_eq!("  -170141183460469231731687303715884105728", 42, "{:>42}", MIN);
+        assert_fmt_eq!("-170141183460469231731687303715884105728", 40, "{:02}", MIN);
+        assert_fmt_eq!("-18446744073709551616", 21, "{}", i128::from_parts(-1, 0));
+        assert_fmt_eq!("-00018446744073709551615", 24, "{:024}", i128::from_parts(-1, 1));
+        for &x in &[-1i64, 0, 1, -42, -0x8000_0000_0000_0000, 0x7fff_ffff_ffff_ffff] {
+            assert_eq!(format!("{:+025}", i128::new(x)), format!("{:+025}", x));
+            assert_eq!(format!("{:^+25}", i128::new(x)), format!("{:^+25}", x));
+            assert_eq!(format!("{:>9}", i128::new(x)), format!("{:>9}", x));
+        }
 
         // Sanity test
         assert_fmt_eq!("ff", 2, "{:x}", -1i8);
@@ -2041,57 +3741,90 @@ impl<'a> Product<&'a i128> for i128 {
     }
 }
 
+impl Sum for Wrapping<i128> {
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item = Self>
+    {
+        iter.fold(Wrapping(ZERO), |acc, elem| Wrapping(acc.0.wrapping_add(elem.0)))
+    }
+}
+
+impl Product for Wrapping<i128> {
+    fn product<I>(iter: I) -> Self
+        where I: Iterator<Item = Self>
+    {
+        iter.fold(Wrapping(ONE), |acc, elem| Wrapping(acc.0.wrapping_mul(elem.0)))
+    }
+}
+
+impl<'a> Sum<&'a Wrapping<i128>> for Wrapping<i128> {
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item = &'a Self>
+    {
+        iter.fold(Wrapping(ZERO), |acc, elem| Wrapping(acc.0.wrapping_add(elem.0)))
+    }
+}
+
+impl<'a> Product<&'a Wrapping<i128>> for Wrapping<i128> {
+    fn product<I>(iter: I) -> Self
+        where I: Iterator<Item = &'a Self>
+    {
+        iter.fold(Wrapping(ONE), |acc, elem| Wrapping(acc.0.wrapping_mul(elem.0)))
+    }
+}
+
 #[cfg(test)]
 mod iter_tests {
     use i128::{i128, ZERO, ONE, MIN, MAX};
+    use traits::Wrapping;
 
     #[test]
     fn test_sum() {
         // Sum<i128>
-        assert_eq!(ZERO, Vec::<i128>::new().into_iter().sum());
-        assert_eq!(ZERO, vec![ZERO, ZERO, ZERO].into_iter().sum());
-        assert_eq!(ZERO, vec![-ONE, ONE].into_iter().sum());
-        assert_eq!(ONE, vec![ONE].into_iter().sum());
-        assert_eq!(i128::from(3i64), vec![ONE, ONE, ONE].into_iter().sum());
-        assert_eq!(-ONE, vec![MAX, MIN].into_iter().sum());
+        assert_eq!(ZERO, Vec::<i128>::new().into_iter().sum::<i128>());
+        assert_eq!(ZERO, vec![ZERO, ZERO, ZERO].into_iter().sum::<i128>());
+        assert_eq!(ZERO, vec![-ONE, ONE].into_iter().sum::<i128>());
+        assert_eq!(ONE, vec![ONE].into_iter().sum::<i128>());
+        assert_eq!(i128::from(3i64), vec![ONE, ONE, ONE].into_iter().sum::<i128>());
+        assert_eq!(-ONE, vec![MAX, MIN].into_iter().sum::<i128>());
         assert_eq!(i128::from(2i64), vec![
             i128::from(-3i64),
             i128::from(10i64),
             MIN,
             MAX - i128::from(4i64),
-        ].into_iter().sum());
-        assert_eq!(MAX, vec![MAX].into_iter().sum());
-        assert_eq!(MIN, vec![MIN].into_iter().sum());
-        assert_eq!(i128::from_parts(7, 42), vec![i128::from_parts(7, 42)].into_iter().sum());
+        ].into_iter().sum::<i128>());
+        assert_eq!(MAX, vec![MAX].into_iter().sum::<i128>());
+        assert_eq!(MIN, vec![MIN].into_iter().sum::<i128>());
+        assert_eq!(i128::from_parts(7, 42), vec![i128::from_parts(7, 42)].into_iter().sum::<i128>());
 
         // Sum<&'a i128>
-        assert_eq!(ZERO, [].iter().sum());
-        assert_eq!(ZERO, [ZERO, ZERO, ZERO].iter().sum());
-        assert_eq!(ZERO, [-ONE, ONE].iter().sum());
-        assert_eq!(ONE, [ONE].iter().sum());
-        assert_eq!(i128::from(3i64), [ONE, ONE, ONE].iter().sum());
-        assert_eq!(-ONE, [MAX, MIN].iter().sum());
+        assert_eq!(ZERO, [].iter().sum::<i128>());
+        assert_eq!(ZERO, [ZERO, ZERO, ZERO].iter().sum::<i128>());
+        assert_eq!(ZERO, [-ONE, ONE].iter().sum::<i128>());
+        assert_eq!(ONE, [ONE].iter().sum::<i128>());
+        assert_eq!(i128::from(3i64), [ONE, ONE, ONE].iter().sum::<i128>());
+        assert_eq!(-ONE, [MAX, MIN].iter().sum::<i128>());
         assert_eq!(i128::from(2i64), [
             i128::from(-3i64),
             i128::from(10i64),
             MIN,
             MAX - i128::from(4i64),
-        ].iter().sum());
-        assert_eq!(MAX, [MAX].iter().sum());
-        assert_eq!(MIN, [MIN].iter().sum());
-        assert_eq!(i128::from_parts(7, 42), [i128::from_parts(7, 42)].iter().sum());
+        ].iter().sum::<i128>());
+        assert_eq!(MAX, [MAX].iter().sum::<i128>());
+        assert_eq!(MIN, [MIN].iter().sum::<i128>());
+        assert_eq!(i128::from_parts(7, 42), [i128::from_parts(7, 42)].iter().sum::<i128>());
     }
 
     #[test]
     fn test_product() {
         // Product<i128>
-        assert_eq!(ONE, Vec::<i128>::new().into_iter().product());
-        assert_eq!(ONE, vec![ONE, ONE, ONE, ONE, ONE].into_iter().product());
-        assert_eq!(ZERO, vec![MAX, ZERO, MIN, ONE].into_iter().product());
-        assert_eq!(MAX, vec![MAX].into_iter().product());
-        assert_eq!(MAX, vec![ONE, MAX].into_iter().product());
-        assert_eq!(MIN, vec![MIN].into_iter().product());
-        assert_eq!(MIN, vec![ONE, MIN].into_iter().product());
+        assert_eq!(ONE, Vec::<i128>::new().into_iter().product::<i128>());
+        assert_eq!(ONE, vec![ONE, ONE, ONE, ONE, ONE].into_iter().product::<i128>());
+        assert_eq!(ZERO, vec![MAX, ZERO, MIN, ONE].into_iter().product::<i128>());
+        assert_eq!(MAX, vec![MAX].into_iter().product::<i128>());
+        assert_eq!(MAX, vec![ONE, MAX].into_iter().product::<i128>());
+        assert_eq!(MIN, vec![MIN].into_iter().product::<i128>());
+        assert_eq!(MIN, vec![ONE, MIN].into_iter().product::<i128>());
         assert_eq!(MIN, vec![
             i128::from(-0x1i64),
             i128::from(0x2i64),
@@ -2101,16 +3834,16 @@ mod iter_tests {
             i128::from(0x10000i64),
             i128::from(0x100000000i64),
             i128::from_parts(0x1, 0x0),
-        ].into_iter().product());
+        ].into_iter().product::<i128>());
 
         // Product<&'a i128>
-        assert_eq!(ONE, [].iter().product());
-        assert_eq!(ONE, [ONE, ONE, ONE, ONE, ONE].iter().product());
-        assert_eq!(ZERO, [MAX, ZERO, MIN, ONE].iter().product());
-        assert_eq!(MAX, [MAX].iter().product());
-        assert_eq!(MAX, [ONE, MAX].iter().product());
-        assert_eq!(MIN, [MIN].iter().product());
-        assert_eq!(MIN, [ONE, MIN].iter().product());
+        assert_eq!(ONE, [].iter().product::<i128>());
+        assert_eq!(ONE, [ONE, ONE, ONE, ONE, ONE].iter().product::<i128>());
+        assert_eq!(ZERO, [MAX, ZERO, MIN, ONE].iter().product::<i128>());
+        assert_eq!(MAX, [MAX].iter().product::<i128>());
+        assert_eq!(MAX, [ONE, MAX].iter().product::<i128>());
+        assert_eq!(MIN, [MIN].iter().product::<i128>());
+        assert_eq!(MIN, [ONE, MIN].iter().product::<i128>());
         assert_eq!(MIN, [
             i128::from(-0x1i64),
             i128::from(0x2i64),
@@ -2120,9 +3853,396 @@ mod iter_tests {
             i128::from(0x10000i64),
             i128::from(0x100000000i64),
             i128::from_parts(0x1, 0x0),
-        ].iter().product());
+        ].iter().product::<i128>());
+    }
+
+    #[test]
+    fn test_wrapping_sum() {
+        assert_eq!(Wrapping(ZERO), Vec::<Wrapping<i128>>::new().into_iter().sum());
+        assert_eq!(Wrapping(MIN), vec![Wrapping(MAX), Wrapping(ONE)].into_iter().sum());
+        assert_eq!(Wrapping(MAX), vec![Wrapping(MIN), Wrapping(-ONE)].into_iter().sum());
+        assert_eq!(Wrapping(ZERO), [Wrapping(MIN), Wrapping(MIN)].iter().sum());
+    }
+
+    #[test]
+    fn test_wrapping_product() {
+        assert_eq!(Wrapping(ONE), Vec::<Wrapping<i128>>::new().into_iter().product());
+        assert_eq!(Wrapping(MIN), vec![Wrapping(MIN), Wrapping(-ONE)].into_iter().product());
+        assert_eq!(Wrapping(ZERO), vec![Wrapping(MIN), Wrapping(i128::new(2))].into_iter().product());
+        assert_eq!(Wrapping(ONE), [Wrapping(MAX), Wrapping(MAX)].iter().product());
+    }
+}
+
+//}}}
+
+
+//{{{ Arrow Decimal128
+
+/// Computes `10^exp`, or `None` if it does not fit in a `u128`.
+impl i128 {
+    /// Returns the 16-byte layout of this integer in an Arrow or Parquet `DECIMAL128` column, i.e.
+    /// the little-endian two's complement representation of the unscaled value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// // 1.23 with scale 2.
+    /// let bytes = i128::new(123).to_arrow_bytes();
+    /// assert_eq!(&bytes[..2], &[123, 0]);
+    /// assert_eq!(i128::from_arrow_bytes(bytes), i128::new(123));
+    /// ```
+    pub fn to_arrow_bytes(self) -> [u8; 16] {
+        self.to_le_bytes()
+    }
+
+    /// Reads an unscaled value from the 16-byte layout of an Arrow or Parquet `DECIMAL128` column.
+    pub fn from_arrow_bytes(bytes: [u8; 16]) -> i128 {
+        i128::from_le_bytes(bytes)
+    }
+
+    /// Checks whether this unscaled value has at most `precision` decimal digits, i.e. whether it
+    /// is valid in a `DECIMAL128` column of that precision.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `precision` is not in the range [1 ... 38].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert!(i128::new(-99999).fits_decimal_precision(5));
+    /// assert!(!i128::new(100000).fits_decimal_precision(5));
+    /// ```
+    pub fn fits_decimal_precision(self, precision: u32) -> bool {
+        assert!(precision != 0 && precision <= DECIMAL128_MAX_PRECISION,
+                "precision must lie in the range `[1, 38]` - found {}",
+                precision);
+        // 10^38 is less than 2^127, so the limit always fits.
+        self.unsigned_abs() < checked_pow10(precision).unwrap()
+    }
+
+    /// Converts an unscaled value from one scale to another, e.g. `12345` with scale 2 (123.45) to
+    /// `123450` with scale 3.
+    ///
+    /// Scales may be negative, in which case the unscaled value is multiplied by a power of 10.
+    ///
+    /// # Return value
+    ///
+    /// `None` if the result overflows, or if decreasing the scale would discard nonzero digits.
+    /// Otherwise, `Some(n)` where `n` has the same value as `self` at the new scale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(12345).rescale_decimal(2, 3), Some(i128::new(123450)));
+    /// assert_eq!(i128::new(12300).rescale_decimal(2, 0), Some(i128::new(123)));
+    /// assert_eq!(i128::new(12345).rescale_decimal(2, 0), None);
+    /// ```
+    pub fn rescale_decimal(self, from_scale: i8, to_scale: i8) -> Option<i128> {
+        if self == ZERO {
+            return Some(ZERO);
+        }
+        let diff = to_scale as i32 - from_scale as i32;
+        if diff >= 0 {
+            self.checked_mul(i128(checked_pow10(diff as u32)?))
+        } else {
+            let (q, r) = div_rem(self, i128(checked_pow10((-diff) as u32)?));
+            if r == ZERO {
+                Some(q)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Parses a decimal number such as `"-123.45"` into an unscaled value with the given scale.
+    ///
+    /// The number may have a sign, and a fractional part starting with `.`. It must have at least
+    /// one digit, and must be exactly representable at the given scale.
+    ///
+    /// # Return value
+    ///
+    /// `Err(ParseIntError)` if the string is not a decimal number, has significant digits below
+    /// 10<sup>-scale</sup>, or does not fit in an `i128`. Otherwise, `Ok(n)` where `n` is the
+    /// unscaled value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::from_decimal_str("-123.45", 3), Ok(i128::new(-123450)));
+    /// assert_eq!(i128::from_decimal_str("7", 2), Ok(i128::new(700)));
+    /// assert!(i128::from_decimal_str("0.125", 2).is_err());
+    /// ```
+    pub fn from_decimal_str(src: &str, scale: i8) -> Result<i128, ParseIntError> {
+        let mut src_chars = src.chars();
+        let (is_negative, digits) = match src_chars.next() {
+            Some('-') => (true, src_chars.as_str()),
+            Some('+') => (false, src_chars.as_str()),
+            Some(_) => (false, src),
+            None => return Err(error::empty()),
+        };
+
+        let out_of_range = || if is_negative { error::underflow() } else { error::overflow() };
+        let abs = parse_decimal_digits(digits, scale).map_err(|e| {
+            if error::is_overflow(&e) { out_of_range() } else { e }
+        })?;
+        let res = from_sign_abs(is_negative, abs);
+        if res != ZERO && res.is_negative() != is_negative {
+            Err(out_of_range())
+        } else {
+            Ok(res)
+        }
+    }
+
+    /// Formats an unscaled value with the given scale as a decimal number, e.g. `-12345` with scale
+    /// 2 as `"-123.45"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-12345).format_scaled(2), "-123.45");
+    /// assert_eq!(i128::new(5).format_scaled(3), "0.005");
+    /// assert_eq!(i128::new(5).format_scaled(-2), "500");
+    /// ```
+    #[cfg(feature="use-std")]
+    pub fn format_scaled(self, scale: i8) -> String {
+        let abs = self.unsigned_abs().format_scaled(scale);
+        if self.is_negative() {
+            format!("-{}", abs)
+        } else {
+            abs
+        }
+    }
+}
+
+#[cfg(test)]
+mod decimal_tests {
+    use i128::{i128, ZERO, ONE, MIN, MAX};
+    use u128::u128;
+    use error;
+
+    #[test]
+    fn test_arrow_bytes() {
+        for &value in &[ZERO, ONE, -ONE, MIN, MAX, i128::from_parts(-0x123456789, 0xabcdef)] {
+            let bytes = value.to_arrow_bytes();
+            assert_eq!(bytes, value.to_le_bytes());
+            assert_eq!(i128::from_arrow_bytes(bytes), value);
+        }
+        assert_eq!(i128::new(-2).to_arrow_bytes()[0], 0xfe);
+    }
+
+    #[test]
+    fn test_fits_decimal_precision() {
+        let max38 = i128(u128::from_str_radix("99999999999999999999999999999999999999", 10).unwrap());
+        assert!(max38.fits_decimal_precision(38));
+        assert!((-max38).fits_decimal_precision(38));
+        assert!(!(max38 + ONE).fits_decimal_precision(38));
+        assert!(!MIN.fits_decimal_precision(38));
+        assert!(ZERO.fits_decimal_precision(1));
+        assert!(i128::new(9).fits_decimal_precision(1));
+        assert!(!i128::new(-10).fits_decimal_precision(1));
+    }
+
+    #[test]
+    #[should_panic(expected="precision must lie in the range")]
+    fn test_fits_decimal_precision_invalid() {
+        ONE.fits_decimal_precision(39);
+    }
+
+    #[test]
+    fn test_rescale_decimal() {
+        assert_eq!(i128::new(-12345).rescale_decimal(2, 4), Some(i128::new(-1234500)));
+        assert_eq!(i128::new(-1234500).rescale_decimal(4, 2), Some(i128::new(-12345)));
+        assert_eq!(i128::new(5).rescale_decimal(0, -1), None);
+        assert_eq!(i128::new(50).rescale_decimal(0, -1), Some(i128::new(5)));
+        assert_eq!(ONE.rescale_decimal(0, 38), Some(i128::from_str_radix("100000000000000000000000000000000000000", 10).unwrap()));
+        assert_eq!(ONE.rescale_decimal(0, 39), None);
+        assert_eq!(ONE.rescale_decimal(100, -100), None);
+        assert_eq!(ZERO.rescale_decimal(-128, 127), Some(ZERO));
+        assert_eq!(MAX.rescale_decimal(127, -128), None);
+    }
+
+    #[test]
+    fn test_from_decimal_str() {
+        assert_eq!(i128::from_decimal_str("123.45", 2), Ok(i128::new(12345)));
+        assert_eq!(i128::from_decimal_str("+.5", 1), Ok(i128::new(5)));
+        assert_eq!(i128::from_decimal_str("-5.", 0), Ok(i128::new(-5)));
+        assert_eq!(i128::from_decimal_str("1.50", 1), Ok(i128::new(15)));
+        assert_eq!(i128::from_decimal_str("1200", -2), Ok(i128::new(12)));
+        assert_eq!(i128::from_decimal_str("-0.000", -5), Ok(ZERO));
+        assert_eq!(i128::from_decimal_str("-1.7014118346046923173168730371588410572800", 38), Ok(MIN));
+        assert_eq!(i128::from_decimal_str("1.7014118346046923173168730371588410572800", 38), Err(error::overflow()));
+        assert_eq!(i128::from_decimal_str("-1.70141183460469231731687303715884105729", 38), Err(error::underflow()));
+        assert_eq!(i128::from_decimal_str("-1.7014118346046923173168730371588410572801", 38), Err(error::invalid_digit()));
+        assert_eq!(i128::from_decimal_str("-170141183460469231731687303715884105728", -39), Err(error::invalid_digit()));
+        assert_eq!(i128::from_decimal_str("1", 39), Err(error::overflow()));
+        assert_eq!(i128::from_decimal_str("", 2), Err(error::empty()));
+        assert_eq!(i128::from_decimal_str("-.", 2), Err(error::invalid_digit()));
+        assert_eq!(i128::from_decimal_str("1.2.3", 2), Err(error::invalid_digit()));
+        assert_eq!(i128::from_decimal_str("1e5", 2), Err(error::invalid_digit()));
+        assert_eq!(i128::from_decimal_str("0.12", 1), Err(error::invalid_digit()));
+    }
+
+    #[test]
+    fn test_format_scaled() {
+        assert_eq!(i128::new(12345).format_scaled(2), "123.45");
+        assert_eq!(i128::new(-12345).format_scaled(5), "-0.12345");
+        assert_eq!(i128::new(-12345).format_scaled(7), "-0.0012345");
+        assert_eq!(ZERO.format_scaled(2), "0.00");
+        assert_eq!(ZERO.format_scaled(-2), "0");
+        assert_eq!(i128::new(-12).format_scaled(-3), "-12000");
+        assert_eq!(MIN.format_scaled(38), "-1.70141183460469231731687303715884105728");
+        for &(value, scale) in &[(MIN, 10), (MAX, 0), (i128::new(-5), 3), (ONE, 38),
+                                 (MIN, -1), (MAX, -1), (MIN + ONE, -128), (-ONE, -39)] {
+            assert_eq!(i128::from_decimal_str(&value.format_scaled(scale), scale), Ok(value));
+        }
     }
 }
 
 //}}}
 
+//{{{ Serde
+
+// Human-readable formats get a decimal string, since many of them (e.g. JSON) cannot represent
+// every 128-bit integer as a number. Binary formats get the 16 bytes in little endian. The `{lo, hi}`
+// struct written by older versions is still accepted, see `serde_legacy`.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+    use serde_crate::{Serialize, Serializer, Deserialize, Deserializer};
+    use serde_crate::de::{self, Visitor, SeqAccess, MapAccess, Unexpected};
+    use serde_legacy::{self, Legacy, CompactVisitor};
+    use u128::u128;
+    use i128::i128;
+    #[cfg(extprim_has_stable_i128)] use compiler_rt::builtins::{U128, I128};
+
+    impl Serialize for i128 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                self.to_le_bytes().serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for i128 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(I128Visitor)
+            } else {
+                CompactVisitor::deserialize(deserializer)
+            }
+        }
+    }
+
+    impl Legacy for i128 {
+        type Part = u64;
+        type Bytes = [u8; 16];
+        const EXPECTING: &'static str = "a signed 128-bit integer";
+
+        fn from_legacy_parts(lo: u64, hi: u64) -> i128 {
+            u128::from_parts(hi, lo).as_i128()
+        }
+
+        fn from_le_bytes(bytes: [u8; 16]) -> i128 {
+            i128::from_le_bytes(bytes)
+        }
+    }
+
+    struct I128Visitor;
+
+    impl<'de> Visitor<'de> for I128Visitor {
+        type Value = i128;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(i128::EXPECTING)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<i128, E> {
+            value.parse().map_err(E::custom)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<i128, E> {
+            Ok(i128::new(value))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<i128, E> {
+            Ok(i128::from_parts(0, value))
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_i128<E: de::Error>(self, value: I128) -> Result<i128, E> {
+            Ok(i128::from_built_in(value))
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_u128<E: de::Error>(self, value: U128) -> Result<i128, E> {
+            if value >> 127 == 0 {
+                Ok(i128::from_built_in(value as I128))
+            } else {
+                Err(E::invalid_value(Unexpected::Other("integer out of range"), &self))
+            }
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<i128, A::Error> {
+            serde_legacy::visit_seq(seq, &self)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<i128, A::Error> {
+            serde_legacy::visit_map(map)
+        }
+
+        fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<i128, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use serde_test::{assert_tokens, assert_de_tokens, assert_de_tokens_error, Configure, Readable, Token};
+    use i128::i128;
+
+    #[test]
+    fn test_readable() {
+        assert_tokens(&i128::new(-1234).readable(), &[Token::Str("-1234")]);
+        assert_tokens(&i128::min_value().readable(), &[Token::Str("-170141183460469231731687303715884105728")]);
+        assert_de_tokens(&i128::new(-1234).readable(), &[Token::I64(-1234)]);
+        assert_de_tokens(&i128::from_parts(0, !0).readable(), &[Token::U64(!0)]);
+        assert_de_tokens_error::<Readable<i128>>(&[Token::Str("1.5")], "invalid digit found in string");
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut tokens = vec![Token::Tuple { len: 16 }, Token::U8(0xfe)];
+        tokens.extend((1..16).map(|_| Token::U8(0xff)));
+        tokens.push(Token::TupleEnd);
+        assert_tokens(&i128::new(-2).compact(), &tokens);
+    }
+
+    #[test]
+    fn test_legacy() {
+        let tokens = [
+            Token::NewtypeStruct { name: "i128" },
+            Token::Struct { name: "u128", len: 2 },
+            Token::Str("lo"), Token::U64(!0 - 1),
+            Token::Str("hi"), Token::U64(!0),
+            Token::StructEnd,
+        ];
+        assert_de_tokens(&i128::new(-2).readable(), &tokens);
+        assert_de_tokens(&i128::new(-2).compact(), &tokens);
+    }
+}
+
+//}}}
@@ -0,0 +1,1422 @@
+//! Signed 256-bit integer.
+//!
+//! `i256` is mainly useful for holding intermediate results which may not fit into an `i128`,
+//! e.g. the full product of two `i128`s (see [`i256::mul_128`]) or the sum of many signed
+//! fixed-point products.
+//!
+//! [`i256::mul_128`]: struct.i256.html#method.mul_128
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::num::ParseIntError;
+use std::ops::*;
+use std::str::FromStr;
+
+#[cfg(feature="rand")] use rand::Rng;
+#[cfg(feature="rand")] use rand::distributions::{Standard, Distribution};
+use num_traits::{Bounded, Zero, One, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv};
+#[cfg(feature="const-traits")] use num_traits::{ConstZero, ConstOne};
+
+use error;
+use format_buffer::FormatBuffer;
+use u128::u128;
+use i128::i128;
+use u256::u256;
+use traits::Wrapping;
+
+//{{{ Structure
+
+/// Number of bits a signed 256-bit number occupies.
+pub const BITS: usize = 256;
+
+/// Number of bytes a signed 256-bit number occupies.
+pub const BYTES: usize = 32;
+
+/// The smallest signed 256-bit integer (`-2**255`).
+pub const MIN: i256 = i256(u256 { lo: ::u128::ZERO, hi: ::i128::MIN.0 });
+
+/// The largest signed 256-bit integer (`2**255 - 1`).
+pub const MAX: i256 = i256(u256 { lo: ::u128::MAX, hi: ::i128::MAX.0 });
+
+/// The constant 0.
+pub const ZERO: i256 = i256(::u256::ZERO);
+
+/// The constant 1.
+pub const ONE: i256 = i256(::u256::ONE);
+
+/// A signed 256-bit number.
+#[derive(Default, Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct i256(
+    #[doc(hidden)]
+    pub u256,
+);
+
+impl i256 {
+    /// Constructs a new 256-bit integer from a signed 128-bit integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new(i128::new(-3)), -i256::new_i64(3));
+    /// ```
+    pub fn new(lo: i128) -> i256 {
+        let hi = if lo.is_negative() { ::u128::MAX } else { ::u128::ZERO };
+        i256(u256 { lo: lo.0, hi: hi })
+    }
+
+    /// Constructs a new 256-bit integer from a signed 64-bit integer.
+    pub fn new_i64(lo: i64) -> i256 {
+        i256::new(i128::new(lo))
+    }
+
+    /// Constructs a new 256-bit integer from the high-128-bit and low-128-bit parts.
+    ///
+    /// The new integer can be considered as `hi * 2**128 + lo`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::i128::i128;
+    /// use extprim::i256::i256;
+    ///
+    /// let number = i256::from_parts(-i128::one(), u128::zero());
+    /// assert_eq!(format!("{}", number), "-340282366920938463463374607431768211456");
+    /// ```
+    pub fn from_parts(hi: i128, lo: u128) -> i256 {
+        i256(u256::from_parts(hi.0, lo))
+    }
+
+    /// Fetches the lower-128-bit of the number.
+    pub fn low128(self) -> u128 {
+        self.0.lo
+    }
+
+    /// Fetches the higher-128-bit of the number.
+    pub fn high128(self) -> i128 {
+        i128(self.0.hi)
+    }
+
+    /// Converts this number to unsigned with wrapping.
+    pub fn as_u256(self) -> u256 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod structure_tests {
+    use u128::u128;
+    use i128::i128;
+    use i256::i256;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(i256::new(i128::new(-1)), i256::from_parts(-i128::one(), u128::max_value()));
+        assert_eq!(i256::new(i128::max_value()), i256::from_parts(i128::zero(), i128::max_value().as_u128()));
+        assert_eq!(i256::new(i128::min_value()).high128(), -i128::one());
+        assert_eq!(i256::new(i128::min_value()).low128(), i128::min_value().as_u128());
+    }
+}
+
+//}}}
+
+//{{{ Rand
+
+#[cfg(feature="rand")]
+impl Distribution<i256> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i256 {
+        i256(rng.gen())
+    }
+}
+
+//}}}
+
+//{{{ Add, Sub
+
+impl i256 {
+    /// Wrapping (modular) addition. Computes `self + other`, wrapping around at the boundary of
+    /// the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-5).wrapping_add(i256::new_i64(6)), i256::one());
+    /// assert_eq!(i256::max_value().wrapping_add(i256::one()), i256::min_value());
+    /// ```
+    pub fn wrapping_add(self, other: i256) -> i256 {
+        i256(self.0.wrapping_add(other.0))
+    }
+
+    /// Wrapping (modular) subtraction. Computes `self - other`, wrapping around at the boundary of
+    /// the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(6).wrapping_sub(i256::new_i64(13)), i256::new_i64(-7));
+    /// assert_eq!(i256::min_value().wrapping_sub(i256::one()), i256::max_value());
+    /// ```
+    pub fn wrapping_sub(self, other: i256) -> i256 {
+        i256(self.0.wrapping_sub(other.0))
+    }
+
+    /// Wrapping (modular) negation. Computes `-self`, wrapping around at the boundary of the type.
+    ///
+    /// The only case where such wrapping can occur is when one negates `MIN` on a signed type
+    /// (where `MIN` is the negative minimal value for the type); this is a positive value that is
+    /// too large to represent in the type. In such a case, this function returns `MIN` itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(7).wrapping_neg(), i256::new_i64(-7));
+    /// assert_eq!(i256::min_value().wrapping_neg(), i256::min_value());
+    /// ```
+    pub fn wrapping_neg(self) -> i256 {
+        i256(self.0.wrapping_neg())
+    }
+
+    /// Calculates `self + other`.
+    ///
+    /// Returns a tuple of the addition along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-5).overflowing_add(i256::new_i64(6)), (i256::one(), false));
+    /// assert_eq!(i256::max_value().overflowing_add(i256::one()), (i256::min_value(), true));
+    /// ```
+    pub fn overflowing_add(self, other: i256) -> (i256, bool) {
+        let left_sign = self.is_negative();
+        let right_sign = other.is_negative();
+        let res = self.wrapping_add(other);
+        let res_sign = res.is_negative();
+        (res, left_sign == right_sign && res_sign != left_sign)
+    }
+
+    /// Calculates `self - other`.
+    ///
+    /// Returns a tuple of the subtraction along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(6).overflowing_sub(i256::new_i64(13)), (i256::new_i64(-7), false));
+    /// assert_eq!(i256::min_value().overflowing_sub(i256::one()), (i256::max_value(), true));
+    /// ```
+    pub fn overflowing_sub(self, other: i256) -> (i256, bool) {
+        let left_sign = self.is_negative();
+        let right_sign = other.is_negative();
+        let res = self.wrapping_sub(other);
+        let res_sign = res.is_negative();
+        (res, left_sign != right_sign && res_sign != left_sign)
+    }
+
+    /// Negates `self`, overflowing if this is equal to the minimum value.
+    ///
+    /// Returns a tuple of the negated version of self along with a boolean indicating whether an
+    /// overflow happened. If self is the minimum value (`i256::MIN`), then the minimum value will
+    /// be returned again and true will be returned for an overflow happening.
+    pub fn overflowing_neg(self) -> (i256, bool) {
+        (self.wrapping_neg(), self == MIN)
+    }
+
+    /// Checked negation. Computes `-self`, returning `None` if `self == MIN`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(7).checked_neg(), Some(i256::new_i64(-7)));
+    /// assert_eq!(i256::min_value().checked_neg(), None);
+    /// ```
+    pub fn checked_neg(self) -> Option<i256> {
+        match self.overflowing_neg() {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Saturating integer addition. Computes `self + other`, saturating at the numeric bounds
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::max_value().saturating_add(i256::one()), i256::max_value());
+    /// assert_eq!(i256::min_value().saturating_add(-i256::one()), i256::min_value());
+    /// ```
+    pub fn saturating_add(self, other: i256) -> i256 {
+        self.checked_add(other).unwrap_or_else(|| {
+            if self.is_negative() { MIN } else { MAX }
+        })
+    }
+
+    /// Saturating integer subtraction. Computes `self - other`, saturating at the numeric bounds
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::min_value().saturating_sub(i256::one()), i256::min_value());
+    /// assert_eq!(i256::max_value().saturating_sub(-i256::one()), i256::max_value());
+    /// ```
+    pub fn saturating_sub(self, other: i256) -> i256 {
+        self.checked_sub(other).unwrap_or_else(|| {
+            if self.is_negative() { MIN } else { MAX }
+        })
+    }
+}
+
+forward_symmetric! {
+    /// Checked integer addition. Computes `self + other`, returning `None` if overflow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-5).checked_add(i256::new_i64(8)), Some(i256::new_i64(3)));
+    /// assert_eq!(i256::max_value().checked_add(i256::one()), None);
+    /// ```
+    impl Add(add, checked_add, wrapping_add, overflowing_add) for i256
+}
+forward_symmetric! {
+    /// Checked integer subtraction. Computes `self - other`, returning `None` if underflow
+    /// occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(5).checked_sub(i256::new_i64(8)), Some(i256::new_i64(-3)));
+    /// assert_eq!(i256::min_value().checked_sub(i256::one()), None);
+    /// ```
+    impl Sub(sub, checked_sub, wrapping_sub, overflowing_sub) for i256
+}
+forward_assign!(AddAssign(add_assign, add) for i256);
+forward_assign!(SubAssign(sub_assign, sub) for i256);
+
+impl Neg for i256 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.checked_neg().unwrap_or_else(|| {
+            #[cfg(feature="overflow-hooks")]
+            ::overflow::record(::overflow::Operation::Neg, "i256");
+            panic!("arithmetic operation overflowed")
+        })
+    }
+}
+
+impl Neg for Wrapping<i256> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Wrapping(self.0.wrapping_neg())
+    }
+}
+
+impl CheckedAdd for i256 {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Self::checked_add(*self, *other)
+    }
+}
+
+impl CheckedSub for i256 {
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Self::checked_sub(*self, *other)
+    }
+}
+
+#[cfg(test)]
+mod add_sub_tests {
+    use u128::u128;
+    use i128::i128;
+    use i256::{i256, ZERO, ONE, MIN, MAX};
+
+    #[test]
+    fn test_add() {
+        assert_eq!(i256::new(i128::max_value()) + ONE, i256::from_parts(i128::zero(), i128::min_value().as_u128()));
+        assert_eq!(i256::new(i128::min_value()) + i256::new(i128::min_value()),
+                    i256::from_parts(-i128::one(), u128::zero()));
+        assert_eq!(MAX.checked_add(ONE), None);
+        assert_eq!(MIN.checked_add(-ONE), None);
+        assert_eq!(MIN.checked_add(MAX), Some(-ONE));
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(ZERO - ONE, -ONE);
+        assert_eq!(MIN.checked_sub(ONE), None);
+        assert_eq!(MAX.checked_sub(-ONE), None);
+        assert_eq!(ZERO.checked_sub(MAX), Some(MIN + ONE));
+        assert_eq!(MIN.wrapping_sub(ONE), MAX);
+    }
+
+    #[test]
+    #[should_panic(expected="arithmetic operation overflowed")]
+    fn test_neg_overflow() {
+        let _ = -MIN;
+    }
+}
+
+//}}}
+
+//{{{ PartialOrd, Ord
+
+impl PartialOrd for i256 {
+    fn partial_cmp(&self, other: &i256) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for i256 {
+    fn cmp(&self, other: &i256) -> Ordering {
+        (self.high128(), self.low128()).cmp(&(other.high128(), other.low128()))
+    }
+}
+
+#[cfg(test)]
+mod cmp_tests {
+    use u128::u128;
+    use i128::i128;
+    use i256::{i256, MIN, MAX, ZERO, ONE};
+
+    #[test]
+    fn test_cmp() {
+        let cases = [
+            MIN,
+            i256::from_parts(-i128::one(), u128::zero()),
+            -ONE,
+            ZERO,
+            ONE,
+            i256::from_parts(i128::one(), u128::zero()),
+            MAX,
+        ];
+        for (i, a) in cases.iter().enumerate() {
+            for (j, b) in cases.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j));
+            }
+        }
+    }
+}
+
+//}}}
+
+//{{{ Not, BitAnd, BitOr, BitXor
+
+impl Not for i256 {
+    type Output = Self;
+    fn not(self) -> Self {
+        i256(!self.0)
+    }
+}
+
+impl BitAnd for i256 {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        i256(self.0 & other.0)
+    }
+}
+
+impl BitOr for i256 {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        i256(self.0 | other.0)
+    }
+}
+
+impl BitXor for i256 {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        i256(self.0 ^ other.0)
+    }
+}
+
+impl Not for Wrapping<i256> {
+    type Output = Self;
+    fn not(self) -> Self {
+        Wrapping(!self.0)
+    }
+}
+
+impl BitAnd for Wrapping<i256> {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Wrapping(self.0 & other.0)
+    }
+}
+
+impl BitOr for Wrapping<i256> {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Wrapping(self.0 | other.0)
+    }
+}
+
+impl BitXor for Wrapping<i256> {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        Wrapping(self.0 ^ other.0)
+    }
+}
+
+forward_assign!(BitAndAssign(bitand_assign, bitand) for i256);
+forward_assign!(BitOrAssign(bitor_assign, bitor) for i256);
+forward_assign!(BitXorAssign(bitxor_assign, bitxor) for i256);
+
+//}}}
+
+//{{{ Shl, Shr
+
+impl i256 {
+    /// Panic-free bitwise shift-left; yields `self << (shift % 256)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::one().wrapping_shl(255), i256::min_value());
+    /// assert_eq!(i256::new_i64(19).wrapping_shl(512), i256::new_i64(19));
+    /// ```
+    pub fn wrapping_shl(self, shift: u32) -> i256 {
+        i256(self.0.wrapping_shl(shift))
+    }
+
+    /// Panic-free arithmetic shift-right; yields `self >> (shift % 256)`. The sign bit is
+    /// extended into the vacated high bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-50).wrapping_shr(2), i256::new_i64(-13));
+    /// assert_eq!(i256::new_i64(19).wrapping_shr(257), i256::new_i64(9));
+    /// ```
+    pub fn wrapping_shr(self, shift: u32) -> i256 {
+        if self.is_negative() {
+            !i256((!self.0).wrapping_shr(shift))
+        } else {
+            i256(self.0.wrapping_shr(shift))
+        }
+    }
+
+    /// Shifts `self` left by `other` bits.
+    ///
+    /// Returns a tuple of the shifted version of self along with a boolean indicating whether the
+    /// shift value was larger than or equal to the number of bits. If the shift value is too
+    /// large, then value is masked by `0xff`, and this value is then used to perform the shift.
+    pub fn overflowing_shl(self, other: u32) -> (i256, bool) {
+        (self.wrapping_shl(other), other >= 256)
+    }
+
+    /// Shifts `self` right by `other` bits.
+    ///
+    /// Returns a tuple of the shifted version of self along with a boolean indicating whether the
+    /// shift value was larger than or equal to the number of bits. If the shift value is too
+    /// large, then value is masked by `0xff`, and this value is then used to perform the shift.
+    pub fn overflowing_shr(self, other: u32) -> (i256, bool) {
+        (self.wrapping_shr(other), other >= 256)
+    }
+}
+
+forward_shift! {
+    /// Checked shift left. Computes `self << other`, returning `None` if the shift is larger than
+    /// or equal to the number of bits in `self` (256).
+    impl Shl(shl, checked_shl, wrapping_shl, overflowing_shl) for i256
+}
+forward_shift! {
+    /// Checked shift right. Computes `self >> other`, returning `None` if the shift is larger than
+    /// or equal to the number of bits in `self` (256).
+    impl Shr(shr, checked_shr, wrapping_shr, overflowing_shr) for i256
+}
+forward_assign!(ShlAssign<u8|u16|u32|u64|usize|i8|i16|i32|i64|isize>(shl_assign, shl) for i256);
+forward_assign!(ShrAssign<u8|u16|u32|u64|usize|i8|i16|i32|i64|isize>(shr_assign, shr) for i256);
+
+#[cfg(test)]
+mod shift_tests {
+    use u128::u128;
+    use i128::i128;
+    use i256::{i256, ONE, MIN};
+
+    #[test]
+    fn test_shr() {
+        assert_eq!(MIN >> 255u32, -ONE);
+        assert_eq!(MIN >> 128u32, i256::new(-i128::one()) << 127u32);
+        assert_eq!(i256::from_parts(-i128::one(), u128::zero()) >> 130u32, i256::new_i64(-1));
+        assert_eq!(i256::from_parts(i128::new(4), u128::zero()) >> 130u32, ONE);
+        assert_eq!(i256::new_i64(-7) >> 1u32, i256::new_i64(-4));
+    }
+
+    #[test]
+    fn test_shl() {
+        assert_eq!(ONE << 255u32, MIN);
+        assert_eq!(i256::new_i64(-1) << 128u32, i256::from_parts(-i128::one(), u128::zero()));
+        assert_eq!(ONE.checked_shl(256), None);
+    }
+}
+
+//}}}
+
+//{{{ Mul
+
+/// Converts a signed integer `x` into the sign (whether it is negative) and the absolute value
+/// `|x|`.
+fn sign_abs(x: i256) -> (bool, u256) {
+    if x.is_negative() {
+        (true, x.0.wrapping_neg())
+    } else {
+        (false, x.0)
+    }
+}
+
+/// Reassembles a sign and absolute value back to a signed integer.
+fn from_sign_abs(sign: bool, abs: u256) -> i256 {
+    i256(if sign { abs.wrapping_neg() } else { abs })
+}
+
+impl i256 {
+    /// Computes the full product of two signed 128-bit integers. The result is never truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::i128::i128;
+    /// use extprim::i256::i256;
+    ///
+    /// let a = i128::min_value();
+    /// assert_eq!(i256::mul_128(a, a), i256::from_parts(i128::from_parts(0x40000000_00000000, 0), u128::zero()));
+    /// assert_eq!(i256::mul_128(a, i128::new(-1)), i256::from_parts(i128::zero(), a.as_u128()));
+    /// ```
+    pub fn mul_128(left: i128, right: i128) -> i256 {
+        let (sa, a) = sign_abs(i256::new(left));
+        let (sb, b) = sign_abs(i256::new(right));
+        from_sign_abs(sa != sb, u256::mul_128(a.lo, b.lo))
+    }
+
+    /// Calculates the multiplication of `self` and `other`.
+    ///
+    /// Returns a tuple of the multiplication along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-6).overflowing_mul(i256::new_i64(11)), (i256::new_i64(-66), false));
+    /// assert_eq!(i256::max_value().overflowing_mul(i256::new_i64(2)), (-i256::new_i64(2), true));
+    /// ```
+    pub fn overflowing_mul(self, other: i256) -> (i256, bool) {
+        if self == ZERO || other == ZERO {
+            return (ZERO, false);
+        }
+
+        let (sa, a) = sign_abs(self);
+        let (sb, b) = sign_abs(other);
+        let res_is_neg = sa != sb;
+
+        let (res, res_overflow) = a.overflowing_mul(b);
+        let res = from_sign_abs(res_is_neg, res);
+        (res, res_overflow || res.is_negative() != res_is_neg)
+    }
+
+    /// Wrapping (modular) multiplication. Computes `self * other`, wrapping around at the boundary
+    /// of the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-6).wrapping_mul(i256::new_i64(11)), i256::new_i64(-66));
+    /// ```
+    pub fn wrapping_mul(self, other: i256) -> i256 {
+        i256(self.0.wrapping_mul(other.0))
+    }
+
+    /// Saturating integer multiplication. Computes `self * other`, saturating at the numeric
+    /// bounds instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::max_value().saturating_mul(i256::new_i64(-2)), i256::min_value());
+    /// ```
+    pub fn saturating_mul(self, other: i256) -> i256 {
+        self.checked_mul(other).unwrap_or_else(|| {
+            if self.is_negative() == other.is_negative() { MAX } else { MIN }
+        })
+    }
+}
+
+forward_symmetric! {
+    /// Checked integer multiplication. Computes `self * other`, returning `None` if underflow or
+    /// overflow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-6).checked_mul(i256::new_i64(11)), Some(i256::new_i64(-66)));
+    /// assert_eq!(i256::min_value().checked_mul(i256::new_i64(-1)), None);
+    /// ```
+    impl Mul(mul, checked_mul, wrapping_mul, overflowing_mul) for i256
+}
+forward_assign!(MulAssign(mul_assign, mul) for i256);
+
+impl CheckedMul for i256 {
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Self::checked_mul(*self, *other)
+    }
+}
+
+#[cfg(test)]
+mod mul_tests {
+    use u128::u128;
+    use i128::i128;
+    use i256::{i256, ONE, MIN, MAX};
+
+    #[test]
+    fn test_mul_128() {
+        let max = i128::max_value();
+        let min = i128::min_value();
+        // (2^127 - 1)^2 = 2^254 - 2^128 + 1
+        assert_eq!(i256::mul_128(max, max), i256::from_parts(i128::from_parts(0x3fffffffffffffff, !0), u128::one()));
+        // -2^127 * (2^127 - 1) = -2^254 + 2^127
+        assert_eq!(i256::mul_128(min, max), i256::from_parts(i128::from_parts(-0x4000000000000000, 0), min.as_u128()));
+        assert_eq!(i256::mul_128(i128::new(-3), i128::new(7)), i256::new_i64(-21));
+        assert_eq!(i256::mul_128(i128::new(-3), i128::new(-7)), i256::new_i64(21));
+        assert_eq!(i256::mul_128(i128::zero(), min), i256::zero());
+    }
+
+    #[test]
+    fn test_mul() {
+        assert_eq!(i256::new_i64(-4) * i256::new_i64(-9), i256::new_i64(36));
+        assert_eq!(MIN * ONE, MIN);
+        assert_eq!(MIN.checked_mul(-ONE), None);
+        assert_eq!(MAX.checked_mul(-ONE), Some(MIN + ONE));
+        assert_eq!((i256::new_i64(1) << 254u32).checked_mul(i256::new_i64(-2)), Some(MIN));
+        assert_eq!((i256::new_i64(1) << 254u32).checked_mul(i256::new_i64(2)), None);
+    }
+}
+
+//}}}
+
+//{{{ Div, Rem
+
+impl i256 {
+    /// Wrapping (modular) division. Computes `self / other`, wrapping around at the boundary of
+    /// the type.
+    ///
+    /// The only case where such wrapping can occur is when one divides `MIN / -1`. In such a case,
+    /// this function returns `MIN` itself.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(100).wrapping_div(i256::new_i64(-8)), i256::new_i64(-12));
+    /// assert_eq!(i256::min_value().wrapping_div(i256::new_i64(-1)), i256::min_value());
+    /// ```
+    pub fn wrapping_div(self, other: i256) -> i256 {
+        let (sa, a) = sign_abs(self);
+        let (sb, b) = sign_abs(other);
+        let res = a.wrapping_div(b);
+        from_sign_abs(sa != sb, res)
+    }
+
+    /// Wrapping (modular) remainder. Computes `self % other`, wrapping around at the boundary of
+    /// the type. For `MIN % -1`, this function returns 0.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(100).wrapping_rem(i256::new_i64(-8)), i256::new_i64(4));
+    /// assert_eq!(i256::new_i64(-100).wrapping_rem(i256::new_i64(8)), i256::new_i64(-4));
+    /// ```
+    pub fn wrapping_rem(self, other: i256) -> i256 {
+        let (sa, a) = sign_abs(self);
+        let (_, b) = sign_abs(other);
+        let res = a.wrapping_rem(b);
+        from_sign_abs(sa, res)
+    }
+
+    /// Calculates the divisor when `self` is divided by `other`.
+    ///
+    /// Returns a tuple of the divisor along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would occur then self is returned.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(100).overflowing_div(i256::new_i64(-8)), (i256::new_i64(-12), false));
+    /// assert_eq!(i256::min_value().overflowing_div(i256::new_i64(-1)), (i256::min_value(), true));
+    /// ```
+    pub fn overflowing_div(self, other: i256) -> (i256, bool) {
+        if self == MIN && other == -ONE {
+            (MIN, true)
+        } else {
+            (self.wrapping_div(other), false)
+        }
+    }
+
+    /// Calculates the remainder when `self` is divided by `other`.
+    ///
+    /// Returns a tuple of the remainder after dividing along with a boolean indicating whether an
+    /// arithmetic overflow would occur. If an overflow would occur then 0 is returned.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(100).overflowing_rem(i256::new_i64(-8)), (i256::new_i64(4), false));
+    /// assert_eq!(i256::min_value().overflowing_rem(i256::new_i64(-1)), (i256::zero(), true));
+    /// ```
+    pub fn overflowing_rem(self, other: i256) -> (i256, bool) {
+        if self == MIN && other == -ONE {
+            (ZERO, true)
+        } else {
+            (self.wrapping_rem(other), false)
+        }
+    }
+
+    /// Checked integer division. Computes `self / other`, returning `None` if `other == 0` or the
+    /// operation results in underflow or overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(100).checked_div(i256::new_i64(-8)), Some(i256::new_i64(-12)));
+    /// assert_eq!(i256::min_value().checked_div(i256::new_i64(-1)), None);
+    /// assert_eq!(i256::new_i64(3).checked_div(i256::zero()), None);
+    /// ```
+    pub fn checked_div(self, other: i256) -> Option<i256> {
+        if other == ZERO || self == MIN && other == -ONE {
+            None
+        } else {
+            Some(self.wrapping_div(other))
+        }
+    }
+
+    /// Checked integer remainder. Computes `self % other`, returning `None` if `other == 0` or the
+    /// operation results in underflow or overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(100).checked_rem(i256::new_i64(-8)), Some(i256::new_i64(4)));
+    /// assert_eq!(i256::min_value().checked_rem(i256::new_i64(-1)), None);
+    /// assert_eq!(i256::new_i64(3).checked_rem(i256::zero()), None);
+    /// ```
+    pub fn checked_rem(self, other: i256) -> Option<i256> {
+        if other == ZERO || self == MIN && other == -ONE {
+            None
+        } else {
+            Some(self.wrapping_rem(other))
+        }
+    }
+}
+
+impl Div for i256 {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        self.wrapping_div(other)
+    }
+}
+
+impl Rem for i256 {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        self.wrapping_rem(other)
+    }
+}
+
+impl Div for Wrapping<i256> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Wrapping(self.0.wrapping_div(other.0))
+    }
+}
+
+impl Rem for Wrapping<i256> {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        Wrapping(self.0.wrapping_rem(other.0))
+    }
+}
+
+forward_assign!(DivAssign(div_assign, div) for i256);
+forward_assign!(RemAssign(rem_assign, rem) for i256);
+
+impl CheckedDiv for i256 {
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        Self::checked_div(*self, *other)
+    }
+}
+
+/// Computes the divisor and remainder simultaneously. Returns `(a/b, a%b)`.
+///
+/// # Panics
+///
+/// This function will panic if `denominator` is 0. If debug assertions is enabled, this function
+/// will also panic on overflow (when computing `div_rem(MIN, -1)`).
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::i256::{div_rem, i256};
+///
+/// assert_eq!(div_rem(i256::new_i64(100), i256::new_i64(-8)), (i256::new_i64(-12), i256::new_i64(4)));
+/// ```
+pub fn div_rem(numerator: i256, denominator: i256) -> (i256, i256) {
+    if cfg!(debug_assertions) && numerator == MIN && denominator == -ONE {
+        panic!("attempted to divide with overflow");
+    }
+    let (sn, n) = sign_abs(numerator);
+    let (sd, d) = sign_abs(denominator);
+    let (div, rem) = ::u256::div_rem(n, d);
+    (from_sign_abs(sn != sd, div), from_sign_abs(sn, rem))
+}
+
+#[cfg(test)]
+mod div_rem_tests {
+    use i128::i128;
+    use i256::{i256, div_rem, ZERO, ONE, MIN, MAX};
+
+    #[test]
+    fn test_overflowing_div_rem() {
+        let ten = i256::new_i64(-10);
+        assert_eq!(MAX.overflowing_div(ten), (MAX / ten, false));
+        assert_eq!(MIN.overflowing_rem(ten), (MIN % ten, false));
+        assert_eq!(MIN.overflowing_div(-ONE), (MIN, true));
+        assert_eq!(MIN.overflowing_rem(-ONE), (ZERO, true));
+    }
+
+    #[test]
+    fn test_div_rem() {
+        assert_eq!(div_rem(MAX, MAX), (ONE, ZERO));
+        assert_eq!(div_rem(MIN, MAX), (-ONE, -ONE));
+        assert_eq!(div_rem(MIN, ONE), (MIN, ZERO));
+        assert_eq!(div_rem(i256::new_i64(-7), i256::new_i64(2)), (i256::new_i64(-3), i256::new_i64(-1)));
+        assert_eq!(div_rem(i256::mul_128(i128::min_value(), i128::new(-9)), i256::new(i128::min_value())),
+                    (i256::new_i64(-9), ZERO));
+    }
+}
+
+//}}}
+
+//{{{ Casting
+
+impl From<i8> for i256 {
+    fn from(arg: i8) -> Self {
+        i256::new_i64(arg as i64)
+    }
+}
+
+impl From<i16> for i256 {
+    fn from(arg: i16) -> Self {
+        i256::new_i64(arg as i64)
+    }
+}
+
+impl From<i32> for i256 {
+    fn from(arg: i32) -> Self {
+        i256::new_i64(arg as i64)
+    }
+}
+
+impl From<i64> for i256 {
+    fn from(arg: i64) -> Self {
+        i256::new_i64(arg)
+    }
+}
+
+impl From<i128> for i256 {
+    fn from(arg: i128) -> Self {
+        i256::new(arg)
+    }
+}
+
+impl From<u128> for i256 {
+    fn from(arg: u128) -> Self {
+        i256(u256::new(arg))
+    }
+}
+
+//}}}
+
+//{{{ Constants
+
+impl i256 {
+    /// Returns the smallest signed 256-bit integer (`-2**255`).
+    pub fn min_value() -> i256 { MIN }
+
+    /// Returns the largest signed 256-bit integer (`2**255 - 1`).
+    pub fn max_value() -> i256 { MAX }
+
+    /// Returns the constant 0.
+    pub fn zero() -> i256 { ZERO }
+
+    /// Returns the constant 1.
+    pub fn one() -> i256 { ONE }
+
+    /// The smallest value that can be represented by this integer type.
+    pub const MIN: i256 = MIN;
+
+    /// The largest value that can be represented by this integer type.
+    pub const MAX: i256 = MAX;
+
+    /// The constant 0.
+    pub const ZERO: i256 = ZERO;
+
+    /// The constant 1.
+    pub const ONE: i256 = ONE;
+}
+
+impl Bounded for i256 {
+    fn min_value() -> Self { MIN }
+    fn max_value() -> Self { MAX }
+}
+
+impl Zero for i256 {
+    fn zero() -> Self { ZERO }
+    fn is_zero(&self) -> bool { *self == ZERO }
+}
+
+impl One for i256 {
+    fn one() -> Self { ONE }
+}
+
+#[cfg(feature="const-traits")]
+impl ConstZero for i256 {
+    const ZERO: Self = ZERO;
+}
+
+#[cfg(feature="const-traits")]
+impl ConstOne for i256 {
+    const ONE: Self = ONE;
+}
+
+//}}}
+
+//{{{ Signed
+
+impl i256 {
+    /// Computes the absolute value of `self`.
+    ///
+    /// # Overflow behavior
+    ///
+    /// The absolute value of `i256::MIN` cannot be represented as an `i256`, and attempting to
+    /// calculate it will cause an overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-10).abs(), i256::new_i64(10));
+    /// ```
+    pub fn abs(self) -> Self {
+        if self.is_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+
+    /// Computes the absolute value of `self` without any wrapping or panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::u256::u256;
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::new_i64(-10).unsigned_abs(), u256::new_u64(10));
+    /// assert_eq!(i256::min_value().unsigned_abs(), u256::from_parts(u128::from_parts(1 << 63, 0), u128::zero()));
+    /// ```
+    pub fn unsigned_abs(self) -> u256 {
+        sign_abs(self).1
+    }
+
+    /// Returns a number representing sign of `self`.
+    ///
+    /// * `0` if the number is zero
+    /// * `1` if the number is positive
+    /// * `-1` if the number is negative
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::max_value().signum(), i256::one());
+    /// assert_eq!(i256::zero().signum(), i256::zero());
+    /// assert_eq!(i256::min_value().signum(), -i256::one());
+    /// ```
+    pub fn signum(self) -> Self {
+        if self.is_negative() {
+            -ONE
+        } else if self == ZERO {
+            ZERO
+        } else {
+            ONE
+        }
+    }
+
+    /// Returns `true` if `self` is positive and `false` if the number is zero or negative.
+    pub fn is_positive(self) -> bool {
+        !self.is_negative() && self != ZERO
+    }
+
+    /// Returns `true` if `self` is negative and `false` if the number is zero or positive.
+    pub fn is_negative(self) -> bool {
+        self.high128().is_negative()
+    }
+}
+
+//}}}
+
+//{{{ FromStr, FromStrRadix
+
+impl i256 {
+    /// Converts a string slice in a given base to an integer.
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(i256::from_str_radix("-ff", 16), Ok(i256::new_i64(-255)));
+    /// ```
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<i256, ParseIntError> {
+        assert!(radix >= 2 && radix <= 36,
+                "from_str_radix_int: must lie in the range `[2, 36]` - found {}",
+                radix);
+
+        let mut src_chars = src.chars();
+        let (is_negative, src) = match src_chars.next() {
+            Some('-') => (true, src_chars.as_str()),
+            Some(_) => (false, src),
+            None => return Err(error::empty()),
+        };
+
+        match u256::from_str_radix(src, radix) {
+            Ok(res) => {
+                let res = from_sign_abs(is_negative, res);
+                if res != ZERO && res.is_negative() != is_negative {
+                    Err(if is_negative {
+                        error::underflow()
+                    } else {
+                        error::overflow()
+                    })
+                } else {
+                    Ok(res)
+                }
+            },
+            Err(e) => {
+                if is_negative && error::is_overflow(&e) {
+                    Err(error::underflow())
+                } else {
+                    Err(e)
+                }
+            },
+        }
+    }
+}
+
+impl FromStr for i256 {
+    type Err = ParseIntError;
+
+    fn from_str(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_radix(src, 10)
+    }
+}
+
+#[cfg(test)]
+mod from_str_tests {
+    use i256::{i256, MIN, MAX};
+    use error;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(MAX), "57896044618658097711785492504343953926634992332820282019728792003956564819967".parse::<i256>());
+        assert_eq!(Ok(MIN), "-57896044618658097711785492504343953926634992332820282019728792003956564819968".parse::<i256>());
+        assert_eq!(Err(error::overflow()), "57896044618658097711785492504343953926634992332820282019728792003956564819968".parse::<i256>());
+        assert_eq!(Err(error::underflow()), "-57896044618658097711785492504343953926634992332820282019728792003956564819969".parse::<i256>());
+        assert_eq!(Ok(i256::new_i64(-12)), "-12".parse::<i256>());
+        assert_eq!(Err(error::empty()), "-".parse::<i256>());
+        assert_eq!(Err(error::empty()), "".parse::<i256>());
+    }
+}
+
+//}}}
+
+//{{{ Binary, LowerHex, UpperHex, String, Show
+
+impl fmt::Binary for i256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl fmt::Octal for i256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl fmt::LowerHex for i256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl fmt::UpperHex for i256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl fmt::Display for i256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if !self.is_negative() {
+            self.0.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; 77];
+            let mut buf = FormatBuffer::new(&mut buffer);
+            write!(&mut buf, "{}", self.0.wrapping_neg())?;
+            formatter.pad_integral(false, "", unsafe { buf.into_str() })
+        }
+    }
+}
+
+impl fmt::Debug for i256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "i256!({})", self)
+    }
+}
+
+#[cfg(test)]
+mod show_tests {
+    use i256::{i256, ZERO, ONE, MIN, MAX};
+
+    #[test]
+    fn test_show() {
+        assert_fmt_eq!("0", 1, "{}", ZERO);
+        assert_fmt_eq!("-1", 2, "{}", -ONE);
+        assert_fmt_eq!("57896044618658097711785492504343953926634992332820282019728792003956564819967", 77, "{}", MAX);
+        assert_fmt_eq!("-57896044618658097711785492504343953926634992332820282019728792003956564819968", 78, "{}", MIN);
+        assert_fmt_eq!("+0012", 5, "{:+05}", i256::new_i64(12));
+        assert_fmt_eq!("i256!(-5)", 9, "{:?}", i256::new_i64(-5));
+        assert_fmt_eq!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", 64, "{:x}", -ONE);
+        assert_fmt_eq!("8000000000000000000000000000000000000000000000000000000000000000", 64, "{:x}", MIN);
+        assert_fmt_eq!("17777777777777777777777777777777777777777777777777777777777777777777777777777777777777", 86, "{:o}", -ONE);
+        assert_fmt_eq!("0o17", 4, "{:#o}", i256::new_i64(15));
+    }
+}
+
+//}}}
+
+//{{{ Serde
+
+// Like `i128`, human-readable formats get a decimal string and binary formats get the 32 bytes in
+// little endian.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+    use serde_crate::{Serialize, Serializer, Deserialize, Deserializer};
+    use serde_crate::de::{self, Visitor, SeqAccess, MapAccess};
+    use serde_legacy::{self, Legacy, CompactVisitor};
+    use traits::ExtInt;
+    use u128::u128;
+    use u256::u256;
+    use i256::i256;
+    #[cfg(extprim_has_stable_i128)] use i128::i128;
+    #[cfg(extprim_has_stable_i128)] use compiler_rt::builtins::{U128, I128};
+
+    impl Serialize for i256 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                ExtInt::to_le_bytes(*self).serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for i256 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(I256Visitor)
+            } else {
+                CompactVisitor::deserialize(deserializer)
+            }
+        }
+    }
+
+    impl Legacy for i256 {
+        type Part = u128;
+        type Bytes = [u8; 32];
+        const EXPECTING: &'static str = "a signed 256-bit integer";
+
+        fn from_legacy_parts(lo: u128, hi: u128) -> i256 {
+            i256(u256::from_parts(hi, lo))
+        }
+
+        fn from_le_bytes(bytes: [u8; 32]) -> i256 {
+            ExtInt::from_le_bytes(bytes)
+        }
+    }
+
+    struct I256Visitor;
+
+    impl<'de> Visitor<'de> for I256Visitor {
+        type Value = i256;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(i256::EXPECTING)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<i256, E> {
+            value.parse().map_err(E::custom)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<i256, E> {
+            Ok(i256::new_i64(value))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<i256, E> {
+            Ok(i256(u256::new_u64(value)))
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_i128<E: de::Error>(self, value: I128) -> Result<i256, E> {
+            Ok(i256::new(i128::from_built_in(value)))
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_u128<E: de::Error>(self, value: U128) -> Result<i256, E> {
+            Ok(i256(u256::new(u128::from_built_in(value))))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<i256, A::Error> {
+            serde_legacy::visit_seq(seq, &self)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<i256, A::Error> {
+            serde_legacy::visit_map(map)
+        }
+
+        fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<i256, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use serde_test::{assert_tokens, assert_de_tokens, assert_de_tokens_error, Configure, Readable, Token};
+    use u128::u128;
+    use i128::i128;
+    use i256::i256;
+
+    #[test]
+    fn test_readable() {
+        assert_tokens(&i256::new_i64(-1234).readable(), &[Token::Str("-1234")]);
+        assert_tokens(&i256::min_value().readable(),
+                      &[Token::Str("-57896044618658097711785492504343953926634992332820282019728792003956564819968")]);
+        assert_de_tokens(&i256::new_i64(-1234).readable(), &[Token::I64(-1234)]);
+        assert_de_tokens(&i256::new_i64(1234).readable(), &[Token::U64(1234)]);
+        assert_de_tokens_error::<Readable<i256>>(&[Token::Str("1.5")], "invalid digit found in string");
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut tokens = vec![Token::Tuple { len: 32 }];
+        tokens.push(Token::U8(0xfe));
+        tokens.extend((1..32).map(|_| Token::U8(0xff)));
+        tokens.push(Token::TupleEnd);
+        assert_tokens(&i256::new_i64(-2).compact(), &tokens);
+    }
+
+    #[test]
+    fn test_legacy() {
+        let value = i256::from_parts(i128::new(-1), u128::new(1));
+        assert_de_tokens(&value.readable(), &[
+            Token::NewtypeStruct { name: "i256" },
+            Token::Struct { name: "u256", len: 2 },
+            Token::Str("lo"), Token::Str("1"),
+            Token::Str("hi"), Token::Str("340282366920938463463374607431768211455"),
+            Token::StructEnd,
+        ]);
+        assert_de_tokens(&value.readable(), &[
+            Token::Seq { len: Some(2) },
+            Token::U64(1),
+            Token::Str("340282366920938463463374607431768211455"),
+            Token::SeqEnd,
+        ]);
+    }
+}
+
+//}}}
@@ -0,0 +1,630 @@
+//! Signed fixed-width integers of arbitrary bit width.
+//!
+//! `Int<BITS, LIMBS>` is the two's complement counterpart of
+//! [`Uint<BITS, LIMBS>`](../uint/struct.Uint.html), with the same restrictions on `LIMBS`.
+//! `Int<128, 2>` converts losslessly from and to [`i128`](../i128/struct.i128.html).
+//!
+//! This module requires Rust 1.57 or above.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::*;
+use std::str::FromStr;
+
+use error;
+use u128::u128;
+use i128::i128;
+use uint::Uint;
+
+//{{{ Structure
+
+/// A signed `BITS`-bit integer in two's complement, stored in `LIMBS` 64-bit limbs.
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Default)]
+pub struct Int<const BITS: usize, const LIMBS: usize>(Uint<BITS, LIMBS>);
+
+/// A 96-bit signed integer.
+pub type I96 = Int<96, 2>;
+/// A 160-bit signed integer.
+pub type I160 = Int<160, 3>;
+/// A 192-bit signed integer.
+pub type I192 = Int<192, 3>;
+/// A 320-bit signed integer.
+pub type I320 = Int<320, 5>;
+/// A 512-bit signed integer.
+pub type I512 = Int<512, 8>;
+
+impl<const BITS: usize, const LIMBS: usize> Int<BITS, LIMBS> {
+    /// Number of bits this integer occupies.
+    pub const BITS: usize = BITS;
+
+    /// The constant 0.
+    pub const ZERO: Self = Int(Uint::ZERO);
+
+    /// The constant 1.
+    pub const ONE: Self = Int(Uint::ONE);
+
+    /// The smallest value (`-2**(BITS-1)`).
+    pub fn min_value() -> Self {
+        Int(Uint::ONE.wrapping_shl(BITS as u32 - 1))
+    }
+
+    /// The largest value (`2**(BITS-1) - 1`).
+    pub fn max_value() -> Self {
+        Int(Uint::MAX.wrapping_shr(1))
+    }
+
+    /// Constructs an integer from the two's complement bit pattern stored in its limbs, least
+    /// significant limb first. Bits above `BITS` are discarded.
+    pub fn from_limbs(limbs: [u64; LIMBS]) -> Self {
+        Int(Uint::from_limbs(limbs))
+    }
+
+    /// Constructs an integer from a 64-bit integer, truncating if `BITS < 64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::int::I96;
+    ///
+    /// assert_eq!(I96::from_i64(-1).as_limbs(), &[!0, 0xffffffff]);
+    /// ```
+    pub fn from_i64(value: i64) -> Self {
+        let mut limbs = [if value < 0 { !0 } else { 0 }; LIMBS];
+        limbs[0] = value as u64;
+        Self::from_limbs(limbs)
+    }
+
+    /// Returns the limbs of the two's complement bit pattern, least significant limb first.
+    pub fn as_limbs(&self) -> &[u64; LIMBS] {
+        self.0.as_limbs()
+    }
+
+    /// Reinterprets the bit pattern as an unsigned integer.
+    pub fn as_uint(self) -> Uint<BITS, LIMBS> {
+        self.0
+    }
+
+    /// Reinterprets the bit pattern of an unsigned integer as signed.
+    pub fn from_uint(value: Uint<BITS, LIMBS>) -> Self {
+        Int(value)
+    }
+
+    /// Returns `true` if `self` is negative and `false` if the number is zero or positive.
+    pub fn is_negative(self) -> bool {
+        let bit = BITS - 1;
+        self.as_limbs()[bit / 64] >> (bit % 64) & 1 != 0
+    }
+
+    /// Returns `true` if `self` is positive and `false` if the number is zero or negative.
+    pub fn is_positive(self) -> bool {
+        !self.is_negative() && !self.0.is_zero()
+    }
+
+    /// Converts into the sign and the absolute value.
+    fn sign_abs(self) -> (bool, Uint<BITS, LIMBS>) {
+        if self.is_negative() {
+            (true, self.0.wrapping_neg())
+        } else {
+            (false, self.0)
+        }
+    }
+
+    /// Reassembles a sign and absolute value back to a signed integer.
+    fn from_sign_abs(sign: bool, abs: Uint<BITS, LIMBS>) -> Self {
+        Int(if sign { abs.wrapping_neg() } else { abs })
+    }
+}
+
+#[cfg(test)]
+mod structure_tests {
+    use int::{Int, I96, I160};
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(I96::max_value().as_limbs(), &[!0, 0x7fffffff]);
+        assert_eq!(I96::min_value().as_limbs(), &[0, 0x80000000]);
+        assert_eq!(I160::from_i64(-2).as_limbs(), &[!1, !0, 0xffffffff]);
+        assert_eq!(Int::<4, 1>::from_i64(-8), Int::<4, 1>::min_value());
+        assert!(I96::min_value().is_negative());
+        assert!(I96::max_value().is_positive());
+        assert!(!I96::ZERO.is_positive() && !I96::ZERO.is_negative());
+    }
+}
+
+//}}}
+
+//{{{ Arithmetic
+
+impl<const BITS: usize, const LIMBS: usize> Int<BITS, LIMBS> {
+    /// Calculates `self + other`.
+    ///
+    /// Returns a tuple of the addition along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::int::I96;
+    ///
+    /// assert_eq!(I96::from_i64(-5).overflowing_add(I96::from_i64(6)), (I96::ONE, false));
+    /// assert_eq!(I96::max_value().overflowing_add(I96::ONE), (I96::min_value(), true));
+    /// ```
+    pub fn overflowing_add(self, other: Self) -> (Self, bool) {
+        let res = Int(self.0.wrapping_add(other.0));
+        let overflow = self.is_negative() == other.is_negative() && res.is_negative() != self.is_negative();
+        (res, overflow)
+    }
+
+    /// Calculates `self - other`.
+    ///
+    /// Returns a tuple of the subtraction along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    pub fn overflowing_sub(self, other: Self) -> (Self, bool) {
+        let res = Int(self.0.wrapping_sub(other.0));
+        let overflow = self.is_negative() != other.is_negative() && res.is_negative() != self.is_negative();
+        (res, overflow)
+    }
+
+    /// Calculates the multiplication of `self` and `other`.
+    ///
+    /// Returns a tuple of the multiplication along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::int::I96;
+    ///
+    /// assert_eq!(I96::from_i64(-6).overflowing_mul(I96::from_i64(11)), (I96::from_i64(-66), false));
+    /// assert_eq!(I96::min_value().overflowing_mul(I96::from_i64(-1)), (I96::min_value(), true));
+    /// ```
+    pub fn overflowing_mul(self, other: Self) -> (Self, bool) {
+        if self.0.is_zero() || other.0.is_zero() {
+            return (Self::ZERO, false);
+        }
+        let (sa, a) = self.sign_abs();
+        let (sb, b) = other.sign_abs();
+        let res_is_neg = sa != sb;
+        let (res, res_overflow) = a.overflowing_mul(b);
+        let res = Self::from_sign_abs(res_is_neg, res);
+        (res, res_overflow || res.is_negative() != res_is_neg)
+    }
+
+    /// Negates `self`, overflowing if this is equal to the minimum value.
+    pub fn overflowing_neg(self) -> (Self, bool) {
+        (Int(self.0.wrapping_neg()), self == Self::min_value())
+    }
+
+    /// Wrapping (modular) addition.
+    pub fn wrapping_add(self, other: Self) -> Self {
+        self.overflowing_add(other).0
+    }
+
+    /// Wrapping (modular) subtraction.
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        self.overflowing_sub(other).0
+    }
+
+    /// Wrapping (modular) multiplication.
+    pub fn wrapping_mul(self, other: Self) -> Self {
+        Int(self.0.wrapping_mul(other.0))
+    }
+
+    /// Wrapping (modular) negation.
+    pub fn wrapping_neg(self) -> Self {
+        self.overflowing_neg().0
+    }
+
+    /// Checked integer addition. Computes `self + other`, returning `None` if overflow occurred.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        match self.overflowing_add(other) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Checked integer subtraction. Computes `self - other`, returning `None` if overflow
+    /// occurred.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.overflowing_sub(other) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Checked integer multiplication. Computes `self * other`, returning `None` if overflow
+    /// occurred.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        match self.overflowing_mul(other) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Checked negation. Computes `-self`, returning `None` if `self == MIN`.
+    pub fn checked_neg(self) -> Option<Self> {
+        match self.overflowing_neg() {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Computes `(self / other, self % other)` with the quotient rounded towards zero, returning
+    /// `None` if `other == 0` or the operation overflows (`MIN / -1`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::int::I96;
+    ///
+    /// assert_eq!(I96::from_i64(-7).checked_div_rem(I96::from_i64(2)), Some((I96::from_i64(-3), I96::from_i64(-1))));
+    /// assert_eq!(I96::min_value().checked_div_rem(I96::from_i64(-1)), None);
+    /// ```
+    pub fn checked_div_rem(self, other: Self) -> Option<(Self, Self)> {
+        if self == Self::min_value() && other == Self::from_i64(-1) {
+            return None;
+        }
+        let (sa, a) = self.sign_abs();
+        let (sb, b) = other.sign_abs();
+        a.checked_div_rem(b).map(|(q, r)| (Self::from_sign_abs(sa != sb, q), Self::from_sign_abs(sa, r)))
+    }
+
+    /// Checked integer division. Computes `self / other`, returning `None` if `other == 0` or the
+    /// operation overflows.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.checked_div_rem(other).map(|(q, _)| q)
+    }
+
+    /// Checked integer remainder. Computes `self % other`, returning `None` if `other == 0` or the
+    /// operation overflows.
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        self.checked_div_rem(other).map(|(_, r)| r)
+    }
+
+    /// Panic-free bitwise shift-left; yields `self << (shift % BITS)`.
+    pub fn wrapping_shl(self, shift: u32) -> Self {
+        Int(self.0.wrapping_shl(shift))
+    }
+
+    /// Panic-free arithmetic shift-right; yields `self >> (shift % BITS)`. The sign bit is
+    /// extended into the vacated high bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::int::I96;
+    ///
+    /// assert_eq!(I96::from_i64(-50).wrapping_shr(2), I96::from_i64(-13));
+    /// ```
+    pub fn wrapping_shr(self, shift: u32) -> Self {
+        if self.is_negative() {
+            Int(!(!self.0).wrapping_shr(shift))
+        } else {
+            Int(self.0.wrapping_shr(shift))
+        }
+    }
+}
+
+macro_rules! impl_arith {
+    ($tn:ident($name:ident, $oname:ident, $wname:ident), $atn:ident($aname:ident)) => {
+        impl<const BITS: usize, const LIMBS: usize> $tn for Int<BITS, LIMBS> {
+            type Output = Self;
+            #[cfg(debug_assertions)]
+            fn $name(self, other: Self) -> Self {
+                match self.$oname(other) {
+                    (v, false) => v,
+                    (_, true) => panic!("arithmetic operation overflowed"),
+                }
+            }
+            #[cfg(not(debug_assertions))]
+            fn $name(self, other: Self) -> Self {
+                self.$wname(other)
+            }
+        }
+
+        impl<const BITS: usize, const LIMBS: usize> $atn for Int<BITS, LIMBS> {
+            fn $aname(&mut self, other: Self) {
+                *self = self.$name(other);
+            }
+        }
+    }
+}
+
+impl_arith!(Add(add, overflowing_add, wrapping_add), AddAssign(add_assign));
+impl_arith!(Sub(sub, overflowing_sub, wrapping_sub), SubAssign(sub_assign));
+impl_arith!(Mul(mul, overflowing_mul, wrapping_mul), MulAssign(mul_assign));
+
+impl<const BITS: usize, const LIMBS: usize> Neg for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.checked_neg().unwrap_or_else(|| panic!("arithmetic operation overflowed"))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Div for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        if other.0.is_zero() {
+            panic!("attempted to divide by zero");
+        }
+        self.checked_div(other).unwrap_or_else(|| panic!("attempted to divide with overflow"))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Rem for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        if other.0.is_zero() {
+            panic!("attempted remainder with a divisor of zero");
+        }
+        self.checked_rem(other).unwrap_or_else(|| panic!("attempted remainder with overflow"))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Shl<u32> for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn shl(self, shift: u32) -> Self {
+        Int(self.0 << shift)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Shr<u32> for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn shr(self, shift: u32) -> Self {
+        if shift as usize >= BITS {
+            panic!("shift operation overflowed");
+        }
+        self.wrapping_shr(shift)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Not for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn not(self) -> Self {
+        Int(!self.0)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> BitAnd for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Int(self.0 & other.0)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> BitOr for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Int(self.0 | other.0)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> BitXor for Int<BITS, LIMBS> {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        Int(self.0 ^ other.0)
+    }
+}
+
+#[cfg(test)]
+mod arith_tests {
+    use int::{Int, I96, I160};
+
+    #[test]
+    fn test_add_sub() {
+        assert_eq!(I96::from_i64(-1) + I96::ONE, I96::ZERO);
+        assert_eq!(I96::max_value().checked_add(I96::ONE), None);
+        assert_eq!(I96::min_value().checked_sub(I96::ONE), None);
+        assert_eq!(I96::min_value().checked_add(I96::max_value()), Some(I96::from_i64(-1)));
+        assert_eq!(I160::ZERO - I160::from_i64(3), I160::from_i64(-3));
+        assert_eq!(Int::<4, 1>::from_i64(7).overflowing_add(Int::<4, 1>::ONE), (Int::<4, 1>::from_i64(-8), true));
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(I160::from_i64(-4) * I160::from_i64(-9), I160::from_i64(36));
+        assert_eq!(I96::from_i64(1 << 47).checked_mul(I96::from_i64(-(1 << 47))), Some(I96::min_value() >> 1));
+        assert_eq!(I96::from_i64(1 << 47).checked_mul(I96::from_i64(1 << 48)), None);
+        assert_eq!(I96::from_i64(1 << 47).checked_mul(I96::from_i64(-(1 << 48))), Some(I96::min_value()));
+        assert_eq!(I160::from_i64(100) / I160::from_i64(-8), I160::from_i64(-12));
+        assert_eq!(I160::from_i64(-100) % I160::from_i64(8), I160::from_i64(-4));
+        assert_eq!(I160::min_value().checked_div(I160::ZERO), None);
+    }
+
+    #[test]
+    fn test_shift() {
+        assert_eq!(I96::min_value() >> 95, I96::from_i64(-1));
+        assert_eq!(I96::from_i64(-1) << 95, I96::min_value());
+        assert_eq!(I96::max_value() >> 94, I96::ONE);
+    }
+
+    #[test]
+    #[should_panic(expected="arithmetic operation overflowed")]
+    fn test_neg_overflow() {
+        let _ = -I96::min_value();
+    }
+}
+
+//}}}
+
+//{{{ PartialOrd, Ord
+
+impl<const BITS: usize, const LIMBS: usize> PartialOrd for Int<BITS, LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Ord for Int<BITS, LIMBS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+//}}}
+
+//{{{ Casting
+
+impl<const BITS: usize, const LIMBS: usize> From<i64> for Int<BITS, LIMBS> {
+    /// Converts a 64-bit integer, truncating if `BITS < 64`.
+    fn from(arg: i64) -> Self {
+        Self::from_i64(arg)
+    }
+}
+
+impl From<i128> for Int<128, 2> {
+    fn from(arg: i128) -> Self {
+        Int(Uint::from(arg.as_u128()))
+    }
+}
+
+impl From<Int<128, 2>> for i128 {
+    fn from(arg: Int<128, 2>) -> Self {
+        i128(u128::from(arg.0))
+    }
+}
+
+#[cfg(test)]
+mod conv_tests {
+    use i128::i128;
+    use int::{Int, I96, I160};
+
+    #[test]
+    fn test_i128() {
+        let a = i128::from_parts(-0x76571c252122c42f, 0x732071c4b48a3b30);
+        let b = Int::<128, 2>::from(a);
+        assert_eq!(b.as_limbs(), &[0x732071c4b48a3b30, 0x89a8e3dadedd3bd1]);
+        assert_eq!(i128::from(b), a);
+        assert!(b < Int::ZERO);
+    }
+
+    #[test]
+    fn test_cmp() {
+        let cases = [I160::min_value(), I160::from_i64(-1), I160::ZERO, I160::ONE, I160::max_value()];
+        for (i, a) in cases.iter().enumerate() {
+            for (j, b) in cases.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j));
+            }
+        }
+        assert!(I96::from_i64(-3) < I96::from_i64(-2));
+    }
+}
+
+//}}}
+
+//{{{ FromStr, FromStrRadix
+
+impl<const BITS: usize, const LIMBS: usize> Int<BITS, LIMBS> {
+    /// Converts a string slice in a given base to an integer.
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::int::I96;
+    ///
+    /// assert_eq!(I96::from_str_radix("-800000000000000000000000", 16), Ok(I96::min_value()));
+    /// ```
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+        let mut src_chars = src.chars();
+        let (is_negative, src) = match src_chars.next() {
+            Some('-') => (true, src_chars.as_str()),
+            Some(_) => (false, src),
+            None => return Err(error::empty()),
+        };
+
+        match Uint::from_str_radix(src, radix) {
+            Ok(res) => {
+                let res = Self::from_sign_abs(is_negative, res);
+                if !res.0.is_zero() && res.is_negative() != is_negative {
+                    Err(if is_negative {
+                        error::underflow()
+                    } else {
+                        error::overflow()
+                    })
+                } else {
+                    Ok(res)
+                }
+            },
+            Err(e) => {
+                if is_negative && error::is_overflow(&e) {
+                    Err(error::underflow())
+                } else {
+                    Err(e)
+                }
+            },
+        }
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> FromStr for Int<BITS, LIMBS> {
+    type Err = ParseIntError;
+
+    fn from_str(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_radix(src, 10)
+    }
+}
+
+//}}}
+
+//{{{ LowerHex, UpperHex, String, Show
+
+impl<const BITS: usize, const LIMBS: usize> fmt::Display for Int<BITS, LIMBS> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let (is_negative, abs) = self.sign_abs();
+        formatter.pad_integral(!is_negative, "", &abs.to_string())
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> fmt::Debug for Int<BITS, LIMBS> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Int<{}>({})", BITS, self)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> fmt::LowerHex for Int<BITS, LIMBS> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> fmt::UpperHex for Int<BITS, LIMBS> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+#[cfg(test)]
+mod show_tests {
+    use int::{I96, Int};
+    use error;
+
+    #[test]
+    fn test_show() {
+        assert_eq!(format!("{}", I96::min_value()), "-39614081257132168796771975168");
+        assert_eq!(format!("{}", I96::max_value()), "39614081257132168796771975167");
+        assert_eq!(format!("{:+05}", I96::from_i64(12)), "+0012");
+        assert_eq!(format!("{:05}", I96::from_i64(-12)), "-0012");
+        assert_eq!(format!("{:?}", I96::from_i64(-1)), "Int<96>(-1)");
+        assert_eq!(format!("{:x}", I96::from_i64(-1)), "ffffffffffffffffffffffff");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("-39614081257132168796771975168".parse::<I96>(), Ok(I96::min_value()));
+        assert_eq!("-39614081257132168796771975169".parse::<I96>(), Err(error::underflow()));
+        assert_eq!("39614081257132168796771975168".parse::<I96>(), Err(error::overflow()));
+        assert_eq!("-".parse::<I96>(), Err(error::empty()));
+        assert_eq!("-8".parse::<Int<4, 1>>(), Ok(Int::<4, 1>::min_value()));
+    }
+}
+
+//}}}
@@ -65,22 +65,87 @@
 
 #![cfg_attr(not(feature="use-std"), no_std)]
 
+#![cfg_attr(clippy, allow(clippy::unnecessary_clippy_cfg, clippy::manual_range_contains, clippy::redundant_field_names))]
+// lints allowed:
+//  - manual_range_contains: `RangeInclusive::contains` requires Rust 1.35, so the range checks are
+//                           written out
+//  - redundant_field_names: struct literals name every field explicitly, as in `u128 { lo: lo, hi: hi }`
+//  - unnecessary_clippy_cfg: tool lints are not recognized before Rust 1.31, so they can only be
+//                            allowed through `cfg_attr`
+
 #[cfg(extprim_channel="unstable")] extern crate test;
 
 #[cfg(feature = "serde")]
-#[macro_use]
-extern crate serde;
+#[cfg_attr(test, macro_use)]
+extern crate serde as serde_crate;
+#[cfg(all(test, feature = "serde"))] extern crate serde_test;
 
 #[cfg(feature="use-std")] extern crate core;
 #[cfg(not(feature="use-std"))] extern crate core as std;
 #[cfg(feature="rand")] extern crate rand;
+#[cfg(feature="primitive-types")] extern crate primitive_types;
+#[cfg(feature="bson")] extern crate bson;
+#[cfg(feature="serde_json")] extern crate serde_json;
+#[cfg(feature="zeroize")] extern crate zeroize;
+#[cfg(feature="borsh")] extern crate borsh;
+#[cfg(feature="rkyv")] extern crate rkyv;
+#[cfg(feature="postgres")] extern crate postgres_types;
+#[cfg(feature="postgres")] extern crate bytes;
+#[cfg(feature="rusqlite")] extern crate rusqlite;
+#[cfg(feature="sqlx")] extern crate sqlx;
+#[cfg(feature="rayon")] extern crate rayon;
+#[cfg(feature="bitvec")] extern crate bitvec;
+#[cfg(feature="subtle")] extern crate subtle;
+#[cfg(feature="num-format")] extern crate num_format;
 extern crate num_traits;
 
+#[macro_use] #[doc(hidden)] pub mod macros;
 #[macro_use] mod forward;
 #[cfg_attr(test, macro_use)] mod format_buffer;
 mod error;
 pub mod traits;
 pub mod u128;
 pub mod i128;
+pub mod u256;
+pub mod i256;
+pub mod modular;
+pub mod rng;
+pub mod lowlevel;
+pub mod accumulator;
+#[cfg(feature="rayon")] pub mod parallel;
+pub mod total;
+pub mod std_compat;
+pub mod radix;
+pub mod fnv;
+pub mod fixed;
+pub mod endian;
+pub mod narrow;
+pub mod f16;
+#[cfg(feature="serde")] pub mod serde;
+#[cfg(feature="serde")] mod serde_legacy;
+#[cfg(feature="capi")] pub mod capi;
+#[cfg(feature="overflow-hooks")] pub mod overflow;
+#[cfg(feature="primitive-types")] mod primitive_types_interop;
+#[cfg(feature="bson")] mod bson_interop;
+#[cfg(feature="zeroize")] mod zeroize_interop;
+#[cfg(feature="subtle")] mod subtle_interop;
+#[cfg(feature="borsh")] mod borsh_interop;
+#[cfg(feature="rkyv")] mod rkyv_interop;
+#[cfg(any(feature="postgres", feature="sqlx"))] mod pg_numeric;
+#[cfg(feature="postgres")] mod postgres_interop;
+#[cfg(feature="rusqlite")] mod rusqlite_interop;
+#[cfg(feature="sqlx")] mod sqlx_interop;
+#[cfg(feature="bitvec")] mod bitvec_interop;
+#[cfg(feature="num-format")] mod num_format_interop;
+#[cfg(extprim_has_const_generics)] pub mod uint;
+#[cfg(extprim_has_const_generics)] pub mod int;
+pub mod flags;
+pub mod bitset;
 mod compiler_rt;
+mod cpu;
+
+pub use error::ParseScientificError;
+#[cfg(feature="bson")] pub use error::Decimal128Error;
+#[cfg(any(feature="postgres", feature="sqlx"))] pub use error::NumericError;
+#[cfg(feature="rkyv")] pub use rkyv_interop::{ArchivedU128, ArchivedI128};
 
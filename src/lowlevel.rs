@@ -0,0 +1,198 @@
+//! Low-level arithmetic kernels.
+//!
+//! These are the building blocks used to implement the 128-bit types, exposed so that other
+//! wide-integer or bignum implementations can reuse them. They operate on the raw 64-bit words
+//! where possible and never allocate.
+//!
+//! ```rust
+//! use extprim::u128::u128;
+//! use extprim::lowlevel;
+//!
+//! let product = lowlevel::u64_long_mul(0xffff_ffff_ffff_ffff, 2);
+//! assert_eq!(product, u128::from_parts(1, 0xffff_ffff_ffff_fffe));
+//!
+//! let (q, r) = lowlevel::udivmod128(product, u128::new(3));
+//! assert_eq!(q * u128::new(3) + r, product);
+//! ```
+
+use u128::{u128, ZERO};
+use u256::u256;
+use compiler_rt;
+
+/// Computes the full 128-bit product of two 64-bit numbers.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::lowlevel::u64_long_mul;
+///
+/// assert_eq!(u64_long_mul(!0, !0), u128::from_parts(!0 - 1, 1));
+/// ```
+pub fn u64_long_mul(left: u64, right: u64) -> u128 {
+    ::u128::u64_long_mul(left, right)
+}
+
+/// Computes the full 256-bit product of two 128-bit numbers, returned as `(high, low)` halves.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::lowlevel::u128_long_mul;
+///
+/// let max = u128::max_value();
+/// assert_eq!(u128_long_mul(max, max), (max - u128::one(), u128::one()));
+/// ```
+pub fn u128_long_mul(left: u128, right: u128) -> (u128, u128) {
+    let product = u256::mul_128(left, right);
+    (product.high128(), product.low128())
+}
+
+/// Computes `n / d`.
+///
+/// # Panics
+///
+/// This function will panic if `d` is 0.
+pub fn udiv128(n: u128, d: u128) -> u128 {
+    check_divisor(d);
+    compiler_rt::udiv128(n, d)
+}
+
+/// Computes `n % d`.
+///
+/// # Panics
+///
+/// This function will panic if `d` is 0.
+pub fn umod128(n: u128, d: u128) -> u128 {
+    check_divisor(d);
+    compiler_rt::umod128(n, d)
+}
+
+/// Computes `(n / d, n % d)` in a single pass.
+///
+/// On 64-bit Unix platforms this calls the `__udivmodti4` routine of the system compiler-rt,
+/// otherwise it uses a port of the same algorithm.
+///
+/// # Panics
+///
+/// This function will panic if `d` is 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::lowlevel::udivmod128;
+///
+/// let n = u128::from_parts(7, 5);
+/// assert_eq!(udivmod128(n, u128::from_parts(1, 0)), (u128::new(7), u128::new(5)));
+/// ```
+pub fn udivmod128(n: u128, d: u128) -> (u128, u128) {
+    check_divisor(d);
+    compiler_rt::udivmod128(n, d)
+}
+
+fn check_divisor(d: u128) {
+    if d == ZERO {
+        panic!("attempted to divide by zero");
+    }
+}
+
+/// Shifts the 128-bit concatenation `hi:lo` left by `shift` bits, and returns the upper 64 bits.
+///
+/// This is the "funnel shift" used to shift multi-limb numbers, where `lo` is the next less
+/// significant limb.
+///
+/// # Panics
+///
+/// This function will panic if `shift` is 64 or larger.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::lowlevel::funnel_shl;
+///
+/// assert_eq!(funnel_shl(0x1234, 0xf000_0000_0000_0000, 4), 0x1234f);
+/// assert_eq!(funnel_shl(0x1234, 0xf000_0000_0000_0000, 0), 0x1234);
+/// ```
+pub fn funnel_shl(hi: u64, lo: u64, shift: u32) -> u64 {
+    assert!(shift < 64, "shift must be less than 64");
+    if shift == 0 {
+        hi
+    } else {
+        hi << shift | lo >> (64 - shift)
+    }
+}
+
+/// Shifts the 128-bit concatenation `hi:lo` right by `shift` bits, and returns the lower 64 bits.
+///
+/// This is the "funnel shift" used to shift multi-limb numbers, where `hi` is the next more
+/// significant limb.
+///
+/// # Panics
+///
+/// This function will panic if `shift` is 64 or larger.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::lowlevel::funnel_shr;
+///
+/// assert_eq!(funnel_shr(0xf, 0x1234_0000_0000_0000, 4), 0xf123_4000_0000_0000);
+/// assert_eq!(funnel_shr(0xf, 0x1234, 0), 0x1234);
+/// ```
+pub fn funnel_shr(hi: u64, lo: u64, shift: u32) -> u64 {
+    assert!(shift < 64, "shift must be less than 64");
+    if shift == 0 {
+        lo
+    } else {
+        lo >> shift | hi << (64 - shift)
+    }
+}
+
+#[cfg(test)]
+mod lowlevel_tests {
+    use u128::u128;
+    use lowlevel::*;
+
+    #[test]
+    fn test_mul() {
+        assert_eq!(u64_long_mul(0, !0), u128::zero());
+        assert_eq!(u64_long_mul(0x1234_5678_9abc_def0, 0x0fed_cba9_8765_4321),
+                   u128::from_parts(0x0121fa00ad77d742, 0x2236d88fe5618cf0));
+        assert_eq!(u128_long_mul(u128::from_parts(1, 0), u128::from_parts(1, 0)),
+                   (u128::one(), u128::zero()));
+    }
+
+    #[test]
+    fn test_div() {
+        let n = u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152);
+        let d = u128::from_parts(0, 0x1_0000_0001);
+        let (q, r) = udivmod128(n, d);
+        assert_eq!(q, udiv128(n, d));
+        assert_eq!(r, umod128(n, d));
+        assert_eq!(q * d + r, n);
+        assert!(r < d);
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_div_by_zero() {
+        udivmod128(u128::one(), u128::zero());
+    }
+
+    #[test]
+    fn test_funnel_shift() {
+        for shift in 0..64 {
+            let value = u128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+            assert_eq!(funnel_shl(value.high64(), value.low64(), shift), (value << shift as usize).high64());
+            assert_eq!(funnel_shr(value.high64(), value.low64(), shift), (value >> shift as usize).low64());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="shift must be less than 64")]
+    fn test_funnel_shift_too_large() {
+        funnel_shl(1, 1, 64);
+    }
+}
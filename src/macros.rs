@@ -0,0 +1,326 @@
+//! Public helper macros.
+
+/// Evaluates an arithmetic expression with every operator replaced by its checked form, returning
+/// `None` if any intermediate step overflows or divides by zero.
+///
+/// The operators `+`, `-`, `*`, `/`, `%` and unary `-` are supported, following the usual
+/// precedence. Each operand must be a single token tree — an identifier, a parenthesized
+/// sub-expression, or a block. Wrap more complex operands such as method calls or field accesses
+/// in braces, e.g. `checked_expr!({x.abs()} * y)`.
+///
+/// The operands must be all `u128` or all `i128`. This is equivalent to wrapping every operand
+/// in [`Checked`](traits/struct.Checked.html) and calling `.get()` on the result.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use] extern crate extprim;
+/// use extprim::u128::u128;
+///
+/// # fn main() {
+/// let a = u128::new(6);
+/// let b = u128::new(7);
+/// let c = u128::new(3);
+/// assert_eq!(checked_expr!(a * b + c - (b / c)), Some(u128::new(43)));
+/// assert_eq!(checked_expr!(c - b * a), None);
+/// assert_eq!(checked_expr!({u128::max_value()} * c / c), None);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! checked_expr {
+    (@munch ($($out:tt)*)) => {
+        ($($out)*)
+    };
+    (@munch ($($out:tt)*) + $($rest:tt)*) => {
+        checked_expr!(@munch ($($out)* +) $($rest)*)
+    };
+    (@munch ($($out:tt)*) - $($rest:tt)*) => {
+        checked_expr!(@munch ($($out)* -) $($rest)*)
+    };
+    (@munch ($($out:tt)*) * $($rest:tt)*) => {
+        checked_expr!(@munch ($($out)* *) $($rest)*)
+    };
+    (@munch ($($out:tt)*) / $($rest:tt)*) => {
+        checked_expr!(@munch ($($out)* /) $($rest)*)
+    };
+    (@munch ($($out:tt)*) % $($rest:tt)*) => {
+        checked_expr!(@munch ($($out)* %) $($rest)*)
+    };
+    (@munch ($($out:tt)*) ($($inner:tt)+) $($rest:tt)*) => {
+        checked_expr!(@munch ($($out)* checked_expr!(@munch () $($inner)+)) $($rest)*)
+    };
+    (@munch ($($out:tt)*) $operand:tt $($rest:tt)*) => {
+        checked_expr!(@munch ($($out)* $crate::traits::Checked::from($operand)) $($rest)*)
+    };
+    ($($expr:tt)+) => {
+        checked_expr!(@munch () $($expr)+).get()
+    };
+}
+
+/// Divides a `u128` by a constant `u64` divisor using a multiplication and shifts.
+///
+/// The multiplier for the divisor is computed at compile time, giving constant-divisor division
+/// performance comparable to what the compiler generates for the primitive types. The divisor must
+/// be a constant expression; use [`ConstDivisor`](u128/struct.ConstDivisor.html) directly for
+/// `u128` divisors, or for the remainder.
+///
+/// # Panics
+///
+/// Dividing by 0 is a compile error.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use] extern crate extprim;
+/// use extprim::u128::u128;
+///
+/// # fn main() {
+/// let n = u128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+/// assert_eq!(div_by_const!(n, 1_000_000_007u64), n / u128::new(1_000_000_007));
+/// # }
+/// ```
+#[cfg(extprim_has_const_fn)]
+#[macro_export]
+macro_rules! div_by_const {
+    ($x:expr, $d:expr) => {{
+        const DIVISOR: $crate::u128::ConstDivisor =
+            $crate::u128::ConstDivisor::new($crate::u128::u128::new($d));
+        DIVISOR.div($x)
+    }};
+}
+
+/// Divides a `u128` by a constant `u64` divisor using a multiplication and shifts.
+///
+/// This compiler does not support `const fn`, so the multiplier is computed at run time.
+#[cfg(not(extprim_has_const_fn))]
+#[macro_export]
+macro_rules! div_by_const {
+    ($x:expr, $d:expr) => {{
+        let divisor = $crate::u128::ConstDivisor::new($crate::u128::u128::new($d));
+        divisor.div($x)
+    }};
+}
+
+// The items used by the exported macros, through `$crate::macros::`.
+#[doc(hidden)]
+pub use std::{fmt, ops, str};
+#[doc(hidden)]
+pub use num_traits::ToPrimitive;
+
+/// Defines a newtype over `u128` or `i128` representing a quantity in some unit, such as an amount
+/// of wei or a duration in nanoseconds.
+///
+/// The generated type derives `Copy`, `Clone`, `Debug`, `Default`, `Hash` and the comparison
+/// traits, and implements `Display` and `FromStr` by delegating to the wrapped integer. It can be
+/// added to and subtracted from values of the same type only, so quantities of different units
+/// cannot be mixed up accidentally. The `+` and `-` operators overflow like those of the wrapped
+/// integer; the methods `checked_add` and `checked_sub` are provided as well.
+///
+/// The value is accessed through the generated `new` and `get` methods.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use] extern crate extprim;
+/// use extprim::u128::u128;
+///
+/// extprim_newtype! {
+///     /// An amount of Ether, in wei.
+///     pub struct Wei(u128);
+/// }
+///
+/// # fn main() {
+/// let balance: Wei = "1000000000000000000".parse().unwrap();
+/// let fee = Wei::new(u128::new(21_000));
+/// assert_eq!((balance - fee).to_string(), "999999999999979000");
+/// assert!(fee < balance);
+/// assert_eq!(fee.checked_sub(balance), None);
+/// assert_eq!(fee.get(), u128::new(21_000));
+/// # }
+/// ```
+///
+/// Values of different units cannot be combined:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate extprim;
+/// use extprim::i128::i128;
+///
+/// extprim_newtype!(pub struct Nanoseconds(i128););
+/// extprim_newtype!(pub struct BasisPoints(i128););
+///
+/// # fn main() {
+/// let _ = Nanoseconds::new(i128::new(5)) + BasisPoints::new(i128::new(5));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! extprim_newtype {
+    ($(#[$attr:meta])* pub struct $name:ident($inner:ty);) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+        pub struct $name($inner);
+        extprim_newtype!(@impl $name($inner));
+    };
+    ($(#[$attr:meta])* struct $name:ident($inner:ty);) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+        struct $name($inner);
+        extprim_newtype!(@impl $name($inner));
+    };
+    (@impl $name:ident($inner:ty)) => {
+        #[allow(dead_code)]
+        impl $name {
+            /// Wraps a raw value.
+            pub fn new(value: $inner) -> $name {
+                $name(value)
+            }
+
+            /// Returns the raw value.
+            pub fn get(self) -> $inner {
+                self.0
+            }
+
+            /// Checked addition. Computes `self + other`, returning `None` if overflow occurred.
+            pub fn checked_add(self, other: $name) -> Option<$name> {
+                self.0.checked_add(other.0).map($name)
+            }
+
+            /// Checked subtraction. Computes `self - other`, returning `None` if overflow
+            /// occurred.
+            pub fn checked_sub(self, other: $name) -> Option<$name> {
+                self.0.checked_sub(other.0).map($name)
+            }
+        }
+
+        impl $crate::macros::ops::Add for $name {
+            type Output = $name;
+            fn add(self, other: $name) -> $name {
+                $name(self.0 + other.0)
+            }
+        }
+
+        impl $crate::macros::ops::Sub for $name {
+            type Output = $name;
+            fn sub(self, other: $name) -> $name {
+                $name(self.0 - other.0)
+            }
+        }
+
+        impl $crate::macros::ops::AddAssign for $name {
+            fn add_assign(&mut self, other: $name) {
+                self.0 = self.0 + other.0;
+            }
+        }
+
+        impl $crate::macros::ops::SubAssign for $name {
+            fn sub_assign(&mut self, other: $name) {
+                self.0 = self.0 - other.0;
+            }
+        }
+
+        impl $crate::macros::fmt::Display for $name {
+            fn fmt(&self, formatter: &mut $crate::macros::fmt::Formatter) -> $crate::macros::fmt::Result {
+                $crate::macros::fmt::Display::fmt(&self.0, formatter)
+            }
+        }
+
+        impl $crate::macros::str::FromStr for $name {
+            type Err = <$inner as $crate::macros::str::FromStr>::Err;
+            fn from_str(src: &str) -> Result<$name, Self::Err> {
+                <$inner as $crate::macros::str::FromStr>::from_str(src).map($name)
+            }
+        }
+    };
+}
+
+/// Defines a method as a `const fn` when the compiler supports `const fn` with branches and
+/// panics, and as a normal function otherwise.
+macro_rules! const_fn {
+    ($(#[$attr:meta])* pub fn $name:ident($($args:tt)*) -> $ret:ty $body:block) => {
+        $(#[$attr])*
+        #[cfg(extprim_has_const_fn)]
+        pub const fn $name($($args)*) -> $ret $body
+
+        $(#[$attr])*
+        #[cfg(not(extprim_has_const_fn))]
+        pub fn $name($($args)*) -> $ret $body
+    }
+}
+
+#[cfg(test)]
+mod checked_expr_tests {
+    use u128::u128;
+    use i128::i128;
+
+    #[test]
+    fn test_unsigned() {
+        let a = u128::new(10);
+        let b = u128::new(4);
+        let zero = u128::zero();
+        assert_eq!(checked_expr!(a), Some(a));
+        assert_eq!(checked_expr!(a + b * b), Some(u128::new(26)));
+        assert_eq!(checked_expr!((a + b) * b), Some(u128::new(56)));
+        assert_eq!(checked_expr!(a % b - b / b), Some(u128::one()));
+        assert_eq!(checked_expr!(a / (b - b)), None);
+        assert_eq!(checked_expr!(b - a + a), None);
+        assert_eq!(checked_expr!(a % zero), None);
+        assert_eq!(checked_expr!(((a))), Some(a));
+        assert_eq!(checked_expr!({u128::max_value()} - a + b * ((b - b) + b / b)), Some(u128::max_value() - u128::new(6)));
+        assert_eq!(checked_expr!({u128::max_value()} + {u128::new(1)}), None);
+    }
+
+    #[test]
+    fn test_signed() {
+        let a = i128::new(-10);
+        let b = i128::new(4);
+        let min = i128::min_value();
+        assert_eq!(checked_expr!(-a * b), Some(i128::new(40)));
+        assert_eq!(checked_expr!(a - -b), Some(i128::new(-6)));
+        assert_eq!(checked_expr!(-min), None);
+        assert_eq!(checked_expr!(min / {i128::new(-1)}), None);
+        assert_eq!(checked_expr!(min + b - b), Some(min));
+    }
+}
+
+#[cfg(test)]
+mod newtype_tests {
+    use std::str::FromStr;
+    use u128::u128;
+    use i128::i128;
+
+    extprim_newtype! {
+        /// A test quantity.
+        pub struct Wei(u128);
+    }
+    extprim_newtype!(struct Nanoseconds(i128););
+
+    #[test]
+    fn test_unsigned() {
+        let mut a = Wei::new(u128::new(10));
+        let b = Wei::new(u128::new(4));
+        assert_eq!(a + b, Wei::new(u128::new(14)));
+        assert_eq!(a - b, Wei::new(u128::new(6)));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(Wei::new(u128::max_value()).checked_add(b), None);
+        a += b;
+        a -= Wei::new(u128::one());
+        assert_eq!(a.get(), u128::new(13));
+        assert!(b < a);
+        assert_eq!(Wei::default(), Wei::new(u128::zero()));
+        assert_eq!(a.to_string(), "13");
+        assert_eq!(format!("{:?}", a), "Wei(u128!(13))");
+        assert_eq!(Wei::from_str("340282366920938463463374607431768211455"), Ok(Wei::new(u128::max_value())));
+        assert!(Wei::from_str("-1").is_err());
+    }
+
+    #[test]
+    fn test_signed() {
+        let a = Nanoseconds::new(i128::new(-5));
+        let b = Nanoseconds::new(i128::new(7));
+        assert_eq!(a + b, Nanoseconds::new(i128::new(2)));
+        assert_eq!(a - b, Nanoseconds::new(i128::new(-12)));
+        assert_eq!(Nanoseconds::new(i128::min_value()).checked_sub(b), None);
+        assert!(a < b);
+        assert_eq!(a.to_string(), "-5");
+        assert_eq!("-5".parse(), Ok(a));
+    }
+}
@@ -0,0 +1,671 @@
+//! Modular arithmetic over a runtime modulus.
+//!
+//! The [`Zn`](struct.Zn.html) type pairs a residue with its modulus, so that the standard
+//! operators `+`, `-` and `*` automatically reduce their result.
+//!
+//! ```rust
+//! use extprim::u128::u128;
+//! use extprim::modular::Zn;
+//!
+//! let m = u128::new(1000000007);
+//! let a = Zn::new(u128::new(1000000000), m);
+//! let b = Zn::new(u128::new(12), m);
+//! assert_eq!((a + b).value(), u128::new(5));
+//! assert_eq!((b - a).value(), u128::new(19));
+//! assert_eq!((a * b).value(), u128::new(999999923));
+//! assert_eq!(b.pow(u128::new(2)).value(), u128::new(144));
+//! ```
+//!
+//! [`Barrett`](struct.Barrett.html) speeds up repeated reductions modulo the same number, and
+//! [`reduce_mersenne`](fn.reduce_mersenne.html) reduces modulo numbers of the special form
+//! 2<sup>k</sup> - c without any multiplication by a reciprocal. The free functions [`crt`](fn.crt.html) and [`discrete_log`](fn.discrete_log.html) solve systems
+//! of congruences and discrete logarithms.
+
+#[cfg(feature="use-std")] use std::collections::HashMap;
+use std::fmt;
+use std::ops::*;
+
+use u128::u128;
+#[cfg(feature="use-std")] use u128::range;
+use u256::u256;
+
+//{{{ Structure
+
+/// An element of the ring of integers modulo `n`, where `n` is chosen at runtime.
+///
+/// Operations between two `Zn` values require both to share the same modulus, and will panic
+/// otherwise.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Zn {
+    value: u128,
+    modulus: u128,
+}
+
+impl Zn {
+    /// Creates a new element by reducing `value` modulo `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
+    pub fn new(value: u128, modulus: u128) -> Zn {
+        assert!(modulus != u128::zero(), "modulus must not be zero");
+        Zn { value: value % modulus, modulus: modulus }
+    }
+
+    /// Returns the reduced value, which always lies in `0 .. modulus`.
+    pub fn value(self) -> u128 {
+        self.value
+    }
+
+    /// Returns the modulus of this element.
+    pub fn modulus(self) -> u128 {
+        self.modulus
+    }
+
+    /// Creates another element sharing the same modulus as `self`.
+    pub fn with_value(self, value: u128) -> Zn {
+        Zn { value: value % self.modulus, modulus: self.modulus }
+    }
+
+    /// Raises `self` to the power of `exp`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::modular::Zn;
+    ///
+    /// let a = Zn::new(u128::new(3), u128::new(1000000007));
+    /// assert_eq!(a.pow(u128::new(200)).value(), u128::new(136318165));
+    /// ```
+    pub fn pow(self, exp: u128) -> Zn {
+        self.with_value(self.value.pow_mod(exp, self.modulus))
+    }
+
+    /// Computes the multiplicative inverse of `self`. Returns `None` if the value is not coprime
+    /// with the modulus.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::modular::Zn;
+    ///
+    /// let a = Zn::new(u128::new(3), u128::new(7));
+    /// assert_eq!(a.inv(), Some(Zn::new(u128::new(5), u128::new(7))));
+    /// assert_eq!(Zn::new(u128::new(2), u128::new(4)).inv(), None);
+    /// ```
+    pub fn inv(self) -> Option<Zn> {
+        self.value.inv_mod(self.modulus).map(|v| self.with_value(v))
+    }
+
+    fn check_modulus(self, other: Zn) {
+        assert!(self.modulus == other.modulus, "mismatched moduli");
+    }
+}
+
+impl fmt::Display for Zn {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+//}}}
+
+//{{{ Operators
+
+macro_rules! impl_zn_op {
+    ($tr:ident, $meth:ident, $tr_assign:ident, $meth_assign:ident, $prim:ident) => {
+        impl $tr for Zn {
+            type Output = Zn;
+            fn $meth(self, other: Zn) -> Zn {
+                self.check_modulus(other);
+                Zn { value: self.value.$prim(other.value, self.modulus), modulus: self.modulus }
+            }
+        }
+
+        impl $tr<u128> for Zn {
+            type Output = Zn;
+            fn $meth(self, other: u128) -> Zn {
+                Zn { value: self.value.$prim(other, self.modulus), modulus: self.modulus }
+            }
+        }
+
+        impl $tr_assign for Zn {
+            fn $meth_assign(&mut self, other: Zn) {
+                *self = self.$meth(other);
+            }
+        }
+
+        impl $tr_assign<u128> for Zn {
+            fn $meth_assign(&mut self, other: u128) {
+                *self = self.$meth(other);
+            }
+        }
+    }
+}
+
+impl_zn_op!(Add, add, AddAssign, add_assign, add_mod);
+impl_zn_op!(Sub, sub, SubAssign, sub_assign, sub_mod);
+impl_zn_op!(Mul, mul, MulAssign, mul_assign, mul_mod);
+
+impl Neg for Zn {
+    type Output = Zn;
+    fn neg(self) -> Zn {
+        Zn { value: u128::zero().sub_mod(self.value, self.modulus), modulus: self.modulus }
+    }
+}
+
+#[cfg(test)]
+mod zn_tests {
+    use u128::u128;
+    use modular::Zn;
+
+    const P: u128 = u128 { lo: 0xffffffffffffff61, hi: !0 };
+    const A: u128 = u128 { lo: 0x8b704240ca1021b8, hi: 0x35d2c4473082b8c1 };
+    const B: u128 = u128 { lo: 0x8cdf8e3b4b75c4d0, hi: 0x76571c252122c42e };
+
+    #[test]
+    fn test_ops() {
+        let a = Zn::new(A, P);
+        let b = Zn::new(B, P);
+        assert_eq!((a * b).value(), u128::from_parts(0xfbdd95f8f3585804, 0xd268ac80c6dbe1e9));
+        assert_eq!((a + b + u128::max_value()).value(),
+                   u128::from_parts(0xac29e06c51a57cf0, 0x184fd07c1585e726));
+        assert_eq!((b - a).value(), u128::from_parts(0x408457ddf0a00b6d, 0x016f4bfa8165a318));
+        assert_eq!(a.pow(B).value(), u128::from_parts(0xdcb309f8e89c4c89, 0xf65b62b86e592083));
+        assert_eq!(a - b + b, a);
+        assert_eq!(-a + a, Zn::new(u128::zero(), P));
+        assert_eq!(a * a.inv().unwrap(), Zn::new(u128::one(), P));
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut x = Zn::new(u128::new(5), u128::new(7));
+        x += u128::new(4);
+        assert_eq!(x.value(), u128::new(2));
+        x -= Zn::new(u128::new(3), u128::new(7));
+        assert_eq!(x.value(), u128::new(6));
+        x *= u128::new(6);
+        assert_eq!(x.value(), u128::new(1));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Zn::new(u128::new(10), u128::new(7))), "3 (mod 7)");
+    }
+
+    #[test]
+    #[should_panic(expected="mismatched moduli")]
+    fn test_mismatched_moduli() {
+        let _ = Zn::new(u128::one(), u128::new(7)) + Zn::new(u128::one(), u128::new(11));
+    }
+
+    #[test]
+    #[should_panic(expected="modulus must not be zero")]
+    fn test_zero_modulus() {
+        Zn::new(u128::one(), u128::zero());
+    }
+}
+
+//}}}
+
+//{{{ Barrett reduction
+
+/// A precomputed reciprocal of a fixed modulus, for reducing many numbers without division.
+///
+/// Reducing a 256-bit number with the `%` operator runs a long division every time. `Barrett`
+/// instead precomputes the modulus shifted to have its top bit set, `d`, together with the
+/// reciprocal `⌊(2^256 - 1) / d⌋ - 2^128`, so that each reduction only takes a few
+/// multiplications (the method of Möller and Granlund, a refinement of Barrett reduction). Any
+/// non-zero modulus is supported, whether odd or even, and the operands need no conversion.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::u256::u256;
+/// use extprim::modular::Barrett;
+///
+/// let m = Barrett::new(u128::new(1000000007));
+/// assert_eq!(m.reduce(u256::from_parts(u128::one(), u128::zero())), u128::new(279632277));
+/// assert_eq!(m.mul_mod(u128::new(1000000000), u128::new(12)), u128::new(999999923));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Barrett {
+    modulus: u128,
+    divisor: u128,
+    shift: u32,
+    reciprocal: u128,
+}
+
+impl Barrett {
+    /// Precomputes the reciprocal of `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
+    pub fn new(modulus: u128) -> Barrett {
+        assert!(modulus != u128::zero(), "modulus must not be zero");
+        let shift = modulus.leading_zeros();
+        let divisor = modulus << shift;
+        // The quotient lies in `2^128 .. 2^129`, so the upper half is always 1.
+        let reciprocal = (u256::max_value() / u256::new(divisor)).low128();
+        Barrett { modulus: modulus, divisor: divisor, shift: shift, reciprocal: reciprocal }
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> u128 {
+        self.modulus
+    }
+
+    /// Computes `(u1 * 2^128 + u0) % divisor`, where `u1 < divisor`.
+    fn rem_2by1(&self, u1: u128, u0: u128) -> u128 {
+        let d = self.divisor;
+        let product = u256::mul_128(self.reciprocal, u1);
+        let (q0, carry) = product.low128().overflowing_add(u0);
+        let q1 = product.high128()
+            .wrapping_add(u1)
+            .wrapping_add(u128::new(carry as u64))
+            .wrapping_add(u128::one());
+        let mut r = u0.wrapping_sub(q1.wrapping_mul(d));
+        if r > q0 {
+            r = r.wrapping_add(d);
+        }
+        if r >= d {
+            r = r.wrapping_sub(d);
+        }
+        r
+    }
+
+    /// Computes `(hi * 2^128 + lo) % modulus`.
+    pub fn reduce_parts(&self, hi: u128, lo: u128) -> u128 {
+        let s = self.shift;
+        let (u2, u1, u0) = if s == 0 {
+            (u128::zero(), hi, lo)
+        } else {
+            (hi >> (128 - s), hi << s | lo >> (128 - s), lo << s)
+        };
+        let r = self.rem_2by1(u2, u1);
+        let r = self.rem_2by1(r, u0);
+        r >> s
+    }
+
+    /// Computes `x % modulus`.
+    pub fn reduce(&self, x: u256) -> u128 {
+        self.reduce_parts(x.high128(), x.low128())
+    }
+
+    /// Computes `(a * b) % modulus`, using the full 256-bit product.
+    ///
+    /// This is the same as `a.mul_mod(b, modulus)`, without any division.
+    pub fn mul_mod(&self, a: u128, b: u128) -> u128 {
+        self.reduce(u256::mul_128(a, b))
+    }
+}
+
+#[cfg(test)]
+mod barrett_tests {
+    use u128::u128;
+    use u256::u256;
+    use modular::Barrett;
+    use rng::Mcg128;
+
+    #[test]
+    fn test_reduce() {
+        let mut rng = Mcg128::new(u128::new(1966));
+        let moduli = [u128::one(), u128::new(2), u128::new(3), u128::new(1000000007),
+                      u128::from_parts(1, 0), u128::from_parts(1, 1), u128::from_parts(0x8000_0000_0000_0000, 0),
+                      u128::max_value() - u128::new(158), u128::max_value()];
+        for &m in moduli.iter() {
+            let barrett = Barrett::new(m);
+            assert_eq!(barrett.modulus(), m);
+            assert_eq!(barrett.reduce(u256::max_value()), (u256::max_value() % u256::new(m)).low128());
+            assert_eq!(barrett.reduce_parts(m, u128::zero()), u128::zero());
+            assert_eq!(barrett.reduce_parts(u128::zero(), m - u128::one()), m - u128::one());
+            for _ in 0..200 {
+                let a = rng.next_u128();
+                let b = rng.next_u128() >> (rng.next_u64() % 128) as u32;
+                assert_eq!(barrett.mul_mod(a, b), a.mul_mod(b, m), "{} * {} % {}", a, b, m);
+                assert_eq!(barrett.reduce_parts(b, a), (u256::from_parts(b, a) % u256::new(m)).low128());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="modulus must not be zero")]
+    fn test_zero_modulus() {
+        Barrett::new(u128::zero());
+    }
+}
+
+//}}}
+
+//{{{ Special moduli
+
+/// Computes `x % (2^k - 1)` by folding the upper bits onto the lower bits, without any division.
+///
+/// Moduli of this form, such as the Mersenne prime 2<sup>127</sup> - 1, are popular in hash
+/// functions since the reduction only needs shifts and additions.
+///
+/// # Panics
+///
+/// This function will panic if `k` is not between 1 and 128.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::u256::u256;
+/// use extprim::modular::reduce_mersenne;
+///
+/// let p = u128::max_value() >> 1u32;
+/// assert_eq!(reduce_mersenne(u256::new(p), 127), u128::zero());
+/// assert_eq!(reduce_mersenne(u256::mul_128(p - u128::one(), p - u128::one()), 127), u128::one());
+/// ```
+pub fn reduce_mersenne(x: u256, k: u32) -> u128 {
+    reduce_pseudo_mersenne(x, k, u128::one())
+}
+
+/// Computes `x % (2^k - c)` by repeatedly replacing `hi * 2^k + lo` with `hi * c + lo`, which
+/// leaves the remainder unchanged.
+///
+/// Each step shrinks the number by about `k - log2(c)` bits, so this is fast when `c` is small
+/// compared to 2<sup>k</sup>, e.g. for the prime 2<sup>128</sup> - 159. A `c` of 0 reduces modulo
+/// 2<sup>k</sup>. If `c` is not less than 2<sup>k/2</sup> (rounded down), folding would take too
+/// many steps, and this falls back to an ordinary `%`.
+///
+/// # Panics
+///
+/// This function will panic if `k` is not between 1 and 128, or if `c` is not less than
+/// 2<sup>k</sup>.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::u256::u256;
+/// use extprim::modular::reduce_pseudo_mersenne;
+///
+/// let max = u128::max_value();
+/// let m = max - u128::new(158);
+/// assert_eq!(reduce_pseudo_mersenne(u256::mul_128(max, max), 128, u128::new(159)), max.mul_mod(max, m));
+/// ```
+pub fn reduce_pseudo_mersenne(mut x: u256, k: u32, c: u128) -> u128 {
+    assert!(k >= 1 && k <= 128, "k must be between 1 and 128");
+    let mask = u256::new(u128::max_value() >> (128 - k));
+    assert!(u256::new(c) <= mask, "c must be less than 2^k");
+
+    let modulus = mask - u256::new(c) + u256::one();
+    if c >> (k / 2) != u128::zero() {
+        return (x % modulus).low128();
+    }
+
+    let c = u256::new(c);
+    loop {
+        let hi = x >> k;
+        if hi == u256::zero() {
+            break;
+        }
+        // Since `c < 2^k`, the product is less than `x` and cannot overflow.
+        x = (x & mask) + hi * c;
+    }
+
+    // Now `x < 2^k`, and `c < 2^(k/2)` makes the modulus larger than `2^(k-1)`, so at most one
+    // subtraction is left.
+    if x >= modulus {
+        x -= modulus;
+    }
+    x.low128()
+}
+
+/// Computes `(a * b) % (2^k - 1)`, using the full 256-bit product.
+///
+/// # Panics
+///
+/// This function will panic if `k` is not between 1 and 128.
+pub fn mul_mod_mersenne(a: u128, b: u128, k: u32) -> u128 {
+    reduce_mersenne(u256::mul_128(a, b), k)
+}
+
+#[cfg(test)]
+mod special_moduli_tests {
+    use u128::u128;
+    use u256::u256;
+    use modular::{reduce_mersenne, reduce_pseudo_mersenne, mul_mod_mersenne};
+    use rng::Mcg128;
+
+    fn reference(x: u256, k: u32, c: u128) -> u128 {
+        let modulus = (u256::one() << k) - u256::new(c);
+        (x % modulus).low128()
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut rng = Mcg128::new(u128::new(1967));
+        let params = [(1, 0), (2, 1), (61, 1), (64, 59), (89, 1), (127, 1), (128, 159), (128, 0),
+                      (128, 1), (100, 0x1234_5678), (128, !0 >> 1), (80, 1 << 63)];
+        for &(k, c) in params.iter() {
+            let c = u128::new(c);
+            for _ in 0..100 {
+                let x = u256::from_parts(rng.next_u128(), rng.next_u128()) >> (rng.next_u64() % 256) as u32;
+                assert_eq!(reduce_pseudo_mersenne(x, k, c), reference(x, k, c), "{} mod 2^{} - {}", x, k, c);
+            }
+            assert_eq!(reduce_pseudo_mersenne(u256::max_value(), k, c), reference(u256::max_value(), k, c));
+        }
+    }
+
+    #[test]
+    fn test_reduce_large_c() {
+        let mut rng = Mcg128::new(u128::new(1967));
+        let params = [(1, u128::one()), (128, u128::max_value()), (128, u128::max_value() >> 1u32),
+                      (128, u128::from_parts(1, 0)), (128, u128::from_parts(0, !0)), (64, u128::new(!0))];
+        for &(k, c) in params.iter() {
+            for _ in 0..100 {
+                let x = u256::from_parts(rng.next_u128(), rng.next_u128()) >> (rng.next_u64() % 256) as u32;
+                assert_eq!(reduce_pseudo_mersenne(x, k, c), reference(x, k, c), "{} mod 2^{} - {}", x, k, c);
+            }
+            assert_eq!(reduce_pseudo_mersenne(u256::max_value(), k, c), reference(u256::max_value(), k, c));
+        }
+    }
+
+    #[test]
+    fn test_mersenne() {
+        let p = u128::max_value() >> 1u32;
+        assert_eq!(reduce_mersenne(u256::zero(), 127), u128::zero());
+        assert_eq!(reduce_mersenne(u256::new(p + u128::one()), 127), u128::one());
+        assert_eq!(reduce_mersenne(u256::max_value(), 128), u128::zero());
+        assert_eq!(mul_mod_mersenne(p - u128::one(), u128::new(2), 127), p - u128::new(2));
+        assert_eq!(mul_mod_mersenne(u128::new(12345), u128::new(67890), 13), u128::new(12345 * 67890 % 8191));
+    }
+
+    #[test]
+    #[should_panic(expected="c must be less than 2^k")]
+    fn test_c_too_large() {
+        reduce_pseudo_mersenne(u256::one(), 64, u128::from_parts(1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected="k must be between 1 and 128")]
+    fn test_k_too_large() {
+        reduce_mersenne(u256::one(), 129);
+    }
+}
+
+//}}}
+
+//{{{ Discrete logarithm
+
+/// Computes the integer square root of `n`, rounded up.
+#[cfg(feature="use-std")]
+fn ceil_sqrt(n: u128) -> u128 {
+    if n == u128::zero() {
+        return n;
+    }
+    let bits = 128 - n.leading_zeros();
+    let mut x = u128::one() << ((bits + 1) >> 1);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+    if x * x < n {
+        x + u128::one()
+    } else {
+        x
+    }
+}
+
+/// Finds the smallest `x` such that `base.pow(x) % modulus == target % modulus`, using the
+/// baby-step giant-step algorithm. Returns `None` if no such `x` exists.
+///
+/// `base` must be coprime with `modulus`, otherwise `None` is returned. Both the time and memory
+/// usage are proportional to the square root of `modulus`, so this is only practical for moduli up
+/// to around 2<sup>64</sup>.
+///
+/// # Panics
+///
+/// This function will panic if `modulus` is 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::modular::discrete_log;
+///
+/// let m = u128::new(1000000007);
+/// assert_eq!(discrete_log(u128::new(5), u128::new(198914779), m), Some(u128::new(200)));
+/// assert_eq!(discrete_log(u128::new(2), u128::new(3), u128::new(7)), None);
+/// ```
+#[cfg(feature="use-std")]
+pub fn discrete_log(base: u128, target: u128, modulus: u128) -> Option<u128> {
+    let base = Zn::new(base, modulus);
+    let target = Zn::new(target, modulus);
+    let m = ceil_sqrt(modulus);
+
+    let mut baby_steps = HashMap::new();
+    let mut power = base.with_value(u128::one());
+    for j in range(u128::zero(), m) {
+        baby_steps.entry(power.value()).or_insert(j);
+        power *= base;
+    }
+
+    // `power` is now `base^m`.
+    let giant_step = power.inv()?;
+    let mut gamma = target;
+    for i in range(u128::zero(), m) {
+        if let Some(&j) = baby_steps.get(&gamma.value()) {
+            return Some(i * m + j);
+        }
+        gamma *= giant_step;
+    }
+    None
+}
+
+#[cfg(all(test, feature="use-std"))]
+mod discrete_log_tests {
+    use u128::u128;
+    use modular::{ceil_sqrt, discrete_log};
+
+    #[test]
+    fn test_ceil_sqrt() {
+        assert_eq!(ceil_sqrt(u128::zero()), u128::zero());
+        assert_eq!(ceil_sqrt(u128::one()), u128::one());
+        assert_eq!(ceil_sqrt(u128::new(15)), u128::new(4));
+        assert_eq!(ceil_sqrt(u128::new(16)), u128::new(4));
+        assert_eq!(ceil_sqrt(u128::new(17)), u128::new(5));
+        assert_eq!(ceil_sqrt(u128::max_value()), u128::from_parts(1, 0));
+        assert_eq!(ceil_sqrt(u128::from_parts(!0 - 1, 1)), u128::new(!0));
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(u128::new(3), u128::one(), u128::new(7)), Some(u128::zero()));
+        assert_eq!(discrete_log(u128::new(3), u128::new(5), u128::new(7)), Some(u128::new(5)));
+        assert_eq!(discrete_log(u128::new(4), u128::new(1), u128::one()), Some(u128::zero()));
+        assert_eq!(discrete_log(u128::new(2), u128::new(4), u128::new(8)), None);
+        assert_eq!(discrete_log(u128::new(4), u128::new(2), u128::new(7)), Some(u128::new(2)));
+        assert_eq!(discrete_log(u128::new(4), u128::new(3), u128::new(7)), None);
+
+        let p = u128::new(4294967291);
+        let x = u128::new(3000000000);
+        let target = u128::new(2).pow_mod(x, p);
+        assert_eq!(discrete_log(u128::new(2), target, p).map(|k| u128::new(2).pow_mod(k, p)),
+                   Some(target));
+    }
+}
+
+//}}}
+
+//{{{ Chinese remainder theorem
+
+/// Finds the smallest `x` such that `x % m == r % m` for every pair `(r, m)` in `congruences`,
+/// using the Chinese remainder theorem.
+///
+/// The moduli must be pairwise coprime, so that the solution is unique modulo their product.
+/// Returns `None` if two moduli share a common factor, or if the product of all moduli does not
+/// fit in a `u128`. An empty system is solved by 0.
+///
+/// # Panics
+///
+/// This function will panic if any modulus is 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::modular::crt;
+///
+/// let system = [(u128::new(2), u128::new(3)), (u128::new(3), u128::new(5)), (u128::new(2), u128::new(7))];
+/// assert_eq!(crt(&system), Some(u128::new(23)));
+/// assert_eq!(crt(&[(u128::new(1), u128::new(4)), (u128::new(3), u128::new(6))]), None);
+/// ```
+pub fn crt(congruences: &[(u128, u128)]) -> Option<u128> {
+    let mut x = u128::zero();
+    let mut product = u128::one();
+    for &(residue, modulus) in congruences {
+        assert!(modulus != u128::zero(), "modulus must not be zero");
+        // Find `k` such that `x + product * k` also satisfies the new congruence. Since
+        // `x < product` and `k < modulus`, the new solution is less than the new product.
+        let inv = (product % modulus).inv_mod(modulus)?;
+        let k = residue.sub_mod(x, modulus).mul_mod(inv, modulus);
+        let next_product = product.checked_mul(modulus)?;
+        x += product * k;
+        product = next_product;
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod crt_tests {
+    use u128::u128;
+    use modular::crt;
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[]), Some(u128::zero()));
+        assert_eq!(crt(&[(u128::new(10), u128::new(7))]), Some(u128::new(3)));
+        assert_eq!(crt(&[(u128::new(5), u128::one()), (u128::new(4), u128::new(9))]), Some(u128::new(4)));
+        assert_eq!(crt(&[(u128::new(1), u128::new(6)), (u128::new(1), u128::new(4))]), None);
+
+        // The moduli multiply to exactly 2^128 - 1.
+        let moduli = [3, 5, 17, 257, 641, 65537, 274177, 6700417, 67280421310721];
+        let x = u128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+        let system: Vec<_> = moduli.iter().map(|&m| (x, u128::new(m))).collect();
+        assert_eq!(crt(&system), Some(x));
+
+        let mut system = system;
+        system.push((u128::zero(), u128::new(2)));
+        assert_eq!(crt(&system), None);
+    }
+
+    #[test]
+    #[should_panic(expected="modulus must not be zero")]
+    fn test_zero_modulus() {
+        crt(&[(u128::one(), u128::zero())]);
+    }
+}
+
+//}}}
@@ -0,0 +1,422 @@
+//! Integers of the odd widths common in binary protocols: 24, 48 and 96 bits.
+//!
+//! Each type is stored in the next larger primitive (`u32`, `u64` or `u128`, and their signed
+//! counterparts), which is kept within the range of the narrow type at all times. The arithmetic
+//! methods mirror those of the primitive types, wrapping or overflowing at the narrow width, and
+//! the operators `+`, `-` and `*` panic on overflow when debug assertions are enabled, like those
+//! of `u128` and `i128`.
+//!
+//! The byte conversions read and write exactly 3, 6 or 12 bytes.
+//!
+//! `u96` converts losslessly from and to [`uint::U96`](../uint/type.U96.html), the 96-bit
+//! arbitrary-width integer, on compilers which support const generics (1.57+). `u96` is meant for
+//! the fields of binary formats, while `U96` computes together with the other widths of `Uint`.
+//!
+//! ```rust
+//! use extprim::narrow::{u24, i48};
+//!
+//! let length = u24::from_be_bytes([0x01, 0x00, 0x02]);
+//! assert_eq!(length.get(), 0x010002);
+//! assert_eq!(u24::max_value().wrapping_add(u24::new(1).unwrap()), u24::min_value());
+//! assert_eq!(u24::new(0x100_0000), None);
+//!
+//! let offset = i48::from_le_bytes([0xfe, 0xff, 0xff, 0xff, 0xff, 0xff]);
+//! assert_eq!(offset.get(), -2);
+//! assert_eq!(offset.to_be_bytes(), [0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+//! ```
+
+use std::fmt;
+use std::ops::{Add, Sub, Mul};
+
+use u128::u128;
+use i128::i128;
+use traits::Wrapping;
+
+/// Byte conversions of the storage types. The primitive integers only have `to_le_bytes` and
+/// `from_le_bytes` since Rust 1.32.
+trait Store: Sized {
+    fn write_le_bytes(self, bytes: &mut [u8]);
+    fn read_le_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_store {
+    ($($ty:ident)*) => {$(
+        impl Store for $ty {
+            fn write_le_bytes(self, bytes: &mut [u8]) {
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = (self >> (8 * i)) as u8;
+                }
+            }
+
+            fn read_le_bytes(bytes: &[u8]) -> $ty {
+                bytes.iter().rev().fold(0, |value, &byte| value << 8 | byte as $ty)
+            }
+        }
+    )*}
+}
+
+impl_store!(u32 u64 i32 i64);
+
+impl Store for u128 {
+    fn write_le_bytes(self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.to_le_bytes());
+    }
+
+    fn read_le_bytes(bytes: &[u8]) -> u128 {
+        let mut buffer = [0; 16];
+        buffer.copy_from_slice(bytes);
+        u128::from_le_bytes(buffer)
+    }
+}
+
+impl Store for i128 {
+    fn write_le_bytes(self, bytes: &mut [u8]) {
+        self.as_u128().write_le_bytes(bytes)
+    }
+
+    fn read_le_bytes(bytes: &[u8]) -> i128 {
+        u128::read_le_bytes(bytes).as_i128()
+    }
+}
+
+macro_rules! narrow_type {
+    (
+        $(#[$attr:meta])*
+        struct $name:ident($store:ident; $bits:expr, $bytes:expr, $store_bytes:expr);
+        signed: $signed:expr,
+        min: $min:expr,
+        max: $max:expr,
+        normalize: |$x:ident| $norm:expr
+    ) => {
+        $(#[$attr])*
+        #[derive(Default, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+        #[allow(non_camel_case_types)]
+        pub struct $name($store);
+
+        impl $name {
+            /// The number of bits of the type.
+            pub const BITS: u32 = $bits;
+
+            /// The smallest value of the type.
+            pub const MIN: $name = $name($min);
+
+            /// The largest value of the type.
+            pub const MAX: $name = $name($max);
+
+            /// Returns the smallest value of the type.
+            pub fn min_value() -> $name {
+                $name::MIN
+            }
+
+            /// Returns the largest value of the type.
+            pub fn max_value() -> $name {
+                $name::MAX
+            }
+
+            /// Truncates a value to the width of the type.
+            fn normalize($x: $store) -> $store {
+                $norm
+            }
+
+            /// Creates a number from a value of the storage type, returning `None` if it is out of
+            /// range.
+            pub fn new(value: $store) -> Option<$name> {
+                if $name::normalize(value) == value {
+                    Some($name(value))
+                } else {
+                    None
+                }
+            }
+
+            /// Creates a number from a value of the storage type, discarding the bits which do not
+            /// fit in the type.
+            pub fn new_wrapping(value: $store) -> $name {
+                $name($name::normalize(value))
+            }
+
+            /// Returns the value as the storage type.
+            pub fn get(self) -> $store {
+                self.0
+            }
+
+            /// Converts a result computed in the storage type, reporting whether it overflowed
+            /// either the storage type or the narrow type.
+            fn from_overflowing((value, overflowed): ($store, bool)) -> ($name, bool) {
+                let result = $name::normalize(value);
+                ($name(result), overflowed || result != value)
+            }
+
+            /// Wrapping (modular) addition. Computes `self + other`, wrapping around at the
+            /// boundary of the type.
+            pub fn wrapping_add(self, other: $name) -> $name {
+                self.overflowing_add(other).0
+            }
+
+            /// Wrapping (modular) subtraction. Computes `self - other`, wrapping around at the
+            /// boundary of the type.
+            pub fn wrapping_sub(self, other: $name) -> $name {
+                self.overflowing_sub(other).0
+            }
+
+            /// Wrapping (modular) multiplication. Computes `self * other`, wrapping around at the
+            /// boundary of the type.
+            pub fn wrapping_mul(self, other: $name) -> $name {
+                self.overflowing_mul(other).0
+            }
+
+            /// Wrapping (modular) negation. Computes `-self`, wrapping around at the boundary of
+            /// the type.
+            pub fn wrapping_neg(self) -> $name {
+                $name::new_wrapping(self.0.wrapping_neg())
+            }
+
+            /// Calculates `self + other`.
+            ///
+            /// Returns a tuple of the addition along with a boolean indicating whether an
+            /// arithmetic overflow would occur.
+            pub fn overflowing_add(self, other: $name) -> ($name, bool) {
+                $name::from_overflowing(self.0.overflowing_add(other.0))
+            }
+
+            /// Calculates `self - other`.
+            ///
+            /// Returns a tuple of the subtraction along with a boolean indicating whether an
+            /// arithmetic overflow would occur.
+            pub fn overflowing_sub(self, other: $name) -> ($name, bool) {
+                $name::from_overflowing(self.0.overflowing_sub(other.0))
+            }
+
+            /// Calculates `self * other`.
+            ///
+            /// Returns a tuple of the multiplication along with a boolean indicating whether an
+            /// arithmetic overflow would occur.
+            pub fn overflowing_mul(self, other: $name) -> ($name, bool) {
+                $name::from_overflowing(self.0.overflowing_mul(other.0))
+            }
+
+            /// Checked negation. Computes `-self`, returning `None` if overflow occurred.
+            pub fn checked_neg(self) -> Option<$name> {
+                self.0.checked_neg().and_then($name::new)
+            }
+
+            /// Checked integer division. Computes `self / other`, returning `None` if `other == 0`
+            /// or the operation results in overflow.
+            pub fn checked_div(self, other: $name) -> Option<$name> {
+                self.0.checked_div(other.0).and_then($name::new)
+            }
+
+            /// Checked integer remainder. Computes `self % other`, returning `None` if
+            /// `other == 0` or the operation results in overflow.
+            pub fn checked_rem(self, other: $name) -> Option<$name> {
+                self.checked_div(other).and(self.0.checked_rem(other.0).map($name))
+            }
+
+            /// Creates a number from its representation as a byte array in big endian.
+            pub fn from_be_bytes(mut bytes: [u8; $bytes]) -> $name {
+                bytes.reverse();
+                $name::from_le_bytes(bytes)
+            }
+
+            /// Creates a number from its representation as a byte array in little endian.
+            pub fn from_le_bytes(bytes: [u8; $bytes]) -> $name {
+                let fill = if $signed && bytes[$bytes - 1] >= 0x80 { 0xff } else { 0 };
+                let mut buffer = [fill; $store_bytes];
+                buffer[.. $bytes].copy_from_slice(&bytes);
+                $name(Store::read_le_bytes(&buffer))
+            }
+
+            /// Returns the memory representation of this integer as a byte array in big endian.
+            pub fn to_be_bytes(self) -> [u8; $bytes] {
+                let mut bytes = self.to_le_bytes();
+                bytes.reverse();
+                bytes
+            }
+
+            /// Returns the memory representation of this integer as a byte array in little
+            /// endian.
+            pub fn to_le_bytes(self) -> [u8; $bytes] {
+                let mut buffer = [0; $store_bytes];
+                self.0.write_le_bytes(&mut buffer);
+                let mut bytes = [0; $bytes];
+                bytes.copy_from_slice(&buffer[.. $bytes]);
+                bytes
+            }
+        }
+
+        forward_symmetric!(
+            /// Checked integer addition. Computes `self + other`, returning `None` if overflow
+            /// occurred.
+            impl Add(add, checked_add, wrapping_add, overflowing_add) for $name
+        );
+        forward_symmetric!(
+            /// Checked integer subtraction. Computes `self - other`, returning `None` if overflow
+            /// occurred.
+            impl Sub(sub, checked_sub, wrapping_sub, overflowing_sub) for $name
+        );
+        forward_symmetric!(
+            /// Checked integer multiplication. Computes `self * other`, returning `None` if
+            /// overflow occurred.
+            impl Mul(mul, checked_mul, wrapping_mul, overflowing_mul) for $name
+        );
+
+        impl From<$name> for $store {
+            fn from(value: $name) -> $store {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.0, formatter)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.0, formatter)
+            }
+        }
+    }
+}
+
+narrow_type! {
+    /// An unsigned 24-bit number.
+    struct u24(u32; 24, 3, 4);
+    signed: false,
+    min: 0,
+    max: 0xff_ffff,
+    normalize: |x| x & 0xff_ffff
+}
+
+narrow_type! {
+    /// An unsigned 48-bit number.
+    struct u48(u64; 48, 6, 8);
+    signed: false,
+    min: 0,
+    max: 0xffff_ffff_ffff,
+    normalize: |x| x & 0xffff_ffff_ffff
+}
+
+narrow_type! {
+    /// An unsigned 96-bit number.
+    struct u96(u128; 96, 12, 16);
+    signed: false,
+    min: u128 { lo: 0, hi: 0 },
+    max: u128 { lo: !0, hi: 0xffff_ffff },
+    normalize: |x| x & u128::from_parts(0xffff_ffff, !0)
+}
+
+narrow_type! {
+    /// A signed 24-bit number.
+    struct i24(i32; 24, 3, 4);
+    signed: true,
+    min: -0x80_0000,
+    max: 0x7f_ffff,
+    normalize: |x| x.wrapping_shl(8).wrapping_shr(8)
+}
+
+narrow_type! {
+    /// A signed 48-bit number.
+    struct i48(i64; 48, 6, 8);
+    signed: true,
+    min: -0x8000_0000_0000,
+    max: 0x7fff_ffff_ffff,
+    normalize: |x| x.wrapping_shl(16).wrapping_shr(16)
+}
+
+narrow_type! {
+    /// A signed 96-bit number.
+    struct i96(i128; 96, 12, 16);
+    signed: true,
+    min: i128(u128 { lo: 0, hi: 0xffff_ffff_8000_0000 }),
+    max: i128(u128 { lo: !0, hi: 0x7fff_ffff }),
+    normalize: |x| x.wrapping_shl(32).wrapping_shr(32)
+}
+
+#[cfg(test)]
+mod narrow_tests {
+    use narrow::{u24, u48, u96, i24, i48, i96};
+    use u128::u128;
+    use i128::i128;
+    use traits::Wrapping;
+
+    #[test]
+    fn test_range() {
+        assert_eq!(u24::new(0xff_ffff), Some(u24::max_value()));
+        assert_eq!(u24::new(0x100_0000), None);
+        assert_eq!(u24::new_wrapping(0x123_4567).get(), 0x23_4567);
+        assert_eq!(i24::new(-0x80_0000), Some(i24::min_value()));
+        assert_eq!(i24::new(0x80_0000), None);
+        assert_eq!(i24::new_wrapping(0x80_0000), i24::min_value());
+        assert_eq!(i48::new_wrapping(0xffff_ffff_ffff).get(), -1);
+        assert_eq!(u96::new(u128::from_parts(0x1_0000_0000, 0)), None);
+        assert_eq!(u96::max_value().get(), u128::from_parts(0xffff_ffff, !0));
+        assert_eq!(i96::new(i128::from_parts(-0x8000_0000, 0)), Some(i96::MIN));
+        assert_eq!(i96::new(i128::from_parts(0x8000_0000, 0)), None);
+        assert_eq!(u48::BITS, 48);
+    }
+
+    #[test]
+    fn test_arith() {
+        let max = u24::max_value();
+        let one = u24::new(1).unwrap();
+        assert_eq!(max.overflowing_add(one), (u24::min_value(), true));
+        assert_eq!(u24::min_value().wrapping_sub(one), max);
+        assert_eq!(max.checked_mul(max), None);
+        assert_eq!(max.wrapping_mul(max), one);
+        assert_eq!(u24::new(0x1000).unwrap().checked_mul(u24::new(0x1000).unwrap()), None);
+        assert_eq!(u24::new(0x1000).unwrap() * u24::new(0xff).unwrap(), u24::new(0xff000).unwrap());
+        assert_eq!(max.checked_div(u24::new(0).unwrap()), None);
+        assert_eq!(one.wrapping_neg(), max);
+        assert_eq!(Wrapping(max) + Wrapping(one), Wrapping(u24::min_value()));
+
+        let min = i48::min_value();
+        let neg_one = i48::new(-1).unwrap();
+        assert_eq!(min.checked_div(neg_one), None);
+        assert_eq!(min.checked_rem(neg_one), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(min.wrapping_neg(), min);
+        assert_eq!(min.overflowing_mul(neg_one), (min, true));
+        assert_eq!(i48::max_value().checked_add(neg_one), i48::new(0x7fff_ffff_fffe));
+        assert_eq!(i48::new(-7).unwrap().checked_rem(i48::new(3).unwrap()), i48::new(-1));
+
+        let a = u96::new(u128::from_parts(0xffff_ffff, 0)).unwrap();
+        assert_eq!(a.overflowing_add(a), (u96::new(u128::from_parts(0xffff_fffe, 0)).unwrap(), true));
+        assert_eq!(i96::MAX.overflowing_add(i96::new(i128::one()).unwrap()), (i96::MIN, true));
+        assert_eq!(i96::MIN - i96::MIN, i96::default());
+    }
+
+    #[test]
+    #[should_panic(expected="arithmetic operation overflowed")]
+    #[cfg(debug_assertions)]
+    fn test_add_overflow() {
+        let _ = i24::max_value() + i24::new(1).unwrap();
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a = u24::new(0x123456).unwrap();
+        assert_eq!(a.to_be_bytes(), [0x12, 0x34, 0x56]);
+        assert_eq!(a.to_le_bytes(), [0x56, 0x34, 0x12]);
+        assert_eq!(u24::from_le_bytes([0x56, 0x34, 0x12]), a);
+        assert_eq!(u24::from_be_bytes([0xff, 0xff, 0xff]), u24::max_value());
+        assert_eq!(i24::from_be_bytes([0xff, 0xff, 0xfe]).get(), -2);
+        assert_eq!(i24::from_le_bytes([0x00, 0x00, 0x80]), i24::min_value());
+        assert_eq!(i24::new(-2).unwrap().to_le_bytes(), [0xfe, 0xff, 0xff]);
+        assert_eq!(u48::from_be_bytes([1, 2, 3, 4, 5, 6]).get(), 0x0102_0304_0506);
+
+        let b = i96::from_be_bytes([0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(b, i96::MIN.wrapping_add(i96::new(i128::one()).unwrap()));
+        assert_eq!(b.to_be_bytes(), [0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(u96::from_le_bytes([0xff; 12]), u96::max_value());
+        assert_eq!(u96::max_value().to_le_bytes(), [0xff; 12]);
+    }
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(u24::max_value().to_string(), "16777215");
+        assert_eq!(format!("{:?}", i48::min_value()), "-140737488355328");
+        assert_eq!(i96::MIN.to_string(), "-39614081257132168796771975168");
+        assert_eq!(u32::from(u24::max_value()), 0xff_ffff);
+    }
+}
@@ -0,0 +1,152 @@
+//! Locale-aware formatting of `u128` and `i128` with the `num-format` crate.
+//!
+//! The `ToFormattedString` trait of `num-format` is sealed, so it cannot be implemented for the
+//! types of this crate. Instead, `u128` and `i128` get inherent `to_formatted_string` and
+//! `write_formatted` methods, which accept the same `Format`s (e.g. a `Locale` or a
+//! `CustomFormat`) and group the digits the same way, including the Indian grouping.
+//!
+//! ```rust
+//! extern crate extprim;
+//! extern crate num_format;
+//!
+//! use extprim::u128::u128;
+//! use extprim::i128::i128;
+//! use num_format::Locale;
+//!
+//! # fn main() {
+//! assert_eq!(u128::new(1234567).to_formatted_string(&Locale::en), "1,234,567");
+//! assert_eq!(i128::new(-1234567).to_formatted_string(&Locale::de), "-1.234.567");
+//! assert_eq!(u128::new(1234567).to_formatted_string(&Locale::hi), "12,34,567");
+//! # }
+//! ```
+
+use std::fmt::{self, Write};
+
+use num_format::{Format, Grouping};
+
+use u128::u128;
+use i128::i128;
+
+/// Writes the decimal digits of `magnitude`, preceded by the minus sign of `format` if
+/// `is_negative`, with the separators of `format` between the groups. Returns the number of bytes
+/// written.
+fn write_grouped<F: Format, W: Write>(is_negative: bool, magnitude: u128, writer: &mut W, format: &F)
+    -> Result<usize, fmt::Error>
+{
+    let digits = magnitude.to_string();
+    let separator = format.separator();
+    let separator = separator.into_str();
+    let minus_sign = format.minus_sign();
+    let minus_sign = if is_negative { minus_sign.into_str() } else { "" };
+
+    // the lengths of the groups, from the least significant.
+    let (first, rest) = match format.grouping() {
+        Grouping::Standard => (3, 3),
+        Grouping::Indian => (3, 2),
+        Grouping::Posix => (digits.len(), digits.len()),
+    };
+    let mut group_ends = Vec::new();
+    let mut end = digits.len();
+    let mut group_len = first;
+    while end > group_len {
+        end -= group_len;
+        group_ends.push(end);
+        group_len = rest;
+    }
+
+    writer.write_str(minus_sign)?;
+    let mut start = 0;
+    for &end in group_ends.iter().rev() {
+        writer.write_str(&digits[start..end])?;
+        writer.write_str(separator)?;
+        start = end;
+    }
+    writer.write_str(&digits[start..])?;
+    Ok(minus_sign.len() + digits.len() + group_ends.len() * separator.len())
+}
+
+macro_rules! impl_formatted {
+    ($ty:ident, |$x:ident| $sign_magnitude:expr) => {
+        impl $ty {
+            /// Formats the number as a string, with the minus sign and digit grouping of a
+            /// `num_format::Format`, e.g. a `Locale`.
+            ///
+            /// This is the equivalent of `num_format::ToFormattedString::to_formatted_string`.
+            pub fn to_formatted_string<F: Format>(&self, format: &F) -> String {
+                let mut result = String::new();
+                self.write_formatted(&mut result, format).unwrap();
+                result
+            }
+
+            /// Writes the number with the minus sign and digit grouping of a
+            /// `num_format::Format`, e.g. a `Locale`, and returns the number of bytes written.
+            ///
+            /// This is the equivalent of `num_format::ToFormattedString::read_to_fmt_writer`.
+            pub fn write_formatted<F: Format, W: Write>(&self, mut writer: W, format: &F) -> Result<usize, fmt::Error> {
+                let $x = *self;
+                let (is_negative, magnitude) = $sign_magnitude;
+                write_grouped(is_negative, magnitude, &mut writer, format)
+            }
+        }
+    }
+}
+
+impl_formatted!(u128, |x| (false, x));
+impl_formatted!(i128, |x| (x.is_negative(), x.unsigned_abs()));
+
+#[cfg(test)]
+mod num_format_tests {
+    use num_format::{Buffer, CustomFormat, Format, Grouping, Locale, ToFormattedStr};
+
+    use u128::u128;
+    use i128::i128;
+
+    /// Formats a primitive integer with `num-format` itself.
+    fn builtin<N: ToFormattedStr, F: Format>(value: N, format: &F) -> String {
+        let mut buffer = Buffer::new();
+        buffer.write_formatted(&value, format);
+        buffer.as_str().to_owned()
+    }
+
+    #[test]
+    fn test_same_as_builtin() {
+        let formats = [
+            CustomFormat::builder().grouping(Grouping::Standard).separator(",").build().unwrap(),
+            CustomFormat::builder().grouping(Grouping::Indian).separator(",").build().unwrap(),
+            CustomFormat::builder().grouping(Grouping::Posix).separator(",").build().unwrap(),
+            CustomFormat::builder().grouping(Grouping::Standard).separator("\u{202f}").minus_sign("\u{2212}").build().unwrap(),
+        ];
+        let mut values = vec![0u64, 1, 12, 123, 1234, 12345, 123456, 1234567, 12345678, !0];
+        let mut power = 1u64;
+        while let Some(p) = power.checked_mul(10) {
+            values.push(p);
+            values.push(p - 1);
+            power = p;
+        }
+        for format in &formats {
+            for &value in &values {
+                let expected = builtin(value, format);
+                assert_eq!(u128::new(value).to_formatted_string(format), expected);
+                let mut written = String::new();
+                assert_eq!(u128::new(value).write_formatted(&mut written, format), Ok(expected.len()));
+                assert_eq!(written, expected);
+                if value <= i64::MAX as u64 {
+                    let value = -(value as i64);
+                    assert_eq!(i128::new(value).to_formatted_string(format), builtin(value, format));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        assert_eq!(u128::max_value().to_formatted_string(&Locale::en),
+                   "340,282,366,920,938,463,463,374,607,431,768,211,455");
+        assert_eq!(i128::min_value().to_formatted_string(&Locale::en),
+                   "-170,141,183,460,469,231,731,687,303,715,884,105,728");
+        assert_eq!(u128::from_parts(1, 0).to_formatted_string(&Locale::hi),
+                   "1,84,46,74,40,73,70,95,51,616");
+        assert_eq!(u128::from_parts(1, 0).to_formatted_string(&Locale::de),
+                   "18.446.744.073.709.551.616");
+    }
+}
@@ -0,0 +1,157 @@
+//! Instrumentation of overflowing arithmetic operators.
+//!
+//! When the `overflow-hooks` feature is enabled, every overflow in the arithmetic operators (`+`,
+//! `-`, `*`, `<<`, `>>` and unary `-`) of the extra primitive types is recorded here before the
+//! operator panics (with debug assertions) or wraps around (without). The result of the operator
+//! is unchanged, so this can be used to monitor how close a long-running program comes to the
+//! limits of the types.
+//!
+//! Each overflow increments a per-operation counter, and calls the hook registered with
+//! [`set_hook`](fn.set_hook.html), if any. The explicit `checked_*`, `wrapping_*`, `overflowing_*`
+//! and `saturating_*` methods do not record anything, since overflow is expected there.
+//!
+//! ```rust
+//! use extprim::traits::Wrapping;
+//! use extprim::u128::u128;
+//! use extprim::overflow::{self, Operation};
+//!
+//! fn log_overflow(operation: Operation, type_name: &'static str) {
+//!     eprintln!("{} overflowed in {:?}", type_name, operation);
+//! }
+//!
+//! overflow::set_hook(Some(log_overflow));
+//! let before = overflow::count(Operation::Add);
+//! let _ = std::panic::catch_unwind(|| u128::max_value() + u128::one());
+//! assert_eq!(overflow::count(Operation::Add), before + 1);
+//!
+//! // Wrapping arithmetic is not an overflow.
+//! let _ = Wrapping(u128::max_value()) + Wrapping(u128::one());
+//! assert_eq!(overflow::count(Operation::Add), before + 1);
+//! ```
+
+use std::mem::transmute;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The operators whose overflows are recorded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Addition, `a + b`.
+    Add,
+    /// Subtraction, `a - b`.
+    Sub,
+    /// Multiplication, `a * b`.
+    Mul,
+    /// Left shift by at least the bit width, `a << b`.
+    Shl,
+    /// Right shift by at least the bit width, `a >> b`.
+    Shr,
+    /// Negation of the minimum value of a signed type, `-a`.
+    Neg,
+}
+
+/// A function called on every recorded overflow, with the operation and the name of the type, e.g.
+/// `"u128"`.
+pub type Hook = fn(Operation, &'static str);
+
+static HOOK: AtomicUsize = AtomicUsize::new(0);
+
+static COUNTS: [AtomicUsize; 6] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+/// Registers a hook to be called on every overflow, replacing the previous one, which is returned.
+///
+/// Passing `None` removes the hook. The hook is global, and may be called from any thread.
+pub fn set_hook(hook: Option<Hook>) -> Option<Hook> {
+    let new = match hook {
+        Some(f) => f as usize,
+        None => 0,
+    };
+    from_usize(HOOK.swap(new, Ordering::SeqCst))
+}
+
+/// Returns the number of overflows of the operation recorded so far, across all types and threads.
+///
+/// The counter wraps around on overflow.
+pub fn count(operation: Operation) -> usize {
+    COUNTS[operation as usize].load(Ordering::Relaxed)
+}
+
+/// Resets all counters to zero.
+pub fn reset_counts() {
+    for counter in &COUNTS {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
+
+fn from_usize(value: usize) -> Option<Hook> {
+    if value == 0 {
+        None
+    } else {
+        Some(unsafe { transmute::<usize, Hook>(value) })
+    }
+}
+
+/// Records an overflow. Called by the operators before they panic or wrap.
+#[cold]
+#[doc(hidden)]
+pub fn record(operation: Operation, type_name: &'static str) {
+    COUNTS[operation as usize].fetch_add(1, Ordering::Relaxed);
+    if let Some(hook) = from_usize(HOOK.load(Ordering::SeqCst)) {
+        hook(operation, type_name);
+    }
+}
+
+#[cfg(test)]
+mod overflow_tests {
+    use std::panic::catch_unwind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use traits::Wrapping;
+    use overflow::{self, Operation};
+    use u128::{u128, MAX, ONE};
+    use i128::{i128, MIN};
+
+    static HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    // Other tests may overflow while the hook is registered, so only calls from the thread spawned
+    // by `test_hook` are counted.
+    fn counting_hook(operation: Operation, type_name: &'static str) {
+        if thread::current().name() == Some("overflow hook test") {
+            assert_eq!((operation, type_name), (Operation::Neg, "i128"));
+            HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // The counters are global and the tests run in parallel, so only lower bounds are checked.
+    #[test]
+    fn test_counts() {
+        let mul = overflow::count(Operation::Mul);
+        let shl = overflow::count(Operation::Shl);
+        let _ = catch_unwind(|| MAX * u128::new(2));
+        let _ = catch_unwind(|| ONE << 128u32);
+        assert!(overflow::count(Operation::Mul) > mul);
+        assert!(overflow::count(Operation::Shl) > shl);
+
+        assert_eq!(Wrapping(MAX) + Wrapping(ONE), Wrapping(u128::zero()));
+        assert_eq!(MAX.checked_add(ONE), None);
+        assert_eq!(MAX.wrapping_add(ONE), u128::zero());
+    }
+
+    #[test]
+    fn test_hook() {
+        let previous = overflow::set_hook(Some(counting_hook));
+        let result = thread::Builder::new().name("overflow hook test".to_owned()).spawn(|| {
+            assert_eq!(-i128::new(5), i128::new(-5));
+            let _ = -MIN;
+        }).unwrap().join();
+        overflow::set_hook(previous);
+        assert!(result.is_err());
+        assert_eq!(HOOK_CALLS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,150 @@
+//! Reductions over slices of `u128`, computed in parallel with `rayon`.
+//!
+//! The slice is split into parts which are reduced on different threads, and the partial results
+//! are then combined. The sums keep 256 bits for every part (see
+//! [`Accumulator`](../accumulator/struct.Accumulator.html)), so a part overflowing a `u128` does not
+//! lose the carry, and the results are the same as those of the sequential functions in
+//! [`accumulator`](../accumulator/index.html).
+//!
+//! ```rust
+//! use extprim::u128::u128;
+//! use extprim::parallel;
+//!
+//! let values = vec![u128::max_value(); 1000];
+//! assert_eq!(parallel::sum_checked(&values), None);
+//! assert_eq!(parallel::sum_checked(&values[..1]), Some(u128::max_value()));
+//! assert_eq!(parallel::count_ones(&values), 128000);
+//! ```
+
+use rayon::prelude::*;
+
+use u128::u128;
+use accumulator::Accumulator;
+
+/// Computes the sum of all values in the slice, or `None` if it does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::parallel::sum_checked;
+///
+/// let values = (1..10001).map(u128::new).collect::<Vec<_>>();
+/// assert_eq!(sum_checked(&values), Some(u128::new(50005000)));
+/// ```
+pub fn sum_checked(values: &[u128]) -> Option<u128> {
+    values.par_iter()
+        .fold(Accumulator::new, |mut acc, &value| {
+            acc.add(value);
+            acc
+        })
+        .reduce(Accumulator::new, |a, b| a + b)
+        .checked_total()
+}
+
+/// Computes the sum of all values in the slice, wrapping around at the boundary of the type.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::parallel::sum_wrapping;
+///
+/// assert_eq!(sum_wrapping(&[u128::max_value(), u128::new(5)]), u128::new(4));
+/// ```
+pub fn sum_wrapping(values: &[u128]) -> u128 {
+    values.par_iter().cloned().reduce(u128::zero, u128::wrapping_add)
+}
+
+/// Returns the smallest value in the slice, or `None` if it is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::parallel::min;
+///
+/// assert_eq!(min(&[u128::new(3), u128::from_parts(1, 0), u128::new(2)]), Some(u128::new(2)));
+/// assert_eq!(min(&[]), None);
+/// ```
+pub fn min(values: &[u128]) -> Option<u128> {
+    values.par_iter().cloned().min()
+}
+
+/// Returns the largest value in the slice, or `None` if it is empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::parallel::max;
+///
+/// assert_eq!(max(&[u128::new(3), u128::from_parts(1, 0), u128::new(2)]), Some(u128::from_parts(1, 0)));
+/// assert_eq!(max(&[]), None);
+/// ```
+pub fn max(values: &[u128]) -> Option<u128> {
+    values.par_iter().cloned().max()
+}
+
+/// Counts the bits which are set in all values of the slice.
+///
+/// The count is a `u64`, which holds the 128 bits of up to 2<sup>57</sup> values.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::parallel::count_ones;
+///
+/// assert_eq!(count_ones(&[u128::new(7), u128::max_value()]), 131);
+/// ```
+pub fn count_ones(values: &[u128]) -> u64 {
+    values.par_iter().map(|value| value.count_ones() as u64).sum()
+}
+
+#[cfg(test)]
+mod parallel_tests {
+    use u128::u128;
+    use accumulator;
+    use parallel::{sum_checked, sum_wrapping, min, max, count_ones};
+
+    #[test]
+    fn test_sums() {
+        let max = u128::max_value();
+        let values = vec![max; 100000];
+        assert_eq!(sum_checked(&values), None);
+        assert_eq!(sum_wrapping(&values), u128::zero().wrapping_sub(u128::new(100000)));
+        assert_eq!(sum_checked(&[]), Some(u128::zero()));
+        assert_eq!(sum_wrapping(&[]), u128::zero());
+
+        // the sum is exactly the maximum value.
+        let n = u128::new(100000);
+        let mut values = vec![max / n; 100000];
+        values.push(max % n);
+        assert_eq!(sum_checked(&values), Some(max));
+        values.push(u128::one());
+        assert_eq!(sum_checked(&values), None);
+
+        let values = (0..100000).map(|i| u128::from_parts(i, i * 3)).collect::<Vec<_>>();
+        assert_eq!(sum_checked(&values), accumulator::sum_checked(&values));
+        assert_eq!(sum_wrapping(&values), accumulator::sum_wrapping(&values));
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mut values = (0..100000).map(|i| u128::from_parts(i % 1000, !i)).collect::<Vec<_>>();
+        assert_eq!(min(&values), Some(u128::from_parts(0, !99000)));
+        assert_eq!(max(&values), Some(u128::from_parts(999, !999)));
+        values.clear();
+        assert_eq!(min(&values), None);
+        assert_eq!(max(&values), None);
+    }
+
+    #[test]
+    fn test_count_ones() {
+        let values = (0..100000).map(|i| u128::from_parts(i, !0)).collect::<Vec<_>>();
+        let expected = (0..100000u64).map(|i| i.count_ones() as u64 + 64).sum::<u64>();
+        assert_eq!(count_ones(&values), expected);
+        assert_eq!(count_ones(&[]), 0);
+    }
+}
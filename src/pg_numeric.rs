@@ -0,0 +1,163 @@
+//! The Postgres `NUMERIC` and `INT8` formats, shared by the `postgres` and `sqlx` features.
+//!
+//! A `NUMERIC` is sent in the binary format of Postgres: a header of the number of digits, the
+//! weight of the first digit, the sign and the display scale, followed by the digits in base 10000
+//! from the most significant. The weight is the power of 10000 of the first digit, and trailing
+//! zero digits are omitted.
+//!
+//! Reading a `NUMERIC` succeeds if it is a finite integer which fits in the target type; trailing
+//! zeros after the decimal point, as in `5.000`, are accepted.
+
+use std::error::Error;
+
+use num_traits::ToPrimitive;
+
+use error::NumericError;
+use u128::{u128, ZERO, div_rem};
+use i128::i128;
+
+pub(crate) type BoxedError = Box<dyn Error + Sync + Send>;
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+
+const NBASE: u64 = 10000;
+
+/// Writes the sign and absolute value of an integer as a `NUMERIC`.
+pub(crate) fn write_numeric<B: Extend<u8>>(is_negative: bool, mut magnitude: u128, out: &mut B) {
+    // base 10000 digits, from the least significant.
+    let mut digits = Vec::with_capacity(10);
+    while magnitude != ZERO {
+        let (quotient, remainder) = div_rem(magnitude, u128::new(NBASE));
+        digits.push(remainder.lo as i16);
+        magnitude = quotient;
+    }
+    let weight = digits.len().saturating_sub(1);
+    let trailing_zeros = digits.iter().take_while(|d| **d == 0).count();
+    let digits = &digits[trailing_zeros..];
+
+    let sign = if is_negative && !digits.is_empty() { NUMERIC_NEG } else { NUMERIC_POS };
+    for field in &[digits.len() as i16, weight as i16, sign as i16, 0] {
+        out.extend(field.to_be_bytes().iter().cloned());
+    }
+    for digit in digits.iter().rev() {
+        out.extend(digit.to_be_bytes().iter().cloned());
+    }
+}
+
+/// Writes a `u128` as a `NUMERIC`.
+pub(crate) fn write_u128<B: Extend<u8>>(value: u128, out: &mut B) {
+    write_numeric(false, value, out);
+}
+
+/// Writes an `i128` as a `NUMERIC`.
+pub(crate) fn write_i128<B: Extend<u8>>(value: i128, out: &mut B) {
+    if value.is_negative() {
+        write_numeric(true, value.wrapping_neg().as_u128(), out);
+    } else {
+        write_numeric(false, value.as_u128(), out);
+    }
+}
+
+/// Reads a `NUMERIC` as the sign and absolute value of an integer.
+pub(crate) fn read_numeric(raw: &[u8]) -> Result<(bool, u128), BoxedError> {
+    let read_i16 = |i: usize| i16::from_be_bytes([raw[i], raw[i + 1]]);
+    if raw.len() < 8 {
+        return Err("invalid buffer size".into());
+    }
+    let num_digits = read_i16(0);
+    let weight = read_i16(2);
+    let sign = read_i16(4) as u16;
+    if num_digits < 0 || raw.len() != 8 + 2 * num_digits as usize {
+        return Err("invalid buffer size".into());
+    }
+    let is_negative = match sign {
+        NUMERIC_POS => false,
+        NUMERIC_NEG => true,
+        _ => return Err(NumericError::NotFinite.into()),
+    };
+
+    let mut magnitude = ZERO;
+    let nbase = u128::new(NBASE);
+    for i in 0..num_digits {
+        let digit = read_i16(8 + 2 * i as usize);
+        if digit < 0 || digit as u64 >= NBASE {
+            return Err("invalid numeric digit".into());
+        }
+        if i > weight {
+            if digit != 0 {
+                return Err(NumericError::NotIntegral.into());
+            }
+        } else {
+            magnitude = magnitude.checked_mul(nbase)
+                .and_then(|m| m.checked_add(u128::new(digit as u64)))
+                .ok_or(NumericError::Overflow)?;
+        }
+    }
+    // the trailing zero digits before the decimal point which are not stored.
+    for _ in num_digits..weight.saturating_add(1) {
+        if magnitude == ZERO {
+            break;
+        }
+        magnitude = magnitude.checked_mul(nbase).ok_or(NumericError::Overflow)?;
+    }
+    Ok((is_negative, magnitude))
+}
+
+/// Reads the text format of a `NUMERIC` or `INT8`, e.g. `-123.000`, as the sign and absolute value
+/// of an integer.
+pub(crate) fn parse_numeric(text: &str) -> Result<(bool, u128), BoxedError> {
+    let (is_negative, text) = match text.as_bytes().first() {
+        Some(&b'-') => (true, &text[1..]),
+        _ => (false, text),
+    };
+    let (integer, fraction) = match text.find('.') {
+        Some(i) => (&text[..i], &text[i + 1..]),
+        None => (text, ""),
+    };
+    if text == "NaN" || text == "Infinity" {
+        return Err(NumericError::NotFinite.into());
+    }
+    if fraction.bytes().any(|b| b != b'0') {
+        return Err(NumericError::NotIntegral.into());
+    }
+    if integer.is_empty() || !integer.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("invalid numeric text".into());
+    }
+    let magnitude = u128::from_str_radix(integer, 10).map_err(|_| NumericError::Overflow)?;
+    Ok((is_negative, magnitude))
+}
+
+/// Reads an `INT8`.
+pub(crate) fn read_int8(raw: &[u8]) -> Result<i64, BoxedError> {
+    if raw.len() != 8 {
+        return Err("invalid buffer size".into());
+    }
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(raw);
+    Ok(i64::from_be_bytes(bytes))
+}
+
+/// Converts the sign and absolute value read from a `NUMERIC` to a `u128`.
+pub(crate) fn to_u128((is_negative, magnitude): (bool, u128)) -> Result<u128, BoxedError> {
+    if is_negative && magnitude != ZERO {
+        Err(NumericError::Overflow.into())
+    } else {
+        Ok(magnitude)
+    }
+}
+
+/// Converts the sign and absolute value read from a `NUMERIC` to an `i128`.
+pub(crate) fn to_i128((is_negative, magnitude): (bool, u128)) -> Result<i128, BoxedError> {
+    let value = if is_negative { magnitude.wrapping_neg() } else { magnitude }.as_i128();
+    if magnitude != ZERO && value.is_negative() != is_negative {
+        Err(NumericError::Overflow.into())
+    } else {
+        Ok(value)
+    }
+}
+
+/// Converts an `INT8` to a `u128`.
+pub(crate) fn int8_to_u128(value: i64) -> Result<u128, BoxedError> {
+    value.to_u64().map(u128::new).ok_or_else(|| NumericError::Overflow.into())
+}
@@ -0,0 +1,182 @@
+//! Conversions between the 128-bit integers of this crate and Postgres values with the `postgres`
+//! crate.
+//!
+//! `u128` and `i128` are stored in `NUMERIC` columns, which can hold every value exactly. Reading a
+//! `NUMERIC` succeeds if it is a finite integer which fits in the target type; trailing zeros after
+//! the decimal point, as in `5.000`, are accepted. `INT8` columns can be read as well, and can be
+//! written if the value fits in an `i64`.
+//!
+//! The binary format of a `NUMERIC` is described in `pg_numeric.rs`, shared with the `sqlx` feature.
+
+use bytes::{BufMut, BytesMut};
+use num_traits::ToPrimitive;
+use postgres_types::{FromSql, IsNull, ToSql, Type};
+
+use error::NumericError;
+use pg_numeric::{self, BoxedError};
+use u128::u128;
+use i128::i128;
+
+fn accepts(ty: &Type) -> bool {
+    *ty == Type::NUMERIC || *ty == Type::INT8
+}
+
+impl ToSql for u128 {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+        if *ty == Type::INT8 {
+            out.put_i64(self.to_i64().ok_or(NumericError::Overflow)?);
+        } else {
+            pg_numeric::write_u128(*self, out);
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+
+    postgres_types::to_sql_checked!();
+}
+
+impl ToSql for i128 {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxedError> {
+        if *ty == Type::INT8 {
+            out.put_i64(self.to_i64().ok_or(NumericError::Overflow)?);
+        } else {
+            pg_numeric::write_i128(*self, out);
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+
+    postgres_types::to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for u128 {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<u128, BoxedError> {
+        if *ty == Type::INT8 {
+            pg_numeric::int8_to_u128(pg_numeric::read_int8(raw)?)
+        } else {
+            pg_numeric::to_u128(pg_numeric::read_numeric(raw)?)
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+}
+
+impl<'a> FromSql<'a> for i128 {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<i128, BoxedError> {
+        if *ty == Type::INT8 {
+            Ok(i128::new(pg_numeric::read_int8(raw)?))
+        } else {
+            pg_numeric::to_i128(pg_numeric::read_numeric(raw)?)
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+}
+
+#[cfg(test)]
+mod postgres_tests {
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, ToSql, Type};
+
+    use error::NumericError;
+    use u128::u128;
+    use i128::i128;
+
+    fn to_sql<T: ToSql>(value: T, ty: &Type) -> Result<Vec<u8>, String> {
+        let mut out = BytesMut::new();
+        value.to_sql_checked(ty, &mut out).map_err(|e| e.to_string())?;
+        Ok(out.to_vec())
+    }
+
+    fn from_sql<'a, T: FromSql<'a>>(ty: &Type, raw: &'a [u8]) -> Result<T, String> {
+        assert!(T::accepts(ty));
+        T::from_sql(ty, raw).map_err(|e| e.to_string())
+    }
+
+    fn numeric(weight: i16, sign: u16, scale: u16, digits: &[i16]) -> Vec<u8> {
+        let mut raw = Vec::new();
+        for field in &[digits.len() as i16, weight, sign as i16, scale as i16] {
+            raw.extend_from_slice(&field.to_be_bytes());
+        }
+        for digit in digits {
+            raw.extend_from_slice(&digit.to_be_bytes());
+        }
+        raw
+    }
+
+    #[test]
+    fn test_to_numeric() {
+        assert_eq!(to_sql(u128::zero(), &Type::NUMERIC), Ok(numeric(0, 0, 0, &[])));
+        assert_eq!(to_sql(u128::new(12345678), &Type::NUMERIC), Ok(numeric(1, 0, 0, &[1234, 5678])));
+        assert_eq!(to_sql(u128::new(100000000), &Type::NUMERIC), Ok(numeric(2, 0, 0, &[1])));
+        assert_eq!(to_sql(u128::new(1_000_000_000_007), &Type::NUMERIC), Ok(numeric(3, 0, 0, &[1, 0, 0, 7])));
+        assert_eq!(to_sql(u128::max_value(), &Type::NUMERIC),
+                   Ok(numeric(9, 0, 0, &[340, 2823, 6692, 938, 4634, 6337, 4607, 4317, 6821, 1455])));
+        assert_eq!(to_sql(i128::new(-12345678), &Type::NUMERIC), Ok(numeric(1, 0x4000, 0, &[1234, 5678])));
+        assert_eq!(to_sql(i128::min_value(), &Type::NUMERIC),
+                   Ok(numeric(9, 0x4000, 0, &[170, 1411, 8346, 469, 2317, 3168, 7303, 7158, 8410, 5728])));
+    }
+
+    #[test]
+    fn test_from_numeric() {
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(0, 0, 0, &[])), Ok(u128::zero()));
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(1, 0, 0, &[1234, 5678])), Ok(u128::new(12345678)));
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(2, 0, 0, &[1])), Ok(u128::new(100000000)));
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(0, 0, 4, &[5, 0])), Ok(u128::new(5)));
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(9, 0, 0, &[340, 2823, 6692, 938, 4634, 6337, 4607, 4317, 6821, 1455])),
+                   Ok(u128::max_value()));
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(1, 0x4000, 0, &[1234, 5678])), Ok(i128::new(-12345678)));
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(9, 0x4000, 0, &[170, 1411, 8346, 469, 2317, 3168, 7303, 7158, 8410, 5728])),
+                   Ok(i128::min_value()));
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(0, 0x4000, 0, &[])), Ok(u128::zero()));
+    }
+
+    #[test]
+    fn test_from_numeric_errors() {
+        let overflow = Err::<u128, _>(NumericError::Overflow.to_string());
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(9, 0, 0, &[340, 2823, 6692, 938, 4634, 6337, 4607, 4317, 6821, 1456])),
+                   overflow);
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(10, 0, 0, &[4])), overflow);
+        assert_eq!(from_sql(&Type::NUMERIC, &numeric(0, 0x4000, 0, &[1])), overflow);
+        assert_eq!(from_sql::<i128>(&Type::NUMERIC, &numeric(9, 0, 0, &[170, 1411, 8346, 469, 2317, 3168, 7303, 7158, 8410, 5728])),
+                   Err(NumericError::Overflow.to_string()));
+        assert_eq!(from_sql::<u128>(&Type::NUMERIC, &numeric(0, 0, 1, &[1, 5000])),
+                   Err(NumericError::NotIntegral.to_string()));
+        assert_eq!(from_sql::<u128>(&Type::NUMERIC, &numeric(-1, 0, 1, &[5000])),
+                   Err(NumericError::NotIntegral.to_string()));
+        assert_eq!(from_sql::<i128>(&Type::NUMERIC, &numeric(0, 0xc000, 0, &[])),
+                   Err(NumericError::NotFinite.to_string()));
+        assert!(from_sql::<i128>(&Type::NUMERIC, &numeric(0, 0, 0, &[10000])).is_err());
+        assert!(from_sql::<i128>(&Type::NUMERIC, &[0, 1, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_int8() {
+        assert_eq!(to_sql(u128::new(258), &Type::INT8), Ok(vec![0, 0, 0, 0, 0, 0, 1, 2]));
+        assert_eq!(to_sql(i128::new(-1), &Type::INT8), Ok(vec![0xff; 8]));
+        assert_eq!(to_sql(u128::new(1 << 63), &Type::INT8), Err(NumericError::Overflow.to_string()));
+        assert_eq!(from_sql(&Type::INT8, &[0, 0, 0, 0, 0, 0, 1, 2]), Ok(u128::new(258)));
+        assert_eq!(from_sql(&Type::INT8, &[0xff; 8]), Ok(i128::new(-1)));
+        assert_eq!(from_sql::<u128>(&Type::INT8, &[0xff; 8]), Err(NumericError::Overflow.to_string()));
+        assert!(from_sql::<u128>(&Type::INT8, &[0; 4]).is_err());
+    }
+
+    #[test]
+    fn test_accepts() {
+        assert!(<u128 as ToSql>::accepts(&Type::NUMERIC));
+        assert!(<i128 as FromSql>::accepts(&Type::INT8));
+        assert!(!<u128 as ToSql>::accepts(&Type::INT4));
+        assert!(!<i128 as FromSql>::accepts(&Type::TEXT));
+        assert!(to_sql(u128::one(), &Type::TEXT).is_err());
+    }
+}
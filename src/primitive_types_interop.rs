@@ -0,0 +1,114 @@
+//! Conversions between the types of this crate and those of the `primitive-types` crate.
+//!
+//! `U128` and `H128` are equivalent to `u128`, and `U256` is equivalent to `u256`. A `U256` can
+//! also be converted to `u128` with `TryFrom`, which fails if the value does not fit. `H128` is
+//! treated as the big-endian byte representation of the number, the same as `primitive-types`
+//! itself uses when converting between `U256` and `H256`.
+
+use std::convert::TryFrom;
+
+use primitive_types::{Error, H128, U128, U256};
+
+use u128::u128;
+use u256::u256;
+
+impl From<u128> for U128 {
+    fn from(arg: u128) -> Self {
+        U128([arg.low64(), arg.high64()])
+    }
+}
+
+impl From<U128> for u128 {
+    fn from(arg: U128) -> Self {
+        u128::from_parts(arg.0[1], arg.0[0])
+    }
+}
+
+impl From<u128> for H128 {
+    fn from(arg: u128) -> Self {
+        H128(arg.to_be_bytes())
+    }
+}
+
+impl From<H128> for u128 {
+    fn from(arg: H128) -> Self {
+        u128::from_be_bytes(arg.0)
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(arg: u128) -> Self {
+        U256([arg.low64(), arg.high64(), 0, 0])
+    }
+}
+
+impl TryFrom<U256> for u128 {
+    type Error = Error;
+
+    fn try_from(arg: U256) -> Result<Self, Error> {
+        let U256(limbs) = arg;
+        if limbs[2] == 0 && limbs[3] == 0 {
+            Ok(u128::from_parts(limbs[1], limbs[0]))
+        } else {
+            Err(Error::Overflow)
+        }
+    }
+}
+
+impl From<u256> for U256 {
+    fn from(arg: u256) -> Self {
+        let (hi, lo) = (arg.high128(), arg.low128());
+        U256([lo.low64(), lo.high64(), hi.low64(), hi.high64()])
+    }
+}
+
+impl From<U256> for u256 {
+    fn from(arg: U256) -> Self {
+        let U256(limbs) = arg;
+        u256::from_parts(u128::from_parts(limbs[3], limbs[2]), u128::from_parts(limbs[1], limbs[0]))
+    }
+}
+
+#[cfg(test)]
+mod primitive_types_tests {
+    use std::convert::TryFrom;
+
+    use primitive_types::{Error, H128, U128, U256};
+
+    use u128::u128;
+    use u256::u256;
+
+    #[test]
+    fn test_u128() {
+        let a = u128::from_parts(0x0123456789abcdef, 0xfedcba9876543210);
+        let b = U128::from_str_radix("0123456789abcdeffedcba9876543210", 16).unwrap();
+        assert_eq!(U128::from(a), b);
+        assert_eq!(u128::from(b), a);
+        assert_eq!(U128::from(u128::max_value()), U128::max_value());
+    }
+
+    #[test]
+    fn test_h128() {
+        let a = u128::from_parts(0x0123456789abcdef, 0xfedcba9876543210);
+        let h = H128::from(a);
+        assert_eq!(h.as_bytes()[0], 0x01);
+        assert_eq!(h.as_bytes()[15], 0x10);
+        assert_eq!(u128::from(h), a);
+    }
+
+    #[test]
+    fn test_u256() {
+        let a = u128::from_parts(0x0123456789abcdef, 0xfedcba9876543210);
+        let b = U256::from(a);
+        assert_eq!(b, U256::from_str_radix("0123456789abcdeffedcba9876543210", 16).unwrap());
+        assert_eq!(u128::try_from(b), Ok(a));
+        assert_eq!(u128::try_from(U256::from(u128::max_value()) + 1), Err(Error::Overflow));
+        assert_eq!(u128::try_from(U256::MAX), Err(Error::Overflow));
+
+        let c = u256::from_parts(u128::new(3), a);
+        let d = U256::from(c);
+        assert_eq!(d, (U256::from(3) << 128) + b);
+        assert_eq!(u256::from(d), c);
+        assert_eq!(u256::from(U256::MAX), u256::max_value());
+    }
+}
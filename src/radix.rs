@@ -0,0 +1,278 @@
+//! Conversion between `u128` and strings in bases up to 64 with custom digits.
+//!
+//! [`u128::from_str_radix`](../u128/struct.u128.html#method.from_str_radix) is limited to bases
+//! 2 to 36, where the digits are `0-9` followed by the case-insensitive letters. Compact textual
+//! encodings of 128-bit identifiers often use larger bases, each with its own set of digits. An
+//! [`Alphabet`](struct.Alphabet.html) lists the digits of such a base in order, and is used with
+//! [`u128::from_str_alphabet`](../u128/struct.u128.html#method.from_str_alphabet) and
+//! [`u128::format_alphabet`](../u128/struct.u128.html#method.format_alphabet).
+//!
+//! ```rust
+//! use extprim::u128::u128;
+//! use extprim::radix::BASE62;
+//!
+//! let id = u128::from_parts(0x0123456789abcdef, 0xfedcba9876543210);
+//! let mut buffer = [0u8; 128];
+//! let encoded = id.format_alphabet(&BASE62, &mut buffer);
+//! assert_eq!(encoded, "296tiiBb3UUmdjYQ3ySu0");
+//! assert_eq!(u128::from_str_alphabet(encoded, &BASE62), Ok(id));
+//! ```
+//!
+//! The numbers are written as plain positional numbers in the given base, without padding,
+//! checksums or sign. In particular, this is *not* the byte-oriented Base64 encoding.
+
+use std::num::ParseIntError;
+use std::str::from_utf8_unchecked;
+
+use error;
+use u128::{u128, ZERO, div_rem};
+
+/// The digits of a base between 2 and 64, in increasing order of value.
+///
+/// Digits are ASCII characters and are matched exactly, so an alphabet containing both cases of a
+/// letter distinguishes them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Alphabet<'a> {
+    digits: &'a [u8],
+}
+
+/// The digits of Bitcoin's Base58, which omits `0`, `O`, `I` and `l` to avoid confusion.
+pub const BASE58: Alphabet<'static> = Alphabet {
+    digits: b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+};
+
+/// The digits `0-9`, `A-Z` and `a-z` of Base62.
+pub const BASE62: Alphabet<'static> = Alphabet {
+    digits: b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+};
+
+/// The digits of the URL- and filename-safe Base64 of RFC 4648, `A-Z`, `a-z`, `0-9`, `-` and `_`.
+pub const BASE64URL: Alphabet<'static> = Alphabet {
+    digits: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+};
+
+impl<'a> Alphabet<'a> {
+    /// Creates an alphabet from the digits in increasing order of value. The radix is the number
+    /// of digits.
+    ///
+    /// Returns `None` unless there are between 2 and 64 digits, which are distinct ASCII
+    /// characters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::radix::Alphabet;
+    ///
+    /// let crockford = Alphabet::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ").unwrap();
+    /// assert_eq!(crockford.radix(), 32);
+    /// assert_eq!(Alphabet::new(b"0"), None);
+    /// assert_eq!(Alphabet::new(b"0120"), None);
+    /// ```
+    pub fn new(digits: &'a [u8]) -> Option<Alphabet<'a>> {
+        if digits.len() < 2 || digits.len() > 64 {
+            return None;
+        }
+        for (i, &c) in digits.iter().enumerate() {
+            if c >= 0x80 || digits[..i].contains(&c) {
+                return None;
+            }
+        }
+        Some(Alphabet { digits: digits })
+    }
+
+    /// Returns the radix of this alphabet, i.e. the number of digits.
+    pub fn radix(&self) -> u32 {
+        self.digits.len() as u32
+    }
+
+    /// Returns the value of a digit, or `None` if the character is not a digit of this alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::radix::BASE58;
+    ///
+    /// assert_eq!(BASE58.digit_value('1'), Some(0));
+    /// assert_eq!(BASE58.digit_value('z'), Some(57));
+    /// assert_eq!(BASE58.digit_value('0'), None);
+    /// ```
+    pub fn digit_value(&self, c: char) -> Option<u32> {
+        if (c as u32) < 0x80 {
+            self.digits.iter().position(|&d| d == c as u8).map(|i| i as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the largest power of the radix which fits in a `u64`, and its exponent.
+    fn chunk(&self) -> (u64, usize) {
+        let radix = self.digits.len() as u64;
+        let mut power = radix;
+        let mut exp = 1;
+        while let Some(p) = power.checked_mul(radix) {
+            power = p;
+            exp += 1;
+        }
+        (power, exp)
+    }
+}
+
+impl u128 {
+    /// Converts a string of digits of the given alphabet to an integer.
+    ///
+    /// Signs and whitespace are not accepted, unless they are digits of the alphabet.
+    ///
+    /// # Return value
+    ///
+    /// `Err(ParseIntError)` if the string is empty, contains a character which is not a digit, or
+    /// represents a number which does not fit in 128 bits. Otherwise, `Ok(n)` where `n` is the
+    /// integer represented by `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::radix::{BASE58, BASE64URL};
+    ///
+    /// assert_eq!(u128::from_str_alphabet("5Q", &BASE58), Ok(u128::new(4 * 58 + 23)));
+    /// assert_eq!(u128::from_str_alphabet("B-", &BASE64URL), Ok(u128::new(64 + 62)));
+    /// assert!(u128::from_str_alphabet("0", &BASE58).is_err());
+    /// ```
+    pub fn from_str_alphabet(src: &str, alphabet: &Alphabet) -> Result<u128, ParseIntError> {
+        if src.is_empty() {
+            return Err(error::empty());
+        }
+
+        let mut result = ZERO;
+        let radix = alphabet.radix() as u64;
+
+        for c in src.chars() {
+            let digit = alphabet.digit_value(c).ok_or_else(error::invalid_digit)?;
+            let int_result = result.checked_mul_64(radix).ok_or_else(error::overflow)?;
+            result = int_result.checked_add(u128::new(digit as u64)).ok_or_else(error::overflow)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Writes the number using the digits of the given alphabet to the end of `buffer`, and
+    /// returns the written part.
+    ///
+    /// The buffer is large enough for every number in every base. Zero is written as the first
+    /// digit of the alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::radix::BASE58;
+    ///
+    /// let mut buffer = [0u8; 128];
+    /// assert_eq!(u128::new(4 * 58 + 23).format_alphabet(&BASE58, &mut buffer), "5Q");
+    /// assert_eq!(u128::zero().format_alphabet(&BASE58, &mut buffer), "1");
+    /// ```
+    pub fn format_alphabet<'b>(self, alphabet: &Alphabet, buffer: &'b mut [u8; 128]) -> &'b str {
+        let radix = alphabet.radix() as u64;
+        let (chunk_power, chunk_len) = alphabet.chunk();
+
+        // As in decimal formatting, the digits are produced a u64-sized chunk at a time, so that
+        // most of the divisions are native ones.
+        let mut value = self;
+        let mut pos = buffer.len();
+        loop {
+            let (rest, mut chunk) = if value.hi == 0 && value.lo < chunk_power {
+                (ZERO, value.lo)
+            } else {
+                let (q, r) = div_rem(value, u128::new(chunk_power));
+                (q, r.lo)
+            };
+
+            let chunk_end = pos;
+            loop {
+                pos -= 1;
+                buffer[pos] = alphabet.digits[(chunk % radix) as usize];
+                chunk /= radix;
+                if chunk == 0 {
+                    break;
+                }
+            }
+            if rest == ZERO {
+                break;
+            }
+            while pos > chunk_end - chunk_len {
+                pos -= 1;
+                buffer[pos] = alphabet.digits[0];
+            }
+            value = rest;
+        }
+
+        unsafe { from_utf8_unchecked(&buffer[pos ..]) }
+    }
+
+    /// Converts the number to a string using the digits of the given alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::radix::BASE64URL;
+    ///
+    /// assert_eq!(u128::max_value().to_str_alphabet(&BASE64URL), "D_____________________");
+    /// ```
+    #[cfg(feature="use-std")]
+    pub fn to_str_alphabet(self, alphabet: &Alphabet) -> String {
+        let mut buffer = [0u8; 128];
+        self.format_alphabet(alphabet, &mut buffer).to_owned()
+    }
+}
+
+#[cfg(test)]
+mod radix_tests {
+    use u128::{u128, ZERO, ONE, MAX};
+    use error;
+    use radix::{Alphabet, BASE58, BASE62, BASE64URL};
+
+    #[test]
+    fn test_alphabets() {
+        for alphabet in &[BASE58, BASE62, BASE64URL] {
+            assert_eq!(Alphabet::new(alphabet.digits), Some(*alphabet));
+        }
+        assert_eq!(BASE58.radix(), 58);
+        assert_eq!(BASE62.radix(), 62);
+        assert_eq!(BASE64URL.radix(), 64);
+        assert!(Alphabet::new(b"01").is_some());
+        assert!(Alphabet::new(&[b'a'; 65]).is_none());
+        assert!(Alphabet::new("0é".as_bytes()).is_none());
+        assert_eq!(BASE64URL.digit_value('_'), Some(63));
+        assert_eq!(BASE64URL.digit_value('é'), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let binary = Alphabet::new(b"01").unwrap();
+        let decimal = Alphabet::new(b"0123456789").unwrap();
+        let values = [ZERO, ONE, MAX, u128::new(57), u128::new(58), u128::from_parts(1, 0),
+                      u128::from_parts(0x0123456789abcdef, 0xfedcba9876543210), MAX - ONE];
+        let mut buffer = [0u8; 128];
+        for &value in &values {
+            assert_eq!(value.format_alphabet(&decimal, &mut buffer), value.to_string());
+            assert_eq!(value.format_alphabet(&binary, &mut buffer), format!("{:b}", value));
+            for alphabet in &[BASE58, BASE62, BASE64URL, binary, decimal] {
+                let encoded = value.to_str_alphabet(alphabet);
+                assert_eq!(u128::from_str_alphabet(&encoded, alphabet), Ok(value));
+            }
+        }
+        assert_eq!(MAX.format_alphabet(&binary, &mut buffer).len(), 128);
+        assert_eq!(u128::from_parts(1, 0).to_str_alphabet(&BASE58), "jpXCZedGfVR");
+    }
+
+    #[test]
+    fn test_from_str_alphabet_errors() {
+        assert_eq!(u128::from_str_alphabet("", &BASE62), Err(error::empty()));
+        assert_eq!(u128::from_str_alphabet("+1", &BASE62), Err(error::invalid_digit()));
+        assert_eq!(u128::from_str_alphabet("O", &BASE58), Err(error::invalid_digit()));
+        assert_eq!(u128::from_str_alphabet("D_____________________", &BASE64URL), Ok(MAX));
+        assert_eq!(u128::from_str_alphabet("EAAAAAAAAAAAAAAAAAAAAA", &BASE64URL), Err(error::overflow()));
+        assert_eq!(u128::from_str_alphabet("AAAAAAAAAAAAAAAAAAAAAAAAAB", &BASE64URL), Ok(ONE));
+    }
+}
@@ -0,0 +1,152 @@
+//! Zero-copy serialization of the 128-bit integers with the `rkyv` crate.
+//!
+//! `u128` and `i128` are archived as [`ArchivedU128`](../struct.ArchivedU128.html) and
+//! [`ArchivedI128`](../struct.ArchivedI128.html). Unlike the archived forms of rkyv's own
+//! primitives, their layout does not depend on the features rkyv is built with: they are always the
+//! 16 bytes of the value in little endian (two's complement for `i128`), with an alignment of 1.
+//! Every bit pattern is valid, so checking an archive never fails because of them.
+
+use std::fmt;
+
+use rkyv::{Archive, Serialize, Deserialize, Place, Portable};
+use rkyv::bytecheck::CheckBytes;
+use rkyv::rancor::Fallible;
+use rkyv::traits::NoUndef;
+
+use u128::u128;
+use i128::i128;
+
+macro_rules! archived_type {
+    ($(#[$attr:meta])* struct $name:ident: $ty:ident) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name([u8; 16]);
+
+        impl $name {
+            /// Returns the archived value.
+            pub fn get(self) -> $ty {
+                $ty::from_le_bytes(self.0)
+            }
+        }
+
+        impl From<$ty> for $name {
+            fn from(value: $ty) -> $name {
+                $name(value.to_le_bytes())
+            }
+        }
+
+        impl From<$name> for $ty {
+            fn from(archived: $name) -> $ty {
+                archived.get()
+            }
+        }
+
+        impl PartialEq<$ty> for $name {
+            fn eq(&self, other: &$ty) -> bool {
+                self.get() == *other
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.debug_tuple(stringify!($name)).field(&self.get()).finish()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.get(), formatter)
+            }
+        }
+
+        // SAFETY: the type is a `#[repr(transparent)]` byte array, without padding or interior
+        // mutability, and any bytes form a valid value.
+        unsafe impl Portable for $name {}
+        unsafe impl NoUndef for $name {}
+        unsafe impl<C: Fallible + ?Sized> CheckBytes<C> for $name {
+            unsafe fn check_bytes(_: *const $name, _: &mut C) -> Result<(), C::Error> {
+                Ok(())
+            }
+        }
+
+        impl Archive for $ty {
+            type Archived = $name;
+            type Resolver = ();
+
+            fn resolve(&self, _: (), out: Place<$name>) {
+                out.write($name::from(*self));
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $ty {
+            fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$ty, D> for $name {
+            fn deserialize(&self, _: &mut D) -> Result<$ty, D::Error> {
+                Ok(self.get())
+            }
+        }
+    }
+}
+
+archived_type! {
+    /// The archived form of a [`u128`](u128/struct.u128.html), with the `rkyv` feature.
+    ///
+    /// It holds the 16 bytes of the value in little endian, and has an alignment of 1.
+    struct ArchivedU128: u128
+}
+
+archived_type! {
+    /// The archived form of an [`i128`](i128/struct.i128.html), with the `rkyv` feature.
+    ///
+    /// It holds the 16 bytes of the value in little endian two's complement, and has an alignment
+    /// of 1.
+    struct ArchivedI128: i128
+}
+
+#[cfg(test)]
+mod rkyv_tests {
+    use std::mem::{size_of, align_of};
+    use rkyv::{to_bytes, from_bytes, access, Archived};
+    use rkyv::rancor::Error;
+
+    use u128::u128;
+    use i128::i128;
+    use super::{ArchivedU128, ArchivedI128};
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(size_of::<ArchivedU128>(), 16);
+        assert_eq!(align_of::<ArchivedU128>(), 1);
+        assert_eq!(align_of::<ArchivedI128>(), 1);
+
+        let value = u128::from_parts(0x100f0e0d0c0b0a09, 0x0807060504030201);
+        let bytes = to_bytes::<Error>(&value).unwrap();
+        assert_eq!(&bytes[..], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        assert_eq!(&to_bytes::<Error>(&i128::new(-2)).unwrap()[..], &(-2i128).to_le_bytes());
+    }
+
+    #[test]
+    fn test_access() {
+        let values = vec![u128::zero(), u128::new(7), u128::max_value()];
+        let bytes = to_bytes::<Error>(&values).unwrap();
+        let archived = access::<Archived<Vec<u128>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived[1], u128::new(7));
+        assert_eq!(archived[2].get(), u128::max_value());
+        assert_eq!(archived[2].to_string(), "340282366920938463463374607431768211455");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = (u128::new(12345), i128::min_value(), vec![i128::new(-1), i128::max_value()]);
+        let bytes = to_bytes::<Error>(&values).unwrap();
+        assert_eq!(from_bytes::<(u128, i128, Vec<i128>), Error>(&bytes).unwrap(), values);
+        assert_eq!(format!("{:?}", ArchivedI128::from(i128::new(-5))), "ArchivedI128(i128!(-5))");
+        assert_eq!(i128::from(ArchivedI128::from(i128::new(-5))), i128::new(-5));
+    }
+}
@@ -0,0 +1,138 @@
+//! A small pseudo-random number generator with 128-bit state.
+//!
+//! [`Mcg128`](struct.Mcg128.html) is a Lehmer multiplicative congruential generator modulo
+//! 2<sup>128</sup>. It is very fast and has good statistical quality for simulations, but is not
+//! cryptographically secure.
+//!
+//! ```rust
+//! use extprim::rng::Mcg128;
+//!
+//! let mut a = Mcg128::new(extprim::u128::u128::new(12345));
+//! let mut b = a.clone();
+//! assert_eq!(a.next_u64(), b.next_u64());
+//! ```
+
+#[cfg(feature="rand")] use rand::{RngCore, SeedableRng, Error};
+
+use u128::u128;
+
+/// The multiplier of the generator, a well-known constant with good spectral test results.
+const MULTIPLIER: u64 = 0xda942042e4dd58b5;
+
+/// A Lehmer multiplicative congruential generator with 128-bit state.
+///
+/// Each step multiplies the state by a fixed 64-bit constant modulo 2<sup>128</sup>, and outputs
+/// the upper 64 bits. The period is 2<sup>126</sup>.
+///
+/// When the `rand` feature is enabled, this type also implements `RngCore` and `SeedableRng`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mcg128 {
+    state: u128,
+}
+
+impl Mcg128 {
+    /// Creates a new generator from a seed.
+    ///
+    /// The state of a multiplicative generator must be odd, so the lowest bit of the seed is
+    /// ignored. Seeds with few bits set produce poor initial outputs; consider
+    /// [`u128::seed_rng`](../u128/struct.u128.html#method.seed_rng) to expand such seeds.
+    pub fn new(seed: u128) -> Mcg128 {
+        Mcg128 { state: seed | u128::one() }
+    }
+
+    /// Returns the next random 64-bit number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::rng::Mcg128;
+    ///
+    /// let mut rng = Mcg128::new(u128::new(42));
+    /// assert_eq!(rng.next_u64(), 0x24);
+    /// assert_eq!(rng.next_u64(), 0x58fa50179d771566);
+    /// ```
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_mul_64(MULTIPLIER);
+        self.state.high64()
+    }
+
+    /// Returns the next random 128-bit number, made of two consecutive 64-bit outputs with the
+    /// first one as the higher half.
+    pub fn next_u128(&mut self) -> u128 {
+        let hi = self.next_u64();
+        let lo = self.next_u64();
+        u128::from_parts(hi, lo)
+    }
+}
+
+#[cfg(feature="rand")]
+impl RngCore for Mcg128 {
+    fn next_u32(&mut self) -> u32 {
+        (Mcg128::next_u64(self) >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Mcg128::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let x = Mcg128::next_u64(self);
+            for (i, byte) in chunk.iter_mut().enumerate() {
+                *byte = (x >> (8 * i)) as u8;
+            }
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature="rand")]
+impl SeedableRng for Mcg128 {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: [u8; 16]) -> Mcg128 {
+        Mcg128::new(u128::from_le_bytes(seed))
+    }
+}
+
+#[cfg(test)]
+mod mcg128_tests {
+    use u128::u128;
+    use rng::Mcg128;
+
+    #[test]
+    fn test_sequence() {
+        let mut rng = Mcg128::new(u128::new(42));
+        assert_eq!(rng.next_u64(), 0x24);
+        assert_eq!(rng.next_u64(), 0x58fa50179d771566);
+        assert_eq!(rng.next_u64(), 0xdcf2f5ac5885eb0f);
+
+        let mut rng = Mcg128::new(u128::zero());
+        assert_eq!(rng, Mcg128::new(u128::one()));
+        assert_eq!(rng.next_u64(), 0);
+        assert_eq!(rng.next_u128(), u128::from_parts(0xbaa09ca73f3265b4, 0xdb76c43996e558d0));
+    }
+
+    #[cfg(feature="rand")]
+    #[test]
+    fn test_rng_core() {
+        use rand::{Rng, RngCore, SeedableRng};
+
+        let mut rng = Mcg128::from_seed([42, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let mut bytes = [0; 10];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(bytes, [0x24, 0, 0, 0, 0, 0, 0, 0, 0x66, 0x15]);
+        assert_eq!(RngCore::next_u32(&mut rng), 0xdcf2f5ac);
+
+        let x: u128 = rng.gen();
+        let mut copy = Mcg128::new(u128::new(42));
+        copy.next_u128();
+        copy.next_u64();
+        assert_eq!(x, u128::from_parts(copy.next_u64(), copy.next_u64()));
+    }
+}
@@ -0,0 +1,154 @@
+//! Storing the 128-bit integers of this crate in SQLite with the `rusqlite` crate.
+//!
+//! SQLite integers only have 64 bits, so `u128` and `i128` are stored as decimal `TEXT`, e.g.
+//! `'340282366920938463463374607431768211455'`. Reading them also accepts an `INTEGER`, if it is in
+//! range.
+//!
+//! To store a 16-byte `BLOB` instead, wrap the value in
+//! [`BigEndian`](../endian/struct.BigEndian.html) or [`LittleEndian`](../endian/struct.LittleEndian.html).
+//! A blob holding a `BigEndian<u128>` sorts in numeric order, which neither text nor any of the
+//! other blobs do.
+//!
+//! Both representations round-trip every value exactly.
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Result;
+
+use endian::{BigEndian, LittleEndian};
+use u128::u128;
+use i128::i128;
+
+impl ToSql for u128 {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl ToSql for i128 {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for u128 {
+    fn column_result(value: ValueRef) -> FromSqlResult<u128> {
+        match value {
+            ValueRef::Integer(i) if i >= 0 => Ok(u128::new(i as u64)),
+            ValueRef::Integer(i) => Err(FromSqlError::OutOfRange(i)),
+            _ => value.as_str()?.parse().map_err(FromSqlError::other),
+        }
+    }
+}
+
+impl FromSql for i128 {
+    fn column_result(value: ValueRef) -> FromSqlResult<i128> {
+        match value {
+            ValueRef::Integer(i) => Ok(i128::new(i)),
+            _ => value.as_str()?.parse().map_err(FromSqlError::other),
+        }
+    }
+}
+
+macro_rules! impl_blob {
+    ($($wrapper:ident<$ty:ident>: $to:ident, $from:ident;)*) => {$(
+        impl ToSql for $wrapper<$ty> {
+            fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+                Ok(ToSqlOutput::from(self.get().$to().to_vec()))
+            }
+        }
+
+        impl FromSql for $wrapper<$ty> {
+            fn column_result(value: ValueRef) -> FromSqlResult<$wrapper<$ty>> {
+                let blob = value.as_blob()?;
+                if blob.len() != 16 {
+                    return Err(FromSqlError::InvalidBlobSize { expected_size: 16, blob_size: blob.len() });
+                }
+                let mut bytes = [0; 16];
+                bytes.copy_from_slice(blob);
+                Ok($wrapper::new($ty::$from(bytes)))
+            }
+        }
+    )*}
+}
+
+impl_blob! {
+    BigEndian<u128>: to_be_bytes, from_be_bytes;
+    BigEndian<i128>: to_be_bytes, from_be_bytes;
+    LittleEndian<u128>: to_le_bytes, from_le_bytes;
+    LittleEndian<i128>: to_le_bytes, from_le_bytes;
+}
+
+#[cfg(test)]
+mod rusqlite_tests {
+    use rusqlite::Connection;
+    use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, Value, ValueRef};
+
+    use endian::{BigEndian, LittleEndian};
+    use u128::u128;
+    use i128::i128;
+
+    fn to_value<T: ToSql>(value: T) -> Value {
+        match value.to_sql().unwrap() {
+            ToSqlOutput::Owned(value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(to_value(u128::max_value()), Value::Text("340282366920938463463374607431768211455".to_owned()));
+        assert_eq!(to_value(i128::new(-5)), Value::Text("-5".to_owned()));
+        assert_eq!(u128::column_result(ValueRef::Text(b"12345")), Ok(u128::new(12345)));
+        assert_eq!(i128::column_result(ValueRef::Text(b"-170141183460469231731687303715884105728")),
+                   Ok(i128::min_value()));
+        assert!(u128::column_result(ValueRef::Text(b"-1")).is_err());
+        assert!(i128::column_result(ValueRef::Text(b"1.5")).is_err());
+        assert_eq!(u128::column_result(ValueRef::Real(1.0)), Err(FromSqlError::InvalidType));
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(u128::column_result(ValueRef::Integer(7)), Ok(u128::new(7)));
+        assert_eq!(u128::column_result(ValueRef::Integer(-7)), Err(FromSqlError::OutOfRange(-7)));
+        assert_eq!(i128::column_result(ValueRef::Integer(-7)), Ok(i128::new(-7)));
+    }
+
+    #[test]
+    fn test_blob() {
+        let value = u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10);
+        let be = (1..17).collect::<Vec<u8>>();
+        let le = be.iter().rev().cloned().collect::<Vec<u8>>();
+        assert_eq!(to_value(BigEndian::new(value)), Value::Blob(be.clone()));
+        assert_eq!(to_value(LittleEndian::new(value)), Value::Blob(le.clone()));
+        assert_eq!(BigEndian::<u128>::column_result(ValueRef::Blob(&be)), Ok(BigEndian::new(value)));
+        assert_eq!(LittleEndian::<u128>::column_result(ValueRef::Blob(&le)), Ok(LittleEndian::new(value)));
+        assert_eq!(to_value(BigEndian::new(i128::new(-1))), Value::Blob(vec![0xff; 16]));
+        assert_eq!(LittleEndian::<i128>::column_result(ValueRef::Blob(&[0xff; 16])), Ok(LittleEndian::new(i128::new(-1))));
+        assert_eq!(BigEndian::<u128>::column_result(ValueRef::Blob(&be[1..])),
+                   Err(FromSqlError::InvalidBlobSize { expected_size: 16, blob_size: 15 }));
+        assert_eq!(BigEndian::<u128>::column_result(ValueRef::Text(b"1")), Err(FromSqlError::InvalidType));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch("CREATE TABLE t (id INTEGER PRIMARY KEY, a TEXT, b BLOB);").unwrap();
+        let values = [u128::zero(), u128::new(256), u128::new(255), u128::max_value(), u128::from_parts(1, 0)];
+        for value in &values {
+            connection.execute("INSERT INTO t (a, b) VALUES (?1, ?2)", (value, BigEndian::new(*value))).unwrap();
+        }
+
+        let mut statement = connection.prepare("SELECT a, b FROM t ORDER BY b").unwrap();
+        let rows = statement.query_map((), |row| Ok((row.get::<_, u128>(0)?, row.get::<_, BigEndian<u128>>(1)?)))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        assert_eq!(rows.iter().map(|r| r.0).collect::<Vec<_>>(), sorted);
+        assert!(rows.iter().all(|r| r.0 == r.1.get()));
+
+        let min: i128 = connection.query_row("SELECT ?1", [i128::min_value()], |row| row.get(0)).unwrap();
+        assert_eq!(min, i128::min_value());
+    }
+}
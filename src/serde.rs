@@ -0,0 +1,449 @@
+//! Alternative serde representations of the 128-bit integers.
+//!
+//! By default `u128` and `i128` are serialized as decimal strings in human-readable formats and as
+//! 16 little endian bytes otherwise. The modules here choose a fixed representation for a single
+//! field with `#[serde(with = "...")]`, whatever the format:
+//!
+//! * [`hex_prefixed`](hex_prefixed/index.html) — a `0x`-prefixed hexadecimal string, e.g.
+//!   `"0x1f"` or `"-0x1f"`, as used by Ethereum JSON-RPC.
+//! * [`decimal_str`](decimal_str/index.html) — a decimal string, e.g. `"31"`.
+//! * [`le_bytes`](le_bytes/index.html) — a byte string of the 16 bytes in little endian.
+//! * [`json_number`](json_number/index.html) — an exact JSON number, e.g. `31`. This needs the
+//!   `serde_json` feature.
+//!
+//! ```rust
+//! extern crate extprim;
+//! #[macro_use] extern crate serde_derive;
+//! extern crate serde_test;
+//!
+//! use extprim::u128::u128;
+//! use extprim::i128::i128;
+//! use serde_test::{assert_tokens, Token};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Transfer {
+//!     #[serde(with = "extprim::serde::hex_prefixed")]
+//!     value: u128,
+//!     #[serde(with = "extprim::serde::decimal_str")]
+//!     fee: i128,
+//! }
+//!
+//! # fn main() {
+//! // In JSON, this is `{"value":"0xde0b6b3a7640000","fee":"-21000"}`.
+//! let transfer = Transfer { value: u128::new(0xde0b6b3a7640000), fee: i128::new(-21000) };
+//! assert_tokens(&transfer, &[
+//!     Token::Struct { name: "Transfer", len: 2 },
+//!     Token::Str("value"), Token::Str("0xde0b6b3a7640000"),
+//!     Token::Str("fee"), Token::Str("-21000"),
+//!     Token::StructEnd,
+//! ]);
+//! # }
+//! ```
+
+use std::fmt;
+
+use serde_crate::de;
+
+use u128::u128;
+use i128::i128;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for ::u128::u128 {}
+    impl Sealed for ::i128::i128 {}
+}
+
+/// The integer types which can be used with the modules in [`extprim::serde`](index.html), i.e.
+/// `u128` and `i128`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Int128: Copy + fmt::Display + sealed::Sealed {
+    #[doc(hidden)]
+    const EXPECTING: &'static str;
+
+    #[doc(hidden)]
+    fn from_decimal_str(value: &str) -> Option<Self>;
+
+    #[doc(hidden)]
+    fn to_le_bytes(self) -> [u8; 16];
+
+    #[doc(hidden)]
+    fn from_le_bytes(bytes: [u8; 16]) -> Self;
+
+    /// Splits the value into the sign and the absolute value.
+    #[doc(hidden)]
+    fn to_sign_magnitude(self) -> (bool, u128);
+
+    /// Builds a value from the sign and the absolute value, or returns `None` if it is out of range.
+    #[doc(hidden)]
+    fn from_sign_magnitude(is_negative: bool, magnitude: u128) -> Option<Self>;
+}
+
+impl Int128 for u128 {
+    const EXPECTING: &'static str = "an unsigned 128-bit integer";
+
+    fn from_decimal_str(value: &str) -> Option<u128> {
+        value.parse().ok()
+    }
+
+    fn to_le_bytes(self) -> [u8; 16] {
+        u128::to_le_bytes(self)
+    }
+
+    fn from_le_bytes(bytes: [u8; 16]) -> u128 {
+        u128::from_le_bytes(bytes)
+    }
+
+    fn to_sign_magnitude(self) -> (bool, u128) {
+        (false, self)
+    }
+
+    fn from_sign_magnitude(is_negative: bool, magnitude: u128) -> Option<u128> {
+        if is_negative && magnitude != u128::zero() {
+            None
+        } else {
+            Some(magnitude)
+        }
+    }
+}
+
+impl Int128 for i128 {
+    const EXPECTING: &'static str = "a signed 128-bit integer";
+
+    fn from_decimal_str(value: &str) -> Option<i128> {
+        value.parse().ok()
+    }
+
+    fn to_le_bytes(self) -> [u8; 16] {
+        i128::to_le_bytes(self)
+    }
+
+    fn from_le_bytes(bytes: [u8; 16]) -> i128 {
+        i128::from_le_bytes(bytes)
+    }
+
+    fn to_sign_magnitude(self) -> (bool, u128) {
+        if self.is_negative() {
+            (true, self.wrapping_neg().as_u128())
+        } else {
+            (false, self.as_u128())
+        }
+    }
+
+    fn from_sign_magnitude(is_negative: bool, magnitude: u128) -> Option<i128> {
+        let value = if is_negative { magnitude.wrapping_neg() } else { magnitude }.as_i128();
+        if magnitude != u128::zero() && value.is_negative() != is_negative {
+            None
+        } else {
+            Some(value)
+        }
+    }
+}
+
+/// Visitor accepting a string, and converting it with the given function.
+struct StrVisitor<T>(fn(&str) -> Option<T>, &'static str);
+
+impl<'de, T: Int128> de::Visitor<'de> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} as a {} string", T::EXPECTING, self.1)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        (self.0)(value).ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+/// Serializes as a `0x`-prefixed lowercase hexadecimal string, with a leading `-` for negative
+/// numbers.
+///
+/// Deserializing also accepts a `0X` prefix and uppercase digits, but the prefix is mandatory.
+pub mod hex_prefixed {
+    use std::fmt;
+    use serde_crate::{Serializer, Deserializer};
+    use super::{Int128, StrVisitor};
+    use u128::u128;
+
+    struct Hex<T>(T);
+
+    impl<T: Int128> fmt::Display for Hex<T> {
+        fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            let (is_negative, magnitude) = self.0.to_sign_magnitude();
+            let sign = if is_negative { "-" } else { "" };
+            write!(formatter, "{}{:#x}", sign, magnitude)
+        }
+    }
+
+    fn parse<T: Int128>(value: &str) -> Option<T> {
+        let (is_negative, value) = match value.as_bytes().first() {
+            Some(&b'-') => (true, &value[1..]),
+            _ => (false, value),
+        };
+        let digits = match value.get(..2) {
+            Some("0x") | Some("0X") => &value[2..],
+            _ => return None,
+        };
+        if digits.starts_with('+') {
+            return None;
+        }
+        let magnitude = u128::from_str_radix(digits, 16).ok()?;
+        T::from_sign_magnitude(is_negative, magnitude)
+    }
+
+    /// Serializes the integer as a `0x`-prefixed hexadecimal string.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: Int128, S: Serializer
+    {
+        serializer.collect_str(&Hex(*value))
+    }
+
+    /// Deserializes the integer from a `0x`-prefixed hexadecimal string.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: Int128, D: Deserializer<'de>
+    {
+        deserializer.deserialize_str(StrVisitor(parse, "hexadecimal"))
+    }
+}
+
+/// Serializes as a decimal string, even in binary formats.
+pub mod decimal_str {
+    use serde_crate::{Serializer, Deserializer};
+    use super::{Int128, StrVisitor};
+
+    /// Serializes the integer as a decimal string.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: Int128, S: Serializer
+    {
+        serializer.collect_str(value)
+    }
+
+    /// Deserializes the integer from a decimal string.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: Int128, D: Deserializer<'de>
+    {
+        deserializer.deserialize_str(StrVisitor(T::from_decimal_str, "decimal"))
+    }
+}
+
+/// Serializes as a byte string holding the 16 bytes in little endian, even in human-readable
+/// formats.
+///
+/// Deserializing also accepts a sequence of 16 bytes, which is how e.g. JSON represents a byte
+/// string.
+pub mod le_bytes {
+    use std::fmt;
+    use std::marker::PhantomData;
+    use serde_crate::{Serializer, Deserializer};
+    use serde_crate::de::{self, Visitor, SeqAccess};
+    use super::Int128;
+
+    struct BytesVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Int128> Visitor<'de> for BytesVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "{} as 16 little endian bytes", T::EXPECTING)
+        }
+
+        fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<T, E> {
+            if value.len() != 16 {
+                return Err(E::invalid_length(value.len(), &self));
+            }
+            let mut bytes = [0; 16];
+            bytes.copy_from_slice(value);
+            Ok(T::from_le_bytes(bytes))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+            let mut bytes = [0; 16];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(de::Error::invalid_length(17, &self));
+            }
+            Ok(T::from_le_bytes(bytes))
+        }
+    }
+
+    /// Serializes the integer as 16 little endian bytes.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: Int128, S: Serializer
+    {
+        serializer.serialize_bytes(&value.to_le_bytes())
+    }
+
+    /// Deserializes the integer from 16 little endian bytes.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: Int128, D: Deserializer<'de>
+    {
+        deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+}
+
+/// Serializes as a JSON number of arbitrary precision.
+///
+/// This module is only available with the `serde_json` feature, which builds `serde_json` with its
+/// `arbitrary_precision` feature. Every `u128` and `i128` is then written as a plain JSON number and
+/// read back exactly, instead of being rejected or rounded through `f64`. Deserializing rejects
+/// numbers with a fraction or an exponent, even if they are integers like `1.0` or `1e3`.
+///
+/// Other formats see how `serde_json` represents such numbers internally, so this module should
+/// only be used with JSON.
+///
+/// ```rust
+/// extern crate extprim;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+///
+/// use extprim::u128::u128;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Balance {
+///     #[serde(with = "extprim::serde::json_number")]
+///     wei: u128,
+/// }
+///
+/// # fn main() {
+/// let json = r#"{"wei":100000000000000000000000}"#;
+/// let balance = serde_json::from_str::<Balance>(json).unwrap();
+/// assert_eq!(balance.wei, u128::from_parts(5421, 0x02c7_e14a_f680_0000));
+/// assert_eq!(serde_json::to_string(&balance).unwrap(), json);
+/// # }
+/// ```
+#[cfg(feature = "serde_json")]
+pub mod json_number {
+    use serde_crate::{ser, Serialize, Serializer, Deserialize, Deserializer};
+    use serde_crate::de::{self, Unexpected};
+    use serde_json::Number;
+    use super::Int128;
+
+    /// Serializes the integer as a JSON number.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: Int128, S: Serializer
+    {
+        let number: Number = value.to_string().parse().map_err(ser::Error::custom)?;
+        number.serialize(serializer)
+    }
+
+    /// Deserializes the integer from a JSON number.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: Int128, D: Deserializer<'de>
+    {
+        let number = Number::deserialize(deserializer)?.to_string();
+        T::from_decimal_str(&number).ok_or_else(|| {
+            de::Error::invalid_value(Unexpected::Other(&number), &T::EXPECTING)
+        })
+    }
+}
+
+#[cfg(test)]
+mod serde_tests {
+    use serde_test::{assert_tokens, assert_de_tokens, assert_de_tokens_error, Token};
+    use u128::u128;
+    use i128::i128;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Hex(#[serde(with = "::serde::hex_prefixed")] u128, #[serde(with = "::serde::hex_prefixed")] i128);
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Decimal(#[serde(with = "::serde::decimal_str")] u128, #[serde(with = "::serde::decimal_str")] i128);
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Bytes(#[serde(with = "::serde::le_bytes")] u128, #[serde(with = "::serde::le_bytes")] i128);
+
+    fn tuple_struct(name: &'static str, a: Token, b: Token) -> Vec<Token> {
+        vec![Token::TupleStruct { name: name, len: 2 }, a, b, Token::TupleStructEnd]
+    }
+
+    #[test]
+    fn test_hex_prefixed() {
+        assert_tokens(&Hex(u128::new(0x1f), i128::new(-0xabc)),
+                      &tuple_struct("Hex", Token::Str("0x1f"), Token::Str("-0xabc")));
+        assert_tokens(&Hex(u128::zero(), i128::min_value()),
+                      &tuple_struct("Hex", Token::Str("0x0"), Token::Str("-0x80000000000000000000000000000000")));
+        assert_tokens(&Hex(u128::max_value(), i128::max_value()),
+                      &tuple_struct("Hex", Token::Str("0xffffffffffffffffffffffffffffffff"),
+                                    Token::Str("0x7fffffffffffffffffffffffffffffff")));
+        assert_de_tokens(&Hex(u128::new(0xAB), i128::zero()),
+                         &tuple_struct("Hex", Token::Str("0XAb"), Token::Str("-0x0")));
+    }
+
+    #[test]
+    fn test_hex_prefixed_errors() {
+        let mut tokens = tuple_struct("Hex", Token::Str("1f"), Token::Str("0x0"));
+        tokens.truncate(2);
+        assert_de_tokens_error::<Hex>(&tokens,
+                                      "invalid value: string \"1f\", expected an unsigned 128-bit integer as a hexadecimal string");
+        tokens[1] = Token::Str("-0x1");
+        assert_de_tokens_error::<Hex>(&tokens,
+                                      "invalid value: string \"-0x1\", expected an unsigned 128-bit integer as a hexadecimal string");
+        tokens[1] = Token::Str("0x+1");
+        assert_de_tokens_error::<Hex>(&tokens,
+                                      "invalid value: string \"0x+1\", expected an unsigned 128-bit integer as a hexadecimal string");
+        let tokens = [Token::TupleStruct { name: "Hex", len: 2 }, Token::Str("0x0"), Token::Str("0x80000000000000000000000000000000")];
+        assert_de_tokens_error::<Hex>(&tokens,
+                                      "invalid value: string \"0x80000000000000000000000000000000\", expected a signed 128-bit integer as a hexadecimal string");
+    }
+
+    #[test]
+    fn test_decimal_str() {
+        assert_tokens(&Decimal(u128::max_value(), i128::new(-21000)),
+                      &tuple_struct("Decimal", Token::Str("340282366920938463463374607431768211455"), Token::Str("-21000")));
+        let tokens = [Token::TupleStruct { name: "Decimal", len: 2 }, Token::U64(1)];
+        assert_de_tokens_error::<Decimal>(&tokens,
+                                          "invalid type: integer `1`, expected an unsigned 128-bit integer as a decimal string");
+        let tokens = [Token::TupleStruct { name: "Decimal", len: 2 }, Token::Str("0x1")];
+        assert_de_tokens_error::<Decimal>(&tokens,
+                                          "invalid value: string \"0x1\", expected an unsigned 128-bit integer as a decimal string");
+    }
+
+    #[test]
+    fn test_le_bytes() {
+        const U: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        const I: &[u8] = &[0xff; 16];
+        let value = Bytes(u128::from_parts(0x100f0e0d0c0b0a09, 0x0807060504030201), i128::new(-1));
+        assert_tokens(&value, &tuple_struct("Bytes", Token::Bytes(U), Token::Bytes(I)));
+
+        let mut tokens = vec![Token::TupleStruct { name: "Bytes", len: 2 }, Token::Seq { len: Some(16) }];
+        tokens.extend(U.iter().cloned().map(Token::U8));
+        tokens.extend_from_slice(&[Token::SeqEnd, Token::ByteBuf(I), Token::TupleStructEnd]);
+        assert_de_tokens(&value, &tokens);
+
+        let tokens = [Token::TupleStruct { name: "Bytes", len: 2 }, Token::Bytes(&U[..15])];
+        assert_de_tokens_error::<Bytes>(&tokens,
+                                        "invalid length 15, expected an unsigned 128-bit integer as 16 little endian bytes");
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Json {
+        #[serde(with = "::serde::json_number")] u: u128,
+        #[serde(with = "::serde::json_number")] i: i128,
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_json_number() {
+        use serde_json::{to_string, from_str};
+
+        let value = Json { u: u128::max_value(), i: i128::min_value() };
+        let json = r#"{"u":340282366920938463463374607431768211455,"i":-170141183460469231731687303715884105728}"#;
+        assert_eq!(to_string(&value).unwrap(), json);
+        assert_eq!(from_str::<Json>(json).unwrap(), value);
+
+        let value = Json { u: u128::new(7), i: i128::new(-7) };
+        assert_eq!(to_string(&value).unwrap(), r#"{"u":7,"i":-7}"#);
+        assert_eq!(from_str::<Json>(r#"{"u":7,"i":-7}"#).unwrap(), value);
+
+        let error = |json| from_str::<Json>(json).unwrap_err().to_string();
+        assert!(error(r#"{"u":-1,"i":0}"#).starts_with("invalid value: -1, expected an unsigned 128-bit integer"));
+        assert!(error(r#"{"u":1.0,"i":0}"#).starts_with("invalid value: 1.0, expected an unsigned 128-bit integer"));
+        assert!(error(r#"{"u":1,"i":1e3}"#).starts_with("invalid value: 1e3, expected a signed 128-bit integer"));
+        assert!(error(r#"{"u":340282366920938463463374607431768211456,"i":0}"#)
+            .starts_with("invalid value: 340282366920938463463374607431768211456, expected"));
+        assert!(error(r#"{"u":"1","i":0}"#).starts_with("invalid type: string \"1\""));
+    }
+}
@@ -0,0 +1,158 @@
+//! Deserializing the representation of the older versions of this crate.
+//!
+//! The integers used to derive `Serialize` and `Deserialize`, so they were written as the struct
+//! `{lo, hi}`, wrapped in a newtype struct for the signed types. In binary formats like bincode,
+//! this is the same as the little-endian bytes written now, but self-describing formats produced a
+//! map (e.g. `{"lo":1,"hi":0}` in JSON) or a sequence of the two fields. The visitors of the
+//! integers accept these forms too, so that existing data can still be read.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use serde_crate::{Deserialize, Deserializer};
+use serde_crate::de::{self, Visitor, SeqAccess, MapAccess, Expected};
+
+/// An integer type which used to be serialized as the struct `{lo, hi}`.
+pub(crate) trait Legacy: Sized {
+    /// The type of the `lo` and `hi` fields.
+    type Part: for<'de> Deserialize<'de>;
+
+    /// The little-endian bytes of the integer.
+    type Bytes: Default + AsMut<[u8]>;
+
+    /// What the visitors expect, e.g. "an unsigned 128-bit integer".
+    const EXPECTING: &'static str;
+
+    fn from_legacy_parts(lo: Self::Part, hi: Self::Part) -> Self;
+
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+}
+
+/// The fields of the legacy struct.
+enum Field {
+    Lo,
+    Hi,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Field, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+const FIELDS: &[&str] = &["lo", "hi"];
+
+impl<'de> Visitor<'de> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("`lo` or `hi`")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Field, E> {
+        match value {
+            0 => Ok(Field::Lo),
+            1 => Ok(Field::Hi),
+            _ => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+        match value {
+            "lo" => Ok(Field::Lo),
+            "hi" => Ok(Field::Hi),
+            _ => Err(E::unknown_field(value, FIELDS)),
+        }
+    }
+}
+
+/// Reads the legacy struct from a sequence of the `lo` and `hi` fields.
+pub(crate) fn visit_seq<'de, T, A, X>(mut seq: A, expected: &X) -> Result<T, A::Error>
+    where T: Legacy, A: SeqAccess<'de>, X: Expected
+{
+    let lo = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, expected))?;
+    let hi = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, expected))?;
+    Ok(T::from_legacy_parts(lo, hi))
+}
+
+/// Reads the legacy struct from a map with the keys `lo` and `hi`.
+pub(crate) fn visit_map<'de, T, A>(mut map: A) -> Result<T, A::Error>
+    where T: Legacy, A: MapAccess<'de>
+{
+    let mut lo = None;
+    let mut hi = None;
+    while let Some(field) = map.next_key()? {
+        match field {
+            Field::Lo if lo.is_none() => lo = Some(map.next_value()?),
+            Field::Hi if hi.is_none() => hi = Some(map.next_value()?),
+            Field::Lo => return Err(de::Error::duplicate_field("lo")),
+            Field::Hi => return Err(de::Error::duplicate_field("hi")),
+        }
+    }
+    let lo = lo.ok_or_else(|| de::Error::missing_field("lo"))?;
+    let hi = hi.ok_or_else(|| de::Error::missing_field("hi"))?;
+    Ok(T::from_legacy_parts(lo, hi))
+}
+
+/// Visitor of the binary formats. It accepts the little-endian bytes as a tuple, or the legacy
+/// struct.
+pub(crate) struct CompactVisitor<T>(PhantomData<T>);
+
+impl<T: Legacy> CompactVisitor<T> {
+    /// Deserializes the integer from a binary format.
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_tuple(size_of::<T::Bytes>(), CompactVisitor(PhantomData))
+    }
+}
+
+impl<'de, T: Legacy> Visitor<'de> for CompactVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(T::EXPECTING)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        // Formats which describe themselves may write the legacy struct as a sequence of 2 fields.
+        if seq.size_hint() == Some(2) {
+            return visit_seq(seq, &self);
+        }
+        let mut bytes = T::Bytes::default();
+        for (i, byte) in bytes.as_mut().iter_mut().enumerate() {
+            *byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(T::from_le_bytes(bytes))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<T, A::Error> {
+        visit_map(map)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        CompactVisitor::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod serde_legacy_tests {
+    use serde_crate::de::value::{SeqDeserializer, Error};
+    use serde_legacy::CompactVisitor;
+    use u128::u128;
+    use u256::u256;
+
+    #[test]
+    fn test_compact_seq() {
+        // e.g. MessagePack writes structs as arrays, whose length is known to the visitor.
+        let legacy = SeqDeserializer::<_, Error>::new(vec![1u64, 2].into_iter());
+        assert_eq!(CompactVisitor::deserialize(legacy), Ok(u128::from_parts(2, 1)));
+
+        let bytes = SeqDeserializer::<_, Error>::new((1..17).map(|b| b as u8));
+        assert_eq!(CompactVisitor::deserialize(bytes), Ok(u128::from_parts(0x100f0e0d0c0b0a09, 0x0807060504030201)));
+
+        let short = SeqDeserializer::<_, Error>::new(vec![0u8; 31].into_iter());
+        assert!(CompactVisitor::<u256>::deserialize(short).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Storing the 128-bit integers of this crate in Postgres with the `sqlx` crate.
+//!
+//! Like with the `postgres` feature, `u128` and `i128` are stored in `NUMERIC` columns. Reading a
+//! `NUMERIC` succeeds if it is a finite integer which fits in the target type; trailing zeros after
+//! the decimal point, as in `5.000`, are accepted. `INT8` columns can be read as well, both in the
+//! binary format and in the text format of simple queries.
+//!
+//! To store a 16-byte `BYTEA` instead, wrap the value in
+//! [`BigEndian`](../endian/struct.BigEndian.html) or [`LittleEndian`](../endian/struct.LittleEndian.html).
+//! A `BYTEA` holding a `BigEndian<u128>` sorts in numeric order.
+
+use sqlx::{Decode, Encode, Postgres, Type, ValueRef};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef};
+use sqlx::postgres::types::Oid;
+
+use endian::{BigEndian, LittleEndian};
+use pg_numeric;
+use u128::u128;
+use i128::i128;
+
+/// The `NUMERIC` type. Its OID can be found in `pg_type.dat` of Postgres.
+const NUMERIC: PgTypeInfo = PgTypeInfo::with_oid(Oid(1700));
+
+fn is_int8(value: &PgValueRef) -> bool {
+    *value.type_info() == <i64 as Type<Postgres>>::type_info()
+}
+
+fn compatible(ty: &PgTypeInfo) -> bool {
+    *ty == NUMERIC || *ty == <i64 as Type<Postgres>>::type_info()
+}
+
+impl Type<Postgres> for u128 {
+    fn type_info() -> PgTypeInfo {
+        NUMERIC
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        compatible(ty)
+    }
+}
+
+impl Type<Postgres> for i128 {
+    fn type_info() -> PgTypeInfo {
+        NUMERIC
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for u128 {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        pg_numeric::write_u128(*self, &mut **buf);
+        Ok(IsNull::No)
+    }
+}
+
+impl Encode<'_, Postgres> for i128 {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        pg_numeric::write_i128(*self, &mut **buf);
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for u128 {
+    fn decode(value: PgValueRef<'r>) -> Result<u128, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary if is_int8(&value) => {
+                pg_numeric::int8_to_u128(pg_numeric::read_int8(value.as_bytes()?)?)
+            }
+            PgValueFormat::Binary => pg_numeric::to_u128(pg_numeric::read_numeric(value.as_bytes()?)?),
+            PgValueFormat::Text => pg_numeric::to_u128(pg_numeric::parse_numeric(value.as_str()?)?),
+        }
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for i128 {
+    fn decode(value: PgValueRef<'r>) -> Result<i128, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary if is_int8(&value) => Ok(i128::new(pg_numeric::read_int8(value.as_bytes()?)?)),
+            PgValueFormat::Binary => pg_numeric::to_i128(pg_numeric::read_numeric(value.as_bytes()?)?),
+            PgValueFormat::Text => pg_numeric::to_i128(pg_numeric::parse_numeric(value.as_str()?)?),
+        }
+    }
+}
+
+macro_rules! impl_bytea {
+    ($($wrapper:ident<$ty:ident>: $to:ident, $from:ident;)*) => {$(
+        impl Type<Postgres> for $wrapper<$ty> {
+            fn type_info() -> PgTypeInfo {
+                <[u8] as Type<Postgres>>::type_info()
+            }
+        }
+
+        impl Encode<'_, Postgres> for $wrapper<$ty> {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                buf.extend_from_slice(&self.get().$to());
+                Ok(IsNull::No)
+            }
+        }
+
+        impl<'r> Decode<'r, Postgres> for $wrapper<$ty> {
+            fn decode(value: PgValueRef<'r>) -> Result<$wrapper<$ty>, BoxDynError> {
+                let bytea = <Vec<u8> as Decode<Postgres>>::decode(value)?;
+                if bytea.len() != 16 {
+                    return Err(format!("expected a bytea of 16 bytes, got {}", bytea.len()).into());
+                }
+                let mut bytes = [0; 16];
+                bytes.copy_from_slice(&bytea);
+                Ok($wrapper::new($ty::$from(bytes)))
+            }
+        }
+    )*}
+}
+
+impl_bytea! {
+    BigEndian<u128>: to_be_bytes, from_be_bytes;
+    BigEndian<i128>: to_be_bytes, from_be_bytes;
+    LittleEndian<u128>: to_le_bytes, from_le_bytes;
+    LittleEndian<i128>: to_le_bytes, from_le_bytes;
+}
+
+#[cfg(test)]
+mod sqlx_tests {
+    use sqlx::{Encode, Postgres, Type};
+    use sqlx::encode::IsNull;
+    use sqlx::postgres::PgArgumentBuffer;
+    use sqlx::postgres::types::Oid;
+
+    use endian::{BigEndian, LittleEndian};
+    use pg_numeric;
+    use u128::u128;
+    use i128::i128;
+
+    fn encode<'q, T: Encode<'q, Postgres>>(value: T) -> Vec<u8> {
+        let mut buf = PgArgumentBuffer::default();
+        match value.encode_by_ref(&mut buf).unwrap() {
+            IsNull::No => buf.to_vec(),
+            IsNull::Yes => unreachable!(),
+        }
+    }
+
+    fn numeric(value: i128) -> Vec<u8> {
+        let mut raw = Vec::new();
+        pg_numeric::write_i128(value, &mut raw);
+        raw
+    }
+
+    #[test]
+    fn test_type() {
+        assert_eq!(<u128 as Type<Postgres>>::type_info().oid(), Some(Oid(1700)));
+        assert!(<i128 as Type<Postgres>>::compatible(&<i64 as Type<Postgres>>::type_info()));
+        assert!(!<u128 as Type<Postgres>>::compatible(&<i32 as Type<Postgres>>::type_info()));
+        assert_eq!(<BigEndian<u128> as Type<Postgres>>::type_info(), <Vec<u8> as Type<Postgres>>::type_info());
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(u128::new(12345678)), numeric(i128::new(12345678)));
+        assert_eq!(encode(i128::min_value()), numeric(i128::min_value()));
+        assert_eq!(encode(u128::max_value()),
+                   vec![0, 10, 0, 9, 0, 0, 0, 0, 1, 84, 11, 7, 26, 36, 3, 170, 18, 26, 24, 193, 17, 255, 16, 221,
+                        26, 165, 5, 175]);
+    }
+
+    #[test]
+    fn test_encode_bytea() {
+        let value = u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10);
+        let be = (1..17).collect::<Vec<u8>>();
+        let le = be.iter().rev().cloned().collect::<Vec<u8>>();
+        assert_eq!(encode(BigEndian::new(value)), be);
+        assert_eq!(encode(LittleEndian::new(value)), le);
+        assert_eq!(encode(BigEndian::new(i128::new(-1))), vec![0xff; 16]);
+    }
+
+    #[test]
+    fn test_parse_numeric() {
+        assert_eq!(pg_numeric::parse_numeric("12345").ok(), Some((false, u128::new(12345))));
+        assert_eq!(pg_numeric::parse_numeric("-5.000").ok(), Some((true, u128::new(5))));
+        assert_eq!(pg_numeric::parse_numeric("340282366920938463463374607431768211455").ok(),
+                   Some((false, u128::max_value())));
+        assert_eq!(pg_numeric::to_u128(pg_numeric::parse_numeric("-0").unwrap()).ok(), Some(u128::zero()));
+        assert_eq!(pg_numeric::to_i128(pg_numeric::parse_numeric("-170141183460469231731687303715884105728").unwrap()).ok(),
+                   Some(i128::min_value()));
+        assert!(pg_numeric::to_u128(pg_numeric::parse_numeric("-1").unwrap()).is_err());
+        assert!(pg_numeric::parse_numeric("340282366920938463463374607431768211456").is_err());
+        assert!(pg_numeric::parse_numeric("1.5").is_err());
+        assert!(pg_numeric::parse_numeric("NaN").is_err());
+        assert!(pg_numeric::parse_numeric("-Infinity").is_err());
+        assert!(pg_numeric::parse_numeric("").is_err());
+        assert!(pg_numeric::parse_numeric("+1").is_err());
+    }
+}
@@ -0,0 +1,778 @@
+//! Methods which complete the API of the built-in `u128` and `i128` types.
+//!
+//! The goal is source compatibility: code that only uses the inherent methods and operators of
+//! the built-in 128-bit integers should compile against extprim's types by changing a type alias,
+//! and behave the same way. This module adds the methods of the standard library which are not
+//! implemented elsewhere, with the same names, signatures and panics. Nothing needs to be
+//! imported, since these are inherent methods of [`u128`](../u128/struct.u128.html) and
+//! [`i128`](../i128/struct.i128.html).
+//!
+//! ```rust
+//! // Switch to `type Int = u128;` when the compiler supports the built-in type.
+//! type Int = extprim::u128::u128;
+//!
+//! fn digits(x: Int) -> u32 {
+//!     x.checked_ilog10().map_or(1, |n| n + 1)
+//! }
+//!
+//! assert_eq!(Int::BITS, 128);
+//! assert_eq!(digits(Int::MAX), 39);
+//! assert_eq!(Int::from_str_radix("+ff", 16).unwrap().isqrt(), Int::from_parts(0, 15));
+//! ```
+//!
+//! The only intended difference to the built-in types is the message of the panics on arithmetic
+//! overflow in debug builds.
+
+use u128::u128;
+use i128::{i128, div_rem_euclid, checked_div_rem_euclid};
+
+/// Reverses the order of bits of a 64-bit number.
+fn reverse_bits_64(x: u64) -> u64 {
+    let x = ((x >> 1) & 0x5555_5555_5555_5555) | ((x & 0x5555_5555_5555_5555) << 1);
+    let x = ((x >> 2) & 0x3333_3333_3333_3333) | ((x & 0x3333_3333_3333_3333) << 2);
+    let x = ((x >> 4) & 0x0f0f_0f0f_0f0f_0f0f) | ((x & 0x0f0f_0f0f_0f0f_0f0f) << 4);
+    x.swap_bytes()
+}
+
+//{{{ u128
+
+impl u128 {
+    /// The smallest value that can be represented by this integer type.
+    pub const MIN: u128 = ::u128::MIN;
+
+    /// The largest value that can be represented by this integer type.
+    pub const MAX: u128 = ::u128::MAX;
+
+    /// The size of this integer type in bits.
+    pub const BITS: u32 = 128;
+
+    /// Returns the number of leading ones in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::from_parts(!0, 0x8000_0000_0000_0000).leading_ones(), 65);
+    /// ```
+    pub fn leading_ones(self) -> u32 {
+        (!self).leading_zeros()
+    }
+
+    /// Returns the number of trailing ones in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::from_parts(1, !0).trailing_ones(), 65);
+    /// ```
+    pub fn trailing_ones(self) -> u32 {
+        (!self).trailing_zeros()
+    }
+
+    /// Reverses the order of bits in the integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(0b1011).reverse_bits(), u128::from_parts(0xd000_0000_0000_0000, 0));
+    /// ```
+    pub fn reverse_bits(self) -> u128 {
+        u128 { lo: reverse_bits_64(self.hi), hi: reverse_bits_64(self.lo) }
+    }
+
+    /// Checked negation. Computes `-self`, returning `None` unless `self == 0`.
+    pub fn checked_neg(self) -> Option<u128> {
+        if self == u128::zero() { Some(self) } else { None }
+    }
+
+    /// Negates `self` in a wrapping fashion, along with a boolean indicating whether an overflow
+    /// happened, i.e. whether `self` is not 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(2).overflowing_neg(), (u128::max_value() - u128::one(), true));
+    /// assert_eq!(u128::zero().overflowing_neg(), (u128::zero(), false));
+    /// ```
+    pub fn overflowing_neg(self) -> (u128, bool) {
+        (self.wrapping_neg(), self != u128::zero())
+    }
+
+    /// Raises `self` to the power of `exp`, along with a boolean indicating whether an overflow
+    /// happened. If an overflow happened then the wrapped value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(3).overflowing_pow(5), (u128::new(243), false));
+    /// assert_eq!(u128::new(2).overflowing_pow(130), (u128::zero(), true));
+    /// ```
+    pub fn overflowing_pow(self, mut exp: u32) -> (u128, bool) {
+        let mut base = self;
+        let mut acc = u128::one();
+        let mut overflown = false;
+        while exp > 0 {
+            if exp & 1 != 0 {
+                let (res, o) = acc.overflowing_mul(base);
+                acc = res;
+                overflown |= o;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                let (res, o) = base.overflowing_mul(base);
+                base = res;
+                overflown |= o;
+            }
+        }
+        (acc, overflown)
+    }
+
+    /// Checked exponentiation. Computes `self.pow(exp)`, returning `None` if an overflow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(2).checked_pow(127), Some(u128::from_parts(0x8000_0000_0000_0000, 0)));
+    /// assert_eq!(u128::new(2).checked_pow(128), None);
+    /// ```
+    pub fn checked_pow(self, exp: u32) -> Option<u128> {
+        match self.overflowing_pow(exp) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping (modular) exponentiation. Computes `self.pow(exp)`, wrapping around at the
+    /// boundary of the type.
+    pub fn wrapping_pow(self, exp: u32) -> u128 {
+        self.overflowing_pow(exp).0
+    }
+
+    /// Saturating exponentiation. Computes `self.pow(exp)`, saturating at the numeric bounds
+    /// instead of overflowing.
+    pub fn saturating_pow(self, exp: u32) -> u128 {
+        self.checked_pow(exp).unwrap_or(::u128::MAX)
+    }
+
+    /// Saturating integer division. Computes `self / other`. This is the same as `self / other`,
+    /// since the division of unsigned numbers cannot overflow.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn saturating_div(self, other: u128) -> u128 {
+        self / other
+    }
+
+    /// Performs Euclidean division. This is the same as `self / other` for unsigned numbers.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn div_euclid(self, other: u128) -> u128 {
+        self / other
+    }
+
+    /// Calculates the least remainder of `self (mod other)`. This is the same as `self % other`
+    /// for unsigned numbers.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn rem_euclid(self, other: u128) -> u128 {
+        self % other
+    }
+
+    /// Checked Euclidean division. Computes `self.div_euclid(other)`, returning `None` if
+    /// `other == 0`.
+    pub fn checked_div_euclid(self, other: u128) -> Option<u128> {
+        self.checked_div(other)
+    }
+
+    /// Checked Euclidean remainder. Computes `self.rem_euclid(other)`, returning `None` if
+    /// `other == 0`.
+    pub fn checked_rem_euclid(self, other: u128) -> Option<u128> {
+        self.checked_rem(other)
+    }
+
+    /// Wrapping Euclidean division. This is the same as `self / other`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn wrapping_div_euclid(self, other: u128) -> u128 {
+        self.wrapping_div(other)
+    }
+
+    /// Wrapping Euclidean remainder. This is the same as `self % other`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn wrapping_rem_euclid(self, other: u128) -> u128 {
+        self.wrapping_rem(other)
+    }
+
+    /// Returns the base 2 logarithm of the number, rounded down, or `None` if `self` is 0.
+    pub fn checked_ilog2(self) -> Option<u32> {
+        if self == u128::zero() {
+            None
+        } else {
+            Some(127 - self.leading_zeros())
+        }
+    }
+
+    /// Returns the base 10 logarithm of the number, rounded down, or `None` if `self` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(999).checked_ilog10(), Some(2));
+    /// assert_eq!(u128::max_value().checked_ilog10(), Some(38));
+    /// assert_eq!(u128::zero().checked_ilog10(), None);
+    /// ```
+    pub fn checked_ilog10(self) -> Option<u32> {
+        const TEN_POW_19: u64 = 10_000_000_000_000_000_000;
+
+        if self == u128::zero() {
+            return None;
+        }
+        let mut n = 0;
+        let mut x = self;
+        while x.hi != 0 || x.lo >= TEN_POW_19 {
+            x /= u128::new(TEN_POW_19);
+            n += 19;
+        }
+        let mut x = x.lo;
+        while x >= 10 {
+            x /= 10;
+            n += 1;
+        }
+        Some(n)
+    }
+
+    /// Returns the base 2 logarithm of the number, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `self` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::from_parts(1, 0).ilog2(), 64);
+    /// ```
+    pub fn ilog2(self) -> u32 {
+        self.checked_ilog2().expect("argument of integer logarithm must be positive")
+    }
+
+    /// Returns the base 10 logarithm of the number, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `self` is 0.
+    pub fn ilog10(self) -> u32 {
+        self.checked_ilog10().expect("argument of integer logarithm must be positive")
+    }
+
+    /// Returns the square root of the number, rounded down.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(99).isqrt(), u128::new(9));
+    /// assert_eq!(u128::max_value().isqrt(), u128::new(!0));
+    /// ```
+    pub fn isqrt(self) -> u128 {
+        if self < u128::new(2) {
+            return self;
+        }
+        // Newton's method decreases monotonically when starting from above the root.
+        let mut x = u128::one() << ((129 - self.leading_zeros()) / 2);
+        loop {
+            let y = (x + self / x) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    /// Computes the absolute difference between `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(3).abs_diff(u128::new(10)), u128::new(7));
+    /// ```
+    pub fn abs_diff(self, other: u128) -> u128 {
+        if self < other {
+            other - self
+        } else {
+            self - other
+        }
+    }
+
+    /// Returns the memory representation of this integer as a byte array in native byte order.
+    pub fn to_ne_bytes(self) -> [u8; 16] {
+        if cfg!(target_endian="little") {
+            self.to_le_bytes()
+        } else {
+            self.to_be_bytes()
+        }
+    }
+
+    /// Creates an integer value from its memory representation as a byte array in native byte
+    /// order.
+    pub fn from_ne_bytes(bytes: [u8; 16]) -> u128 {
+        if cfg!(target_endian="little") {
+            u128::from_le_bytes(bytes)
+        } else {
+            u128::from_be_bytes(bytes)
+        }
+    }
+}
+
+//}}}
+
+//{{{ i128
+
+impl i128 {
+    /// The smallest value that can be represented by this integer type.
+    pub const MIN: i128 = ::i128::MIN;
+
+    /// The largest value that can be represented by this integer type.
+    pub const MAX: i128 = ::i128::MAX;
+
+    /// The size of this integer type in bits.
+    pub const BITS: u32 = 128;
+
+    /// Returns the number of leading ones in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-4).leading_ones(), 126);
+    /// ```
+    pub fn leading_ones(self) -> u32 {
+        self.0.leading_ones()
+    }
+
+    /// Returns the number of trailing ones in the binary representation of `self`.
+    pub fn trailing_ones(self) -> u32 {
+        self.0.trailing_ones()
+    }
+
+    /// Reverses the order of bits in the integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::one().reverse_bits(), i128::min_value());
+    /// ```
+    pub fn reverse_bits(self) -> i128 {
+        i128(self.0.reverse_bits())
+    }
+
+    /// Raises `self` to the power of `exp`, along with a boolean indicating whether an overflow
+    /// happened. If an overflow happened then the wrapped value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-3).overflowing_pow(5), (i128::new(-243), false));
+    /// assert_eq!(i128::new(-2).overflowing_pow(127), (i128::min_value(), false));
+    /// assert_eq!(i128::new(2).overflowing_pow(127), (i128::min_value(), true));
+    /// ```
+    pub fn overflowing_pow(self, mut exp: u32) -> (i128, bool) {
+        let mut base = self;
+        let mut acc = i128::one();
+        let mut overflown = false;
+        while exp > 0 {
+            if exp & 1 != 0 {
+                let (res, o) = acc.overflowing_mul(base);
+                acc = res;
+                overflown |= o;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                let (res, o) = base.overflowing_mul(base);
+                base = res;
+                overflown |= o;
+            }
+        }
+        (acc, overflown)
+    }
+
+    /// Checked exponentiation. Computes `self.pow(exp)`, returning `None` if an overflow occurred.
+    pub fn checked_pow(self, exp: u32) -> Option<i128> {
+        match self.overflowing_pow(exp) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping (modular) exponentiation. Computes `self.pow(exp)`, wrapping around at the
+    /// boundary of the type.
+    pub fn wrapping_pow(self, exp: u32) -> i128 {
+        self.overflowing_pow(exp).0
+    }
+
+    /// Saturating exponentiation. Computes `self.pow(exp)`, saturating at the numeric bounds
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-10).saturating_pow(39), i128::min_value());
+    /// assert_eq!(i128::new(-10).saturating_pow(40), i128::max_value());
+    /// ```
+    pub fn saturating_pow(self, exp: u32) -> i128 {
+        self.checked_pow(exp).unwrap_or(if self.is_negative() && exp & 1 != 0 {
+            ::i128::MIN
+        } else {
+            ::i128::MAX
+        })
+    }
+
+    /// Saturating integer division. Computes `self / other`, saturating at the numeric bounds
+    /// instead of overflowing, i.e. `MIN / -1` is `MAX`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn saturating_div(self, other: i128) -> i128 {
+        match self.overflowing_div(other) {
+            (v, false) => v,
+            (_, true) => ::i128::MAX,
+        }
+    }
+
+    /// Calculates the quotient of Euclidean division of `self` by `other`, such that the
+    /// remainder [`rem_euclid`](#method.rem_euclid) is never negative.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0. If debug assertions is enabled, this function
+    /// will also panic on overflow (when computing `MIN.div_euclid(-1)`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-7).div_euclid(i128::new(4)), i128::new(-2));
+    /// assert_eq!(i128::new(-7).div_euclid(i128::new(-4)), i128::new(2));
+    /// ```
+    pub fn div_euclid(self, other: i128) -> i128 {
+        div_rem_euclid(self, other).0
+    }
+
+    /// Calculates the least non-negative remainder of `self (mod other)`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0. If debug assertions is enabled, this function
+    /// will also panic on overflow (when computing `MIN.rem_euclid(-1)`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-7).rem_euclid(i128::new(4)), i128::new(1));
+    /// assert_eq!(i128::new(-7).rem_euclid(i128::new(-4)), i128::new(1));
+    /// ```
+    pub fn rem_euclid(self, other: i128) -> i128 {
+        div_rem_euclid(self, other).1
+    }
+
+    /// Checked Euclidean division. Computes `self.div_euclid(other)`, returning `None` if
+    /// `other == 0` or the division overflows.
+    pub fn checked_div_euclid(self, other: i128) -> Option<i128> {
+        checked_div_rem_euclid(self, other).map(|(q, _)| q)
+    }
+
+    /// Checked Euclidean remainder. Computes `self.rem_euclid(other)`, returning `None` if
+    /// `other == 0` or the division overflows.
+    pub fn checked_rem_euclid(self, other: i128) -> Option<i128> {
+        checked_div_rem_euclid(self, other).map(|(_, r)| r)
+    }
+
+    /// Wrapping Euclidean division. Computes `self.div_euclid(other)`, wrapping around at the
+    /// boundary of the type, i.e. `MIN.wrapping_div_euclid(-1)` is `MIN`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn wrapping_div_euclid(self, other: i128) -> i128 {
+        let q = self.wrapping_div(other);
+        if self.wrapping_rem(other).is_negative() {
+            if other.is_negative() {
+                q.wrapping_add(i128::one())
+            } else {
+                q.wrapping_sub(i128::one())
+            }
+        } else {
+            q
+        }
+    }
+
+    /// Wrapping Euclidean remainder. Computes `self.rem_euclid(other)`, wrapping around at the
+    /// boundary of the type, i.e. `MIN.wrapping_rem_euclid(-1)` is 0.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    pub fn wrapping_rem_euclid(self, other: i128) -> i128 {
+        let r = self.wrapping_rem(other);
+        if r.is_negative() {
+            if other.is_negative() { r.wrapping_sub(other) } else { r.wrapping_add(other) }
+        } else {
+            r
+        }
+    }
+
+    /// Returns the base 2 logarithm of the number, rounded down, or `None` if `self` is not
+    /// positive.
+    pub fn checked_ilog2(self) -> Option<u32> {
+        if self.is_positive() { self.0.checked_ilog2() } else { None }
+    }
+
+    /// Returns the base 10 logarithm of the number, rounded down, or `None` if `self` is not
+    /// positive.
+    pub fn checked_ilog10(self) -> Option<u32> {
+        if self.is_positive() { self.0.checked_ilog10() } else { None }
+    }
+
+    /// Returns the base 2 logarithm of the number, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `self` is not positive.
+    pub fn ilog2(self) -> u32 {
+        self.checked_ilog2().expect("argument of integer logarithm must be positive")
+    }
+
+    /// Returns the base 10 logarithm of the number, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `self` is not positive.
+    pub fn ilog10(self) -> u32 {
+        self.checked_ilog10().expect("argument of integer logarithm must be positive")
+    }
+
+    /// Returns the square root of the number, rounded down, or `None` if `self` is negative.
+    pub fn checked_isqrt(self) -> Option<i128> {
+        if self.is_negative() { None } else { Some(i128(self.0.isqrt())) }
+    }
+
+    /// Returns the square root of the number, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `self` is negative.
+    pub fn isqrt(self) -> i128 {
+        self.checked_isqrt().expect("argument of integer square root cannot be negative")
+    }
+
+    /// Computes the absolute value of `self` without any wrapping or panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::min_value().unsigned_abs(), u128::from_parts(0x8000_0000_0000_0000, 0));
+    /// ```
+    pub fn unsigned_abs(self) -> u128 {
+        if self.is_negative() { self.0.wrapping_neg() } else { self.0 }
+    }
+
+    /// Computes the absolute value of `self`, along with a boolean indicating whether an overflow
+    /// happened, i.e. whether `self` is `MIN`.
+    pub fn overflowing_abs(self) -> (i128, bool) {
+        (i128(self.unsigned_abs()), self == ::i128::MIN)
+    }
+
+    /// Checked absolute value. Computes `self.abs()`, returning `None` if `self == MIN`.
+    pub fn checked_abs(self) -> Option<i128> {
+        match self.overflowing_abs() {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping (modular) absolute value. Computes `self.abs()`, wrapping around at the boundary
+    /// of the type, i.e. `MIN.wrapping_abs()` is `MIN`.
+    pub fn wrapping_abs(self) -> i128 {
+        self.overflowing_abs().0
+    }
+
+    /// Saturating absolute value. Computes `self.abs()`, returning `MAX` if `self == MIN`.
+    pub fn saturating_abs(self) -> i128 {
+        self.checked_abs().unwrap_or(::i128::MAX)
+    }
+
+    /// Computes the absolute difference between `self` and `other`, which always fits in a
+    /// `u128`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(i128::new(-3).abs_diff(i128::new(10)), u128::new(13));
+    /// assert_eq!(i128::min_value().abs_diff(i128::max_value()), u128::max_value());
+    /// ```
+    pub fn abs_diff(self, other: i128) -> u128 {
+        if self < other {
+            other.0.wrapping_sub(self.0)
+        } else {
+            self.0.wrapping_sub(other.0)
+        }
+    }
+
+    /// Returns the memory representation of this integer as a byte array in native byte order.
+    pub fn to_ne_bytes(self) -> [u8; 16] {
+        self.0.to_ne_bytes()
+    }
+
+    /// Creates an integer value from its memory representation as a byte array in native byte
+    /// order.
+    pub fn from_ne_bytes(bytes: [u8; 16]) -> i128 {
+        i128(u128::from_ne_bytes(bytes))
+    }
+}
+
+//}}}
+
+#[cfg(all(test, extprim_has_isqrt))]
+mod std_compat_tests {
+    use u128::u128;
+    use i128::i128;
+    use compiler_rt::builtins::{U128, I128};
+
+    fn unsigned_values() -> Vec<U128> {
+        let mut values = vec![0, 1, 2, 3, 9, 10, 99, 100, 0xffff_ffff_ffff_ffff, 1 << 64,
+                              10_000_000_000_000_000_000, 0x1234_5678_9abc_def0_0fed_cba9_8765_4321];
+        for shift in 0..128 {
+            values.push(!0 >> shift);
+            values.push(1 << shift);
+        }
+        values
+    }
+
+    fn signed_values() -> Vec<I128> {
+        let mut values: Vec<I128> = unsigned_values().into_iter().map(|x| x as I128).collect();
+        values.extend(unsigned_values().into_iter().map(|x| (x as I128).wrapping_neg()));
+        values
+    }
+
+    #[test]
+    fn test_unsigned() {
+        assert_eq!(u128::MAX.as_built_in(), U128::MAX);
+        assert_eq!(u128::BITS, U128::MAX.count_ones());
+        for &a in unsigned_values().iter() {
+            let x = u128::from_built_in(a);
+            assert_eq!(x.leading_ones(), (!a).leading_zeros(), "{:x}", a);
+            assert_eq!(x.trailing_ones(), (!a).trailing_zeros(), "{:x}", a);
+            assert_eq!(x.reverse_bits().as_built_in(), a.reverse_bits(), "{:x}", a);
+            assert_eq!(x.overflowing_neg().1, a != 0);
+            assert_eq!(x.checked_ilog2(), if a == 0 { None } else { Some(127 - a.leading_zeros()) });
+            assert_eq!(x.checked_ilog10(), if a == 0 { None } else { Some(a.to_string().len() as u32 - 1) });
+            assert_eq!(x.isqrt().as_built_in(), a.isqrt(), "{:x}", a);
+            assert_eq!(u128::from_ne_bytes(x.to_ne_bytes()), x);
+            for exp in 0..130 {
+                let (v, o) = x.overflowing_pow(exp);
+                assert_eq!((v.as_built_in(), o), a.overflowing_pow(exp), "{:x} ** {}", a, exp);
+            }
+            for &b in unsigned_values().iter() {
+                let y = u128::from_built_in(b);
+                assert_eq!(x.abs_diff(y).as_built_in(), a.abs_diff(b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed() {
+        assert_eq!(i128::MIN.as_built_in(), I128::MIN);
+        assert_eq!(i128::MAX.as_built_in(), I128::MAX);
+        for &a in signed_values().iter() {
+            let x = i128::from_built_in(a);
+            assert_eq!(x.leading_ones(), (!a).leading_zeros(), "{:x}", a);
+            assert_eq!(x.reverse_bits().as_built_in(), a.reverse_bits());
+            assert_eq!(x.unsigned_abs().as_built_in(), a.wrapping_abs() as U128);
+            assert_eq!(x.checked_abs().map(|v| v.as_built_in()), a.checked_abs());
+            assert_eq!(x.saturating_abs().as_built_in(), a.saturating_abs());
+            assert_eq!(x.checked_ilog2(), if a > 0 { Some(127 - a.leading_zeros()) } else { None });
+            assert_eq!(x.checked_isqrt().map(|v| v.as_built_in()), a.checked_isqrt());
+            for exp in 0..130 {
+                let (v, o) = x.overflowing_pow(exp);
+                assert_eq!((v.as_built_in(), o), a.overflowing_pow(exp), "{:x} ** {}", a, exp);
+                assert_eq!(x.saturating_pow(exp).as_built_in(), a.saturating_pow(exp));
+            }
+            for &b in signed_values().iter().step_by(7) {
+                let y = i128::from_built_in(b);
+                assert_eq!(x.abs_diff(y).as_built_in(), a.abs_diff(b));
+                if b != 0 {
+                    assert_eq!(x.wrapping_div_euclid(y).as_built_in(), a.wrapping_div_euclid(b), "{} / {}", a, b);
+                    assert_eq!(x.wrapping_rem_euclid(y).as_built_in(), a.wrapping_rem_euclid(b), "{} % {}", a, b);
+                    assert_eq!(x.checked_div_euclid(y).map(|v| v.as_built_in()), a.checked_div_euclid(b));
+                    assert_eq!(x.checked_rem_euclid(y).map(|v| v.as_built_in()), a.checked_rem_euclid(b));
+                    assert_eq!(x.saturating_div(y).as_built_in(), a.saturating_div(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        for s in &["", "+", "-", "+1", "-1", "++1", "+-1", "-+1", "00", "-0", "+ff"] {
+            assert_eq!(u128::from_str_radix(s, 16).map(|x| x.as_built_in()), U128::from_str_radix(s, 16), "{:?}", s);
+            assert_eq!(i128::from_str_radix(s, 16).map(|x| x.as_built_in()), I128::from_str_radix(s, 16), "{:?}", s);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="argument of integer logarithm must be positive")]
+    fn test_ilog10_of_zero() {
+        u128::zero().ilog10();
+    }
+
+    #[test]
+    #[should_panic(expected="argument of integer square root cannot be negative")]
+    fn test_isqrt_of_negative() {
+        i128::new(-1).isqrt();
+    }
+}
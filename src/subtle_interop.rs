@@ -0,0 +1,95 @@
+//! Constant-time comparison and selection of the 128-bit integers with the `subtle` crate.
+//!
+//! The implementations are built from those of `u64` on the two halves, without branches on the
+//! values. Signed integers are compared by flipping the sign bit, which maps them to unsigned
+//! integers of the same order.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater, ConstantTimeLess};
+
+use u128::u128;
+use i128::i128;
+
+impl ConstantTimeEq for u128 {
+    fn ct_eq(&self, other: &u128) -> Choice {
+        self.lo.ct_eq(&other.lo) & self.hi.ct_eq(&other.hi)
+    }
+}
+
+impl ConditionallySelectable for u128 {
+    fn conditional_select(a: &u128, b: &u128, choice: Choice) -> u128 {
+        u128 {
+            lo: u64::conditional_select(&a.lo, &b.lo, choice),
+            hi: u64::conditional_select(&a.hi, &b.hi, choice),
+        }
+    }
+}
+
+impl ConstantTimeGreater for u128 {
+    fn ct_gt(&self, other: &u128) -> Choice {
+        self.hi.ct_gt(&other.hi) | (self.hi.ct_eq(&other.hi) & self.lo.ct_gt(&other.lo))
+    }
+}
+
+impl ConstantTimeLess for u128 {}
+
+impl ConstantTimeEq for i128 {
+    fn ct_eq(&self, other: &i128) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for i128 {
+    fn conditional_select(a: &i128, b: &i128, choice: Choice) -> i128 {
+        i128(u128::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl ConstantTimeGreater for i128 {
+    fn ct_gt(&self, other: &i128) -> Choice {
+        self.to_ordered_u128().ct_gt(&other.to_ordered_u128())
+    }
+}
+
+impl ConstantTimeLess for i128 {}
+
+#[cfg(test)]
+mod subtle_tests {
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater, ConstantTimeLess};
+
+    use u128::u128;
+    use i128::i128;
+
+    #[test]
+    fn test_u128() {
+        let values = [u128::zero(), u128::one(), u128::new(!0), u128::from_parts(1, 0),
+                      u128::from_parts(1, 1), u128::from_parts(!0, 0), u128::max_value()];
+        for a in &values {
+            for b in &values {
+                assert_eq!(bool::from(a.ct_eq(b)), a == b);
+                assert_eq!(bool::from(a.ct_gt(b)), a > b);
+                assert_eq!(bool::from(a.ct_lt(b)), a < b);
+                assert_eq!(u128::conditional_select(a, b, Choice::from(0)), *a);
+                assert_eq!(u128::conditional_select(a, b, Choice::from(1)), *b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_i128() {
+        let values = [i128::min_value(), i128::from_parts(-1, 0), i128::new(-1), i128::zero(),
+                      i128::one(), i128::from_parts(0, !0), i128::max_value()];
+        for a in &values {
+            for b in &values {
+                assert_eq!(bool::from(a.ct_eq(b)), a == b);
+                assert_eq!(bool::from(a.ct_gt(b)), a > b);
+                assert_eq!(bool::from(a.ct_lt(b)), a < b);
+                assert_eq!(i128::conditional_select(a, b, Choice::from(0)), *a);
+                assert_eq!(i128::conditional_select(a, b, Choice::from(1)), *b);
+            }
+        }
+
+        let mut x = i128::new(-5);
+        x.conditional_assign(&i128::new(7), Choice::from(1));
+        assert_eq!(x, i128::new(7));
+    }
+}
@@ -0,0 +1,253 @@
+//! Panic-free versions of the operations which may panic.
+//!
+//! The operators of `u128` and `i128` panic on overflow in debug builds and on division by zero,
+//! and a few methods such as `from_str_radix` panic on invalid arguments. The functions in this
+//! module never panic: every failure is reported as `None` or `Err` instead, so they can be used
+//! where panicking is not an option, e.g. in firmware which is checked to contain no panic paths.
+//!
+//! The functions are generic over [`TotalInt`](trait.TotalInt.html), which is implemented for
+//! `u128` and `i128`. To keep the operator syntax, use [`Checked`](struct.Checked.html), which is
+//! re-exported here since its operators never panic either.
+//!
+//! ```rust
+//! use extprim::u128::u128;
+//! use extprim::total;
+//!
+//! let a = u128::new(100);
+//! assert_eq!(total::div_rem(a, u128::new(8)), Some((u128::new(12), u128::new(4))));
+//! assert_eq!(total::div(a, u128::zero()), None);
+//! assert_eq!(total::parse_radix::<u128>("ff", 16), Ok(u128::new(255)));
+//! assert!(total::parse_radix::<u128>("ff", 99).is_err());
+//! ```
+
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use error;
+use traits::Round;
+use u128::u128;
+use i128::i128;
+
+pub use traits::Checked;
+
+/// Integer types which support the panic-free operations of this module.
+pub trait TotalInt: Copy + FromStr<Err=ParseIntError> {
+    #[doc(hidden)]
+    const ONE: Self;
+
+    #[doc(hidden)]
+    fn total_add(self, other: Self) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_sub(self, other: Self) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_mul(self, other: Self) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_div(self, other: Self) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_rem(self, other: Self) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_neg(self) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_shl(self, shift: u32) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_shr(self, shift: u32) -> Option<Self>;
+    #[doc(hidden)]
+    fn total_from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError>;
+    #[doc(hidden)]
+    fn total_from_f64(value: f64) -> Option<Self>;
+}
+
+macro_rules! impl_total_int {
+    ($ty:ident, $neg:expr) => {
+        impl TotalInt for $ty {
+            const ONE: $ty = ::$ty::ONE;
+
+            fn total_add(self, other: $ty) -> Option<$ty> { self.checked_add(other) }
+            fn total_sub(self, other: $ty) -> Option<$ty> { self.checked_sub(other) }
+            fn total_mul(self, other: $ty) -> Option<$ty> { self.checked_mul(other) }
+            fn total_div(self, other: $ty) -> Option<$ty> { self.checked_div(other) }
+            fn total_rem(self, other: $ty) -> Option<$ty> { self.checked_rem(other) }
+            fn total_neg(self) -> Option<$ty> { $neg(self) }
+            fn total_shl(self, shift: u32) -> Option<$ty> { self.checked_shl(shift) }
+            fn total_shr(self, shift: u32) -> Option<$ty> { self.checked_shr(shift) }
+
+            fn total_from_str_radix(src: &str, radix: u32) -> Result<$ty, ParseIntError> {
+                if radix >= 2 && radix <= 36 {
+                    $ty::from_str_radix(src, radix)
+                } else {
+                    Err(error::invalid_digit())
+                }
+            }
+
+            fn total_from_f64(value: f64) -> Option<$ty> {
+                $ty::from_f64_with(value, Round::Trunc)
+            }
+        }
+    }
+}
+
+impl_total_int!(u128, |x: u128| if x == u128::zero() { Some(x) } else { None });
+impl_total_int!(i128, i128::checked_neg);
+
+/// Computes `a + b`, returning `None` on overflow.
+pub fn add<T: TotalInt>(a: T, b: T) -> Option<T> {
+    a.total_add(b)
+}
+
+/// Computes `a - b`, returning `None` on overflow.
+pub fn sub<T: TotalInt>(a: T, b: T) -> Option<T> {
+    a.total_sub(b)
+}
+
+/// Computes `a * b`, returning `None` on overflow.
+pub fn mul<T: TotalInt>(a: T, b: T) -> Option<T> {
+    a.total_mul(b)
+}
+
+/// Computes `a / b`, returning `None` if `b` is 0 or the division overflows.
+pub fn div<T: TotalInt>(a: T, b: T) -> Option<T> {
+    a.total_div(b)
+}
+
+/// Computes `a % b`, returning `None` if `b` is 0 or the division overflows.
+pub fn rem<T: TotalInt>(a: T, b: T) -> Option<T> {
+    a.total_rem(b)
+}
+
+/// Computes `(a / b, a % b)`, returning `None` if `b` is 0 or the division overflows.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::i128::i128;
+/// use extprim::total;
+///
+/// assert_eq!(total::div_rem(i128::new(-7), i128::new(2)), Some((i128::new(-3), i128::new(-1))));
+/// assert_eq!(total::div_rem(i128::min_value(), i128::new(-1)), None);
+/// ```
+pub fn div_rem<T: TotalInt>(a: T, b: T) -> Option<(T, T)> {
+    let q = a.total_div(b)?;
+    let r = a.total_rem(b)?;
+    Some((q, r))
+}
+
+/// Computes `-a`, returning `None` on overflow. For unsigned numbers, only 0 can be negated.
+pub fn neg<T: TotalInt>(a: T) -> Option<T> {
+    a.total_neg()
+}
+
+/// Computes `a << shift`, returning `None` if `shift` is 128 or larger.
+pub fn shl<T: TotalInt>(a: T, shift: u32) -> Option<T> {
+    a.total_shl(shift)
+}
+
+/// Computes `a >> shift`, returning `None` if `shift` is 128 or larger.
+pub fn shr<T: TotalInt>(a: T, shift: u32) -> Option<T> {
+    a.total_shr(shift)
+}
+
+/// Raises `base` to the power of `exp`, returning `None` on overflow.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::u128;
+/// use extprim::total;
+///
+/// assert_eq!(total::pow(u128::new(10), 38), Some(u128::from_parts(5421010862427522170, 687399551400673280)));
+/// assert_eq!(total::pow(u128::new(10), 39), None);
+/// ```
+pub fn pow<T: TotalInt>(base: T, mut exp: u32) -> Option<T> {
+    let mut result = T::ONE;
+    let mut base = base;
+    while exp > 0 {
+        if exp & 1 != 0 {
+            result = result.total_mul(base)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = base.total_mul(base)?;
+        }
+    }
+    Some(result)
+}
+
+/// Parses a decimal string. This is the same as `str::parse`, which never panics.
+pub fn parse<T: TotalInt>(src: &str) -> Result<T, ParseIntError> {
+    src.parse()
+}
+
+/// Parses a string in the given base. Unlike `from_str_radix`, an invalid radix is reported as an
+/// "invalid digit" error instead of a panic.
+pub fn parse_radix<T: TotalInt>(src: &str, radix: u32) -> Result<T, ParseIntError> {
+    T::total_from_str_radix(src, radix)
+}
+
+/// Converts a floating point number, rounding towards zero. Returns `None` if the number is NaN,
+/// infinite, or outside of the range of the type.
+pub fn from_f64<T: TotalInt>(value: f64) -> Option<T> {
+    T::total_from_f64(value)
+}
+
+#[cfg(test)]
+mod total_tests {
+    use u128::u128;
+    use i128::i128;
+    use error;
+    use total;
+
+    #[test]
+    fn test_unsigned() {
+        let max = u128::max_value();
+        let two = u128::new(2);
+        assert_eq!(total::add(max, u128::one()), None);
+        assert_eq!(total::add(two, two), Some(u128::new(4)));
+        assert_eq!(total::sub(u128::one(), two), None);
+        assert_eq!(total::mul(max, two), None);
+        assert_eq!(total::div(max, u128::zero()), None);
+        assert_eq!(total::rem(max, u128::zero()), None);
+        assert_eq!(total::div_rem(max, u128::zero()), None);
+        assert_eq!(total::div_rem(max, max), Some((u128::one(), u128::zero())));
+        assert_eq!(total::neg(u128::zero()), Some(u128::zero()));
+        assert_eq!(total::neg(two), None);
+        assert_eq!(total::shl(two, 127), Some(u128::zero()));
+        assert_eq!(total::shl(two, 128), None);
+        assert_eq!(total::shr(two, 128), None);
+        assert_eq!(total::pow(two, 127), Some(u128::from_parts(1 << 63, 0)));
+        assert_eq!(total::pow(two, 128), None);
+        assert_eq!(total::pow(u128::zero(), 0), Some(u128::one()));
+        assert_eq!(total::pow(u128::one(), !0), Some(u128::one()));
+    }
+
+    #[test]
+    fn test_signed() {
+        let min = i128::min_value();
+        let minus_one = -i128::one();
+        assert_eq!(total::div(min, minus_one), None);
+        assert_eq!(total::rem(min, minus_one), None);
+        assert_eq!(total::neg(min), None);
+        assert_eq!(total::neg(minus_one), Some(i128::one()));
+        assert_eq!(total::pow(i128::new(-2), 127), Some(min));
+        assert_eq!(total::pow(i128::new(2), 127), None);
+        assert_eq!(total::sub(min, i128::one()), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(total::parse::<u128>("123"), Ok(u128::new(123)));
+        assert_eq!(total::parse::<i128>("-123"), Ok(i128::new(-123)));
+        assert_eq!(total::parse::<u128>(""), Err(error::empty()));
+        assert_eq!(total::parse_radix::<i128>("-zz", 36), Ok(i128::new(-1295)));
+        assert_eq!(total::parse_radix::<u128>("1", 1), Err(error::invalid_digit()));
+        assert_eq!(total::parse_radix::<i128>("1", 37), Err(error::invalid_digit()));
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(total::from_f64::<u128>(2.5), Some(u128::new(2)));
+        assert_eq!(total::from_f64::<u128>(-1.0), None);
+        assert_eq!(total::from_f64::<i128>(-2.5), Some(i128::new(-2)));
+        assert_eq!(total::from_f64::<i128>(f64::NAN), None);
+        assert_eq!(total::from_f64::<i128>(1e39), None);
+    }
+}
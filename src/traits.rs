@@ -1,11 +1,33 @@
 //! Traits for conversion between the extra primitive types.
 
-use num_traits::{ToPrimitive, NumCast, One, Float};
+use num_traits::{ToPrimitive, NumCast, Zero, One, Float, CheckedAdd, CheckedMul};
 #[cfg(feature="use-std")] use num_traits::Num;
 use u128::u128;
 use i128::i128;
+use u256::u256;
+use i256::i256;
 #[cfg(extprim_has_stable_i128)] use compiler_rt::builtins::{U128, I128};
-use std::ops::MulAssign;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::{Sum, Product};
+use std::num::ParseIntError;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
+use std::ops::{Not, BitAnd, BitOr, BitXor, Shl, Shr, ShlAssign, ShrAssign};
+use std::str::FromStr;
+
+/// Rounding mode used when converting a floating point number to an integer, e.g. in
+/// [`u128::from_f64_with`](../u128/struct.u128.html#method.from_f64_with).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Round {
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round to the nearest integer. Half-way cases are rounded away from zero, like `f64::round`.
+    Nearest,
+    /// Round towards zero, i.e. discard the fractional part.
+    Trunc,
+}
 
 /// Trait for converting itself into the extra primitive types.
 ///
@@ -255,6 +277,136 @@ impl<T: ToPrimitive> ToExtraPrimitive for T {
     }
 }
 
+/// Trait for converting the extra primitive types into itself.
+///
+/// This is the counterpart of `ToExtraPrimitive`, in the same way `num_traits::FromPrimitive`
+/// complements `num_traits::ToPrimitive`. It is implemented for all primitive integers and floats,
+/// as well as `u128` and `i128` themselves.
+///
+/// # Note
+///
+/// Converting u128/i128 to f32/f64 will always succeed. Values too large for the float type become
+/// infinity, similar to how `num_traits::ToPrimitive` treat the float conversions.
+///
+/// ```rust
+/// use extprim::traits::FromExtraPrimitive;
+/// use extprim::u128::u128;
+/// use extprim::i128::i128;
+///
+/// assert_eq!(<u8 as FromExtraPrimitive>::from_u128(u128::new(200)), Some(200u8));
+/// assert_eq!(<u8 as FromExtraPrimitive>::from_u128(u128::new(300)), None);
+/// assert_eq!(<i32 as FromExtraPrimitive>::from_i128(i128::new(-7)), Some(-7i32));
+/// assert_eq!(<u64 as FromExtraPrimitive>::from_i128(i128::new(-7)), None);
+/// assert_eq!(<f64 as FromExtraPrimitive>::from_u128(u128::from_parts(1, 0)), Some(2.0f64.powi(64)));
+/// ```
+pub trait FromExtraPrimitive: Sized {
+    /// Converts an unsigned 128-bit integer into itself. Returns `None` if the number cannot be
+    /// represented by this type.
+    fn from_u128(n: u128) -> Option<Self>;
+
+    /// Converts a signed 128-bit integer into itself. Returns `None` if the number cannot be
+    /// represented by this type.
+    fn from_i128(n: i128) -> Option<Self>;
+}
+
+macro_rules! impl_from_extra_primitive {
+    ($ty:ty) => {
+        impl FromExtraPrimitive for $ty {
+            fn from_u128(n: u128) -> Option<Self> {
+                NumCast::from(n)
+            }
+
+            fn from_i128(n: i128) -> Option<Self> {
+                NumCast::from(n)
+            }
+        }
+    }
+}
+
+impl_from_extra_primitive!(u8);
+impl_from_extra_primitive!(i8);
+impl_from_extra_primitive!(u16);
+impl_from_extra_primitive!(i16);
+impl_from_extra_primitive!(u32);
+impl_from_extra_primitive!(i32);
+impl_from_extra_primitive!(u64);
+impl_from_extra_primitive!(i64);
+impl_from_extra_primitive!(usize);
+impl_from_extra_primitive!(isize);
+impl_from_extra_primitive!(f32);
+impl_from_extra_primitive!(f64);
+
+#[cfg(extprim_has_stable_i128)]
+impl_from_extra_primitive!(U128);
+#[cfg(extprim_has_stable_i128)]
+impl_from_extra_primitive!(I128);
+
+#[cfg(test)]
+mod from_extra_primitive_tests {
+    use u128::u128;
+    use i128::i128;
+    use traits::FromExtraPrimitive;
+
+    #[test]
+    fn test_u128_to_int() {
+        assert_eq!(<u8 as FromExtraPrimitive>::from_u128(u128::new(0)), Some(0));
+        assert_eq!(<u8 as FromExtraPrimitive>::from_u128(u128::new(255)), Some(255));
+        assert_eq!(<u8 as FromExtraPrimitive>::from_u128(u128::new(256)), None);
+        assert_eq!(<i8 as FromExtraPrimitive>::from_u128(u128::new(127)), Some(127));
+        assert_eq!(<i8 as FromExtraPrimitive>::from_u128(u128::new(128)), None);
+        assert_eq!(<u64 as FromExtraPrimitive>::from_u128(u128::new(!0)), Some(!0));
+        assert_eq!(<u64 as FromExtraPrimitive>::from_u128(u128::from_parts(1, 0)), None);
+        assert_eq!(<i64 as FromExtraPrimitive>::from_u128(u128::new(0x7fffffff_ffffffff)), Some(0x7fffffff_ffffffff));
+        assert_eq!(<i64 as FromExtraPrimitive>::from_u128(u128::new(0x80000000_00000000)), None);
+        assert_eq!(<usize as FromExtraPrimitive>::from_u128(u128::max_value()), None);
+    }
+
+    #[test]
+    fn test_i128_to_int() {
+        assert_eq!(<u8 as FromExtraPrimitive>::from_i128(i128::new(255)), Some(255));
+        assert_eq!(<u8 as FromExtraPrimitive>::from_i128(i128::new(-1)), None);
+        assert_eq!(<i8 as FromExtraPrimitive>::from_i128(i128::new(-128)), Some(-128));
+        assert_eq!(<i8 as FromExtraPrimitive>::from_i128(i128::new(-129)), None);
+        assert_eq!(<i64 as FromExtraPrimitive>::from_i128(i128::new(-0x80000000_00000000)), Some(-0x80000000_00000000));
+        assert_eq!(<i64 as FromExtraPrimitive>::from_i128(i128::from_parts(-2, !0)), None);
+        assert_eq!(<u64 as FromExtraPrimitive>::from_i128(i128::from_parts(0, !0)), Some(!0));
+        assert_eq!(<isize as FromExtraPrimitive>::from_i128(i128::min_value()), None);
+    }
+
+    #[test]
+    fn test_128_to_float() {
+        assert_eq!(<f64 as FromExtraPrimitive>::from_u128(u128::new(0)), Some(0.0));
+        assert_eq!(<f64 as FromExtraPrimitive>::from_u128(u128::from_parts(1, 0)), Some(18446744073709551616.0));
+        assert_eq!(<f64 as FromExtraPrimitive>::from_i128(i128::new(-3)), Some(-3.0));
+        assert_eq!(<f64 as FromExtraPrimitive>::from_i128(i128::min_value()), Some(-170141183460469231731687303715884105728.0));
+        assert_eq!(<f32 as FromExtraPrimitive>::from_u128(u128::new(1 << 40)), Some(1099511627776.0));
+        assert_eq!(<f32 as FromExtraPrimitive>::from_u128(u128::max_value()), Some(f32::INFINITY));
+        assert_eq!(<f32 as FromExtraPrimitive>::from_i128(i128::min_value()), Some(-170141183460469231731687303715884105728.0));
+        assert_eq!(<f64 as FromExtraPrimitive>::from_u128(u128::max_value()), Some(340282366920938463463374607431768211455.0));
+    }
+
+    #[test]
+    fn test_128_to_128() {
+        assert_eq!(<u128 as FromExtraPrimitive>::from_u128(u128::max_value()), Some(u128::max_value()));
+        assert_eq!(<u128 as FromExtraPrimitive>::from_i128(i128::max_value()), Some(u128::from_parts(0x7fffffff_ffffffff, !0)));
+        assert_eq!(<u128 as FromExtraPrimitive>::from_i128(i128::new(-1)), None);
+        assert_eq!(<i128 as FromExtraPrimitive>::from_u128(u128::from_parts(0x7fffffff_ffffffff, !0)), Some(i128::max_value()));
+        assert_eq!(<i128 as FromExtraPrimitive>::from_u128(u128::from_parts(0x80000000_00000000, 0)), None);
+        assert_eq!(<i128 as FromExtraPrimitive>::from_i128(i128::min_value()), Some(i128::min_value()));
+    }
+
+    #[test]
+    #[cfg(extprim_has_stable_i128)]
+    fn test_128_to_built_in() {
+        use compiler_rt::builtins::{U128, I128};
+
+        assert_eq!(<U128 as FromExtraPrimitive>::from_u128(u128::max_value()), Some(!0));
+        assert_eq!(<U128 as FromExtraPrimitive>::from_i128(i128::new(-1)), None);
+        assert_eq!(<I128 as FromExtraPrimitive>::from_i128(i128::new(-1)), Some(-1));
+        assert_eq!(<I128 as FromExtraPrimitive>::from_u128(u128::max_value()), None);
+    }
+}
+
 impl NumCast for u128 {
     fn from<T: ToPrimitive>(n: T) -> Option<u128> {
         #[cfg(extprim_has_stable_i128)] {
@@ -311,13 +463,961 @@ mod num_cast_tests {
     }
 }
 
+/// Trait for computing the quotient and remainder of a division in one call.
+///
+/// This allows generic code (e.g. radix conversion) to obtain both results without knowing the
+/// concrete type. For `u128` and `i128` this is faster than calling `/` and `%` separately.
+///
+/// # Panics
+///
+/// Panics if `rhs` is 0, or when the division overflows (e.g. `MIN.div_rem(-1)`), in the same way
+/// as the `/` operator.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::traits::DivRem;
+/// use extprim::u128::u128;
+/// use extprim::i128::i128;
+///
+/// assert_eq!(100u32.div_rem(8), (12, 4));
+/// assert_eq!(u128::new(100).div_rem(u128::new(8)), (u128::new(12), u128::new(4)));
+/// assert_eq!(i128::new(-100).div_rem(i128::new(8)), (i128::new(-12), i128::new(-4)));
+/// ```
+pub trait DivRem: Sized {
+    /// Computes `(self / rhs, self % rhs)`.
+    fn div_rem(self, rhs: Self) -> (Self, Self);
+}
+
+macro_rules! impl_div_rem_for_primitive {
+    ($ty:ty) => {
+        impl DivRem for $ty {
+            fn div_rem(self, rhs: Self) -> (Self, Self) {
+                (self / rhs, self % rhs)
+            }
+        }
+    }
+}
+
+impl_div_rem_for_primitive!(u8);
+impl_div_rem_for_primitive!(i8);
+impl_div_rem_for_primitive!(u16);
+impl_div_rem_for_primitive!(i16);
+impl_div_rem_for_primitive!(u32);
+impl_div_rem_for_primitive!(i32);
+impl_div_rem_for_primitive!(u64);
+impl_div_rem_for_primitive!(i64);
+impl_div_rem_for_primitive!(usize);
+impl_div_rem_for_primitive!(isize);
+
+#[cfg(extprim_has_stable_i128)]
+impl_div_rem_for_primitive!(U128);
+#[cfg(extprim_has_stable_i128)]
+impl_div_rem_for_primitive!(I128);
+
+impl DivRem for u128 {
+    fn div_rem(self, rhs: u128) -> (u128, u128) {
+        ::u128::div_rem(self, rhs)
+    }
+}
+
+impl DivRem for i128 {
+    fn div_rem(self, rhs: i128) -> (i128, i128) {
+        ::i128::div_rem(self, rhs)
+    }
+}
+
+#[cfg(test)]
+mod div_rem_trait_tests {
+    use u128::u128;
+    use i128::i128;
+    use traits::DivRem;
+
+    fn to_digits<T: DivRem + Copy + PartialEq + From<u8>>(mut n: T, radix: u8) -> Vec<T> {
+        let zero = T::from(0);
+        let radix = T::from(radix);
+        let mut digits = Vec::new();
+        while n != zero {
+            let (q, r) = n.div_rem(radix);
+            digits.push(r);
+            n = q;
+        }
+        digits
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(7u8.div_rem(2), (3, 1));
+        assert_eq!((-7i8).div_rem(2), (-3, -1));
+        assert_eq!(7i64.div_rem(-2), (-3, 1));
+        assert_eq!(0usize.div_rem(5), (0, 0));
+        assert_eq!(to_digits(1234u32, 10), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_u128() {
+        assert_eq!(u128::max_value().div_rem(u128::new(!0)),
+                    (u128::from_parts(1, 1), u128::zero()));
+        assert_eq!(u128::from_parts(1, 0).div_rem(u128::new(3)),
+                    (u128::new(0x55555555_55555555), u128::one()));
+        assert_eq!(to_digits(u128::new(0xabc), 16),
+                    vec![u128::new(0xc), u128::new(0xb), u128::new(0xa)]);
+    }
+
+    #[test]
+    fn test_i128() {
+        assert_eq!(i128::new(9).div_rem(i128::new(-4)), (i128::new(-2), i128::new(1)));
+        assert_eq!(i128::new(-9).div_rem(i128::new(4)), (i128::new(-2), i128::new(-1)));
+        assert_eq!(i128::min_value().div_rem(i128::max_value()), (-i128::one(), -i128::one()));
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_div_by_zero() {
+        u128::one().div_rem(u128::zero());
+    }
+}
+
+/// Extension trait providing overflow-checked sums and products of iterators.
+///
+/// Unlike `Iterator::sum()` and `Iterator::product()`, these methods never panic nor silently wrap
+/// around. They return `None` as soon as an overflow is detected, without consuming the rest of
+/// the iterator, which makes them suitable for aggregating untrusted input.
+///
+/// The items can be any type convertible to the accumulator type `T`, e.g. a sequence of `u64`
+/// can be summed into a `u128`. Use `.cloned()` to sum an iterator of references.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::traits::CheckedIterator;
+/// use extprim::u128::u128;
+///
+/// let values = [u128::new(1), u128::new(2), u128::new(3)];
+/// assert_eq!(values.iter().cloned().checked_sum::<u128>(), Some(u128::new(6)));
+/// assert_eq!(values.iter().cloned().checked_product::<u128>(), Some(u128::new(6)));
+///
+/// let large = vec![u128::max_value(), u128::one()];
+/// assert_eq!(large.into_iter().checked_sum::<u128>(), None);
+///
+/// let wide = vec![!0u64, !0u64];
+/// assert_eq!(wide.into_iter().checked_sum::<u128>(), Some(u128::from_parts(1, !0 - 1)));
+/// ```
+pub trait CheckedIterator: Iterator + Sized {
+    /// Sums the elements of the iterator, returning `None` on overflow. Returns zero for an empty
+    /// iterator.
+    fn checked_sum<T>(self) -> Option<T>
+    where
+        T: Zero + CheckedAdd,
+        Self::Item: Into<T>,
+    {
+        let mut acc = T::zero();
+        for item in self {
+            match acc.checked_add(&item.into()) {
+                Some(res) => acc = res,
+                None => return None,
+            }
+        }
+        Some(acc)
+    }
+
+    /// Multiplies the elements of the iterator, returning `None` on overflow. Returns one for an
+    /// empty iterator.
+    fn checked_product<T>(self) -> Option<T>
+    where
+        T: One + CheckedMul,
+        Self::Item: Into<T>,
+    {
+        let mut acc = T::one();
+        for item in self {
+            match acc.checked_mul(&item.into()) {
+                Some(res) => acc = res,
+                None => return None,
+            }
+        }
+        Some(acc)
+    }
+}
+
+impl<I: Iterator> CheckedIterator for I {}
+
+#[cfg(test)]
+mod checked_iterator_tests {
+    use u128::u128;
+    use i128::i128;
+    use traits::CheckedIterator;
+
+    #[test]
+    fn test_checked_sum() {
+        assert_eq!(Vec::<u128>::new().into_iter().checked_sum::<u128>(), Some(u128::zero()));
+        assert_eq!((1..101u64).checked_sum::<u128>(), Some(u128::new(5050)));
+        assert_eq!(vec![u128::max_value(), u128::zero()].into_iter().checked_sum::<u128>(),
+                    Some(u128::max_value()));
+        assert_eq!(vec![i128::max_value(), i128::one()].into_iter().checked_sum::<i128>(), None);
+        assert_eq!(vec![i128::min_value(), i128::max_value()].into_iter().checked_sum::<i128>(),
+                    Some(-i128::one()));
+        assert_eq!(vec![-1i64, -2, -3].into_iter().checked_sum::<i128>(), Some(i128::new(-6)));
+    }
+
+    #[test]
+    fn test_checked_product() {
+        assert_eq!(Vec::<u128>::new().into_iter().checked_product::<u128>(), Some(u128::one()));
+        assert_eq!((1..26u64).checked_product::<u128>(),
+                    Some(u128::from_parts(0xcd4a0, 0x619fb0907bc00000)));
+        assert!((1..35u64).checked_product::<u128>().is_some());
+        assert_eq!((1..36u64).checked_product::<u128>(), None);
+        assert_eq!(vec![i128::new(-2); 127].into_iter().checked_product::<i128>(), Some(i128::min_value()));
+        assert_eq!(vec![i128::new(-2); 128].into_iter().checked_product::<i128>(), None);
+    }
+
+    #[test]
+    fn test_stops_at_first_overflow() {
+        let mut consumed = 0;
+        let res = vec![u128::max_value(), u128::one(), u128::one(), u128::one()]
+            .into_iter()
+            .inspect(|_| consumed += 1)
+            .checked_sum::<u128>();
+        assert_eq!(res, None);
+        assert_eq!(consumed, 2);
+    }
+}
+
 /// Wrapper for `u128` and `i128` to turn arithmetic operators to wrapping ones.
 ///
 /// Equivalent to `std::num::Wrapping`, but due to E0117 (orphan rule) we need to define it here to
 /// implement operators on it.
+///
+/// With the `serde` feature, it is (de)serialized exactly like the wrapped value, the same as
+/// `std::num::Wrapping`.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default)]
 pub struct Wrapping<T>(pub T);
 
+/// Wrapper for `u128` and `i128` to turn arithmetic operators to checked ones.
+///
+/// The wrapped value becomes `None` once any operation overflows (or divides by zero, or shifts by
+/// 128 bits or more), and stays `None` for the rest of the expression. This allows a long expression to be written with the
+/// normal operators and checked only once at the end, instead of chaining `checked_*` calls with
+/// `and_then`.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::traits::Checked;
+/// use extprim::u128::u128;
+///
+/// let a = Checked::new(u128::new(10));
+/// let b = u128::new(3);
+/// assert_eq!((a * b + b - u128::new(1)).get(), Some(u128::new(32)));
+/// assert_eq!((a - b * u128::new(4) + b).get(), None);
+/// assert_eq!((a / u128::zero()).get(), None);
+///
+/// let mut total = Checked::new(u128::max_value());
+/// total += u128::one();
+/// assert_eq!(total.get(), None);
+/// ```
+///
+/// With the `serde` feature, it is (de)serialized exactly like the wrapped `Option`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Checked<T>(pub Option<T>);
+
+impl<T> Checked<T> {
+    /// Wraps a value.
+    pub fn new(value: T) -> Checked<T> {
+        Checked(Some(value))
+    }
+
+    /// Returns the result of the computation, or `None` if any step overflowed.
+    pub fn get(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> From<T> for Checked<T> {
+    fn from(value: T) -> Checked<T> {
+        Checked::new(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde_crate::{Serialize, Serializer, Deserialize, Deserializer};
+    use serde_crate::de::Error;
+    use u128::u128;
+    use i128::i128;
+    use traits::{Wrapping, Checked, Saturating, NonZero};
+
+    impl<T: Serialize> Serialize for Wrapping<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Wrapping<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(Wrapping)
+        }
+    }
+
+    impl<T: Serialize> Serialize for Saturating<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Saturating<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(Saturating)
+        }
+    }
+
+    impl<T: Serialize> Serialize for Checked<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Checked<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Option::deserialize(deserializer).map(Checked)
+        }
+    }
+
+    impl<T: Serialize> Serialize for NonZero<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    macro_rules! impl_deserialize_non_zero {
+        ($($ty:ident)*) => {$(
+            impl<'de> Deserialize<'de> for NonZero<$ty> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = $ty::deserialize(deserializer)?;
+                    NonZero::<$ty>::new(value).ok_or_else(|| D::Error::custom("expected a non-zero integer"))
+                }
+            }
+        )*}
+    }
+
+    impl_deserialize_non_zero!(u128 i128);
+}
+
+macro_rules! impl_checked_op {
+    ($ty:ty, $tn:ident($name:ident, $cname:ident), $atn:ident($aname:ident)) => {
+        impl $tn for Checked<$ty> {
+            type Output = Self;
+            fn $name(self, other: Self) -> Self {
+                Checked(match (self.0, other.0) {
+                    (Some(x), Some(y)) => x.$cname(y),
+                    _ => None,
+                })
+            }
+        }
+
+        impl $tn<$ty> for Checked<$ty> {
+            type Output = Self;
+            fn $name(self, other: $ty) -> Self {
+                Checked(self.0.and_then(|x| x.$cname(other)))
+            }
+        }
+
+        impl $atn for Checked<$ty> {
+            fn $aname(&mut self, other: Self) {
+                *self = self.$name(other);
+            }
+        }
+
+        impl $atn<$ty> for Checked<$ty> {
+            fn $aname(&mut self, other: $ty) {
+                *self = self.$name(other);
+            }
+        }
+    }
+}
+
+macro_rules! impl_checked_ops {
+    ($($ty:ty)*) => {$(
+        impl_checked_op!($ty, Add(add, checked_add), AddAssign(add_assign));
+        impl_checked_op!($ty, Sub(sub, checked_sub), SubAssign(sub_assign));
+        impl_checked_op!($ty, Mul(mul, checked_mul), MulAssign(mul_assign));
+        impl_checked_op!($ty, Div(div, checked_div), DivAssign(div_assign));
+        impl_checked_op!($ty, Rem(rem, checked_rem), RemAssign(rem_assign));
+    )*}
+}
+
+impl_checked_ops!(u128 i128);
+
+macro_rules! impl_checked_shift {
+    ($ty:ty, $tn:ident($name:ident, $cname:ident), $atn:ident($aname:ident)) => {
+        impl $tn<u32> for Checked<$ty> {
+            type Output = Self;
+            fn $name(self, other: u32) -> Self {
+                Checked(self.0.and_then(|x| x.$cname(other)))
+            }
+        }
+
+        impl $atn<u32> for Checked<$ty> {
+            fn $aname(&mut self, other: u32) {
+                *self = self.$name(other);
+            }
+        }
+    }
+}
+
+impl_checked_shift!(u128, Shl(shl, checked_shl), ShlAssign(shl_assign));
+impl_checked_shift!(u128, Shr(shr, checked_shr), ShrAssign(shr_assign));
+impl_checked_shift!(i128, Shl(shl, checked_shl), ShlAssign(shl_assign));
+impl_checked_shift!(i128, Shr(shr, checked_shr), ShrAssign(shr_assign));
+
+impl Neg for Checked<i128> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Checked(self.0.and_then(i128::checked_neg))
+    }
+}
+
+#[cfg(test)]
+mod checked_tests {
+    use u128::u128;
+    use i128::i128;
+    use traits::Checked;
+
+    #[test]
+    fn test_u128() {
+        let a = Checked::new(u128::max_value());
+        assert_eq!((a - u128::one()).get(), Some(u128::max_value() - u128::one()));
+        assert_eq!((a + u128::one() - u128::one()).get(), None);
+        assert_eq!((a % u128::new(10)).get(), Some(u128::new(5)));
+        assert_eq!((a % u128::zero()).get(), None);
+        assert_eq!((Checked::new(u128::new(6)) * Checked::new(u128::new(7))).get(), Some(u128::new(42)));
+        assert_eq!((Checked(None) * Checked::new(u128::zero())).get(), None);
+        assert_eq!((Checked::new(u128::one()) << 127 >> 120).get(), Some(u128::new(128)));
+        assert_eq!((Checked::new(u128::one()) << 128).get(), None);
+
+        let mut bits = Checked::new(u128::new(5));
+        bits <<= 3;
+        bits >>= 1;
+        assert_eq!(bits.get(), Some(u128::new(20)));
+        bits >>= 200;
+        assert_eq!(bits.get(), None);
+    }
+
+    #[test]
+    fn test_i128() {
+        let min = Checked::from(i128::min_value());
+        assert_eq!((-min).get(), None);
+        assert_eq!((min / -i128::one()).get(), None);
+        assert_eq!((min + i128::one()).get().map(|x| -x), Some(i128::max_value()));
+        assert_eq!((-Checked::new(i128::new(5)) * i128::new(3) - i128::new(1)).get(), Some(i128::new(-16)));
+        assert_eq!((Checked::new(i128::new(-8)) >> 2 << 1).get(), Some(i128::new(-4)));
+        assert_eq!((min >> 128).get(), None);
+
+        let mut acc = Checked::new(i128::zero());
+        for _ in 0..3 {
+            acc -= i128::max_value();
+        }
+        assert_eq!(acc.get(), None);
+    }
+}
+
+/// Wrapper for `u128` and `i128` to turn arithmetic operators to overflowing ones.
+///
+/// Every operation wraps around on overflow, like [`Wrapping`](struct.Wrapping.html), and sets the
+/// sticky `overflowed` flag, which stays set for the rest of the expression. This computes the
+/// wrapped result of a long expression together with whether any step overflowed, e.g. to emulate
+/// the overflow flag of a CPU.
+///
+/// As with `overflowing_shl` and `overflowing_shr`, a shift only counts as overflowing if the
+/// shift amount is 128 or more. Division and remainder by zero still panic.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::traits::Overflowing;
+/// use extprim::u128::u128;
+///
+/// let a = Overflowing::new(u128::max_value());
+/// assert_eq!((a + u128::new(2) - u128::new(1)).get(), (u128::zero(), true));
+/// assert_eq!((a - u128::new(2) + u128::new(2)).get(), (u128::max_value(), false));
+///
+/// let mut acc = Overflowing::new(u128::new(3));
+/// acc *= u128::new(5);
+/// acc <<= 130;
+/// assert_eq!(acc.get(), (u128::new(60), true));
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash)]
+pub struct Overflowing<T> {
+    /// The wrapped result of the computation.
+    pub value: T,
+    /// Whether any step of the computation overflowed.
+    pub overflowed: bool,
+}
+
+impl<T> Overflowing<T> {
+    /// Wraps a value, with the overflow flag cleared.
+    pub fn new(value: T) -> Overflowing<T> {
+        Overflowing { value: value, overflowed: false }
+    }
+
+    /// Returns the wrapped result of the computation, and whether any step overflowed.
+    pub fn get(self) -> (T, bool) {
+        (self.value, self.overflowed)
+    }
+}
+
+impl<T> From<T> for Overflowing<T> {
+    fn from(value: T) -> Overflowing<T> {
+        Overflowing::new(value)
+    }
+}
+
+macro_rules! impl_overflowing_op {
+    ($ty:ty, $tn:ident($name:ident, $oname:ident), $atn:ident($aname:ident)) => {
+        impl $tn for Overflowing<$ty> {
+            type Output = Self;
+            fn $name(self, other: Self) -> Self {
+                let (value, overflowed) = self.value.$oname(other.value);
+                Overflowing { value: value, overflowed: self.overflowed || other.overflowed || overflowed }
+            }
+        }
+
+        impl_overflowing_op!(@rhs $ty, $ty, $tn($name, $oname), $atn($aname));
+
+        impl $atn for Overflowing<$ty> {
+            fn $aname(&mut self, other: Self) {
+                *self = self.$name(other);
+            }
+        }
+    };
+    (@rhs $ty:ty, $rhs:ty, $tn:ident($name:ident, $oname:ident), $atn:ident($aname:ident)) => {
+        impl $tn<$rhs> for Overflowing<$ty> {
+            type Output = Self;
+            fn $name(self, other: $rhs) -> Self {
+                let (value, overflowed) = self.value.$oname(other);
+                Overflowing { value: value, overflowed: self.overflowed || overflowed }
+            }
+        }
+
+        impl $atn<$rhs> for Overflowing<$ty> {
+            fn $aname(&mut self, other: $rhs) {
+                *self = self.$name(other);
+            }
+        }
+    };
+}
+
+macro_rules! impl_overflowing_ops {
+    ($($ty:ident)*) => {$(
+        impl_overflowing_op!($ty, Add(add, overflowing_add), AddAssign(add_assign));
+        impl_overflowing_op!($ty, Sub(sub, overflowing_sub), SubAssign(sub_assign));
+        impl_overflowing_op!($ty, Mul(mul, overflowing_mul), MulAssign(mul_assign));
+        impl_overflowing_op!($ty, Div(div, overflowing_div), DivAssign(div_assign));
+        impl_overflowing_op!($ty, Rem(rem, overflowing_rem), RemAssign(rem_assign));
+        impl_overflowing_op!(@rhs $ty, u32, Shl(shl, overflowing_shl), ShlAssign(shl_assign));
+        impl_overflowing_op!(@rhs $ty, u32, Shr(shr, overflowing_shr), ShrAssign(shr_assign));
+    )*}
+}
+
+impl_overflowing_ops!(u128 i128);
+
+impl Neg for Overflowing<i128> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        let (value, overflowed) = self.value.overflowing_neg();
+        Overflowing { value: value, overflowed: self.overflowed || overflowed }
+    }
+}
+
+#[cfg(test)]
+mod overflowing_tests {
+    use u128::u128;
+    use i128::i128;
+    use traits::Overflowing;
+
+    #[test]
+    fn test_u128() {
+        let max = Overflowing::new(u128::max_value());
+        let two = Overflowing::from(u128::new(2));
+        assert_eq!((max * two).get(), (u128::max_value() - u128::one(), true));
+        assert_eq!((two - max + max).get(), (u128::new(2), true));
+        assert_eq!((max / two % u128::new(10)).get(), (u128::new(7), false));
+        assert_eq!((two << 127).get(), (u128::zero(), false));
+        assert_eq!((two << 129).get(), (u128::new(4), true));
+        assert_eq!((max >> 128).get(), (u128::max_value(), true));
+        assert_eq!(Overflowing::<u128>::default().get(), (u128::zero(), false));
+
+        let mut acc = Overflowing::new(u128::zero());
+        acc -= two;
+        acc += two;
+        assert_eq!(acc.get(), (u128::zero(), true));
+        acc *= two;
+        acc /= two;
+        acc %= two;
+        assert!(acc.overflowed);
+    }
+
+    #[test]
+    fn test_i128() {
+        let min = Overflowing::new(i128::min_value());
+        let one = Overflowing::new(i128::one());
+        assert_eq!((-min).get(), (i128::min_value(), true));
+        assert_eq!((-one).get(), (-i128::one(), false));
+        assert_eq!((min / -i128::one()).get(), (i128::min_value(), true));
+        assert_eq!((min % -i128::one()).get(), (i128::zero(), true));
+        assert_eq!((min - one + one).get(), (i128::min_value(), true));
+        assert_eq!((one << 127).get(), (i128::min_value(), false));
+        assert_eq!((min >> 127).get(), (-i128::one(), false));
+        assert_eq!((Overflowing::new(i128::new(-4)) * i128::new(5)).get(), (i128::new(-20), false));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_by_zero() {
+        let _ = Overflowing::new(u128::one()) / u128::zero();
+    }
+}
+
+/// Wrapper for `u128` and `i128` to turn arithmetic operators to saturating ones.
+///
+/// The operators `+`, `-`, `*`, `<<` and `>>` clamp the result to the bounds of the type instead
+/// of overflowing, by calling the corresponding `saturating_*` method. The right hand side of a
+/// shift is a `u32`. Shifting left saturates when any significant bit would be shifted out, while
+/// shifting right by 128 bits or more gives 0 (or -1 for a negative `i128`). `Sum` and `Product`
+/// saturate in the same way, at every step of the iteration.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::traits::Saturating;
+/// use extprim::u128::u128;
+///
+/// let mut total = Saturating(u128::max_value() - u128::new(5));
+/// total += Saturating(u128::new(10));
+/// assert_eq!(total, Saturating(u128::max_value()));
+/// assert_eq!(Saturating(u128::new(3)) - Saturating(u128::new(5)), Saturating(u128::zero()));
+/// assert_eq!(Saturating(u128::new(3)) << 127, Saturating(u128::max_value()));
+/// ```
+///
+/// With the `serde` feature, it is (de)serialized exactly like the wrapped value.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default, Hash)]
+pub struct Saturating<T>(pub T);
+
+macro_rules! impl_saturating_op {
+    ($ty:ty, $rhs:ty { $x:ident => $unwrap:expr }, $tn:ident($name:ident, $sname:ident), $atn:ident($aname:ident)) => {
+        impl $tn<$rhs> for Saturating<$ty> {
+            type Output = Self;
+            fn $name(self, $x: $rhs) -> Self {
+                Saturating(self.0.$sname($unwrap))
+            }
+        }
+
+        impl $atn<$rhs> for Saturating<$ty> {
+            fn $aname(&mut self, other: $rhs) {
+                *self = self.$name(other);
+            }
+        }
+    }
+}
+
+macro_rules! impl_saturating_ops {
+    ($($ty:ident)*) => {$(
+        impl_saturating_op!($ty, Saturating<$ty> { x => x.0 }, Add(add, saturating_add), AddAssign(add_assign));
+        impl_saturating_op!($ty, Saturating<$ty> { x => x.0 }, Sub(sub, saturating_sub), SubAssign(sub_assign));
+        impl_saturating_op!($ty, Saturating<$ty> { x => x.0 }, Mul(mul, saturating_mul), MulAssign(mul_assign));
+        impl_saturating_op!($ty, u32 { x => x }, Shl(shl, saturating_shl), ShlAssign(shl_assign));
+        impl_saturating_op!($ty, u32 { x => x }, Shr(shr, saturating_shr), ShrAssign(shr_assign));
+
+        impl Sum for Saturating<$ty> {
+            fn sum<I>(iter: I) -> Self
+                where I: Iterator<Item = Self>
+            {
+                iter.fold(Saturating($ty::zero()), |acc, elem| Saturating(acc.0.saturating_add(elem.0)))
+            }
+        }
+
+        impl Product for Saturating<$ty> {
+            fn product<I>(iter: I) -> Self
+                where I: Iterator<Item = Self>
+            {
+                iter.fold(Saturating($ty::one()), |acc, elem| Saturating(acc.0.saturating_mul(elem.0)))
+            }
+        }
+
+        impl<'a> Sum<&'a Saturating<$ty>> for Saturating<$ty> {
+            fn sum<I>(iter: I) -> Self
+                where I: Iterator<Item = &'a Self>
+            {
+                iter.fold(Saturating($ty::zero()), |acc, elem| Saturating(acc.0.saturating_add(elem.0)))
+            }
+        }
+
+        impl<'a> Product<&'a Saturating<$ty>> for Saturating<$ty> {
+            fn product<I>(iter: I) -> Self
+                where I: Iterator<Item = &'a Self>
+            {
+                iter.fold(Saturating($ty::one()), |acc, elem| Saturating(acc.0.saturating_mul(elem.0)))
+            }
+        }
+    )*}
+}
+
+impl_saturating_ops!(u128 i128);
+
+impl Neg for Saturating<i128> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Saturating(self.0.saturating_neg())
+    }
+}
+
+#[cfg(test)]
+mod saturating_tests {
+    use u128::u128;
+    use i128::i128;
+    use traits::Saturating;
+
+    #[test]
+    fn test_u128() {
+        let max = Saturating(u128::max_value());
+        let two = Saturating(u128::new(2));
+        assert_eq!(max + two, max);
+        assert_eq!(two - max, Saturating(u128::zero()));
+        assert_eq!(max * two, max);
+        assert_eq!(two * two - Saturating(u128::one()), Saturating(u128::new(3)));
+        assert_eq!(two << 126, Saturating(u128::from_parts(0x8000000000000000, 0)));
+        assert_eq!(two << 127, max);
+        assert_eq!(Saturating(u128::zero()) << 1000, Saturating(u128::zero()));
+        assert_eq!(max >> 127, Saturating(u128::one()));
+        assert_eq!(max >> 128, Saturating(u128::zero()));
+
+        let mut acc = Saturating(u128::zero());
+        acc -= two;
+        acc += two;
+        acc <<= 3;
+        acc *= two;
+        acc >>= 1;
+        assert_eq!(acc, Saturating(u128::new(16)));
+    }
+
+    #[test]
+    fn test_i128() {
+        let min = Saturating(i128::min_value());
+        let max = Saturating(i128::max_value());
+        let neg_two = Saturating(i128::new(-2));
+        assert_eq!(min + neg_two, min);
+        assert_eq!(max - neg_two, max);
+        assert_eq!(min * neg_two, max);
+        assert_eq!(max * neg_two, min);
+        assert_eq!(-min, max);
+        assert_eq!(-neg_two, Saturating(i128::new(2)));
+        assert_eq!(neg_two << 126, min);
+        assert_eq!(neg_two << 127, min);
+        assert_eq!(Saturating(i128::new(3)) << 125, Saturating(i128::from_parts(0x6000000000000000, 0)));
+        assert_eq!(Saturating(i128::new(3)) << 126, max);
+        assert_eq!(neg_two >> 1, Saturating(i128::new(-1)));
+        assert_eq!(neg_two >> 500, Saturating(i128::new(-1)));
+        assert_eq!(max >> 500, Saturating(i128::zero()));
+    }
+
+    #[test]
+    fn test_sum() {
+        let values = [u128::max_value() - u128::new(1), u128::new(5), u128::new(1)];
+        assert_eq!(values.iter().map(|&x| Saturating(x)).sum::<Saturating<u128>>(), Saturating(u128::max_value()));
+        assert_eq!(Vec::<Saturating<u128>>::new().iter().sum::<Saturating<u128>>(), Saturating(u128::zero()));
+
+        // the sum saturates at the maximum before the -1 is added.
+        let max = Saturating(i128::max_value());
+        let one = Saturating(i128::one());
+        assert_eq!(vec![max, one, -one].into_iter().sum::<Saturating<i128>>(), max - one);
+        assert_eq!([-max, -one, -one, one].iter().sum::<Saturating<i128>>(), Saturating(i128::min_value() + i128::one()));
+        assert_eq!([Saturating(i128::new(-3)), Saturating(i128::new(5))].iter().sum::<Saturating<i128>>(), Saturating(i128::new(2)));
+    }
+
+    #[test]
+    fn test_product() {
+        let two = Saturating(u128::new(2));
+        let max = Saturating(u128::max_value());
+        assert_eq!(vec![two, max, two].into_iter().product::<Saturating<u128>>(), max);
+        // the product saturates before it reaches the 0.
+        assert_eq!([max, two, Saturating(u128::zero())].iter().product::<Saturating<u128>>(), Saturating(u128::zero()));
+        assert_eq!(Vec::<Saturating<u128>>::new().into_iter().product::<Saturating<u128>>(), Saturating(u128::one()));
+
+        // MIN * -1 saturates at MAX, and MAX * -1 is MIN + 1.
+        let min = Saturating(i128::min_value());
+        let neg_one = Saturating(i128::new(-1));
+        assert_eq!([min, neg_one, neg_one].iter().product::<Saturating<i128>>(), Saturating(i128::min_value() + i128::one()));
+        assert_eq!(vec![Saturating(i128::new(-2)), Saturating(i128::new(3))].into_iter().product::<Saturating<i128>>(),
+                   Saturating(i128::new(-6)));
+    }
+}
+
+/// A `u128` or `i128` which is known not to be zero.
+///
+/// Equivalent to `std::num::NonZeroU128` and `std::num::NonZeroI128`, which can only hold the
+/// built-in types. With the `rand` feature, `Standard` samples the non-zero values uniformly, and
+/// with the `serde` feature, it is (de)serialized like the wrapped value, rejecting 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::traits::NonZeroU128;
+/// use extprim::u128::u128;
+///
+/// let n = NonZeroU128::new(u128::new(7)).unwrap();
+/// assert_eq!(n.get(), u128::new(7));
+/// assert_eq!(NonZeroU128::new(u128::zero()), None);
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+pub struct NonZero<T>(T);
+
+/// An unsigned 128-bit number which is not zero.
+pub type NonZeroU128 = NonZero<u128>;
+
+/// A signed 128-bit number which is not zero.
+pub type NonZeroI128 = NonZero<i128>;
+
+macro_rules! impl_non_zero {
+    ($($ty:ident)*) => {$(
+        impl NonZero<$ty> {
+            /// Wraps a value, or returns `None` if it is 0.
+            pub fn new(value: $ty) -> Option<NonZero<$ty>> {
+                if value == $ty::zero() {
+                    None
+                } else {
+                    Some(NonZero(value))
+                }
+            }
+
+            /// Wraps a value without checking whether it is 0.
+            ///
+            /// # Safety
+            ///
+            /// The value must not be 0.
+            pub unsafe fn new_unchecked(value: $ty) -> NonZero<$ty> {
+                NonZero(value)
+            }
+
+            /// Returns the wrapped value.
+            pub fn get(self) -> $ty {
+                self.0
+            }
+        }
+
+        impl From<NonZero<$ty>> for $ty {
+            fn from(value: NonZero<$ty>) -> $ty {
+                value.0
+            }
+        }
+
+        impl fmt::Display for NonZero<$ty> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(formatter)
+            }
+        }
+    )*}
+}
+
+impl_non_zero!(u128 i128);
+
+#[cfg(feature="rand")]
+mod rand_impls {
+    use rand::Rng;
+    use rand::distributions::{Standard, Distribution};
+    use u128::u128;
+    use i128::i128;
+    use traits::{Wrapping, Saturating, NonZero};
+
+    impl<T> Distribution<Wrapping<T>> for Standard where Standard: Distribution<T> {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Wrapping<T> {
+            Wrapping(rng.gen())
+        }
+    }
+
+    impl<T> Distribution<Saturating<T>> for Standard where Standard: Distribution<T> {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Saturating<T> {
+            Saturating(rng.gen())
+        }
+    }
+
+    // Zero is rejected and resampled, which keeps the distribution uniform. The loop practically
+    // never runs twice, since a random 128-bit number is 0 with probability 2^-128.
+    macro_rules! impl_sample_non_zero {
+        ($($ty:ident)*) => {$(
+            impl Distribution<NonZero<$ty>> for Standard {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> NonZero<$ty> {
+                    loop {
+                        if let Some(value) = NonZero::<$ty>::new(rng.gen()) {
+                            return value;
+                        }
+                    }
+                }
+            }
+        )*}
+    }
+
+    impl_sample_non_zero!(u128 i128);
+}
+
+#[cfg(test)]
+mod non_zero_tests {
+    use u128::u128;
+    use i128::i128;
+    use traits::{NonZeroU128, NonZeroI128};
+
+    #[test]
+    fn test_new() {
+        assert_eq!(NonZeroU128::new(u128::zero()), None);
+        assert_eq!(NonZeroI128::new(i128::zero()), None);
+        assert_eq!(NonZeroU128::new(u128::max_value()).map(u128::from), Some(u128::max_value()));
+        assert_eq!(NonZeroI128::new(-i128::one()).map(NonZeroI128::get), Some(-i128::one()));
+        assert_eq!(unsafe { NonZeroU128::new_unchecked(u128::one()) }.to_string(), "1");
+        assert!(NonZeroI128::new(i128::min_value()) < NonZeroI128::new(i128::one()));
+    }
+
+    #[cfg(feature="rand")]
+    #[test]
+    fn test_rand() {
+        use rand::{Rng, RngCore, Error};
+        use traits::Wrapping;
+
+        /// Produces `zeros` zero words, and then the numbers 1, 2, 3, ...
+        struct ZerosFirst {
+            zeros: u32,
+            next: u64,
+        }
+
+        impl RngCore for ZerosFirst {
+            fn next_u32(&mut self) -> u32 { self.next_u64() as u32 }
+            fn next_u64(&mut self) -> u64 {
+                if self.zeros > 0 {
+                    self.zeros -= 1;
+                    0
+                } else {
+                    self.next += 1;
+                    self.next
+                }
+            }
+            fn fill_bytes(&mut self, _: &mut [u8]) { unimplemented!() }
+            fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), Error> { unimplemented!() }
+        }
+
+        let mut rng = ZerosFirst { zeros: 5, next: 0 };
+        let n: NonZeroU128 = rng.gen();
+        assert_eq!(n.get(), u128::one());
+        assert_eq!(rng.zeros, 0);
+
+        let mut rng = ZerosFirst { zeros: 4, next: 0 };
+        let n: NonZeroI128 = rng.gen();
+        assert_eq!(n.get(), i128::from_parts(1, 2));
+
+        let mut rng = ZerosFirst { zeros: 0, next: 0 };
+        let w: Wrapping<u128> = rng.gen();
+        assert_eq!(w, Wrapping(u128::from_parts(1, 2)));
+        let w: Wrapping<i128> = rng.gen();
+        assert_eq!(w, Wrapping(i128::from_parts(3, 4)));
+    }
+}
+
 /// Raise `base` to the power of `exp`, using exponentiation by squaring.
 ///
 /// # Examples
@@ -382,3 +1482,301 @@ pub fn parse_rust_int_lit<T: Num>(s: &str, is_negative: bool) -> Result<T, T::Fr
     T::from_str_radix(&digits, base)
 }
 
+
+/// The common interface of the extended integer types `u128`, `i128`, `u256` and `i256`.
+///
+/// Generic algorithms can be written once against this trait and used with any of the types. The
+/// trait covers construction from and decomposition into the two halves, the arithmetic
+/// operations in their checked, wrapping and overflowing forms, bit operations and the byte
+/// representation.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::traits::ExtInt;
+/// use extprim::u128::u128;
+/// use extprim::i128::i128;
+/// use extprim::u256::u256;
+///
+/// fn checked_sum_of_squares<T: ExtInt>(values: &[T]) -> Option<T> {
+///     values.iter().try_fold(T::ZERO, |acc, &x| acc.checked_add(x.checked_mul(x)?))
+/// }
+///
+/// assert_eq!(checked_sum_of_squares(&[u128::new(3), u128::new(4)]), Some(u128::new(25)));
+/// assert_eq!(checked_sum_of_squares(&[i128::new(-3), i128::max_value()]), None);
+/// assert_eq!(checked_sum_of_squares(&[u256::new(u128::max_value())]),
+///            Some(u256::from_parts(u128::max_value() - u128::one(), u128::one())));
+/// ```
+pub trait ExtInt: Copy + Default + Ord + Hash + fmt::Debug + fmt::Display
+    + FromStr<Err=ParseIntError>
+    + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> + Div<Output=Self> + Rem<Output=Self>
+    + Not<Output=Self> + BitAnd<Output=Self> + BitOr<Output=Self> + BitXor<Output=Self>
+    + Shl<u32, Output=Self> + Shr<u32, Output=Self>
+{
+    /// The type of the upper half, which is signed if `Self` is signed.
+    type High: Copy;
+
+    /// The type of the lower half, which is always unsigned.
+    type Low: Copy;
+
+    /// The byte array holding the memory representation of the number.
+    type Bytes: Copy + AsRef<[u8]> + AsMut<[u8]>;
+
+    /// The size of this integer type in bits.
+    const BITS: u32;
+    /// The number 0.
+    const ZERO: Self;
+    /// The number 1.
+    const ONE: Self;
+    /// The smallest value that can be represented by this integer type.
+    const MIN: Self;
+    /// The largest value that can be represented by this integer type.
+    const MAX: Self;
+
+    /// Constructs a number from its upper and lower halves.
+    fn from_parts(hi: Self::High, lo: Self::Low) -> Self;
+    /// Returns the upper half of the number.
+    fn high(self) -> Self::High;
+    /// Returns the lower half of the number.
+    fn low(self) -> Self::Low;
+
+    /// Computes `self + other`, returning `None` on overflow.
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// Computes `self - other`, returning `None` on overflow.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    /// Computes `self * other`, returning `None` on overflow.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// Computes `self / other`, returning `None` if `other` is 0 or the division overflows.
+    fn checked_div(self, other: Self) -> Option<Self>;
+    /// Computes `self % other`, returning `None` if `other` is 0 or the division overflows.
+    fn checked_rem(self, other: Self) -> Option<Self>;
+
+    /// Computes `self + other`, wrapping around at the boundary of the type.
+    fn wrapping_add(self, other: Self) -> Self;
+    /// Computes `self - other`, wrapping around at the boundary of the type.
+    fn wrapping_sub(self, other: Self) -> Self;
+    /// Computes `self * other`, wrapping around at the boundary of the type.
+    fn wrapping_mul(self, other: Self) -> Self;
+    /// Computes `-self`, wrapping around at the boundary of the type.
+    fn wrapping_neg(self) -> Self;
+    /// Computes `self << (shift % BITS)`.
+    fn wrapping_shl(self, shift: u32) -> Self;
+    /// Computes `self >> (shift % BITS)`.
+    fn wrapping_shr(self, shift: u32) -> Self;
+
+    /// Computes `self + other`, along with a boolean indicating whether an overflow happened.
+    fn overflowing_add(self, other: Self) -> (Self, bool);
+    /// Computes `self - other`, along with a boolean indicating whether an overflow happened.
+    fn overflowing_sub(self, other: Self) -> (Self, bool);
+    /// Computes `self * other`, along with a boolean indicating whether an overflow happened.
+    fn overflowing_mul(self, other: Self) -> (Self, bool);
+
+    /// Returns the number of ones in the binary representation of `self`.
+    fn count_ones(self) -> u32;
+    /// Returns the number of leading zeros in the binary representation of `self`.
+    fn leading_zeros(self) -> u32;
+    /// Returns the number of trailing zeros in the binary representation of `self`.
+    fn trailing_zeros(self) -> u32;
+
+    /// Returns the memory representation of the number in little-endian byte order.
+    fn to_le_bytes(self) -> Self::Bytes;
+    /// Returns the memory representation of the number in big-endian byte order.
+    fn to_be_bytes(self) -> Self::Bytes;
+    /// Creates a number from its memory representation in little-endian byte order.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+    /// Creates a number from its memory representation in big-endian byte order.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! impl_ext_int {
+    (
+        $ty:ident($high:ty, $high_fn:ident; $low:ty, $low_fn:ident; $bytes:expr) {
+            $($rest:tt)*
+        }
+    ) => {
+        impl ExtInt for $ty {
+            type High = $high;
+            type Low = $low;
+            type Bytes = [u8; $bytes];
+
+            const BITS: u32 = $bytes * 8;
+            const ZERO: $ty = ::$ty::ZERO;
+            const ONE: $ty = ::$ty::ONE;
+            const MIN: $ty = ::$ty::MIN;
+            const MAX: $ty = ::$ty::MAX;
+
+            fn from_parts(hi: $high, lo: $low) -> $ty { $ty::from_parts(hi, lo) }
+            fn high(self) -> $high { $ty::$high_fn(self) }
+            fn low(self) -> $low { $ty::$low_fn(self) }
+
+            fn checked_add(self, other: $ty) -> Option<$ty> { $ty::checked_add(self, other) }
+            fn checked_sub(self, other: $ty) -> Option<$ty> { $ty::checked_sub(self, other) }
+            fn checked_mul(self, other: $ty) -> Option<$ty> { $ty::checked_mul(self, other) }
+            fn checked_div(self, other: $ty) -> Option<$ty> { $ty::checked_div(self, other) }
+            fn checked_rem(self, other: $ty) -> Option<$ty> { $ty::checked_rem(self, other) }
+
+            fn wrapping_add(self, other: $ty) -> $ty { $ty::wrapping_add(self, other) }
+            fn wrapping_sub(self, other: $ty) -> $ty { $ty::wrapping_sub(self, other) }
+            fn wrapping_mul(self, other: $ty) -> $ty { $ty::wrapping_mul(self, other) }
+            fn wrapping_neg(self) -> $ty { $ty::wrapping_neg(self) }
+            fn wrapping_shl(self, shift: u32) -> $ty { $ty::wrapping_shl(self, shift) }
+            fn wrapping_shr(self, shift: u32) -> $ty { $ty::wrapping_shr(self, shift) }
+
+            fn overflowing_add(self, other: $ty) -> ($ty, bool) { $ty::overflowing_add(self, other) }
+            fn overflowing_sub(self, other: $ty) -> ($ty, bool) { $ty::overflowing_sub(self, other) }
+            fn overflowing_mul(self, other: $ty) -> ($ty, bool) { $ty::overflowing_mul(self, other) }
+
+            $($rest)*
+        }
+    }
+}
+
+impl_ext_int!(u128(u64, high64; u64, low64; 16) {
+    fn count_ones(self) -> u32 { u128::count_ones(self) }
+    fn leading_zeros(self) -> u32 { u128::leading_zeros(self) }
+    fn trailing_zeros(self) -> u32 { u128::trailing_zeros(self) }
+
+    fn to_le_bytes(self) -> [u8; 16] { u128::to_le_bytes(self) }
+    fn to_be_bytes(self) -> [u8; 16] { u128::to_be_bytes(self) }
+    fn from_le_bytes(bytes: [u8; 16]) -> u128 { u128::from_le_bytes(bytes) }
+    fn from_be_bytes(bytes: [u8; 16]) -> u128 { u128::from_be_bytes(bytes) }
+});
+
+impl_ext_int!(i128(i64, high64; u64, low64; 16) {
+    fn count_ones(self) -> u32 { i128::count_ones(self) }
+    fn leading_zeros(self) -> u32 { i128::leading_zeros(self) }
+    fn trailing_zeros(self) -> u32 { i128::trailing_zeros(self) }
+
+    fn to_le_bytes(self) -> [u8; 16] { i128::to_le_bytes(self) }
+    fn to_be_bytes(self) -> [u8; 16] { i128::to_be_bytes(self) }
+    fn from_le_bytes(bytes: [u8; 16]) -> i128 { i128::from_le_bytes(bytes) }
+    fn from_be_bytes(bytes: [u8; 16]) -> i128 { i128::from_be_bytes(bytes) }
+});
+
+/// Concatenates the byte representations of the two halves of a 256-bit number.
+fn join_bytes(first: [u8; 16], second: [u8; 16]) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes[..16].copy_from_slice(&first);
+    bytes[16..].copy_from_slice(&second);
+    bytes
+}
+
+/// Splits the byte representation of a 256-bit number into the two halves.
+fn split_bytes(bytes: [u8; 32]) -> ([u8; 16], [u8; 16]) {
+    let mut first = [0; 16];
+    let mut second = [0; 16];
+    first.copy_from_slice(&bytes[..16]);
+    second.copy_from_slice(&bytes[16..]);
+    (first, second)
+}
+
+impl_ext_int!(u256(u128, high128; u128, low128; 32) {
+    fn count_ones(self) -> u32 { u256::count_ones(self) }
+    fn leading_zeros(self) -> u32 { u256::leading_zeros(self) }
+    fn trailing_zeros(self) -> u32 { u256::trailing_zeros(self) }
+
+    fn to_le_bytes(self) -> [u8; 32] {
+        join_bytes(self.low128().to_le_bytes(), self.high128().to_le_bytes())
+    }
+
+    fn to_be_bytes(self) -> [u8; 32] {
+        join_bytes(self.high128().to_be_bytes(), self.low128().to_be_bytes())
+    }
+
+    fn from_le_bytes(bytes: [u8; 32]) -> u256 {
+        let (lo, hi) = split_bytes(bytes);
+        u256::from_parts(u128::from_le_bytes(hi), u128::from_le_bytes(lo))
+    }
+
+    fn from_be_bytes(bytes: [u8; 32]) -> u256 {
+        let (hi, lo) = split_bytes(bytes);
+        u256::from_parts(u128::from_be_bytes(hi), u128::from_be_bytes(lo))
+    }
+});
+
+impl_ext_int!(i256(i128, high128; u128, low128; 32) {
+    fn count_ones(self) -> u32 { self.0.count_ones() }
+    fn leading_zeros(self) -> u32 { self.0.leading_zeros() }
+    fn trailing_zeros(self) -> u32 { self.0.trailing_zeros() }
+
+    fn to_le_bytes(self) -> [u8; 32] { ExtInt::to_le_bytes(self.0) }
+    fn to_be_bytes(self) -> [u8; 32] { ExtInt::to_be_bytes(self.0) }
+    fn from_le_bytes(bytes: [u8; 32]) -> i256 { i256(ExtInt::from_le_bytes(bytes)) }
+    fn from_be_bytes(bytes: [u8; 32]) -> i256 { i256(ExtInt::from_be_bytes(bytes)) }
+});
+
+#[cfg(test)]
+mod ext_int_tests {
+    use u128::u128;
+    use i128::i128;
+    use u256::u256;
+    use i256::i256;
+    use traits::ExtInt;
+
+    fn check_basics<T: ExtInt>() {
+        assert_eq!(T::MIN.checked_sub(T::ONE), None);
+        assert_eq!(T::MAX.checked_add(T::ONE), None);
+        assert_eq!(T::MAX.wrapping_add(T::ONE), T::MIN);
+        assert_eq!(T::ONE.wrapping_shl(T::BITS - 1).trailing_zeros(), T::BITS - 1);
+        assert_eq!(T::ZERO.leading_zeros(), T::BITS);
+        assert_eq!(T::ONE.checked_div(T::ZERO), None);
+        assert_eq!(T::from_parts(T::MAX.high(), T::MAX.low()), T::MAX);
+        assert_eq!(T::from_le_bytes(T::MAX.to_le_bytes()), T::MAX);
+        assert_eq!(T::from_be_bytes(T::MIN.to_be_bytes()), T::MIN);
+        assert_eq!(T::ONE.to_le_bytes().as_ref()[0], 1);
+        assert_eq!(T::ONE.to_be_bytes().as_ref()[T::BITS as usize / 8 - 1], 1);
+        assert_eq!("1".parse::<T>(), Ok(T::ONE));
+    }
+
+    #[test]
+    fn test_basics() {
+        check_basics::<u128>();
+        check_basics::<i128>();
+        check_basics::<u256>();
+        check_basics::<i256>();
+    }
+
+    #[test]
+    fn test_bytes_256() {
+        let a = u256::from_parts(u128::from_parts(0x0102, 0x0304), u128::from_parts(0x0506, 0x0708));
+        let le = ExtInt::to_le_bytes(a);
+        assert_eq!(&le[..2], &[0x08, 0x07]);
+        assert_eq!(&le[30..], &[0, 0]);
+        assert_eq!(le[24], 0x02);
+        let be = ExtInt::to_be_bytes(i256::from_parts(i128::new(-1), u128::zero()));
+        assert_eq!(&be[..16], &[0xff; 16]);
+        assert_eq!(&be[16..], &[0; 16]);
+    }
+
+    #[test]
+    fn test_associated_constants() {
+        const TABLE: [u128; 4] = [u128::ZERO, u128::ONE, u128::MIN, u128::MAX];
+        assert_eq!(TABLE, [u128::zero(), u128::one(), u128::min_value(), u128::max_value()]);
+        assert_eq!([i128::ZERO, i128::ONE, i128::MIN, i128::MAX],
+                   [i128::zero(), i128::one(), i128::min_value(), i128::max_value()]);
+        assert_eq!([u256::ZERO, u256::ONE, u256::MIN, u256::MAX],
+                   [u256::zero(), u256::one(), u256::min_value(), u256::max_value()]);
+        assert_eq!([i256::ZERO, i256::ONE, i256::MIN, i256::MAX],
+                   [i256::zero(), i256::one(), i256::min_value(), i256::max_value()]);
+    }
+
+    #[cfg(feature="const-traits")]
+    #[test]
+    fn test_const_traits() {
+        use num_traits::{ConstZero, ConstOne};
+
+        fn check_identities<T: ConstZero + ConstOne + ExtInt>() {
+            assert_eq!(<T as ConstZero>::ZERO, T::zero());
+            assert_eq!(<T as ConstOne>::ONE, T::one());
+        }
+
+        // the inherent constants take precedence over those of the traits in scope.
+        const TABLE: [u128; 2] = [u128::ZERO, u128::ONE];
+        assert_eq!(TABLE, [u128::zero(), u128::one()]);
+
+        check_identities::<u128>();
+        check_identities::<i128>();
+        check_identities::<u256>();
+        check_identities::<i256>();
+    }
+}
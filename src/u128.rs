@@ -3,20 +3,24 @@
 use std::cmp::Ordering;
 use std::fmt::{self, Write};
 use std::iter::{Product, Sum};
+use std::mem;
 use std::num::ParseIntError;
 use std::ops::*;
 use std::str::FromStr;
 use std::u64;
 
-#[cfg(feature="rand")] use rand::Rng;
+#[cfg(feature="rand")] use rand::{Rng, SeedableRng};
 #[cfg(feature="rand")] use rand::distributions::{Standard, Distribution};
+#[cfg(feature="rand")] use rand::distributions::uniform::{SampleUniform, UniformSampler, SampleBorrow};
 use num_traits::*;
 
 use compiler_rt::{udiv128, umod128, udivmod128};
-use error;
-use format_buffer::FormatBuffer;
+#[cfg(extprim_has_const_fn)] use compiler_rt::udivmod128_const;
+use error::{self, ParseScientificError};
+use format_buffer::{FormatBuffer, format_decimal};
 use i128::i128;
-use traits::{ToExtraPrimitive, Wrapping};
+use u256::u256;
+use traits::{ToExtraPrimitive, FromExtraPrimitive, Wrapping, Round};
 #[cfg(extprim_has_stable_i128)] use compiler_rt::builtins::{I128, U128};
 
 //{{{ Structure
@@ -39,8 +43,14 @@ pub const ZERO: u128 = MIN;
 /// The constant 1.
 pub const ONE: u128 = u128 { lo: 1, hi: 0 };
 
+/// The maximum length of an unsigned 128-bit number formatted in decimal without padding (39,
+/// the number of digits of [`MAX`](constant.MAX.html)).
+pub const MAX_STR_LEN: usize = 39;
+
 /// An unsigned 128-bit number.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+///
+/// With the `serde` feature, it is serialized as a decimal string in human-readable formats, and
+/// as 16 bytes in little endian in binary formats.
 #[derive(Default, Copy, Clone, Hash, PartialEq, Eq)]
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -64,20 +74,20 @@ pub struct u128 {
 
 impl u128 {
     /// Constructs a new 128-bit integer from a 64-bit integer.
-    #[cfg(extprim_channel="stable")]
+    #[cfg(not(any(extprim_channel="unstable", extprim_has_const_fn)))]
     pub fn new(lo: u64) -> u128 {
         u128 { lo: lo, hi: 0 }
     }
 
     /// Constructs a new 128-bit integer from a 64-bit integer.
-    #[cfg(extprim_channel="unstable")]
+    #[cfg(any(extprim_channel="unstable", extprim_has_const_fn))]
     pub const fn new(lo: u64) -> u128 {
         u128 { lo: lo, hi: 0 }
     }
 
     /// Constructs a new 128-bit integer from the built-in 128-bit integer.
     #[cfg(extprim_has_stable_i128)]
-    #[cfg(extprim_channel="stable")]
+    #[cfg(not(any(extprim_channel="unstable", extprim_has_const_fn)))]
     pub fn from_built_in(value: U128) -> u128 {
         u128 {
             lo: (value & 0xffff_ffff_ffff_ffff) as u64,
@@ -87,7 +97,7 @@ impl u128 {
 
     /// Constructs a new 128-bit integer from the built-in 128-bit integer.
     #[cfg(extprim_has_stable_i128)]
-    #[cfg(extprim_channel="unstable")]
+    #[cfg(any(extprim_channel="unstable", extprim_has_const_fn))]
     pub const fn from_built_in(value: U128) -> u128 {
         u128 {
             lo: (value & 0xffff_ffff_ffff_ffff) as u64,
@@ -107,7 +117,7 @@ impl u128 {
     /// let number = u128::from_parts(6692605942, 14083847773837265618);
     /// assert_eq!(format!("{}", number), "123456789012345678901234567890");
     /// ```
-    #[cfg(extprim_channel="stable")]
+    #[cfg(not(any(extprim_channel="unstable", extprim_has_const_fn)))]
     pub fn from_parts(hi: u64, lo: u64) -> u128 {
         u128 { lo: lo, hi: hi }
     }
@@ -124,7 +134,7 @@ impl u128 {
     /// let number = u128::from_parts(6692605942, 14083847773837265618);
     /// assert_eq!(format!("{}", number), "123456789012345678901234567890");
     /// ```
-    #[cfg(extprim_channel="unstable")]
+    #[cfg(any(extprim_channel="unstable", extprim_has_const_fn))]
     pub const fn from_parts(hi: u64, lo: u64) -> u128 {
         u128 { lo: lo, hi: hi }
     }
@@ -157,6 +167,50 @@ impl u128 {
         self.hi
     }
 
+    /// Exchanges the higher-64-bit and lower-64-bit of the number.
+    ///
+    /// This is the same as rotating the number by 64 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let number = u128::from_parts(1, 2);
+    /// assert_eq!(number.swap_halves(), u128::from_parts(2, 1));
+    /// ```
+    pub fn swap_halves(self) -> u128 {
+        u128 { lo: self.hi, hi: self.lo }
+    }
+
+    /// Returns the number with the lower-64-bit replaced by `lo`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let number = u128::from_parts(1, 2);
+    /// assert_eq!(number.with_low64(3), u128::from_parts(1, 3));
+    /// ```
+    pub fn with_low64(self, lo: u64) -> u128 {
+        u128::from_parts(self.hi, lo)
+    }
+
+    /// Returns the number with the higher-64-bit replaced by `hi`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let number = u128::from_parts(1, 2);
+    /// assert_eq!(number.with_high64(3), u128::from_parts(3, 2));
+    /// ```
+    pub fn with_high64(self, hi: u64) -> u128 {
+        u128::from_parts(hi, self.lo)
+    }
+
     /// Converts this number to signed with wrapping.
     ///
     /// # Examples
@@ -193,6 +247,224 @@ impl Distribution<u128> for Standard {
     }
 }
 
+/// The back-end of `rand::distributions::Uniform<u128>`, sampling uniformly from a range.
+///
+/// This type should not be used directly, use `Uniform` or `Rng::gen_range` instead.
+#[cfg(feature="rand")]
+#[derive(Clone, Copy, Debug)]
+pub struct UniformU128 {
+    low: u128,
+    /// The number of possible values, or 0 if the range covers all 2<sup>128</sup> values.
+    range: u128,
+    /// The largest accepted raw sample. Rejecting the samples above this removes the bias of the
+    /// final modulo reduction.
+    zone: u128,
+}
+
+#[cfg(feature="rand")]
+impl SampleUniform for u128 {
+    type Sampler = UniformU128;
+}
+
+#[cfg(feature="rand")]
+impl UniformSampler for UniformU128 {
+    type X = u128;
+
+    fn new<B1, B2>(low: B1, high: B2) -> UniformU128
+        where B1: SampleBorrow<u128> + Sized,
+              B2: SampleBorrow<u128> + Sized
+    {
+        let low = *low.borrow();
+        let high = *high.borrow();
+        assert!(low < high, "Uniform::new called with `low >= high`");
+        UniformU128::new_inclusive(low, high - ONE)
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> UniformU128
+        where B1: SampleBorrow<u128> + Sized,
+              B2: SampleBorrow<u128> + Sized
+    {
+        let low = *low.borrow();
+        let high = *high.borrow();
+        assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
+        let range = high.wrapping_sub(low).wrapping_add(ONE);
+        let zone = if range == ZERO {
+            MAX
+        } else {
+            MAX - range.wrapping_neg() % range
+        };
+        UniformU128 { low: low, range: range, zone: zone }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u128 {
+        loop {
+            let v: u128 = rng.gen();
+            if v <= self.zone {
+                return if self.range == ZERO {
+                    v
+                } else {
+                    self.low + v % self.range
+                };
+            }
+        }
+    }
+}
+
+/// Multiplier of the 128-bit PCG generator used to expand seeds.
+#[cfg(feature="rand")]
+const PCG_MUL: u128 = u128 { lo: 0x4385df649fccf645, hi: 0x2360ed051fc65da4 };
+
+/// Increment of the 128-bit PCG generator used to expand seeds.
+#[cfg(feature="rand")]
+const PCG_INC: u128 = u128 { lo: 0x14057b7ef767814f, hi: 0x5851f42d4c957f2d };
+
+impl u128 {
+    /// Converts this number into a 16-byte seed, in little-endian byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let seed = u128::new(0x0102).to_seed_bytes();
+    /// assert_eq!(seed, [2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// assert_eq!(u128::from_seed_bytes(seed), u128::new(0x0102));
+    /// ```
+    pub fn to_seed_bytes(self) -> [u8; 16] {
+        self.to_le_bytes()
+    }
+
+    /// Recovers a number from a 16-byte seed created by [`to_seed_bytes`](#method.to_seed_bytes).
+    pub fn from_seed_bytes(seed: [u8; 16]) -> u128 {
+        u128::from_le_bytes(seed)
+    }
+
+    /// Creates a random number generator seeded by this number.
+    ///
+    /// This is the 128-bit counterpart of `SeedableRng::seed_from_u64`. The value is expanded to
+    /// fill the seed of any length using a PCG generator with 128-bit state (XSL-RR output), so
+    /// seeds with low Hamming weight such as 0 still give well-mixed generator states. The
+    /// expansion is fixed, hence the same number always reproduces the same generator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate rand;
+    /// extern crate extprim;
+    ///
+    /// use rand::Rng;
+    /// use rand::rngs::StdRng;
+    /// use extprim::u128::u128;
+    ///
+    /// # fn main() {
+    /// let mut a: StdRng = u128::new(42).seed_rng();
+    /// let mut b: StdRng = u128::new(42).seed_rng();
+    /// assert_eq!(a.gen::<u64>(), b.gen::<u64>());
+    /// # }
+    /// ```
+    #[cfg(feature="rand")]
+    pub fn seed_rng<R: SeedableRng>(self) -> R {
+        let mut state = self;
+        let mut seed = R::Seed::default();
+        for chunk in seed.as_mut().chunks_mut(8) {
+            state = state.wrapping_mul(PCG_MUL).wrapping_add(PCG_INC);
+            let rot = (state.hi >> 58) as u32;
+            let x = (state.hi ^ state.lo).rotate_right(rot);
+            for (i, byte) in chunk.iter_mut().enumerate() {
+                *byte = (x >> (8 * i)) as u8;
+            }
+        }
+        R::from_seed(seed)
+    }
+}
+
+#[cfg(all(test, feature="rand"))]
+mod rand_tests {
+    use rand::{RngCore, SeedableRng, Error};
+    use u128::u128;
+
+    struct SeedRecorder<S>(S);
+
+    impl<S> RngCore for SeedRecorder<S> {
+        fn next_u32(&mut self) -> u32 { 0 }
+        fn next_u64(&mut self) -> u64 { 0 }
+        fn fill_bytes(&mut self, _: &mut [u8]) {}
+        fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), Error> { Ok(()) }
+    }
+
+    impl SeedableRng for SeedRecorder<[u8; 16]> {
+        type Seed = [u8; 16];
+        fn from_seed(seed: [u8; 16]) -> Self { SeedRecorder(seed) }
+    }
+
+    impl SeedableRng for SeedRecorder<[u8; 20]> {
+        type Seed = [u8; 20];
+        fn from_seed(seed: [u8; 20]) -> Self { SeedRecorder(seed) }
+    }
+
+    #[test]
+    fn test_seed_rng() {
+        let rng: SeedRecorder<[u8; 20]> = u128::zero().seed_rng();
+        assert_eq!(rng.0, [
+            0xef, 0x4e, 0x3d, 0x52, 0x31, 0x89, 0xf9, 0xcb, 0x70, 0x68,
+            0x35, 0x8d, 0x1b, 0xb9, 0x98, 0x4d, 0xf1, 0xf8, 0x95, 0xe6,
+        ]);
+        let rng: SeedRecorder<[u8; 16]> = u128::from_parts(0x0123456789abcdef, 0x0011223344556677).seed_rng();
+        assert_eq!(rng.0, [
+            0x31, 0x7b, 0x53, 0x60, 0xdc, 0x47, 0x1c, 0xf2,
+            0xd7, 0xa4, 0xe9, 0xa3, 0x91, 0x8d, 0xc9, 0x52,
+        ]);
+    }
+
+    #[test]
+    fn test_uniform() {
+        use rand::distributions::{Distribution, Uniform};
+        use rng::Mcg128;
+
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+
+        let max = u128::max_value();
+        let full = Uniform::new_inclusive(u128::zero(), max);
+        let sample = full.sample(&mut rng);
+        assert!(sample != full.sample(&mut rng));
+
+        let top = Uniform::new(max - u128::one(), max);
+        for _ in 0..10 {
+            assert_eq!(top.sample(&mut rng), max - u128::one());
+            assert_eq!(Uniform::new_inclusive(max, max).sample(&mut rng), max);
+        }
+
+        let low = u128::from_parts(1, !0 - 1);
+        let dist = Uniform::new_inclusive(low, low + u128::new(3));
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            let x = dist.sample(&mut rng);
+            assert!(x >= low && x <= low + u128::new(3));
+            seen[(x - low).low64() as usize] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+
+        let upper_half = Uniform::new_inclusive(u128::from_parts(1 << 63, 0), max);
+        for _ in 0..100 {
+            assert!(upper_half.sample(&mut rng).high64() >= 1 << 63);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="Uniform::new called with `low >= high`")]
+    fn test_uniform_empty() {
+        use rand::distributions::Uniform;
+        Uniform::new(u128::one(), u128::one());
+    }
+
+    #[test]
+    fn test_seed_bytes() {
+        let a = u128::from_parts(0x0123456789abcdef, 0x0011223344556677);
+        assert_eq!(a.to_seed_bytes(), a.to_le_bytes());
+        assert_eq!(u128::from_seed_bytes(a.to_seed_bytes()), a);
+    }
+}
+
 //}}}
 
 //{{{ Add, Sub
@@ -389,7 +661,19 @@ impl Saturating for u128 {
     }
 
     fn saturating_sub(self, other: Self) -> Self {
-        Self::saturating_add(self, other)
+        Self::saturating_sub(self, other)
+    }
+}
+
+impl SaturatingAdd for u128 {
+    fn saturating_add(&self, other: &Self) -> Self {
+        Self::saturating_add(*self, *other)
+    }
+}
+
+impl SaturatingSub for u128 {
+    fn saturating_sub(&self, other: &Self) -> Self {
+        Self::saturating_sub(*self, *other)
     }
 }
 
@@ -483,6 +767,21 @@ mod add_sub_tests {
     fn test_sub_overflow() {
         let _ = ZERO - ONE;
     }
+
+    #[test]
+    fn test_saturating_traits() {
+        use num_traits::{Saturating, SaturatingAdd, SaturatingSub, SaturatingMul};
+
+        fn saturate<T: SaturatingAdd + SaturatingSub + SaturatingMul>(a: T, b: T) -> (T, T, T) {
+            (a.saturating_add(&b), a.saturating_sub(&b), a.saturating_mul(&b))
+        }
+
+        assert_eq!(saturate(MAX, ONE), (MAX, MAX - ONE, MAX));
+        assert_eq!(saturate(ONE, MAX), (MAX, ZERO, MAX));
+        assert_eq!(saturate(u128::new(6), u128::new(4)), (u128::new(10), u128::new(2), u128::new(24)));
+        assert_eq!(Saturating::saturating_sub(ONE, MAX), ZERO);
+        assert_eq!(Saturating::saturating_add(ONE, MAX), MAX);
+    }
 }
 
 //}}}
@@ -501,6 +800,77 @@ impl Ord for u128 {
     }
 }
 
+impl u128 {
+    const_fn! {
+        /// Returns the smaller of `self` and `other`.
+        ///
+        /// Unlike `Ord::min`, this is a `const fn` on compilers that support it.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use extprim::u128::u128;
+        ///
+        /// assert_eq!(u128::new(3).min(u128::from_parts(1, 0)), u128::new(3));
+        /// ```
+        pub fn min(self, other: u128) -> u128 {
+            if self.hi < other.hi || self.hi == other.hi && self.lo <= other.lo {
+                self
+            } else {
+                other
+            }
+        }
+    }
+
+    const_fn! {
+        /// Returns the larger of `self` and `other`.
+        ///
+        /// Unlike `Ord::max`, this is a `const fn` on compilers that support it.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use extprim::u128::u128;
+        ///
+        /// assert_eq!(u128::new(3).max(u128::from_parts(1, 0)), u128::from_parts(1, 0));
+        /// ```
+        pub fn max(self, other: u128) -> u128 {
+            if self.hi > other.hi || self.hi == other.hi && self.lo >= other.lo {
+                self
+            } else {
+                other
+            }
+        }
+    }
+
+    const_fn! {
+        /// Restricts `self` to the range `min ..= max`.
+        ///
+        /// Unlike `Ord::clamp`, this is a `const fn` on compilers that support it.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `min` is greater than `max`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use extprim::u128::u128;
+        ///
+        /// let (lo, hi) = (u128::new(10), u128::new(20));
+        /// assert_eq!(u128::new(5).clamp(lo, hi), lo);
+        /// assert_eq!(u128::new(15).clamp(lo, hi), u128::new(15));
+        /// assert_eq!(u128::max_value().clamp(lo, hi), hi);
+        /// ```
+        pub fn clamp(self, min: u128, max: u128) -> u128 {
+            if min.hi > max.hi || min.hi == max.hi && min.lo > max.lo {
+                panic!("clamp: min must not be greater than max");
+            }
+            self.max(min).min(max)
+        }
+    }
+}
+
 #[cfg(test)]
 mod cmp_tests {
     use u128::{u128, MAX, ZERO, ONE};
@@ -519,6 +889,36 @@ mod cmp_tests {
         assert!(d != c);
         assert!(d >= c);
     }
+
+    #[test]
+    fn test_min_max_clamp() {
+        let a = u128::from_parts(1, 0);
+        let b = u128::new(!0);
+        assert_eq!(a.min(b), b);
+        assert_eq!(b.min(a), b);
+        assert_eq!(a.max(b), a);
+        assert_eq!(MAX.max(ZERO), MAX);
+        assert_eq!(ONE.clamp(b, a), b);
+        assert_eq!(MAX.clamp(b, a), a);
+        assert_eq!(a.clamp(ZERO, MAX), a);
+        assert_eq!(a.clamp(a, a), a);
+    }
+
+    #[test]
+    #[should_panic(expected="clamp: min must not be greater than max")]
+    fn test_clamp_invalid_range() {
+        ONE.clamp(MAX, ZERO);
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    fn test_const_min_max() {
+        const LIMIT: u128 = u128 { lo: 5, hi: 0 };
+        const CLAMPED: u128 = MAX.clamp(ONE, LIMIT);
+        const SMALLER: u128 = LIMIT.min(ZERO);
+        assert_eq!(CLAMPED, LIMIT);
+        assert_eq!(SMALLER, ZERO);
+    }
 }
 
 //}}}
@@ -581,9 +981,36 @@ impl BitXor for Wrapping<u128> {
     }
 }
 
-forward_assign!(BitAndAssign(bitand_assign, bitand) for u128);
-forward_assign!(BitOrAssign(bitor_assign, bitor) for u128);
-forward_assign!(BitXorAssign(bitxor_assign, bitxor) for u128);
+macro_rules! impl_bitwise_primitive {
+    ($($ty:ty)*) => {$(
+        impl BitAnd<$ty> for u128 {
+            type Output = Self;
+            fn bitand(self, other: $ty) -> Self {
+                u128 { lo: self.lo & (other as u64), hi: 0 }
+            }
+        }
+
+        impl BitOr<$ty> for u128 {
+            type Output = Self;
+            fn bitor(self, other: $ty) -> Self {
+                u128 { lo: self.lo | (other as u64), hi: self.hi }
+            }
+        }
+
+        impl BitXor<$ty> for u128 {
+            type Output = Self;
+            fn bitxor(self, other: $ty) -> Self {
+                u128 { lo: self.lo ^ (other as u64), hi: self.hi }
+            }
+        }
+    )*}
+}
+
+impl_bitwise_primitive!(u8 u16 u32 u64 usize);
+
+forward_assign!(BitAndAssign<u128|u8|u16|u32|u64|usize>(bitand_assign, bitand) for u128);
+forward_assign!(BitOrAssign<u128|u8|u16|u32|u64|usize>(bitor_assign, bitor) for u128);
+forward_assign!(BitXorAssign<u128|u8|u16|u32|u64|usize>(bitxor_assign, bitxor) for u128);
 
 #[cfg(test)]
 mod bitwise_tests {
@@ -615,6 +1042,27 @@ mod bitwise_tests {
                     u128::from_parts(0x206f313ea29823bd, 0x66e0bc7aa198785a),
                     u128::from_parts(0x70de47294a6ecdf4, 0x7de64c4d0880042b));
     }
+
+    #[test]
+    fn test_bitwise_primitive() {
+        let a = u128::from_parts(0x50b17617e8f6ee49, 0x1b06f037a9187c71);
+        assert_eq!(a & 0xffu8, u128::new(0x71));
+        assert_eq!(a & 0xffff_0000u32, u128::new(0xa918_0000));
+        assert_eq!(a & !0u64, u128::new(0x1b06f037a9187c71));
+        assert_eq!(a | 0x8000_0000_0000_0000u64, u128::from_parts(0x50b17617e8f6ee49, 0x9b06f037a9187c71));
+        assert_eq!(a ^ 0xffffu16, u128::from_parts(0x50b17617e8f6ee49, 0x1b06f037a918838e));
+        assert_eq!(a | 1usize, a);
+
+        let mut b = a;
+        b &= 0xf0u8;
+        assert_eq!(b, u128::new(0x70));
+        b |= 0x0fu8;
+        assert_eq!(b, u128::new(0x7f));
+        b ^= 0x3u32;
+        assert_eq!(b, u128::new(0x7c));
+        b ^= a;
+        assert_eq!(b, a ^ 0x7cu64);
+    }
 }
 
 //}}}
@@ -717,6 +1165,105 @@ impl u128 {
     pub fn overflowing_shr(self, other: u32) -> (u128, bool) {
         (self.wrapping_shr(other), other >= 128)
     }
+
+    /// Saturating shift left. Computes `self * 2**other`, saturating at the numeric bounds instead
+    /// of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(7).saturating_shl(66), u128::from_parts(28, 0));
+    /// assert_eq!(u128::new(7).saturating_shl(126), u128::max_value());
+    /// assert_eq!(u128::zero().saturating_shl(200), u128::zero());
+    /// ```
+    pub fn saturating_shl(self, other: u32) -> u128 {
+        if self == ZERO {
+            ZERO
+        } else if other > self.leading_zeros() {
+            MAX
+        } else {
+            self.wrapping_shl(other)
+        }
+    }
+
+    /// Saturating shift right. Computes `self / 2**other` rounded down, giving 0 instead of
+    /// overflowing when the shift is larger than or equal to the number of bits (128).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::max_value().saturating_shr(66), u128::new(0x3fffffffffffffff));
+    /// assert_eq!(u128::max_value().saturating_shr(128), u128::zero());
+    /// ```
+    pub fn saturating_shr(self, other: u32) -> u128 {
+        if other >= 128 {
+            ZERO
+        } else {
+            self.wrapping_shr(other)
+        }
+    }
+
+    /// Shifts `self` left by `shift` bits, returning the result and the bits shifted out.
+    ///
+    /// The shifted-out bits are returned in the lowest `shift` bits of the second element, so
+    /// they can be OR'ed into the next higher limb when shifting a multi-word number made of
+    /// `u128` limbs. Together the pair is the 256-bit result of the shift.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `shift` is greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_parts(0xabcd_0000_0000_0000, 0x1234);
+    /// assert_eq!(a.shl_with_carry(16), (u128::new(0x1234_0000), u128::new(0xabcd)));
+    /// assert_eq!(a.shl_with_carry(0), (a, u128::zero()));
+    /// assert_eq!(a.shl_with_carry(128), (u128::zero(), a));
+    /// ```
+    pub fn shl_with_carry(self, shift: u32) -> (u128, u128) {
+        assert!(shift <= 128, "shift amount must be at most 128");
+        match shift {
+            0 => (self, ZERO),
+            128 => (ZERO, self),
+            _ => (self.wrapping_shl(shift), self.wrapping_shr(128 - shift)),
+        }
+    }
+
+    /// Shifts `self` right by `shift` bits, returning the result and the bits shifted out.
+    ///
+    /// The shifted-out bits are returned in the highest `shift` bits of the second element, so
+    /// they can be OR'ed into the next lower limb when shifting a multi-word number made of
+    /// `u128` limbs.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `shift` is greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_parts(0xabcd, 0x1234);
+    /// assert_eq!(a.shr_with_carry(16), (u128::from_parts(0, 0xabcd_0000_0000_0000),
+    ///                                   u128::from_parts(0x1234_0000_0000_0000, 0)));
+    /// assert_eq!(a.shr_with_carry(128), (u128::zero(), a));
+    /// ```
+    pub fn shr_with_carry(self, shift: u32) -> (u128, u128) {
+        assert!(shift <= 128, "shift amount must be at most 128");
+        match shift {
+            0 => (self, ZERO),
+            128 => (ZERO, self),
+            _ => (self.wrapping_shr(shift), self.wrapping_shl(128 - shift)),
+        }
+    }
 }
 
 forward_shift! {
@@ -756,6 +1303,33 @@ forward_assign!(ShrAssign<u8|u16|u32|u64|usize|i8|i16|i32|i64|isize>(shr_assign,
 mod shift_tests {
     use u128::u128;
 
+    #[test]
+    fn test_shift_with_carry() {
+        use u256::u256;
+
+        let a = u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152);
+        let b = u128::from_parts(0xf8025363ddcd51d8, 0x509d78e4a3008bcd);
+        let ab = u256::from_parts(a, b);
+        for shift in 0..129 {
+            // shifting the 256-bit number `a:b` one limb at a time.
+            let (lo, carry) = b.shl_with_carry(shift);
+            let (hi, _) = a.shl_with_carry(shift);
+            assert_eq!(u256::from_parts(carry, lo), u256::new(b) << shift);
+            assert_eq!(u256::from_parts(hi | carry, lo), ab << shift);
+
+            let (hi, carry) = a.shr_with_carry(shift);
+            let (lo, _) = b.shr_with_carry(shift);
+            assert_eq!(u256::from_parts(hi, carry), u256::from_parts(a, u128::zero()) >> shift);
+            assert_eq!(u256::from_parts(hi, lo | carry), ab >> shift);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="shift amount must be at most 128")]
+    fn test_shl_with_carry_too_far() {
+        u128::one().shl_with_carry(129);
+    }
+
     #[test]
     fn test_shl() {
         assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152) << 0,
@@ -855,30 +1429,37 @@ mod shift_bench {
         });
     }
 
+    #[bench]
+    fn bench_rotate_left(bencher: &mut Bencher) {
+        let number = u128::from_parts(9741918172058430398, 3937562729638942691);
+        bencher.iter(|| {
+            for i in BENCH_SHIFTS {
+                black_box(number.rotate_left(*i));
+            }
+        });
+    }
+
 }
 
 //}}}
 
 //{{{ Mul
 
+/// Computes the product of two unsigned 64-bit integers without any CPU-specific instructions.
+pub(crate) fn u64_long_mul_portable(left: u64, right: u64) -> u128 {
+    let (hi, lo) = ::compiler_rt::long_mul(left, right);
+    u128::from_parts(hi, lo)
+}
+
 /// Computes the product of two unsigned 64-bit integers. Returns a 128-bit
 /// integer.
 #[cfg(not(all(target_arch="x86_64", extprim_channel="unstable")))]
-fn u64_long_mul(left: u64, right: u64) -> u128 {
-    let a = left >> 32;
-    let b = left & 0xffffffff;
-    let c = right >> 32;
-    let d = right & 0xffffffff;
-
-    let lo = b.wrapping_mul(d);
-    let (mid, carry) = a.wrapping_mul(d).overflowing_add(b.wrapping_mul(c));
-    let hi = a.wrapping_mul(c).wrapping_add(if carry { 1 << 32 } else { 0 });
-
-    u128::from_parts(hi, lo).wrapping_add(u128::from_parts(mid >> 32, mid << 32))
+pub(crate) fn u64_long_mul(left: u64, right: u64) -> u128 {
+    ::cpu::mul_64(left, right)
 }
 
 #[cfg(all(target_arch="x86_64", extprim_channel="unstable"))]
-fn u64_long_mul(left: u64, right: u64) -> u128 {
+pub(crate) fn u64_long_mul(left: u64, right: u64) -> u128 {
     unsafe {
         let mut result: u128 = ::std::mem::uninitialized();
         llvm_asm!("
@@ -894,6 +1475,50 @@ fn u64_long_mul(left: u64, right: u64) -> u128 {
     }
 }
 
+#[cfg(not(all(feature="native-backend", extprim_has_stable_i128)))]
+fn wrapping_mul_128(left: u128, right: u128) -> u128 {
+    let a = left.hi;
+    let b = left.lo;
+    let c = right.hi;
+    let d = right.lo;
+    let mut low = u64_long_mul(b, d);
+    let ad = a.wrapping_mul(d);
+    let bc = b.wrapping_mul(c);
+    low.hi = low.hi.wrapping_add(ad).wrapping_add(bc);
+    low
+}
+
+#[cfg(not(all(feature="native-backend", extprim_has_stable_i128)))]
+fn overflowing_mul_128(left: u128, right: u128) -> (u128, bool) {
+    let a = left.hi;
+    let b = left.lo;
+    let c = right.hi;
+    let d = right.lo;
+
+    let (hi, hi_overflow_mul) = match (a, c) {
+        (a, 0) => a.overflowing_mul(d),
+        (0, c) => c.overflowing_mul(b),
+        (a, c) => (a.wrapping_mul(d).wrapping_add(c.wrapping_mul(b)), true),
+    };
+
+    let mut low = u64_long_mul(b, d);
+    let (hi, hi_overflow_add) = low.hi.overflowing_add(hi);
+    low.hi = hi;
+
+    (low, hi_overflow_mul || hi_overflow_add)
+}
+
+#[cfg(all(feature="native-backend", extprim_has_stable_i128))]
+fn wrapping_mul_128(left: u128, right: u128) -> u128 {
+    u128::from_built_in(left.as_built_in().wrapping_mul(right.as_built_in()))
+}
+
+#[cfg(all(feature="native-backend", extprim_has_stable_i128))]
+fn overflowing_mul_128(left: u128, right: u128) -> (u128, bool) {
+    let (product, overflowed) = left.as_built_in().overflowing_mul(right.as_built_in());
+    (u128::from_built_in(product), overflowed)
+}
+
 impl u128 {
     /// Wrapping (modular) multiplication. Computes `self * other`, wrapping around at the boundary
     /// of the type.
@@ -910,15 +1535,7 @@ impl u128 {
     /// assert_eq!(a.wrapping_mul(b), u128::new(15));
     /// ```
     pub fn wrapping_mul(self, other: u128) -> u128 {
-        let a = self.hi;
-        let b = self.lo;
-        let c = other.hi;
-        let d = other.lo;
-        let mut low = u64_long_mul(b, d);
-        let ad = a.wrapping_mul(d);
-        let bc = b.wrapping_mul(c);
-        low.hi = low.hi.wrapping_add(ad).wrapping_add(bc);
-        low
+        wrapping_mul_128(self, other)
     }
 
     /// Calculates the multiplication of `self` and `other`.
@@ -938,22 +1555,7 @@ impl u128 {
     /// assert_eq!(a.overflowing_mul(b), (u128::new(15), true));
     /// ```
     pub fn overflowing_mul(self, other: u128) -> (u128, bool) {
-        let a = self.hi;
-        let b = self.lo;
-        let c = other.hi;
-        let d = other.lo;
-
-        let (hi, hi_overflow_mul) = match (a, c) {
-            (a, 0) => a.overflowing_mul(d),
-            (0, c) => c.overflowing_mul(b),
-            (a, c) => (a.wrapping_mul(d).wrapping_add(c.wrapping_mul(b)), true),
-        };
-
-        let mut low = u64_long_mul(b, d);
-        let (hi, hi_overflow_add) = low.hi.overflowing_add(hi);
-        low.hi = hi;
-
-        (low, hi_overflow_mul || hi_overflow_add)
+        overflowing_mul_128(self, other)
     }
 
     /// Saturating integer multiplication. Computes `self * other`, saturating at the numeric
@@ -1092,7 +1694,74 @@ impl CheckedMul for u128 {
     }
 }
 
-#[cfg(test)]
+impl SaturatingMul for u128 {
+    fn saturating_mul(&self, other: &Self) -> Self {
+        Self::saturating_mul(*self, *other)
+    }
+}
+
+/// Computes the carry-less product of two 64-bit numbers with shifts and exclusive-ors. The
+/// running time does not depend on the operands.
+#[cfg_attr(all(target_arch="aarch64", target_feature="aes", extprim_has_stable_i128), allow(dead_code))]
+pub(crate) fn clmul64_portable(a: u64, b: u64) -> u128 {
+    let mut lo = 0;
+    let mut hi = 0;
+    for i in 0..64 {
+        let mask = ((b >> i) & 1).wrapping_neg();
+        lo ^= (a << i) & mask;
+        // Shifting in two steps avoids the invalid shift by 64 when `i == 0`.
+        hi ^= ((a >> 1) >> (63 - i)) & mask;
+    }
+    u128 { lo: lo, hi: hi }
+}
+
+#[cfg(target_arch="x86_64")]
+fn clmul64(a: u64, b: u64) -> u128 {
+    ::cpu::clmul_64(a, b)
+}
+
+#[cfg(all(target_arch="aarch64", target_feature="aes", extprim_has_stable_i128))]
+fn clmul64(a: u64, b: u64) -> u128 {
+    unsafe { u128::from_built_in(::std::arch::aarch64::vmull_p64(a, b)) }
+}
+
+#[cfg(not(any(target_arch="x86_64",
+              all(target_arch="aarch64", target_feature="aes", extprim_has_stable_i128))))]
+fn clmul64(a: u64, b: u64) -> u128 {
+    clmul64_portable(a, b)
+}
+
+impl u128 {
+    /// Computes the carry-less product of `self` and `other`, i.e. the product of the two
+    /// numbers as polynomials over GF(2), where the bits are the coefficients and addition is
+    /// exclusive-or. Returns the `(high, low)` halves of the 256-bit result.
+    ///
+    /// This is the building block of CRC folding and of the GHASH function of AES-GCM. When the
+    /// crate is compiled with the `pclmulqdq` target feature on x86-64 (or the CPU supports it and
+    /// the `cpu-dispatch` feature is enabled), or with the `aes` target feature on AArch64, the
+    /// multiplication uses the dedicated CPU instruction; otherwise it uses shifts and
+    /// exclusive-ors, whose running time does not depend on the operands either.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// // (x + 1) * (x + 1) = x^2 + 1
+    /// assert_eq!(u128::new(3).clmul(u128::new(3)), (u128::zero(), u128::new(5)));
+    ///
+    /// let x127 = u128::from_parts(1 << 63, 0);
+    /// assert_eq!(x127.clmul(x127), (u128::from_parts(1 << 62, 0), u128::zero()));
+    /// ```
+    pub fn clmul(self, other: u128) -> (u128, u128) {
+        let lo = clmul64(self.lo, other.lo);
+        let hi = clmul64(self.hi, other.hi);
+        let mid = clmul64(self.lo, other.hi) ^ clmul64(self.hi, other.lo);
+        (u128 { lo: hi.lo ^ mid.hi, hi: hi.hi }, u128 { lo: lo.lo, hi: lo.hi ^ mid.lo })
+    }
+}
+
+#[cfg(test)]
 mod mul_tests {
     use std::u64;
     use u128::{u128, u64_long_mul, ZERO, ONE, MAX};
@@ -1191,6 +1860,31 @@ mod mul_tests {
         assert_eq!(MAX.checked_mul(ONE), Some(MAX));
         assert_eq!(MAX.saturating_mul(ONE), MAX);
     }
+
+    #[test]
+    fn test_clmul() {
+        use u128::{clmul64, clmul64_portable};
+        use u256::u256;
+        use rng::Mcg128;
+
+        let mut rng = Mcg128::new(u128::new(1968));
+        for _ in 0..100 {
+            let a = rng.next_u128();
+            let b = rng.next_u128();
+            let mut expected = u256::zero();
+            for i in 0..128u32 {
+                if (b >> i) & ONE != ZERO {
+                    expected ^= u256::new(a) << i;
+                }
+            }
+            assert_eq!(a.clmul(b), (expected.high128(), expected.low128()));
+            assert_eq!(b.clmul(a), a.clmul(b));
+            assert_eq!(clmul64(a.lo, b.lo), clmul64_portable(a.lo, b.lo));
+        }
+        assert_eq!(MAX.clmul(ONE), (ZERO, MAX));
+        assert_eq!(MAX.clmul(ZERO), (ZERO, ZERO));
+        assert_eq!(clmul64_portable(!0, !0), u128::from_parts(0x5555_5555_5555_5555, 0x5555_5555_5555_5555));
+    }
 }
 
 #[cfg(all(test, extprim_channel="unstable"))]
@@ -1388,6 +2082,67 @@ impl u128 {
     }
 }
 
+#[cfg(extprim_has_const_fn)]
+impl u128 {
+    /// Computes `self / other` in a `const` context.
+    ///
+    /// This gives the same result as the `/` operator, but is usable when initializing constants
+    /// and statics. It is slower than `/` at runtime, so prefer the operator outside of constants.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0. In a `const` context this is a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::{u128, MAX};
+    ///
+    /// const TEN_POW_19: u128 = u128::new(10_000_000_000_000_000_000);
+    /// const QUOTIENT: u128 = MAX.const_div(TEN_POW_19);
+    /// assert_eq!(QUOTIENT, u128::max_value() / TEN_POW_19);
+    /// ```
+    pub const fn const_div(self, other: u128) -> u128 {
+        udivmod128_const(self, other).0
+    }
+
+    /// Computes `self % other` in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0. In a `const` context this is a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// const REM: u128 = u128::from_parts(1, 0).const_rem(u128::new(10));
+    /// assert_eq!(REM, u128::new(6));
+    /// ```
+    pub const fn const_rem(self, other: u128) -> u128 {
+        udivmod128_const(self, other).1
+    }
+
+    /// Computes `(self / other, self % other)` in a `const` context.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0. In a `const` context this is a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// const QR: (u128, u128) = u128::from_parts(1, 0).const_div_rem(u128::new(10));
+    /// assert_eq!(QR, (u128::new(1844674407370955161), u128::new(6)));
+    /// ```
+    pub const fn const_div_rem(self, other: u128) -> (u128, u128) {
+        udivmod128_const(self, other)
+    }
+}
+
 impl Div for u128 {
     type Output = Self;
     fn div(self, other: Self) -> Self {
@@ -1395,6 +2150,34 @@ impl Div for u128 {
     }
 }
 
+impl u128 {
+    /// Computes `self % other`, where the divisor is a 64-bit number. Since the remainder is less
+    /// than the divisor, the result is returned as a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let key = u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152);
+    /// let shard: u64 = key % 16u64;
+    /// assert_eq!(shard, 2);
+    /// assert_eq!(u128::max_value().rem_u64(1000), 455);
+    /// ```
+    pub fn rem_u64(self, other: u64) -> u64 {
+        if other == 0 {
+            panic!("attempted remainder with a divisor of zero");
+        }
+        // Reducing the high word first makes the quotient of the full division fit in 64 bits.
+        let n = u128 { lo: self.lo, hi: self.hi % other };
+        umod128(n, u128::new(other)).lo
+    }
+}
+
 impl Rem for u128 {
     type Output = Self;
     fn rem(self, other: Self) -> Self {
@@ -1402,6 +2185,13 @@ impl Rem for u128 {
     }
 }
 
+impl Rem<u64> for u128 {
+    type Output = u64;
+    fn rem(self, other: u64) -> u64 {
+        self.rem_u64(other)
+    }
+}
+
 impl Div for Wrapping<u128> {
     type Output = Self;
     fn div(self, other: Self) -> Self {
@@ -1449,6 +2239,52 @@ pub fn div_rem(numerator: u128, denominator: u128) -> (u128, u128) {
     }
 }
 
+impl u128 {
+    /// Computes `self / divisor`, assuming that `self` is an exact multiple of `divisor`.
+    ///
+    /// Instead of a full division, the quotient is computed by multiplying with the inverse of the
+    /// divisor modulo 2<sup>128</sup>, which exists for odd divisors. Even divisors are first
+    /// reduced by shifting out their trailing zeros. This makes the function much faster than `/`
+    /// when the divisibility is already known, e.g. when normalizing fractions by their gcd.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `divisor` is 0. In debug mode, it will also panic if `self` is
+    /// not a multiple of `divisor`. In release mode, the result is unspecified in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let n = u128::from_parts(0x1e5c, 0x53f09dac5b28f152);
+    /// let d = u128::new(0x2a3f_0c1e_5b79);
+    /// assert_eq!((n * d).exact_div(d), n);
+    /// assert_eq!(u128::new(96).exact_div(u128::new(24)), u128::new(4));
+    /// ```
+    pub fn exact_div(self, divisor: u128) -> u128 {
+        if divisor == ZERO {
+            panic!("attempted to divide by zero");
+        }
+        let shift = divisor.trailing_zeros();
+        let odd = divisor >> shift;
+
+        // Newton's iteration doubles the number of correct low bits of the inverse in each
+        // step. The initial value is correct to 5 bits, so 5 steps are enough for 128 bits.
+        let mut inverse = odd.wrapping_mul(u128::new(3)) ^ u128::new(2);
+        for _ in 0 .. 5 {
+            inverse = inverse.wrapping_mul(u128::new(2).wrapping_sub(odd.wrapping_mul(inverse)));
+        }
+
+        let quotient = (self >> shift).wrapping_mul(inverse);
+        // The wrapping product gives back `self` even for a non-multiple, so only the full product
+        // reveals a remainder.
+        debug_assert!(quotient.checked_mul(divisor) == Some(self),
+                      "exact_div: value is not a multiple of the divisor");
+        quotient
+    }
+}
+
 #[cfg(test)]
 mod div_rem_tests {
     use u128::{u128, ONE, ZERO, div_rem};
@@ -1469,6 +2305,22 @@ mod div_rem_tests {
         assert_eq!(u128::from_parts(3, 1) / u128::from_parts(3, 0), ONE);
     }
 
+    #[test]
+    fn test_rem_u64() {
+        let n = u128::from_parts(9071183389512669386, 9598842501673620991);
+        for &d in &[1u64, 2, 3, 10, 6108228772930395530, 9071183389512669386, 1 << 63, !0] {
+            assert_eq!(u128::new(n % d), n % u128::new(d));
+            assert_eq!(u128::new(u128::max_value().rem_u64(d)), u128::max_value() % u128::new(d));
+        }
+        assert_eq!(ZERO.rem_u64(7), 0);
+    }
+
+    #[test]
+    #[should_panic(expected="attempted remainder with a divisor of zero")]
+    fn test_rem_u64_by_zero() {
+        ONE.rem_u64(0);
+    }
+
     #[test]
     #[should_panic(expected="attempted to divide by zero")]
     fn test_div_by_zero() {
@@ -1501,1001 +2353,3780 @@ mod div_rem_tests {
                     (u128::new(3),
                         u128::from_parts(1173272007174095132, 11108405285635420054)));
     }
-}
-
-//}}}
-
-//{{{ Casting
-
-fn ldexp(base: f64, exp: u32) -> f64 {
-    // TODO the built-in `ldexp` is deprecated. Find alternate native implementation instead.
-    base * 2.0 * (1u64 << (exp-1)) as f64
-}
 
-impl ToPrimitive for u128 {
-    fn to_i64(&self) -> Option<i64> {
-        if self.hi != 0 {
-            None
-        } else {
-            self.lo.to_i64()
-        }
+    #[test]
+    #[cfg(extprim_has_const_fn)]
+    fn test_const_div_rem() {
+        use u128::MAX;
+
+        const POWERS_OF_TEN: [u128; 3] = [
+            MAX.const_div(u128::new(10)),
+            MAX.const_div(u128::new(100)),
+            MAX.const_div(u128::new(1000)),
+        ];
+        const REM: u128 = MAX.const_rem(u128::new(1000));
+
+        assert_eq!(POWERS_OF_TEN[0], u128::max_value() / u128::new(10));
+        assert_eq!(POWERS_OF_TEN[1], u128::max_value() / u128::new(100));
+        assert_eq!(POWERS_OF_TEN[2], u128::max_value() / u128::new(1000));
+        assert_eq!(REM, u128::new(455));
+        assert_eq!(u128::from_parts(10328265298226767242, 6197012475834382470)
+                        .const_div_rem(u128::from_parts(3051664430350890703, 4511783754636171344)),
+                    (u128::new(3),
+                        u128::from_parts(1173272007174095132, 11108405285635420054)));
     }
 
-    fn to_u64(&self) -> Option<u64> {
-        if self.hi != 0 {
-            None
-        } else {
-            Some(self.lo)
+    #[test]
+    fn test_exact_div() {
+        let divisors = [
+            ONE, u128::new(2), u128::new(3), u128::new(10), u128::new(1 << 63),
+            u128::from_parts(1, 0), u128::from_parts(0x3, 0x7fff_ffff_ffff_ffff),
+            u128::from_parts(0x8000_0000_0000_0000, 0), u128::max_value(),
+        ];
+        let quotients = [
+            ZERO, ONE, u128::new(12345), u128::from_parts(0, !0), u128::from_parts(0x1234, 0x5678),
+        ];
+        for &d in &divisors {
+            for &q in &quotients {
+                if let Some(n) = q.checked_mul(d) {
+                    assert_eq!(n.exact_div(d), q);
+                    assert_eq!(n.exact_div(d), n / d);
+                }
+            }
         }
     }
 
-    fn to_f64(&self) -> Option<f64> {
-        if self.hi != 0 {
-            let shift_size = 64 - self.hi.leading_zeros();
-            let truncated = (*self >> shift_size).lo as f64;
-            Some(ldexp(truncated, shift_size))
-        } else {
-            self.lo.to_f64()
-        }
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_exact_div_by_zero() {
+        ONE.exact_div(ZERO);
     }
 
-    #[cfg(extprim_has_stable_i128)]
-    fn to_i128(&self) -> Option<I128> {
-        if self.hi < 0x80000000_00000000 {
-            Some(self.as_built_in() as I128)
-        } else {
-            None
-        }
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected="exact_div: value is not a multiple of the divisor")]
+    fn test_exact_div_inexact() {
+        u128::new(10).exact_div(u128::new(3));
     }
 
-    #[cfg(extprim_has_stable_i128)]
-    fn to_u128(&self) -> Option<U128> {
-        Some(self.as_built_in())
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected="exact_div: value is not a multiple of the divisor")]
+    fn test_exact_div_inexact_wrapping() {
+        // 2^127 * 3 wraps around to 2^127.
+        u128::from_parts(1 << 63, 0).exact_div(u128::new(3));
     }
 }
 
-impl FromPrimitive for u128 {
-    fn from_u64(n: u64) -> Option<u128> {
-        ToExtraPrimitive::to_u128(&n)
-    }
+//}}}
 
-    fn from_i64(n: i64) -> Option<u128> {
-        ToExtraPrimitive::to_u128(&n)
-    }
+//{{{ Division by constants
 
-    fn from_f64(n: f64) -> Option<u128> {
-        ToExtraPrimitive::to_u128(&n)
-    }
+/// A nonzero divisor with a precomputed multiplier, so that dividing by it takes a multiplication,
+/// an addition and two shifts instead of a long division.
+///
+/// This is the method of Granlund and Montgomery, *Division by Invariant Integers using
+/// Multiplication* (1994), which compilers use for constant divisors of the primitive types. The
+/// divisor is usually created at compile time by the [`div_by_const!`](../macro.div_by_const.html)
+/// macro, or stored in a `const` item:
+///
+/// ```rust
+/// use extprim::u128::{u128, ConstDivisor};
+///
+/// const BILLION: ConstDivisor = ConstDivisor::new(u128::new(1_000_000_000));
+///
+/// let n = u128::from_parts(12345, 67890);
+/// assert_eq!(n / BILLION, n / u128::new(1_000_000_000));
+/// assert_eq!(n % BILLION, n % u128::new(1_000_000_000));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConstDivisor {
+    divisor: u128,
+    multiplier: u128,
+    /// `ceil(log2(divisor))`.
+    log2: u32,
 }
 
-impl ToExtraPrimitive for u128 {
-    fn to_u128(&self) -> Option<u128> {
-        Some(*self)
-    }
+impl ConstDivisor {
+    const_fn! {
+        /// Precomputes the multiplier for dividing by `divisor`.
+        ///
+        /// This is a `const fn` on compilers that support it.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `divisor` is 0. In a `const` context this is a compile
+        /// error.
+        pub fn new(divisor: u128) -> ConstDivisor {
+            if divisor.hi == 0 && divisor.lo == 0 {
+                panic!("attempted to divide by zero");
+            }
 
-    fn to_i128(&self) -> Option<i128> {
-        if self.hi >= 0x8000_0000_0000_0000 {
-            None
-        } else {
-            Some(i128(*self))
+            // log2 = ceil(log2(d)) is the bit length of d - 1.
+            let (dm1_lo, borrow) = divisor.lo.overflowing_sub(1);
+            let dm1_hi = divisor.hi - borrow as u64;
+            let log2 = if dm1_hi == 0 { 64 - dm1_lo.leading_zeros() } else { 128 - dm1_hi.leading_zeros() };
+
+            // a = 2^log2 - d, wrapping around for log2 == 128. This is less than d.
+            let (p_lo, p_hi) = if log2 < 64 {
+                (1u64 << log2, 0)
+            } else if log2 < 128 {
+                (0, 1u64 << (log2 - 64))
+            } else {
+                (0, 0)
+            };
+            let (a_lo, borrow) = p_lo.overflowing_sub(divisor.lo);
+            let a_hi = p_hi.wrapping_sub(divisor.hi).wrapping_sub(borrow as u64);
+
+            // multiplier = floor(a * 2^128 / d) + 1, by long division one bit at a time.
+            let (mut r_lo, mut r_hi) = (a_lo, a_hi);
+            let (mut q_lo, mut q_hi) = (0u64, 0u64);
+            let mut i = 128;
+            while i > 0 {
+                i -= 1;
+                let carry = r_hi >> 63;
+                r_hi = r_hi << 1 | r_lo >> 63;
+                r_lo <<= 1;
+                if carry != 0 || r_hi > divisor.hi || (r_hi == divisor.hi && r_lo >= divisor.lo) {
+                    let (lo, borrow) = r_lo.overflowing_sub(divisor.lo);
+                    r_hi = r_hi.wrapping_sub(divisor.hi).wrapping_sub(borrow as u64);
+                    r_lo = lo;
+                    if i < 64 {
+                        q_lo |= 1 << i;
+                    } else {
+                        q_hi |= 1 << (i - 64);
+                    }
+                }
+            }
+            let (m_lo, carry) = q_lo.overflowing_add(1);
+            let multiplier = u128 { lo: m_lo, hi: q_hi + carry as u64 };
+
+            ConstDivisor { divisor: divisor, multiplier: multiplier, log2: log2 }
         }
     }
-}
 
-impl From<u8> for u128 {
-    fn from(arg: u8) -> Self {
-        u128::new(arg as u64)
+    /// Returns the divisor.
+    pub fn divisor(&self) -> u128 {
+        self.divisor
     }
-}
 
-impl From<u16> for u128 {
-    fn from(arg: u16) -> Self {
-        u128::new(arg as u64)
+    /// Computes `n / self.divisor()`.
+    pub fn div(&self, n: u128) -> u128 {
+        let t = u256::mul_128(self.multiplier, n).high128();
+        let (shift_1, shift_2) = if self.log2 == 0 { (0, 0) } else { (1, self.log2 - 1) };
+        // (n - t) >> 1 + t is (n + t) >> 1 without overflowing.
+        (t + ((n - t) >> shift_1)) >> shift_2
     }
-}
 
-impl From<u32> for u128 {
-    fn from(arg: u32) -> Self {
-        u128::new(arg as u64)
+    /// Computes `n % self.divisor()`.
+    pub fn rem(&self, n: u128) -> u128 {
+        self.div_rem(n).1
+    }
+
+    /// Computes `(n / self.divisor(), n % self.divisor())`.
+    pub fn div_rem(&self, n: u128) -> (u128, u128) {
+        let q = self.div(n);
+        (q, n - q.wrapping_mul(self.divisor))
     }
 }
 
-impl From<u64> for u128 {
-    fn from(arg: u64) -> Self {
-        u128::new(arg)
+impl Div<ConstDivisor> for u128 {
+    type Output = u128;
+    fn div(self, other: ConstDivisor) -> u128 {
+        other.div(self)
     }
 }
 
-#[cfg(extprim_has_stable_i128)]
-impl From<U128> for u128 {
-    fn from(arg: U128) -> Self {
-        u128::from_built_in(arg)
+impl Rem<ConstDivisor> for u128 {
+    type Output = u128;
+    fn rem(self, other: ConstDivisor) -> u128 {
+        other.rem(self)
     }
 }
 
 #[cfg(test)]
-mod conv_tests {
-    use u128::{u128, MAX};
-    use num_traits::ToPrimitive;
+mod const_divisor_tests {
+    use u128::{u128, ConstDivisor, ZERO, ONE, MAX, div_rem};
+    use rng::Mcg128;
 
     #[test]
-    fn test_u128_to_f64() {
-        assert_eq!(u128::new(0).to_f64(), Some(0.0f64));
-        assert_eq!(u128::new(1).to_f64(), Some(1.0f64));
-        assert_eq!(u128::new(2).to_f64(), Some(2.0f64));
-        assert_eq!(MAX.to_f64(), Some(340282366920938463463374607431768211455.0f64));
+    fn test_matches_division() {
+        let mut rng = Mcg128::new(u128::new(0xd1d));
+        let mut divisors = vec![
+            ONE, u128::new(2), u128::new(3), u128::new(7), u128::new(10), u128::new(1_000_000_007),
+            u128::new(!0), u128::from_parts(1, 0), u128::from_parts(1, 1), u128::from_parts(0x8000_0000_0000_0000, 0),
+            u128::from_parts(0x8000_0000_0000_0000, 1), MAX - ONE, MAX,
+        ];
+        for _ in 0..20 {
+            let d = rng.next_u128();
+            divisors.push(d);
+            divisors.push(d >> (d.lo % 128) as usize);
+        }
+        let mut numerators = vec![ZERO, ONE, MAX, MAX - ONE, u128::from_parts(0, !0), u128::from_parts(1, 0)];
+        numerators.extend((0..50).map(|_| rng.next_u128()));
+
+        for &d in &divisors {
+            if d == ZERO {
+                continue;
+            }
+            let divisor = ConstDivisor::new(d);
+            assert_eq!(divisor.divisor(), d);
+            for &n in numerators.iter().chain(&[d, d - ONE, d.wrapping_add(ONE), d.wrapping_mul(u128::new(3))]) {
+                assert_eq!(divisor.div_rem(n), div_rem(n, d), "{} / {}", n, d);
+                assert_eq!(n / divisor, n / d);
+                assert_eq!(n % divisor, n % d);
+            }
+        }
     }
 
     #[test]
-    #[cfg(extprim_has_stable_i128)]
-    fn test_builtin_u128_to_u128() {
-        assert_eq!(u128::from_built_in(0x35d2c4473082b8c1_8b704240ca1021b8u128), u128::from_parts(0x35d2c4473082b8c1, 0x8b704240ca1021b8));
-        assert_eq!(u128::from_parts(0x35d2c4473082b8c1, 0x8b704240ca1021b8).as_built_in(), 0x35d2c4473082b8c1_8b704240ca1021b8u128);
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_zero_divisor() {
+        ConstDivisor::new(ZERO);
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    fn test_div_by_const() {
+        let n = u128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+        assert_eq!(div_by_const!(n, 1_000_000_007u64), n / u128::new(1_000_000_007));
+        assert_eq!(div_by_const!(n, 1), n);
+        assert_eq!(div_by_const!(MAX, 1 << 63), u128::from_parts(1, !0));
     }
 }
 
 //}}}
 
-//{{{ Constants
+//{{{ Modular arithmetic
 
 impl u128 {
-    /// Returns the smallest unsigned 128-bit integer (0).
-    pub fn min_value() -> u128 { MIN }
-
-    /// Returns the largest unsigned 128-bit integer
-    /// (`340_282_366_920_938_463_463_374_607_431_768_211_455`).
-    pub fn max_value() -> u128 { MAX }
-
-    /// Returns the constant 0.
-    pub fn zero() -> u128 { ZERO }
-
-    /// Returns the constant 1.
-    pub fn one() -> u128 { ONE }
-}
-
-impl Bounded for u128 {
-    fn min_value() -> Self { MIN }
-    fn max_value() -> Self { MAX }
-}
-
-impl Zero for u128 {
-    fn zero() -> Self { ZERO }
-    fn is_zero(&self) -> bool { *self == ZERO }
-}
-
-impl One for u128 {
-    fn one() -> Self { ONE }
-}
-
-//}}}
-
-//{{{ PrimInt
-
-impl u128 {
-    /// Returns the number of ones in the binary representation of `self`.
+    /// Computes `(self + other) % modulus`, without overflowing in the intermediate sum.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// let n = u128::from_str_radix("6f32f1ef8b18a2bc3cea59789c79d441", 16).unwrap();
-    /// assert_eq!(n.count_ones(), 67);
+    /// let m = u128::max_value() - u128::new(158);
+    /// assert_eq!(m.wrapping_sub(u128::one()).add_mod(u128::new(5), m), u128::new(4));
+    /// assert_eq!(u128::new(6).add_mod(u128::new(5), u128::new(7)), u128::new(4));
     /// ```
-    pub fn count_ones(self) -> u32 {
-        self.lo.count_ones() + self.hi.count_ones()
+    pub fn add_mod(self, other: u128, modulus: u128) -> u128 {
+        let a = self % modulus;
+        let b = other % modulus;
+        let (sum, overflow) = a.overflowing_add(b);
+        if overflow || sum >= modulus {
+            sum.wrapping_sub(modulus)
+        } else {
+            sum
+        }
     }
 
-    /// Returns the number of zeros in the binary representation of `self` (including leading zeros).
+    /// Computes `(self - other) mod modulus`, the result always lying in `0 .. modulus`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// let n = u128::from_str_radix("6f32f1ef8b18a2bc3cea59789c79d441", 16).unwrap();
-    /// assert_eq!(n.count_zeros(), 61);
+    /// assert_eq!(u128::new(2).sub_mod(u128::new(5), u128::new(7)), u128::new(4));
     /// ```
-    pub fn count_zeros(self) -> u32 {
-        self.lo.count_zeros() + self.hi.count_zeros()
+    pub fn sub_mod(self, other: u128, modulus: u128) -> u128 {
+        let a = self % modulus;
+        let b = other % modulus;
+        if a >= b {
+            a - b
+        } else {
+            modulus - (b - a)
+        }
     }
 
-    /// Returns the number of leading zeros in the binary representation of `self`.
+    /// Computes `(self * other) % modulus`, using the full 256-bit product.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// assert_eq!(u128::zero().leading_zeros(), 128);
-    /// assert_eq!(u128::one().leading_zeros(), 127);
-    /// assert_eq!(u128::max_value().leading_zeros(), 0);
-    /// assert_eq!((u128::one() << 24u32).leading_zeros(), 103);
-    /// assert_eq!((u128::one() << 124u32).leading_zeros(), 3);
+    /// let m = u128::max_value() - u128::new(158);
+    /// assert_eq!(u128::max_value().mul_mod(u128::max_value(), m), u128::new(24964));
     /// ```
-    pub fn leading_zeros(self) -> u32 {
-        if self.hi == 0 {
-            64 + self.lo.leading_zeros()
-        } else {
-            self.hi.leading_zeros()
-        }
+    pub fn mul_mod(self, other: u128, modulus: u128) -> u128 {
+        let product = u256::mul_128(self, other);
+        ::u256::div_rem(product, u256::new(modulus)).1.low128()
     }
 
-    /// Returns the number of trailing zeros in the binary representation of `self`.
+    /// Computes `self.pow(exp) % modulus` by exponentiation by squaring.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// assert_eq!(u128::zero().trailing_zeros(), 128);
-    /// assert_eq!(u128::one().trailing_zeros(), 0);
-    /// assert_eq!((u128::one() << 24u32).trailing_zeros(), 24);
-    /// assert_eq!((u128::one() << 124u32).trailing_zeros(), 124);
+    /// assert_eq!(u128::new(3).pow_mod(u128::new(200), u128::new(1000000007)), u128::new(136318165));
+    /// assert_eq!(u128::new(3).pow_mod(u128::zero(), u128::one()), u128::zero());
     /// ```
-    pub fn trailing_zeros(self) -> u32 {
-        if self.lo == 0 {
-            64 + self.hi.trailing_zeros()
-        } else {
-            self.lo.trailing_zeros()
+    pub fn pow_mod(self, mut exp: u128, modulus: u128) -> u128 {
+        let mut base = self % modulus;
+        let mut acc = ONE % modulus;
+        while exp != ZERO {
+            if exp.lo & 1 != 0 {
+                acc = acc.mul_mod(base, modulus);
+            }
+            exp >>= 1;
+            base = base.mul_mod(base, modulus);
         }
+        acc
     }
 
-    /// Shifts the bits to the left by a specified amount, `shift`, wrapping the truncated bits to
-    /// the end of the resulting integer.
+    /// Computes the modular multiplicative inverse of `self`, i.e. the number `x` in
+    /// `0 .. modulus` where `(self * x) % modulus == 1 % modulus`. Returns `None` if `self` and
+    /// `modulus` are not coprime.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// let a = u128::from_str_radix("d0cf4b50cfe20765fff4b4e3f741cf6d", 16).unwrap();
-    /// let b = u128::from_str_radix("19e96a19fc40ecbffe969c7ee839edba", 16).unwrap();
-    /// assert_eq!(a.rotate_left(5), b);
+    /// assert_eq!(u128::new(3).inv_mod(u128::new(7)), Some(u128::new(5)));
+    /// assert_eq!(u128::new(2).inv_mod(u128::new(4)), None);
     /// ```
-    pub fn rotate_left(self, shift: u32) -> Self {
-        let rotated = match shift & 63 {
-            0 => self,
-            n => u128 {
-                lo: self.lo << n | self.hi >> 64u32.wrapping_sub(n),
-                hi: self.hi << n | self.lo >> 64u32.wrapping_sub(n),
-            },
-        };
-        if shift & 64 == 0 {
-            rotated
+    pub fn inv_mod(self, modulus: u128) -> Option<u128> {
+        let (mut r0, mut r1) = (modulus, self % modulus);
+        let (mut t0, mut t1) = (ZERO, ONE % modulus);
+        while r1 != ZERO {
+            let (q, r) = div_rem(r0, r1);
+            r0 = r1;
+            r1 = r;
+            let t = t0.sub_mod(q.mul_mod(t1, modulus), modulus);
+            t0 = t1;
+            t1 = t;
+        }
+        if r0 == ONE {
+            Some(t0)
         } else {
-            u128 { lo: rotated.hi, hi: rotated.lo }
+            None
         }
     }
 
-    /// Shifts the bits to the right by a specified amount, `shift`, wrapping the truncated bits to
-    /// the beginning of the resulting integer.
+    /// Computes `self.pow(exp) % modulus` in constant time with respect to `self` and `exp`.
     ///
-    /// # Examples
+    /// This uses a fixed 4-bit window over all 128 bits of the exponent, and the reduction and
+    /// table lookups are made without branches or memory accesses depending on the secret values.
+    /// It is much slower than [`pow_mod`](#method.pow_mod), and is intended for protocols where
+    /// the exponent must be kept secret. The modulus is treated as public.
     ///
-    /// ```rust
-    /// use extprim::u128::u128;
+    /// Note that the compiler does not guarantee the generated code is free of timing side
+    /// channels; this function only avoids secret-dependent control flow at the source level.
     ///
-    /// let a = u128::from_str_radix("d0cf4b50cfe20765fff4b4e3f741cf6d", 16).unwrap();
-    /// let b = u128::from_str_radix("6e867a5a867f103b2fffa5a71fba0e7b", 16).unwrap();
-    /// assert_eq!(a.rotate_right(5), b);
-    /// ```
-    pub fn rotate_right(self, shift: u32) -> Self {
-        self.rotate_left(128u32.wrapping_sub(shift))
-    }
-
-    /// Reverses the byte order of the integer.
+    /// # Panics
+    ///
+    /// This function will panic if `modulus` is 0.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// let a = u128::from_str_radix("0123456789abcdef1112223334445556", 16).unwrap();
-    /// let b = u128::from_str_radix("5655443433221211efcdab8967452301", 16).unwrap();
-    /// assert_eq!(a.swap_bytes(), b);
+    /// assert_eq!(u128::new(3).pow_mod_ct(u128::new(200), u128::new(1000000007)), u128::new(136318165));
     /// ```
-    pub fn swap_bytes(self) -> Self {
-        u128 { lo: self.hi.swap_bytes(), hi: self.lo.swap_bytes() }
+    pub fn pow_mod_ct(self, exp: u128, modulus: u128) -> u128 {
+        assert!(modulus != ZERO, "attempted remainder with a divisor of zero");
+        let base = ct_reduce(u256::new(self), modulus);
+
+        let mut table = [ONE % modulus; 16];
+        for i in 1..16 {
+            table[i] = ct_mul_mod(table[i - 1], base, modulus);
+        }
+
+        let mut acc = table[0];
+        for window in (0..32u32).rev() {
+            for _ in 0..4 {
+                acc = ct_mul_mod(acc, acc, modulus);
+            }
+            let index = (exp >> (window * 4)).lo & 15;
+            let mut factor = ZERO;
+            for (k, entry) in table.iter().enumerate() {
+                factor |= *entry & ct_mask(ct_eq(index, k as u64));
+            }
+            acc = ct_mul_mod(acc, factor, modulus);
+        }
+        acc
     }
+}
 
-    /// Converts an integer from big endian to the target's endianness.
-    ///
-    /// On big endian this is a no-op. On little endian the bytes are swapped.
-    pub fn from_be(x: Self) -> Self {
-        if cfg!(target_endian="big") {
-            x
-        } else {
-            x.swap_bytes()
+/// Returns 1 if `a == b`, and 0 otherwise, without branching.
+fn ct_eq(a: u64, b: u64) -> u64 {
+    let x = a ^ b;
+    ((x | x.wrapping_neg()) >> 63) ^ 1
+}
+
+/// Expands a bit (0 or 1) to a mask of all zeros or all ones.
+fn ct_mask(bit: u64) -> u128 {
+    let mask = bit.wrapping_neg();
+    u128::from_parts(mask, mask)
+}
+
+/// Computes `value % modulus` by a bit-serial shift-and-subtract, taking the same steps regardless
+/// of `value`.
+fn ct_reduce(value: u256, modulus: u128) -> u128 {
+    let words = [value.high128(), value.low128()];
+    let mut r = ZERO;
+    for word in &words {
+        for i in (0..128u32).rev() {
+            let top = r.hi >> 63;
+            r = (r << 1) | u128::new((*word >> i).lo & 1);
+            let (lo, borrow_lo) = r.lo.overflowing_sub(modulus.lo);
+            let (hi, borrow_hi_1) = r.hi.overflowing_sub(modulus.hi);
+            let (hi, borrow_hi_2) = hi.overflowing_sub(borrow_lo as u64);
+            let borrow = borrow_hi_1 as u64 | borrow_hi_2 as u64;
+            let mask = ct_mask(top | (borrow ^ 1));
+            r = (u128::from_parts(hi, lo) & mask) | (r & !mask);
         }
     }
+    r
+}
 
-    /// Converts an integer from little endian to the target's endianness.
-    ///
-    /// On little endian this is a no-op. On big endian the bytes are swapped.
-    pub fn from_le(x: Self) -> Self {
-        if cfg!(target_endian="little") {
-            x
-        } else {
-            x.swap_bytes()
+fn ct_mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    ct_reduce(u256::mul_128(a, b), modulus)
+}
+
+#[cfg(test)]
+mod modular_tests {
+    use u128::u128;
+
+    const P: u128 = u128 { lo: 0xffffffffffffff61, hi: !0 };
+    const A: u128 = u128 { lo: 0x8b704240ca1021b8, hi: 0x35d2c4473082b8c1 };
+    const B: u128 = u128 { lo: 0x8cdf8e3b4b75c4d0, hi: 0x76571c252122c42e };
+
+    #[test]
+    fn test_add_sub_mod() {
+        assert_eq!(A.add_mod(B, P).add_mod(u128::max_value(), P),
+                    u128::from_parts(0xac29e06c51a57cf0, 0x184fd07c1585e726));
+        assert_eq!(A.sub_mod(B, P).add_mod(B, P), A);
+        assert_eq!(B.sub_mod(A, P), u128::from_parts(0x408457ddf0a00b6d, 0x016f4bfa8165a318));
+        assert_eq!(u128::max_value().add_mod(u128::max_value(), u128::max_value()), u128::zero());
+        assert_eq!(u128::new(5).sub_mod(u128::new(5), u128::one()), u128::zero());
+    }
+
+    #[test]
+    fn test_mul_pow_mod() {
+        assert_eq!(A.mul_mod(B, P), u128::from_parts(0xfbdd95f8f3585804, 0xd268ac80c6dbe1e9));
+        assert_eq!(A.pow_mod(B, P), u128::from_parts(0xdcb309f8e89c4c89, 0xf65b62b86e592083));
+        assert_eq!(A.pow_mod(P - u128::one(), P), u128::one());
+        assert_eq!(u128::zero().pow_mod(u128::zero(), P), u128::one());
+    }
+
+    #[test]
+    fn test_inv_mod() {
+        let inv = u128::from_parts(0xce151fab4a6ab265, 0x997f973aa9b5f527);
+        assert_eq!(A.inv_mod(P), Some(inv));
+        assert_eq!(A.mul_mod(inv, P), u128::one());
+        assert_eq!(u128::zero().inv_mod(P), None);
+        assert_eq!(u128::zero().inv_mod(u128::one()), Some(u128::zero()));
+        assert_eq!(u128::new(6).inv_mod(u128::new(9)), None);
+    }
+
+    #[test]
+    fn test_pow_mod_ct() {
+        assert_eq!(A.pow_mod_ct(B, P), u128::from_parts(0xdcb309f8e89c4c89, 0xf65b62b86e592083));
+        assert_eq!(u128::max_value().pow_mod_ct(u128::new(2), P), u128::new(0x6184));
+        assert_eq!(A.pow_mod_ct(u128::zero(), P), u128::one());
+        assert_eq!(A.pow_mod_ct(B, u128::one()), u128::zero());
+
+        let moduli = [u128::new(2), u128::new(1000000007), u128::from_parts(1, 0), B, u128::max_value()];
+        let exps = [u128::one(), u128::new(0x1234_5678), A, u128::max_value()];
+        for m in &moduli {
+            for e in &exps {
+                assert_eq!(B.pow_mod_ct(*e, *m), B.pow_mod(*e, *m));
+            }
         }
     }
 
-    /// Converts `self` to big endian from the target's endianness.
-    ///
-    /// On big endian this is a no-op. On little endian the bytes are swapped.
-    pub fn to_be(self) -> Self {
-        Self::from_be(self)
+    #[test]
+    #[should_panic(expected="attempted remainder with a divisor of zero")]
+    fn test_mod_by_zero() {
+        u128::one().add_mod(u128::one(), u128::zero());
     }
 
-    /// Converts self to little endian from the target's endianness.
-    ///
-    /// On little endian this is a no-op. On big endian the bytes are swapped.
-    pub fn to_le(self) -> Self {
-        Self::from_le(self)
+    #[test]
+    #[should_panic(expected="attempted remainder with a divisor of zero")]
+    fn test_pow_mod_ct_by_zero() {
+        u128::one().pow_mod_ct(u128::one(), u128::zero());
     }
+}
 
-    /// Raises `self` to the power of `exp`, using exponentiation by squaring.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use extprim::u128::u128;
-    /// use std::str::FromStr;
-    ///
-    /// assert_eq!(u128::new(5).pow(30), u128::from_str("931322574615478515625").unwrap());
-    /// ```
-    pub fn pow(self, exp: u32) -> Self {
-        pow(self, exp as usize)
+//}}}
+
+//{{{ Gcd, Lcm
+
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            mem::swap(&mut a, &mut b);
+        }
+        b -= a;
+        if b == 0 {
+            return a << shift;
+        }
     }
+}
 
-    /// Returns `true` if and only if `self == 2**k` for some `k`.
+impl u128 {
+    /// Computes the greatest common divisor of `self` and `other`.
+    ///
+    /// This uses the binary GCD algorithm (Stein's algorithm), which only needs shifts and
+    /// subtractions, avoiding the expensive 128-bit division. The result of `gcd(0, 0)` is 0.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// assert!(!u128::new(0).is_power_of_two());
-    /// assert!( u128::new(1).is_power_of_two());
-    /// assert!( u128::new(2).is_power_of_two());
-    /// assert!(!u128::new(3).is_power_of_two());
+    /// assert_eq!(u128::new(48).gcd(u128::new(180)), u128::new(12));
+    /// assert_eq!(u128::from_parts(3, 0).gcd(u128::from_parts(0, 1 << 63)), u128::new(1 << 63));
+    /// assert_eq!(u128::zero().gcd(u128::new(7)), u128::new(7));
     /// ```
-    pub fn is_power_of_two(self) -> bool {
-        self != ZERO && (self & self.wrapping_sub(ONE)) == ZERO
+    pub fn gcd(self, other: u128) -> u128 {
+        let mut a = self;
+        let mut b = other;
+        if a == ZERO {
+            return b;
+        }
+        if b == ZERO {
+            return a;
+        }
+        let shift = (a | b).trailing_zeros();
+        a >>= a.trailing_zeros();
+        loop {
+            b >>= b.trailing_zeros();
+            if a.hi == 0 && b.hi == 0 {
+                return u128::new(gcd_u64(a.lo, b.lo)) << shift;
+            }
+            if a > b {
+                mem::swap(&mut a, &mut b);
+            }
+            b -= a;
+            if b == ZERO {
+                return a << shift;
+            }
+        }
     }
 
-    /// Returns the smallest power of two greater than or equal to `self`. Unspecified behavior on
-    /// overflow.
-    ///
-    /// # Examples
+    /// Computes the least common multiple of `self` and `other`. The result is 0 if either
+    /// number is 0.
     ///
-    /// ```
-    /// use extprim::u128::u128;
+    /// # Overflow behavior
     ///
-    /// assert_eq!(u128::zero().next_power_of_two(), u128::new(1));
-    /// assert_eq!(u128::one().next_power_of_two(), u128::new(1));
-    /// assert_eq!(u128::new(384).next_power_of_two(), u128::new(512));
-    /// assert_eq!(u128::new(0x80000000_00000001).next_power_of_two(), u128::from_parts(1, 0));
-    /// assert_eq!(u128::from_parts(0x80000000_00000000, 0).next_power_of_two(), u128::from_parts(0x80000000_00000000, 0));
-    /// ```
-    pub fn next_power_of_two(self) -> Self {
-        let leading_zeros = self.wrapping_sub(ONE).leading_zeros();
-        ONE.wrapping_shl(128 - leading_zeros)
-    }
-
-    /// Returns the smallest power of two greater than or equal to `self`. If the next power of two
-    /// is greater than the type's maximum value, `None` is returned, otherwise the power of two is
-    /// wrapped in `Some`.
+    /// If the result cannot be represented as a `u128`, this function will panic in debug mode and
+    /// wrap around in optimized code, the same as the `*` operator.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```rust
     /// use extprim::u128::u128;
     ///
-    /// assert_eq!(u128::zero().checked_next_power_of_two(), Some(u128::new(1)));
-    /// assert_eq!(u128::one().checked_next_power_of_two(), Some(u128::new(1)));
-    /// assert_eq!(u128::new(384).checked_next_power_of_two(), Some(u128::new(512)));
-    /// assert_eq!(u128::new(0x80000000_00000001).checked_next_power_of_two(), Some(u128::from_parts(1, 0)));
-    /// assert_eq!(u128::from_parts(0x80000000_00000000, 0).checked_next_power_of_two(), Some(u128::from_parts(0x80000000_00000000, 0)));
-    /// assert_eq!(u128::from_parts(0x80000000_00000000, 1).checked_next_power_of_two(), None);
-    /// assert_eq!(u128::max_value().checked_next_power_of_two(), None);
+    /// assert_eq!(u128::new(48).lcm(u128::new(180)), u128::new(720));
+    /// assert_eq!(u128::zero().lcm(u128::new(7)), u128::zero());
     /// ```
-    pub fn checked_next_power_of_two(self) -> Option<Self> {
-        if self == ZERO {
-            Some(ONE)
+    pub fn lcm(self, other: u128) -> u128 {
+        if self == ZERO || other == ZERO {
+            ZERO
         } else {
-            let leading_zeros = self.wrapping_sub(ONE).leading_zeros();
-            ONE.checked_shl(128 - leading_zeros)
+            self / self.gcd(other) * other
         }
     }
 }
 
-impl PrimInt for u128 {
-    fn count_ones(self) -> u32 { Self::count_ones(self) }
-    fn count_zeros(self) -> u32 { Self::count_zeros(self) }
-    fn leading_zeros(self) -> u32 { Self::leading_zeros(self) }
-    fn trailing_zeros(self) -> u32 { Self::trailing_zeros(self) }
-    fn rotate_left(self, shift: u32) -> Self { Self::rotate_left(self, shift) }
-    fn rotate_right(self, shift: u32) -> Self { Self::rotate_right(self, shift) }
-    fn swap_bytes(self) -> Self { Self::swap_bytes(self) }
-    fn from_be(x: Self) -> Self { Self::from_be(x) }
-    fn from_le(x: Self) -> Self { Self::from_le(x) }
-    fn to_be(self) -> Self { Self::to_be(self) }
-    fn to_le(self) -> Self { Self::to_le(self) }
-    fn pow(self, exp: u32) -> Self { Self::pow(self, exp) }
+#[cfg(test)]
+mod gcd_tests {
+    use u128::{u128, ZERO, ONE, MAX};
 
-    fn signed_shl(self, shift: u32) -> Self {
-        self << (shift as usize)
+    #[test]
+    fn test_gcd() {
+        assert_eq!(ZERO.gcd(ZERO), ZERO);
+        assert_eq!(MAX.gcd(ZERO), MAX);
+        assert_eq!(MAX.gcd(MAX), MAX);
+        assert_eq!(MAX.gcd(ONE), ONE);
+        assert_eq!(MAX.gcd(MAX - ONE), ONE);
+        assert_eq!(u128::from_parts(1 << 63, 0).gcd(u128::from_parts(3 << 26, 0)),
+                   u128::from_parts(1 << 26, 0));
+        assert_eq!(u128::from_parts(0x42f099ab23105489, 0x44a18d84bc60ca50)
+                        .gcd(u128::from_parts(0x76571c252122c42e, 0x8cdf8e3b4b75c4d0)),
+                   u128::new(0x10));
+        assert_eq!(u128::from_parts(0x181c8, 0x181c8).gcd(u128::from_parts(0x242b38, 0x242b38)),
+                   u128::from_parts(0x78, 0x78));
+        assert_eq!(u128::new(1071).gcd(u128::new(462)), u128::new(21));
     }
 
-    fn signed_shr(self, shift: u32) -> Self {
-        (i128(self) >> (shift as usize)).0
+    #[test]
+    fn test_lcm() {
+        assert_eq!(ZERO.lcm(ZERO), ZERO);
+        assert_eq!(MAX.lcm(ONE), MAX);
+        assert_eq!(MAX.lcm(MAX), MAX);
+        assert_eq!(u128::from_parts(0x181c8, 0x181c8).lcm(u128::from_parts(0x242b38, 0x242b38)),
+                   u128::from_parts(0x7446f108, 0x7446f108));
+    }
+}
+
+//}}}
+
+//{{{ Casting
+
+fn ldexp(base: f64, exp: u32) -> f64 {
+    // TODO the built-in `ldexp` is deprecated. Find alternate native implementation instead.
+    base * 2.0 * (1u64 << (exp-1)) as f64
+}
+
+impl ToPrimitive for u128 {
+    fn to_i64(&self) -> Option<i64> {
+        if self.hi != 0 {
+            None
+        } else {
+            self.lo.to_i64()
+        }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        if self.hi != 0 {
+            None
+        } else {
+            Some(self.lo)
+        }
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        if self.hi != 0 {
+            let shift_size = 64 - self.hi.leading_zeros();
+            let truncated = (*self >> shift_size).lo as f64;
+            Some(ldexp(truncated, shift_size))
+        } else {
+            self.lo.to_f64()
+        }
+    }
+
+    #[cfg(extprim_has_stable_i128)]
+    fn to_i128(&self) -> Option<I128> {
+        if self.hi < 0x80000000_00000000 {
+            Some(self.as_built_in() as I128)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(extprim_has_stable_i128)]
+    fn to_u128(&self) -> Option<U128> {
+        Some(self.as_built_in())
+    }
+}
+
+impl FromPrimitive for u128 {
+    fn from_u64(n: u64) -> Option<u128> {
+        ToExtraPrimitive::to_u128(&n)
+    }
+
+    fn from_i64(n: i64) -> Option<u128> {
+        ToExtraPrimitive::to_u128(&n)
+    }
+
+    fn from_f64(n: f64) -> Option<u128> {
+        ToExtraPrimitive::to_u128(&n)
+    }
+}
+
+impl ToExtraPrimitive for u128 {
+    fn to_u128(&self) -> Option<u128> {
+        Some(*self)
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        if self.hi >= 0x8000_0000_0000_0000 {
+            None
+        } else {
+            Some(i128(*self))
+        }
+    }
+}
+
+impl FromExtraPrimitive for u128 {
+    fn from_u128(n: u128) -> Option<u128> {
+        Some(n)
+    }
+
+    fn from_i128(n: i128) -> Option<u128> {
+        ToExtraPrimitive::to_u128(&n)
+    }
+}
+
+impl From<u8> for u128 {
+    fn from(arg: u8) -> Self {
+        u128::new(arg as u64)
+    }
+}
+
+impl From<u16> for u128 {
+    fn from(arg: u16) -> Self {
+        u128::new(arg as u64)
+    }
+}
+
+impl From<u32> for u128 {
+    fn from(arg: u32) -> Self {
+        u128::new(arg as u64)
+    }
+}
+
+impl From<u64> for u128 {
+    fn from(arg: u64) -> Self {
+        u128::new(arg)
+    }
+}
+
+#[cfg(extprim_has_stable_i128)]
+impl From<U128> for u128 {
+    fn from(arg: U128) -> Self {
+        u128::from_built_in(arg)
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl From<u128> for U128 {
+    fn from(arg: u128) -> Self {
+        arg.as_built_in()
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialEq<U128> for u128 {
+    fn eq(&self, other: &U128) -> bool {
+        self.as_built_in() == *other
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialEq<u128> for U128 {
+    fn eq(&self, other: &u128) -> bool {
+        *self == other.as_built_in()
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialOrd<U128> for u128 {
+    fn partial_cmp(&self, other: &U128) -> Option<Ordering> {
+        self.as_built_in().partial_cmp(other)
+    }
+}
+
+#[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+impl PartialOrd<u128> for U128 {
+    fn partial_cmp(&self, other: &u128) -> Option<Ordering> {
+        self.partial_cmp(&other.as_built_in())
+    }
+}
+
+impl u128 {
+    /// Converts a 64-bit floating point number to an unsigned 128-bit integer, using the given
+    /// rounding mode.
+    ///
+    /// Returns `None` if the number is NaN, infinite, or if the rounded value is outside of the
+    /// range `0 ..= 2**128 - 1`. Negative numbers which round to zero, including `-0.0`, are
+    /// converted to 0. Integers which are exactly representable in `f32` can be converted by
+    /// casting to `f64` first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::traits::Round;
+    ///
+    /// assert_eq!(u128::from_f64_with(2.5, Round::Floor), Some(u128::new(2)));
+    /// assert_eq!(u128::from_f64_with(2.5, Round::Ceil), Some(u128::new(3)));
+    /// assert_eq!(u128::from_f64_with(2.5, Round::Nearest), Some(u128::new(3)));
+    /// assert_eq!(u128::from_f64_with(2.5, Round::Trunc), Some(u128::new(2)));
+    /// assert_eq!(u128::from_f64_with(-0.5, Round::Ceil), Some(u128::zero()));
+    /// assert_eq!(u128::from_f64_with(-0.5, Round::Floor), None);
+    /// assert_eq!(u128::from_f64_with(2.0f64.powi(128), Round::Floor), None);
+    /// ```
+    pub fn from_f64_with(value: f64, round: Round) -> Option<u128> {
+        if value.is_sign_negative() {
+            // rounding `-x` is the same as negating the reverse rounding of `x`.
+            let reversed = match round {
+                Round::Floor => Round::Ceil,
+                Round::Ceil => Round::Floor,
+                r => r,
+            };
+            return match u128::from_f64_with(-value, reversed) {
+                Some(ZERO) => Some(ZERO),
+                _ => None,
+            };
+        }
+
+        let bits = value.to_bits();
+        let biased_exp = (bits >> 52 & 0x7ff) as i32;
+        let fraction = bits & 0xf_ffff_ffff_ffff;
+        if biased_exp == 0x7ff {
+            // NaN or infinity.
+            return None;
+        }
+        let (mantissa, exp) = if biased_exp == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, biased_exp - 1075)
+        };
+
+        // value = mantissa * 2**exp, where mantissa < 2**53.
+        if exp >= 0 {
+            return if exp >= 128 || exp > 75 && mantissa >> (128 - exp) as u32 != 0 {
+                None
+            } else {
+                Some(u128::new(mantissa) << exp as u32)
+            };
+        }
+
+        let shift = (-exp) as u32;
+        let (int_part, frac_part, half) = if shift >= 64 {
+            // the value is below 2**-11, so it never rounds to nearest 1.
+            (0, mantissa, !0)
+        } else {
+            (mantissa >> shift, mantissa & ((1 << shift) - 1), 1 << (shift - 1))
+        };
+        let round_up = match round {
+            Round::Floor | Round::Trunc => false,
+            Round::Ceil => frac_part != 0,
+            Round::Nearest => frac_part >= half,
+        };
+        Some(u128::new(int_part + round_up as u64))
+    }
+
+    /// Converts a 64-bit floating point number to an unsigned 128-bit integer, rounding towards
+    /// zero and saturating at the bounds.
+    ///
+    /// This matches the behavior of an `as` cast between primitive types: NaN and negative numbers
+    /// are converted to 0, and numbers at or above 2<sup>128</sup> (including infinity) are
+    /// converted to `u128::max_value()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::from_f64_saturating(2.9), u128::new(2));
+    /// assert_eq!(u128::from_f64_saturating(-2.9), u128::zero());
+    /// assert_eq!(u128::from_f64_saturating(1e100), u128::max_value());
+    /// assert_eq!(u128::from_f64_saturating(0.0 / 0.0), u128::zero());
+    /// ```
+    pub fn from_f64_saturating(value: f64) -> u128 {
+        u128::from_f64_with(value, Round::Trunc).unwrap_or(if value > 0.0 { MAX } else { ZERO })
+    }
+
+    /// Converts a 32-bit floating point number to an unsigned 128-bit integer, rounding towards
+    /// zero and saturating at the bounds, like an `as` cast.
+    ///
+    /// See [`from_f64_saturating`](#method.from_f64_saturating) for details.
+    pub fn from_f32_saturating(value: f32) -> u128 {
+        u128::from_f64_saturating(value as f64)
+    }
+
+    /// Decomposes this integer into the 64-bit floating point number nearest to it, as a mantissa
+    /// and a binary exponent.
+    ///
+    /// Returns `(mantissa, exponent, exact)`, where `mantissa * 2**exponent` is `self` rounded to
+    /// 53 significant bits (ties to even), `mantissa < 2**53` and `exponent >= 0`. `exact` is
+    /// `true` if no bits were lost in the rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(12345).to_f64_parts(), (12345, 0, true));
+    /// assert_eq!(u128::from_parts(1, 0).to_f64_parts(), (1 << 52, 12, true));
+    /// assert_eq!(u128::from_parts(1, 1).to_f64_parts(), (1 << 52, 12, false));
+    /// assert_eq!(u128::max_value().to_f64_parts(), (1 << 52, 76, false));
+    /// ```
+    pub fn to_f64_parts(self) -> (u64, i32, bool) {
+        let bit_length = self.bit_length();
+        if bit_length <= 53 {
+            return (self.lo, 0, true);
+        }
+
+        let mut shift = bit_length - 53;
+        let mut mantissa = (self >> shift as usize).lo;
+        let rest = self & ((ONE << shift as usize) - ONE);
+        let half = ONE << (shift - 1) as usize;
+        if rest > half || rest == half && mantissa & 1 != 0 {
+            mantissa += 1;
+            if mantissa == 1 << 53 {
+                mantissa >>= 1;
+                shift += 1;
+            }
+        }
+        (mantissa, shift as i32, rest == ZERO)
+    }
+
+    /// Converts this integer to a 64-bit floating point number, reporting whether any precision
+    /// was lost.
+    ///
+    /// Returns `Ok` if the value is exactly representable as an `f64`, otherwise returns `Err`
+    /// with the nearest `f64` (ties to even).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(1 << 53).to_f64_exact(), Ok(9007199254740992.0));
+    /// assert_eq!(u128::new((1 << 53) + 1).to_f64_exact(), Err(9007199254740992.0));
+    /// assert_eq!(u128::max_value().to_f64_exact(), Err(340282366920938463463374607431768211456.0));
+    /// ```
+    pub fn to_f64_exact(self) -> Result<f64, f64> {
+        let (mantissa, exp, exact) = self.to_f64_parts();
+        // `exp <= 76`, so the power of two is a normal number and the product never overflows.
+        let value = mantissa as f64 * f64::from_bits(((1023 + exp) as u64) << 52);
+        if exact {
+            Ok(value)
+        } else {
+            Err(value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod conv_tests {
+    use u128::{u128, MAX};
+    use num_traits::ToPrimitive;
+
+    #[test]
+    fn test_u128_to_f64() {
+        assert_eq!(u128::new(0).to_f64(), Some(0.0f64));
+        assert_eq!(u128::new(1).to_f64(), Some(1.0f64));
+        assert_eq!(u128::new(2).to_f64(), Some(2.0f64));
+        assert_eq!(MAX.to_f64(), Some(340282366920938463463374607431768211455.0f64));
+    }
+
+    #[test]
+    fn test_to_f64_exact() {
+        assert_eq!(u128::new(0).to_f64_exact(), Ok(0.0));
+        assert_eq!(u128::new(9007199254740993).to_f64_exact(), Err(9007199254740992.0));
+        assert_eq!(u128::new(9007199254740995).to_f64_exact(), Err(9007199254740996.0));
+        assert_eq!(u128::new(!0).to_f64_exact(), Err(18446744073709551616.0));
+        assert_eq!(u128::from_parts(0x10_0000, 0x8000_0000).to_f64_parts(), (1 << 52, 32, false));
+        assert_eq!(u128::from_parts(0x10_0000, 0x8000_0001).to_f64_parts(), ((1 << 52) + 1, 32, false));
+        assert_eq!(u128::from_parts(0x10_0000, 0x1_0000_0000).to_f64_parts(), ((1 << 52) + 1, 32, true));
+        assert_eq!(u128::from_parts(0x10_0000, 0x1_8000_0000).to_f64_parts(), ((1 << 52) + 2, 32, false));
+        for k in 0..128usize {
+            let power = u128::one() << k;
+            let expected = 2.0f64.powi(k as i32);
+            assert_eq!(power.to_f64_exact(), Ok(expected));
+            if k > 0 {
+                assert_eq!((power - u128::one()).to_f64_exact().is_ok(), k <= 53);
+            }
+        }
+        assert_eq!(MAX.to_f64_exact(), Err(340282366920938463463374607431768211456.0));
+    }
+
+    #[test]
+    fn test_from_f64_with() {
+        use traits::Round;
+
+        const ALL: [Round; 4] = [Round::Floor, Round::Ceil, Round::Nearest, Round::Trunc];
+
+        for &round in &ALL {
+            assert_eq!(u128::from_f64_with(0.0, round), Some(u128::zero()));
+            assert_eq!(u128::from_f64_with(-0.0, round), Some(u128::zero()));
+            assert_eq!(u128::from_f64_with(1.0, round), Some(u128::one()));
+            assert_eq!(u128::from_f64_with(-1.0, round), None);
+            assert_eq!(u128::from_f64_with(18446744073709551616.0, round), Some(u128::from_parts(1, 0)));
+            assert_eq!(u128::from_f64_with(340282366920938463463374607431768211456.0, round), None);
+            assert_eq!(u128::from_f64_with(340282366920938425684442744474606501888.0, round),
+                        Some(u128::from_parts(0xfffffffffffff800, 0)));
+            assert_eq!(u128::from_f64_with(1e300, round), None);
+            assert_eq!(u128::from_f64_with(f64::MAX, round), None);
+            assert_eq!(u128::from_f64_with(f64::INFINITY, round), None);
+            assert_eq!(u128::from_f64_with(f64::NEG_INFINITY, round), None);
+            assert_eq!(u128::from_f64_with(f64::NAN, round), None);
+            assert_eq!(u128::from_f64_with(4503599627370497.0, round), Some(u128::new(4503599627370497)));
+        }
+
+        // subnormals and tiny values.
+        assert_eq!(u128::from_f64_with(5e-324, Round::Floor), Some(u128::zero()));
+        assert_eq!(u128::from_f64_with(5e-324, Round::Ceil), Some(u128::one()));
+        assert_eq!(u128::from_f64_with(5e-324, Round::Nearest), Some(u128::zero()));
+        assert_eq!(u128::from_f64_with(-5e-324, Round::Ceil), Some(u128::zero()));
+        assert_eq!(u128::from_f64_with(-5e-324, Round::Floor), None);
+
+        // half-way cases.
+        assert_eq!(u128::from_f64_with(0.5, Round::Nearest), Some(u128::one()));
+        assert_eq!(u128::from_f64_with(0.49999999999999994, Round::Nearest), Some(u128::zero()));
+        assert_eq!(u128::from_f64_with(1.5, Round::Nearest), Some(u128::new(2)));
+        assert_eq!(u128::from_f64_with(2.5, Round::Nearest), Some(u128::new(3)));
+        assert_eq!(u128::from_f64_with(4503599627370495.5, Round::Nearest), Some(u128::new(4503599627370496)));
+        assert_eq!(u128::from_f64_with(4503599627370495.5, Round::Trunc), Some(u128::new(4503599627370495)));
+        assert_eq!(u128::from_f64_with(-0.5, Round::Nearest), None);
+        assert_eq!(u128::from_f64_with(-0.4, Round::Nearest), Some(u128::zero()));
+
+        assert_eq!(u128::from_f64_with(2.75, Round::Floor), Some(u128::new(2)));
+        assert_eq!(u128::from_f64_with(2.75, Round::Ceil), Some(u128::new(3)));
+        assert_eq!(u128::from_f64_with(2.25, Round::Nearest), Some(u128::new(2)));
+        assert_eq!(u128::from_f64_with(2.75, Round::Trunc), Some(u128::new(2)));
+    }
+
+    #[test]
+    fn test_from_float_saturating() {
+        assert_eq!(u128::from_f64_saturating(0.0), u128::zero());
+        assert_eq!(u128::from_f64_saturating(-0.0), u128::zero());
+        assert_eq!(u128::from_f64_saturating(0.99), u128::zero());
+        assert_eq!(u128::from_f64_saturating(-1e300), u128::zero());
+        assert_eq!(u128::from_f64_saturating(18446744073709551616.5), u128::from_parts(1, 0));
+        assert_eq!(u128::from_f64_saturating(340282366920938425684442744474606501888.0),
+                   u128::from_parts(0xffff_ffff_ffff_f800, 0));
+        assert_eq!(u128::from_f64_saturating(340282366920938463463374607431768211456.0), MAX);
+        assert_eq!(u128::from_f64_saturating(f64::INFINITY), MAX);
+        assert_eq!(u128::from_f64_saturating(f64::NEG_INFINITY), u128::zero());
+        assert_eq!(u128::from_f64_saturating(f64::NAN), u128::zero());
+        assert_eq!(u128::from_f32_saturating(f32::MAX), u128::from_parts(0xffff_ff00_0000_0000, 0));
+        assert_eq!(u128::from_f32_saturating(f32::INFINITY), MAX);
+        assert_eq!(u128::from_f32_saturating(16777217.0), u128::new(16777216));
+        assert_eq!(u128::from_f32_saturating(-f32::NAN), u128::zero());
+    }
+
+    #[test]
+    #[cfg(extprim_has_stable_i128)]
+    fn test_from_float_saturating_matches_cast() {
+        use compiler_rt::builtins::U128;
+
+        let values = [0.0, 0.5, 1.0, -1.0, 1e19, 1e38, 3.4e38, 3.5e38, 1e300, -1e300,
+                      f64::INFINITY, f64::NEG_INFINITY, f64::NAN, f64::MIN_POSITIVE, 123456789.987];
+        for &v in &values {
+            assert_eq!(u128::from_f64_saturating(v).as_built_in(), v as U128);
+            assert_eq!(u128::from_f32_saturating(v as f32).as_built_in(), v as f32 as U128);
+        }
+    }
+
+    #[test]
+    #[cfg(extprim_has_stable_i128)]
+    fn test_builtin_u128_to_u128() {
+        assert_eq!(u128::from_built_in(0x35d2c4473082b8c1_8b704240ca1021b8u128), u128::from_parts(0x35d2c4473082b8c1, 0x8b704240ca1021b8));
+        assert_eq!(u128::from_parts(0x35d2c4473082b8c1, 0x8b704240ca1021b8).as_built_in(), 0x35d2c4473082b8c1_8b704240ca1021b8u128);
+    }
+
+    #[test]
+    #[cfg(all(extprim_has_stable_i128, feature="builtin-interop"))]
+    fn test_builtin_u128_interop() {
+        let a = u128::from_parts(0x35d2c4473082b8c1, 0x8b704240ca1021b8);
+        use compiler_rt::builtins::U128;
+
+        let b: U128 = a.into();
+        assert_eq!(b, 0x35d2c4473082b8c1_8b704240ca1021b8u128);
+        assert_eq!(u128::from(b), a);
+        assert!(a == b);
+        assert!(b == a);
+        assert!(a != b + 1);
+        assert!(b + 1 != a);
+        assert!(a < b + 1);
+        assert!(b + 1 > a);
+        assert!(a > b - 1);
+        assert!(b - 1 < a);
+        assert!(a <= b);
+        assert!(b >= a);
+    }
+}
+
+//}}}
+
+//{{{ Constants
+
+impl u128 {
+    /// Returns the smallest unsigned 128-bit integer (0).
+    pub fn min_value() -> u128 { MIN }
+
+    /// Returns the largest unsigned 128-bit integer
+    /// (`340_282_366_920_938_463_463_374_607_431_768_211_455`).
+    pub fn max_value() -> u128 { MAX }
+
+    /// Returns the constant 0.
+    pub fn zero() -> u128 { ZERO }
+
+    /// Returns the constant 1.
+    pub fn one() -> u128 { ONE }
+
+    /// The constant 0.
+    pub const ZERO: u128 = ZERO;
+
+    /// The constant 1.
+    pub const ONE: u128 = ONE;
+}
+
+impl Bounded for u128 {
+    fn min_value() -> Self { MIN }
+    fn max_value() -> Self { MAX }
+}
+
+impl Zero for u128 {
+    fn zero() -> Self { ZERO }
+    fn is_zero(&self) -> bool { *self == ZERO }
+}
+
+impl One for u128 {
+    fn one() -> Self { ONE }
+}
+
+#[cfg(feature="const-traits")]
+impl ConstZero for u128 {
+    const ZERO: Self = ZERO;
+}
+
+#[cfg(feature="const-traits")]
+impl ConstOne for u128 {
+    const ONE: Self = ONE;
+}
+
+//}}}
+
+//{{{ PrimInt
+
+impl u128 {
+    /// Returns the number of ones in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let n = u128::from_str_radix("6f32f1ef8b18a2bc3cea59789c79d441", 16).unwrap();
+    /// assert_eq!(n.count_ones(), 67);
+    /// ```
+    pub fn count_ones(self) -> u32 {
+        self.lo.count_ones() + self.hi.count_ones()
+    }
+
+    /// Returns the number of zeros in the binary representation of `self` (including leading zeros).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let n = u128::from_str_radix("6f32f1ef8b18a2bc3cea59789c79d441", 16).unwrap();
+    /// assert_eq!(n.count_zeros(), 61);
+    /// ```
+    pub fn count_zeros(self) -> u32 {
+        self.lo.count_zeros() + self.hi.count_zeros()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::zero().leading_zeros(), 128);
+    /// assert_eq!(u128::one().leading_zeros(), 127);
+    /// assert_eq!(u128::max_value().leading_zeros(), 0);
+    /// assert_eq!((u128::one() << 24u32).leading_zeros(), 103);
+    /// assert_eq!((u128::one() << 124u32).leading_zeros(), 3);
+    /// ```
+    pub fn leading_zeros(self) -> u32 {
+        if self.hi == 0 {
+            64 + self.lo.leading_zeros()
+        } else {
+            self.hi.leading_zeros()
+        }
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::zero().trailing_zeros(), 128);
+    /// assert_eq!(u128::one().trailing_zeros(), 0);
+    /// assert_eq!((u128::one() << 24u32).trailing_zeros(), 24);
+    /// assert_eq!((u128::one() << 124u32).trailing_zeros(), 124);
+    /// ```
+    pub fn trailing_zeros(self) -> u32 {
+        if self.lo == 0 {
+            64 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+
+    /// Returns the number of bits needed to represent `self`, i.e. the position of the highest set
+    /// bit plus one. Zero needs 0 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::zero().bit_length(), 0);
+    /// assert_eq!(u128::one().bit_length(), 1);
+    /// assert_eq!(u128::new(255).bit_length(), 8);
+    /// assert_eq!(u128::new(256).bit_length(), 9);
+    /// assert_eq!(u128::max_value().bit_length(), 128);
+    /// ```
+    pub fn bit_length(self) -> u32 {
+        128 - self.leading_zeros()
+    }
+
+    /// Shifts the bits to the left by a specified amount, `shift`, wrapping the truncated bits to
+    /// the end of the resulting integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_str_radix("d0cf4b50cfe20765fff4b4e3f741cf6d", 16).unwrap();
+    /// let b = u128::from_str_radix("19e96a19fc40ecbffe969c7ee839edba", 16).unwrap();
+    /// assert_eq!(a.rotate_left(5), b);
+    /// ```
+    pub fn rotate_left(self, shift: u32) -> Self {
+        // Branchless funnel shift. Bit 6 of the shift selects whether the two halves are swapped
+        // (using a mask instead of a branch), and the lower 6 bits are the funnel amount. Shifting
+        // by `(x >> 1) >> (63 - n)` instead of `x >> (64 - n)` avoids an out-of-range shift when
+        // `n == 0`. Bits above 6 are ignored, so shifts >= 128 are taken modulo 128.
+        let n = shift & 63;
+        let swap_mask = 0u64.wrapping_sub((shift >> 6 & 1) as u64);
+        let diff = (self.lo ^ self.hi) & swap_mask;
+        let lo = self.lo ^ diff;
+        let hi = self.hi ^ diff;
+        u128 {
+            lo: lo << n | (hi >> 1) >> (63 - n),
+            hi: hi << n | (lo >> 1) >> (63 - n),
+        }
+    }
+
+    /// Shifts the bits to the right by a specified amount, `shift`, wrapping the truncated bits to
+    /// the beginning of the resulting integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_str_radix("d0cf4b50cfe20765fff4b4e3f741cf6d", 16).unwrap();
+    /// let b = u128::from_str_radix("6e867a5a867f103b2fffa5a71fba0e7b", 16).unwrap();
+    /// assert_eq!(a.rotate_right(5), b);
+    /// ```
+    pub fn rotate_right(self, shift: u32) -> Self {
+        self.rotate_left(128u32.wrapping_sub(shift))
+    }
+
+    /// Reverses the byte order of the integer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_str_radix("0123456789abcdef1112223334445556", 16).unwrap();
+    /// let b = u128::from_str_radix("5655443433221211efcdab8967452301", 16).unwrap();
+    /// assert_eq!(a.swap_bytes(), b);
+    /// ```
+    pub fn swap_bytes(self) -> Self {
+        u128 { lo: self.hi.swap_bytes(), hi: self.lo.swap_bytes() }
+    }
+
+    /// Converts an integer from big endian to the target's endianness.
+    ///
+    /// On big endian this is a no-op. On little endian the bytes are swapped.
+    pub fn from_be(x: Self) -> Self {
+        if cfg!(target_endian="big") {
+            x
+        } else {
+            x.swap_bytes()
+        }
+    }
+
+    /// Converts an integer from little endian to the target's endianness.
+    ///
+    /// On little endian this is a no-op. On big endian the bytes are swapped.
+    pub fn from_le(x: Self) -> Self {
+        if cfg!(target_endian="little") {
+            x
+        } else {
+            x.swap_bytes()
+        }
+    }
+
+    /// Converts `self` to big endian from the target's endianness.
+    ///
+    /// On big endian this is a no-op. On little endian the bytes are swapped.
+    pub fn to_be(self) -> Self {
+        Self::from_be(self)
+    }
+
+    /// Converts self to little endian from the target's endianness.
+    ///
+    /// On little endian this is a no-op. On big endian the bytes are swapped.
+    pub fn to_le(self) -> Self {
+        Self::from_le(self)
+    }
+
+    /// Returns the memory representation of this integer as a byte array in big-endian (network)
+    /// byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10);
+    /// assert_eq!(a.to_be_bytes(), [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    /// ```
+    pub fn to_be_bytes(self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let word = if i < 8 { self.hi } else { self.lo };
+            *byte = (word >> (56 - 8 * (i % 8))) as u8;
+        }
+        bytes
+    }
+
+    /// Returns the memory representation of this integer as a byte array in little-endian byte
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10);
+    /// assert_eq!(a.to_le_bytes(), [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1]);
+    /// ```
+    pub fn to_le_bytes(self) -> [u8; 16] {
+        self.swap_bytes().to_be_bytes()
+    }
+
+    /// Creates an integer from its representation as a byte array in big-endian byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let bytes = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+    /// assert_eq!(u128::from_be_bytes(bytes), u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10));
+    /// ```
+    pub fn from_be_bytes(bytes: [u8; 16]) -> u128 {
+        let mut hi = 0;
+        let mut lo = 0;
+        for &byte in &bytes[..8] {
+            hi = hi << 8 | byte as u64;
+        }
+        for &byte in &bytes[8..] {
+            lo = lo << 8 | byte as u64;
+        }
+        u128 { lo: lo, hi: hi }
+    }
+
+    /// Creates an integer from its representation as a byte array in little-endian byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let bytes = [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+    /// assert_eq!(u128::from_le_bytes(bytes), u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10));
+    /// ```
+    pub fn from_le_bytes(bytes: [u8; 16]) -> u128 {
+        u128::from_be_bytes(bytes).swap_bytes()
+    }
+
+    /// Returns the two 64-bit words of this integer, the least significant word first.
+    ///
+    /// In this order, bit `i` of the number is bit `i % 64` of word `i / 64`, which is the layout
+    /// expected by bit-collection crates using `u64` storage with least-significant-bit-first
+    /// ordering. For instance, with [bitvec](https://crates.io/crates/bitvec) a `u128` can be
+    /// viewed as a bit slice without copying bit by bit:
+    ///
+    /// ```ignore
+    /// use bitvec::prelude::*;
+    ///
+    /// let bits = BitArray::<[u64; 2], Lsb0>::new(value.to_le_words());
+    /// assert_eq!(bits[127], value.leading_zeros() == 0);
+    /// let value = u128::from_le_words(bits.into_inner());
+    /// ```
+    ///
+    /// With the `bitvec` feature, `BitArray<[u64; 2], Lsb0>` also implements `From<u128>`, and
+    /// `u128` implements `From<BitArray<[u64; 2], Lsb0>>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_parts(0x0102030405060708, 0x090a0b0c0d0e0f10);
+    /// assert_eq!(a.to_le_words(), [0x090a0b0c0d0e0f10, 0x0102030405060708]);
+    /// ```
+    pub fn to_le_words(self) -> [u64; 2] {
+        [self.lo, self.hi]
+    }
+
+    /// Creates an integer from its two 64-bit words, the least significant word first. This is the
+    /// inverse of [`to_le_words`](#method.to_le_words).
+    pub fn from_le_words(words: [u64; 2]) -> u128 {
+        u128 { lo: words[0], hi: words[1] }
+    }
+
+    /// Maps `self` to an `i128` by flipping the sign bit, so that the order of the results is the
+    /// same as the order of the inputs. This is the inverse of
+    /// [`i128::to_ordered_u128`](../i128/struct.i128.html#method.to_ordered_u128).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::i128::i128;
+    ///
+    /// assert_eq!(u128::zero().to_ordered_i128(), i128::min_value());
+    /// assert_eq!(u128::max_value().to_ordered_i128(), i128::max_value());
+    /// assert_eq!(i128::new(-5).to_ordered_u128().to_ordered_i128(), i128::new(-5));
+    /// ```
+    pub fn to_ordered_i128(self) -> i128 {
+        i128(u128 { lo: self.lo, hi: self.hi ^ (1 << 63) })
+    }
+
+    /// Returns a byte string whose lexicographical order is the same as the numerical order of
+    /// `self`, suitable as a key in byte-ordered storage. This is the same as
+    /// [`to_be_bytes`](#method.to_be_bytes).
+    pub fn to_sortable_key(self) -> [u8; 16] {
+        self.to_be_bytes()
+    }
+
+    /// Recovers a number from a key created by [`to_sortable_key`](#method.to_sortable_key).
+    pub fn from_sortable_key(key: [u8; 16]) -> u128 {
+        u128::from_be_bytes(key)
+    }
+
+    /// Returns a mask with the highest `n` bits set and the rest cleared.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::prefix_mask(0), u128::zero());
+    /// assert_eq!(u128::prefix_mask(4), u128::from_parts(0xf0000000_00000000, 0));
+    /// assert_eq!(u128::prefix_mask(128), u128::max_value());
+    /// ```
+    pub fn prefix_mask(n: u32) -> Self {
+        assert!(n <= 128, "mask length must be at most 128");
+        !u128::suffix_mask(128 - n)
+    }
+
+    /// Returns a mask with the lowest `n` bits set and the rest cleared.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `n` is greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::suffix_mask(0), u128::zero());
+    /// assert_eq!(u128::suffix_mask(68), u128::from_parts(0xf, !0));
+    /// assert_eq!(u128::suffix_mask(128), u128::max_value());
+    /// ```
+    pub fn suffix_mask(n: u32) -> Self {
+        assert!(n <= 128, "mask length must be at most 128");
+        if n == 0 {
+            ZERO
+        } else {
+            MAX >> (128 - n)
+        }
+    }
+
+    /// Returns the number of leading bits `self` and `other` have in common, i.e. the length of
+    /// their longest common prefix. Equal numbers share all 128 bits.
+    ///
+    /// This is the depth at which the two keys diverge in a binary trie, or the longest prefix
+    /// length of an IPv6 network containing both addresses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_parts(0x2001_0db8_0000_0000, 1);
+    /// let b = u128::from_parts(0x2001_0db8_8000_0000, 1);
+    /// assert_eq!(a.common_prefix_len(b), 32);
+    /// assert_eq!(a.common_prefix_len(a), 128);
+    /// assert_eq!(u128::zero().common_prefix_len(u128::max_value()), 0);
+    /// ```
+    pub fn common_prefix_len(self, other: u128) -> u32 {
+        (self ^ other).leading_zeros()
+    }
+
+    /// Returns the longest common prefix of `self` and `other`, with all bits after the prefix
+    /// cleared.
+    ///
+    /// The result, together with [`common_prefix_len`](#method.common_prefix_len), describes the
+    /// smallest aligned block containing both numbers, e.g. the IPv6 network aggregating two
+    /// addresses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let a = u128::from_parts(0x2001_0db8_0000_0000, 1);
+    /// let b = u128::from_parts(0x2001_0db8_8000_0000, 1);
+    /// assert_eq!(a.common_prefix(b), u128::from_parts(0x2001_0db8_0000_0000, 0));
+    /// assert_eq!(a.common_prefix(a), a);
+    /// ```
+    pub fn common_prefix(self, other: u128) -> u128 {
+        self & u128::prefix_mask(self.common_prefix_len(other))
+    }
+
+    /// Keeps the lowest `bits` bits of `self` and clears the rest, i.e. truncates `self` to a
+    /// `bits`-bit unsigned number.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `bits` is greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(0x1234).truncate_to(8), u128::new(0x34));
+    /// assert_eq!(u128::max_value().truncate_to(0), u128::zero());
+    /// ```
+    pub fn truncate_to(self, bits: u32) -> u128 {
+        assert!(bits <= 128, "bit width must be at most 128");
+        self & u128::suffix_mask(bits)
+    }
+
+    /// Treats the lowest `bits` bits of `self` as an unsigned `bits`-bit number, and zero-extends
+    /// it to 128 bits. The higher bits of `self` are ignored.
+    ///
+    /// This is the same as [`truncate_to`](#method.truncate_to), named after the operation on
+    /// narrower registers. The signed counterpart is
+    /// [`i128::sign_extend_from`](../i128/struct.i128.html#method.sign_extend_from).
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `bits` is greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(0xff80).zero_extend_from(8), u128::new(0x80));
+    /// ```
+    pub fn zero_extend_from(self, bits: u32) -> u128 {
+        self.truncate_to(bits)
+    }
+
+    /// Raises `self` to the power of `exp`, using exponentiation by squaring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(u128::new(5).pow(30), u128::from_str("931322574615478515625").unwrap());
+    /// ```
+    pub fn pow(self, exp: u32) -> Self {
+        pow(self, exp as usize)
+    }
+
+    /// Returns `true` if and only if `self == 2**k` for some `k`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert!(!u128::new(0).is_power_of_two());
+    /// assert!( u128::new(1).is_power_of_two());
+    /// assert!( u128::new(2).is_power_of_two());
+    /// assert!(!u128::new(3).is_power_of_two());
+    /// ```
+    pub fn is_power_of_two(self) -> bool {
+        self != ZERO && (self & self.wrapping_sub(ONE)) == ZERO
+    }
+
+    /// Returns the smallest power of two greater than or equal to `self`. Unspecified behavior on
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::zero().next_power_of_two(), u128::new(1));
+    /// assert_eq!(u128::one().next_power_of_two(), u128::new(1));
+    /// assert_eq!(u128::new(384).next_power_of_two(), u128::new(512));
+    /// assert_eq!(u128::new(0x80000000_00000001).next_power_of_two(), u128::from_parts(1, 0));
+    /// assert_eq!(u128::from_parts(0x80000000_00000000, 0).next_power_of_two(), u128::from_parts(0x80000000_00000000, 0));
+    /// ```
+    pub fn next_power_of_two(self) -> Self {
+        let leading_zeros = self.wrapping_sub(ONE).leading_zeros();
+        ONE.wrapping_shl(128 - leading_zeros)
+    }
+
+    /// Returns the smallest power of two greater than or equal to `self`. If the next power of two
+    /// is greater than the type's maximum value, `None` is returned, otherwise the power of two is
+    /// wrapped in `Some`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::zero().checked_next_power_of_two(), Some(u128::new(1)));
+    /// assert_eq!(u128::one().checked_next_power_of_two(), Some(u128::new(1)));
+    /// assert_eq!(u128::new(384).checked_next_power_of_two(), Some(u128::new(512)));
+    /// assert_eq!(u128::new(0x80000000_00000001).checked_next_power_of_two(), Some(u128::from_parts(1, 0)));
+    /// assert_eq!(u128::from_parts(0x80000000_00000000, 0).checked_next_power_of_two(), Some(u128::from_parts(0x80000000_00000000, 0)));
+    /// assert_eq!(u128::from_parts(0x80000000_00000000, 1).checked_next_power_of_two(), None);
+    /// assert_eq!(u128::max_value().checked_next_power_of_two(), None);
+    /// ```
+    pub fn checked_next_power_of_two(self) -> Option<Self> {
+        if self == ZERO {
+            Some(ONE)
+        } else {
+            let leading_zeros = self.wrapping_sub(ONE).leading_zeros();
+            ONE.checked_shl(128 - leading_zeros)
+        }
+    }
+
+    /// Returns `true` if `self` is a multiple of `align`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert!(u128::from_parts(1, 0x1000).is_aligned_to(u128::new(0x1000)));
+    /// assert!(!u128::from_parts(1, 0x1800).is_aligned_to(u128::new(0x1000)));
+    /// assert!(u128::zero().is_aligned_to(u128::from_parts(1 << 63, 0)));
+    /// ```
+    pub fn is_aligned_to(self, align: u128) -> bool {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        self & align.wrapping_sub(ONE) == ZERO
+    }
+
+    /// Rounds `self` down to the nearest multiple of `align`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::from_parts(1, 0x1fff).align_down(u128::new(0x1000)), u128::from_parts(1, 0x1000));
+    /// assert_eq!(u128::max_value().align_down(u128::from_parts(1, 0)), u128::from_parts(!0, 0));
+    /// ```
+    pub fn align_down(self, align: u128) -> u128 {
+        self.checked_align_down(align).expect("alignment must be a power of two")
+    }
+
+    /// Rounds `self` up to the nearest multiple of `align`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two, or if the result overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::from_parts(1, 0x1001).align_up(u128::new(0x1000)), u128::from_parts(1, 0x2000));
+    /// assert_eq!(u128::new(0x1000).align_up(u128::new(0x1000)), u128::new(0x1000));
+    /// assert_eq!(u128::zero().align_up(u128::new(0x1000)), u128::zero());
+    /// ```
+    pub fn align_up(self, align: u128) -> u128 {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        self.checked_align_up(align).expect("arithmetic operation overflowed")
+    }
+
+    /// Rounds `self` down to the nearest multiple of `align`. Returns `None` if `align` is not a
+    /// power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(100).checked_align_down(u128::new(16)), Some(u128::new(96)));
+    /// assert_eq!(u128::new(100).checked_align_down(u128::new(12)), None);
+    /// assert_eq!(u128::new(100).checked_align_down(u128::zero()), None);
+    /// ```
+    pub fn checked_align_down(self, align: u128) -> Option<u128> {
+        if align.is_power_of_two() {
+            Some(self & !align.wrapping_sub(ONE))
+        } else {
+            None
+        }
+    }
+
+    /// Rounds `self` up to the nearest multiple of `align`. Returns `None` if `align` is not a
+    /// power of two, or if the result overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::new(100).checked_align_up(u128::new(16)), Some(u128::new(112)));
+    /// assert_eq!(u128::new(100).checked_align_up(u128::new(12)), None);
+    /// assert_eq!(u128::max_value().checked_align_up(u128::new(2)), None);
+    /// assert_eq!(u128::max_value().checked_align_up(u128::one()), Some(u128::max_value()));
+    /// ```
+    pub fn checked_align_up(self, align: u128) -> Option<u128> {
+        if align.is_power_of_two() {
+            let mask = align.wrapping_sub(ONE);
+            self.checked_add(mask).map(|x| x & !mask)
+        } else {
+            None
+        }
+    }
+}
+
+impl PrimInt for u128 {
+    fn count_ones(self) -> u32 { Self::count_ones(self) }
+    fn count_zeros(self) -> u32 { Self::count_zeros(self) }
+    fn leading_zeros(self) -> u32 { Self::leading_zeros(self) }
+    fn trailing_zeros(self) -> u32 { Self::trailing_zeros(self) }
+    fn rotate_left(self, shift: u32) -> Self { Self::rotate_left(self, shift) }
+    fn rotate_right(self, shift: u32) -> Self { Self::rotate_right(self, shift) }
+    fn swap_bytes(self) -> Self { Self::swap_bytes(self) }
+    fn from_be(x: Self) -> Self { Self::from_be(x) }
+    fn from_le(x: Self) -> Self { Self::from_le(x) }
+    fn to_be(self) -> Self { Self::to_be(self) }
+    fn to_le(self) -> Self { Self::to_le(self) }
+    fn pow(self, exp: u32) -> Self { Self::pow(self, exp) }
+
+    fn signed_shl(self, shift: u32) -> Self {
+        self << (shift as usize)
+    }
+
+    fn signed_shr(self, shift: u32) -> Self {
+        (i128(self) >> (shift as usize)).0
     }
 
     fn unsigned_shl(self, shift: u32) -> Self {
         self << (shift as usize)
     }
 
-    fn unsigned_shr(self, shift: u32) -> Self {
-        self >> (shift as usize)
+    fn unsigned_shr(self, shift: u32) -> Self {
+        self >> (shift as usize)
+    }
+}
+
+impl Unsigned for u128 {
+}
+
+#[cfg(test)]
+mod prim_int_tests {
+    use std::u64;
+    use u128::{u128, MAX, ZERO, ONE};
+
+    #[test]
+    fn test_bit_length() {
+        assert_eq!(ZERO.bit_length(), 0);
+        for k in 0..128u32 {
+            let power = ONE << k as usize;
+            assert_eq!(power.bit_length(), k + 1);
+            assert_eq!((power - ONE).bit_length(), k);
+            assert_eq!((power | ONE).bit_length(), k + 1);
+        }
+        assert_eq!(MAX.bit_length(), 128);
+        assert_eq!(u128::from_parts(1, u64::MAX).bit_length(), 65);
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(0),
+                    u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152));
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(1),
+                    u128::from_parts(0xf2e3c00d872bafb, 0xa9f84ed62d9478a9));
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(3),
+                    u128::from_parts(0x43cb8f00361caebe, 0xea7e13b58b651e2a));
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(64),
+                    u128::from_parts(0x53f09dac5b28f152, 0x1e5c7801b0e575f7));
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(120),
+                    u128::from_parts(0x5c7801b0e575f753, 0xf09dac5b28f1521e));
+
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(128),
+                    u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152));
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(129),
+                    u128::from_parts(0xf2e3c00d872bafb, 0xa9f84ed62d9478a9));
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_left(!0),
+                    u128::from_parts(0xf2e3c00d872bafb, 0xa9f84ed62d9478a9));
+        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_left(64),
+                    u128::from_parts(0x53f09dac5b28f152, 0x1e5c7801b0e575f7));
+        assert_eq!(u128::from_parts(0x8000000000000000, 1).rotate_left(1),
+                    u128::from_parts(0, 3));
+        for i in 0..256 {
+            let a = u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152);
+            assert_eq!(a.rotate_left(i).rotate_right(i), a);
+            assert_eq!(a.rotate_left(i), a << (i & 127) | a >> ((128 - (i & 127)) & 127));
+        }
+    }
+
+    #[test]
+    fn test_swap_bytes() {
+        assert_eq!(u128::from_parts(0xf0d6891695897d01, 0xb6e2f3a4b065e277).swap_bytes(),
+                    u128::from_parts(0x77e265b0a4f3e2b6, 0x017d89951689d6f0));
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        assert_eq!(u128::from_parts(1, 0).leading_zeros(), 63);
+        assert_eq!(u128::from_parts(1, 4).leading_zeros(), 63);
+        assert_eq!(u128::from_parts(0, 1).leading_zeros(), 127);
+        assert_eq!(u128::from_parts(0, 0).leading_zeros(), 128);
+    }
+
+    #[test]
+    fn test_trailing_zeros() {
+        assert_eq!(u128::from_parts(1, 0).trailing_zeros(), 64);
+        assert_eq!(u128::from_parts(3, 0).trailing_zeros(), 64);
+        assert_eq!(u128::from_parts(1, 4).trailing_zeros(), 2);
+        assert_eq!(u128::from_parts(0, 1).trailing_zeros(), 0);
+        assert_eq!(u128::from_parts(0, 0).trailing_zeros(), 128);
+    }
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(Some(u128::from_parts(u64::MAX, 0)),
+                    u128::from_parts(u64::MAX-1, u64::MAX)
+                        .checked_add(u128::new(1)));
+        assert_eq!(Some(u128::from_parts(u64::MAX, 0)), u128::new(1)
+                        .checked_add(u128::from_parts(u64::MAX-1, u64::MAX)));
+        assert_eq!(None, u128::from_parts(u64::MAX, 1)
+                        .checked_add(u128::from_parts(u64::MAX, 2)));
+        assert_eq!(None, MAX.checked_add(u128::new(1)));
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        assert_eq!(None, ZERO.checked_sub(ONE));
+        assert_eq!(None, ZERO.checked_sub(MAX));
+        assert_eq!(None, ONE.checked_sub(MAX));
+        assert_eq!(Some(ONE), ONE.checked_sub(ZERO));
+        assert_eq!(Some(MAX), MAX.checked_sub(ZERO));
+        assert_eq!(Some(MAX-ONE), MAX.checked_sub(ONE));
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        assert_eq!(Some(ONE), ONE.checked_mul(ONE));
+        assert_eq!(Some(MAX), MAX.checked_mul(ONE));
+        assert_eq!(None, MAX.checked_mul(MAX));
+        assert_eq!(None, MAX.checked_mul(u128::new(2)));
+        assert_eq!(None, u128::from_parts(1, 0).checked_mul(u128::from_parts(1, 0)));
+        let res = u128::from_parts(u64::MAX-1, 1);
+        assert_eq!(Some(res), u128::new(u64::MAX).checked_mul(u128::new(u64::MAX)));
+    }
+
+    #[test]
+    fn test_checked_div() {
+        assert_eq!(Some(ONE), ONE.checked_div(ONE));
+        assert_eq!(Some(MAX), MAX.checked_div(ONE));
+        assert_eq!(Some(ZERO), ONE.checked_div(MAX));
+        assert_eq!(Some(ZERO), ZERO.checked_div(MAX));
+        assert_eq!(None, ONE.checked_div(ZERO));
+        assert_eq!(None, MAX.checked_div(ZERO));
+    }
+
+    #[test]
+    fn test_checked_next_power_of_two() {
+        assert_eq!(u128::from_parts(0, 0x80000000_00000000).next_power_of_two(),
+                    u128::from_parts(0, 0x80000000_00000000));
+        assert_eq!(u128::from_parts(0, 0x80000000_00000001).next_power_of_two(),
+                    u128::from_parts(1, 0));
+        assert_eq!(u128::from_parts(1, 0).next_power_of_two(),
+                    u128::from_parts(1, 0));
+        assert_eq!(u128::from_parts(1, 1).next_power_of_two(),
+                    u128::from_parts(2, 0));
+        assert_eq!(u128::from_parts(0x80000000_00000000, 0).checked_next_power_of_two(),
+                    Some(u128::from_parts(0x80000000_00000000, 0)));
+        assert_eq!(u128::from_parts(0x80000000_00000000, 1).checked_next_power_of_two(),
+                    None);
+        assert_eq!(u128::from_parts(0x80000000_00000001, 0).checked_next_power_of_two(),
+                    None);
+
+    }
+
+    #[test]
+    fn test_align() {
+        let page = u128::new(4096);
+        let top = u128::from_parts(1 << 63, 0);
+        for &(x, down, up) in &[
+            (ZERO, ZERO, ZERO),
+            (ONE, ZERO, page),
+            (page, page, page),
+            (u128::from_parts(7, 4097), u128::from_parts(7, 4096), u128::from_parts(7, 8192)),
+            (u128::from_parts(7, !0), u128::from_parts(7, !4095), u128::from_parts(8, 0)),
+        ] {
+            assert_eq!(x.align_down(page), down);
+            assert_eq!(x.align_up(page), up);
+            assert_eq!(x.is_aligned_to(page), x == down);
+            assert!(down.is_aligned_to(page) && up.is_aligned_to(page));
+        }
+        assert_eq!(MAX.align_down(top), top);
+        assert_eq!(ONE.align_up(top), top);
+        assert_eq!((top + ONE).checked_align_up(top), None);
+        assert_eq!(MAX.checked_align_up(page), None);
+        assert_eq!(MAX.checked_align_down(MAX), None);
+        assert_eq!(MAX.checked_align_up(ZERO), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "alignment must be a power of two")]
+    fn test_align_not_power_of_two() {
+        u128::new(100).align_up(u128::new(24));
+    }
+
+    #[test]
+    fn test_masks() {
+        for n in 0..129 {
+            assert_eq!(u128::prefix_mask(n).count_ones(), n);
+            assert_eq!((!u128::prefix_mask(n)).leading_zeros(), n);
+            assert_eq!(u128::suffix_mask(n).count_ones(), n);
+            assert_eq!((!u128::suffix_mask(n)).trailing_zeros(), n);
+            assert_eq!(u128::prefix_mask(n) | u128::suffix_mask(128 - n), MAX);
+        }
+        assert_eq!(u128::prefix_mask(64), u128::from_parts(u64::MAX, 0));
+        assert_eq!(u128::suffix_mask(64), u128::from_parts(0, u64::MAX));
+        assert_eq!(u128::suffix_mask(1), ONE);
+        assert_eq!(u128::prefix_mask(127), !ONE);
+    }
+
+    #[test]
+    #[should_panic(expected="mask length must be at most 128")]
+    fn test_prefix_mask_too_long() {
+        u128::prefix_mask(129);
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        let value = u128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+        for n in 0..128 {
+            let other = value ^ (ONE << (127 - n) as usize);
+            assert_eq!(value.common_prefix_len(other), n);
+            assert_eq!(other.common_prefix_len(value), n);
+            assert_eq!(value.common_prefix(other), value & u128::prefix_mask(n));
+            assert_eq!(value.common_prefix(other), other.common_prefix(value));
+        }
+        assert_eq!(value.common_prefix_len(value), 128);
+        assert_eq!(value.common_prefix(value), value);
+        assert_eq!(ZERO.common_prefix(MAX), ZERO);
+        assert_eq!(u128::from_parts(5, 0).common_prefix_len(u128::from_parts(5, 1)), 127);
+        assert_eq!(u128::from_parts(5, 0).common_prefix(u128::from_parts(5, u64::MAX)), u128::from_parts(5, 0));
+    }
+
+    #[test]
+    fn test_truncate_zero_extend() {
+        let value = u128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+        for bits in 0..129 {
+            assert_eq!(value.truncate_to(bits), value & u128::suffix_mask(bits));
+            assert_eq!(value.zero_extend_from(bits), value.truncate_to(bits));
+            assert_eq!(MAX.truncate_to(bits).count_ones(), bits);
+        }
+        assert_eq!(value.truncate_to(64), u128::new(0xfedc_ba98_7654_3210));
+        assert_eq!(value.truncate_to(68), u128::from_parts(0xf, 0xfedc_ba98_7654_3210));
+        assert_eq!(value.truncate_to(128), value);
+    }
+
+    #[test]
+    #[should_panic(expected="bit width must be at most 128")]
+    fn test_truncate_too_wide() {
+        ONE.truncate_to(129);
+    }
+
+    #[test]
+    fn test_sortable_key() {
+        let values = [ZERO, ONE, u128::from_parts(0, u64::MAX), u128::from_parts(1, 0),
+                      u128::from_parts(0x8000000000000000, 0), MAX - ONE, MAX];
+        for w in values.windows(2) {
+            assert!(w[0].to_ordered_i128() < w[1].to_ordered_i128());
+            assert!(w[0].to_sortable_key() < w[1].to_sortable_key());
+        }
+        for v in &values {
+            assert_eq!(v.to_ordered_i128().to_ordered_u128(), *v);
+            assert_eq!(u128::from_sortable_key(v.to_sortable_key()), *v);
+        }
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a = u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152);
+        let be = [0x1e, 0x5c, 0x78, 0x01, 0xb0, 0xe5, 0x75, 0xf7, 0x53, 0xf0, 0x9d, 0xac, 0x5b, 0x28, 0xf1, 0x52];
+        let mut le = be;
+        le.reverse();
+        assert_eq!(a.to_be_bytes(), be);
+        assert_eq!(a.to_le_bytes(), le);
+        assert_eq!(u128::from_be_bytes(be), a);
+        assert_eq!(u128::from_le_bytes(le), a);
+        assert_eq!(MAX.to_be_bytes(), [0xff; 16]);
+        assert_eq!(u128::from_le_bytes([0; 16]), ZERO);
+        assert_eq!(u128::from_le_bytes([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]), ONE);
+    }
+
+    #[test]
+    fn test_words() {
+        let a = u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152);
+        assert_eq!(a.to_le_words(), [0x53f09dac5b28f152, 0x1e5c7801b0e575f7]);
+        assert_eq!(u128::from_le_words(a.to_le_words()), a);
+        assert_eq!(ONE.to_le_words(), [1, 0]);
+        for i in 0..128u32 {
+            let words = (ONE << i).to_le_words();
+            assert_eq!(words[(i / 64) as usize], 1 << (i % 64));
+        }
+    }
+}
+
+#[cfg(all(test, extprim_channel="unstable"))]
+mod checked_add_sub_bench {
+    use u128::u128;
+    use test::{Bencher, black_box};
+
+    const BENCH_CHECKED_ADD_SUB: &'static [u128] = &[
+        u128 { lo: 8530639231766041497, hi: 1287710968871074399 },
+        u128 { lo: 1203542656178406941, hi: 17699966409461566340 },
+        u128 { lo: 718458371035876551, hi: 3606247509203879903 },
+        u128 { lo: 9776046594219398139, hi: 11242044896228553946 },
+        u128 { lo: 7902474877314354323, hi: 15571658655527718712 },
+        u128 { lo: 12666717328207407901, hi: 18395053205720380381 },
+        u128 { lo: 17339836091522731855, hi: 15731019889221707237 },
+        u128 { lo: 8366128025082480321, hi: 13984191269538716594 },
+        u128 { lo: 8593645006461074455, hi: 10189081980804969201 },
+        u128 { lo: 8264027155501625330, hi: 6198464561866207623 },
+        u128 { lo: 10849132074109635036, hi: 5777302818880052808 },
+        u128 { lo: 8053806942953838280, hi: 4617639587817452744 },
+        u128 { lo: 7575409236673560956, hi: 10773137480165156891 },
+        u128 { lo: 4323210863932108621, hi: 16058751318664008901 },
+        u128 { lo: 336314576898396552, hi: 8743495691718489785 },
+        u128 { lo: 6527874161908570477, hi: 926686061690459595 },
+        u128 { lo: 15442937728615642560, hi: 2666553580477360520 },
+        u128 { lo: 11855805362816810591, hi: 17643219502201004064 },
+        u128 { lo: 16313274500479459547, hi: 5436651574417345289 },
+        u128 { lo: 15008613641935618684, hi: 12105224025714335156 },
+    ];
+
+    #[bench]
+    fn bench_checked_add(bencher: &mut Bencher) {
+        bencher.iter(|| {
+            for a in BENCH_CHECKED_ADD_SUB {
+                for b in BENCH_CHECKED_ADD_SUB {
+                    black_box(a.checked_add(*b));
+                }
+            }
+        })
+    }
+
+    #[bench]
+    fn bench_checked_sub(bencher: &mut Bencher) {
+        bencher.iter(|| {
+            for a in BENCH_CHECKED_ADD_SUB {
+                for b in BENCH_CHECKED_ADD_SUB {
+                    black_box(a.checked_sub(*b));
+                }
+            }
+        })
+    }
+}
+
+
+//}}}
+
+//{{{ FromStr, FromStrRadix
+
+/// Parses a string of digits in the given base, without any sign.
+pub(crate) fn parse_digits(src: &str, radix: u32) -> Result<u128, ParseIntError> {
+    if src.is_empty() {
+        return Err(error::empty());
+    }
+
+    let mut result = ZERO;
+    let radix64 = radix as u64;
+
+    for c in src.chars() {
+        let digit = c.to_digit(radix).ok_or_else(error::invalid_digit)?;
+        let int_result = result.checked_mul_64(radix64).ok_or_else(error::overflow)?;
+        let digit128 = u128::new(digit as u64);
+        result = int_result.checked_add(digit128).ok_or_else(error::overflow)?;
+    }
+
+    Ok(result)
+}
+
+/// Computes `10**exp`, or `None` if it overflows.
+pub(crate) fn checked_pow10(exp: u32) -> Option<u128> {
+    let mut result = ONE;
+    for _ in 0..exp {
+        result = result.checked_mul_64(10)?;
+    }
+    Some(result)
+}
+
+/// Parses a decimal number without any sign, such as `"123.45"`, into an unscaled value with the
+/// given scale. Returns an invalid digit error if the number has significant digits below
+/// 10<sup>-scale</sup>, e.g. fractional digits beyond a positive scale, or non-zero units with a
+/// negative scale.
+pub(crate) fn parse_decimal_digits(src: &str, scale: i8) -> Result<u128, ParseIntError> {
+    let (int_part, frac_part) = match src.find('.') {
+        Some(i) => (&src[..i], &src[i + 1..]),
+        None => (src, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(error::invalid_digit());
+    }
+
+    // Trailing zeros of the fractional part do not change the value, and may be beyond the
+    // scale.
+    let mut frac_len = frac_part.len();
+    while frac_len > 0 && frac_part.as_bytes()[frac_len - 1] == b'0' {
+        frac_len -= 1;
+    }
+    let frac_part = &frac_part[..frac_len];
+
+    // With a negative shift, the last `-shift` digits are below the scale. They are dropped before
+    // accumulating, so that they cannot overflow, and must be zeros.
+    let shift = scale as i32 - frac_part.len() as i32;
+    let num_digits = int_part.len() + frac_part.len();
+    let num_kept = if shift < 0 { num_digits.saturating_sub((-shift) as usize) } else { num_digits };
+
+    let mut result = ZERO;
+    for (i, b) in int_part.bytes().chain(frac_part.bytes()).enumerate() {
+        let digit = (b as char).to_digit(10).ok_or_else(error::invalid_digit)?;
+        if i >= num_kept {
+            if digit != 0 {
+                return Err(error::invalid_digit());
+            }
+        } else {
+            let int_result = result.checked_mul_64(10).ok_or_else(error::overflow)?;
+            result = int_result.checked_add(u128::new(digit as u64)).ok_or_else(error::overflow)?;
+        }
+    }
+
+    if shift <= 0 || result == ZERO {
+        Ok(result)
+    } else {
+        checked_pow10(shift as u32)
+            .and_then(|p| result.checked_mul(p))
+            .ok_or_else(error::overflow)
+    }
+}
+
+/// Parses the digits `bytes[start..]` in the given base in a `const` context. Panics on an invalid
+/// digit, and returns `None` on overflow.
+#[cfg(extprim_has_const_fn)]
+pub(crate) const fn parse_digits_const(bytes: &[u8], start: usize, radix: u32) -> Option<u128> {
+    if bytes.is_empty() {
+        panic!("cannot parse integer from empty string");
+    }
+    if start >= bytes.len() {
+        panic!("invalid digit found in string");
+    }
+
+    let radix64 = radix as u64;
+    let mut result = ZERO;
+    let mut i = start;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let digit = match c {
+            b'0' ..= b'9' => (c - b'0') as u64,
+            b'a' ..= b'z' => (c - b'a') as u64 + 10,
+            b'A' ..= b'Z' => (c - b'A') as u64 + 10,
+            _ => 36,
+        };
+        if digit >= radix64 {
+            panic!("invalid digit found in string");
+        }
+
+        // result * radix + digit, done in 32-bit pieces of the low word so that the carries fit.
+        let lo_lo = (result.lo & 0xffff_ffff) * radix64 + digit;
+        let lo_hi = (result.lo >> 32) * radix64 + (lo_lo >> 32);
+        let hi = match result.hi.checked_mul(radix64) {
+            Some(hi) => hi.checked_add(lo_hi >> 32),
+            None => None,
+        };
+        result = match hi {
+            Some(hi) => u128::from_parts(hi, (lo_lo & 0xffff_ffff) | (lo_hi << 32)),
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(result)
+}
+
+impl u128 {
+    /// Converts a string slice in a given base to an integer.
+    ///
+    /// The digits may be preceded by a `+` sign. Leading and trailing whitespace represent an
+    /// error.
+    ///
+    /// # Arguments
+    ///
+    /// - src: A string slice
+    /// - radix: The base to use. Must lie in the range [2 ... 36].
+    ///
+    /// # Return value
+    ///
+    /// `Err(ParseIntError)` if the string did not represent a valid number. Otherwise, `Ok(n)`
+    /// where `n` is the integer represented by `src`.
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<u128, ParseIntError> {
+        assert!(radix >= 2 && radix <= 36,
+                "from_str_radix_int: must lie in the range `[2, 36]` - found {}",
+                radix);
+
+        let mut src_chars = src.chars();
+        let digits = match src_chars.next() {
+            Some('+') => src_chars.as_str(),
+            Some(_) => src,
+            None => return Err(error::empty()),
+        };
+        if digits.is_empty() {
+            return Err(error::invalid_digit());
+        }
+        parse_digits(digits, radix)
+    }
+
+    /// Converts a decimal string slice which may be written in scientific notation to an integer.
+    ///
+    /// The string consists of decimal digits, optionally followed by a fractional part starting
+    /// with `.`, optionally followed by an exponent `e` or `E` with an optional sign. The value
+    /// must be an exact integer, so `"2.5e10"` is accepted but `"2.5"` is not.
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Return value
+    ///
+    /// `Err(ParseScientificError::NotIntegral)` if the string represents a valid number which is
+    /// not an integer. `Err(ParseScientificError::Int(e))` if the string did not represent a valid
+    /// number or the number is too large. Otherwise, `Ok(n)` where `n` is the integer represented
+    /// by `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::ParseScientificError;
+    ///
+    /// assert_eq!(u128::from_str_scientific("1e30"), Ok(u128::from_parts(54210108624, 5076944270305263616)));
+    /// assert_eq!(u128::from_str_scientific("2.5e10"), Ok(u128::new(25_000_000_000)));
+    /// assert_eq!(u128::from_str_scientific("1200e-2"), Ok(u128::new(12)));
+    /// assert_eq!(u128::from_str_scientific("2.5"), Err(ParseScientificError::NotIntegral));
+    /// assert!(u128::from_str_scientific("1e39").is_err());
+    /// ```
+    pub fn from_str_scientific(src: &str) -> Result<u128, ParseScientificError> {
+        if src.is_empty() {
+            return Err(error::empty().into());
+        }
+
+        let (mantissa, exponent) = match src.find(&['e', 'E'][..]) {
+            Some(i) => (&src[..i], Some(&src[i+1..])),
+            None => (src, None),
+        };
+        let (int_part, frac_part) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], &mantissa[i+1..]),
+            None => (mantissa, ""),
+        };
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(error::invalid_digit().into());
+        }
+        if int_part.chars().chain(frac_part.chars()).any(|c| c.to_digit(10).is_none()) {
+            return Err(error::invalid_digit().into());
+        }
+
+        // The exponent saturates instead of overflowing. Any exponent large enough to saturate
+        // would make a non-zero mantissa overflow or become non-integral anyway.
+        let mut exp = match exponent {
+            None => 0i64,
+            Some(e) => {
+                let mut e_chars = e.chars();
+                let (is_negative, digits) = match e_chars.next() {
+                    Some('-') => (true, e_chars.as_str()),
+                    Some('+') => (false, e_chars.as_str()),
+                    _ => (false, e),
+                };
+                if digits.is_empty() {
+                    return Err(error::invalid_digit().into());
+                }
+                let mut value = 0i64;
+                for c in digits.chars() {
+                    let digit = c.to_digit(10).ok_or_else(error::invalid_digit)?;
+                    value = value.saturating_mul(10).saturating_add(digit as i64);
+                }
+                if is_negative { -value } else { value }
+            }
+        };
+        exp = exp.saturating_sub(frac_part.len() as i64);
+
+        // Trailing zeros can be cancelled against a negative exponent.
+        let digits = mantissa.as_bytes();
+        let mut len = digits.len();
+        while exp < 0 && len > 0 && (digits[len - 1] == b'0' || digits[len - 1] == b'.') {
+            if digits[len - 1] == b'0' {
+                exp += 1;
+            }
+            len -= 1;
+        }
+
+        let mut result = ZERO;
+        let mut is_zero = true;
+        for &b in &digits[..len] {
+            if b == b'.' {
+                continue;
+            }
+            is_zero = is_zero && b == b'0';
+            if exp < 0 {
+                continue;
+            }
+            let int_result = result.checked_mul_64(10).ok_or_else(error::overflow)?;
+            result = int_result.checked_add(u128::new((b - b'0') as u64)).ok_or_else(error::overflow)?;
+        }
+
+        if is_zero {
+            Ok(ZERO)
+        } else if exp < 0 {
+            Err(ParseScientificError::NotIntegral)
+        } else {
+            for _ in 0..exp {
+                result = result.checked_mul_64(10).ok_or_else(error::overflow)?;
+            }
+            Ok(result)
+        }
+    }
+
+    /// Parses a decimal number such as `"123.456789"` into an unscaled value with the given scale,
+    /// e.g. an amount of money in units of 10<sup>-scale</sup>.
+    ///
+    /// The number may have a leading `+` sign, and a fractional part starting with `.`. It must
+    /// have at least one digit, and must be exactly representable at the given scale. This is the
+    /// unsigned counterpart of
+    /// [`i128::from_decimal_str`](../i128/struct.i128.html#method.from_decimal_str).
+    ///
+    /// # Return value
+    ///
+    /// An overflow error if the unscaled value does not fit in a `u128`. An invalid digit error if
+    /// the string is not a decimal number, or has significant digits below 10<sup>-scale</sup>.
+    /// Otherwise, `Ok(n)` where `n` is the unscaled value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let amount = u128::new(123_456_789) * u128::new(1_000_000_000_000);
+    /// assert_eq!(u128::from_decimal_str("123.456789", 18), Ok(amount));
+    /// assert_eq!(u128::from_decimal_str("1.50", 1), Ok(u128::new(15)));
+    /// assert!(u128::from_decimal_str("0.125", 2).is_err());
+    /// assert!(u128::from_decimal_str("1000000000000000000000", 18).is_err());
+    /// ```
+    pub fn from_decimal_str(src: &str, scale: i8) -> Result<u128, ParseIntError> {
+        let mut src_chars = src.chars();
+        let digits = match src_chars.next() {
+            Some('+') => src_chars.as_str(),
+            Some(_) => src,
+            None => return Err(error::empty()),
+        };
+        parse_decimal_digits(digits, scale)
+    }
+
+    /// Formats an unscaled value with the given scale as a decimal number, e.g. `12345` with scale
+    /// 2 as `"123.45"`.
+    ///
+    /// All `scale` fractional digits are written, including trailing zeros. This is the inverse of
+    /// [`from_decimal_str`](#method.from_decimal_str).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let amount = u128::new(123_456_789) * u128::new(1_000_000_000_000);
+    /// assert_eq!(amount.format_scaled(18), "123.456789000000000000");
+    /// assert_eq!(u128::new(5).format_scaled(3), "0.005");
+    /// assert_eq!(u128::new(5).format_scaled(-2), "500");
+    /// ```
+    #[cfg(feature="use-std")]
+    pub fn format_scaled(self, scale: i8) -> String {
+        let digits = self.to_string();
+        if scale <= 0 {
+            let mut result = digits;
+            if self != ZERO {
+                for _ in 0..-(scale as i32) {
+                    result.push('0');
+                }
+            }
+            return result;
+        }
+
+        let scale = scale as usize;
+        let mut result = String::with_capacity(digits.len().max(scale) + 2);
+        if digits.len() > scale {
+            let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+            result.push_str(int_part);
+            result.push('.');
+            result.push_str(frac_part);
+        } else {
+            result.push_str("0.");
+            for _ in digits.len()..scale {
+                result.push('0');
+            }
+            result.push_str(&digits);
+        }
+        result
+    }
+}
+
+#[cfg(extprim_has_const_fn)]
+impl u128 {
+    /// Converts a decimal string slice to an integer in a `const` context.
+    ///
+    /// This accepts the same strings as `from_str`, and is meant for writing large constants
+    /// without the literal macros.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the string does not represent a valid number. In a `const`
+    /// context this is a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// const K: u128 = u128::parse_const("123456789012345678901234567890");
+    /// assert_eq!(K, u128::from_parts(6692605942, 14083847773837265618));
+    /// ```
+    pub const fn parse_const(src: &str) -> u128 {
+        u128::parse_const_radix(src, 10)
+    }
+
+    /// Converts a string slice in a given base to an integer in a `const` context.
+    ///
+    /// This accepts the same strings as `from_str_radix`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `radix` is not in the range [2 ... 36], or if the string does
+    /// not represent a valid number. In a `const` context this is a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// const MASK: u128 = u128::parse_const_radix("ffffffffffffffff0000000000000000", 16);
+    /// assert_eq!(MASK, u128::from_parts(!0, 0));
+    /// ```
+    pub const fn parse_const_radix(src: &str, radix: u32) -> u128 {
+        assert!(radix >= 2 && radix <= 36, "from_str_radix_int: must lie in the range `[2, 36]`");
+
+        let bytes = src.as_bytes();
+        let start = if !bytes.is_empty() && bytes[0] == b'+' { 1 } else { 0 };
+        match parse_digits_const(bytes, start, radix) {
+            Some(result) => result,
+            None => panic!("number too large to fit in target type"),
+        }
+    }
+}
+
+impl Num for u128 {
+    type FromStrRadixErr = ParseIntError;
+
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+        Self::from_str_radix(src, radix)
+    }
+}
+
+impl FromStr for u128 {
+    type Err = ParseIntError;
+
+    fn from_str(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_radix(src, 10)
+    }
+}
+
+#[cfg(test)]
+mod from_str_tests {
+    use u128::{u128, MAX, ZERO};
+    use error;
+
+    #[test]
+    fn test_from_str_radix() {
+        const TEST_RESULTS: &[&str] = &[
+            "10011011100100101101000110001011110001010011011101110001100111001000101000101101010100100100010100111001011101010000110001010101",
+            "110120222012101010211220122102022000210010022000111102212102202222012022120111212",
+            "2123210231012023301103131301213020220231110210110321131100301111",
+            "3330311440012420033140113104304110413013304434422141400",
+            "13113233024433543105511522325553410033343505511205",
+            "1634565460422653144356213116334346545422433412",
+            "2334455061361233561471050552444247135206125",
+            "13528171124818368023108014385382865276455",
+            "206792664785365372185662205006093552725",
+            "67649064a7890404084060a25479431a98470",
+            "360187787119a95bb767ba32bb0a5b642505",
+            "29c058245bb23487574aca216c29577b882",
+            "3184907b028135c9183b72cdac9c103109",
+            "4bd69b73d8a16036ebec88cd6bb33d335",
+            "9b92d18bc537719c8a2d524539750c55",
+            "1840gefbd6g31a6ecgg7gc50bd70g1g7",
+            "49dheg38e0608a9f4a9267e4g4aagg5",
+            "h0h83ahe8172ah96d68dfe26e94124",
+            "3h0ea36ada20a526i53ee31044e1g5",
+            "jde641e697f962kkidc27ce2edcj2",
+            "57bb2c3jgc5h08a1ga70l48l6hc3b",
+            "1c8ma26907bj977e8j19da70g8h9e",
+            "b547gj6f5egh808nmcnebbeji765",
+            "3i36o07m0i9185n46481i4noc990",
+            "17f9kaldpa569n4p5gagei47konf",
+            "cfq5a3mohb80l380dbnbkq58fdn",
+            "4oqm8ncn25iij172m7giopbaol9",
+            "1rrq11r63qkjr4s06jq142klq23",
+            "oc5mpkf55e6kpj97prm765q0o5",
+            "an9nde76jttn4ifukgsdinhsc8",
+            "4rib8onh9ne6e8kbai8ksna32l",
+            "28b35bg89n93in6l8rfpijv92b",
+            "12ajr3pwad0qofcfuk1wbutlp7",
+            "i3svxg6wovmba6en6lp37x4cu",
+            "97kl2slyj5vbekzxp0lmn5v85",
+        ];
+
+        let v = u128::from_parts(11210252820717990300, 9956704808456227925);
+
+        for (base2, res) in TEST_RESULTS.iter().enumerate() {
+            assert_eq!(Ok(v), u128::from_str_radix(res, (base2+2) as u32));
+        }
+
+        assert_eq!(Ok(ZERO), u128::from_str_radix("0", 2));
+        assert_eq!(Ok(ZERO), u128::from_str_radix("0000000000000000000000000000000000", 36));
+        assert_eq!(Err(error::invalid_digit()), u128::from_str_radix("123", 3));
+        assert_eq!(Err(error::invalid_digit()), u128::from_str_radix("-1", 10));
+        assert_eq!(Err(error::empty()), u128::from_str_radix("", 10));
+        assert_eq!(Ok(MAX), u128::from_str_radix("f5lxx1zz5pnorynqglhzmsp33", 36));
+        assert_eq!(Err(error::overflow()), u128::from_str_radix("f5lxx1zz5pnorynqglhzmsp34", 36));
+        assert_eq!(Err(error::overflow()), u128::from_str_radix("f5lxx1zz5pnorynqglhzmsp43", 36));
+    }
+
+    #[test]
+    fn test_from_str_scientific() {
+        use error::ParseScientificError;
+
+        assert_eq!(Ok(ZERO), u128::from_str_scientific("0"));
+        assert_eq!(Ok(ZERO), u128::from_str_scientific("0.000e-100000000000000000000"));
+        assert_eq!(Ok(ZERO), u128::from_str_scientific("0e100000000000000000000"));
+        assert_eq!(Ok(u128::new(123)), u128::from_str_scientific("123"));
+        assert_eq!(Ok(u128::new(123)), u128::from_str_scientific("123."));
+        assert_eq!(Ok(u128::new(123)), u128::from_str_scientific("123.000"));
+        assert_eq!(Ok(u128::new(5)), u128::from_str_scientific(".5e1"));
+        assert_eq!(Ok(u128::new(125)), u128::from_str_scientific("12.50e1"));
+        assert_eq!(Ok(u128::new(1_000_000)), u128::from_str_scientific("1E6"));
+        assert_eq!(Ok(u128::new(1_000_000)), u128::from_str_scientific("1e+6"));
+        assert_eq!(Ok(u128::new(12)), u128::from_str_scientific("120000e-4"));
+        assert_eq!(Ok(u128::new(12)), u128::from_str_scientific("1200.00e-2"));
+        assert_eq!(Ok(MAX), u128::from_str_scientific("340282366920938463463374607431768211455"));
+        assert_eq!(Ok(MAX), u128::from_str_scientific("3.40282366920938463463374607431768211455e38"));
+        assert_eq!(Ok(u128::from_parts(0x4b3b4ca85a86c47a, 0x098a224000000000)),
+                    u128::from_str_scientific("1e38"));
+
+        assert_eq!(Err(ParseScientificError::NotIntegral), u128::from_str_scientific("2.5"));
+        assert_eq!(Err(ParseScientificError::NotIntegral), u128::from_str_scientific("1200.0e-3"));
+        assert_eq!(Err(ParseScientificError::NotIntegral), u128::from_str_scientific("1e-1"));
+        assert_eq!(Err(ParseScientificError::NotIntegral), u128::from_str_scientific("1e-100000000000000000000"));
+
+        assert_eq!(Err(ParseScientificError::Int(error::overflow())), u128::from_str_scientific("1e39"));
+        assert_eq!(Err(ParseScientificError::Int(error::overflow())),
+                    u128::from_str_scientific("340282366920938463463374607431768211456"));
+        assert_eq!(Err(ParseScientificError::Int(error::overflow())),
+                    u128::from_str_scientific("1e100000000000000000000"));
+        assert_eq!(Err(ParseScientificError::Int(error::empty())), u128::from_str_scientific(""));
+        for s in &["e5", ".", ".e1", "1e", "1e+", "1e-", "1.2.3", "1e1.5", "-1", "+1", " 1", "1 ", "0x10", "1e5e5"] {
+            assert_eq!(Err(ParseScientificError::Int(error::invalid_digit())), u128::from_str_scientific(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_from_decimal_str() {
+        assert_eq!(u128::from_decimal_str("123.456789", 18), Ok(u128::new(123456789) * u128::new(1000000000000)));
+        assert_eq!(u128::from_decimal_str("+.5", 1), Ok(u128::new(5)));
+        assert_eq!(u128::from_decimal_str("5.", 0), Ok(u128::new(5)));
+        assert_eq!(u128::from_decimal_str("1.50", 1), Ok(u128::new(15)));
+        assert_eq!(u128::from_decimal_str("1200", -2), Ok(u128::new(12)));
+        assert_eq!(u128::from_decimal_str("0.000", -5), Ok(ZERO));
+        assert_eq!(u128::from_decimal_str("3.40282366920938463463374607431768211455", 38), Ok(MAX));
+        assert_eq!(u128::from_decimal_str("3.40282366920938463463374607431768211456", 38), Err(error::overflow()));
+        assert_eq!(u128::from_decimal_str("1", 39), Err(error::overflow()));
+        assert_eq!(u128::from_decimal_str("", 2), Err(error::empty()));
+        assert_eq!(u128::from_decimal_str(".", 2), Err(error::invalid_digit()));
+        assert_eq!(u128::from_decimal_str("-1", 2), Err(error::invalid_digit()));
+        assert_eq!(u128::from_decimal_str("1.2.3", 2), Err(error::invalid_digit()));
+        assert_eq!(u128::from_decimal_str("0.12", 1), Err(error::invalid_digit()));
+        assert_eq!(u128::from_decimal_str("1201", -2), Err(error::invalid_digit()));
+        assert_eq!(u128::from_decimal_str("3402823669209384634633746074317682114540", -1),
+                   Ok(MAX - u128::one()));
+        assert_eq!(u128::from_decimal_str("340282366920938463463374607431768211455", -39),
+                   Err(error::invalid_digit()));
+        assert_eq!(u128::from_decimal_str("0", -128), Ok(ZERO));
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    fn test_parse_const() {
+        const K: u128 = u128::parse_const("123456789012345678901234567890");
+        const LARGEST: u128 = u128::parse_const_radix("+ffffffffffffffffffffffffffffffff", 16);
+        const Z: u128 = u128::parse_const_radix("Zz", 36);
+        assert_eq!(Some(K), u128::from_str_radix("123456789012345678901234567890", 10).ok());
+        assert_eq!(LARGEST, MAX);
+        assert_eq!(Z, u128::new(35 * 36 + 35));
+        assert_eq!(u128::parse_const("0"), ZERO);
+        assert_eq!(u128::parse_const("340282366920938463463374607431768211455"), MAX);
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    #[should_panic(expected = "number too large to fit in target type")]
+    fn test_parse_const_overflow() {
+        u128::parse_const("340282366920938463463374607431768211456");
+    }
+
+    #[cfg(extprim_has_const_fn)]
+    #[test]
+    #[should_panic(expected = "invalid digit found in string")]
+    fn test_parse_const_invalid_digit() {
+        u128::parse_const_radix("12", 2);
+    }
+}
+
+//}}}
+
+//{{{ Binary, LowerHex, UpperHex, Octal, String, Show
+
+impl fmt::Display for u128 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == 0 {
+            self.lo.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; MAX_STR_LEN];
+            formatter.pad_integral(true, "", format_decimal(*self, &mut buffer))
+        }
+    }
+}
+
+impl u128 {
+    /// Returns the number of decimal digits of the number. Zero has 1 digit.
+    ///
+    /// This is the length of the number formatted with `{}`, which is at most
+    /// [`MAX_STR_LEN`](constant.MAX_STR_LEN.html).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::zero().num_digits(), 1);
+    /// assert_eq!(u128::new(999).num_digits(), 3);
+    /// assert_eq!(u128::new(1000).num_digits(), 4);
+    /// assert_eq!(u128::max_value().num_digits(), 39);
+    /// ```
+    pub fn num_digits(self) -> u32 {
+        if self.hi == 0 {
+            let mut n = 1;
+            let mut x = self.lo;
+            while x >= 10 {
+                x /= 10;
+                n += 1;
+            }
+            n
+        } else {
+            self.ilog10() + 1
+        }
+    }
+}
+
+impl fmt::Debug for u128 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "u128!({})", self)
+    }
+}
+
+impl fmt::Binary for u128 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == 0 {
+            self.lo.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; 128];
+            let mut buf = FormatBuffer::new(&mut buffer);
+
+            write!(&mut buf, "{:b}{:064b}", self.hi, self.lo)?;
+            formatter.pad_integral(true, "0b", unsafe { buf.into_str() })
+        }
+    }
+}
+
+impl fmt::LowerHex for u128 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == 0 {
+            self.lo.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; 32];
+            let mut buf = FormatBuffer::new(&mut buffer);
+
+            write!(&mut buf, "{:x}{:016x}", self.hi, self.lo)?;
+            formatter.pad_integral(true, "0x", unsafe { buf.into_str() })
+        }
+    }
+}
+
+impl fmt::UpperHex for u128 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == 0 {
+            self.lo.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; 32];
+            let mut buf = FormatBuffer::new(&mut buffer);
+
+            write!(&mut buf, "{:X}{:016X}", self.hi, self.lo)?;
+            formatter.pad_integral(true, "0x", unsafe { buf.into_str() })
+        }
+    }
+}
+
+impl fmt::Octal for u128 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        const MASK: u64 = (1 << 63) - 1;
+
+        let lo = self.lo & MASK;
+        let mid = (self.hi << 1 | self.lo >> 63) & MASK;
+        let hi = self.hi >> 62;
+
+        let mut buffer = [0u8; 43];
+        let mut buf = FormatBuffer::new(&mut buffer);
+
+        if hi != 0 {
+            write!(&mut buf, "{:o}{:021o}{:021o}", hi, mid, lo)?;
+        } else if mid != 0 {
+            write!(&mut buf, "{:o}{:021o}", mid, lo)?;
+        } else {
+            return lo.fmt(formatter);
+        }
+
+        formatter.pad_integral(true, "0o", unsafe { buf.into_str() })
     }
 }
 
-impl Unsigned for u128 {
+#[cfg(test)]
+mod show_tests {
+    use u128::{u128, MAX, MAX_STR_LEN};
+
+    #[test]
+    fn test_display() {
+        assert_fmt_eq!("0", 1,
+                       "{}", u128::new(0));
+        assert_fmt_eq!("10578104835920319894", 20,
+                       "{}", u128::new(10578104835920319894));
+        assert_fmt_eq!("91484347284476727216111035283008240438", 38,
+                       "{}", u128::from_parts(4959376403712401289, 46322452157807414));
+        assert_fmt_eq!("221073131124184722582670274216994227164", 39,
+                       "{}", u128::from_parts(11984398452150693167, 12960002013829219292));
+        assert_fmt_eq!("340282366920938463463374607431768211455", 39,
+                       "{}", MAX);
+        assert_fmt_eq!("100000000000000000000000000000000000000", 39,
+                       "{}", u128::from_parts(5421010862427522170, 687399551400673280));
+        assert_fmt_eq!("+00340282366920938463463374607431768211455", 42,
+                       "{:+042}", MAX);
+    }
+
+    #[test]
+    fn test_num_digits() {
+        let mut x = u128::new(1);
+        for n in 1..40 {
+            assert_eq!(x.num_digits(), n);
+            assert_eq!((x - u128::new(1)).num_digits(), if n == 1 { 1 } else { n - 1 });
+            assert_eq!(x.to_string().len(), n as usize);
+            x = x.wrapping_mul(u128::new(10));
+        }
+        assert_eq!(MAX.num_digits() as usize, MAX_STR_LEN);
+        assert_eq!(MAX.to_string().len(), MAX_STR_LEN);
+    }
+
+    #[test]
+    fn test_binary() {
+        assert_fmt_eq!("0", 1,
+                       "{:b}", u128::new(0));
+        assert_fmt_eq!("111001011001111000111001100100010100111010010001110101100101011", 63,
+                       "{:b}", u128::new(8272862688628501291));
+        assert_fmt_eq!("10101011011101011000001011010101101001110110100010000100010001111001010100010010110000000000100100101001100100010110111010011011", 128,
+                       "{:b}", u128::from_parts(12354925006909113415, 10741859206816689819));
+        assert_fmt_eq!("10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", 128,
+                       "{:b}", u128::from_parts(9223372036854775808, 0));
+        assert_fmt_eq!("11111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111", 128,
+                       "{:b}", MAX);
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_fmt_eq!("0", 1,
+                       "{:x}", u128::new(0));
+        assert_fmt_eq!("25c22f8602efedb5", 16,
+                       "{:x}", u128::new(2720789377506602421));
+        assert_fmt_eq!("2c73d4b3d1a46f081a04e1ea9846faee", 32,
+                       "{:x}", u128::from_parts(3203137628772003592, 1874871742586354414));
+        assert_fmt_eq!("80000000000000000000000000000000", 32,
+                       "{:x}", u128::from_parts(9223372036854775808, 0));
+        assert_fmt_eq!(" 0xA", 4,
+                       "{:#4X}", u128::new(10));
+        assert_fmt_eq!("25C22F8602EFEDB5", 16,
+                       "{:X}", u128::new(2720789377506602421));
+        assert_fmt_eq!("2C73D4B3D1A46F081A04E1EA9846FAEE", 32,
+                       "{:X}", u128::from_parts(3203137628772003592, 1874871742586354414));
+        assert_fmt_eq!("C000000000000000000000000000000", 31,
+                       "{:X}", u128::from_parts(864691128455135232, 0));
+    }
+
+    #[test]
+    fn test_octal() {
+        assert_fmt_eq!("0", 1,
+                       "{:o}", u128::new(0));
+        assert_fmt_eq!("351462366146756037170", 21,
+                       "{:o}", u128::new(4208138189379485304));
+        assert_fmt_eq!("7000263630010212417200", 22,
+                       "{:o}", u128::from_parts(3, 9229698078115241600));
+        assert_fmt_eq!("3465177151267706210351536216110755202064135", 43,
+                       "{:o}", u128::from_parts(16620520452737763444, 15533412710015854685));
+        assert_fmt_eq!("3777777777777777777777777777777777777777777", 43,
+                       "{:o}", u128::from_parts(18446744073709551615, 18446744073709551615));
+        assert_fmt_eq!("2000000000000000000000000000000000000000000", 43,
+                       "{:o}", u128::from_parts(9223372036854775808, 0));
+    }
+
+    #[test]
+    fn test_format_scaled() {
+        assert_eq!(u128::new(12345).format_scaled(2), "123.45");
+        assert_eq!(u128::new(12345).format_scaled(5), "0.12345");
+        assert_eq!(u128::new(12345).format_scaled(7), "0.0012345");
+        assert_eq!(u128::zero().format_scaled(2), "0.00");
+        assert_eq!(u128::zero().format_scaled(-2), "0");
+        assert_eq!(u128::new(12).format_scaled(-3), "12000");
+        assert_eq!(MAX.format_scaled(38), "3.40282366920938463463374607431768211455");
+        let max_1 = MAX - u128::one();
+        for &(value, scale) in &[(MAX, 10), (MAX, 0), (u128::new(5), 3), (u128::one(), 38),
+                                 (max_1, -1), (MAX, -1), (MAX, -128), (u128::one(), -38), (u128::one(), -39)] {
+            assert_eq!(u128::from_decimal_str(&value.format_scaled(scale), scale), Ok(value));
+        }
+    }
+}
+
+//}}}
+
+//{{{ Sum, Product
+
+impl Sum for u128 {
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item = Self>
+    {
+        iter.fold(ZERO, Add::add)
+    }
+}
+
+impl Product for u128 {
+    fn product<I>(iter: I) -> Self
+        where I: Iterator<Item = Self>
+    {
+        iter.fold(ONE, Mul::mul)
+    }
+}
+
+impl<'a> Sum<&'a u128> for u128 {
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item = &'a Self>
+    {
+        iter.fold(ZERO, |acc, elem| acc + *elem)
+    }
+}
+
+impl<'a> Product<&'a u128> for u128 {
+    fn product<I>(iter: I) -> Self
+        where I: Iterator<Item = &'a Self>
+    {
+        iter.fold(ONE, |acc, elem| acc * *elem)
+    }
+}
+
+impl Sum for Wrapping<u128> {
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item = Self>
+    {
+        iter.fold(Wrapping(ZERO), |acc, elem| Wrapping(acc.0.wrapping_add(elem.0)))
+    }
+}
+
+impl Product for Wrapping<u128> {
+    fn product<I>(iter: I) -> Self
+        where I: Iterator<Item = Self>
+    {
+        iter.fold(Wrapping(ONE), |acc, elem| Wrapping(acc.0.wrapping_mul(elem.0)))
+    }
+}
+
+impl<'a> Sum<&'a Wrapping<u128>> for Wrapping<u128> {
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item = &'a Self>
+    {
+        iter.fold(Wrapping(ZERO), |acc, elem| Wrapping(acc.0.wrapping_add(elem.0)))
+    }
+}
+
+impl<'a> Product<&'a Wrapping<u128>> for Wrapping<u128> {
+    fn product<I>(iter: I) -> Self
+        where I: Iterator<Item = &'a Self>
+    {
+        iter.fold(Wrapping(ONE), |acc, elem| Wrapping(acc.0.wrapping_mul(elem.0)))
+    }
 }
 
 #[cfg(test)]
-mod prim_int_tests {
-    use std::u64;
-    use u128::{u128, MAX, ZERO, ONE};
+mod iter_tests {
+    use u128::{u128, ZERO, ONE, MIN, MAX};
+    use traits::Wrapping;
 
     #[test]
-    fn test_rotate() {
-        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(0),
-                    u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152));
-        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(1),
-                    u128::from_parts(0xf2e3c00d872bafb, 0xa9f84ed62d9478a9));
-        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(3),
-                    u128::from_parts(0x43cb8f00361caebe, 0xea7e13b58b651e2a));
-        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(64),
-                    u128::from_parts(0x53f09dac5b28f152, 0x1e5c7801b0e575f7));
-        assert_eq!(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152).rotate_right(120),
-                    u128::from_parts(0x5c7801b0e575f753, 0xf09dac5b28f1521e));
+    fn test_sum() {
+        // Sum<u128>
+        assert_eq!(ZERO, Vec::<u128>::new().into_iter().sum::<u128>());
+        assert_eq!(ZERO, vec![ZERO, ZERO, ZERO].into_iter().sum::<u128>());
+        assert_eq!(ONE, vec![ONE].into_iter().sum::<u128>());
+        assert_eq!(u128::from(3u64), vec![ONE, ONE, ONE].into_iter().sum::<u128>());
+        assert_eq!(MAX, vec![MAX].into_iter().sum::<u128>());
+        assert_eq!(MIN, vec![MIN].into_iter().sum::<u128>());
+        assert_eq!(u128::from_parts(7, 42), vec![u128::from_parts(7, 42)].into_iter().sum::<u128>());
+
+        // Sum<&'a u128>
+        assert_eq!(ZERO, [].iter().sum::<u128>());
+        assert_eq!(ZERO, [ZERO, ZERO, ZERO].iter().sum::<u128>());
+        assert_eq!(ONE, [ONE].iter().sum::<u128>());
+        assert_eq!(u128::from(3u64), [ONE, ONE, ONE].iter().sum::<u128>());
+        assert_eq!(MAX, [MAX].iter().sum::<u128>());
+        assert_eq!(MIN, [MIN].iter().sum::<u128>());
+        assert_eq!(u128::from_parts(7, 42), [u128::from_parts(7, 42)].iter().sum::<u128>());
     }
 
     #[test]
-    fn test_swap_bytes() {
-        assert_eq!(u128::from_parts(0xf0d6891695897d01, 0xb6e2f3a4b065e277).swap_bytes(),
-                    u128::from_parts(0x77e265b0a4f3e2b6, 0x017d89951689d6f0));
+    fn test_product() {
+        // Product<u128>
+        assert_eq!(ONE, Vec::<u128>::new().into_iter().product::<u128>());
+        assert_eq!(ONE, vec![ONE, ONE, ONE, ONE, ONE, ONE].into_iter().product::<u128>());
+        assert_eq!(ZERO, vec![ONE, MAX, ZERO].into_iter().product::<u128>());
+        assert_eq!(MAX, vec![MAX].into_iter().product::<u128>());
+        assert_eq!(MAX, vec![ONE, MAX].into_iter().product::<u128>());
+        assert_eq!(MIN, vec![MIN].into_iter().product::<u128>());
+        assert_eq!(MIN, vec![ONE, MIN].into_iter().product::<u128>());
+        assert_eq!(MAX, vec![
+            u128::from(3u64),
+            u128::from(5u64),
+            u128::from(17u64),
+            u128::from(257u64),
+            u128::from(641u64),
+            u128::from(65537u64),
+            u128::from(274177u64),
+            u128::from(6700417u64),
+            u128::from(67280421310721u64),
+        ].into_iter().product::<u128>());
+
+        // Product<&'a u128>
+        assert_eq!(ONE, [].iter().product::<u128>());
+        assert_eq!(ONE, [ONE, ONE, ONE, ONE, ONE, ONE].iter().product::<u128>());
+        assert_eq!(ZERO, [ONE, MAX, ZERO].iter().product::<u128>());
+        assert_eq!(MAX, [MAX].iter().product::<u128>());
+        assert_eq!(MAX, [ONE, MAX].iter().product::<u128>());
+        assert_eq!(MIN, [MIN].iter().product::<u128>());
+        assert_eq!(MIN, [ONE, MIN].iter().product::<u128>());
+        assert_eq!(MAX, [
+            u128::from(3u64),
+            u128::from(5u64),
+            u128::from(17u64),
+            u128::from(257u64),
+            u128::from(641u64),
+            u128::from(65537u64),
+            u128::from(274177u64),
+            u128::from(6700417u64),
+            u128::from(67280421310721u64),
+        ].iter().product::<u128>());
     }
 
     #[test]
-    fn test_leading_zeros() {
-        assert_eq!(u128::from_parts(1, 0).leading_zeros(), 63);
-        assert_eq!(u128::from_parts(1, 4).leading_zeros(), 63);
-        assert_eq!(u128::from_parts(0, 1).leading_zeros(), 127);
-        assert_eq!(u128::from_parts(0, 0).leading_zeros(), 128);
+    fn test_wrapping_sum() {
+        assert_eq!(Wrapping(ZERO), Vec::<Wrapping<u128>>::new().into_iter().sum());
+        assert_eq!(Wrapping(ONE), vec![Wrapping(MAX), Wrapping(ONE), Wrapping(ONE)].into_iter().sum());
+        assert_eq!(Wrapping(MAX - ONE), vec![Wrapping(MAX); 2].into_iter().sum());
+        assert_eq!(Wrapping(ONE), [Wrapping(MAX), Wrapping(ONE), Wrapping(ONE)].iter().sum());
+    }
+
+    #[test]
+    fn test_wrapping_product() {
+        assert_eq!(Wrapping(ONE), Vec::<Wrapping<u128>>::new().into_iter().product());
+        assert_eq!(Wrapping(ONE), vec![Wrapping(MAX), Wrapping(MAX)].into_iter().product());
+        assert_eq!(Wrapping(ZERO), vec![Wrapping(u128::from_parts(1, 0)); 2].into_iter().product());
+        assert_eq!(Wrapping(u128::from_parts(!0 - 1, 1)), [Wrapping(u128::new(!0)); 2].iter().product());
+    }
+}
+
+//}}}
+
+
+//{{{ Range, RangeStep
+
+/// An iterator over the half-open range `start .. end`, created by [`range`](fn.range.html).
+#[derive(Clone, Debug)]
+pub struct Range {
+    start: u128,
+    end: u128,
+}
+
+/// An iterator over the half-open range `start .. end` advancing by a fixed step, created by
+/// [`range_step`](fn.range_step.html).
+#[derive(Clone, Debug)]
+pub struct RangeStep {
+    next: u128,
+    step: u128,
+    remaining: u128,
+}
+
+/// Returns an iterator over the half-open range `start .. end`.
+///
+/// This is the stable replacement of `start .. end`, which requires the unstable `Step` trait to
+/// be iterable.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::{u128, range};
+///
+/// let v = range(u128::new(3), u128::new(6)).collect::<Vec<_>>();
+/// assert_eq!(v, vec![u128::new(3), u128::new(4), u128::new(5)]);
+///
+/// let mut r = range(u128::zero(), u128::max_value());
+/// assert_eq!(r.next_back(), Some(u128::max_value() - u128::one()));
+/// assert_eq!(r.size_hint(), (usize::max_value(), None));
+/// ```
+pub fn range(start: u128, end: u128) -> Range {
+    Range { start: start, end: end }
+}
+
+/// Returns an iterator over `start, start + step, start + 2*step, ...` which stops before reaching
+/// `end`.
+///
+/// # Panics
+///
+/// This function will panic if `step` is 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u128::{u128, range_step};
+///
+/// let v = range_step(u128::new(1), u128::new(10), u128::new(4)).collect::<Vec<_>>();
+/// assert_eq!(v, vec![u128::new(1), u128::new(5), u128::new(9)]);
+///
+/// let v = range_step(u128::new(1), u128::new(10), u128::new(4)).rev().collect::<Vec<_>>();
+/// assert_eq!(v, vec![u128::new(9), u128::new(5), u128::new(1)]);
+/// ```
+pub fn range_step(start: u128, end: u128, step: u128) -> RangeStep {
+    assert!(step != ZERO, "range_step: step must not be zero");
+    let remaining = if start < end {
+        (end - start - ONE) / step + ONE
+    } else {
+        ZERO
+    };
+    RangeStep { next: start, step: step, remaining: remaining }
+}
+
+fn count_to_size_hint(count: u128) -> (usize, Option<usize>) {
+    match count.to_usize() {
+        Some(n) => (n, Some(n)),
+        None => (!0, None),
+    }
+}
+
+impl Iterator for Range {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        if self.start < self.end {
+            let n = self.start;
+            self.start = n + ONE;
+            Some(n)
+        } else {
+            None
+        }
     }
 
-    #[test]
-    fn test_trailing_zeros() {
-        assert_eq!(u128::from_parts(1, 0).trailing_zeros(), 64);
-        assert_eq!(u128::from_parts(3, 0).trailing_zeros(), 64);
-        assert_eq!(u128::from_parts(1, 4).trailing_zeros(), 2);
-        assert_eq!(u128::from_parts(0, 1).trailing_zeros(), 0);
-        assert_eq!(u128::from_parts(0, 0).trailing_zeros(), 128);
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        count_to_size_hint(self.end.saturating_sub(self.start))
     }
+}
 
-    #[test]
-    fn test_checked_add() {
-        assert_eq!(Some(u128::from_parts(u64::MAX, 0)),
-                    u128::from_parts(u64::MAX-1, u64::MAX)
-                        .checked_add(u128::new(1)));
-        assert_eq!(Some(u128::from_parts(u64::MAX, 0)), u128::new(1)
-                        .checked_add(u128::from_parts(u64::MAX-1, u64::MAX)));
-        assert_eq!(None, u128::from_parts(u64::MAX, 1)
-                        .checked_add(u128::from_parts(u64::MAX, 2)));
-        assert_eq!(None, MAX.checked_add(u128::new(1)));
+impl DoubleEndedIterator for Range {
+    fn next_back(&mut self) -> Option<u128> {
+        if self.start < self.end {
+            self.end -= ONE;
+            Some(self.end)
+        } else {
+            None
+        }
     }
+}
 
-    #[test]
-    fn test_checked_sub() {
-        assert_eq!(None, ZERO.checked_sub(ONE));
-        assert_eq!(None, ZERO.checked_sub(MAX));
-        assert_eq!(None, ONE.checked_sub(MAX));
-        assert_eq!(Some(ONE), ONE.checked_sub(ZERO));
-        assert_eq!(Some(MAX), MAX.checked_sub(ZERO));
-        assert_eq!(Some(MAX-ONE), MAX.checked_sub(ONE));
+impl Iterator for RangeStep {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        if self.remaining == ZERO {
+            return None;
+        }
+        let n = self.next;
+        self.remaining -= ONE;
+        // may wrap after yielding the final element, but the value is never observed.
+        self.next = n.wrapping_add(self.step);
+        Some(n)
     }
 
-    #[test]
-    fn test_checked_mul() {
-        assert_eq!(Some(ONE), ONE.checked_mul(ONE));
-        assert_eq!(Some(MAX), MAX.checked_mul(ONE));
-        assert_eq!(None, MAX.checked_mul(MAX));
-        assert_eq!(None, MAX.checked_mul(u128::new(2)));
-        assert_eq!(None, u128::from_parts(1, 0).checked_mul(u128::from_parts(1, 0)));
-        let res = u128::from_parts(u64::MAX-1, 1);
-        assert_eq!(Some(res), u128::new(u64::MAX).checked_mul(u128::new(u64::MAX)));
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        count_to_size_hint(self.remaining)
     }
+}
 
-    #[test]
-    fn test_checked_div() {
-        assert_eq!(Some(ONE), ONE.checked_div(ONE));
-        assert_eq!(Some(MAX), MAX.checked_div(ONE));
-        assert_eq!(Some(ZERO), ONE.checked_div(MAX));
-        assert_eq!(Some(ZERO), ZERO.checked_div(MAX));
-        assert_eq!(None, ONE.checked_div(ZERO));
-        assert_eq!(None, MAX.checked_div(ZERO));
+impl DoubleEndedIterator for RangeStep {
+    fn next_back(&mut self) -> Option<u128> {
+        if self.remaining == ZERO {
+            return None;
+        }
+        self.remaining -= ONE;
+        Some(self.next + self.step * self.remaining)
     }
+}
 
-    #[test]
-    fn test_checked_next_power_of_two() {
-        assert_eq!(u128::from_parts(0, 0x80000000_00000000).next_power_of_two(),
-                    u128::from_parts(0, 0x80000000_00000000));
-        assert_eq!(u128::from_parts(0, 0x80000000_00000001).next_power_of_two(),
-                    u128::from_parts(1, 0));
-        assert_eq!(u128::from_parts(1, 0).next_power_of_two(),
-                    u128::from_parts(1, 0));
-        assert_eq!(u128::from_parts(1, 1).next_power_of_two(),
-                    u128::from_parts(2, 0));
-        assert_eq!(u128::from_parts(0x80000000_00000000, 0).checked_next_power_of_two(),
-                    Some(u128::from_parts(0x80000000_00000000, 0)));
-        assert_eq!(u128::from_parts(0x80000000_00000000, 1).checked_next_power_of_two(),
-                    None);
-        assert_eq!(u128::from_parts(0x80000000_00000001, 0).checked_next_power_of_two(),
-                    None);
+#[cfg(test)]
+mod range_tests {
+    use u128::{range, range_step, u128, ZERO, ONE, MAX};
 
+    #[test]
+    fn test_range() {
+        assert_eq!(range(u128::new(5), u128::new(5)).count(), 0);
+        assert_eq!(range(u128::new(6), u128::new(5)).next(), None);
+        assert_eq!(range(u128::new(6), u128::new(5)).size_hint(), (0, Some(0)));
+
+        let start = u128::from_parts(1, !0 - 1);
+        let v = range(start, start + u128::new(4)).collect::<Vec<_>>();
+        assert_eq!(v, vec![
+            u128::from_parts(1, !0 - 1),
+            u128::from_parts(1, !0),
+            u128::from_parts(2, 0),
+            u128::from_parts(2, 1),
+        ]);
+
+        let mut r = range(MAX - u128::new(2), MAX);
+        assert_eq!(r.size_hint(), (2, Some(2)));
+        assert_eq!(r.next_back(), Some(MAX - ONE));
+        assert_eq!(r.next(), Some(MAX - u128::new(2)));
+        assert_eq!(r.next(), None);
+        assert_eq!(r.next_back(), None);
     }
-}
 
-#[cfg(all(test, extprim_channel="unstable"))]
-mod checked_add_sub_bench {
-    use u128::u128;
-    use test::{Bencher, black_box};
+    #[test]
+    fn test_range_step() {
+        assert_eq!(range_step(ZERO, ZERO, ONE).count(), 0);
+        assert_eq!(range_step(ZERO, MAX, MAX).collect::<Vec<_>>(), vec![ZERO]);
+        assert_eq!(range_step(ONE, MAX, MAX).collect::<Vec<_>>(), vec![ONE]);
 
-    const BENCH_CHECKED_ADD_SUB: &'static [u128] = &[
-        u128 { lo: 8530639231766041497, hi: 1287710968871074399 },
-        u128 { lo: 1203542656178406941, hi: 17699966409461566340 },
-        u128 { lo: 718458371035876551, hi: 3606247509203879903 },
-        u128 { lo: 9776046594219398139, hi: 11242044896228553946 },
-        u128 { lo: 7902474877314354323, hi: 15571658655527718712 },
-        u128 { lo: 12666717328207407901, hi: 18395053205720380381 },
-        u128 { lo: 17339836091522731855, hi: 15731019889221707237 },
-        u128 { lo: 8366128025082480321, hi: 13984191269538716594 },
-        u128 { lo: 8593645006461074455, hi: 10189081980804969201 },
-        u128 { lo: 8264027155501625330, hi: 6198464561866207623 },
-        u128 { lo: 10849132074109635036, hi: 5777302818880052808 },
-        u128 { lo: 8053806942953838280, hi: 4617639587817452744 },
-        u128 { lo: 7575409236673560956, hi: 10773137480165156891 },
-        u128 { lo: 4323210863932108621, hi: 16058751318664008901 },
-        u128 { lo: 336314576898396552, hi: 8743495691718489785 },
-        u128 { lo: 6527874161908570477, hi: 926686061690459595 },
-        u128 { lo: 15442937728615642560, hi: 2666553580477360520 },
-        u128 { lo: 11855805362816810591, hi: 17643219502201004064 },
-        u128 { lo: 16313274500479459547, hi: 5436651574417345289 },
-        u128 { lo: 15008613641935618684, hi: 12105224025714335156 },
-    ];
+        let step = u128::from_parts(1, 0);
+        let mut r = range_step(MAX - step * u128::new(3), MAX, step);
+        assert_eq!(r.size_hint(), (3, Some(3)));
+        assert_eq!(r.next(), Some(MAX - step * u128::new(3)));
+        assert_eq!(r.next_back(), Some(MAX - step));
+        assert_eq!(r.next(), Some(MAX - step * u128::new(2)));
+        assert_eq!(r.next(), None);
+        assert_eq!(r.next_back(), None);
 
-    #[bench]
-    fn bench_checked_add(bencher: &mut Bencher) {
-        bencher.iter(|| {
-            for a in BENCH_CHECKED_ADD_SUB {
-                for b in BENCH_CHECKED_ADD_SUB {
-                    black_box(a.checked_add(*b));
-                }
-            }
-        })
+        assert_eq!(range_step(ZERO, MAX, ONE).size_hint(), (!0, None));
     }
 
-    #[bench]
-    fn bench_checked_sub(bencher: &mut Bencher) {
-        bencher.iter(|| {
-            for a in BENCH_CHECKED_ADD_SUB {
-                for b in BENCH_CHECKED_ADD_SUB {
-                    black_box(a.checked_sub(*b));
-                }
-            }
-        })
+    #[test]
+    #[should_panic(expected="step must not be zero")]
+    fn test_range_step_zero() {
+        range_step(ZERO, ONE, ZERO);
     }
 }
 
-
 //}}}
 
-//{{{ FromStr, FromStrRadix
+//{{{ Powers
+
+/// An iterator over the successive powers of a number, created by
+/// [`u128::powers_of`](struct.u128.html#method.powers_of).
+#[derive(Clone, Debug)]
+pub struct PowersOf {
+    base: u128,
+    next: Option<u128>,
+}
 
 impl u128 {
-    /// Converts a string slice in a given base to an integer.
+    /// Returns an iterator over `1, base, base**2, base**3, ...`, which stops before the first
+    /// power that overflows.
     ///
-    /// Leading and trailing whitespace represent an error.
+    /// When `base` is 0 or 1 the powers never overflow, and the iterator is infinite.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// - src: A string slice
-    /// - radix: The base to use. Must lie in the range [2 ... 36].
+    /// ```rust
+    /// use extprim::u128::u128;
     ///
-    /// # Return value
+    /// let v = u128::powers_of(u128::new(10)).take(4).collect::<Vec<_>>();
+    /// assert_eq!(v, vec![u128::new(1), u128::new(10), u128::new(100), u128::new(1000)]);
     ///
-    /// `Err(ParseIntError)` if the string did not represent a valid number. Otherwise, `Ok(n)`
-    /// where `n` is the integer represented by `src`.
-    pub fn from_str_radix(src: &str, radix: u32) -> Result<u128, ParseIntError> {
-        assert!(radix >= 2 && radix <= 36,
-                "from_str_radix_int: must lie in the range `[2, 36]` - found {}",
-                radix);
+    /// assert_eq!(u128::powers_of(u128::new(10)).count(), 39);
+    /// assert_eq!(u128::powers_of(u128::new(2)).last(), Some(u128::from_parts(1 << 63, 0)));
+    /// ```
+    pub fn powers_of(base: u128) -> PowersOf {
+        PowersOf { base: base, next: Some(ONE) }
+    }
+}
 
-        if src.is_empty() {
-            return Err(error::empty());
+impl PowersOf {
+    /// Returns `base**n`, or `None` if it overflows.
+    ///
+    /// The result does not depend on how far the iterator has advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let powers = u128::powers_of(u128::new(10));
+    /// assert_eq!(powers.nth_power(19), Some(u128::from_parts(0, 10_000_000_000_000_000_000)));
+    /// assert_eq!(powers.nth_power(39), None);
+    /// ```
+    pub fn nth_power(&self, n: u32) -> Option<u128> {
+        self.base.checked_pow(n)
+    }
+}
+
+impl Iterator for PowersOf {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        let n = self.next?;
+        self.next = n.checked_mul(self.base);
+        Some(n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next {
+            None => (0, Some(0)),
+            Some(_) if self.base <= ONE => (!0, None),
+            Some(n) => {
+                // the number of remaining powers is 1 + floor(log_base(MAX / n)).
+                let mut count = 1;
+                let mut limit = MAX / n;
+                while limit >= self.base {
+                    limit /= self.base;
+                    count += 1;
+                }
+                (count, Some(count))
+            }
         }
+    }
+}
 
-        let mut result = ZERO;
-        let radix64 = radix as u64;
+#[cfg(test)]
+mod powers_tests {
+    use u128::{u128, ZERO, ONE, MAX};
 
-        for c in src.chars() {
-            let digit = c.to_digit(radix).ok_or_else(error::invalid_digit)?;
-            let int_result = result.checked_mul_64(radix64).ok_or_else(error::overflow)?;
-            let digit128 = u128::new(digit as u64);
-            result = int_result.checked_add(digit128).ok_or_else(error::overflow)?;
+    #[test]
+    fn test_powers_of() {
+        let three = u128::new(3);
+        let mut expected = ONE;
+        let mut powers = u128::powers_of(three);
+        for i in 0..81 {
+            assert_eq!(powers.size_hint(), (81 - i, Some(81 - i)));
+            assert_eq!(powers.next(), Some(expected));
+            expected = expected.wrapping_mul(three);
         }
+        assert_eq!(powers.size_hint(), (0, Some(0)));
+        assert_eq!(powers.next(), None);
 
-        Ok(result)
+        assert_eq!(u128::powers_of(u128::new(2)).count(), 128);
+        assert_eq!(u128::powers_of(u128::new(16)).count(), 32);
+        assert_eq!(u128::powers_of(MAX).collect::<Vec<_>>(), vec![ONE, MAX]);
+        assert_eq!(u128::powers_of(u128::from_parts(1, 0)).count(), 2);
+        assert_eq!(u128::powers_of(ZERO).take(3).collect::<Vec<_>>(), vec![ONE, ZERO, ZERO]);
+        assert_eq!(u128::powers_of(ONE).size_hint(), (!0, None));
     }
-}
 
-impl Num for u128 {
-    type FromStrRadixErr = ParseIntError;
-
-    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
-        Self::from_str_radix(src, radix)
+    #[test]
+    fn test_nth_power() {
+        let mut powers = u128::powers_of(u128::new(7));
+        powers.next();
+        assert_eq!(powers.nth_power(0), Some(ONE));
+        assert_eq!(powers.nth_power(2), Some(u128::new(49)));
+        assert_eq!(powers.nth_power(45), u128::powers_of(u128::new(7)).last());
+        assert_eq!(powers.nth_power(46), None);
+        assert_eq!(u128::powers_of(ZERO).nth_power(1000), Some(ZERO));
     }
 }
 
-impl FromStr for u128 {
-    type Err = ParseIntError;
+//}}}
 
-    fn from_str(src: &str) -> Result<Self, ParseIntError> {
-        Self::from_str_radix(src, 10)
-    }
+//{{{ Field packing
+
+/// Number of bits of the millisecond timestamp in a ULID.
+pub const ULID_TIMESTAMP_BITS: u32 = 48;
+
+/// Number of random bits in a ULID.
+pub const ULID_RANDOM_BITS: u32 = 80;
+
+/// An iterator over the bit fields of a number, created by
+/// [`u128::unpack_fields`](struct.u128.html#method.unpack_fields).
+#[derive(Clone, Debug)]
+pub struct UnpackFields<'a> {
+    value: u128,
+    remaining: u32,
+    widths: ::std::slice::Iter<'a, u32>,
 }
 
-#[cfg(test)]
-mod from_str_tests {
-    use u128::{u128, MAX, ZERO};
-    use error;
+impl<'a> Iterator for UnpackFields<'a> {
+    type Item = u128;
 
-    #[test]
-    fn test_from_str_radix() {
-        const TEST_RESULTS: &'static [&'static str] = &[
-            "10011011100100101101000110001011110001010011011101110001100111001000101000101101010100100100010100111001011101010000110001010101",
-            "110120222012101010211220122102022000210010022000111102212102202222012022120111212",
-            "2123210231012023301103131301213020220231110210110321131100301111",
-            "3330311440012420033140113104304110413013304434422141400",
-            "13113233024433543105511522325553410033343505511205",
-            "1634565460422653144356213116334346545422433412",
-            "2334455061361233561471050552444247135206125",
-            "13528171124818368023108014385382865276455",
-            "206792664785365372185662205006093552725",
-            "67649064a7890404084060a25479431a98470",
-            "360187787119a95bb767ba32bb0a5b642505",
-            "29c058245bb23487574aca216c29577b882",
-            "3184907b028135c9183b72cdac9c103109",
-            "4bd69b73d8a16036ebec88cd6bb33d335",
-            "9b92d18bc537719c8a2d524539750c55",
-            "1840gefbd6g31a6ecgg7gc50bd70g1g7",
-            "49dheg38e0608a9f4a9267e4g4aagg5",
-            "h0h83ahe8172ah96d68dfe26e94124",
-            "3h0ea36ada20a526i53ee31044e1g5",
-            "jde641e697f962kkidc27ce2edcj2",
-            "57bb2c3jgc5h08a1ga70l48l6hc3b",
-            "1c8ma26907bj977e8j19da70g8h9e",
-            "b547gj6f5egh808nmcnebbeji765",
-            "3i36o07m0i9185n46481i4noc990",
-            "17f9kaldpa569n4p5gagei47konf",
-            "cfq5a3mohb80l380dbnbkq58fdn",
-            "4oqm8ncn25iij172m7giopbaol9",
-            "1rrq11r63qkjr4s06jq142klq23",
-            "oc5mpkf55e6kpj97prm765q0o5",
-            "an9nde76jttn4ifukgsdinhsc8",
-            "4rib8onh9ne6e8kbai8ksna32l",
-            "28b35bg89n93in6l8rfpijv92b",
-            "12ajr3pwad0qofcfuk1wbutlp7",
-            "i3svxg6wovmba6en6lp37x4cu",
-            "97kl2slyj5vbekzxp0lmn5v85",
-        ];
+    fn next(&mut self) -> Option<u128> {
+        let width = *self.widths.next()?;
+        self.remaining -= width;
+        // `remaining` can only be 128 when `width` is 0, in which case the mask clears everything.
+        Some(self.value.wrapping_shr(self.remaining) & u128::suffix_mask(width))
+    }
 
-        let v = u128::from_parts(11210252820717990300, 9956704808456227925);
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.widths.size_hint()
+    }
+}
 
-        for (base2, res) in TEST_RESULTS.iter().enumerate() {
-            assert_eq!(Ok(v), u128::from_str_radix(*res, (base2+2) as u32));
+impl u128 {
+    /// Packs a list of `(width, value)` bit fields into a single number. The first field occupies
+    /// the most significant bits, and the last field occupies the least significant bits.
+    ///
+    /// Returns `None` if the total width exceeds 128 bits, or if any value does not fit in its
+    /// width.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let id = u128::pack_fields(&[(4, u128::new(0xa)), (8, u128::new(0xbc))]);
+    /// assert_eq!(id, Some(u128::new(0xabc)));
+    /// assert_eq!(u128::pack_fields(&[(4, u128::new(0x10))]), None);
+    /// assert_eq!(u128::pack_fields(&[(100, u128::zero()), (29, u128::zero())]), None);
+    /// ```
+    pub fn pack_fields(fields: &[(u32, u128)]) -> Option<u128> {
+        let mut result = ZERO;
+        let mut total = 0;
+        for &(width, value) in fields {
+            if width > 128 - total || value > u128::suffix_mask(width) {
+                return None;
+            }
+            // `result` can only be non-zero when `width < 128`.
+            result = result.wrapping_shl(width) | value;
+            total += width;
         }
+        Some(result)
+    }
 
-        assert_eq!(Ok(ZERO), u128::from_str_radix("0", 2));
-        assert_eq!(Ok(ZERO), u128::from_str_radix("0000000000000000000000000000000000", 36));
-        assert_eq!(Err(error::invalid_digit()), u128::from_str_radix("123", 3));
-        assert_eq!(Err(error::invalid_digit()), u128::from_str_radix("-1", 10));
-        assert_eq!(Err(error::empty()), u128::from_str_radix("", 10));
-        assert_eq!(Ok(MAX), u128::from_str_radix("f5lxx1zz5pnorynqglhzmsp33", 36));
-        assert_eq!(Err(error::overflow()), u128::from_str_radix("f5lxx1zz5pnorynqglhzmsp34", 36));
-        assert_eq!(Err(error::overflow()), u128::from_str_radix("f5lxx1zz5pnorynqglhzmsp43", 36));
+    /// Unpacks the bit fields of the given widths, the inverse of
+    /// [`pack_fields`](#method.pack_fields). The fields are read from the least significant
+    /// `sum(widths)` bits of `self`, with the first field in the most significant bits.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the total width exceeds 128 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let fields = u128::new(0xabc).unpack_fields(&[4, 8]).collect::<Vec<_>>();
+    /// assert_eq!(fields, vec![u128::new(0xa), u128::new(0xbc)]);
+    /// ```
+    pub fn unpack_fields<'a>(self, widths: &'a [u32]) -> UnpackFields<'a> {
+        let total = widths.iter().fold(0u32, |acc, w| acc.saturating_add(*w));
+        assert!(total <= 128, "total width of fields must be at most 128");
+        UnpackFields { value: self, remaining: total, widths: widths.iter() }
     }
-}
-
-//}}}
-
-//{{{ Binary, LowerHex, UpperHex, Octal, String, Show
-
-impl fmt::Display for u128 {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        if self.hi == 0 {
-            self.lo.fmt(formatter)
-        } else {
-            const TEN19: u128 = u128 { lo: 10000000000000000000, hi: 0 };
 
-            let mut buffer = [0u8; 39];
-            let mut buf = FormatBuffer::new(&mut buffer);
+    /// Creates a ULID (Universally Unique Lexicographically Sortable Identifier) from a 48-bit
+    /// millisecond timestamp and 80 bits of randomness. Returns `None` if either part is out of
+    /// range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// let ulid = u128::ulid_from_parts(0x0123_4567_89ab, u128::new(42)).unwrap();
+    /// assert_eq!(ulid, u128::from_parts(0x0123_4567_89ab_0000, 42));
+    /// assert_eq!(ulid.ulid_timestamp(), 0x0123_4567_89ab);
+    /// assert_eq!(ulid.ulid_random(), u128::new(42));
+    /// ```
+    pub fn ulid_from_parts(timestamp_ms: u64, random: u128) -> Option<u128> {
+        u128::pack_fields(&[
+            (ULID_TIMESTAMP_BITS, u128::new(timestamp_ms)),
+            (ULID_RANDOM_BITS, random),
+        ])
+    }
 
-            let (mid, lo) = div_rem(*self, TEN19);
-            if mid.hi == 0 {
-                write!(&mut buf, "{}{:019}", mid.lo, lo.lo)?;
-            } else {
-                let (hi, mid) = div_rem(mid, TEN19);
-                write!(&mut buf, "{}{:019}{:019}", hi.lo, mid.lo, lo.lo)?;
-            }
+    /// Generates a ULID with the given millisecond timestamp and random bits drawn from `rng`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `timestamp_ms` does not fit in 48 bits.
+    #[cfg(feature="rand")]
+    pub fn random_ulid<R: Rng + ?Sized>(timestamp_ms: u64, rng: &mut R) -> u128 {
+        let random = rng.gen::<u128>() & u128::suffix_mask(ULID_RANDOM_BITS);
+        u128::ulid_from_parts(timestamp_ms, random).expect("ULID timestamp must fit in 48 bits")
+    }
 
-            formatter.pad_integral(true, "", unsafe { buf.into_str() })
-        }
+    /// Extracts the millisecond timestamp of a ULID.
+    pub fn ulid_timestamp(self) -> u64 {
+        self.hi >> (64 - ULID_TIMESTAMP_BITS)
     }
-}
 
-impl fmt::Debug for u128 {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        write!(formatter, "u128!({})", self)
+    /// Extracts the random part of a ULID.
+    pub fn ulid_random(self) -> u128 {
+        self & u128::suffix_mask(ULID_RANDOM_BITS)
     }
 }
 
-impl fmt::Binary for u128 {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        if self.hi == 0 {
-            self.lo.fmt(formatter)
-        } else {
-            let mut buffer = [0u8; 128];
-            let mut buf = FormatBuffer::new(&mut buffer);
+#[cfg(test)]
+mod field_tests {
+    use u128::{u128, ZERO, MAX};
 
-            write!(&mut buf, "{:b}{:064b}", self.hi, self.lo)?;
-            formatter.pad_integral(true, "0b", unsafe { buf.into_str() })
-        }
+    #[test]
+    fn test_pack_fields() {
+        assert_eq!(u128::pack_fields(&[]), Some(ZERO));
+        assert_eq!(u128::pack_fields(&[(128, MAX)]), Some(MAX));
+        assert_eq!(u128::pack_fields(&[(0, ZERO), (128, MAX), (0, ZERO)]), Some(MAX));
+        assert_eq!(u128::pack_fields(&[(0, u128::new(1))]), None);
+        assert_eq!(u128::pack_fields(&[(128, MAX), (1, ZERO)]), None);
+        assert_eq!(u128::pack_fields(&[(41, u128::new(0x1_2345_6789)), (10, u128::new(5)), (12, u128::new(0xfff))]),
+                   Some(u128::new(0x1_2345_6789 << 22 | 5 << 12 | 0xfff)));
+        assert_eq!(u128::pack_fields(&[(64, u128::new(1)), (64, u128::new(2))]),
+                   Some(u128::from_parts(1, 2)));
     }
-}
 
-impl fmt::LowerHex for u128 {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        if self.hi == 0 {
-            self.lo.fmt(formatter)
-        } else {
-            let mut buffer = [0u8; 32];
-            let mut buf = FormatBuffer::new(&mut buffer);
+    #[test]
+    fn test_unpack_fields() {
+        let value = u128::from_parts(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210);
+        assert_eq!(value.unpack_fields(&[0, 128, 0]).collect::<Vec<_>>(), vec![ZERO, value, ZERO]);
+        assert_eq!(value.unpack_fields(&[64, 64]).collect::<Vec<_>>(),
+                   vec![u128::new(0x0123_4567_89ab_cdef), u128::new(0xfedc_ba98_7654_3210)]);
+        assert_eq!(value.unpack_fields(&[4, 8]).collect::<Vec<_>>(), vec![u128::new(2), u128::new(0x10)]);
+        let widths = [48, 7, 73];
+        let fields = value.unpack_fields(&widths).collect::<Vec<_>>();
+        let packed = widths.iter().cloned().zip(fields).collect::<Vec<_>>();
+        assert_eq!(u128::pack_fields(&packed), Some(value));
+    }
 
-            write!(&mut buf, "{:x}{:016x}", self.hi, self.lo)?;
-            formatter.pad_integral(true, "0x", unsafe { buf.into_str() })
-        }
+    #[test]
+    #[should_panic(expected="total width of fields must be at most 128")]
+    fn test_unpack_fields_too_wide() {
+        MAX.unpack_fields(&[100, 29]);
     }
-}
 
-impl fmt::UpperHex for u128 {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        if self.hi == 0 {
-            self.lo.fmt(formatter)
-        } else {
-            let mut buffer = [0u8; 32];
-            let mut buf = FormatBuffer::new(&mut buffer);
+    #[test]
+    fn test_ulid() {
+        assert_eq!(u128::ulid_from_parts(1 << 48, ZERO), None);
+        assert_eq!(u128::ulid_from_parts(0, u128::from_parts(1 << 16, 0)), None);
+        let ulid = u128::ulid_from_parts((1 << 48) - 1, u128::from_parts(0xffff, !0)).unwrap();
+        assert_eq!(ulid, MAX);
+        assert_eq!(ulid.ulid_timestamp(), (1 << 48) - 1);
+        assert_eq!(ulid.ulid_random(), u128::from_parts(0xffff, !0));
+    }
 
-            write!(&mut buf, "{:X}{:016X}", self.hi, self.lo)?;
-            formatter.pad_integral(true, "0x", unsafe { buf.into_str() })
-        }
+    #[cfg(feature="rand")]
+    #[test]
+    fn test_random_ulid() {
+        use rand::rngs::StdRng;
+
+        let mut rng: StdRng = u128::new(1).seed_rng();
+        let a = u128::random_ulid(1_500_000_000_000, &mut rng);
+        let b = u128::random_ulid(1_500_000_000_001, &mut rng);
+        assert_eq!(a.ulid_timestamp(), 1_500_000_000_000);
+        assert_eq!(b.ulid_timestamp(), 1_500_000_000_001);
+        assert!(a < b);
     }
 }
 
-impl fmt::Octal for u128 {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        const MASK: u64 = (1 << 63) - 1;
+//}}}
 
-        let lo = self.lo & MASK;
-        let mid = (self.hi << 1 | self.lo >> 63) & MASK;
-        let hi = self.hi >> 62;
+//{{{ Mix
 
-        let mut buffer = [0u8; 43];
-        let mut buf = FormatBuffer::new(&mut buffer);
+/// First multiplier of `mix`, the multiplier of the 128-bit PCG generator.
+const MIX_K1: u128 = u128 { lo: 0x4385df649fccf645, hi: 0x2360ed051fc65da4 };
 
-        if hi != 0 {
-            write!(&mut buf, "{:o}{:021o}{:021o}", hi, mid, lo)?;
-        } else if mid != 0 {
-            write!(&mut buf, "{:o}{:021o}", mid, lo)?;
-        } else {
-            return lo.fmt(formatter);
-        }
+/// Second multiplier of `mix`, the 128-bit fractional part of the golden ratio rounded to odd.
+const MIX_K2: u128 = u128 { lo: 0xf39cc0605cedc835, hi: 0x9e3779b97f4a7c15 };
 
-        formatter.pad_integral(true, "0o", unsafe { buf.into_str() })
+impl u128 {
+    /// Scrambles the bits of `self` with an avalanche finalizer, so that flipping any input bit
+    /// flips each output bit with a probability close to 1/2.
+    ///
+    /// The function consists of alternating xor-shift and odd-constant multiply rounds, in the
+    /// style of the murmur3 and xxh3 finalizers extended to 128 bits. Every step is invertible,
+    /// hence this is a bijection, and `mix(0) == 0`. It is suitable for finalizing hash functions
+    /// or deriving independent RNG streams from a counter, but is not cryptographically secure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    ///
+    /// assert_eq!(u128::zero().mix(), u128::zero());
+    /// assert_eq!(u128::one().mix(), u128::from_parts(0x094dd64b3b929ebd, 0x9588c8e200c08458));
+    /// ```
+    pub fn mix(self) -> u128 {
+        let mut x = self;
+        x ^= x >> 71;
+        x = x.wrapping_mul(MIX_K1);
+        x ^= x >> 67;
+        x = x.wrapping_mul(MIX_K2);
+        x ^= x >> 64;
+        x
     }
 }
 
 #[cfg(test)]
-mod show_tests {
-    use u128::{u128, MAX};
-
-    #[test]
-    fn test_display() {
-        assert_fmt_eq!("0", 1,
-                       "{}", u128::new(0));
-        assert_fmt_eq!("10578104835920319894", 20,
-                       "{}", u128::new(10578104835920319894));
-        assert_fmt_eq!("91484347284476727216111035283008240438", 38,
-                       "{}", u128::from_parts(4959376403712401289, 46322452157807414));
-        assert_fmt_eq!("221073131124184722582670274216994227164", 39,
-                       "{}", u128::from_parts(11984398452150693167, 12960002013829219292));
-        assert_fmt_eq!("340282366920938463463374607431768211455", 39,
-                       "{}", MAX);
-        assert_fmt_eq!("100000000000000000000000000000000000000", 39,
-                       "{}", u128::from_parts(5421010862427522170, 687399551400673280));
-        assert_fmt_eq!("+00340282366920938463463374607431768211455", 42,
-                       "{:+042}", MAX);
-    }
-
-    #[test]
-    fn test_binary() {
-        assert_fmt_eq!("0", 1,
-                       "{:b}", u128::new(0));
-        assert_fmt_eq!("111001011001111000111001100100010100111010010001110101100101011", 63,
-                       "{:b}", u128::new(8272862688628501291));
-        assert_fmt_eq!("10101011011101011000001011010101101001110110100010000100010001111001010100010010110000000000100100101001100100010110111010011011", 128,
-                       "{:b}", u128::from_parts(12354925006909113415, 10741859206816689819));
-        assert_fmt_eq!("10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", 128,
-                       "{:b}", u128::from_parts(9223372036854775808, 0));
-        assert_fmt_eq!("11111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111", 128,
-                       "{:b}", MAX);
-    }
+mod mix_tests {
+    use u128::{u128, ONE};
 
     #[test]
-    fn test_hex() {
-        assert_fmt_eq!("0", 1,
-                       "{:x}", u128::new(0));
-        assert_fmt_eq!("25c22f8602efedb5", 16,
-                       "{:x}", u128::new(2720789377506602421));
-        assert_fmt_eq!("2c73d4b3d1a46f081a04e1ea9846faee", 32,
-                       "{:x}", u128::from_parts(3203137628772003592, 1874871742586354414));
-        assert_fmt_eq!("80000000000000000000000000000000", 32,
-                       "{:x}", u128::from_parts(9223372036854775808, 0));
-        assert_fmt_eq!(" 0xA", 4,
-                       "{:#4X}", u128::new(10));
-        assert_fmt_eq!("25C22F8602EFEDB5", 16,
-                       "{:X}", u128::new(2720789377506602421));
-        assert_fmt_eq!("2C73D4B3D1A46F081A04E1EA9846FAEE", 32,
-                       "{:X}", u128::from_parts(3203137628772003592, 1874871742586354414));
-        assert_fmt_eq!("C000000000000000000000000000000", 31,
-                       "{:X}", u128::from_parts(864691128455135232, 0));
+    fn test_mix() {
+        assert_eq!(u128::from_parts(1 << 63, 0).mix(), u128::from_parts(0x0acdf5e787415ffc, 0xd3808049f3ad424a));
+        assert_eq!(u128::from_parts(0x0123456789abcdef, 0x0011223344556677).mix(),
+                   u128::from_parts(0x18737455340d22e8, 0x78871526a59b5a04));
     }
 
     #[test]
-    fn test_octal() {
-        assert_fmt_eq!("0", 1,
-                       "{:o}", u128::new(0));
-        assert_fmt_eq!("351462366146756037170", 21,
-                       "{:o}", u128::new(4208138189379485304));
-        assert_fmt_eq!("7000263630010212417200", 22,
-                       "{:o}", u128::from_parts(3, 9229698078115241600));
-        assert_fmt_eq!("3465177151267706210351536216110755202064135", 43,
-                       "{:o}", u128::from_parts(16620520452737763444, 15533412710015854685));
-        assert_fmt_eq!("3777777777777777777777777777777777777777777", 43,
-                       "{:o}", u128::from_parts(18446744073709551615, 18446744073709551615));
-        assert_fmt_eq!("2000000000000000000000000000000000000000000", 43,
-                       "{:o}", u128::from_parts(9223372036854775808, 0));
+    fn test_avalanche() {
+        let inputs = [
+            u128::from_parts(0x0123456789abcdef, 0x0011223344556677),
+            u128::from_parts(0, 0x2a),
+            u128::max_value(),
+        ];
+        for input in &inputs {
+            let base = input.mix();
+            let mut total = 0;
+            for bit in 0..128u32 {
+                let flipped = (*input ^ (ONE << bit)).mix();
+                let diff = (base ^ flipped).count_ones();
+                assert!(diff >= 24 && diff <= 104, "bit {} of {:?} flips only {} bits", bit, input, diff);
+                total += diff;
+            }
+            let average = total / 128;
+            assert!(average >= 60 && average <= 68, "average flipped bits is {}", average);
+        }
     }
 }
 
 //}}}
 
-//{{{ Sum, Product
+//{{{ Serde
+
+// Human-readable formats get a decimal string, since many of them (e.g. JSON) cannot represent
+// every 128-bit integer as a number. Binary formats get the 16 bytes in little endian. The `{lo, hi}`
+// struct written by older versions is still accepted, see `serde_legacy`.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+    use serde_crate::{Serialize, Serializer, Deserialize, Deserializer};
+    use serde_crate::de::{self, Visitor, SeqAccess, MapAccess, Unexpected};
+    use serde_legacy::{self, Legacy, CompactVisitor};
+    use u128::u128;
+    #[cfg(extprim_has_stable_i128)] use compiler_rt::builtins::{U128, I128};
 
-impl Sum for u128 {
-    fn sum<I>(iter: I) -> Self
-        where I: Iterator<Item = Self>
-    {
-        iter.fold(ZERO, Add::add)
+    impl Serialize for u128 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                self.to_le_bytes().serialize(serializer)
+            }
+        }
     }
-}
 
-impl Product for u128 {
-    fn product<I>(iter: I) -> Self
-        where I: Iterator<Item = Self>
-    {
-        iter.fold(ONE, Mul::mul)
+    impl<'de> Deserialize<'de> for u128 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(U128Visitor)
+            } else {
+                CompactVisitor::deserialize(deserializer)
+            }
+        }
     }
-}
 
-impl<'a> Sum<&'a u128> for u128 {
-    fn sum<I>(iter: I) -> Self
-        where I: Iterator<Item = &'a Self>
-    {
-        iter.fold(ZERO, |acc, elem| acc + *elem)
+    impl Legacy for u128 {
+        type Part = u64;
+        type Bytes = [u8; 16];
+        const EXPECTING: &'static str = "an unsigned 128-bit integer";
+
+        fn from_legacy_parts(lo: u64, hi: u64) -> u128 {
+            u128::from_parts(hi, lo)
+        }
+
+        fn from_le_bytes(bytes: [u8; 16]) -> u128 {
+            u128::from_le_bytes(bytes)
+        }
     }
-}
 
-impl<'a> Product<&'a u128> for u128 {
-    fn product<I>(iter: I) -> Self
-        where I: Iterator<Item = &'a Self>
-    {
-        iter.fold(ONE, |acc, elem| acc * *elem)
+    struct U128Visitor;
+
+    impl<'de> Visitor<'de> for U128Visitor {
+        type Value = u128;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(u128::EXPECTING)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<u128, E> {
+            value.parse().map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<u128, E> {
+            Ok(u128::new(value))
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<u128, E> {
+            if value >= 0 {
+                Ok(u128::new(value as u64))
+            } else {
+                Err(E::invalid_value(Unexpected::Signed(value), &self))
+            }
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_u128<E: de::Error>(self, value: U128) -> Result<u128, E> {
+            Ok(u128::from_built_in(value))
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_i128<E: de::Error>(self, value: I128) -> Result<u128, E> {
+            if value >= 0 {
+                Ok(u128::from_built_in(value as U128))
+            } else {
+                Err(E::invalid_value(Unexpected::Other("negative integer"), &self))
+            }
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<u128, A::Error> {
+            serde_legacy::visit_seq(seq, &self)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<u128, A::Error> {
+            serde_legacy::visit_map(map)
+        }
     }
 }
 
-#[cfg(test)]
-mod iter_tests {
-    use u128::{u128, ZERO, ONE, MIN, MAX};
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use serde_test::{assert_tokens, assert_de_tokens, assert_de_tokens_error, Configure, Readable, Token};
+    use u128::u128;
 
     #[test]
-    fn test_sum() {
-        // Sum<u128>
-        assert_eq!(ZERO, Vec::<u128>::new().into_iter().sum());
-        assert_eq!(ZERO, vec![ZERO, ZERO, ZERO].into_iter().sum());
-        assert_eq!(ONE, vec![ONE].into_iter().sum());
-        assert_eq!(u128::from(3u64), vec![ONE, ONE, ONE].into_iter().sum());
-        assert_eq!(MAX, vec![MAX].into_iter().sum());
-        assert_eq!(MIN, vec![MIN].into_iter().sum());
-        assert_eq!(u128::from_parts(7, 42), vec![u128::from_parts(7, 42)].into_iter().sum());
-
-        // Sum<&'a u128>
-        assert_eq!(ZERO, [].iter().sum());
-        assert_eq!(ZERO, [ZERO, ZERO, ZERO].iter().sum());
-        assert_eq!(ONE, [ONE].iter().sum());
-        assert_eq!(u128::from(3u64), [ONE, ONE, ONE].iter().sum());
-        assert_eq!(MAX, [MAX].iter().sum());
-        assert_eq!(MIN, [MIN].iter().sum());
-        assert_eq!(u128::from_parts(7, 42), [u128::from_parts(7, 42)].iter().sum());
+    fn test_readable() {
+        assert_tokens(&u128::new(1234).readable(), &[Token::Str("1234")]);
+        assert_tokens(&u128::max_value().readable(), &[Token::Str("340282366920938463463374607431768211455")]);
+        assert_de_tokens(&u128::new(1234).readable(), &[Token::U64(1234)]);
+        assert_de_tokens(&u128::new(1234).readable(), &[Token::I64(1234)]);
+        assert_de_tokens_error::<Readable<u128>>(&[Token::I64(-1)],
+                                                 "invalid value: integer `-1`, expected an unsigned 128-bit integer");
+        assert_de_tokens_error::<Readable<u128>>(&[Token::Str("-1")], "invalid digit found in string");
+        assert_de_tokens_error::<Readable<u128>>(&[Token::Str("340282366920938463463374607431768211456")],
+                                                 "number too large to fit in target type");
     }
 
     #[test]
-    fn test_product() {
-        // Product<u128>
-        assert_eq!(ONE, Vec::<u128>::new().into_iter().product());
-        assert_eq!(ONE, vec![ONE, ONE, ONE, ONE, ONE, ONE].into_iter().product());
-        assert_eq!(ZERO, vec![ONE, MAX, ZERO].into_iter().product());
-        assert_eq!(MAX, vec![MAX].into_iter().product());
-        assert_eq!(MAX, vec![ONE, MAX].into_iter().product());
-        assert_eq!(MIN, vec![MIN].into_iter().product());
-        assert_eq!(MIN, vec![ONE, MIN].into_iter().product());
-        assert_eq!(MAX, vec![
-            u128::from(3u64),
-            u128::from(5u64),
-            u128::from(17u64),
-            u128::from(257u64),
-            u128::from(641u64),
-            u128::from(65537u64),
-            u128::from(274177u64),
-            u128::from(6700417u64),
-            u128::from(67280421310721u64),
-        ].into_iter().product());
+    fn test_compact() {
+        let value = u128::from_parts(0x0f0e0d0c0b0a0908, 0x0706050403020100);
+        let mut tokens = vec![Token::Tuple { len: 16 }];
+        tokens.extend((0..16).map(Token::U8));
+        tokens.push(Token::TupleEnd);
+        assert_tokens(&value.compact(), &tokens);
+    }
 
-        // Product<&'a u128>
-        assert_eq!(ONE, [].iter().product());
-        assert_eq!(ONE, [ONE, ONE, ONE, ONE, ONE, ONE].iter().product());
-        assert_eq!(ZERO, [ONE, MAX, ZERO].iter().product());
-        assert_eq!(MAX, [MAX].iter().product());
-        assert_eq!(MAX, [ONE, MAX].iter().product());
-        assert_eq!(MIN, [MIN].iter().product());
-        assert_eq!(MIN, [ONE, MIN].iter().product());
-        assert_eq!(MAX, [
-            u128::from(3u64),
-            u128::from(5u64),
-            u128::from(17u64),
-            u128::from(257u64),
-            u128::from(641u64),
-            u128::from(65537u64),
-            u128::from(274177u64),
-            u128::from(6700417u64),
-            u128::from(67280421310721u64),
-        ].iter().product());
+    #[test]
+    fn test_legacy() {
+        let value = u128::from_parts(2, 1);
+        let map = [
+            Token::Struct { name: "u128", len: 2 },
+            Token::Str("lo"), Token::U64(1),
+            Token::Str("hi"), Token::U64(2),
+            Token::StructEnd,
+        ];
+        assert_de_tokens(&value.readable(), &map);
+        assert_de_tokens(&value.compact(), &map);
+        assert_de_tokens(&value.readable(), &[Token::Seq { len: Some(2) }, Token::U64(1), Token::U64(2), Token::SeqEnd]);
+        assert_de_tokens_error::<Readable<u128>>(&[
+            Token::Map { len: None },
+            Token::Str("lo"), Token::U64(1),
+            Token::Str("lo"), Token::U64(1),
+        ], "duplicate field `lo`");
+        assert_de_tokens_error::<Readable<u128>>(&[Token::Map { len: None }, Token::Str("mid")],
+                                                 "unknown field `mid`, expected `lo` or `hi`");
     }
 }
 
 //}}}
-
@@ -0,0 +1,1606 @@
+//! Unsigned 256-bit integer.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::num::ParseIntError;
+use std::ops::*;
+use std::str::FromStr;
+
+#[cfg(feature="rand")] use rand::Rng;
+#[cfg(feature="rand")] use rand::distributions::{Standard, Distribution};
+use num_traits::{Bounded, Zero, One, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv};
+#[cfg(feature="const-traits")] use num_traits::{ConstZero, ConstOne};
+
+use error;
+use format_buffer::FormatBuffer;
+use u128::{u128, u64_long_mul};
+use compiler_rt::udivmod_limbs;
+use i256::i256;
+use traits::Wrapping;
+
+//{{{ Structure
+
+/// Number of bits an unsigned 256-bit number occupies.
+pub const BITS: usize = 256;
+
+/// Number of bytes an unsigned 256-bit number occupies.
+pub const BYTES: usize = 32;
+
+/// The smallest unsigned 256-bit integer (0).
+pub const MIN: u256 = u256 { lo: ::u128::MIN, hi: ::u128::MIN };
+
+/// The largest unsigned 256-bit integer (`2**256 - 1`).
+pub const MAX: u256 = u256 { lo: ::u128::MAX, hi: ::u128::MAX };
+
+/// The constant 0.
+pub const ZERO: u256 = MIN;
+
+/// The constant 1.
+pub const ONE: u256 = u256 { lo: ::u128::ONE, hi: ::u128::ZERO };
+
+/// An unsigned 256-bit number.
+#[derive(Default, Copy, Clone, Hash, PartialEq, Eq)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct u256 {
+    /// The lower 128-bit of the number.
+    #[doc(hidden)]
+    #[cfg(target_endian="little")]
+    pub lo: u128,
+
+    /// The higher 128-bit of the number.
+    #[doc(hidden)]
+    pub hi: u128,
+
+    /// The lower 128-bit of the number.
+    #[doc(hidden)]
+    #[cfg(target_endian="big")]
+    pub lo: u128,
+}
+
+impl u256 {
+    /// Constructs a new 256-bit integer from a 128-bit integer.
+    pub fn new(lo: u128) -> u256 {
+        u256 { lo: lo, hi: ::u128::ZERO }
+    }
+
+    /// Constructs a new 256-bit integer from the high-128-bit and low-128-bit parts.
+    ///
+    /// The new integer can be considered as `hi * 2**128 + lo`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::u256::u256;
+    ///
+    /// let number = u256::from_parts(u128::new(1), u128::zero());
+    /// assert_eq!(format!("{}", number), "340282366920938463463374607431768211456");
+    /// ```
+    pub fn from_parts(hi: u128, lo: u128) -> u256 {
+        u256 { lo: lo, hi: hi }
+    }
+
+    /// Fetches the lower-128-bit of the number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::u256::u256;
+    ///
+    /// let number = u256::from_parts(u128::new(3), u128::new(7));
+    /// assert_eq!(number.low128(), u128::new(7));
+    /// ```
+    pub fn low128(self) -> u128 {
+        self.lo
+    }
+
+    /// Fetches the higher-128-bit of the number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::u256::u256;
+    ///
+    /// let number = u256::from_parts(u128::new(3), u128::new(7));
+    /// assert_eq!(number.high128(), u128::new(3));
+    /// ```
+    pub fn high128(self) -> u128 {
+        self.hi
+    }
+
+    /// Returns the 64-bit limbs of this number, least significant first.
+    fn to_limbs(self) -> [u64; 4] {
+        [self.lo.lo, self.lo.hi, self.hi.lo, self.hi.hi]
+    }
+
+    /// Constructs a number from 64-bit limbs, least significant first.
+    fn from_limbs(limbs: [u64; 4]) -> u256 {
+        u256 {
+            lo: u128::from_parts(limbs[1], limbs[0]),
+            hi: u128::from_parts(limbs[3], limbs[2]),
+        }
+    }
+
+    /// Converts this number to signed with wrapping.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    /// use extprim::i256::i256;
+    ///
+    /// assert_eq!(u256::max_value().as_i256(), -i256::one());
+    /// assert_eq!(i256::new_i64(-1).as_u256(), u256::max_value());
+    /// ```
+    pub fn as_i256(self) -> i256 {
+        i256(self)
+    }
+}
+
+#[cfg(test)]
+mod structure_tests {
+    use u128::u128;
+    use u256::u256;
+
+    #[test]
+    fn test_parts() {
+        let a = u256::from_parts(u128::from_parts(1, 2), u128::from_parts(3, 4));
+        assert_eq!(a.high128(), u128::from_parts(1, 2));
+        assert_eq!(a.low128(), u128::from_parts(3, 4));
+        assert_eq!(u256::new(u128::new(5)), u256::from_parts(u128::zero(), u128::new(5)));
+    }
+}
+
+//}}}
+
+//{{{ Rand
+
+#[cfg(feature="rand")]
+impl Distribution<u256> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u256 {
+        let (lo, hi) = rng.gen();
+        u256::from_parts(hi, lo)
+    }
+}
+
+//}}}
+
+//{{{ Add, Sub
+
+impl u256 {
+    /// Wrapping (modular) addition. Computes `self + other`, wrapping around at the boundary of
+    /// the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(5).wrapping_add(u256::new_u64(6)), u256::new_u64(11));
+    /// assert_eq!(u256::max_value().wrapping_add(u256::one()), u256::zero());
+    /// ```
+    pub fn wrapping_add(self, other: u256) -> u256 {
+        self.overflowing_add(other).0
+    }
+
+    /// Wrapping (modular) subtraction. Computes `self - other`, wrapping around at the boundary of
+    /// the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(6).wrapping_sub(u256::new_u64(5)), u256::one());
+    /// assert_eq!(u256::new_u64(5).wrapping_sub(u256::new_u64(6)), u256::max_value());
+    /// ```
+    pub fn wrapping_sub(self, other: u256) -> u256 {
+        self.overflowing_sub(other).0
+    }
+
+    /// Calculates `self + other`.
+    ///
+    /// Returns a tuple of the addition along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(6).overflowing_add(u256::new_u64(13)), (u256::new_u64(19), false));
+    /// assert_eq!(u256::max_value().overflowing_add(u256::one()), (u256::zero(), true));
+    /// ```
+    pub fn overflowing_add(self, other: u256) -> (u256, bool) {
+        let (lo, lo_carry) = self.lo.overflowing_add(other.lo);
+        let (hi, hi_carry_1) = self.hi.overflowing_add(u128::new(lo_carry as u64));
+        let (hi, hi_carry_2) = hi.overflowing_add(other.hi);
+        (u256::from_parts(hi, lo), hi_carry_1 || hi_carry_2)
+    }
+
+    /// Calculates `self - other`.
+    ///
+    /// Returns a tuple of the subtraction along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(6).overflowing_sub(u256::new_u64(5)), (u256::one(), false));
+    /// assert_eq!(u256::new_u64(5).overflowing_sub(u256::new_u64(6)), (u256::max_value(), true));
+    /// ```
+    pub fn overflowing_sub(self, other: u256) -> (u256, bool) {
+        let (lo, lo_borrow) = self.lo.overflowing_sub(other.lo);
+        let (hi, hi_borrow_1) = self.hi.overflowing_sub(u128::new(lo_borrow as u64));
+        let (hi, hi_borrow_2) = hi.overflowing_sub(other.hi);
+        (u256::from_parts(hi, lo), hi_borrow_1 || hi_borrow_2)
+    }
+
+    /// Saturating integer addition. Computes `self + other`, saturating at the numeric bounds
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(13).saturating_add(u256::new_u64(7)), u256::new_u64(20));
+    /// assert_eq!(u256::max_value().saturating_add(u256::one()), u256::max_value());
+    /// ```
+    pub fn saturating_add(self, other: u256) -> u256 {
+        self.checked_add(other).unwrap_or(MAX)
+    }
+
+    /// Saturating integer subtraction. Computes `self - other`, saturating at the numeric bounds
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(91).saturating_sub(u256::new_u64(13)), u256::new_u64(78));
+    /// assert_eq!(u256::new_u64(13).saturating_sub(u256::new_u64(91)), u256::zero());
+    /// ```
+    pub fn saturating_sub(self, other: u256) -> u256 {
+        self.checked_sub(other).unwrap_or(ZERO)
+    }
+
+    /// Wrapping (modular) negation. Computes `-self`, wrapping around at the boundary of the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::zero().wrapping_neg(), u256::zero());
+    /// assert_eq!(u256::one().wrapping_neg(), u256::max_value());
+    /// ```
+    pub fn wrapping_neg(self) -> u256 {
+        ONE.wrapping_add(!self)
+    }
+}
+
+forward_symmetric! {
+    /// Checked integer addition. Computes `self + other`, returning `None` if overflow occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(5).checked_add(u256::new_u64(8)), Some(u256::new_u64(13)));
+    /// assert_eq!(u256::max_value().checked_add(u256::max_value()), None);
+    /// ```
+    impl Add(add, checked_add, wrapping_add, overflowing_add) for u256
+}
+forward_symmetric! {
+    /// Checked integer subtraction. Computes `self - other`, returning `None` if underflow
+    /// occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(8).checked_sub(u256::new_u64(5)), Some(u256::new_u64(3)));
+    /// assert_eq!(u256::new_u64(5).checked_sub(u256::new_u64(8)), None);
+    /// ```
+    impl Sub(sub, checked_sub, wrapping_sub, overflowing_sub) for u256
+}
+forward_assign!(AddAssign(add_assign, add) for u256);
+forward_assign!(SubAssign(sub_assign, sub) for u256);
+
+impl Neg for Wrapping<u256> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Wrapping(self.0.wrapping_neg())
+    }
+}
+
+impl CheckedAdd for u256 {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Self::checked_add(*self, *other)
+    }
+}
+
+impl CheckedSub for u256 {
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Self::checked_sub(*self, *other)
+    }
+}
+
+#[cfg(test)]
+mod add_sub_tests {
+    use u128::u128;
+    use u256::{u256, ZERO, ONE, MAX};
+
+    #[test]
+    fn test_add() {
+        assert_eq!(u256::new(u128::max_value()) + ONE, u256::from_parts(u128::one(), u128::zero()));
+        assert_eq!(u256::from_parts(u128::new(3), u128::max_value()) + u256::new(u128::new(2)),
+                    u256::from_parts(u128::new(4), u128::one()));
+        assert_eq!(MAX.checked_add(ONE), None);
+        assert_eq!(MAX.overflowing_add(MAX), (MAX - ONE, true));
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(u256::from_parts(u128::one(), u128::zero()) - ONE, u256::new(u128::max_value()));
+        assert_eq!(ZERO.checked_sub(ONE), None);
+        assert_eq!(ZERO.wrapping_sub(ONE), MAX);
+        assert_eq!(ZERO.saturating_sub(ONE), ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected="arithmetic operation overflowed")]
+    #[cfg(debug_assertions)]
+    fn test_add_overflow() {
+        let _ = MAX + ONE;
+    }
+}
+
+//}}}
+
+//{{{ PartialOrd, Ord
+
+impl PartialOrd for u256 {
+    fn partial_cmp(&self, other: &u256) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for u256 {
+    fn cmp(&self, other: &u256) -> Ordering {
+        (self.hi, self.lo).cmp(&(other.hi, other.lo))
+    }
+}
+
+#[cfg(test)]
+mod cmp_tests {
+    use u128::u128;
+    use u256::u256;
+
+    #[test]
+    fn test_cmp() {
+        let small = u256::from_parts(u128::zero(), u128::max_value());
+        let large = u256::from_parts(u128::one(), u128::zero());
+        assert!(small < large);
+        assert!(large > small);
+        assert!(u256::zero() < u256::one());
+        assert!(u256::max_value() >= large);
+    }
+}
+
+//}}}
+
+//{{{ Not, BitAnd, BitOr, BitXor
+
+impl Not for u256 {
+    type Output = Self;
+    fn not(self) -> Self {
+        u256 { lo: !self.lo, hi: !self.hi }
+    }
+}
+
+impl BitAnd for u256 {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        u256 { lo: self.lo & other.lo, hi: self.hi & other.hi }
+    }
+}
+
+impl BitOr for u256 {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        u256 { lo: self.lo | other.lo, hi: self.hi | other.hi }
+    }
+}
+
+impl BitXor for u256 {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        u256 { lo: self.lo ^ other.lo, hi: self.hi ^ other.hi }
+    }
+}
+
+impl Not for Wrapping<u256> {
+    type Output = Self;
+    fn not(self) -> Self {
+        Wrapping(!self.0)
+    }
+}
+
+impl BitAnd for Wrapping<u256> {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Wrapping(self.0 & other.0)
+    }
+}
+
+impl BitOr for Wrapping<u256> {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Wrapping(self.0 | other.0)
+    }
+}
+
+impl BitXor for Wrapping<u256> {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self {
+        Wrapping(self.0 ^ other.0)
+    }
+}
+
+forward_assign!(BitAndAssign(bitand_assign, bitand) for u256);
+forward_assign!(BitOrAssign(bitor_assign, bitor) for u256);
+forward_assign!(BitXorAssign(bitxor_assign, bitxor) for u256);
+
+//}}}
+
+//{{{ Shl, Shr
+
+impl u256 {
+    /// Panic-free bitwise shift-left; yields `self << (shift % 256)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::one().wrapping_shl(128), u256::from_parts(u128::one(), u128::zero()));
+    /// assert_eq!(u256::one().wrapping_shl(257), u256::new_u64(2));
+    /// ```
+    pub fn wrapping_shl(self, shift: u32) -> u256 {
+        let shift = shift & 255;
+        if shift == 0 {
+            self
+        } else if shift >= 128 {
+            u256 { lo: ::u128::ZERO, hi: self.lo.wrapping_shl(shift - 128) }
+        } else {
+            u256 {
+                lo: self.lo.wrapping_shl(shift),
+                hi: self.hi.wrapping_shl(shift) | self.lo.wrapping_shr(128 - shift),
+            }
+        }
+    }
+
+    /// Panic-free bitwise shift-right; yields `self >> (shift % 256)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::from_parts(u128::one(), u128::zero()).wrapping_shr(128), u256::one());
+    /// assert_eq!(u256::new_u64(4).wrapping_shr(257), u256::new_u64(2));
+    /// ```
+    pub fn wrapping_shr(self, shift: u32) -> u256 {
+        let shift = shift & 255;
+        if shift == 0 {
+            self
+        } else if shift >= 128 {
+            u256 { lo: self.hi.wrapping_shr(shift - 128), hi: ::u128::ZERO }
+        } else {
+            u256 {
+                lo: self.lo.wrapping_shr(shift) | self.hi.wrapping_shl(128 - shift),
+                hi: self.hi.wrapping_shr(shift),
+            }
+        }
+    }
+
+    /// Shifts `self` left by `other` bits.
+    ///
+    /// Returns a tuple of the shifted version of `self` along with a boolean indicating whether
+    /// the shift value was larger than or equal to the number of bits (256). If the shift value
+    /// is too large, then value is masked by 255, and this value is then used to perform the
+    /// shift.
+    pub fn overflowing_shl(self, other: u32) -> (u256, bool) {
+        (self.wrapping_shl(other), other >= 256)
+    }
+
+    /// Shifts `self` right by `other` bits.
+    ///
+    /// Returns a tuple of the shifted version of `self` along with a boolean indicating whether
+    /// the shift value was larger than or equal to the number of bits (256). If the shift value
+    /// is too large, then value is masked by 255, and this value is then used to perform the
+    /// shift.
+    pub fn overflowing_shr(self, other: u32) -> (u256, bool) {
+        (self.wrapping_shr(other), other >= 256)
+    }
+}
+
+forward_shift! {
+    /// Checked shift left. Computes `self << other`, returning `None` if the shift is larger than
+    /// or equal to the number of bits in `self` (256).
+    impl Shl(shl, checked_shl, wrapping_shl, overflowing_shl) for u256
+}
+forward_shift! {
+    /// Checked shift right. Computes `self >> other`, returning `None` if the shift is larger than
+    /// or equal to the number of bits in `self` (256).
+    impl Shr(shr, checked_shr, wrapping_shr, overflowing_shr) for u256
+}
+forward_assign!(ShlAssign<u8|u16|u32|u64|usize|i8|i16|i32|i64|isize>(shl_assign, shl) for u256);
+forward_assign!(ShrAssign<u8|u16|u32|u64|usize|i8|i16|i32|i64|isize>(shr_assign, shr) for u256);
+
+#[cfg(test)]
+mod shift_tests {
+    use u128::u128;
+    use u256::{u256, ONE};
+
+    #[test]
+    fn test_shl() {
+        let a = u256::from_parts(u128::from_parts(1, 2), u128::from_parts(3, 4));
+        assert_eq!(a << 0u32, a);
+        assert_eq!(a << 64u32, u256::from_parts(u128::from_parts(2, 3), u128::from_parts(4, 0)));
+        assert_eq!(a << 192u32, u256::from_parts(u128::from_parts(4, 0), u128::zero()));
+        assert_eq!(a << 1u32, u256::from_parts(u128::from_parts(2, 4), u128::from_parts(6, 8)));
+        assert_eq!(ONE << 255u32, u256::from_parts(u128::from_parts(1 << 63, 0), u128::zero()));
+        assert_eq!(ONE.checked_shl(256), None);
+    }
+
+    #[test]
+    fn test_shr() {
+        let a = u256::from_parts(u128::from_parts(1, 2), u128::from_parts(3, 4));
+        assert_eq!(a >> 0u32, a);
+        assert_eq!(a >> 64u32, u256::from_parts(u128::from_parts(0, 1), u128::from_parts(2, 3)));
+        assert_eq!(a >> 192u32, u256::new(u128::new(1)));
+        assert_eq!(a >> 129u32, u256::new(u128::from_parts(0, 0x8000000000000001)));
+        assert_eq!(ONE.checked_shr(256), None);
+    }
+
+    #[test]
+    #[should_panic(expected="shift operation overflowed")]
+    #[cfg(debug_assertions)]
+    fn test_shl_overflow() {
+        let _ = ONE << 256u32;
+    }
+}
+
+//}}}
+
+//{{{ Mul
+
+/// Computes the full product of two 128-bit numbers as little-endian 64-bit limbs, by schoolbook
+/// multiplication.
+pub(crate) fn mul_128_portable(left: u128, right: u128) -> [u64; 4] {
+    let a = [left.lo, left.hi];
+    let b = [right.lo, right.hi];
+    let mut r = [0u64; 4];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let t = u64_long_mul(x, y)
+                .wrapping_add(u128::new(r[i + j]))
+                .wrapping_add(u128::new(carry));
+            r[i + j] = t.lo;
+            carry = t.hi;
+        }
+        r[i + 2] = carry;
+    }
+    r
+}
+
+impl u256 {
+    /// Computes the full product of two unsigned 128-bit integers. The result is never truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u128::u128;
+    /// use extprim::u256::u256;
+    ///
+    /// let a = u128::max_value();
+    /// assert_eq!(u256::mul_128(a, a), u256::from_parts(a - u128::one(), u128::one()));
+    /// ```
+    pub fn mul_128(left: u128, right: u128) -> u256 {
+        u256::from_limbs(::cpu::mul_128(left, right))
+    }
+
+    /// Wrapping (modular) multiplication. Computes `self * other`, wrapping around at the boundary
+    /// of the type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(6).wrapping_mul(u256::new_u64(9)), u256::new_u64(54));
+    ///
+    /// let a = u256::max_value() - u256::new_u64(2);
+    /// let b = u256::max_value() - u256::new_u64(4);
+    /// assert_eq!(a.wrapping_mul(b), u256::new_u64(15));
+    /// ```
+    pub fn wrapping_mul(self, other: u256) -> u256 {
+        // Truncated 4-limb schoolbook multiplication. Only the 10 partial products contributing
+        // to the lower 256 bits are computed, and those landing on the top limb only need their
+        // lower 64 bits. Karatsuba does not reduce the number of multiplications at this size (see
+        // the `mul_bench` module).
+        let a = self.to_limbs();
+        let b = other.to_limbs();
+        let mut r = [0u64; 4];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &y) in b.iter().take(4 - i).enumerate() {
+                if i + j == 3 {
+                    r[3] = r[3].wrapping_add(x.wrapping_mul(y)).wrapping_add(carry);
+                } else {
+                    let t = u64_long_mul(x, y)
+                        .wrapping_add(u128::new(r[i + j]))
+                        .wrapping_add(u128::new(carry));
+                    r[i + j] = t.lo;
+                    carry = t.hi;
+                }
+            }
+        }
+        u256::from_limbs(r)
+    }
+
+    /// Calculates the multiplication of `self` and `other`.
+    ///
+    /// Returns a tuple of the multiplication along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(6).overflowing_mul(u256::new_u64(9)), (u256::new_u64(54), false));
+    ///
+    /// let a = u256::max_value() - u256::new_u64(2);
+    /// let b = u256::max_value() - u256::new_u64(4);
+    /// assert_eq!(a.overflowing_mul(b), (u256::new_u64(15), true));
+    /// ```
+    pub fn overflowing_mul(self, other: u256) -> (u256, bool) {
+        let (hi, hi_overflow_mul) = if self.hi == ::u128::ZERO {
+            other.hi.overflowing_mul(self.lo)
+        } else if other.hi == ::u128::ZERO {
+            self.hi.overflowing_mul(other.lo)
+        } else {
+            (self.hi.wrapping_mul(other.lo).wrapping_add(self.lo.wrapping_mul(other.hi)), true)
+        };
+
+        let mut low = u256::mul_128(self.lo, other.lo);
+        let (hi, hi_overflow_add) = low.hi.overflowing_add(hi);
+        low.hi = hi;
+
+        (low, hi_overflow_mul || hi_overflow_add)
+    }
+
+    /// Saturating integer multiplication. Computes `self * other`, saturating at the numeric
+    /// bounds instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(6).saturating_mul(u256::new_u64(9)), u256::new_u64(54));
+    /// assert_eq!(u256::max_value().saturating_mul(u256::new_u64(2)), u256::max_value());
+    /// ```
+    pub fn saturating_mul(self, other: u256) -> u256 {
+        self.checked_mul(other).unwrap_or(MAX)
+    }
+}
+
+forward_symmetric! {
+    /// Checked integer multiplication. Computes `self * other`, returning `None` if overflow
+    /// occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(5).checked_mul(u256::new_u64(8)), Some(u256::new_u64(40)));
+    /// assert_eq!(u256::max_value().checked_mul(u256::new_u64(2)), None);
+    /// ```
+    impl Mul(mul, checked_mul, wrapping_mul, overflowing_mul) for u256
+}
+forward_assign!(MulAssign(mul_assign, mul) for u256);
+
+impl CheckedMul for u256 {
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Self::checked_mul(*self, *other)
+    }
+}
+
+#[cfg(test)]
+mod mul_tests {
+    use u128::u128;
+    use u256::{u256, ZERO, ONE, MAX};
+
+    #[test]
+    fn test_mul_128() {
+        assert_eq!(u256::mul_128(u128::zero(), u128::max_value()), ZERO);
+        assert_eq!(u256::mul_128(u128::from_parts(1, 0), u128::from_parts(1, 0)),
+                    u256::from_parts(u128::one(), u128::zero()));
+        assert_eq!(u256::mul_128(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152),
+                                  u128::from_parts(0xd3a0c9d4b3ecc1a0, 0x0b5226f1d22d28a9)),
+                    u256::from_parts(u128::from_parts(0x191948a439ff210d, 0xbe442de631e564a9),
+                                     u128::from_parts(0xc3132ed68c68478c, 0xd42ffb6043261f22)));
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = u256::from_parts(u128::new(3), u128::new(5));
+        assert_eq!(a * u256::new(u128::new(7)), u256::from_parts(u128::new(21), u128::new(35)));
+        assert_eq!(a.checked_mul(u256::from_parts(u128::one(), u128::zero())), None);
+        assert_eq!(MAX.wrapping_mul(MAX), ONE);
+        assert_eq!(MAX.overflowing_mul(ONE), (MAX, false));
+        assert_eq!(u256::from_parts(u128::one(), u128::zero()).overflowing_mul(u256::from_parts(u128::one(), u128::zero())),
+                    (ZERO, true));
+    }
+
+    #[test]
+    fn test_wrapping_mul_limbs() {
+        assert_eq!(u256::from_parts(u128::from_parts(0xd23f0824128b2f33, 0x0c5c7fd0a6a3a450), u128::from_parts(0x6513270e269e0d37, 0xf2a74de452e6b438))
+                    .wrapping_mul(u256::from_parts(u128::from_parts(0x36f675cc81e74ef5, 0xe8e25d940ed90475), u128::from_parts(0x9531985d5d9dc9f8, 0x1818e811892f902b))),
+                   u256::from_parts(u128::from_parts(0x65f99d1ee00db3dc, 0x2ae0851bd5090f34), u128::from_parts(0x1bd44e608453d25b, 0x1517ea80c067c568)));
+        assert_eq!(u256::from_parts(u128::from_parts(0x8d116ece1738f7d9, 0x3d9c172411e20b8f), u128::from_parts(0x6b0d549b6f03675a, 0x1600a35a099950d8))
+                    .wrapping_mul(u256::from_parts(u128::from_parts(0xa170b33839263059, 0xf28c105d1fb17c23), u128::from_parts(0x90c192cfd3ac94af, 0x0f21ddb66cad4a26))),
+                   u256::from_parts(u128::from_parts(0xc7ecb566d0c16b61, 0xa3aaeb1811319d01), u128::from_parts(0xfa0d282761d9bd88, 0x14d7626a80187010)));
+        assert_eq!(u256::from_parts(u128::from_parts(0x0cb1e29c658cda14, 0x95e60af593bd04cf), u128::from_parts(0x0fd630f1f29d0da9, 0x953f48f1a09f76b5))
+                    .wrapping_mul(u256::from_parts(u128::from_parts(0x6b4cb2424a23d596, 0x2217beaddbc496cb), u128::from_parts(0x8e81973e0becd7b0, 0x3898d190f9ebdacc))),
+                   u256::from_parts(u128::from_parts(0x2f74db17e1de53fa, 0x0f0e9b92a25a6284), u128::from_parts(0xbc8b9d96e8900768, 0x6aa9fafbcf4fba3c)));
+    }
+}
+
+#[cfg(all(test, extprim_channel="unstable"))]
+mod mul_bench {
+    use u128::u128;
+    use u256::u256;
+    use test::{Bencher, black_box};
+
+    // composition of u128 operations, the original implementation.
+    fn naive_wrapping_mul(left: u256, right: u256) -> u256 {
+        let mut low = u256::mul_128(left.lo, right.lo);
+        let ad = left.hi.wrapping_mul(right.lo);
+        let bc = left.lo.wrapping_mul(right.hi);
+        low.hi = low.hi.wrapping_add(ad).wrapping_add(bc);
+        low
+    }
+
+    // Karatsuba on the 128-bit halves. The cross term is only needed modulo 2^128, so
+    // `(a0 + a1)(b0 + b1) - a0 b0 - a1 b1` can be computed with wrapping arithmetic.
+    fn karatsuba_wrapping_mul(left: u256, right: u256) -> u256 {
+        let z0 = u256::mul_128(left.lo, right.lo);
+        let z2 = left.hi.wrapping_mul(right.hi);
+        let m = left.lo.wrapping_add(left.hi).wrapping_mul(right.lo.wrapping_add(right.hi));
+        let cross = m.wrapping_sub(z0.lo).wrapping_sub(z2);
+        u256::from_parts(z0.hi.wrapping_add(cross), z0.lo)
+    }
+
+    const LEFT: u256 = u256 {
+        lo: u128 { lo: 0xf2a74de452e6b438, hi: 0x6513270e269e0d37 },
+        hi: u128 { lo: 0x0c5c7fd0a6a3a450, hi: 0xd23f0824128b2f33 },
+    };
+    const RIGHT: u256 = u256 {
+        lo: u128 { lo: 0x1818e811892f902b, hi: 0x9531985d5d9dc9f8 },
+        hi: u128 { lo: 0xe8e25d940ed90475, hi: 0x36f675cc81e74ef5 },
+    };
+
+    #[bench]
+    fn bench_mul_schoolbook(bencher: &mut Bencher) {
+        bencher.iter(|| black_box(black_box(LEFT).wrapping_mul(black_box(RIGHT))));
+    }
+
+    #[bench]
+    fn bench_mul_naive(bencher: &mut Bencher) {
+        assert_eq!(naive_wrapping_mul(LEFT, RIGHT), LEFT.wrapping_mul(RIGHT));
+        bencher.iter(|| black_box(naive_wrapping_mul(black_box(LEFT), black_box(RIGHT))));
+    }
+
+    #[bench]
+    fn bench_mul_karatsuba(bencher: &mut Bencher) {
+        assert_eq!(karatsuba_wrapping_mul(LEFT, RIGHT), LEFT.wrapping_mul(RIGHT));
+        bencher.iter(|| black_box(karatsuba_wrapping_mul(black_box(LEFT), black_box(RIGHT))));
+    }
+}
+
+//}}}
+
+//{{{ Div, Rem
+
+/// Computes `(n / d, n % d)` where `d` is non-zero.
+fn udivmod256(n: u256, d: u256) -> (u256, u256) {
+    if n.hi == ::u128::ZERO && d.hi == ::u128::ZERO {
+        let (q, r) = ::u128::div_rem(n.lo, d.lo);
+        return (u256::new(q), u256::new(r));
+    }
+    if n < d {
+        return (ZERO, n);
+    }
+
+    if d.hi == ::u128::ZERO && d.lo.hi == 0 {
+        // Short division by a 64-bit number, one 64-bit digit at a time.
+        let d = d.lo.lo;
+        let digits = [n.hi.hi, n.hi.lo, n.lo.hi, n.lo.lo];
+        let mut q = [0u64; 4];
+        let mut rem = 0u64;
+        for (i, &digit) in digits.iter().enumerate() {
+            let (dq, dr) = ::u128::div_rem(u128::from_parts(rem, digit), u128::new(d));
+            q[i] = dq.lo;
+            rem = dr.lo;
+        }
+        return (u256::from_parts(u128::from_parts(q[0], q[1]), u128::from_parts(q[2], q[3])),
+                u256::new(u128::new(rem)));
+    }
+
+    // Long division by a divisor of 2 to 4 64-bit digits.
+    let mut u = [n.lo.lo, n.lo.hi, n.hi.lo, n.hi.hi];
+    let mut v = [d.lo.lo, d.lo.hi, d.hi.lo, d.hi.hi];
+    let mut q = [0u64; 4];
+    let v_len = v.iter().rposition(|&limb| limb != 0).unwrap() + 1;
+    udivmod_limbs(&mut u, &mut v[.. v_len], &mut q[.. 5 - v_len]);
+    (u256::from_parts(u128::from_parts(q[3], q[2]), u128::from_parts(q[1], q[0])),
+     u256::from_parts(u128::from_parts(u[3], u[2]), u128::from_parts(u[1], u[0])))
+}
+
+
+impl u256 {
+    /// Wrapping (modular) division. Computes `self / other`. Wrapped division on unsigned types is
+    /// just normal division.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(100).wrapping_div(u256::new_u64(8)), u256::new_u64(12));
+    /// ```
+    pub fn wrapping_div(self, other: u256) -> u256 {
+        self.checked_div(other)
+            .unwrap_or_else(|| panic!("attempted to divide by zero"))
+    }
+
+    /// Wrapping (modular) remainder. Computes `self % other`. Wrapped remainder calculation on
+    /// unsigned types is just the regular remainder calculation.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(100).wrapping_rem(u256::new_u64(8)), u256::new_u64(4));
+    /// ```
+    pub fn wrapping_rem(self, other: u256) -> u256 {
+        self.checked_rem(other)
+            .unwrap_or_else(|| panic!("attempted remainder with a divisor of zero"))
+    }
+
+    /// Checked integer division. Computes `self / other`, returning `None` if `other == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(100).checked_div(u256::new_u64(8)), Some(u256::new_u64(12)));
+    /// assert_eq!(u256::new_u64(100).checked_div(u256::zero()), None);
+    /// ```
+    pub fn checked_div(self, other: u256) -> Option<u256> {
+        if other == ZERO {
+            None
+        } else {
+            Some(udivmod256(self, other).0)
+        }
+    }
+
+    /// Checked integer remainder. Computes `self % other`, returning `None` if `other == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(100).checked_rem(u256::new_u64(8)), Some(u256::new_u64(4)));
+    /// assert_eq!(u256::new_u64(100).checked_rem(u256::zero()), None);
+    /// ```
+    pub fn checked_rem(self, other: u256) -> Option<u256> {
+        if other == ZERO {
+            None
+        } else {
+            Some(udivmod256(self, other).1)
+        }
+    }
+
+    /// Calculates the divisor when `self` is divided by `other`.
+    ///
+    /// Returns a tuple of the divisor along with a boolean indicating whether
+    /// an arithmetic overflow would occur. Note that for unsigned integers
+    /// overflow never occurs, so the second value is always `false`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(100).overflowing_div(u256::new_u64(8)), (u256::new_u64(12), false));
+    /// ```
+    pub fn overflowing_div(self, other: u256) -> (u256, bool) {
+        (self.wrapping_div(other), false)
+    }
+
+    /// Calculates the remainder when `self` is divided by `other`.
+    ///
+    /// Returns a tuple of the remainder along with a boolean indicating whether
+    /// an arithmetic overflow would occur. Note that for unsigned integers
+    /// overflow never occurs, so the second value is always `false`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(100).overflowing_rem(u256::new_u64(8)), (u256::new_u64(4), false));
+    /// ```
+    pub fn overflowing_rem(self, other: u256) -> (u256, bool) {
+        (self.wrapping_rem(other), false)
+    }
+}
+
+impl Div for u256 {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        self.wrapping_div(other)
+    }
+}
+
+impl Rem for u256 {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        self.wrapping_rem(other)
+    }
+}
+
+impl Div for Wrapping<u256> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Wrapping(self.0.wrapping_div(other.0))
+    }
+}
+
+impl Rem for Wrapping<u256> {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        Wrapping(self.0.wrapping_rem(other.0))
+    }
+}
+
+forward_assign!(DivAssign(div_assign, div) for u256);
+forward_assign!(RemAssign(rem_assign, rem) for u256);
+
+impl CheckedDiv for u256 {
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        Self::checked_div(*self, *other)
+    }
+}
+
+/// Computes the divisor and remainder simultaneously. Returns `(a/b, a%b)`.
+///
+/// # Panics
+///
+/// This function will panic if `denominator` is 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use extprim::u256::{div_rem, u256};
+///
+/// assert_eq!(div_rem(u256::new_u64(100), u256::new_u64(8)), (u256::new_u64(12), u256::new_u64(4)));
+/// ```
+pub fn div_rem(numerator: u256, denominator: u256) -> (u256, u256) {
+    if denominator == ZERO {
+        panic!("attempted to divide by zero");
+    } else {
+        udivmod256(numerator, denominator)
+    }
+}
+
+#[cfg(test)]
+mod div_rem_tests {
+    use u128::u128;
+    use u256::{u256, ZERO, ONE, MAX, div_rem};
+
+    #[test]
+    fn test_overflowing_div_rem() {
+        let ten = u256::new_u64(10);
+        assert_eq!(MAX.overflowing_div(ten), (MAX / ten, false));
+        assert_eq!(MAX.overflowing_rem(ten), (u256::new_u64(5), false));
+        assert_eq!(ZERO.overflowing_div(MAX), (ZERO, false));
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_overflowing_div_by_zero() {
+        ONE.overflowing_div(ZERO);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        assert_eq!(div_rem(MAX, MAX), (ONE, ZERO));
+        assert_eq!(div_rem(MAX, u256::new(u128::new(10))),
+                    (u256::from_parts(u128::from_parts(0x1999999999999999, 0x9999999999999999),
+                                      u128::from_parts(0x9999999999999999, 0x9999999999999999)),
+                     u256::new(u128::new(5))));
+        assert_eq!(div_rem(MAX, u256::new(u128::max_value())),
+                    (u256::from_parts(u128::one(), u128::one()), ZERO));
+        assert_eq!(div_rem(u256::from_parts(u128::from_parts(0x1e5c7801b0e575f7, 0x53f09dac5b28f152),
+                                            u128::from_parts(0xd3a0c9d4b3ecc1a0, 0x0b5226f1d22d28a9)),
+                            u256::from_parts(u128::new(0x2d2d7bcb0d6dc5c7), u128::from_parts(0x6a27b4a0a970c0e1, 0x4c1e8f2a8899c1fb))),
+                    (u256::new(u128::new(0xac0ad1fb01982ec8)),
+                     u256::from_parts(u128::new(0x125678946e87c552), u128::from_parts(0xcb2234bde9d388ab, 0x3c01d805512a8291))));
+        assert_eq!(div_rem(u256::new(u128::new(7)), MAX), (ZERO, u256::new(u128::new(7))));
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_div_by_zero() {
+        let _ = ONE / ZERO;
+    }
+}
+
+//}}}
+
+//{{{ Casting
+
+impl u256 {
+    /// Constructs a new 256-bit integer from a 64-bit integer.
+    pub fn new_u64(lo: u64) -> u256 {
+        u256::new(u128::new(lo))
+    }
+}
+
+impl From<u8> for u256 {
+    fn from(arg: u8) -> Self {
+        u256::new_u64(arg as u64)
+    }
+}
+
+impl From<u16> for u256 {
+    fn from(arg: u16) -> Self {
+        u256::new_u64(arg as u64)
+    }
+}
+
+impl From<u32> for u256 {
+    fn from(arg: u32) -> Self {
+        u256::new_u64(arg as u64)
+    }
+}
+
+impl From<u64> for u256 {
+    fn from(arg: u64) -> Self {
+        u256::new_u64(arg)
+    }
+}
+
+impl From<u128> for u256 {
+    fn from(arg: u128) -> Self {
+        u256::new(arg)
+    }
+}
+
+//}}}
+
+//{{{ Constants
+
+impl u256 {
+    /// Returns the smallest unsigned 256-bit integer (0).
+    pub fn min_value() -> u256 { MIN }
+
+    /// Returns the largest unsigned 256-bit integer (`2**256 - 1`).
+    pub fn max_value() -> u256 { MAX }
+
+    /// Returns the constant 0.
+    pub fn zero() -> u256 { ZERO }
+
+    /// Returns the constant 1.
+    pub fn one() -> u256 { ONE }
+
+    /// The smallest value that can be represented by this integer type.
+    pub const MIN: u256 = MIN;
+
+    /// The largest value that can be represented by this integer type.
+    pub const MAX: u256 = MAX;
+
+    /// The constant 0.
+    pub const ZERO: u256 = ZERO;
+
+    /// The constant 1.
+    pub const ONE: u256 = ONE;
+}
+
+impl Bounded for u256 {
+    fn min_value() -> Self { MIN }
+    fn max_value() -> Self { MAX }
+}
+
+impl Zero for u256 {
+    fn zero() -> Self { ZERO }
+    fn is_zero(&self) -> bool { *self == ZERO }
+}
+
+impl One for u256 {
+    fn one() -> Self { ONE }
+}
+
+#[cfg(feature="const-traits")]
+impl ConstZero for u256 {
+    const ZERO: Self = ZERO;
+}
+
+#[cfg(feature="const-traits")]
+impl ConstOne for u256 {
+    const ONE: Self = ONE;
+}
+
+//}}}
+
+//{{{ PrimInt
+
+impl u256 {
+    /// Returns the number of ones in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::max_value().count_ones(), 256);
+    /// ```
+    pub fn count_ones(self) -> u32 {
+        self.lo.count_ones() + self.hi.count_ones()
+    }
+
+    /// Returns the number of zeros in the binary representation of `self` (including leading
+    /// zeros).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::one().count_zeros(), 255);
+    /// ```
+    pub fn count_zeros(self) -> u32 {
+        self.lo.count_zeros() + self.hi.count_zeros()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::one().leading_zeros(), 255);
+    /// assert_eq!(u256::zero().leading_zeros(), 256);
+    /// ```
+    pub fn leading_zeros(self) -> u32 {
+        if self.hi == ::u128::ZERO {
+            128 + self.lo.leading_zeros()
+        } else {
+            self.hi.leading_zeros()
+        }
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::u256::u256;
+    ///
+    /// assert_eq!(u256::new_u64(8).trailing_zeros(), 3);
+    /// assert_eq!(u256::zero().trailing_zeros(), 256);
+    /// ```
+    pub fn trailing_zeros(self) -> u32 {
+        if self.lo == ::u128::ZERO {
+            128 + self.hi.trailing_zeros()
+        } else {
+            self.lo.trailing_zeros()
+        }
+    }
+}
+
+//}}}
+
+//{{{ FromStr, FromStrRadix
+
+impl u256 {
+    /// Converts a string slice in a given base to an integer.
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Arguments
+    ///
+    /// - src: A string slice
+    /// - radix: The base to use. Must lie in the range [2 ... 36].
+    ///
+    /// # Return value
+    ///
+    /// `Err(ParseIntError)` if the string did not represent a valid number. Otherwise, `Ok(n)`
+    /// where `n` is the integer represented by `src`.
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<u256, ParseIntError> {
+        assert!(radix >= 2 && radix <= 36,
+                "from_str_radix_int: must lie in the range `[2, 36]` - found {}",
+                radix);
+
+        if src.is_empty() {
+            return Err(error::empty());
+        }
+
+        let mut result = ZERO;
+        let radix256 = u256::new_u64(radix as u64);
+
+        for c in src.chars() {
+            let digit = c.to_digit(radix).ok_or_else(error::invalid_digit)?;
+            let int_result = result.checked_mul(radix256).ok_or_else(error::overflow)?;
+            result = int_result.checked_add(u256::new_u64(digit as u64)).ok_or_else(error::overflow)?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl FromStr for u256 {
+    type Err = ParseIntError;
+
+    fn from_str(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_radix(src, 10)
+    }
+}
+
+#[cfg(test)]
+mod from_str_tests {
+    use u128::u128;
+    use u256::{u256, MAX, ZERO};
+    use error;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(ZERO), "0".parse::<u256>());
+        assert_eq!(Ok(MAX), "115792089237316195423570985008687907853269984665640564039457584007913129639935".parse::<u256>());
+        assert_eq!(Err(error::overflow()), "115792089237316195423570985008687907853269984665640564039457584007913129639936".parse::<u256>());
+        assert_eq!(Ok(u256::from_parts(u128::one(), u128::zero())), u256::from_str_radix("100000000000000000000000000000000", 16));
+        assert_eq!(Err(error::invalid_digit()), u256::from_str_radix("12a", 10));
+        assert_eq!(Err(error::empty()), u256::from_str_radix("", 10));
+    }
+}
+
+//}}}
+
+//{{{ Binary, LowerHex, UpperHex, String, Show
+
+impl fmt::Display for u256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == ::u128::ZERO {
+            self.lo.fmt(formatter)
+        } else {
+            const TEN19: u64 = 10000000000000000000;
+
+            let mut chunks = [0u64; 5];
+            let mut count = 0;
+            let mut n = *self;
+            while n != ZERO {
+                let (q, r) = udivmod256(n, u256::new_u64(TEN19));
+                chunks[count] = r.lo.lo;
+                count += 1;
+                n = q;
+            }
+
+            let mut buffer = [0u8; 78];
+            let mut buf = FormatBuffer::new(&mut buffer);
+            write!(&mut buf, "{}", chunks[count - 1])?;
+            for chunk in chunks[.. count - 1].iter().rev() {
+                write!(&mut buf, "{:019}", chunk)?;
+            }
+
+            formatter.pad_integral(true, "", unsafe { buf.into_str() })
+        }
+    }
+}
+
+impl fmt::Debug for u256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "u256!({})", self)
+    }
+}
+
+impl fmt::Binary for u256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == ::u128::ZERO {
+            self.lo.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; 256];
+            let mut buf = FormatBuffer::new(&mut buffer);
+
+            write!(&mut buf, "{:b}{:0128b}", self.hi, self.lo)?;
+            formatter.pad_integral(true, "0b", unsafe { buf.into_str() })
+        }
+    }
+}
+
+impl fmt::Octal for u256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mask = ::u128::u128::suffix_mask(126);
+
+        let lo = self.lo & mask;
+        let mid = (self.hi << 2u32 | self.lo >> 126u32) & mask;
+        let hi = self.hi >> 124u32;
+
+        let mut buffer = [0u8; 86];
+        let mut buf = FormatBuffer::new(&mut buffer);
+
+        if hi != ::u128::ZERO {
+            write!(&mut buf, "{:o}{:042o}{:042o}", hi, mid, lo)?;
+        } else if mid != ::u128::ZERO {
+            write!(&mut buf, "{:o}{:042o}", mid, lo)?;
+        } else {
+            return lo.fmt(formatter);
+        }
+
+        formatter.pad_integral(true, "0o", unsafe { buf.into_str() })
+    }
+}
+
+impl fmt::LowerHex for u256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == ::u128::ZERO {
+            self.lo.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; 64];
+            let mut buf = FormatBuffer::new(&mut buffer);
+
+            write!(&mut buf, "{:x}{:032x}", self.hi, self.lo)?;
+            formatter.pad_integral(true, "0x", unsafe { buf.into_str() })
+        }
+    }
+}
+
+impl fmt::UpperHex for u256 {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.hi == ::u128::ZERO {
+            self.lo.fmt(formatter)
+        } else {
+            let mut buffer = [0u8; 64];
+            let mut buf = FormatBuffer::new(&mut buffer);
+
+            write!(&mut buf, "{:X}{:032X}", self.hi, self.lo)?;
+            formatter.pad_integral(true, "0x", unsafe { buf.into_str() })
+        }
+    }
+}
+
+#[cfg(test)]
+mod show_tests {
+    use u128::u128;
+    use u256::{u256, MAX};
+
+    #[test]
+    fn test_display() {
+        assert_fmt_eq!("0", 1, "{}", u256::zero());
+        assert_fmt_eq!("340282366920938463463374607431768211455", 39, "{}", u256::new(u128::max_value()));
+        assert_fmt_eq!("340282366920938463463374607431768211456", 39, "{}", u256::from_parts(u128::one(), u128::zero()));
+        assert_fmt_eq!("115792089237316195423570985008687907853269984665640564039457584007913129639935", 78,
+                        "{}", MAX);
+        assert_fmt_eq!("1000000000000000000000000000000000000000000000000000000000000000000000000000", 76,
+                        "{}", u256::from_parts(u128::from_parts(0x0235fadd81c2822b, 0xb3f07877973d50f2),
+                                               u128::from_parts(0x8bf22a31be8ee800, 0)));
+        assert_fmt_eq!("u256!(340282366920938463463374607431768211456)", 46, "{:?}", u256::from_parts(u128::one(), u128::zero()));
+        assert_fmt_eq!("+000340282366920938463463374607431768211456", 43, "{:+043}", u256::from_parts(u128::one(), u128::zero()));
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_fmt_eq!("ff", 2, "{:x}", u256::new_u64(255));
+        assert_fmt_eq!("100000000000000000000000000000000", 33, "{:x}", u256::from_parts(u128::one(), u128::zero()));
+        assert_fmt_eq!("0x1000000000000000000000000000000AB", 35, "{:#X}", u256::from_parts(u128::one(), u128::new(0xab)));
+        assert_fmt_eq!("0b11", 4, "{:#b}", u256::new_u64(3));
+        assert_fmt_eq!("0o17", 4, "{:#o}", u256::new_u64(15));
+        assert_fmt_eq!("4000000000000000000000000000000000000000000", 43, "{:o}", u256::from_parts(u128::one(), u128::zero()));
+        assert_fmt_eq!("17777777777777777777777777777777777777777777777777777777777777777777777777777777777777", 86,
+                        "{:o}", MAX);
+        assert_fmt_eq!("100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                        129, "{:b}", u256::from_parts(u128::one(), u128::zero()));
+    }
+}
+
+//}}}
+
+//{{{ Serde
+
+// Like `u128`, human-readable formats get a decimal string and binary formats get the 32 bytes in
+// little endian.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+    use serde_crate::{Serialize, Serializer, Deserialize, Deserializer};
+    use serde_crate::de::{self, Visitor, SeqAccess, MapAccess, Unexpected};
+    use serde_legacy::{self, Legacy, CompactVisitor};
+    use traits::ExtInt;
+    use u128::u128;
+    use u256::u256;
+    #[cfg(extprim_has_stable_i128)] use compiler_rt::builtins::{U128, I128};
+
+    impl Serialize for u256 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                ExtInt::to_le_bytes(*self).serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for u256 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(U256Visitor)
+            } else {
+                CompactVisitor::deserialize(deserializer)
+            }
+        }
+    }
+
+    impl Legacy for u256 {
+        type Part = u128;
+        type Bytes = [u8; 32];
+        const EXPECTING: &'static str = "an unsigned 256-bit integer";
+
+        fn from_legacy_parts(lo: u128, hi: u128) -> u256 {
+            u256::from_parts(hi, lo)
+        }
+
+        fn from_le_bytes(bytes: [u8; 32]) -> u256 {
+            ExtInt::from_le_bytes(bytes)
+        }
+    }
+
+    struct U256Visitor;
+
+    impl<'de> Visitor<'de> for U256Visitor {
+        type Value = u256;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(u256::EXPECTING)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<u256, E> {
+            value.parse().map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<u256, E> {
+            Ok(u256::new_u64(value))
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<u256, E> {
+            if value >= 0 {
+                Ok(u256::new_u64(value as u64))
+            } else {
+                Err(E::invalid_value(Unexpected::Signed(value), &self))
+            }
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_u128<E: de::Error>(self, value: U128) -> Result<u256, E> {
+            Ok(u256::new(u128::from_built_in(value)))
+        }
+
+        #[cfg(extprim_has_stable_i128)]
+        fn visit_i128<E: de::Error>(self, value: I128) -> Result<u256, E> {
+            if value >= 0 {
+                Ok(u256::new(u128::from_built_in(value as U128)))
+            } else {
+                Err(E::invalid_value(Unexpected::Other("negative integer"), &self))
+            }
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<u256, A::Error> {
+            serde_legacy::visit_seq(seq, &self)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<u256, A::Error> {
+            serde_legacy::visit_map(map)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use serde_test::{assert_tokens, assert_de_tokens, assert_de_tokens_error, Configure, Readable, Token};
+    use u128::u128;
+    use u256::u256;
+
+    #[test]
+    fn test_readable() {
+        assert_tokens(&u256::new_u64(1234).readable(), &[Token::Str("1234")]);
+        assert_tokens(&u256::max_value().readable(),
+                      &[Token::Str("115792089237316195423570985008687907853269984665640564039457584007913129639935")]);
+        assert_de_tokens(&u256::new_u64(1234).readable(), &[Token::U64(1234)]);
+        assert_de_tokens_error::<Readable<u256>>(&[Token::I64(-1)],
+                                                 "invalid value: integer `-1`, expected an unsigned 256-bit integer");
+    }
+
+    #[test]
+    fn test_compact() {
+        let value = u256::from_parts(u128::from_parts(0x1f1e1d1c1b1a1918, 0x1716151413121110),
+                                     u128::from_parts(0x0f0e0d0c0b0a0908, 0x0706050403020100));
+        let mut tokens = vec![Token::Tuple { len: 32 }];
+        tokens.extend((0..32).map(Token::U8));
+        tokens.push(Token::TupleEnd);
+        assert_tokens(&value.compact(), &tokens);
+    }
+
+    #[test]
+    fn test_legacy() {
+        let value = u256::from_parts(u128::new(2), u128::new(1));
+        assert_de_tokens(&value.readable(), &[
+            Token::Struct { name: "u256", len: 2 },
+            Token::Str("lo"), Token::Str("1"),
+            Token::Str("hi"), Token::Str("2"),
+            Token::StructEnd,
+        ]);
+        assert_de_tokens_error::<Readable<u256>>(&[Token::Map { len: None }, Token::Str("lo"), Token::U64(1), Token::MapEnd],
+                                                 "missing field `hi`");
+
+        let mut tokens = vec![Token::Struct { name: "u256", len: 2 }, Token::Str("lo"), Token::Tuple { len: 16 }];
+        tokens.push(Token::U8(1));
+        tokens.extend((1..16).map(|_| Token::U8(0)));
+        tokens.extend(vec![Token::TupleEnd, Token::Str("hi"), Token::Tuple { len: 16 }]);
+        tokens.push(Token::U8(2));
+        tokens.extend((1..16).map(|_| Token::U8(0)));
+        tokens.extend(vec![Token::TupleEnd, Token::StructEnd]);
+        assert_de_tokens(&value.compact(), &tokens);
+    }
+}
+
+//}}}
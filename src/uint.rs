@@ -0,0 +1,1000 @@
+//! Unsigned fixed-width integers of arbitrary bit width.
+//!
+//! `Uint<BITS, LIMBS>` is a `BITS`-bit unsigned integer stored in `LIMBS` 64-bit limbs. Stable
+//! Rust cannot compute the array length from `BITS` yet, so `LIMBS` must be given explicitly; it
+//! is checked to be exactly `(BITS + 63) / 64` at compile time. The aliases [`U96`], [`U160`],
+//! [`U192`], [`U320`] and [`U512`] spell out some common widths.
+//!
+//! Arithmetic behaves like the primitive types: the `+`, `-` and `*` operators panic on overflow
+//! in debug mode and wrap in release mode, and the `checked_*`, `wrapping_*` and
+//! `overflowing_*` methods make the choice explicit. Bits above `BITS` are always kept zero, so
+//! no manual masking is needed.
+//!
+//! `Uint<128, 2>` converts losslessly from and to [`u128`](../u128/struct.u128.html), and `U96`
+//! from and to [`narrow::u96`](../narrow/struct.u96.html).
+//!
+//! This module requires Rust 1.57 or above.
+//!
+//! [`U96`]: type.U96.html
+//! [`U160`]: type.U160.html
+//! [`U192`]: type.U192.html
+//! [`U320`]: type.U320.html
+//! [`U512`]: type.U512.html
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::*;
+use std::str::FromStr;
+
+use error;
+use u128::u128;
+use narrow::u96;
+use compiler_rt::builtins::U128;
+use compiler_rt::udivmod_limbs;
+
+//{{{ Structure
+
+/// An unsigned `BITS`-bit integer, stored in `LIMBS` 64-bit limbs.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Uint<const BITS: usize, const LIMBS: usize> {
+    limbs: [u64; LIMBS],
+}
+
+/// A 96-bit unsigned integer.
+pub type U96 = Uint<96, 2>;
+/// A 160-bit unsigned integer.
+pub type U160 = Uint<160, 3>;
+/// A 192-bit unsigned integer.
+pub type U192 = Uint<192, 3>;
+/// A 320-bit unsigned integer.
+pub type U320 = Uint<320, 5>;
+/// A 512-bit unsigned integer.
+pub type U512 = Uint<512, 8>;
+
+impl<const BITS: usize, const LIMBS: usize> Uint<BITS, LIMBS> {
+    /// Number of bits this integer occupies.
+    pub const BITS: usize = BITS;
+
+    /// Number of 64-bit limbs backing this integer.
+    pub const LIMBS: usize = LIMBS;
+
+    /// The mask of the valid bits in the most significant limb.
+    const MASK: u64 = {
+        assert!(BITS > 0 && LIMBS * 64 >= BITS && LIMBS * 64 < BITS + 64,
+                "LIMBS must be equal to (BITS + 63) / 64");
+        !0 >> (LIMBS * 64 - BITS)
+    };
+
+    /// The constant 0.
+    pub const ZERO: Self = {
+        let _ = Self::MASK;
+        Uint { limbs: [0; LIMBS] }
+    };
+
+    /// The constant 1.
+    pub const ONE: Self = {
+        let mut limbs = Self::ZERO.limbs;
+        limbs[0] = 1;
+        Uint { limbs: limbs }
+    };
+
+    /// The largest value (`2**BITS - 1`).
+    pub const MAX: Self = {
+        let mut limbs = [!0; LIMBS];
+        limbs[LIMBS - 1] = Self::MASK;
+        Uint { limbs: limbs }
+    };
+
+    /// Constructs an integer from its limbs, least significant limb first. Bits above `BITS` are
+    /// discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// let a = U96::from_limbs([1, !0]);
+    /// assert_eq!(a.as_limbs(), &[1, 0xffffffff]);
+    /// ```
+    pub fn from_limbs(limbs: [u64; LIMBS]) -> Self {
+        let mut res = Uint { limbs: limbs };
+        res.limbs[LIMBS - 1] &= Self::MASK;
+        res
+    }
+
+    /// Constructs an integer from a 64-bit integer, truncating if `BITS < 64`.
+    pub fn from_u64(value: u64) -> Self {
+        let mut limbs = Self::ZERO.limbs;
+        limbs[0] = value;
+        Self::from_limbs(limbs)
+    }
+
+    /// Returns the limbs of this integer, least significant limb first.
+    pub fn as_limbs(&self) -> &[u64; LIMBS] {
+        &self.limbs
+    }
+
+    /// Returns whether this integer is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|&l| l == 0)
+    }
+
+    /// Returns the smallest value (0).
+    pub fn min_value() -> Self { Self::ZERO }
+
+    /// Returns the largest value (`2**BITS - 1`).
+    pub fn max_value() -> Self { Self::MAX }
+
+    /// Returns the constant 0.
+    pub fn zero() -> Self { Self::ZERO }
+
+    /// Returns the constant 1.
+    pub fn one() -> Self { Self::ONE }
+
+    /// Returns whether the most significant limb has bits above `BITS` set.
+    fn exceeds_mask(&self) -> bool {
+        self.limbs[LIMBS - 1] & !Self::MASK != 0
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Default for Uint<BITS, LIMBS> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+#[cfg(test)]
+mod structure_tests {
+    use uint::{Uint, U96, U160, U192, U320, U512};
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(U96::MAX.as_limbs(), &[!0, 0xffffffff]);
+        assert_eq!(U160::MAX.as_limbs(), &[!0, !0, 0xffffffff]);
+        assert_eq!(U192::MAX.as_limbs(), &[!0, !0, !0]);
+        assert_eq!(U320::MAX.as_limbs(), &[!0; 5]);
+        assert_eq!(U512::MAX.as_limbs(), &[!0; 8]);
+        assert_eq!(Uint::<256, 4>::ONE.as_limbs(), &[1, 0, 0, 0]);
+        assert_eq!(Uint::<64, 1>::MAX.as_limbs(), &[!0]);
+        assert_eq!(Uint::<1, 1>::MAX.as_limbs(), &[1]);
+        assert_eq!(Uint::<7, 1>::from_u64(0x1ff), Uint::<7, 1>::MAX);
+        assert_eq!(U96::BITS, 96);
+        assert_eq!(U96::LIMBS, 2);
+        assert!(U160::ZERO.is_zero());
+        assert_eq!(U160::default(), U160::ZERO);
+    }
+}
+
+//}}}
+
+//{{{ Add, Sub
+
+impl<const BITS: usize, const LIMBS: usize> Uint<BITS, LIMBS> {
+    /// Calculates `self + other`.
+    ///
+    /// Returns a tuple of the addition along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// assert_eq!(U96::from_u64(!0).overflowing_add(U96::ONE), (U96::from_limbs([0, 1]), false));
+    /// assert_eq!(U96::MAX.overflowing_add(U96::ONE), (U96::ZERO, true));
+    /// ```
+    pub fn overflowing_add(self, other: Self) -> (Self, bool) {
+        let mut res = Self::ZERO;
+        let mut carry = false;
+        for i in 0 .. LIMBS {
+            let (sum, c1) = self.limbs[i].overflowing_add(other.limbs[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            res.limbs[i] = sum;
+            carry = c1 || c2;
+        }
+        let overflow = carry || res.exceeds_mask();
+        res.limbs[LIMBS - 1] &= Self::MASK;
+        (res, overflow)
+    }
+
+    /// Calculates `self - other`.
+    ///
+    /// Returns a tuple of the subtraction along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// assert_eq!(U96::from_limbs([0, 1]).overflowing_sub(U96::ONE), (U96::from_u64(!0), false));
+    /// assert_eq!(U96::ZERO.overflowing_sub(U96::ONE), (U96::MAX, true));
+    /// ```
+    pub fn overflowing_sub(self, other: Self) -> (Self, bool) {
+        let mut res = Self::ZERO;
+        let mut borrow = false;
+        for i in 0 .. LIMBS {
+            let (diff, b1) = self.limbs[i].overflowing_sub(other.limbs[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            res.limbs[i] = diff;
+            borrow = b1 || b2;
+        }
+        res.limbs[LIMBS - 1] &= Self::MASK;
+        (res, borrow)
+    }
+
+    /// Wrapping (modular) addition. Computes `self + other`, wrapping around at `2**BITS`.
+    pub fn wrapping_add(self, other: Self) -> Self {
+        self.overflowing_add(other).0
+    }
+
+    /// Wrapping (modular) subtraction. Computes `self - other`, wrapping around at `2**BITS`.
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        self.overflowing_sub(other).0
+    }
+
+    /// Checked integer addition. Computes `self + other`, returning `None` if overflow occurred.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        match self.overflowing_add(other) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Checked integer subtraction. Computes `self - other`, returning `None` if underflow
+    /// occurred.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.overflowing_sub(other) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Saturating integer addition. Computes `self + other`, saturating at the numeric bounds
+    /// instead of overflowing.
+    pub fn saturating_add(self, other: Self) -> Self {
+        self.checked_add(other).unwrap_or(Self::MAX)
+    }
+
+    /// Saturating integer subtraction. Computes `self - other`, saturating at the numeric bounds
+    /// instead of overflowing.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        self.checked_sub(other).unwrap_or(Self::ZERO)
+    }
+
+    /// Wrapping (modular) negation. Computes `-self`, wrapping around at `2**BITS`.
+    pub fn wrapping_neg(self) -> Self {
+        Self::ZERO.wrapping_sub(self)
+    }
+}
+
+#[cfg(test)]
+mod add_sub_tests {
+    use uint::{U96, U160, Uint};
+
+    #[test]
+    fn test_add() {
+        assert_eq!(U160::from_limbs([!0, !0, 0]) + U160::ONE, U160::from_limbs([0, 0, 1]));
+        assert_eq!(U160::MAX.checked_add(U160::ONE), None);
+        assert_eq!(U96::MAX.wrapping_add(U96::MAX), U96::MAX - U96::ONE);
+        assert_eq!(U96::MAX.saturating_add(U96::ONE), U96::MAX);
+        assert_eq!(Uint::<3, 1>::from_u64(5).overflowing_add(Uint::<3, 1>::from_u64(4)),
+                    (Uint::<3, 1>::ONE, true));
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(U160::from_limbs([0, 0, 1]) - U160::ONE, U160::from_limbs([!0, !0, 0]));
+        assert_eq!(U96::ZERO.checked_sub(U96::ONE), None);
+        assert_eq!(U96::ONE.wrapping_neg(), U96::MAX);
+        assert_eq!(U96::ONE.saturating_sub(U96::MAX), U96::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected="arithmetic operation overflowed")]
+    #[cfg(debug_assertions)]
+    fn test_add_overflow() {
+        let _ = U96::MAX + U96::ONE;
+    }
+}
+
+//}}}
+
+//{{{ Mul
+
+impl<const BITS: usize, const LIMBS: usize> Uint<BITS, LIMBS> {
+    /// Calculates the multiplication of `self` and `other`.
+    ///
+    /// Returns a tuple of the multiplication along with a boolean indicating whether an arithmetic
+    /// overflow would occur. If an overflow would have occurred then the wrapped value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// let a = U96::from_u64(1 << 48);
+    /// assert_eq!(a.overflowing_mul(U96::from_u64(1 << 47)), (U96::from_limbs([0, 1 << 31]), false));
+    /// assert_eq!(a.overflowing_mul(a), (U96::ZERO, true));
+    /// ```
+    pub fn overflowing_mul(self, other: Self) -> (Self, bool) {
+        let mut res = Self::ZERO;
+        let mut overflow = false;
+        for i in 0 .. LIMBS {
+            let mut carry: U128 = 0;
+            for j in 0 .. LIMBS {
+                if i + j < LIMBS {
+                    let t = (self.limbs[i] as U128) * (other.limbs[j] as U128)
+                        + res.limbs[i + j] as U128
+                        + carry;
+                    res.limbs[i + j] = t as u64;
+                    carry = t >> 64;
+                } else if self.limbs[i] != 0 && other.limbs[j] != 0 {
+                    overflow = true;
+                }
+            }
+            overflow |= carry != 0;
+        }
+        overflow |= res.exceeds_mask();
+        res.limbs[LIMBS - 1] &= Self::MASK;
+        (res, overflow)
+    }
+
+    /// Wrapping (modular) multiplication. Computes `self * other`, wrapping around at `2**BITS`.
+    pub fn wrapping_mul(self, other: Self) -> Self {
+        self.overflowing_mul(other).0
+    }
+
+    /// Checked integer multiplication. Computes `self * other`, returning `None` if overflow
+    /// occurred.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        match self.overflowing_mul(other) {
+            (v, false) => Some(v),
+            (_, true) => None,
+        }
+    }
+
+    /// Saturating integer multiplication. Computes `self * other`, saturating at the numeric
+    /// bounds instead of overflowing.
+    pub fn saturating_mul(self, other: Self) -> Self {
+        self.checked_mul(other).unwrap_or(Self::MAX)
+    }
+}
+
+#[cfg(test)]
+mod mul_tests {
+    use uint::{U96, U192, U512, Uint};
+
+    #[test]
+    fn test_mul() {
+        let a = U192::from_limbs([!0, !0, 0]);
+        assert_eq!(a * U192::from_u64(2), U192::from_limbs([!1, !0, 1]));
+        assert_eq!(a.checked_mul(a), None);
+        assert_eq!(a.wrapping_mul(a), U192::from_limbs([1, 0, !1]));
+        assert_eq!(U192::MAX.wrapping_mul(U192::MAX), U192::ONE);
+        let b = U512::from_limbs([0, 0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(b * b, U512::from_limbs([0, 0, 0, 0, 0, 0, 1, 0]));
+        assert_eq!((b * b).checked_mul(b), None);
+        assert_eq!(U96::from_limbs([0, 1 << 31]).checked_mul(U96::from_u64(2)), None);
+        assert_eq!(U96::MAX.saturating_mul(U96::from_u64(3)), U96::MAX);
+        assert_eq!(Uint::<5, 1>::from_u64(5) * Uint::<5, 1>::from_u64(6), Uint::<5, 1>::from_u64(30));
+        assert_eq!(Uint::<5, 1>::from_u64(6).checked_mul(Uint::<5, 1>::from_u64(6)), None);
+    }
+}
+
+//}}}
+
+//{{{ Div, Rem
+
+impl<const BITS: usize, const LIMBS: usize> Uint<BITS, LIMBS> {
+    /// Divides `self` by a 64-bit integer, returning the quotient and the remainder.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `other` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// assert_eq!(U96::from_limbs([5, 1]).div_rem_u64(2), (U96::from_limbs([1 << 63 | 2, 0]), 1));
+    /// ```
+    pub fn div_rem_u64(self, other: u64) -> (Self, u64) {
+        if other == 0 {
+            panic!("attempted to divide by zero");
+        }
+        let mut res = Self::ZERO;
+        let mut rem: U128 = 0;
+        for i in (0 .. LIMBS).rev() {
+            let n = rem << 64 | self.limbs[i] as U128;
+            res.limbs[i] = (n / other as U128) as u64;
+            rem = n % other as U128;
+        }
+        (res, rem as u64)
+    }
+
+    /// Computes `(self / other, self % other)`, returning `None` if `other == 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U160;
+    ///
+    /// let a = U160::from_limbs([7, 0, 5]);
+    /// let b = U160::from_limbs([0, 2, 0]);
+    /// assert_eq!(a.checked_div_rem(b), Some((U160::from_limbs([1 << 63, 2, 0]), U160::from_u64(7))));
+    /// assert_eq!(a.checked_div_rem(U160::ZERO), None);
+    /// ```
+    pub fn checked_div_rem(self, other: Self) -> Option<(Self, Self)> {
+        if other.is_zero() {
+            return None;
+        }
+        if other.limbs[1 ..].iter().all(|&l| l == 0) {
+            let (q, r) = self.div_rem_u64(other.limbs[0]);
+            return Some((q, Self::from_u64(r)));
+        }
+        if self < other {
+            return Some((Self::ZERO, self));
+        }
+
+        // Long division by a divisor of at least 2 64-bit digits, sharing the kernel of `u256`.
+        let mut q = Self::ZERO;
+        let mut r = self;
+        let mut d = other.limbs;
+        let r_len = r.limbs.iter().rposition(|&l| l != 0).unwrap() + 1;
+        let d_len = d.iter().rposition(|&l| l != 0).unwrap() + 1;
+        udivmod_limbs(&mut r.limbs[.. r_len], &mut d[.. d_len], &mut q.limbs[.. r_len - d_len + 1]);
+        Some((q, r))
+    }
+
+    /// Computes `self * mul + add`, returning `None` if overflow occurred.
+    fn checked_mul_add_u64(self, mul: u64, add: u64) -> Option<Self> {
+        let mut res = Self::ZERO;
+        let mut carry = add as U128;
+        for i in 0 .. LIMBS {
+            let t = self.limbs[i] as U128 * mul as U128 + carry;
+            res.limbs[i] = t as u64;
+            carry = t >> 64;
+        }
+        if carry != 0 || res.exceeds_mask() {
+            None
+        } else {
+            Some(res)
+        }
+    }
+
+    /// Checked integer division. Computes `self / other`, returning `None` if `other == 0`.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.checked_div_rem(other).map(|(q, _)| q)
+    }
+
+    /// Checked integer remainder. Computes `self % other`, returning `None` if `other == 0`.
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        self.checked_div_rem(other).map(|(_, r)| r)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Div for Uint<BITS, LIMBS> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        self.checked_div(other).unwrap_or_else(|| panic!("attempted to divide by zero"))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Rem for Uint<BITS, LIMBS> {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        self.checked_rem(other).unwrap_or_else(|| panic!("attempted remainder with a divisor of zero"))
+    }
+}
+
+#[cfg(test)]
+mod div_rem_tests {
+    use uint::{Uint, U96, U192, U512};
+    use u128::u128;
+    use u256::u256;
+    use rng::Mcg128;
+
+    #[test]
+    fn test_div_rem() {
+        assert_eq!(U192::MAX / U192::MAX, U192::ONE);
+        assert_eq!(U192::MAX / U192::from_limbs([!0, !0, 0]), U192::from_limbs([0, 1, 0]));
+        assert_eq!(U192::MAX % U192::from_limbs([!0, !0, 0]), U192::from_u64(!0));
+        assert_eq!(U192::MAX % U192::from_limbs([0, 0, 1]), U192::from_limbs([!0, !0, 0]));
+        assert_eq!(U192::MAX.div_rem_u64(10), (U192::from_limbs([0x9999999999999999, 0x9999999999999999, 0x1999999999999999]), 5));
+        assert_eq!(U96::from_u64(7) / U96::MAX, U96::ZERO);
+        assert_eq!(U96::from_u64(7) % U96::MAX, U96::from_u64(7));
+    }
+
+    #[test]
+    fn test_div_rem_random() {
+        let mut rng = Mcg128::new(u128::new(0x5eed));
+        for i in 0..1000u32 {
+            let n = u256::from_parts(rng.next_u128(), rng.next_u128());
+            let d = u256::from_parts(rng.next_u128(), rng.next_u128()) >> (i as usize % 256) | u256::one();
+            let to_uint = |x: u256| Uint::<256, 4>::from_limbs([x.low128().low64(), x.low128().high64(), x.high128().low64(), x.high128().high64()]);
+            assert_eq!(to_uint(n).checked_div_rem(to_uint(d)), Some((to_uint(n / d), to_uint(n % d))));
+
+            let mut n = [0u64; 8];
+            let mut d = [0u64; 8];
+            for limb in &mut n {
+                *limb = rng.next_u64();
+            }
+            for limb in &mut d[.. 1 + i as usize % 8] {
+                *limb = rng.next_u64();
+            }
+            let (n, d) = (U512::from_limbs(n), U512::from_limbs(d) | U512::ONE);
+            let (q, r) = n.checked_div_rem(d).unwrap();
+            assert_eq!(q.checked_mul(d).and_then(|qd| qd.checked_add(r)), Some(n));
+            assert!(r < d);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected="attempted to divide by zero")]
+    fn test_div_by_zero() {
+        let _ = U96::ONE / U96::ZERO;
+    }
+}
+
+//}}}
+
+//{{{ Not, BitAnd, BitOr, BitXor
+
+impl<const BITS: usize, const LIMBS: usize> Not for Uint<BITS, LIMBS> {
+    type Output = Self;
+    fn not(mut self) -> Self {
+        for limb in &mut self.limbs {
+            *limb = !*limb;
+        }
+        self.limbs[LIMBS - 1] &= Self::MASK;
+        self
+    }
+}
+
+macro_rules! impl_bitwise {
+    ($tn:ident($name:ident), $atn:ident($aname:ident), $op:tt) => {
+        impl<const BITS: usize, const LIMBS: usize> $tn for Uint<BITS, LIMBS> {
+            type Output = Self;
+            fn $name(mut self, other: Self) -> Self {
+                for i in 0 .. LIMBS {
+                    self.limbs[i] $op other.limbs[i];
+                }
+                self
+            }
+        }
+
+        impl<const BITS: usize, const LIMBS: usize> $atn for Uint<BITS, LIMBS> {
+            fn $aname(&mut self, other: Self) {
+                *self = self.$name(other);
+            }
+        }
+    }
+}
+
+impl_bitwise!(BitAnd(bitand), BitAndAssign(bitand_assign), &=);
+impl_bitwise!(BitOr(bitor), BitOrAssign(bitor_assign), |=);
+impl_bitwise!(BitXor(bitxor), BitXorAssign(bitxor_assign), ^=);
+
+//}}}
+
+//{{{ Shl, Shr
+
+impl<const BITS: usize, const LIMBS: usize> Uint<BITS, LIMBS> {
+    /// Panic-free bitwise shift-left; yields `self << (shift % BITS)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// assert_eq!(U96::ONE.wrapping_shl(95), U96::from_limbs([0, 1 << 31]));
+    /// assert_eq!(U96::ONE.wrapping_shl(96), U96::ONE);
+    /// ```
+    pub fn wrapping_shl(self, shift: u32) -> Self {
+        let shift = shift as usize % BITS;
+        let limb_shift = shift / 64;
+        let bit_shift = shift % 64;
+        let mut res = Self::ZERO;
+        for i in limb_shift .. LIMBS {
+            res.limbs[i] = self.limbs[i - limb_shift] << bit_shift;
+            if bit_shift != 0 && i > limb_shift {
+                res.limbs[i] |= self.limbs[i - limb_shift - 1] >> (64 - bit_shift);
+            }
+        }
+        res.limbs[LIMBS - 1] &= Self::MASK;
+        res
+    }
+
+    /// Panic-free bitwise shift-right; yields `self >> (shift % BITS)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// assert_eq!(U96::MAX.wrapping_shr(95), U96::ONE);
+    /// assert_eq!(U96::MAX.wrapping_shr(96), U96::MAX);
+    /// ```
+    pub fn wrapping_shr(self, shift: u32) -> Self {
+        let shift = shift as usize % BITS;
+        let limb_shift = shift / 64;
+        let bit_shift = shift % 64;
+        let mut res = Self::ZERO;
+        for i in 0 .. LIMBS - limb_shift {
+            res.limbs[i] = self.limbs[i + limb_shift] >> bit_shift;
+            if bit_shift != 0 && i + limb_shift + 1 < LIMBS {
+                res.limbs[i] |= self.limbs[i + limb_shift + 1] << (64 - bit_shift);
+            }
+        }
+        res
+    }
+
+    /// Checked shift left. Computes `self << other`, returning `None` if the shift is larger than
+    /// or equal to `BITS`.
+    pub fn checked_shl(self, shift: u32) -> Option<Self> {
+        if shift as usize >= BITS {
+            None
+        } else {
+            Some(self.wrapping_shl(shift))
+        }
+    }
+
+    /// Checked shift right. Computes `self >> other`, returning `None` if the shift is larger than
+    /// or equal to `BITS`.
+    pub fn checked_shr(self, shift: u32) -> Option<Self> {
+        if shift as usize >= BITS {
+            None
+        } else {
+            Some(self.wrapping_shr(shift))
+        }
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Shl<u32> for Uint<BITS, LIMBS> {
+    type Output = Self;
+    fn shl(self, shift: u32) -> Self {
+        self.checked_shl(shift).unwrap_or_else(|| panic!("shift operation overflowed"))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Shr<u32> for Uint<BITS, LIMBS> {
+    type Output = Self;
+    fn shr(self, shift: u32) -> Self {
+        self.checked_shr(shift).unwrap_or_else(|| panic!("shift operation overflowed"))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> ShlAssign<u32> for Uint<BITS, LIMBS> {
+    fn shl_assign(&mut self, shift: u32) {
+        *self = *self << shift;
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> ShrAssign<u32> for Uint<BITS, LIMBS> {
+    fn shr_assign(&mut self, shift: u32) {
+        *self = *self >> shift;
+    }
+}
+
+#[cfg(test)]
+mod shift_tests {
+    use uint::{U160, U192};
+
+    #[test]
+    fn test_shift() {
+        let a = U192::from_limbs([1, 2, 3]);
+        assert_eq!(a << 64, U192::from_limbs([0, 1, 2]));
+        assert_eq!(a << 1, U192::from_limbs([2, 4, 6]));
+        assert_eq!(a << 65, U192::from_limbs([0, 2, 4]));
+        assert_eq!(a >> 64, U192::from_limbs([2, 3, 0]));
+        assert_eq!(a >> 1, U192::from_limbs([0, 1 << 63 | 1, 1]));
+        assert_eq!(a >> 129, U192::ONE);
+        assert_eq!(U160::MAX << 159, U160::from_limbs([0, 0, 1 << 31]));
+        assert_eq!(U160::MAX << 32, U160::from_limbs([!0 << 32, !0, !0 >> 32]));
+        assert_eq!(U160::ONE.checked_shl(160), None);
+        assert_eq!(U160::ONE.checked_shr(160), None);
+    }
+}
+
+//}}}
+
+//{{{ PartialOrd, Ord
+
+impl<const BITS: usize, const LIMBS: usize> PartialOrd for Uint<BITS, LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> Ord for Uint<BITS, LIMBS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+}
+
+//}}}
+
+//{{{ PrimInt
+
+impl<const BITS: usize, const LIMBS: usize> Uint<BITS, LIMBS> {
+    /// Returns the number of ones in the binary representation of `self`.
+    pub fn count_ones(self) -> u32 {
+        self.limbs.iter().map(|l| l.count_ones()).sum()
+    }
+
+    /// Returns the number of zeros in the binary representation of `self` (including leading
+    /// zeros).
+    pub fn count_zeros(self) -> u32 {
+        BITS as u32 - self.count_ones()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// assert_eq!(U96::ONE.leading_zeros(), 95);
+    /// assert_eq!(U96::ZERO.leading_zeros(), 96);
+    /// ```
+    pub fn leading_zeros(self) -> u32 {
+        let unused = (LIMBS * 64 - BITS) as u32;
+        for i in (0 .. LIMBS).rev() {
+            if self.limbs[i] != 0 {
+                return ((LIMBS - 1 - i) * 64) as u32 + self.limbs[i].leading_zeros() - unused;
+            }
+        }
+        BITS as u32
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of `self`.
+    pub fn trailing_zeros(self) -> u32 {
+        for i in 0 .. LIMBS {
+            if self.limbs[i] != 0 {
+                return (i * 64) as u32 + self.limbs[i].trailing_zeros();
+            }
+        }
+        BITS as u32
+    }
+}
+
+//}}}
+
+//{{{ Operators
+
+macro_rules! impl_arith {
+    ($tn:ident($name:ident, $oname:ident, $wname:ident), $atn:ident($aname:ident)) => {
+        impl<const BITS: usize, const LIMBS: usize> $tn for Uint<BITS, LIMBS> {
+            type Output = Self;
+            #[cfg(debug_assertions)]
+            fn $name(self, other: Self) -> Self {
+                match self.$oname(other) {
+                    (v, false) => v,
+                    (_, true) => panic!("arithmetic operation overflowed"),
+                }
+            }
+            #[cfg(not(debug_assertions))]
+            fn $name(self, other: Self) -> Self {
+                self.$wname(other)
+            }
+        }
+
+        impl<const BITS: usize, const LIMBS: usize> $atn for Uint<BITS, LIMBS> {
+            fn $aname(&mut self, other: Self) {
+                *self = self.$name(other);
+            }
+        }
+    }
+}
+
+impl_arith!(Add(add, overflowing_add, wrapping_add), AddAssign(add_assign));
+impl_arith!(Sub(sub, overflowing_sub, wrapping_sub), SubAssign(sub_assign));
+impl_arith!(Mul(mul, overflowing_mul, wrapping_mul), MulAssign(mul_assign));
+
+impl<const BITS: usize, const LIMBS: usize> DivAssign for Uint<BITS, LIMBS> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> RemAssign for Uint<BITS, LIMBS> {
+    fn rem_assign(&mut self, other: Self) {
+        *self = *self % other;
+    }
+}
+
+//}}}
+
+//{{{ Casting
+
+impl<const BITS: usize, const LIMBS: usize> From<u64> for Uint<BITS, LIMBS> {
+    /// Converts a 64-bit integer, truncating if `BITS < 64`.
+    fn from(arg: u64) -> Self {
+        Self::from_u64(arg)
+    }
+}
+
+impl From<u128> for Uint<128, 2> {
+    fn from(arg: u128) -> Self {
+        Uint { limbs: [arg.lo, arg.hi] }
+    }
+}
+
+impl From<Uint<128, 2>> for u128 {
+    fn from(arg: Uint<128, 2>) -> Self {
+        u128::from_parts(arg.limbs[1], arg.limbs[0])
+    }
+}
+
+impl From<u96> for U96 {
+    fn from(arg: u96) -> Self {
+        let value = arg.get();
+        Uint { limbs: [value.lo, value.hi] }
+    }
+}
+
+impl From<U96> for u96 {
+    fn from(arg: U96) -> Self {
+        u96::new_wrapping(u128::from_parts(arg.limbs[1], arg.limbs[0]))
+    }
+}
+
+#[cfg(test)]
+mod conv_tests {
+    use u128::u128;
+    use narrow::u96;
+    use uint::{Uint, U96};
+
+    #[test]
+    fn test_u128() {
+        let a = u128::from_parts(0x35d2c4473082b8c1, 0x8b704240ca1021b8);
+        let b = Uint::<128, 2>::from(a);
+        assert_eq!(b.as_limbs(), &[0x8b704240ca1021b8, 0x35d2c4473082b8c1]);
+        assert_eq!(u128::from(b), a);
+        assert_eq!(u128::from(b * Uint::from(3u64)), a * u128::new(3));
+        assert_eq!(Uint::<8, 1>::from(0x1234u64), Uint::<8, 1>::from_u64(0x34));
+    }
+
+    #[test]
+    fn test_u96() {
+        let a = u96::from_be_bytes([0x80, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let b = U96::from(a);
+        assert_eq!(b.as_limbs(), &[0x0203040506070809, 0x8000_0001]);
+        assert_eq!(u96::from(b), a);
+        assert_eq!(U96::from(u96::max_value()), U96::MAX);
+        assert_eq!(u96::from(U96::MAX), u96::max_value());
+    }
+}
+
+//}}}
+
+//{{{ FromStr, FromStrRadix
+
+impl<const BITS: usize, const LIMBS: usize> Uint<BITS, LIMBS> {
+    /// Converts a string slice in a given base to an integer.
+    ///
+    /// Leading and trailing whitespace represent an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use extprim::uint::U96;
+    ///
+    /// assert_eq!(U96::from_str_radix("ffffffffffffffffffffffff", 16), Ok(U96::MAX));
+    /// assert!(U96::from_str_radix("1000000000000000000000000", 16).is_err());
+    /// ```
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+        assert!((2..=36).contains(&radix),
+                "from_str_radix_int: must lie in the range `[2, 36]` - found {}",
+                radix);
+
+        if src.is_empty() {
+            return Err(error::empty());
+        }
+
+        let mut result = Self::ZERO;
+        for c in src.chars() {
+            let digit = c.to_digit(radix).ok_or_else(error::invalid_digit)?;
+            result = result.checked_mul_add_u64(radix as u64, digit as u64).ok_or_else(error::overflow)?;
+        }
+        Ok(result)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> FromStr for Uint<BITS, LIMBS> {
+    type Err = ParseIntError;
+
+    fn from_str(src: &str) -> Result<Self, ParseIntError> {
+        Self::from_str_radix(src, 10)
+    }
+}
+
+//}}}
+
+//{{{ LowerHex, UpperHex, String, Show
+
+impl<const BITS: usize, const LIMBS: usize> fmt::Display for Uint<BITS, LIMBS> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        const TEN19: u64 = 10000000000000000000;
+
+        let mut chunks = Vec::with_capacity(LIMBS + 1);
+        let mut n = *self;
+        loop {
+            let (q, r) = n.div_rem_u64(TEN19);
+            chunks.push(r);
+            if q.is_zero() {
+                break;
+            }
+            n = q;
+        }
+
+        let mut s = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            s.push_str(&format!("{:019}", chunk));
+        }
+        formatter.pad_integral(true, "", &s)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> fmt::Debug for Uint<BITS, LIMBS> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Uint<{}>({})", BITS, self)
+    }
+}
+
+macro_rules! impl_hex {
+    ($tn:ident, $fmt:expr, $padded:expr) => {
+        impl<const BITS: usize, const LIMBS: usize> fmt::$tn for Uint<BITS, LIMBS> {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                let top = self.limbs.iter().rposition(|&l| l != 0).unwrap_or(0);
+                let mut s = format!($fmt, self.limbs[top]);
+                for limb in self.limbs[.. top].iter().rev() {
+                    s.push_str(&format!($padded, limb));
+                }
+                formatter.pad_integral(true, "0x", &s)
+            }
+        }
+    }
+}
+
+impl_hex!(LowerHex, "{:x}", "{:016x}");
+impl_hex!(UpperHex, "{:X}", "{:016X}");
+
+#[cfg(test)]
+mod show_tests {
+    use uint::{U96, U160, Uint};
+    use error;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", U96::ZERO), "0");
+        assert_eq!(format!("{}", U96::MAX), "79228162514264337593543950335");
+        assert_eq!(format!("{}", U160::MAX), "1461501637330902918203684832716283019655932542975");
+        assert_eq!(format!("{}", Uint::<256, 4>::MAX),
+                    "115792089237316195423570985008687907853269984665640564039457584007913129639935");
+        assert_eq!(format!("{:>5}", Uint::<4, 1>::MAX), "   15");
+        assert_eq!(format!("{:?}", U96::ONE), "Uint<96>(1)");
+        assert_eq!(format!("{:x}", U160::MAX), "ffffffffffffffffffffffffffffffffffffffff");
+        assert_eq!(format!("{:#X}", U96::from_limbs([0xab, 1])), "0x100000000000000AB");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("79228162514264337593543950335".parse::<U96>(), Ok(U96::MAX));
+        assert_eq!("79228162514264337593543950336".parse::<U96>(), Err(error::overflow()));
+        assert_eq!("1461501637330902918203684832716283019655932542975".parse::<U160>(), Ok(U160::MAX));
+        assert_eq!("".parse::<U96>(), Err(error::empty()));
+        assert_eq!("12a".parse::<U96>(), Err(error::invalid_digit()));
+        assert_eq!("15".parse::<Uint<4, 1>>(), Ok(Uint::<4, 1>::MAX));
+        assert_eq!("16".parse::<Uint<4, 1>>(), Err(error::overflow()));
+        assert_eq!("9".parse::<Uint<3, 1>>(), Err(error::overflow()));
+    }
+}
+
+//}}}
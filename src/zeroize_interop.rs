@@ -0,0 +1,79 @@
+//! Support for wiping the integers of this crate from memory with the `zeroize` crate.
+//!
+//! The integers are plain `Copy` data whose default value is zero, so they implement `Zeroize`
+//! through `DefaultIsZeroes`. `Wrapping` and `Checked` implement `Zeroize` if the wrapped type does.
+//! To wipe a secret automatically when it goes out of scope, hold it in a `zeroize::Zeroizing`.
+//! `NonZero` cannot be zeroized, as zero is not a valid value.
+
+use zeroize::{DefaultIsZeroes, Zeroize};
+
+use u128::u128;
+use i128::i128;
+use u256::u256;
+use i256::i256;
+use traits::{Wrapping, Checked};
+
+impl DefaultIsZeroes for u128 {}
+impl DefaultIsZeroes for i128 {}
+impl DefaultIsZeroes for u256 {}
+impl DefaultIsZeroes for i256 {}
+
+impl<T: Zeroize> Zeroize for Wrapping<T> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> Zeroize for Checked<T> {
+    fn zeroize(&mut self) {
+        // wipes the value before setting the option to `None`.
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod zeroize_tests {
+    use zeroize::{Zeroize, Zeroizing};
+
+    use u128::u128;
+    use i128::i128;
+    use u256::u256;
+    use i256::i256;
+    use traits::{Wrapping, Checked};
+
+    #[test]
+    fn test_integers() {
+        let mut a = u128::max_value();
+        a.zeroize();
+        assert_eq!(a, u128::zero());
+
+        let mut b = i128::min_value();
+        b.zeroize();
+        assert_eq!(b, i128::zero());
+
+        let mut c = [u256::max_value(), u256::new(u128::new(5))];
+        c.zeroize();
+        assert_eq!(c, [u256::zero(), u256::zero()]);
+
+        let mut d = i256::min_value();
+        d.zeroize();
+        assert_eq!(d, i256::zero());
+    }
+
+    #[test]
+    fn test_wrappers() {
+        let mut a = Wrapping(u128::max_value());
+        a.zeroize();
+        assert_eq!(a, Wrapping(u128::zero()));
+
+        let mut b = Checked::new(i128::new(-5));
+        b.zeroize();
+        assert_eq!(b, Checked(None));
+    }
+
+    #[test]
+    fn test_zeroizing() {
+        let secret = Zeroizing::new(u128::from_parts(0x0123456789abcdef, 0xfedcba9876543210));
+        assert_eq!(*secret + u128::one(), u128::from_parts(0x0123456789abcdef, 0xfedcba9876543211));
+    }
+}